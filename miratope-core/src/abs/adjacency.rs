@@ -0,0 +1,483 @@
+//! Declares the [`AdjacencyGraph`] type, which records which elements of the
+//! same rank in a polytope are adjacent to each other, along with some of its
+//! invariants.
+
+use std::collections::VecDeque;
+
+use super::{Abstract, Ranked};
+
+use vec_like::VecLike;
+
+/// An undirected graph without loops or multiple edges, whose nodes are the
+/// elements of some rank in a polytope. The nodes are numbered like the
+/// elements they represent.
+///
+/// These graphs are returned by [`Abstract::adjacency`]. The graph of the
+/// facets of a polytope, for instance, can be used to unfold it, or to build
+/// it up one facet at a time.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AdjacencyGraph {
+    /// The sorted indices of the neighbors of each node.
+    neighbors: Vec<Vec<usize>>,
+}
+
+impl AdjacencyGraph {
+    /// Builds a graph with a given number of nodes from a list of edges
+    /// between them. Loops and repeated edges are ignored.
+    ///
+    /// # Panics
+    /// Panics if any edge refers to a node that doesn't exist.
+    pub fn from_edges<I: IntoIterator<Item = (usize, usize)>>(node_count: usize, edges: I) -> Self {
+        let mut neighbors = vec![Vec::new(); node_count];
+
+        for (a, b) in edges {
+            if a != b {
+                neighbors[a].push(b);
+                neighbors[b].push(a);
+            }
+        }
+
+        for list in &mut neighbors {
+            list.sort_unstable();
+            list.dedup();
+        }
+
+        Self { neighbors }
+    }
+
+    /// Returns the number of nodes of the graph.
+    pub fn node_count(&self) -> usize {
+        self.neighbors.len()
+    }
+
+    /// Returns the number of edges of the graph.
+    pub fn edge_count(&self) -> usize {
+        self.neighbors.iter().map(Vec::len).sum::<usize>() / 2
+    }
+
+    /// Returns the sorted indices of the neighbors of a node.
+    pub fn neighbors(&self, node: usize) -> &[usize] {
+        &self.neighbors[node]
+    }
+
+    /// Returns the number of neighbors of a node.
+    pub fn degree(&self, node: usize) -> usize {
+        self.neighbors[node].len()
+    }
+
+    /// Returns whether two nodes are adjacent.
+    pub fn is_adjacent(&self, a: usize, b: usize) -> bool {
+        self.neighbors[a].binary_search(&b).is_ok()
+    }
+
+    /// Searches the graph breadth-first from some node, calling a function
+    /// on every node reached along with its distance to the start, in the
+    /// order they're found.
+    fn search<F: FnMut(usize, usize)>(&self, start: usize, visited: &mut [bool], mut f: F) {
+        let mut queue = VecDeque::new();
+        visited[start] = true;
+        queue.push_back((start, 0));
+
+        while let Some((node, dist)) = queue.pop_front() {
+            f(node, dist);
+
+            for &other in &self.neighbors[node] {
+                if !visited[other] {
+                    visited[other] = true;
+                    queue.push_back((other, dist + 1));
+                }
+            }
+        }
+    }
+
+    /// Returns the nodes in the component of a node, in the order they're
+    /// found by a breadth-first search from it.
+    pub fn bfs(&self, start: usize) -> Vec<usize> {
+        let mut order = Vec::new();
+        let mut visited = vec![false; self.node_count()];
+        self.search(start, &mut visited, |node, _| order.push(node));
+        order
+    }
+
+    /// Returns every node in the order of a breadth-first search, where the
+    /// components are searched one after the other, each of them starting
+    /// from its node with the least index.
+    pub fn bfs_all(&self) -> Vec<usize> {
+        self.components().into_iter().flatten().collect()
+    }
+
+    /// Returns the distance from a node to every other, or `None` for the
+    /// nodes that can't be reached from it.
+    pub fn distances(&self, start: usize) -> Vec<Option<usize>> {
+        let mut distances = vec![None; self.node_count()];
+        let mut visited = vec![false; self.node_count()];
+        self.search(start, &mut visited, |node, dist| {
+            distances[node] = Some(dist)
+        });
+        distances
+    }
+
+    /// Returns the connected components of the graph, each of them in the
+    /// order of a breadth-first search from its node with the least index.
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let mut components = Vec::new();
+        let mut visited = vec![false; self.node_count()];
+
+        for start in 0..self.node_count() {
+            if !visited[start] {
+                let mut component = Vec::new();
+                self.search(start, &mut visited, |node, _| component.push(node));
+                components.push(component);
+            }
+        }
+
+        components
+    }
+
+    /// Returns whether the graph is connected. The empty graph is considered
+    /// connected.
+    pub fn is_connected(&self) -> bool {
+        self.components().len() <= 1
+    }
+
+    /// Returns the degrees of the nodes, from greatest to least.
+    pub fn degree_sequence(&self) -> Vec<usize> {
+        let mut degrees: Vec<_> = self.neighbors.iter().map(Vec::len).collect();
+        degrees.sort_unstable_by(|a, b| b.cmp(a));
+        degrees
+    }
+
+    /// Returns the greatest distance between two nodes of the graph, or
+    /// `None` if it's empty or disconnected.
+    pub fn diameter(&self) -> Option<usize> {
+        let mut diameter = None;
+
+        for start in 0..self.node_count() {
+            for dist in self.distances(start) {
+                diameter = diameter.max(Some(dist?));
+            }
+        }
+
+        diameter
+    }
+
+    /// Returns the length of the shortest cycle in the graph, or `None` if it
+    /// has no cycles.
+    pub fn girth(&self) -> Option<usize> {
+        let mut girth: Option<usize> = None;
+
+        for start in 0..self.node_count() {
+            // The distance and parent of every node in a breadth-first search.
+            let mut tree = vec![None; self.node_count()];
+            let mut queue = VecDeque::new();
+            tree[start] = Some((0, start));
+            queue.push_back(start);
+
+            while let Some(node) = queue.pop_front() {
+                let (dist, parent) = tree[node].unwrap();
+
+                for &other in &self.neighbors[node] {
+                    match tree[other] {
+                        None => {
+                            tree[other] = Some((dist + 1, node));
+                            queue.push_back(other);
+                        }
+
+                        // Any edge outside of the search tree closes a cycle
+                        // through the start, or one shorter than it.
+                        Some((other_dist, _)) if other != parent => {
+                            let len = dist + other_dist + 1;
+                            girth = Some(girth.map_or(len, |girth| girth.min(len)));
+                        }
+
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        girth
+    }
+
+    /// Returns the vertex connectivity of the graph, the least number of
+    /// nodes that need to be removed for it to become disconnected or
+    /// trivial.
+    ///
+    /// By Balinski's theorem, the skeleton of any convex polytope of rank `n`
+    /// is at least `n - 1`-connected (in our rank convention).
+    pub fn vertex_connectivity(&self) -> usize {
+        let node_count = self.node_count();
+        if node_count == 0 {
+            return 0;
+        }
+
+        // Every node is split in two, joined by an arc through which it can
+        // only be crossed once.
+        let mut network = FlowNetwork::new(2 * node_count);
+        for (node, neighbors) in self.neighbors.iter().enumerate() {
+            network.add_arc(2 * node, 2 * node + 1);
+
+            for &other in neighbors {
+                network.add_arc(2 * node + 1, 2 * other);
+            }
+        }
+
+        // Even's algorithm: if the graph is k-connected, some separating set
+        // misses one of the first k + 1 nodes.
+        let mut connectivity = (node_count - 1).min(self.degree_sequence()[node_count - 1]);
+        let mut source = 0;
+        while source <= connectivity && source < node_count {
+            for sink in source + 1..node_count {
+                if !self.is_adjacent(source, sink) {
+                    connectivity = network.max_flow(2 * source + 1, 2 * sink, connectivity);
+                }
+            }
+
+            source += 1;
+        }
+
+        connectivity
+    }
+
+    /// Returns the edge connectivity of the graph, the least number of edges
+    /// that need to be removed for it to become disconnected.
+    pub fn edge_connectivity(&self) -> usize {
+        let node_count = self.node_count();
+        if node_count == 0 {
+            return 0;
+        }
+
+        let mut network = FlowNetwork::new(node_count);
+        for (node, neighbors) in self.neighbors.iter().enumerate() {
+            for &other in neighbors {
+                network.add_arc(node, other);
+            }
+        }
+
+        // Any cut separates the first node from some other.
+        let mut connectivity = self.degree_sequence()[node_count - 1];
+        for sink in 1..node_count {
+            connectivity = network.max_flow(0, sink, connectivity);
+        }
+
+        connectivity
+    }
+}
+
+/// A flow network where every arc has unit capacity, used to count the
+/// disjoint paths between two nodes of a graph.
+struct FlowNetwork {
+    /// The indices of the arcs out of every node. These include the reverses
+    /// of the arcs into it.
+    arcs: Vec<Vec<usize>>,
+
+    /// The node every arc points to. Every arc is followed by its reverse.
+    heads: Vec<usize>,
+
+    /// The capacity of every arc.
+    capacities: Vec<usize>,
+}
+
+impl FlowNetwork {
+    /// Initializes a network with a given number of nodes and no arcs.
+    fn new(node_count: usize) -> Self {
+        Self {
+            arcs: vec![Vec::new(); node_count],
+            heads: Vec::new(),
+            capacities: Vec::new(),
+        }
+    }
+
+    /// Adds an arc with unit capacity between two nodes.
+    fn add_arc(&mut self, from: usize, to: usize) {
+        self.arcs[from].push(self.heads.len());
+        self.heads.push(to);
+        self.capacities.push(1);
+
+        self.arcs[to].push(self.heads.len());
+        self.heads.push(from);
+        self.capacities.push(0);
+    }
+
+    /// Returns the maximum flow between two nodes, or the limit if it's
+    /// greater than it.
+    fn max_flow(&self, source: usize, sink: usize, limit: usize) -> usize {
+        let mut capacities = self.capacities.clone();
+        let mut flow = 0;
+
+        while flow < limit {
+            // The arc through which every node is reached.
+            let mut reached_by = vec![None; self.arcs.len()];
+            let mut queue = VecDeque::new();
+            queue.push_back(source);
+
+            while let Some(node) = queue.pop_front() {
+                for &arc in &self.arcs[node] {
+                    let head = self.heads[arc];
+                    if capacities[arc] != 0 && head != source && reached_by[head].is_none() {
+                        reached_by[head] = Some(arc);
+                        queue.push_back(head);
+                    }
+                }
+            }
+
+            if reached_by[sink].is_none() {
+                break;
+            }
+
+            // Pushes a unit of flow along the path found.
+            let mut node = sink;
+            while let Some(arc) = reached_by[node] {
+                capacities[arc] -= 1;
+                capacities[arc ^ 1] += 1;
+                node = self.heads[arc ^ 1];
+            }
+
+            flow += 1;
+        }
+
+        flow
+    }
+}
+
+impl Abstract {
+    /// Returns the graph of the elements with a given rank, where two of them
+    /// are adjacent whenever they share a subelement. Returns `None` if the
+    /// rank is less than 2, since all vertices share the minimal element, or
+    /// if there's no elements of that rank.
+    pub fn adjacency(&self, rank: usize) -> Option<AdjacencyGraph> {
+        if rank < 2 || rank > self.rank() {
+            return None;
+        }
+
+        // The elements through each subelement.
+        let mut sups = vec![Vec::new(); self.el_count(rank - 1)];
+        for (idx, el) in self[rank].iter().enumerate() {
+            for &sub in &el.subs {
+                sups[sub].push(idx);
+            }
+        }
+
+        let edges = sups.iter().flat_map(|sups| {
+            sups.iter()
+                .enumerate()
+                .flat_map(move |(i, &a)| sups[i + 1..].iter().map(move |&b| (a, b)))
+        });
+
+        Some(AdjacencyGraph::from_edges(self.el_count(rank), edges))
+    }
+
+    /// Returns the graph of the facets of the polytope, where two of them are
+    /// adjacent whenever they share a ridge. Returns `None` if the polytope
+    /// has no ridges.
+    pub fn facet_adjacency(&self) -> Option<AdjacencyGraph> {
+        self.adjacency(self.rank().checked_sub(1)?)
+    }
+
+    /// Returns the 1-skeleton of the polytope, the graph of its vertices and
+    /// edges.
+    pub fn skeleton(&self) -> AdjacencyGraph {
+        let edges = self
+            .ranks()
+            .get(2)
+            .into_iter()
+            .flat_map(|edges| edges.iter())
+            .map(|edge| (edge.subs[0], edge.subs[1]));
+
+        AdjacencyGraph::from_edges(self.el_count(1), edges)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Polytope;
+
+    /// Checks the facet graphs of some polytopes.
+    #[test]
+    fn facet_adjacency() {
+        // The facets of a cube make up an octahedron.
+        let graph = Abstract::cube().facet_adjacency().unwrap();
+        assert_eq!(graph.node_count(), 6);
+        assert_eq!(graph.edge_count(), 12);
+        assert!((0..6).all(|f| graph.degree(f) == 4));
+        assert!(graph.is_connected());
+
+        // The cells of a simplex are all adjacent.
+        let graph = Abstract::simplex(5).facet_adjacency().unwrap();
+        assert_eq!(graph.edge_count(), 10);
+
+        // The edges of a polygon form a cycle.
+        let graph = Abstract::polygon(5).facet_adjacency().unwrap();
+        assert_eq!(graph.edge_count(), 5);
+        assert!(graph.is_adjacent(0, graph.neighbors(0)[1]));
+
+        assert!(Abstract::point().facet_adjacency().is_none());
+        assert!(Abstract::cube().adjacency(1).is_none());
+        assert!(Abstract::cube().adjacency(5).is_none());
+    }
+
+    /// Checks the searches through the graph of a compound.
+    #[test]
+    fn search() {
+        let mut compound = Abstract::cube();
+        compound.comp_append(Abstract::polygon(5).prism());
+        let graph = compound.facet_adjacency().unwrap();
+
+        let components = graph.components();
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0].len(), 6);
+        assert_eq!(components[1].len(), 7);
+        assert!(!graph.is_connected());
+        assert_eq!(graph.bfs_all().len(), 13);
+
+        // The face of a cube opposite to another is two steps away.
+        let distances = graph.distances(0);
+        assert_eq!(distances[0], Some(0));
+        assert_eq!(distances.iter().filter(|&&d| d == Some(1)).count(), 4);
+        assert_eq!(distances.iter().filter(|&&d| d == Some(2)).count(), 1);
+        assert!(distances[6..].iter().all(Option::is_none));
+
+        assert_eq!(graph.bfs(6), components[1]);
+    }
+
+    /// Checks the invariants of the skeletons of some polytopes.
+    #[test]
+    fn skeleton() {
+        // The skeleton of a cube is 3-regular, bipartite, and 3-connected.
+        let graph = Abstract::cube().skeleton();
+        assert_eq!(graph.degree_sequence(), vec![3; 8]);
+        assert_eq!(graph.diameter(), Some(3));
+        assert_eq!(graph.girth(), Some(4));
+        assert_eq!(graph.vertex_connectivity(), 3);
+        assert_eq!(graph.edge_connectivity(), 3);
+
+        // The skeleton of a simplex is complete.
+        let graph = Abstract::simplex(5).skeleton();
+        assert_eq!(graph.diameter(), Some(1));
+        assert_eq!(graph.girth(), Some(3));
+        assert_eq!(graph.vertex_connectivity(), 4);
+
+        // The skeleton of a polygon is a cycle.
+        let graph = Abstract::polygon(7).skeleton();
+        assert_eq!(graph.diameter(), Some(3));
+        assert_eq!(graph.girth(), Some(7));
+        assert_eq!(graph.vertex_connectivity(), 2);
+        assert_eq!(graph.edge_connectivity(), 2);
+
+        // A dyad has no cycles, and a point has no edges.
+        let graph = Abstract::dyad().skeleton();
+        assert_eq!(graph.girth(), None);
+        assert_eq!(graph.vertex_connectivity(), 1);
+        assert_eq!(Abstract::point().skeleton().edge_count(), 0);
+        assert_eq!(Abstract::nullitope().skeleton().diameter(), None);
+
+        // A compound is disconnected.
+        let mut compound = Abstract::cube();
+        compound.comp_append(Abstract::cube());
+        let graph = compound.skeleton();
+        assert_eq!(graph.diameter(), None);
+        assert_eq!(graph.vertex_connectivity(), 0);
+        assert_eq!(graph.edge_connectivity(), 0);
+    }
+}
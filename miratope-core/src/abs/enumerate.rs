@@ -0,0 +1,271 @@
+//! Enumerates the abstract polytopes of a given rank with a small number of
+//! flags.
+//!
+//! Every abstract polytope is determined up to isomorphism by its flag graph,
+//! the graph whose vertices are its flags and where two flags are joined by an
+//! edge of color `i` whenever they differ only in their element of rank `i`
+//! (using the usual mathematical convention for ranks). We build every
+//! connected graph with edges of `rank - 1` colors such that each vertex has
+//! exactly one edge of each color, and such that edges whose colors aren't
+//! consecutive form squares. We then keep those that come from a polytope.
+//!
+//! Isomorphic graphs are rejected by only keeping those whose labeling is the
+//! breadth-first labeling that's lexicographically smallest over all starting
+//! flags.
+
+use super::Abstract;
+use crate::Polytope;
+
+/// Marks an edge that hasn't been set yet.
+const UNSET: usize = usize::MAX;
+
+/// A partially built flag graph. Flags are labeled in the order in which a
+/// breadth-first search starting from flag `0` finds them, visiting the
+/// neighbors of each flag in order of color.
+struct FlagGraph {
+    /// The number of colors, i.e. the rank of the polytope minus 1.
+    colors: usize,
+
+    /// The maximum number of flags.
+    max_flags: usize,
+
+    /// The neighbor of every flag through an edge of every color, or
+    /// [`UNSET`] if it hasn't been set yet.
+    adj: Vec<Vec<usize>>,
+}
+
+impl FlagGraph {
+    /// Initializes a graph with a single flag and no edges.
+    fn new(colors: usize, max_flags: usize) -> Self {
+        Self {
+            colors,
+            max_flags,
+            adj: vec![vec![UNSET; colors]],
+        }
+    }
+
+    /// The number of flags that have been added.
+    fn len(&self) -> usize {
+        self.adj.len()
+    }
+
+    /// Adds an edge of a given color between two flags.
+    fn link(&mut self, f: usize, g: usize, color: usize) {
+        self.adj[f][color] = g;
+        self.adj[g][color] = f;
+    }
+
+    /// Removes an edge of a given color between two flags.
+    fn unlink(&mut self, f: usize, g: usize, color: usize) {
+        self.adj[f][color] = UNSET;
+        self.adj[g][color] = UNSET;
+    }
+
+    /// Checks whether a new edge of a given color from `f` to `g` would give
+    /// two edges with the same endpoints, or a path of non-consecutive colors
+    /// `i`, `j`, `i`, `j` that doesn't close into a square.
+    fn can_link(&self, f: usize, g: usize, i: usize) -> bool {
+        if self.adj[f].contains(&g) {
+            return false;
+        }
+
+        for j in 0..self.colors {
+            if i.max(j) - i.min(j) < 2 {
+                continue;
+            }
+
+            for &(x, y) in &[(f, g), (g, f)] {
+                let a = self.adj[x][j];
+                let b = self.adj[y][j];
+
+                if a != UNSET && b != UNSET {
+                    let c = self.adj[a][i];
+                    if c != UNSET && c != b {
+                        return false;
+                    }
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Sets every remaining edge in every possible way, starting from the
+    /// given color of the given flag, and passes every complete graph into
+    /// `found`.
+    fn search(&mut self, mut flag: usize, mut color: usize, found: &mut impl FnMut(&Self)) {
+        // Finds the next edge that hasn't been set.
+        loop {
+            if flag == self.len() {
+                found(self);
+                return;
+            } else if color == self.colors {
+                flag += 1;
+                color = 0;
+            } else if self.adj[flag][color] != UNSET {
+                color += 1;
+            } else {
+                break;
+            }
+        }
+
+        // Links the flag to a flag that's been found already.
+        for g in (flag + 1)..self.len() {
+            if self.adj[g][color] == UNSET && self.can_link(flag, g, color) {
+                self.link(flag, g, color);
+                self.search(flag, color + 1, found);
+                self.unlink(flag, g, color);
+            }
+        }
+
+        // Links the flag to a new flag.
+        if self.len() < self.max_flags {
+            let g = self.len();
+            self.adj.push(vec![UNSET; self.colors]);
+            self.link(flag, g, color);
+            self.search(flag, color + 1, found);
+            self.adj.pop();
+            self.adj[flag][color] = UNSET;
+        }
+    }
+
+    /// Checks whether the breadth-first labeling of the graph starting at flag
+    /// `0` is the lexicographically smallest over all starting flags.
+    fn is_canonical(&self) -> bool {
+        let len = self.len();
+        let mut label = vec![UNSET; len];
+        let mut order = Vec::with_capacity(len);
+
+        'start: for start in 1..len {
+            label.iter_mut().for_each(|l| *l = UNSET);
+            order.clear();
+            label[start] = 0;
+            order.push(start);
+
+            let mut idx = 0;
+            while idx < len {
+                let f = order[idx];
+
+                for color in 0..self.colors {
+                    let g = self.adj[f][color];
+                    if label[g] == UNSET {
+                        label[g] = order.len();
+                        order.push(g);
+                    }
+
+                    let cmp = label[g].cmp(&self.adj[idx][color]);
+                    if cmp.is_lt() {
+                        return false;
+                    } else if cmp.is_gt() {
+                        continue 'start;
+                    }
+                }
+
+                idx += 1;
+            }
+        }
+
+        true
+    }
+}
+
+/// Returns every abstract polytope of a given rank with at most a given number
+/// of flags, up to isomorphism. The polytopes are sorted by their number of
+/// flags.
+///
+/// The number of flag graphs that need to be searched grows very quickly, so
+/// this is only practical for small flag counts.
+pub fn polytopes(rank: usize, max_flags: usize) -> Vec<Abstract> {
+    if max_flags == 0 {
+        return Vec::new();
+    }
+
+    match rank {
+        0 => return vec![Abstract::nullitope()],
+        1 => return vec![Abstract::point()],
+        _ => {}
+    }
+
+    let mut polytopes = Vec::new();
+    let mut graph = FlagGraph::new(rank - 1, max_flags);
+    graph.search(0, 0, &mut |graph| {
+        if graph.is_canonical() {
+            if let Some(poly) = Abstract::from_flag_graph(&graph.adj) {
+                polytopes.push((graph.len(), poly));
+            }
+        }
+    });
+
+    polytopes.sort_by_key(|(len, _)| *len);
+    polytopes.into_iter().map(|(_, poly)| poly).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abs::Ranked;
+
+    /// Returns the element counts of every polytope.
+    fn el_counts(polytopes: &[Abstract]) -> Vec<Vec<usize>> {
+        polytopes
+            .iter()
+            .map(|p| p.el_count_iter().collect())
+            .collect()
+    }
+
+    /// Checks that we find exactly one polygon for every even flag count.
+    #[test]
+    fn polygons() {
+        let polygons = polytopes(3, 12);
+        assert_eq!(
+            el_counts(&polygons),
+            (2..=6).map(|n| vec![1, n, n, 1]).collect::<Vec<_>>()
+        );
+    }
+
+    /// Checks the polyhedra with at most 12 flags.
+    #[test]
+    fn polyhedra() {
+        let polyhedra = polytopes(4, 12);
+        let mut counts = el_counts(&polyhedra);
+        counts.sort();
+
+        assert_eq!(
+            counts,
+            vec![
+                vec![1, 2, 2, 2, 1],
+                vec![1, 2, 3, 3, 1],
+                vec![1, 3, 3, 2, 1],
+            ]
+        );
+    }
+
+    /// Checks that the square dihedron is found, and exported correctly.
+    #[test]
+    fn square_dihedron() {
+        let dihedron = polytopes(4, 16)
+            .into_iter()
+            .find(|p| p.el_count_iter().eq([1, 4, 4, 2, 1].iter().copied()))
+            .unwrap();
+
+        let matrices = dihedron.incidence_matrices();
+        assert_eq!(matrices.len(), 2);
+        for row in &matrices[0] {
+            assert_eq!(row.iter().filter(|&&b| b).count(), 2);
+        }
+        for row in &matrices[1] {
+            assert_eq!(row.iter().filter(|&&b| b).count(), 2);
+        }
+
+        assert!(dihedron.incidence_matrices_string().starts_with("4 4\n"));
+    }
+
+    /// Checks that the small polytopes are found.
+    #[test]
+    fn small() {
+        assert_eq!(polytopes(0, 1).len(), 1);
+        assert_eq!(polytopes(1, 1).len(), 1);
+        assert_eq!(polytopes(2, 2).len(), 1);
+        assert_eq!(polytopes(3, 3).len(), 0);
+    }
+}
@@ -3,14 +3,14 @@
 
 use std::{
     cmp::Ordering,
-    collections::{hash_map::Entry, HashMap, HashSet, VecDeque},
+    collections::{hash_map::Entry, BTreeSet, HashMap, HashSet, VecDeque},
     hash::{Hash, Hasher},
     iter,
     ops::Range,
 };
 
 use crate::{
-    abs::{ranked::Ranked, Abstract},
+    abs::{ranked::Ranked, Abstract, AbstractBuilder, SubelementList},
     Polytope,
 };
 
@@ -439,6 +439,16 @@ impl<'a> OrientedFlagIter<'a> {
         }
     }
 
+    /// Returns an upper bound for the number of flag events this iterator will
+    /// return. This is the number of flags of the polytope, plus one for the
+    /// possible non-orientable event.
+    ///
+    /// The bound is attained whenever the polytope is connected and all flag
+    /// changes are applied, save for the orientability event.
+    pub fn count_hint(&self) -> usize {
+        self.polytope.flag_count() + 1
+    }
+
     /// Returns a new iterator over oriented flags, discarding the
     /// non-orientable event.
     pub fn filter_flags(self) -> impl Iterator<Item = OrientedFlag> + 'a {
@@ -668,6 +678,365 @@ impl FlagSet {
     }
 }
 
+/// Returns the index of the component of every flag of a flag graph in the
+/// subgraph with the edges of the given colors, along with the number of
+/// components.
+fn components(
+    adj: &[Vec<usize>],
+    colors: impl Iterator<Item = usize> + Clone,
+) -> (Vec<usize>, usize) {
+    let mut component = vec![usize::MAX; adj.len()];
+    let mut count = 0;
+
+    for start in 0..adj.len() {
+        if component[start] != usize::MAX {
+            continue;
+        }
+
+        component[start] = count;
+        let mut stack = vec![start];
+        while let Some(f) = stack.pop() {
+            for color in colors.clone() {
+                let g = adj[f][color];
+                if component[g] == usize::MAX {
+                    component[g] = count;
+                    stack.push(g);
+                }
+            }
+        }
+
+        count += 1;
+    }
+
+    (component, count)
+}
+
+/// The [flag graph](https://polytope.miraheze.org/wiki/Flag) of a polytope,
+/// whose vertices are its flags, and where two flags are joined by an edge
+/// whenever they differ in a single element.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FlagGraph {
+    /// The flags of the polytope.
+    pub flags: Vec<Flag>,
+
+    /// The flag that differs from every flag only in its element of every
+    /// rank. Namely, `adj[f][i]` is the index of the flag that differs from
+    /// `flags[f]` only in its element with rank `i + 1`.
+    pub adj: Vec<Vec<usize>>,
+}
+
+impl FlagGraph {
+    /// Returns the number of flags in the graph.
+    pub fn len(&self) -> usize {
+        self.flags.len()
+    }
+
+    /// Returns whether the graph has no flags, which never happens for the
+    /// flag graph of a polytope.
+    pub fn is_empty(&self) -> bool {
+        self.flags.is_empty()
+    }
+
+    /// Returns the number of colors of the edges, i.e. the number of distinct
+    /// flag changes.
+    pub fn colors(&self) -> usize {
+        self.adj.first().map_or(0, Vec::len)
+    }
+
+    /// Builds the omnitruncate of the polytope with this flag graph. Its
+    /// vertices are the flags, in the same order, and its elements of rank
+    /// `k + 1` are the components of the subgraphs that only use the edges of
+    /// some `k` colors. Every element is contained in those it's a component
+    /// of a subgraph of.
+    ///
+    /// The components of each subgraph are found in a single pass over the
+    /// flags, so this takes linear time in the number of flags for any fixed
+    /// rank.
+    pub fn omnitruncate(&self) -> Abstract {
+        self.omnitruncate_with(|_, _| true)
+            .expect("the omnitruncate was never stopped")
+    }
+
+    /// Builds the omnitruncate of the polytope with this flag graph, as in
+    /// [`Self::omnitruncate`].
+    ///
+    /// Before each subgraph is split into components, `progress` is called
+    /// with the number of subgraphs done and their total number. The
+    /// construction stops and returns `None` if it returns `false`.
+    pub fn omnitruncate_with<F: FnMut(usize, usize) -> bool>(
+        &self,
+        mut progress: F,
+    ) -> Option<Abstract> {
+        let len = self.len();
+        let colors = self.colors();
+
+        // Every set of colors but the empty and the full one gives a subgraph.
+        let total = (1usize << colors).saturating_sub(2);
+        let mut done = 0;
+
+        let mut builder = AbstractBuilder::with_rank_capacity(colors + 1);
+        builder.push_min();
+        builder.push_vertices(len);
+
+        // The components of the subgraphs that use a given set of colors, and
+        // the index of the first of these components among the elements of
+        // their rank. Every flag is its own component when no colors are used.
+        let mut prev_layer = HashMap::new();
+        prev_layer.insert(0usize, (0, (0..len).collect::<Vec<_>>()));
+
+        // The maximal element is added separately, so that the omnitruncate of
+        // a compound is a compound of omnitruncates.
+        for k in 1..colors {
+            let mut layer = HashMap::new();
+            let mut list = SubelementList::new();
+
+            for set in (0..1usize << colors).filter(|set| set.count_ones() as usize == k) {
+                if !progress(done, total) {
+                    return None;
+                }
+                done += 1;
+
+                let in_set = (0..colors).filter(|&i| set & (1 << i) != 0);
+                let (component, count) = components(&self.adj, in_set.clone());
+                let mut subs = vec![BTreeSet::new(); count];
+
+                // The subelements are the components of the subgraphs that use
+                // one color less.
+                for i in in_set {
+                    let (offset, sub_component) = &prev_layer[&(set & !(1 << i))];
+                    for f in 0..len {
+                        subs[component[f]].insert(offset + sub_component[f]);
+                    }
+                }
+
+                layer.insert(set, (list.len(), component));
+                for el_subs in subs {
+                    list.push(el_subs.into_iter().collect::<Vec<_>>().into());
+                }
+            }
+
+            builder.push(list);
+            prev_layer = layer;
+        }
+
+        if colors > 0 {
+            builder.push_max();
+        }
+
+        // Safety: the omnitruncate of a polytope is a polytope. Its sections
+        // are omnitruncates of sections of the original polytope, and products
+        // of these.
+        Some(unsafe { builder.build() })
+    }
+}
+
+impl Abstract {
+    /// Returns the flag graph of the polytope. The polytope can be rebuilt
+    /// from its adjacencies through [`Self::from_flag_graph`].
+    pub fn flag_graph(&self) -> FlagGraph {
+        let sorted;
+        let poly = if self.sorted() {
+            self
+        } else {
+            sorted = self.sorted_clone();
+            &sorted
+        };
+
+        let flags: Vec<_> = poly.flags().collect();
+        let indices: HashMap<_, _> = flags
+            .iter()
+            .enumerate()
+            .map(|(idx, flag)| (flag.clone(), idx))
+            .collect();
+
+        let colors = poly.rank().saturating_sub(1);
+        let adj = flags
+            .iter()
+            .map(|flag| {
+                (1..=colors)
+                    .map(|r| indices[&flag.change(poly, r)])
+                    .collect()
+            })
+            .collect();
+
+        FlagGraph { flags, adj }
+    }
+
+    /// Returns the connected component of the flag graph that contains the
+    /// first flag of the polytope. The flags are found through a breadth-first
+    /// search, so those of other components are never built.
+    pub fn first_flag_graph(&self) -> FlagGraph {
+        self.first_flag_graph_with(|_, _| true)
+            .expect("the search was never stopped")
+    }
+
+    /// Returns the connected component of the flag graph that contains the
+    /// first flag of the polytope, as in [`Self::first_flag_graph`].
+    ///
+    /// Every so often, `progress` is called with the number of flags found and
+    /// the total number of flags of the polytope. The search stops and returns
+    /// `None` if it returns `false`.
+    pub fn first_flag_graph_with<F: FnMut(usize, usize) -> bool>(
+        &self,
+        mut progress: F,
+    ) -> Option<FlagGraph> {
+        /// How many flags are visited between calls to `progress`.
+        const STEP: usize = 1 << 10;
+
+        let sorted;
+        let poly = if self.sorted() {
+            self
+        } else {
+            sorted = self.sorted_clone();
+            &sorted
+        };
+
+        let total = poly.flag_count();
+        let colors = poly.rank().saturating_sub(1);
+
+        let first = poly.first_flag();
+        let mut indices = HashMap::new();
+        indices.insert(first.clone(), 0);
+        let mut flags = vec![first];
+        let mut adj = Vec::new();
+
+        // The flags are numbered in the order they're found, and we visit
+        // them in that same order.
+        while let Some(flag) = flags.get(adj.len()).cloned() {
+            if adj.len() % STEP == 0 && !progress(adj.len(), total) {
+                return None;
+            }
+
+            let mut neighbors = Vec::with_capacity(colors);
+            for r in 1..=colors {
+                let new_flag = flag.change(poly, r);
+                let idx = match indices.entry(new_flag) {
+                    Entry::Occupied(entry) => *entry.get(),
+                    Entry::Vacant(entry) => {
+                        flags.push(entry.key().clone());
+                        *entry.insert(flags.len() - 1)
+                    }
+                };
+
+                neighbors.push(idx);
+            }
+
+            adj.push(neighbors);
+        }
+
+        Some(FlagGraph { flags, adj })
+    }
+
+    /// Returns a copy of the polytope with its elements sorted.
+    fn sorted_clone(&self) -> Self {
+        let mut clone = self.clone();
+        clone.element_sort();
+        clone
+    }
+
+    /// Builds the polytope with a given flag graph, or returns `None` if
+    /// there's no such polytope. The flag graph is given as a list with an
+    /// entry for every flag, and `adj[f][i]` is the flag that differs from `f`
+    /// only in its element with rank `i + 1`.
+    pub fn from_flag_graph(adj: &[Vec<usize>]) -> Option<Self> {
+        let len = adj.len();
+        let colors = adj.first()?.len();
+
+        if colors == 0 {
+            return (len == 1).then(Self::point);
+        }
+
+        // Every color must give an involution without fixed points, and
+        // different colors must give different neighbors.
+        for (f, neighbors) in adj.iter().enumerate() {
+            if neighbors.len() != colors {
+                return None;
+            }
+
+            for (i, &g) in neighbors.iter().enumerate() {
+                if g >= len || g == f || adj[g].get(i) != Some(&f) || neighbors[..i].contains(&g) {
+                    return None;
+                }
+            }
+        }
+
+        // The elements of rank i + 1 are the components of the subgraph that
+        // doesn't use the edges of color i. The minimal and maximal elements
+        // are added for convenience.
+        let mut faces = vec![(vec![0; len], 1)];
+        for i in 0..colors {
+            faces.push(components(adj, (0..colors).filter(|&j| j != i)));
+        }
+        faces.push((vec![0; len], 1));
+
+        // The flag graph must be connected.
+        if components(adj, 0..colors).1 != 1 {
+            return None;
+        }
+
+        // Different flags must have different elements.
+        let mut chains = HashSet::new();
+        for f in 0..len {
+            if !chains.insert(faces.iter().map(|(c, _)| c[f]).collect::<Vec<_>>()) {
+                return None;
+            }
+        }
+
+        // The flags that only differ in their elements of ranks strictly
+        // between two others must be connected by edges of these ranks. This
+        // implies the diamond property and strong connectivity.
+        for lo in 0..faces.len() {
+            for hi in (lo + 2)..faces.len() {
+                let (component, _) = components(adj, lo..(hi - 1));
+                let mut sections = HashMap::new();
+
+                for f in 0..len {
+                    let outside: Vec<_> = faces[..=lo]
+                        .iter()
+                        .chain(&faces[hi..])
+                        .map(|(c, _)| c[f])
+                        .collect();
+
+                    if *sections.entry(outside).or_insert(component[f]) != component[f] {
+                        return None;
+                    }
+                }
+            }
+        }
+
+        let mut builder = AbstractBuilder::with_rank_capacity(colors + 1);
+        builder.push_min();
+        builder.push_vertices(faces[1].1);
+
+        // Builds the subelements of every element.
+        for r in 2..=colors {
+            let (component, count) = &faces[r];
+            let mut subs = vec![BTreeSet::new(); *count];
+            for f in 0..len {
+                subs[component[f]].insert(faces[r - 1].0[f]);
+            }
+
+            let mut list = SubelementList::with_capacity(*count);
+            for el_subs in subs {
+                list.push(el_subs.into_iter().collect::<Vec<_>>().into());
+            }
+            builder.push(list);
+        }
+        builder.push_max();
+
+        // Safety: we've checked that the poset has the diamond property and
+        // is strongly connected.
+        let poly = unsafe { builder.build() };
+
+        // Every chain of elements must come from a flag.
+        if poly.flag_count() == len {
+            Some(poly)
+        } else {
+            None
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -678,6 +1047,14 @@ mod tests {
         polytope.element_sort();
 
         assert_eq!(expected, polytope.flags().count(), "flag count mismatch");
+        assert_eq!(expected, polytope.flag_count(), "counted flags mismatch");
+
+        // The polytope is connected, so the hint is attained up to the
+        // orientability event.
+        let events = polytope.flag_events().count();
+        let hint = polytope.flag_events().count_hint();
+        assert!(events <= hint, "flag event count hint too low");
+        assert!(events + 1 >= hint, "flag event count hint not attained");
 
         assert_eq!(
             expected,
@@ -987,4 +1364,42 @@ mod tests {
         test_flags(&mut snic, 240);
         test_flags(&mut ti, 360);
     }
+
+    /// Checks that the flag event count hint bounds the events of a compound,
+    /// where only the flags of one component are returned.
+    #[test]
+    fn compound_count_hint() {
+        let mut compound = Abstract::cube();
+        compound.comp_append(Abstract::polygon(5).prism());
+        compound.element_sort();
+
+        let events = compound.flag_events().count();
+        assert_eq!(events, 48);
+        assert!(events <= compound.flag_events().count_hint());
+        assert_eq!(compound.flag_events().count_hint(), 48 + 60 + 1);
+    }
+
+    /// Checks that polytopes can be rebuilt from their flag graphs.
+    #[test]
+    fn flag_graph() {
+        for poly in [
+            Abstract::point(),
+            Abstract::polygon(5),
+            Abstract::cube(),
+            Abstract::polygon(3).duoprism(&Abstract::polygon(4)),
+        ] {
+            let graph = poly.flag_graph();
+            assert_eq!(graph.len(), poly.flag_count());
+            assert_eq!(graph.colors(), poly.rank() - 1);
+
+            for (f, neighbors) in graph.adj.iter().enumerate() {
+                for (i, &g) in neighbors.iter().enumerate() {
+                    assert_eq!(graph.adj[g][i], f);
+                }
+            }
+
+            let rebuilt = Abstract::from_flag_graph(&graph.adj).unwrap();
+            assert!(rebuilt.el_count_iter().eq(poly.el_count_iter()));
+        }
+    }
 }
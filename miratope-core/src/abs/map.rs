@@ -0,0 +1,172 @@
+//! Declares the [`PolytopeMap`] type, which records how the elements of a
+//! polytope built by some construction correspond to those of the polytope it
+//! was built from.
+
+use std::ops::Index;
+
+use super::{Abstract, ElementMap, Ranked};
+
+use vec_like::VecLike;
+
+/// Records, for every element of a polytope, the element of another polytope
+/// that it comes from. Each element is given as a pair of its rank and its
+/// index, so that maps can change ranks, as the dual does.
+///
+/// These maps are returned by the constructions on abstract polytopes, so that
+/// one can trace back where an element of the result came from. For instance,
+/// an element of a duoprism comes from a pair of elements of its factors, and
+/// it's mapped to each of these by one of the two maps returned by
+/// [`Abstract::duoprism_and_maps`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PolytopeMap(ElementMap<(usize, usize)>);
+
+impl From<ElementMap<(usize, usize)>> for PolytopeMap {
+    fn from(map: ElementMap<(usize, usize)>) -> Self {
+        Self(map)
+    }
+}
+
+impl From<Vec<Vec<(usize, usize)>>> for PolytopeMap {
+    fn from(map: Vec<Vec<(usize, usize)>>) -> Self {
+        Self(map.into())
+    }
+}
+
+impl Index<(usize, usize)> for PolytopeMap {
+    type Output = (usize, usize);
+
+    /// Gets the rank and index of the element that the element with a given
+    /// rank and index is sent to.
+    fn index(&self, index: (usize, usize)) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl PolytopeMap {
+    /// Returns the map that sends every element of a polytope to itself.
+    pub fn identity(abs: &Abstract) -> Self {
+        abs.el_count_iter()
+            .enumerate()
+            .map(|(r, count)| (0..count).map(|idx| (r, idx)).collect())
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    /// Returns the underlying element map.
+    pub fn element_map(&self) -> &ElementMap<(usize, usize)> {
+        &self.0
+    }
+
+    /// Returns the rank of the polytope the map is defined on.
+    pub fn rank(&self) -> usize {
+        self.0.len() - 1
+    }
+
+    /// Returns the rank and index of the element that the element with a
+    /// given rank and index is sent to, if the latter exists.
+    pub fn get(&self, rank: usize, idx: usize) -> Option<(usize, usize)> {
+        self.0.get(rank)?.get(idx).copied()
+    }
+
+    /// Returns all of the elements that are sent to the element with a given
+    /// rank and index, in order of rank and index.
+    pub fn preimage(&self, rank: usize, idx: usize) -> Vec<(usize, usize)> {
+        let mut preimage = Vec::new();
+
+        for (r, elements) in self.0.iter().enumerate() {
+            for (i, &el) in elements.iter().enumerate() {
+                if el == (rank, idx) {
+                    preimage.push((r, i));
+                }
+            }
+        }
+
+        preimage
+    }
+
+    /// Composes two maps. If `self` sends the elements of a polytope `P` to
+    /// those of `Q`, and `other` sends the elements of `Q` to those of `R`, the
+    /// result sends the elements of `P` to those of `R`.
+    ///
+    /// Returns `None` if `self` sends some element outside of the domain of
+    /// `other`.
+    pub fn then(&self, other: &Self) -> Option<Self> {
+        self.0
+            .iter()
+            .map(|elements| {
+                elements
+                    .iter()
+                    .map(|&(rank, idx)| other.get(rank, idx))
+                    .collect::<Option<Vec<_>>>()
+            })
+            .collect::<Option<Vec<_>>>()
+            .map(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Polytope;
+
+    /// Checks that the dual of the dual gets back every element.
+    #[test]
+    fn dual_map() {
+        let prism = Abstract::polygon(5).prism();
+        let (dual, map) = prism.dual_and_map();
+        let (_, dual_map) = dual.dual_and_map();
+
+        assert_eq!(map.get(1, 3), Some((3, 3)));
+        assert_eq!(map.preimage(4, 0), vec![(0, 0)]);
+        assert_eq!(dual_map.then(&map), Some(PolytopeMap::identity(&prism)));
+    }
+
+    /// Checks that the maps from a duoprism trace its elements back to its
+    /// factors.
+    #[test]
+    fn duoprism_maps() {
+        let p = Abstract::polygon(3);
+        let q = Abstract::polygon(4);
+        let (duoprism, p_map, q_map) = p.duoprism_and_maps(&q);
+
+        // Every proper element comes from a pair of proper elements.
+        for (r, elements) in duoprism.iter().enumerate().skip(1) {
+            for idx in 0..elements.len() {
+                let (p_rank, _) = p_map[(r, idx)];
+                let (q_rank, _) = q_map[(r, idx)];
+                assert_eq!(p_rank + q_rank, r + 1);
+            }
+        }
+
+        // The square prisms come from the edges of the triangle.
+        let facets = p_map.rank() - 1;
+        for edge in 0..3 {
+            let preimage: Vec<_> = p_map
+                .preimage(2, edge)
+                .into_iter()
+                .filter(|&(r, _)| r == facets)
+                .collect();
+            assert_eq!(preimage.len(), 1);
+            assert_eq!(duoprism[preimage[0]].subs.len(), 6);
+        }
+    }
+
+    /// Checks that the maps from a duopyramid are given in the order of the
+    /// factors.
+    #[test]
+    fn duopyramid_maps() {
+        let p = Abstract::dyad();
+        let q = Abstract::polygon(5);
+        let (duopyramid, p_map, q_map) = p.duopyramid_and_maps(&q);
+
+        for (r, elements) in duopyramid.iter().enumerate() {
+            for idx in 0..elements.len() {
+                let (p_rank, p_idx) = p_map[(r, idx)];
+                let (q_rank, q_idx) = q_map[(r, idx)];
+                assert_eq!(p_rank + q_rank, r);
+                assert!(p_idx < p.el_count(p_rank));
+                assert!(q_idx < q.el_count(q_rank));
+            }
+        }
+    }
+}
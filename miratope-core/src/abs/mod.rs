@@ -1,25 +1,32 @@
 //! Declares the [`Abstract`] polytope type and all associated data structures.
 
+pub mod adjacency;
 pub mod antiprism;
+pub mod enumerate;
 pub mod flag;
+pub mod map;
 pub mod product;
 pub mod ranked;
+pub mod regular;
 pub mod valid;
 
 use std::{
-    collections::{BTreeSet, HashMap},
+    borrow::Cow,
+    collections::{BTreeSet, HashMap, HashSet},
     convert::Infallible,
     ops::{Index, IndexMut},
     slice, vec, iter,
 };
 
-use self::flag::{Flag, FlagSet};
-use super::Polytope;
+use self::flag::{Flag, FlagChanges, FlagEvent, FlagIter, OrientedFlagIter};
+use super::{Polytope, SectionError};
 
 use vec_like::VecLike;
 
 use partitions::PartitionVec;
 
+pub use adjacency::AdjacencyGraph;
+pub use map::PolytopeMap;
 pub use ranked::*;
 pub use valid::*;
 
@@ -28,18 +35,25 @@ pub use valid::*;
 ///
 /// This struct is not stable, and its fields are subject to change as we see
 /// fit.
-#[derive(Clone, Copy, Debug)]
+#[derive(Debug, Default)]
 #[non_exhaustive]
 pub struct Metadata {
-    /// Whether every single element's subelements and superelements are sorted
-    /// by index. This is a necessary condition for the methods that iterate
-    /// over flags.
-    sorted: bool,
+    /// The ranks of the dual polytope, if they've been computed since the last
+    /// time the polytope was modified. This makes it so that taking the dual
+    /// of a dual amounts to swapping two pointers.
+    dual: Option<Box<Ranks>>,
+
+    /// The faces of the polyhedron as rewritten by [`Abstract::orient`], if
+    /// they've been computed since the last time the polytope was modified.
+    /// Holds `None` if the polyhedron is non-orientable.
+    oriented_faces: Option<Option<Box<ElementList>>>,
 }
 
-impl Default for Metadata {
-    fn default() -> Self {
-        Self { sorted: false }
+/// Cloning a polytope doesn't clone its caches, so that copies don't pay
+/// twice for memory they'll likely never use.
+impl Clone for Metadata {
+    fn clone(&self) -> Self {
+        Default::default()
     }
 }
 
@@ -142,6 +156,7 @@ impl Index<usize> for Abstract {
 
 impl IndexMut<usize> for Abstract {
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.clear_cache();
         &mut self.ranks[index]
     }
 }
@@ -192,9 +207,10 @@ impl Abstract {
     }
 
     /// Returns whether the indices of all the subelements and superelements are
-    /// sorted. Gets this from the polytope's metadata.
+    /// sorted. This is a necessary condition for the methods that iterate over
+    /// flags. Every rank keeps track of this separately.
     pub fn sorted(&self) -> bool {
-        self.meta.sorted
+        self.ranks.sorted()
     }
 
     /// Sets the metadata of the polytope that stores whether the indices of the
@@ -204,7 +220,19 @@ impl Abstract {
     /// Setting this flag incorrectly will cause algorithms to behave
     /// unpredictably, potentially causing UB.
     pub unsafe fn set_sorted(&mut self, sorted: bool) {
-        self.meta.sorted = sorted;
+        self.ranks.set_sorted(sorted);
+    }
+
+    /// Returns whether the dual of the polytope is currently cached.
+    pub fn dual_cached(&self) -> bool {
+        self.meta.dual.is_some()
+    }
+
+    /// Forgets the cached dual and orientation of the polytope, if any. This
+    /// must be called whenever the polytope is modified without going through
+    /// [`Self::ranks_mut`].
+    fn clear_cache(&mut self) {
+        self.meta.reset();
     }
 
     /// Returns an iterator over the [`ElementLists`](ElementList) of each rank.
@@ -213,24 +241,48 @@ impl Abstract {
     }
 
     /// Takes the dual of an abstract polytope in place. This can never fail.
+    ///
+    /// The original polytope is cached, so that taking the dual again just
+    /// swaps it back in.
     pub fn dual_mut(&mut self) {
-        // Safety: duals of polytopes are polytopes.
-        let sorted = self.sorted();
-        let ranks = unsafe { self.ranks_mut() };
-        ranks.for_each_element_mut(Element::swap_mut);
-        ranks.reverse();
+        let dual = match self.meta.dual.take() {
+            Some(dual) => *dual,
+            None => self.dual_ranks(),
+        };
+
+        let ranks = std::mem::replace(&mut self.ranks, dual);
+        self.meta.dual = Some(Box::new(ranks));
+        self.meta.oriented_faces = None;
+    }
 
-        // Safety: if the original elements were sorted, so will these be.
-        unsafe {
-            self.set_sorted(sorted);
+    /// Computes the ranks of the dual polytope, ignoring the cache.
+    fn dual_ranks(&self) -> Ranks {
+        let mut dual = self.ranks.clone();
+        dual.for_each_element_mut(Element::swap_mut);
+        dual.reverse();
+
+        // If the original elements were sorted, so will these be.
+        for (dual_els, els) in dual.iter_mut().zip(self.ranks.iter().rev()) {
+            // Safety: swapping subelements and superelements doesn't change
+            // whether they're sorted.
+            unsafe { dual_els.set_sorted(els.sorted()) };
         }
+
+        dual
     }
 
     /// Takes the dual of an abstract polytope. This can never fail.
+    ///
+    /// Unlike [`Self::dual_mut`], this doesn't cache the original polytope in
+    /// the dual, although it will reuse the cached dual if there is one.
     pub fn dual(&self) -> Self {
-        let mut clone = self.clone();
-        clone.dual_mut();
-        clone
+        let ranks = match &self.meta.dual {
+            Some(dual) => dual.as_ref().clone(),
+            None => self.dual_ranks(),
+        };
+
+        // Safety: the dual of a valid polytope is a valid polytope.
+        unsafe { Self::from_ranks(ranks) }
     }
 
     /// Converts an abstract polytope into its dual. This can never fail.
@@ -239,6 +291,189 @@ impl Abstract {
         self
     }
 
+    /// Takes the dual of an abstract polytope, along with the map that sends
+    /// every element of the dual to the element of the original polytope it
+    /// comes from.
+    pub fn dual_and_map(&self) -> (Self, PolytopeMap) {
+        let map = self
+            .el_count_iter()
+            .enumerate()
+            .rev()
+            .map(|(r, count)| (0..count).map(|idx| (r, idx)).collect())
+            .collect::<Vec<_>>();
+
+        (self.dual(), map.into())
+    }
+
+    /// Builds a duopyramid from two polytopes, along with the maps that send
+    /// each of its elements to the elements of `self` and `other` it comes
+    /// from.
+    pub fn duopyramid_and_maps(&self, other: &Self) -> (Self, PolytopeMap, PolytopeMap) {
+        product::duopyramid_and_maps(self, other)
+    }
+
+    /// Builds a duoprism from two polytopes, along with the maps that send
+    /// each of its elements to the elements of `self` and `other` it comes
+    /// from.
+    pub fn duoprism_and_maps(&self, other: &Self) -> (Self, PolytopeMap, PolytopeMap) {
+        product::duoprism_and_maps(self, other)
+    }
+
+    /// Builds a duotegum from two polytopes, along with the maps that send
+    /// each of its elements to the elements of `self` and `other` it comes
+    /// from.
+    pub fn duotegum_and_maps(&self, other: &Self) -> (Self, PolytopeMap, PolytopeMap) {
+        product::duotegum_and_maps(self, other)
+    }
+
+    /// Builds a duocomb from two polytopes, along with the maps that send
+    /// each of its elements to the elements of `self` and `other` it comes
+    /// from.
+    pub fn duocomb_and_maps(&self, other: &Self) -> (Self, PolytopeMap, PolytopeMap) {
+        product::duocomb_and_maps(self, other)
+    }
+
+    /// Orients every face of the polytope consistently. This rewrites the
+    /// subelements of each face, so that walking through its edges in order
+    /// traces its boundary in the direction given by the orientation of its
+    /// flags. In a polyhedron, this makes adjacent faces go through their
+    /// common edges in opposite directions.
+    ///
+    /// A face made out of many cycles lists the edges of each of them in
+    /// succession. Each component of the polytope is oriented independently.
+    ///
+    /// Returns `false` and leaves the polytope untouched if it's
+    /// non-orientable. Since the subelements of the faces won't be sorted
+    /// anymore, calling [`Polytope::element_sort`] afterwards undoes this.
+    pub fn orient(&mut self) -> bool {
+        let rank = self.rank();
+
+        // Nothing to do if the polytope has no faces.
+        if rank < 3 {
+            return true;
+        }
+
+        if !self.sorted() {
+            self.element_sort();
+        }
+
+        let face_count = self[3].len();
+        let mut new_subs = vec![Vec::new(); face_count];
+        let mut placed = vec![HashSet::new(); face_count];
+        let mut all_flags = HashSet::new();
+
+        // The endpoint of an edge opposite to a given vertex.
+        let other = |edge: usize, v: usize| {
+            let subs = &self[2][edge].subs;
+            if subs[0] == v {
+                subs[1]
+            } else {
+                subs[0]
+            }
+        };
+
+        for flag in self.flags() {
+            if all_flags.contains(&flag) {
+                continue;
+            }
+
+            for flag_event in
+                OrientedFlagIter::with_flags(self, FlagChanges::all(rank), flag.into())
+            {
+                let oriented_flag = match flag_event {
+                    FlagEvent::Flag(oriented_flag) => oriented_flag,
+                    FlagEvent::NonOrientable => return false,
+                };
+
+                let (v, mut edge, face) = (
+                    oriented_flag.flag[1],
+                    oriented_flag.flag[2],
+                    oriented_flag.flag[3],
+                );
+                all_flags.insert(oriented_flag.flag);
+
+                if placed[face].contains(&edge) {
+                    continue;
+                }
+
+                // Flags with a positive orientation walk away from their
+                // vertex, those with a negative orientation walk towards it.
+                let mut prev = if oriented_flag.orientation.sign() > 0.0 {
+                    v
+                } else {
+                    other(edge, v)
+                };
+
+                // We walk around the cycle until we get back to where we
+                // started.
+                loop {
+                    placed[face].insert(edge);
+                    new_subs[face].push(edge);
+
+                    let cur = other(edge, prev);
+                    let next = self[1][cur].sups.iter().copied().find(|&e| {
+                        e != edge && !placed[face].contains(&e) && self[3][face].subs.contains(&e)
+                    });
+
+                    match next {
+                        Some(next) => {
+                            prev = cur;
+                            edge = next;
+                        }
+                        None => break,
+                    }
+                }
+            }
+        }
+
+        for (face, subs) in self.ranks[3].iter_mut().zip(new_subs) {
+            face.subs = subs.into();
+        }
+
+        self.clear_cache();
+        true
+    }
+
+    /// Returns the faces of a polyhedron with their edges listed as in
+    /// [`Self::orient`], without modifying the polytope. Returns `None` if the
+    /// polytope isn't a polyhedron or if it's non-orientable.
+    ///
+    /// The faces are borrowed from the cache if [`Self::cache_orientation`]
+    /// has been called since the polytope was last modified, and computed
+    /// from scratch otherwise.
+    pub fn oriented_faces(&self) -> Option<Cow<'_, ElementList>> {
+        if self.rank() != 4 {
+            return None;
+        }
+
+        match &self.meta.oriented_faces {
+            Some(faces) => faces.as_deref().map(Cow::Borrowed),
+            None => self.compute_oriented_faces().map(Cow::Owned),
+        }
+    }
+
+    /// Orients a copy of the polyhedron and returns its faces, or `None` if
+    /// it's non-orientable.
+    fn compute_oriented_faces(&self) -> Option<ElementList> {
+        let mut abs = self.clone();
+        abs.orient().then(|| abs.into_iter().nth(3).unwrap())
+    }
+
+    /// Computes the faces returned by [`Self::oriented_faces`] and caches
+    /// them until the polytope is modified. Does nothing if the polytope isn't
+    /// a polyhedron, or if they're already cached.
+    pub fn cache_orientation(&mut self) {
+        if self.rank() == 4 && self.meta.oriented_faces.is_none() {
+            let faces = self.compute_oriented_faces().map(Box::new);
+            self.meta.oriented_faces = Some(faces);
+        }
+    }
+
+    /// Returns whether the orientation of the polytope is currently cached.
+    pub fn orientation_cached(&self) -> bool {
+        self.meta.oriented_faces.is_some()
+    }
+
     /// Builds an [antiprism](https://polytope.miraheze.org/wiki/Antiprism)
     /// based on a given polytope. Also returns the indices of the vertices that
     /// form the base and the dual base, in that order.
@@ -252,6 +487,124 @@ impl Abstract {
         antiprism::antiprism(self)
     }
 
+    /// Builds an [antitegum](https://polytope.miraheze.org/wiki/Antitegum)
+    /// based on a given polytope, as the dual of its antiprism. This can never
+    /// fail for an abstract polytope.
+    pub fn antitegum(&self) -> Self {
+        self.antiprism().dual()
+    }
+
+    /// Builds a [cupola](https://polytope.miraheze.org/wiki/Cupola) whose top
+    /// face has `n` sides.
+    ///
+    /// The vertices of the bottom face come first, in cyclic order, followed
+    /// by those of the top face. The top vertex `j` is adjacent to the bottom
+    /// vertices `2j - 1` and `2j`.
+    pub fn cupola(n: usize) -> Self {
+        assert!(n >= 2, "A cupola must have at least 2 sides on top.");
+        let (bottom, top) = (2 * n, 3 * n);
+        let mut edges = SubelementList::with_capacity(5 * n);
+
+        // The edges of the bottom face.
+        for i in 0..bottom {
+            edges.push(vec![i, (i + 1) % bottom].into());
+        }
+
+        // The edges of the top face.
+        for j in 0..n {
+            edges.push(vec![bottom + j, bottom + (j + 1) % n].into());
+        }
+
+        // The lateral edges.
+        for j in 0..n {
+            edges.push(vec![2 * j, bottom + j].into());
+            edges.push(vec![2 * j + 1, bottom + (j + 1) % n].into());
+        }
+
+        let mut faces = SubelementList::with_capacity(2 * n + 2);
+        faces.push((0..bottom).collect());
+        faces.push((bottom..top).collect());
+
+        // The squares and the triangles.
+        for j in 0..n {
+            faces.push(vec![2 * j, bottom + j, top + 2 * j, top + 2 * j + 1].into());
+        }
+        for j in 0..n {
+            faces.push(vec![2 * j + 1, top + 2 * j + 1, top + (2 * j + 2) % bottom].into());
+        }
+
+        let mut builder = AbstractBuilder::with_rank_capacity(4);
+        builder.push_min();
+        builder.push_vertices(top);
+        builder.push(edges);
+        builder.push(faces);
+        builder.push_max();
+
+        // Safety: a cupola is a valid polytope.
+        unsafe { builder.build() }
+    }
+
+    /// Builds a [rotunda](https://polytope.miraheze.org/wiki/Rotunda) whose top
+    /// face has `n` sides. Its lateral faces are `n` pentagons and `2n`
+    /// triangles.
+    ///
+    /// The vertices of the bottom face come first, in cyclic order, followed
+    /// by those of the top face, and then by those in between. The top vertex
+    /// `j` lies over the bottom edge `2j`, and the middle vertex `j` is
+    /// adjacent to the bottom vertices `2j + 1` and `2j + 2`, and to the top
+    /// vertices `j` and `j + 1`.
+    pub fn rotunda(n: usize) -> Self {
+        assert!(n >= 2, "A rotunda must have at least 2 sides on top.");
+        let (bottom, top, middle) = (2 * n, 3 * n, 4 * n);
+        let mut edges = SubelementList::with_capacity(7 * n);
+
+        // The edges of the bottom face.
+        for i in 0..bottom {
+            edges.push(vec![i, (i + 1) % bottom].into());
+        }
+
+        // The edges of the top face.
+        for j in 0..n {
+            edges.push(vec![bottom + j, bottom + (j + 1) % n].into());
+        }
+
+        // The edges through the middle vertices.
+        for j in 0..n {
+            let m = top + j;
+            edges.push(vec![2 * j + 1, m].into());
+            edges.push(vec![(2 * j + 2) % bottom, m].into());
+            edges.push(vec![bottom + j, m].into());
+            edges.push(vec![bottom + (j + 1) % n, m].into());
+        }
+
+        let mut faces = SubelementList::with_capacity(3 * n + 2);
+        faces.push((0..bottom).collect());
+        faces.push((bottom..top).collect());
+
+        // The pentagons, and the triangles on the bottom and on the top.
+        for j in 0..n {
+            let prev = top + 4 * ((j + n - 1) % n);
+            let edge = top + 4 * j;
+            faces.push(vec![2 * j, edge, edge + 2, prev + 3, prev + 1].into());
+        }
+        for j in 0..n {
+            faces.push(vec![2 * j + 1, top + 4 * j, top + 4 * j + 1].into());
+        }
+        for j in 0..n {
+            faces.push(vec![bottom + j, top + 4 * j + 2, top + 4 * j + 3].into());
+        }
+
+        let mut builder = AbstractBuilder::with_rank_capacity(4);
+        builder.push_min();
+        builder.push_vertices(middle);
+        builder.push(edges);
+        builder.push(faces);
+        builder.push_max();
+
+        // Safety: a rotunda is a valid polytope.
+        unsafe { builder.build() }
+    }
+
     /// Gets the indices of the vertices of an element in the polytope, if it
     /// exists.
     pub fn element_vertices(&self, rank: usize, idx: usize) -> Option<Vec<usize>> {
@@ -265,77 +618,259 @@ impl Abstract {
         Some((element_hash.to_vertices(), element_hash.to_polytope(self)))
     }
 
-    /// Returns the omnitruncate of a polytope, along with the flags that make
-    /// up its respective vertices.
+    /// Gets the polytope whose facets are the elements with a given rank and
+    /// given indices, along with the indices of its vertices in the original
+    /// polytope. If a single index is given, this is just the element itself.
     ///
-    /// # Panics
-    /// You must call [`Polytope::element_sort`] before calling this method.
-    pub fn omnitruncate_and_flags(&self) -> (Self, Vec<Flag>) {
-        let mut flag_sets = vec![FlagSet::new_all(self)];
-        let mut new_flag_sets = Vec::new();
-        let rank = self.rank();
+    /// Returns `None` if any of the elements doesn't exist, or if no elements
+    /// are given.
+    pub fn elements_and_vertices(&self, rank: usize, idxs: &[usize]) -> Option<(Vec<usize>, Self)> {
+        let element_hash = ElementHash::from_elements(self, rank, idxs)?;
+        Some((element_hash.to_vertices(), element_hash.to_polytope(self)))
+    }
 
-        // The elements of each rank... backwards.
-        let mut ranks = Vec::with_capacity(rank + 1);
+    /// Returns the number of [`Flag`]s of the polytope, without building any of
+    /// them.
+    ///
+    /// The number of flags through an element is the sum of the number of
+    /// flags through each of its subelements, so we can count them in a single
+    /// pass from the minimal element up to the maximal element.
+    pub fn flag_count(&self) -> usize {
+        let mut counts = vec![1];
+
+        for elements in self.iter().skip(1) {
+            counts = elements
+                .iter()
+                .map(|el| el.subs.iter().map(|&sub| counts[sub]).sum())
+                .collect();
+        }
 
-        // Adds elements of each rank, except for vertices and the minimal
-        // element.
-        for _ in (2..=rank).rev() {
-            let mut subelements = SubelementList::new();
+        counts.into_iter().sum()
+    }
 
-            // Gets the subelements of each element.
-            for flag_set in flag_sets {
-                let mut subs = Subelements::new();
+    /// Returns the indices of the facets in the order they're found by a
+    /// breadth-first search, where two facets are adjacent whenever they share
+    /// a ridge. Every connected component is searched in turn, starting from
+    /// its facet with the least index.
+    ///
+    /// Revealing the facets in this order builds up the polytope piece by
+    /// piece, with every new facet attached to the ones before it.
+    pub fn facet_bfs_order(&self) -> Vec<usize> {
+        self.facet_adjacency()
+            .map(|graph| graph.bfs_all())
+            .unwrap_or_default()
+    }
 
-                // Each subset represents a new element.
-                // todo: just return an iterator here.
-                for subset in flag_set.subsets(self) {
-                    // We do a brute-force check to see if we've found this
-                    // element before.
-                    //
-                    // TODO: think of something better?
-                    match new_flag_sets
-                        .iter()
-                        .enumerate()
-                        .find(|(_, new_flag_set)| subset == **new_flag_set)
-                    {
-                        // This is a repeat element.
-                        Some((idx, _)) => {
-                            subs.push(idx);
-                        }
+    /// Returns the incidence matrices between the elements of consecutive
+    /// ranks, from the vertices and edges up to the ridges and facets. The
+    /// entry at row `i` and column `j` of the matrix with index `r` says
+    /// whether the element with index `i` and rank `r + 1` is a subelement of
+    /// the element with index `j` and rank `r + 2`.
+    pub fn incidence_matrices(&self) -> Vec<Vec<Vec<bool>>> {
+        (2..self.rank())
+            .map(|r| {
+                let mut matrix = vec![vec![false; self.el_count(r)]; self.el_count(r - 1)];
+                for (j, el) in self[r].iter().enumerate() {
+                    for &i in &el.subs {
+                        matrix[i][j] = true;
+                    }
+                }
+                matrix
+            })
+            .collect()
+    }
 
-                        // This is a new element.
+    /// Writes the [incidence matrices](Self::incidence_matrices) of the
+    /// polytope as text. Each matrix is preceded by its number of rows and
+    /// columns, and followed by a blank line.
+    pub fn incidence_matrices_string(&self) -> String {
+        let mut string = String::new();
+
+        for matrix in self.incidence_matrices() {
+            let cols = matrix.first().map_or(0, Vec::len);
+            string.push_str(&format!("{} {}\n", matrix.len(), cols));
+
+            for row in matrix {
+                let row: Vec<_> = row.iter().map(|&b| if b { "1" } else { "0" }).collect();
+                string.push_str(&row.join(" "));
+                string.push('\n');
+            }
+
+            string.push('\n');
+        }
+
+        string
+    }
+
+    /// Returns the automorphisms of the polytope, each of them as the map it
+    /// induces on the elements of every rank.
+    ///
+    /// An automorphism is determined by the image of a single flag. We try
+    /// sending the first flag to each flag, and extend the map through the flag
+    /// changes, keeping it only if it's consistent. The flag graph is assumed
+    /// to be connected, as it is in any polytope that isn't a compound.
+    ///
+    /// # Panics
+    /// You must call [`Polytope::element_sort`] before calling this method.
+    pub fn automorphisms(&self) -> Vec<ElementMap<usize>> {
+        let rank = self.rank();
+        let flags: Vec<_> = FlagIter::new(self).collect();
+        let flag_idx: HashMap<_, _> = flags.iter().zip(0..).collect();
+        let first = flag_idx[&self.first_flag()];
+        let mut automorphisms = Vec::new();
+
+        'targets: for target in 0..flags.len() {
+            let mut image = vec![None; flags.len()];
+            image[first] = Some(target);
+            let mut queue = vec![first];
+
+            while let Some(flag) = queue.pop() {
+                let flag_image = image[flag].unwrap();
+
+                for r in 1..rank {
+                    let changed = flag_idx[&flags[flag].change(self, r)];
+                    let changed_image = flag_idx[&flags[flag_image].change(self, r)];
+
+                    match image[changed] {
+                        Some(idx) if idx != changed_image => continue 'targets,
+                        Some(_) => {}
                         None => {
-                            subs.push(new_flag_sets.len());
-                            new_flag_sets.push(subset);
+                            image[changed] = Some(changed_image);
+                            queue.push(changed);
                         }
                     }
                 }
+            }
+
+            let mut map = ElementMap::new();
+            for elements in self.iter() {
+                map.push(vec![0; elements.len()]);
+            }
+            for (flag, flag_image) in image.into_iter().enumerate() {
+                if let Some(flag_image) = flag_image {
+                    for r in 0..=rank {
+                        map[r][flags[flag][r]] = flags[flag_image][r];
+                    }
+                }
+            }
+            automorphisms.push(map);
+        }
+
+        automorphisms
+    }
 
-                subelements.push(subs);
+    /// Builds the quotient of a polytope by an involution that sends every
+    /// element to its antipode, given as a map on the elements of each rank.
+    /// Each element is identified with its antipode, and represented by
+    /// whichever of the two has the lower index.
+    ///
+    /// Returns `None` if any proper element is its own antipode, if some
+    /// element has two antipodal subelements, or if the quotient isn't dyadic.
+    /// In particular, this is the case for dyads and anything below them, as
+    /// the quotient of a dyad would be an edge with a single vertex.
+    pub fn antipodal_quotient(&self, antipodes: &ElementMap<usize>) -> Option<Self> {
+        let rank = self.rank();
+        if rank < 3 {
+            return None;
+        }
+
+        // The class of every element, and the representative of every class.
+        let mut classes = vec![vec![0]];
+        let mut reps = vec![vec![0]];
+        for r in 1..rank {
+            let mut class = vec![None; self.el_count(r)];
+            let mut rep = Vec::new();
+
+            for idx in 0..class.len() {
+                let antipode = antipodes[(r, idx)];
+                if antipode == idx {
+                    return None;
+                }
+
+                if class[idx].is_none() {
+                    class[idx] = Some(rep.len());
+                    class[antipode] = Some(rep.len());
+                    rep.push(idx);
+                }
             }
 
-            ranks.push(subelements);
-            flag_sets = new_flag_sets;
-            new_flag_sets = Vec::new();
+            classes.push(class.into_iter().map(Option::unwrap).collect());
+            reps.push(rep);
         }
 
-        // todo: it might be better to just return an iterator.
-        let mut flags = Vec::new();
-        for flag_set in flag_sets {
-            debug_assert_eq!(flag_set.len(), 1);
-            flags.push(flag_set.flags.into_iter().next().unwrap());
+        let mut builder = AbstractBuilder::with_rank_capacity(rank);
+        builder.push_min();
+        builder.push_vertices(reps[1].len());
+
+        for r in 2..rank {
+            let mut subelements = SubelementList::with_capacity(reps[r].len());
+
+            for &idx in &reps[r] {
+                let subs = &self[(r, idx)].subs;
+                let mut new_subs: Vec<_> = subs.iter().map(|&sub| classes[r - 1][sub]).collect();
+                new_subs.sort_unstable();
+                new_subs.dedup();
+
+                if new_subs.len() != subs.len() {
+                    return None;
+                }
+                subelements.push(new_subs.into());
+            }
+
+            builder.push(subelements);
         }
 
-        ranks.push(SubelementList::vertices(flags.len()));
-        ranks.push(SubelementList::min());
+        builder.push_max();
 
-        let builder: AbstractBuilder = ranks.into_iter().rev().collect();
+        // Safety: we've checked that the quotient is dyadic, and the rest of
+        // the conditions are inherited from the original polytope.
+        builder
+            .ranks()
+            .is_dyadic()
+            .is_ok()
+            .then(|| unsafe { builder.build() })
+    }
+
+    /// Returns the omnitruncate of a polytope, along with the flags that make
+    /// up its respective vertices. See
+    /// [`FlagGraph::omnitruncate`](flag::FlagGraph::omnitruncate).
+    ///
+    /// Only the flags connected to the first one are used, so the omnitruncate
+    /// of a compound is that of its first component.
+    ///
+    /// # Panics
+    /// You must call [`Polytope::element_sort`] before calling this method.
+    pub fn omnitruncate_and_flags(&self) -> (Self, Vec<Flag>) {
+        self.omnitruncate_and_flags_with(|_, _| true)
+            .expect("the omnitruncate was never stopped")
+    }
 
-        // Safety: we've built an omnitruncate based on the polytope. For a
-        // proof that this construction yields a valid abstract polytope, see
-        // [TODO: write proof].
-        (unsafe { builder.build() }, flags)
+    /// Returns the omnitruncate of a polytope, along with the flags that make
+    /// up its respective vertices, as in [`Self::omnitruncate_and_flags`].
+    ///
+    /// Every so often, `progress` is called with an estimate of the work done
+    /// and of the total work. The construction stops and returns `None` if it
+    /// returns `false`.
+    ///
+    /// # Panics
+    /// You must call [`Polytope::element_sort`] before calling this method.
+    pub fn omnitruncate_and_flags_with<F: FnMut(usize, usize) -> bool>(
+        &self,
+        mut progress: F,
+    ) -> Option<(Self, Vec<Flag>)> {
+        // Finding the flags and splitting a subgraph into components take
+        // roughly the same time, as both visit every flag once.
+        let subgraphs = (1usize << self.rank().saturating_sub(1)).saturating_sub(2);
+        let graph = self.first_flag_graph_with(|done, total| {
+            progress(done, total * (subgraphs + 1))
+        })?;
+
+        let len = graph.len();
+        let omni = graph.omnitruncate_with(|done, total| {
+            progress(len * (done + 1), len * (total + 1))
+        })?;
+
+        Some((omni, graph.flags))
     }
 
     /// Returns an arbitrary truncate as an abstract polytope.
@@ -554,6 +1089,13 @@ impl Polytope for Abstract {
         }
     }
 
+    /// Builds a [demihypercube](https://polytope.miraheze.org/wiki/Demihypercube)
+    /// with a given rank, which must be at least 4. Its face lattice is read
+    /// off from a concrete demihypercube.
+    fn demihypercube(rank: usize) -> Self {
+        crate::conc::Concrete::demihypercube(rank).into_abs()
+    }
+
     fn vertex_map(&self) -> ElementMap<usize> {
         // Maps every element of the polytope to one of its vertices.
         let mut vertex_map = ElementMap::new();
@@ -690,6 +1232,13 @@ impl Polytope for Abstract {
         Ok(self.antiprism())
     }
 
+    /// Builds an [antitegum](https://polytope.miraheze.org/wiki/Antitegum)
+    /// based on a given polytope. Use [`Self::antitegum`] instead, as this
+    /// method can never fail.
+    fn try_antitegum(&self) -> Result<Self, Self::DualError> {
+        Ok(self.antitegum())
+    }
+
     /// Returns the flag omnitruncate of a polytope.
     fn omnitruncate(&self) -> Self {
         self.omnitruncate_and_flags().0
@@ -746,17 +1295,21 @@ impl Polytope for Abstract {
     }
 
     /// Gets the element figure with a given rank and index as a polytope.
-    fn element_fig(&self, rank: usize, idx: usize) -> Result<Option<Self>, Self::DualError> {
-        if rank <= self.rank() {
-            // todo: this is quite inefficient for a small element figure since
-            // we take the dual of the entire thing.
-            if let Some(mut element_fig) = self.try_dual()?.element(self.rank() - rank, idx) {
-                element_fig.try_dual_mut()?;
-                return Ok(Some(element_fig));
-            }
-        }
+    fn elements(&self, rank: usize, idxs: &[usize]) -> Option<Self> {
+        Some(self.elements_and_vertices(rank, idxs)?.1)
+    }
 
-        Ok(None)
+    fn element_fig(&self, rank: usize, idx: usize) -> Result<Self, SectionError<Self::DualError>> {
+        crate::check_element(self, rank, idx)?;
+
+        // todo: this is quite inefficient for a small element figure since
+        // we take the dual of the entire thing.
+        let mut element_fig = self
+            .dual()
+            .element(self.rank() - rank, idx)
+            .expect("the element was checked to exist");
+        element_fig.dual_mut();
+        Ok(element_fig)
     }
 
     /// Builds a [duopyramid](https://polytope.miraheze.org/wiki/Pyramid_product)
@@ -795,6 +1348,7 @@ impl Polytope for Abstract {
     /// polytope in place. Does nothing in the case of the nullitope.
     fn ditope_mut(&mut self) {
         if self.rank() != 0 {
+            self.clear_cache();
             let rank = self.rank();
             let ranks = &mut self.ranks;
 
@@ -814,6 +1368,7 @@ impl Polytope for Abstract {
     /// given polytope in place. Does nothing in case of the nullitope.
     fn hosotope_mut(&mut self) {
         if self.rank() != 0 {
+            self.clear_cache();
             let ranks = &mut self.ranks;
 
             for v in &mut ranks[1] {
@@ -911,7 +1466,7 @@ impl IndexMut<(usize, usize)> for Abstract {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test;
+    use crate::{conc::Concrete, file::FromFile, test, Surface};
 
     /// Checks a nullitope.
     #[test]
@@ -1018,4 +1573,388 @@ mod tests {
         test(&Abstract::polygon(6).into_dual(), [1, 6, 6, 1]);
         test(&Abstract::cube().into_dual(), [1, 6, 12, 8, 1]);
     }
+
+    /// Tests the Petrie polygons of a few polytopes.
+    #[test]
+    fn petrie_polygons() {
+        let mut tet = Abstract::tetrahedron();
+        tet.element_sort();
+        assert_eq!(tet.petrie_polygon_length(), Some(4));
+        assert_eq!(tet.petrie_polygons().len(), 3);
+
+        let mut cube = Abstract::cube();
+        cube.element_sort();
+        assert_eq!(cube.petrie_polygon_length(), Some(6));
+        assert_eq!(cube.petrie_polygons().len(), 4);
+
+        let mut tesseract = Abstract::hypercube(5);
+        tesseract.element_sort();
+        assert_eq!(tesseract.petrie_polygon_length(), Some(8));
+        assert_eq!(tesseract.petrie_polygons().len(), 24);
+
+        for polygon in cube.petrie_polygons() {
+            assert_eq!(polygon.len(), 6);
+        }
+    }
+
+    /// Returns the edges of the faces of a polytope as pairs of vertices, in
+    /// the direction in which they're listed.
+    fn directed_edges(poly: &Abstract) -> Vec<(usize, usize)> {
+        let mut directed = Vec::new();
+
+        for face in poly[3].iter() {
+            let edges: Vec<_> = face.subs.iter().map(|&e| &poly[2][e].subs).collect();
+
+            for (i, edge) in edges.iter().enumerate() {
+                let next = edges[(i + 1) % edges.len()];
+                let (v, w) = (edge[0], edge[1]);
+
+                // The head of an edge is the vertex it shares with the next.
+                directed.push(if next.contains(&w) { (v, w) } else { (w, v) });
+            }
+        }
+
+        directed
+    }
+
+    /// Checks that sortedness is tracked separately for every rank.
+    #[test]
+    fn sorted() {
+        let mut poly = Abstract::cube();
+        poly.element_sort();
+        assert!(poly.sorted());
+
+        // Taking the dual preserves sortedness.
+        let dual = poly.dual();
+        assert!(dual.sorted());
+
+        // Modifying a rank only marks that rank as unsorted.
+        poly[3][0].subs.reverse();
+        assert!(!poly.sorted());
+        for r in 0..=poly.rank() {
+            assert_eq!(poly[r].sorted(), r != 3);
+        }
+
+        // Clones keep the flags, and iterating mutably also clears them.
+        assert!(!poly.clone()[3].sorted());
+        for el in poly[2].iter_mut() {
+            el.sups.reverse();
+        }
+        assert!(!poly[2].sorted());
+
+        poly.element_sort();
+        assert!(poly.sorted());
+        let mut cube = Abstract::cube();
+        cube.element_sort();
+        assert_eq!(poly.ranks(), cube.ranks());
+    }
+
+    /// Tests that orienting a polyhedron makes adjacent faces go through their
+    /// common edges in opposite directions.
+    #[test]
+    fn orient() {
+        for mut poly in [
+            Abstract::cube(),
+            Abstract::tetrahedron(),
+            Abstract::octahedron(),
+        ] {
+            assert!(poly.orient());
+            assert!(!poly.sorted());
+
+            let directed = directed_edges(&poly);
+            let unique: HashSet<_> = directed.iter().copied().collect();
+            assert_eq!(directed.len(), 2 * poly.edge_count());
+            assert_eq!(
+                unique.len(),
+                directed.len(),
+                "Faces aren't oriented consistently."
+            );
+        }
+
+        // The tetrahemihexahedron can't be oriented.
+        let mut thah = Concrete::from_off(
+            "OFF
+            6 7 12
+            1 0 0
+            -1 0 0
+            0 1 0
+            0 -1 0
+            0 0 1
+            0 0 -1
+            3 0 2 4
+            3 0 3 5
+            3 1 2 5
+            3 1 3 4
+            4 0 2 1 3
+            4 2 4 3 5
+            4 0 4 1 5",
+        )
+        .unwrap()
+        .abs;
+        assert!(!thah.orient());
+    }
+
+    /// Tests that the orientation of a polyhedron is cached until it's
+    /// modified, and that it's only computed for polyhedra.
+    #[test]
+    fn orientation_cache() {
+        let face_subs = |faces: &ElementList| -> Vec<Vec<usize>> {
+            faces.iter().map(|face| face.subs.iter().copied().collect()).collect()
+        };
+
+        let mut cube = Abstract::cube();
+        let faces = face_subs(&cube.oriented_faces().unwrap());
+        assert!(!cube.orientation_cached());
+
+        cube.cache_orientation();
+        assert!(cube.orientation_cached());
+        assert_eq!(face_subs(&cube.oriented_faces().unwrap()), faces);
+
+        // Neither clones nor modifications carry the cache around.
+        assert!(!cube.clone().orientation_cached());
+        cube.dual_mut();
+        assert!(!cube.orientation_cached());
+        let _ = &mut cube[0];
+        cube.cache_orientation();
+        assert!(cube.orientation_cached());
+        let _ = &mut cube[0];
+        assert!(!cube.orientation_cached());
+
+        // Non-orientable polyhedra cache their failure.
+        let mut hemi = Abstract::tetrahedron().petrial().unwrap();
+        hemi.cache_orientation();
+        assert!(hemi.orientation_cached());
+        assert!(hemi.oriented_faces().is_none());
+
+        // Only polyhedra get oriented.
+        let mut tesseract = Abstract::hypercube(5);
+        tesseract.cache_orientation();
+        assert!(!tesseract.orientation_cached());
+        assert!(tesseract.oriented_faces().is_none());
+    }
+
+    /// Tests element figures and sections, including their edge cases.
+    #[test]
+    fn sections() {
+        let cube = Abstract::cube();
+
+        test(&cube.verf(0).unwrap(), [1, 3, 3, 1]);
+        test(&cube.element_fig(0, 0).unwrap(), [1, 8, 12, 6, 1]);
+        test(&cube.element_fig(4, 0).unwrap(), [1]);
+        test(&Abstract::nullitope().element_fig(0, 0).unwrap(), [1]);
+
+        assert!(matches!(
+            cube.element_fig(5, 0),
+            Err(SectionError::Rank {
+                rank: 5,
+                max_rank: 4
+            })
+        ));
+        assert!(matches!(
+            cube.verf(8),
+            Err(SectionError::Index { count: 8, .. })
+        ));
+        assert!(Abstract::nullitope().verf(0).is_err());
+
+        // A vertex and a square through it.
+        let vertices = cube.element_vertices(3, 0).unwrap();
+        let vertex = vertices[0];
+        test(&cube.section(1, vertex, 3, 0).unwrap(), [1, 2, 1]);
+        test(&cube.section(0, 0, 3, 0).unwrap(), [1, 4, 4, 1]);
+
+        // A vertex and a square not through it.
+        let vertex = (0..8).find(|v| !vertices.contains(v)).unwrap();
+        assert!(matches!(
+            cube.section(1, vertex, 3, 0),
+            Err(SectionError::NotIncident)
+        ));
+    }
+
+    /// Tests that the dual is cached, and that the cache is dropped whenever
+    /// the polytope changes.
+    #[test]
+    fn dual_cache() {
+        let mut poly = Abstract::polygon(5).pyramid();
+        assert!(!poly.dual_cached());
+
+        poly.dual_mut();
+        assert!(poly.dual_cached());
+        test(&poly, [1, 6, 10, 6, 1]);
+
+        // Taking the dual twice gives back the original polytope.
+        poly.dual_mut();
+        assert!(poly.dual_cached());
+        test(&poly, [1, 6, 10, 6, 1]);
+        assert_eq!(poly[(4, 0)].subs.len(), 6);
+        assert_eq!(poly[(1, 5)].sups.len(), 5);
+
+        // Modifying the polytope invalidates the cache.
+        poly.ditope_mut();
+        assert!(!poly.dual_cached());
+        poly.dual_mut();
+        test(&poly, [1, 2, 6, 10, 6, 1]);
+
+        unsafe {
+            poly.ranks_mut();
+        }
+        assert!(!poly.dual_cached());
+
+        // Indexing mutably invalidates the cache.
+        poly.dual_mut();
+        assert!(poly.dual_cached());
+        let _ = &mut poly[0];
+        assert!(!poly.dual_cached());
+
+        // Neither clones nor duals carry a cache around.
+        poly.dual_mut();
+        assert!(!poly.clone().dual_cached());
+        let dual = poly.dual();
+        assert!(!dual.dual_cached());
+        test(&dual, [1, 6, 10, 6, 2, 1]);
+    }
+
+    /// Checks the number of automorphisms of some polytopes.
+    #[test]
+    fn automorphisms() {
+        let mut pentagon = Abstract::polygon(5);
+        pentagon.element_sort();
+        assert_eq!(pentagon.automorphisms().len(), 10);
+
+        let mut cube = Abstract::cube();
+        cube.element_sort();
+        assert_eq!(cube.automorphisms().len(), 48);
+
+        let mut pyramid = Abstract::polygon(4).pyramid();
+        pyramid.element_sort();
+        let automorphisms = pyramid.automorphisms();
+        assert_eq!(automorphisms.len(), 8);
+
+        // The apex is fixed by every automorphism.
+        let apex = (0..pyramid.vertex_count())
+            .find(|&v| pyramid[(1, v)].sups.len() == 4)
+            .unwrap();
+        assert!(automorphisms.iter().all(|map| map[(1, apex)] == apex));
+    }
+
+    /// Checks the omnitruncates of some polytopes, and their truncates.
+    #[test]
+    fn omnitruncate() {
+        test(&Abstract::point().omnitruncate(), [1, 1]);
+        test(&Abstract::dyad().omnitruncate(), [1, 2, 1]);
+        test(&Abstract::polygon(5).omnitruncate(), [1, 10, 10, 1]);
+
+        let mut tetrahedron = Abstract::tetrahedron();
+        tetrahedron.element_sort();
+        let (omni, flags) = tetrahedron.omnitruncate_and_flags();
+        test(&omni, [1, 24, 36, 14, 1]);
+        assert_eq!(flags.len(), 24);
+
+        // The omnitruncated tesseract.
+        let mut tesseract = Abstract::hypercube(5);
+        tesseract.element_sort();
+        test(&tesseract.omnitruncate(), [1, 384, 768, 464, 80, 1]);
+
+        // Compounds give the omnitruncate of their first component.
+        let mut compound = Abstract::cube();
+        compound.comp_append(Abstract::tetrahedron());
+        compound.element_sort();
+        test(&compound.omnitruncate(), [1, 48, 72, 26, 1]);
+        test(&compound.flag_graph().omnitruncate(), [1, 72, 108, 40, 1]);
+        assert_eq!(compound.first_flag_graph().len(), 48);
+
+        // The construction can be stopped midway.
+        assert!(tesseract.omnitruncate_and_flags_with(|_, _| false).is_none());
+
+        // The truncated cube.
+        let mut cube = Abstract::cube();
+        cube.element_sort();
+        test(&cube.truncate_and_flags(vec![0, 1]).0, [1, 24, 36, 14, 1]);
+    }
+
+    /// Checks that compounds are split back into their components.
+    #[test]
+    fn components() {
+        let mut compound = Abstract::cube();
+        compound.comp_append(Abstract::polygon(5).prism());
+        compound.comp_append(Abstract::cube());
+
+        let components = compound.components();
+        assert_eq!(components.len(), 3);
+        test(&components[0], [1, 8, 12, 6, 1]);
+        test(&components[1], [1, 10, 15, 7, 1]);
+        test(&components[2], [1, 8, 12, 6, 1]);
+
+        // A polytope that isn't a compound is its only component.
+        let components = Abstract::cube().components();
+        assert_eq!(components.len(), 1);
+        test(&components[0], [1, 8, 12, 6, 1]);
+    }
+
+    /// Checks the order in which the facets of some polytopes are revealed.
+    #[test]
+    fn facet_bfs_order() {
+        let cube = Abstract::cube();
+        let order = cube.facet_bfs_order();
+        let mut sorted = order.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..6).collect::<Vec<_>>());
+
+        // Every face but the last one is adjacent to the first.
+        let shares_edge = |f: usize, g: usize| {
+            cube[(3, f)]
+                .subs
+                .iter()
+                .any(|e| cube[(3, g)].subs.contains(e))
+        };
+        assert_eq!(order[0], 0);
+        assert!(order[1..5].iter().all(|&f| shares_edge(0, f)));
+        assert!(!shares_edge(0, order[5]));
+
+        // Components are revealed one after the other.
+        let mut compound = Abstract::cube();
+        compound.comp_append(Abstract::cube());
+        let order = compound.facet_bfs_order();
+        assert_eq!(order.len(), 12);
+        assert!(order[..6].iter().all(|&f| f < 6));
+
+        assert!(Abstract::point().facet_bfs_order().is_empty());
+    }
+
+    /// Checks the Euler characteristics of some polytopes.
+    #[test]
+    fn euler_characteristic() {
+        assert_eq!(Abstract::polygon(5).euler_characteristic(), 0);
+        assert_eq!(Abstract::cube().euler_characteristic(), 2);
+        assert_eq!(Abstract::polygon(7).prism().euler_characteristic(), 2);
+        assert_eq!(Abstract::hypercube(5).euler_characteristic(), 0);
+        assert_eq!(Abstract::simplex(6).euler_characteristic(), 2);
+    }
+
+    /// Checks the surfaces of some polyhedra.
+    #[test]
+    fn surface() {
+        assert_eq!(
+            Abstract::cube().surface_mut(),
+            Some(Surface::Orientable { genus: 0 })
+        );
+
+        // The Petrial of the cube is a map on the torus.
+        let mut petrial = Abstract::cube().petrial().unwrap();
+        assert_eq!(
+            petrial.surface_mut(),
+            Some(Surface::Orientable { genus: 1 })
+        );
+
+        // The Petrial of the tetrahedron is the hemicube.
+        let mut petrial = Abstract::tetrahedron().petrial().unwrap();
+        let surface = petrial.surface_mut().unwrap();
+        assert_eq!(surface, Surface::NonOrientable { genus: 1 });
+        assert_eq!(surface.to_string(), "non-orientable, genus 1");
+
+        // Compounds and non-polyhedra don't have a surface.
+        let mut compound = Abstract::cube();
+        compound.comp_append(Abstract::cube());
+        assert_eq!(compound.surface_mut(), None);
+        assert_eq!(Abstract::polygon(4).surface_mut(), None);
+    }
 }
@@ -139,26 +139,51 @@ fn product<const MIN: bool, const MAX: bool>(p: &Abstract, q: &Abstract) -> Abst
     // The rank of the product.
     let rank = p_rank + q_rank - min_u - max_u;
 
-    // Initializes the element lists. These will only contain the
-    // subelements as they're generated. When they're complete, we'll call
-    // push_subs for each of them into a new Abstract.
-    let mut builder = AbstractBuilder::with_rank_capacity(rank + 1);
+    // A duocomb with a point has no elements coming from pairs of proper
+    // elements. We set its vertices manually, which are also its facets.
+    if MIN && MAX && (p_rank == 1 || q_rank == 1) {
+        let vertex_count = p.vertex_count() * q.vertex_count();
+        let vertices = (0..vertex_count)
+            .map(|_| Element::new(vec![0].into(), vec![0].into()))
+            .collect();
+        let ranks: Ranks = vec![
+            ElementList::min(vertex_count),
+            vertices,
+            ElementList::max(vertex_count),
+        ]
+        .into();
+
+        // Safety: this is the same structure built for these products before.
+        // Its indices are sorted.
+        unsafe {
+            let mut res = Abstract::from_ranks(ranks);
+            res.set_sorted(true);
+            return res;
+        }
+    }
+
+    // Both the subelements and the superelements of every element are found
+    // directly from those of the factors, so that no links need to be rebuilt
+    // or sorted afterwards.
     let offset_memo = OffsetMemo::<MIN, MAX>::new(p, q);
+    let mut ranks = Ranks::with_capacity(rank + 1);
 
-    // If MIN, we have to set a minimal element and the vertices manually.
+    // If MIN, we have to set a minimal element manually.
     if MIN {
-        builder.push_min();
-        builder.push_vertices(p.vertex_count() * q.vertex_count());
+        ranks.push(ElementList::min(p.vertex_count() * q.vertex_count()));
     }
 
-    let lo = 2 * min_u;
-    let hi = rank - max_u;
+    // This can't underflow, as the duocombs with a point were handled above.
+    let prod_hi = rank - max_u;
 
     // Adds elements in order of rank.
-    for prod_rank in lo..=hi {
+    for prod_rank in min_u..=prod_hi {
         let lo = (min_u as isize).max((prod_rank + min_u) as isize - q_hi as isize) as usize;
         let hi = p_hi.min(prod_rank);
-        let mut subelements = SubelementList::new();
+        let count = (lo..=hi)
+            .map(|p_el_rank| p.el_count(p_el_rank) * q.el_count(prod_rank + min_u - p_el_rank))
+            .sum();
+        let mut elements = ElementList::with_capacity(count);
 
         // Adds elements by lexicographic order of the ranks.
         for p_el_rank in lo..=hi {
@@ -168,53 +193,167 @@ fn product<const MIN: bool, const MAX: bool>(p: &Abstract, q: &Abstract) -> Abst
             // with every element in q with rank q_els_rank.
             for (p_idx, p_el) in p[p_el_rank].iter().enumerate() {
                 for (q_idx, q_el) in q[q_el_rank].iter().enumerate() {
-                    let mut subs = Subelements::new();
-
-                    // Products of p's subelements with q.
-                    if !MIN || p_el_rank != 1 {
-                        for &p_sub in &p_el.subs {
-                            subs.push(offset_memo.get_element_index(
-                                p_el_rank - 1,
-                                p_sub,
-                                q,
-                                q_el_rank,
-                                q_idx,
-                            ))
+                    let mut subs = Subelements::with_capacity(p_el.subs.len() + q_el.subs.len());
+                    let mut sups = Superelements::with_capacity(p_el.sups.len() + q_el.sups.len());
+
+                    // The vertices of a product with MIN are only linked to
+                    // the minimal element.
+                    if MIN && prod_rank == 1 {
+                        subs.push(0);
+                    } else {
+                        // Products of p's subelements with q.
+                        if !MIN || p_el_rank != 1 {
+                            for &p_sub in &p_el.subs {
+                                subs.push(offset_memo.get_element_index(
+                                    p_el_rank - 1,
+                                    p_sub,
+                                    q,
+                                    q_el_rank,
+                                    q_idx,
+                                ))
+                            }
+                        }
+
+                        // Products of q's subelements with p.
+                        if !MIN || q_el_rank != 1 {
+                            for &q_sub in &q_el.subs {
+                                subs.push(offset_memo.get_element_index(
+                                    p_el_rank,
+                                    p_idx,
+                                    q,
+                                    q_el_rank - 1,
+                                    q_sub,
+                                ))
+                            }
                         }
                     }
 
-                    // Products of q's subelements with p.
-                    if !MIN || q_el_rank != 1 {
-                        for &q_sub in &q_el.subs {
-                            subs.push(offset_memo.get_element_index(
-                                p_el_rank,
-                                p_idx,
-                                q,
-                                q_el_rank - 1,
-                                q_sub,
-                            ))
+                    // The facets of a product with MAX are only linked to the
+                    // maximal element.
+                    if MAX && prod_rank == prod_hi {
+                        sups.push(0);
+                    } else {
+                        // Products of q's superelements with p. These come
+                        // first, as they have a lower rank in p.
+                        if q_el_rank != q_hi {
+                            for &q_sup in &q_el.sups {
+                                sups.push(offset_memo.get_element_index(
+                                    p_el_rank,
+                                    p_idx,
+                                    q,
+                                    q_el_rank + 1,
+                                    q_sup,
+                                ))
+                            }
+                        }
+
+                        // Products of p's superelements with q.
+                        if p_el_rank != p_hi {
+                            for &p_sup in &p_el.sups {
+                                sups.push(offset_memo.get_element_index(
+                                    p_el_rank + 1,
+                                    p_sup,
+                                    q,
+                                    q_el_rank,
+                                    q_idx,
+                                ))
+                            }
                         }
                     }
 
-                    subelements.push(subs)
+                    elements.push(Element::new(subs, sups));
                 }
             }
         }
 
-        builder.push(subelements);
+        ranks.push(elements);
     }
 
     // If MAX, we have to set a maximal element manually.
     if MAX {
-        builder.push_max();
+        let facet_count = ranks[rank - 1].len();
+        ranks.push(ElementList::max(facet_count));
     }
 
-    // TODO: If `p` and `q` are sorted, this should be too?
-
     // Safety: we've built one of the four products on polytopes. For a
     // proof that these constructions yield valid abstract polytopes, see
     // [TODO: write proof].
-    unsafe { builder.build() }
+    let mut res = unsafe { Abstract::from_ranks(ranks) };
+
+    // The elements are added by lexicographic order of the ranks and indices
+    // they come from, so sorted factors give a sorted product.
+    if p.sorted() && q.sorted() {
+        // Safety: see above.
+        unsafe { res.set_sorted(true) };
+    }
+
+    res
+}
+
+/// Returns the maps that send every element of a [`product`] of two polytopes
+/// to the elements of `p` and `q` it comes from. The elements are listed in
+/// the same order as in the product.
+fn product_maps<const MIN: bool, const MAX: bool>(
+    p: &Abstract,
+    q: &Abstract,
+) -> (PolytopeMap, PolytopeMap) {
+    let p_rank = p.rank();
+    let q_rank = q.rank();
+
+    // The nullitope comes from the minimal elements.
+    if (MIN || MAX) && (p_rank == 0 || q_rank == 0) {
+        return (vec![vec![(0, 0)]].into(), vec![vec![(0, 0)]].into());
+    }
+
+    let min_u = MIN as usize;
+    let max_u = MAX as usize;
+    let p_hi = p_rank - max_u;
+    let q_hi = q_rank - max_u;
+    let rank = p_rank + q_rank - min_u - max_u;
+
+    let mut p_map = Vec::with_capacity(rank + 1);
+    let mut q_map = Vec::with_capacity(rank + 1);
+
+    // The minimal element and the vertices.
+    if MIN {
+        p_map.push(vec![(0, 0)]);
+        q_map.push(vec![(0, 0)]);
+
+        let (p_count, q_count) = (p.vertex_count(), q.vertex_count());
+        p_map.push((0..p_count * q_count).map(|i| (1, i / q_count)).collect());
+        q_map.push((0..p_count * q_count).map(|i| (1, i % q_count)).collect());
+    }
+
+    // The duocomb of two points has no ranks between its vertices and its
+    // maximal element.
+    for prod_rank in (2 * min_u)..=rank.saturating_sub(max_u) {
+        let lo = (min_u as isize).max((prod_rank + min_u) as isize - q_hi as isize) as usize;
+        let hi = p_hi.min(prod_rank);
+        let mut p_row = Vec::new();
+        let mut q_row = Vec::new();
+
+        for p_el_rank in lo..=hi {
+            let q_el_rank = prod_rank + min_u - p_el_rank;
+
+            for p_idx in 0..p.el_count(p_el_rank) {
+                for q_idx in 0..q.el_count(q_el_rank) {
+                    p_row.push((p_el_rank, p_idx));
+                    q_row.push((q_el_rank, q_idx));
+                }
+            }
+        }
+
+        p_map.push(p_row);
+        q_map.push(q_row);
+    }
+
+    // The maximal element.
+    if MAX {
+        p_map.push(vec![(p_rank, 0)]);
+        q_map.push(vec![(q_rank, 0)]);
+    }
+
+    (p_map.into(), q_map.into())
 }
 
 /// Builds a [duopyramid](https://polytope.miraheze.org/wiki/Pyramid_product)
@@ -249,6 +388,43 @@ pub(super) fn duocomb(p: &Abstract, q: &Abstract) -> Abstract {
     product::<true, true>(p, q)
 }
 
+/// Builds a duopyramid, along with the maps that send each of its elements to
+/// the elements of `p` and `q` it comes from.
+pub(super) fn duopyramid_and_maps(
+    p: &Abstract,
+    q: &Abstract,
+) -> (Abstract, PolytopeMap, PolytopeMap) {
+    let (q_map, p_map) = product_maps::<false, false>(q, p);
+    (duopyramid(p, q), p_map, q_map)
+}
+
+/// Builds a duoprism, along with the maps that send each of its elements to
+/// the elements of `p` and `q` it comes from.
+pub(super) fn duoprism_and_maps(
+    p: &Abstract,
+    q: &Abstract,
+) -> (Abstract, PolytopeMap, PolytopeMap) {
+    let (p_map, q_map) = product_maps::<true, false>(p, q);
+    (duoprism(p, q), p_map, q_map)
+}
+
+/// Builds a duotegum, along with the maps that send each of its elements to
+/// the elements of `p` and `q` it comes from.
+pub(super) fn duotegum_and_maps(
+    p: &Abstract,
+    q: &Abstract,
+) -> (Abstract, PolytopeMap, PolytopeMap) {
+    let (q_map, p_map) = product_maps::<false, true>(q, p);
+    (duotegum(p, q), p_map, q_map)
+}
+
+/// Builds a duocomb, along with the maps that send each of its elements to
+/// the elements of `p` and `q` it comes from.
+pub(super) fn duocomb_and_maps(p: &Abstract, q: &Abstract) -> (Abstract, PolytopeMap, PolytopeMap) {
+    let (p_map, q_map) = product_maps::<true, true>(p, q);
+    (duocomb(p, q), p_map, q_map)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -321,4 +497,62 @@ mod tests {
     fn duocomb() {
         test_duoproduct(Abstract::duocomb, |m, n| [1, m * n, 2 * m * n, m * n, 1])
     }
+
+    /// Checks products involving a point, which have no elements coming from
+    /// pairs of proper elements in some cases.
+    #[test]
+    fn point_product() {
+        let point = Abstract::point();
+        let pentagon = Abstract::polygon(5);
+
+        test(&point.duopyramid(&point), [1, 2, 1]);
+        test(&point.duoprism(&point), [1, 1]);
+        test(&point.duotegum(&point), [1, 1]);
+        test(&point.duocomb(&point), [1, 1, 1]);
+
+        for (p, q) in [(&point, &pentagon), (&pentagon, &point)] {
+            test(&p.duopyramid(q), [1, 6, 10, 6, 1]);
+            test(&p.duoprism(q), [1, 5, 5, 1]);
+            test(&p.duotegum(q), [1, 5, 5, 1]);
+            test(&p.duocomb(q), [1, 5, 1]);
+        }
+    }
+
+    /// Checks that products of sorted polytopes are sorted, and that their
+    /// links are consistent.
+    #[test]
+    fn sorted() {
+        let mut factors = vec![
+            Abstract::nullitope(),
+            Abstract::polygon(5),
+            Abstract::cube(),
+            Abstract::orthoplex(4),
+        ];
+        for p in &mut factors {
+            p.element_sort();
+        }
+
+        let products: [fn(&Abstract, &Abstract) -> Abstract; 4] = [
+            Abstract::duopyramid,
+            Abstract::duoprism,
+            Abstract::duotegum,
+            Abstract::duocomb,
+        ];
+
+        for p in &factors {
+            for q in &factors {
+                for product in &products {
+                    let prod = product(p, q);
+                    prod.assert_valid();
+                    assert!(prod.sorted());
+
+                    // The copy would otherwise keep the sorted flags.
+                    let mut ranks = prod.ranks().clone();
+                    unsafe { ranks.set_sorted(false) };
+                    ranks.element_sort();
+                    assert_eq!(&ranks, prod.ranks());
+                }
+            }
+        }
+    }
 }
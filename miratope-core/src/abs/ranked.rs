@@ -10,6 +10,7 @@ use std::{
 
 use super::Abstract;
 
+#[cfg(feature = "parallel")]
 use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
 use vec_like::*;
 
@@ -18,7 +19,7 @@ use vec_like::*;
 /// of an abstract polytope.
 ///
 /// Internally, this is just a wrapper around a `Vec<Vec<T>>`.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 #[repr(transparent)]
 pub struct ElementMap<T>(Vec<Vec<T>>);
 impl_veclike!(@for [T] ElementMap<T>, Item = Vec<T>);
@@ -136,18 +137,148 @@ impl Element {
 
 /// A list of [`Elements`](Element) of the same rank.
 ///
-/// Internally, this is just a wrapper around a `Vec<Element>`.
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
-#[repr(transparent)]
-pub struct ElementList(pub Vec<Element>);
-impl_veclike!(ElementList, Item = Element);
+/// Internally, this is a wrapper around a `Vec<Element>`, together with a flag
+/// that stores whether the subelements and superelements of every element are
+/// known to be sorted. Any mutable access to the elements clears this flag, so
+/// that [`Ranks::element_sort`] only needs to sort the ranks that have changed.
+#[derive(Debug, Clone)]
+pub struct ElementList {
+    /// The elements of the list.
+    elements: Vec<Element>,
+
+    /// Whether the subelements and superelements of every element are known
+    /// to be sorted by index.
+    sorted: bool,
+}
+
+impl VecLike for ElementList {
+    type VecItem = Element;
+}
+
+impl Default for ElementList {
+    fn default() -> Self {
+        Self::from_inner(Vec::new())
+    }
+}
+
+/// The sortedness flag is just a cache, so it's ignored when comparing lists.
+impl PartialEq for ElementList {
+    fn eq(&self, other: &Self) -> bool {
+        self.elements == other.elements
+    }
+}
+
+impl Eq for ElementList {}
+
+impl std::hash::Hash for ElementList {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.elements.hash(state)
+    }
+}
+
+impl Index<usize> for ElementList {
+    type Output = Element;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.elements[index]
+    }
+}
+
+impl IndexMut<usize> for ElementList {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.sorted = false;
+        &mut self.elements[index]
+    }
+}
+
+impl AsRef<Vec<Element>> for ElementList {
+    fn as_ref(&self) -> &Vec<Element> {
+        &self.elements
+    }
+}
+
+/// Every mutable access to the elements other than indexing goes through here,
+/// so this is where we mark the list as unsorted.
+impl AsMut<Vec<Element>> for ElementList {
+    fn as_mut(&mut self) -> &mut Vec<Element> {
+        self.sorted = false;
+        &mut self.elements
+    }
+}
+
+impl AsRef<[Element]> for ElementList {
+    fn as_ref(&self) -> &[Element] {
+        self.as_inner().as_slice()
+    }
+}
+
+impl AsMut<[Element]> for ElementList {
+    fn as_mut(&mut self) -> &mut [Element] {
+        self.as_inner_mut().as_mut_slice()
+    }
+}
+
+impl From<Vec<Element>> for ElementList {
+    fn from(elements: Vec<Element>) -> Self {
+        Self {
+            elements,
+            sorted: false,
+        }
+    }
+}
+
+impl From<ElementList> for Vec<Element> {
+    fn from(list: ElementList) -> Self {
+        list.elements
+    }
+}
+
+impl Extend<Element> for ElementList {
+    fn extend<I: IntoIterator<Item = Element>>(&mut self, iter: I) {
+        self.as_inner_mut().extend(iter)
+    }
+}
+
+impl IntoIterator for ElementList {
+    type Item = Element;
+    type IntoIter = vec::IntoIter<Element>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.elements.into_iter()
+    }
+}
 
+impl<'a> IntoIterator for &'a ElementList {
+    type Item = &'a Element;
+    type IntoIter = slice::Iter<'a, Element>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut ElementList {
+    type Item = &'a mut Element;
+    type IntoIter = slice::IterMut<'a, Element>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl FromIterator<Element> for ElementList {
+    fn from_iter<I: IntoIterator<Item = Element>>(iter: I) -> Self {
+        Self::from_inner(iter.into_iter().collect())
+    }
+}
+
+#[cfg(feature = "parallel")]
 impl<'a> rayon::iter::IntoParallelIterator for &'a mut ElementList {
     type Iter = rayon::slice::IterMut<'a, Element>;
     type Item = &'a mut Element;
 
     fn into_par_iter(self) -> Self::Iter {
-        self.0.par_iter_mut()
+        self.as_inner_mut().par_iter_mut()
     }
 }
 
@@ -155,13 +286,51 @@ impl ElementList {
     /// Returns the element list for the nullitope in a polytope with a given
     /// vertex count.
     pub fn min(vertex_count: usize) -> Self {
-        Self(vec![Element::min(vertex_count)])
+        Self {
+            elements: vec![Element::min(vertex_count)],
+            sorted: true,
+        }
     }
 
     /// Returns the element list for the maximal element in a polytope with a
     /// given facet count.
     pub fn max(facet_count: usize) -> Self {
-        Self(vec![Element::max(facet_count)])
+        Self {
+            elements: vec![Element::max(facet_count)],
+            sorted: true,
+        }
+    }
+
+    /// Returns whether the subelements and superelements of every element in
+    /// the list are known to be sorted by index.
+    pub fn sorted(&self) -> bool {
+        self.sorted
+    }
+
+    /// Sets whether the subelements and superelements of every element in the
+    /// list are sorted by index.
+    ///
+    /// # Safety
+    /// Setting this flag incorrectly will cause algorithms to behave
+    /// unpredictably, potentially causing UB.
+    pub unsafe fn set_sorted(&mut self, sorted: bool) {
+        self.sorted = sorted;
+    }
+
+    /// Sorts the subelements and superelements of every element by index, in
+    /// parallel if the `parallel` feature is enabled. Does nothing if they're
+    /// already sorted.
+    pub fn element_sort(&mut self) {
+        if self.sorted {
+            return;
+        }
+
+        #[cfg(feature = "parallel")]
+        self.par_iter_mut().for_each(Element::sort);
+        #[cfg(not(feature = "parallel"))]
+        self.iter_mut().for_each(Element::sort);
+
+        self.sorted = true;
     }
 }
 
@@ -267,7 +436,7 @@ type IterMutFn = for<'r> fn(&'r mut ElementList) -> slice::IterMut<'r, Element>;
 
 /// The signature of the function that turns an `ElementList` into an owned
 /// iterator.
-type IntoIterFn = fn(ElementList) -> std::vec::IntoIter<Element>;
+type IntoIterFn = fn(ElementList) -> vec::IntoIter<Element>;
 
 /// The signature of the function that returns the length of an `ElementList`.
 type LenFn = for<'r> fn(&'r ElementList) -> usize;
@@ -466,7 +635,8 @@ impl Ranks {
             .flatten()
     }
 
-    /// Applies a function to all elements in parallel.
+    /// Applies a function to all elements, in parallel if the `parallel`
+    /// feature is enabled.
     pub fn for_each_element_mut<F: Fn(&mut Element) + Sync + Send>(&mut self, f: F) {
         // No use parallelizing over all minimal or maximal elements.
         f(self.min_mut());
@@ -474,13 +644,37 @@ impl Ranks {
 
         let rank = self.rank();
         for elements in self.iter_mut().take(rank).skip(1) {
+            #[cfg(feature = "parallel")]
             elements.par_iter_mut().for_each(&f);
+            #[cfg(not(feature = "parallel"))]
+            elements.iter_mut().for_each(&f);
         }
     }
 
-    /// Sorts all of the superelements and subelements by index.
+    /// Returns whether the subelements and superelements of every element are
+    /// known to be sorted by index.
+    pub fn sorted(&self) -> bool {
+        self.iter().all(ElementList::sorted)
+    }
+
+    /// Sets whether the subelements and superelements of every element are
+    /// sorted by index.
+    ///
+    /// # Safety
+    /// Setting this flag incorrectly will cause algorithms to behave
+    /// unpredictably, potentially causing UB.
+    pub unsafe fn set_sorted(&mut self, sorted: bool) {
+        for elements in self.iter_mut() {
+            elements.set_sorted(sorted);
+        }
+    }
+
+    /// Sorts all of the superelements and subelements by index. Only the ranks
+    /// that have been modified since they were last sorted are sorted again.
     pub fn element_sort(&mut self) {
-        self.for_each_element_mut(Element::sort)
+        for elements in self.iter_mut() {
+            elements.element_sort();
+        }
     }
 }
 
@@ -604,6 +798,13 @@ impl AbstractBuilder {
     pub unsafe fn build(self) -> Abstract {
         Abstract::from_ranks(self.0)
     }
+
+    /// Returns the ranks built so far, consuming the builder in the process.
+    /// Unlike with [`build`](Self::build), these don't need to form a valid
+    /// polytope.
+    pub fn into_ranks(self) -> Ranks {
+        self.0
+    }
 }
 
 impl Extend<Subelements> for AbstractBuilder {
@@ -647,12 +848,26 @@ impl ElementHash {
     /// representing a particular element (as a polytope). If the element
     /// doesn't exist, we return `None`.
     pub fn new(poly: &Abstract, rank: usize, idx: usize) -> Option<Self> {
-        poly.get_element(rank, idx)?;
+        Self::from_elements(poly, rank, &[idx])
+    }
+
+    /// Returns a map from elements on a polytope to elements on a new polytope
+    /// whose facets are a given set of elements of the same rank. If any of
+    /// the elements doesn't exist, or if no elements are given, we return
+    /// `None`.
+    pub fn from_elements(poly: &Abstract, rank: usize, idxs: &[usize]) -> Option<Self> {
+        if idxs.is_empty() {
+            return None;
+        }
 
         // A vector of HashMaps. The k-th entry is a map from k-elements of the
         // original polytope into k-elements in a new polytope.
         let mut hashes: Vec<_> = iter::repeat_with(HashMap::new).take(rank + 1).collect();
-        hashes[rank].insert(idx, 0);
+        for &idx in idxs {
+            poly.get_element(rank, idx)?;
+            let len = hashes[rank].len();
+            hashes[rank].entry(idx).or_insert(len);
+        }
 
         // Gets subindices of subindices, until reaching the vertices.
         for r in (1..=rank).rev() {
@@ -682,7 +897,7 @@ impl ElementHash {
 
     /// Gets the indices of the elements of a given rank in the original
     /// polytope.
-    fn to_elements(&self, rank: usize) -> Vec<usize> {
+    pub fn to_elements(&self, rank: usize) -> Vec<usize> {
         if let Some(elements) = self.get(rank) {
             let mut new_elements = Vec::new();
             new_elements.resize(elements.len(), 0);
@@ -697,12 +912,20 @@ impl ElementHash {
         }
     }
 
+    /// Gets the index in the new polytope of the element with a given rank and
+    /// index in the original polytope, if it's in the map.
+    pub fn index(&self, rank: usize, idx: usize) -> Option<usize> {
+        self.get(rank)?.get(&idx).copied()
+    }
+
     /// Gets the indices of the vertices in the original polytope.
     pub fn to_vertices(&self) -> Vec<usize> {
         self.to_elements(1)
     }
 
-    /// Gets the indices of the vertices of a given element in a polytope.
+    /// Gets the polytope made out of the recursive subelements in the map. If
+    /// the map was built from more than one element, a maximal element is
+    /// added on top of them.
     pub fn to_polytope(&self, poly: &Abstract) -> Abstract {
         // TODO: use an AbstractBuilder instead, probably.
         let rank = self.rank();
@@ -749,6 +972,16 @@ impl ElementHash {
             abs.push(elements);
         }
 
+        // Adds a maximal element if needed.
+        let facet_count = self.0[rank].len();
+        if facet_count > 1 {
+            for facet in abs[rank].iter_mut() {
+                facet.sups.push(0);
+            }
+
+            abs.push(ElementList::max(facet_count));
+        }
+
         // Safety: TODO document
         unsafe { Abstract::from_ranks(abs) }
     }
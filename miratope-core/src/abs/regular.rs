@@ -0,0 +1,681 @@
+//! Builds regular abstract polytopes from presentations of their automorphism
+//! groups.
+//!
+//! The automorphism group of a regular polytope is a string C-group: it's
+//! generated by involutions `ρ₀, ρ₁, …, ρₙ₋₁`, where `ρᵢ` and `ρⱼ` commute
+//! whenever `i` and `j` aren't consecutive, and that satisfy a certain
+//! intersection property. Conversely, every string C-group is the automorphism
+//! group of a regular polytope, whose flags correspond to the elements of the
+//! group.
+//!
+//! We read such a group as a Schläfli symbol, which gives the quotient of a
+//! Coxeter group, followed by any extra relations. We then find the elements
+//! of the group by [coset enumeration](https://en.wikipedia.org/wiki/Todd%E2%80%93Coxeter_algorithm),
+//! and build the polytope from its flag graph.
+
+use std::{collections::VecDeque, fmt::Display, iter::Peekable, str::CharIndices};
+
+use super::Abstract;
+
+/// The default maximum number of cosets that we define while enumerating the
+/// elements of a group.
+pub const MAX_COSETS: usize = 1 << 22;
+
+/// The maximum length of a relator, after raising it to its exponent. Coset
+/// enumeration scans every relator from every coset, so its running time grows
+/// with the square of this length even for groups as simple as the dihedral
+/// ones.
+pub const MAX_RELATOR_LEN: usize = 1 << 12;
+
+/// Marks an entry of a coset table that hasn't been set yet.
+const UNSET: usize = usize::MAX;
+
+/// The result of an operation involving string C-groups.
+pub type CGroupResult<T> = Result<T, CGroupError>;
+
+/// Represents an error while parsing a string C-group, or while building its
+/// polytope.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CGroupError {
+    /// The presentation ended unexpectedly.
+    UnexpectedEnding {
+        /// The position at which the reader found the error.
+        pos: usize,
+    },
+
+    /// An invalid symbol was found.
+    InvalidSymbol {
+        /// The position at which the reader found the error.
+        pos: usize,
+    },
+
+    /// An entry of the Schläfli symbol was less than 2, or so large that its
+    /// relator would be longer than [`MAX_RELATOR_LEN`].
+    InvalidEntry {
+        /// The position at which the reader found the error.
+        pos: usize,
+    },
+
+    /// A relation was empty, or was raised to the power 0.
+    EmptyRelation {
+        /// The position at which the reader found the error.
+        pos: usize,
+    },
+
+    /// A relation was longer than [`MAX_RELATOR_LEN`] after being raised to
+    /// its power.
+    RelationTooLong {
+        /// The position at which the reader found the error.
+        pos: usize,
+    },
+
+    /// A relation used a generator that doesn't exist.
+    InvalidGenerator {
+        /// The index of the generator.
+        generator: usize,
+
+        /// The position at which the reader found the error.
+        pos: usize,
+    },
+
+    /// The coset enumeration needed more cosets than allowed. This usually
+    /// means that the group is infinite.
+    TooManyCosets {
+        /// The maximum number of cosets.
+        max: usize,
+    },
+
+    /// The group isn't the automorphism group of a polytope.
+    NotPolytopal,
+}
+
+impl Display for CGroupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            // The presentation ended unexpectedly.
+            Self::UnexpectedEnding { pos } => {
+                write!(f, "presentation ended unexpectedly at position {}", pos)
+            }
+
+            // An invalid symbol was found.
+            Self::InvalidSymbol { pos } => write!(f, "invalid symbol found at position {}", pos),
+
+            // An entry of the Schläfli symbol was less than 2, or too large.
+            Self::InvalidEntry { pos } => {
+                write!(f, "invalid Schläfli symbol entry at position {}", pos)
+            }
+
+            // A relation was empty.
+            Self::EmptyRelation { pos } => write!(f, "empty relation at position {}", pos),
+
+            // A relation was too long.
+            Self::RelationTooLong { pos } => write!(
+                f,
+                "relation at position {} is longer than {} generators",
+                pos, MAX_RELATOR_LEN
+            ),
+
+            // A relation used a generator that doesn't exist.
+            Self::InvalidGenerator { generator, pos } => {
+                write!(f, "invalid generator {} at position {}", generator, pos)
+            }
+
+            // The coset enumeration needed more cosets than allowed.
+            Self::TooManyCosets { max } => {
+                write!(f, "coset enumeration exceeded {} cosets", max)
+            }
+
+            // The group isn't the automorphism group of a polytope.
+            Self::NotPolytopal => write!(f, "the group is not a string C-group"),
+        }
+    }
+}
+
+impl std::error::Error for CGroupError {}
+
+/// A coset table for the trivial subgroup of a group generated by involutions,
+/// built by the Hasse–Lewis–Todd–Coxeter strategy. Every coset stands for an
+/// element of the group.
+struct CosetTable {
+    /// The coset obtained by multiplying every coset by every generator.
+    table: Vec<Vec<usize>>,
+
+    /// The coset that every coset has been identified with, or itself if it's
+    /// still live.
+    parent: Vec<usize>,
+
+    /// The cosets that have been identified with others, but whose entries
+    /// haven't yet been moved.
+    queue: VecDeque<usize>,
+
+    /// The maximum number of cosets we may define.
+    max: usize,
+}
+
+impl CosetTable {
+    /// Initializes a coset table with a single coset.
+    fn new(gens: usize, max: usize) -> Self {
+        Self {
+            table: vec![vec![UNSET; gens]],
+            parent: vec![0],
+            queue: VecDeque::new(),
+            max,
+        }
+    }
+
+    /// The number of generators.
+    fn gens(&self) -> usize {
+        self.table[0].len()
+    }
+
+    /// Returns whether a coset hasn't been identified with another.
+    fn is_live(&self, c: usize) -> bool {
+        self.parent[c] == c
+    }
+
+    /// Finds the live coset that a coset has been identified with.
+    fn rep(&mut self, c: usize) -> usize {
+        let mut r = c;
+        while self.parent[r] != r {
+            r = self.parent[r];
+        }
+
+        // Path compression.
+        let mut c = c;
+        while self.parent[c] != r {
+            let next = self.parent[c];
+            self.parent[c] = r;
+            c = next;
+        }
+
+        r
+    }
+
+    /// Defines a new coset as the product of a coset and a generator.
+    fn define(&mut self, c: usize, g: usize) -> CGroupResult<()> {
+        let d = self.table.len();
+        if d == self.max {
+            return Err(CGroupError::TooManyCosets { max: self.max });
+        }
+
+        self.table.push(vec![UNSET; self.gens()]);
+        self.parent.push(d);
+        self.table[c][g] = d;
+        self.table[d][g] = c;
+        Ok(())
+    }
+
+    /// Identifies two cosets, keeping the smaller one.
+    fn merge(&mut self, a: usize, b: usize) {
+        let a = self.rep(a);
+        let b = self.rep(b);
+
+        if a != b {
+            let (a, b) = (a.min(b), a.max(b));
+            self.parent[b] = a;
+            self.queue.push_back(b);
+        }
+    }
+
+    /// Identifies two cosets, along with every other pair of cosets this
+    /// forces to be equal.
+    fn coincidence(&mut self, a: usize, b: usize) {
+        self.merge(a, b);
+
+        while let Some(e) = self.queue.pop_front() {
+            for g in 0..self.gens() {
+                let f = self.table[e][g];
+                if f == UNSET {
+                    continue;
+                }
+
+                self.table[f][g] = UNSET;
+                let e1 = self.rep(e);
+                let f1 = self.rep(f);
+
+                if self.table[e1][g] != UNSET {
+                    let t = self.table[e1][g];
+                    self.merge(f1, t);
+                } else if self.table[f1][g] != UNSET {
+                    let t = self.table[f1][g];
+                    self.merge(e1, t);
+                } else {
+                    self.table[e1][g] = f1;
+                    self.table[f1][g] = e1;
+                }
+            }
+        }
+    }
+
+    /// Traces a relator from a coset from both ends, defining new cosets until
+    /// it closes up, and deducing any entries or coincidences.
+    fn scan_and_fill(&mut self, c: usize, word: &[usize]) -> CGroupResult<()> {
+        let (mut f, mut b) = (c, c);
+        let (mut i, mut j) = (0, word.len());
+
+        loop {
+            // Scans forwards.
+            while i < j && self.table[f][word[i]] != UNSET {
+                f = self.table[f][word[i]];
+                i += 1;
+            }
+
+            if i == j {
+                if f != b {
+                    self.coincidence(f, b);
+                }
+                return Ok(());
+            }
+
+            // Scans backwards. Every generator is its own inverse.
+            while j > i && self.table[b][word[j - 1]] != UNSET {
+                b = self.table[b][word[j - 1]];
+                j -= 1;
+            }
+
+            if j == i {
+                self.coincidence(f, b);
+                return Ok(());
+            } else if j == i + 1 {
+                self.table[f][word[i]] = b;
+                self.table[b][word[i]] = f;
+                return Ok(());
+            } else {
+                self.define(f, word[i])?;
+            }
+        }
+    }
+
+    /// Runs the coset enumeration, and returns the action of every generator
+    /// on the live cosets, indexed from `0` with the identity first.
+    fn enumerate(mut self, relators: &[Vec<usize>]) -> CGroupResult<Vec<Vec<usize>>> {
+        let mut c = 0;
+        while c < self.table.len() {
+            for relator in relators {
+                if !self.is_live(c) {
+                    break;
+                }
+                self.scan_and_fill(c, relator)?;
+            }
+
+            if self.is_live(c) {
+                for g in 0..self.gens() {
+                    if self.table[c][g] == UNSET {
+                        self.define(c, g)?;
+                    }
+                }
+            }
+
+            c += 1;
+        }
+
+        // Relabels the live cosets.
+        let mut index = vec![UNSET; self.table.len()];
+        let mut count = 0;
+        for (c, idx) in index.iter_mut().enumerate() {
+            if self.parent[c] == c {
+                *idx = count;
+                count += 1;
+            }
+        }
+
+        let mut action = Vec::with_capacity(count);
+        for c in 0..self.table.len() {
+            if self.is_live(c) {
+                let row = (0..self.gens())
+                    .map(|g| {
+                        let d = self.table[c][g];
+                        index[self.rep(d)]
+                    })
+                    .collect();
+                action.push(row);
+            }
+        }
+
+        Ok(action)
+    }
+}
+
+/// A presentation of a string C-group, given by a Schläfli symbol along with
+/// any number of extra relations between its generators.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StringCGroup {
+    /// The Schläfli symbol. Its `i`-th entry is the order of `ρᵢρᵢ₊₁`.
+    schlafli: Vec<usize>,
+
+    /// Other words in the generators that equal the identity.
+    relators: Vec<Vec<usize>>,
+}
+
+impl StringCGroup {
+    /// Initializes the presentation of the Coxeter group with a given
+    /// Schläfli symbol.
+    pub fn new(schlafli: Vec<usize>) -> Self {
+        Self {
+            schlafli,
+            relators: Vec::new(),
+        }
+    }
+
+    /// Returns the Schläfli symbol of the group.
+    pub fn schlafli(&self) -> &[usize] {
+        &self.schlafli
+    }
+
+    /// Returns the number of generators of the group. This is one less than
+    /// the rank of its polytope.
+    pub fn generator_count(&self) -> usize {
+        self.schlafli.len() + 1
+    }
+
+    /// Adds the relation that a word in the generators, raised to a given
+    /// power, equals the identity.
+    ///
+    /// Returns an error if the word uses a generator that doesn't exist, if
+    /// the relation is empty, or if it's longer than [`MAX_RELATOR_LEN`]. The
+    /// positions in these errors are those of the offending generators in the
+    /// word, or 0 if the error concerns the whole relation.
+    pub fn add_relation(&mut self, word: &[usize], exp: usize) -> CGroupResult<()> {
+        self.push_relation(word, exp, 0)
+    }
+
+    /// Adds a relation, which was read at a given position.
+    fn push_relation(&mut self, word: &[usize], exp: usize, pos: usize) -> CGroupResult<()> {
+        let gens = self.generator_count();
+        if let Some((idx, &generator)) = word.iter().enumerate().find(|&(_, &g)| g >= gens) {
+            return Err(CGroupError::InvalidGenerator {
+                generator,
+                pos: pos + idx,
+            });
+        }
+
+        if word.is_empty() || exp == 0 {
+            return Err(CGroupError::EmptyRelation { pos });
+        }
+
+        match word.len().checked_mul(exp) {
+            Some(len) if len <= MAX_RELATOR_LEN => {
+                self.relators.push(word.repeat(exp));
+                Ok(())
+            }
+            _ => Err(CGroupError::RelationTooLong { pos }),
+        }
+    }
+
+    /// Parses a presentation such as `{3,5,3} (012)^5 (123)^5`. This consists
+    /// of a Schläfli symbol, followed by any number of relations. Each of them
+    /// is a word in the generators, written as the digits of their indices,
+    /// optionally in parentheses and raised to a power.
+    pub fn parse(input: &str) -> CGroupResult<Self> {
+        let mut chars = input.char_indices().peekable();
+        let len = input.len();
+
+        // Reads the Schläfli symbol.
+        skip_whitespace(&mut chars);
+        match chars.next() {
+            Some((_, '{')) => {}
+            Some((pos, _)) => return Err(CGroupError::InvalidSymbol { pos }),
+            None => return Err(CGroupError::UnexpectedEnding { pos: len }),
+        }
+
+        let mut schlafli = Vec::new();
+        skip_whitespace(&mut chars);
+        if let Some(&(_, '}')) = chars.peek() {
+            chars.next();
+        } else {
+            loop {
+                skip_whitespace(&mut chars);
+                let pos = chars.peek().map_or(len, |&(pos, _)| pos);
+                let entry = read_number(&mut chars, len)?;
+                if !(2..=MAX_RELATOR_LEN / 2).contains(&entry) {
+                    return Err(CGroupError::InvalidEntry { pos });
+                }
+                schlafli.push(entry);
+
+                skip_whitespace(&mut chars);
+                match chars.next() {
+                    Some((_, ',')) => {}
+                    Some((_, '}')) => break,
+                    Some((pos, _)) => return Err(CGroupError::InvalidSymbol { pos }),
+                    None => return Err(CGroupError::UnexpectedEnding { pos: len }),
+                }
+            }
+        }
+
+        let mut group = Self::new(schlafli);
+        let gens = group.generator_count();
+
+        // Reads the relations.
+        loop {
+            skip_whitespace(&mut chars);
+            let mut word = Vec::new();
+
+            let pos = match chars.peek() {
+                Some(&(pos, '(')) => {
+                    chars.next();
+                    loop {
+                        match chars.next() {
+                            Some((_, ')')) => break,
+                            Some((_, c)) if c.is_whitespace() => {}
+                            Some((pos, c)) => word.push(read_generator(c, pos, gens)?),
+                            None => return Err(CGroupError::UnexpectedEnding { pos: len }),
+                        }
+                    }
+                    pos
+                }
+                Some(&(pos, _)) => {
+                    while let Some(&(pos, c)) = chars.peek() {
+                        if c.is_whitespace() || c == '^' {
+                            break;
+                        }
+                        word.push(read_generator(c, pos, gens)?);
+                        chars.next();
+                    }
+                    pos
+                }
+                None => break,
+            };
+
+            let exp = if let Some(&(_, '^')) = chars.peek() {
+                chars.next();
+                read_number(&mut chars, len)?
+            } else {
+                1
+            };
+
+            group.push_relation(&word, exp, pos)?;
+        }
+
+        Ok(group)
+    }
+
+    /// Returns every relator of the group, including those of its Coxeter
+    /// group.
+    fn relators(&self) -> Vec<Vec<usize>> {
+        let gens = self.generator_count();
+        let mut relators = Vec::new();
+
+        for (i, &p) in self.schlafli.iter().enumerate() {
+            relators.push([i, i + 1].repeat(p));
+        }
+
+        for i in 0..gens {
+            for j in (i + 2)..gens {
+                relators.push(vec![i, j, i, j]);
+            }
+        }
+
+        relators.extend(self.relators.iter().cloned());
+        relators
+    }
+
+    /// Finds every element of the group, using at most a given number of
+    /// cosets. Returns a list with an entry for every element, the first of
+    /// which is the identity, that says which element we get when we multiply
+    /// it by each generator on the right.
+    pub fn elements(&self, max_cosets: usize) -> CGroupResult<Vec<Vec<usize>>> {
+        CosetTable::new(self.generator_count(), max_cosets).enumerate(&self.relators())
+    }
+
+    /// Returns the order of the group.
+    pub fn order(&self) -> CGroupResult<usize> {
+        Ok(self.elements(MAX_COSETS)?.len())
+    }
+
+    /// Builds the regular polytope whose automorphism group is this group.
+    pub fn polytope(&self) -> CGroupResult<Abstract> {
+        // The flags of the polytope are the elements of the group, and the
+        // flag changes are the multiplications by the generators.
+        let flag_graph = self.elements(MAX_COSETS)?;
+        Abstract::from_flag_graph(&flag_graph).ok_or(CGroupError::NotPolytopal)
+    }
+}
+
+/// Skips any whitespace in a presentation.
+fn skip_whitespace(chars: &mut Peekable<CharIndices<'_>>) {
+    while let Some(&(_, c)) = chars.peek() {
+        if !c.is_whitespace() {
+            break;
+        }
+        chars.next();
+    }
+}
+
+/// Reads a positive integer in a presentation.
+fn read_number(chars: &mut Peekable<CharIndices<'_>>, len: usize) -> CGroupResult<usize> {
+    let mut number: Option<usize> = None;
+
+    while let Some(&(pos, c)) = chars.peek() {
+        match c.to_digit(10) {
+            Some(d) => {
+                number = number
+                    .unwrap_or(0)
+                    .checked_mul(10)
+                    .and_then(|n| n.checked_add(d as usize));
+                if number.is_none() {
+                    return Err(CGroupError::InvalidSymbol { pos });
+                }
+                chars.next();
+            }
+            None if number.is_none() => return Err(CGroupError::InvalidSymbol { pos }),
+            None => break,
+        }
+    }
+
+    number.ok_or(CGroupError::UnexpectedEnding { pos: len })
+}
+
+/// Reads the index of a generator in a relation.
+fn read_generator(c: char, pos: usize, gens: usize) -> CGroupResult<usize> {
+    let generator = c.to_digit(10).ok_or(CGroupError::InvalidSymbol { pos })? as usize;
+
+    if generator < gens {
+        Ok(generator)
+    } else {
+        Err(CGroupError::InvalidGenerator { generator, pos })
+    }
+}
+
+impl Abstract {
+    /// Builds the regular polytope with a given string C-group as its
+    /// automorphism group. See [`StringCGroup::parse`] for the format of the
+    /// presentation.
+    pub fn from_c_group(input: &str) -> CGroupResult<Self> {
+        StringCGroup::parse(input)?.polytope()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+
+    /// Checks the polytopes built from Coxeter groups.
+    #[test]
+    fn coxeter() {
+        test(&Abstract::from_c_group("{}").unwrap(), [1, 2, 1]);
+        test(&Abstract::from_c_group("{5}").unwrap(), [1, 5, 5, 1]);
+        test(&Abstract::from_c_group("{4, 3}").unwrap(), [1, 8, 12, 6, 1]);
+        test(
+            &Abstract::from_c_group("{3,3,3}").unwrap(),
+            [1, 5, 10, 10, 5, 1],
+        );
+    }
+
+    /// Checks some quotients of Coxeter groups.
+    #[test]
+    fn quotients() {
+        test(
+            &Abstract::from_c_group("{4,3} (012)^3").unwrap(),
+            [1, 4, 6, 3, 1],
+        );
+        test(
+            &Abstract::from_c_group("{4,4} (0121)^3").unwrap(),
+            [1, 9, 18, 9, 1],
+        );
+    }
+
+    /// Checks the 11-cell and the 57-cell.
+    #[test]
+    fn cells() {
+        let eleven_cell = StringCGroup::parse("{3,5,3} (012)^5 (123)^5").unwrap();
+        assert_eq!(eleven_cell.order(), Ok(660));
+        test(&eleven_cell.polytope().unwrap(), [1, 11, 55, 55, 11, 1]);
+
+        test(
+            &Abstract::from_c_group("{5,3,5} (012)^5 (123)^5").unwrap(),
+            [1, 57, 171, 171, 57, 1],
+        );
+    }
+
+    /// Checks that invalid presentations are rejected.
+    #[test]
+    fn errors() {
+        assert_eq!(
+            StringCGroup::parse("{4,3"),
+            Err(CGroupError::UnexpectedEnding { pos: 4 })
+        );
+        assert_eq!(
+            StringCGroup::parse("{1,3}"),
+            Err(CGroupError::InvalidEntry { pos: 1 })
+        );
+        assert_eq!(
+            StringCGroup::parse("{4,3} (013)^2"),
+            Err(CGroupError::InvalidGenerator {
+                generator: 3,
+                pos: 9
+            })
+        );
+        assert_eq!(
+            StringCGroup::parse("{4,3} 01^99999999999"),
+            Err(CGroupError::RelationTooLong { pos: 6 })
+        );
+        assert_eq!(
+            StringCGroup::parse("{99999}"),
+            Err(CGroupError::InvalidEntry { pos: 1 })
+        );
+        assert_eq!(
+            StringCGroup::parse("{4,3} (01)^0"),
+            Err(CGroupError::EmptyRelation { pos: 6 })
+        );
+        assert_eq!(
+            StringCGroup::parse("{4,3} ()^2"),
+            Err(CGroupError::EmptyRelation { pos: 6 })
+        );
+
+        let mut group = StringCGroup::new(vec![4, 3]);
+        assert_eq!(
+            group.add_relation(&[0, 1, 3], 2),
+            Err(CGroupError::InvalidGenerator {
+                generator: 3,
+                pos: 2
+            })
+        );
+        assert_eq!(group.add_relation(&[0, 1, 2], 3), Ok(()));
+
+        assert_eq!(
+            Abstract::from_c_group("{4,3} 1").unwrap_err(),
+            CGroupError::NotPolytopal
+        );
+    }
+}
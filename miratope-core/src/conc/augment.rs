@@ -0,0 +1,697 @@
+//! Contains the operations that attach polytopes onto the facets of others,
+//! like the elongations used to build many of the Johnson solids.
+
+use std::collections::{HashMap, HashSet};
+
+use super::{cycle::CycleList, Concrete, ConcretePolytope};
+use crate::{
+    abs::{
+        ranked::{AbstractBuilder, SubelementList, Subelements},
+        Abstract, ElementHash, Ranked,
+    },
+    float::Float,
+    geometry::{Point, Subspace, Vector},
+    Polytope,
+};
+
+use vec_like::*;
+
+/// Returns the sorted vertex indices of every element of a polytope. The entry
+/// `sets[r][i]` contains the vertices of the element of rank `r` and index `i`.
+/// The minimal element is given no vertices.
+fn vertex_sets(abs: &Abstract) -> Vec<Vec<Vec<usize>>> {
+    let mut sets = Vec::with_capacity(abs.rank() + 1);
+    sets.push(vec![Vec::new()]);
+
+    if abs.rank() >= 1 {
+        sets.push((0..abs.vertex_count()).map(|v| vec![v]).collect());
+    }
+
+    for r in 2..=abs.rank() {
+        let prev: &Vec<Vec<usize>> = &sets[r - 1];
+        let new = abs[r]
+            .iter()
+            .map(|el| {
+                let set: HashSet<_> = el
+                    .subs
+                    .iter()
+                    .flat_map(|&s| prev[s].iter().copied())
+                    .collect();
+                let mut set: Vec<_> = set.into_iter().collect();
+                set.sort_unstable();
+                set
+            })
+            .collect();
+
+        sets.push(new);
+    }
+
+    sets
+}
+
+/// The polytopes that [`Concrete::augment_cap`] can attach onto a polygon.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cap {
+    /// A cupola, whose base has twice as many sides as its top.
+    Cupola,
+
+    /// A rotunda, whose base has twice as many sides as its top.
+    Rotunda,
+}
+
+impl Concrete {
+    /// Returns the index of the facet with the most vertices, which we consider
+    /// the base of the polytope, or `None` if there's no single such facet.
+    pub fn base_facet(&self) -> Option<usize> {
+        let rank = self.rank();
+        if rank < 2 {
+            return None;
+        }
+
+        let counts: Vec<_> = (0..self.facet_count())
+            .map(|idx| {
+                self.abs
+                    .element_vertices(rank - 1, idx)
+                    .map_or(0, |v| v.len())
+            })
+            .collect();
+        let max = *counts.iter().max()?;
+
+        let mut maxima = counts
+            .iter()
+            .enumerate()
+            .filter(|&(_, &count)| count == max);
+        let (idx, _) = maxima.next()?;
+        if maxima.next().is_none() {
+            Some(idx)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the outward unit normal of a facet, or `None` if the polytope
+    /// isn't full-dimensional.
+    fn facet_normal(&self, facet: usize) -> Option<Vector<f64>> {
+        let rank = self.rank();
+        if rank < 2 || self.dim() != Some(rank - 1) {
+            return None;
+        }
+
+        let vertices = self.element_vertices_ref(rank - 1, facet)?;
+        let subspace = Subspace::from_points(vertices.into_iter());
+        if !subspace.is_hyperplane() {
+            return None;
+        }
+
+        // The normal pointing towards the center points inwards.
+        let gravicenter = self.gravicenter()?;
+        subspace.normal(&gravicenter).map(|n| -n)
+    }
+
+    /// Glues a polytope onto a facet of this one. The other polytope must have
+    /// a facet whose vertices coincide with those of the given facet. Both of
+    /// these facets are removed, and any other elements they have in common
+    /// are identified.
+    ///
+    /// Returns `None` if the polytopes don't have the same rank, or if there's
+    /// no matching facet in the other polytope.
+    pub fn glue(&self, facet: usize, cap: &Self) -> Option<Self> {
+        let rank = self.rank();
+        if rank < 3 || cap.rank() != rank || facet >= self.facet_count() {
+            return None;
+        }
+
+        let self_sets = vertex_sets(&self.abs);
+        let cap_sets = vertex_sets(&cap.abs);
+        let facet_vertices = &self_sets[rank - 1][facet];
+
+        // Finds the vertices of the cap that lie on the facet.
+        let vertex_map: Vec<Option<usize>> = cap
+            .vertices
+            .iter()
+            .map(|v| {
+                facet_vertices
+                    .iter()
+                    .copied()
+                    .find(|&w| (v - &self.vertices[w]).norm() < f64::eps())
+            })
+            .collect();
+
+        // Maps a set of vertices of the cap into a sorted set of vertices of
+        // the polytope, if they all lie on the facet.
+        let map_set = |set: &[usize]| -> Option<Vec<usize>> {
+            let mut set: Vec<_> = set.iter().map(|&v| vertex_map[v]).collect::<Option<_>>()?;
+            set.sort_unstable();
+            Some(set)
+        };
+
+        let cap_facet = (0..cap.facet_count())
+            .find(|&idx| map_set(&cap_sets[rank - 1][idx]).as_ref() == Some(facet_vertices))?;
+        let facet_hash = ElementHash::new(&self.abs, rank - 1, facet)?;
+        let cap_facet_hash = ElementHash::new(&cap.abs, rank - 1, cap_facet)?;
+
+        // Maps every vertex of the cap to a vertex of the new polytope.
+        let mut vertices = self.vertices.clone();
+        let mut maps = vec![Vec::new(); rank];
+        maps[1] = vertex_map
+            .iter()
+            .enumerate()
+            .map(|(idx, &v)| {
+                v.unwrap_or_else(|| {
+                    vertices.push(cap.vertices[idx].clone());
+                    vertices.len() - 1
+                })
+            })
+            .collect();
+
+        // Maps every other element of the cap to an element of the new
+        // polytope, by matching vertex sets on the glued facets. The glued
+        // facets themselves are left out.
+        for r in 2..rank - 1 {
+            let shared: HashMap<_, _> = facet_hash
+                .to_elements(r)
+                .into_iter()
+                .map(|idx| (self_sets[r][idx].clone(), idx))
+                .collect();
+            let cap_shared: HashSet<_> = cap_facet_hash.to_elements(r).into_iter().collect();
+            let mut count = self.el_count(r);
+
+            for (idx, set) in cap_sets[r].iter().enumerate() {
+                let new_idx = if cap_shared.contains(&idx) {
+                    *shared.get(&map_set(set)?)?
+                } else {
+                    count += 1;
+                    count - 1
+                };
+
+                maps[r].push(new_idx);
+            }
+        }
+
+        let mut builder = AbstractBuilder::with_rank_capacity(rank);
+        builder.push_min();
+        builder.push_vertices(vertices.len());
+
+        for r in 2..rank {
+            let mut list = SubelementList::new();
+
+            for (idx, el) in self.abs[r].iter().enumerate() {
+                if r != rank - 1 || idx != facet {
+                    list.push(el.subs.clone());
+                }
+            }
+
+            let cap_shared: HashSet<_> = cap_facet_hash.to_elements(r).into_iter().collect();
+            for (idx, el) in cap.abs[r].iter().enumerate() {
+                if !cap_shared.contains(&idx) {
+                    list.push(
+                        el.subs
+                            .iter()
+                            .map(|&s| maps[r - 1][s])
+                            .collect::<Subelements>(),
+                    );
+                }
+            }
+
+            builder.push(list);
+        }
+
+        builder.push_max();
+
+        // Safety: gluing two polytopes along a common facet gives a valid
+        // polytope.
+        Some(Self::new(vertices, unsafe { builder.build() }))
+    }
+
+    /// Elongates a polytope by attaching a prism onto one of its facets. The
+    /// height of the prism equals the length of the facet's first edge, which
+    /// is what makes the result uniform in the case of Johnson solids.
+    ///
+    /// Returns `None` if the polytope isn't full-dimensional, or if the facet
+    /// doesn't exist.
+    pub fn elongate(&self, facet: usize) -> Option<Self> {
+        let rank = self.rank();
+        let normal = self.facet_normal(facet)?;
+        let base = self.element(rank - 1, facet)?;
+        let height = base.edge_len(0)?;
+
+        // The vertices of a prism product are ordered as pairs, the second
+        // entry being the vertex of the dyad.
+        let vertices = base
+            .vertices
+            .iter()
+            .flat_map(|v| vec![v.clone(), v + &normal * height])
+            .collect();
+        let prism = Self::new(vertices, base.abs.prism());
+
+        self.glue(facet, &prism)
+    }
+
+    /// Gyroelongates a polytope by attaching an antiprism onto one of its
+    /// facets. The dual base of the antiprism is placed so that it has the same
+    /// circumradius as the facet, and at the height that makes the lateral
+    /// edges as long as the facet's first edge.
+    ///
+    /// Returns `None` if the polytope isn't full-dimensional, if the facet
+    /// doesn't exist, or if the lateral edges can't have the required length.
+    pub fn gyroelongate(&self, facet: usize) -> Option<Self> {
+        let rank = self.rank();
+        let normal = self.facet_normal(facet)?;
+        let base = self.element(rank - 1, facet)?;
+        let edge_len = base.edge_len(0)?;
+        let center = base.gravicenter()?;
+        let radius = (&base.vertices[0] - &center).norm();
+
+        // Every ridge of the polytope on the base gives a vertex of the dual
+        // base, in the direction of its center.
+        let mut dual_vertices = Vec::with_capacity(base.facet_count());
+        for idx in 0..base.facet_count() {
+            let ridge = base.element_vertices_ref(rank - 2, idx)?;
+            let ridge_center = ridge
+                .iter()
+                .fold(Point::zeros(center.len()), |acc, &v| acc + v)
+                / ridge.len() as f64;
+
+            let dir = (ridge_center - &center).try_normalize(f64::eps())?;
+            dual_vertices.push(&center + dir * radius);
+        }
+
+        // Finds the height at which the lateral edges have the right length.
+        let (abs, base_idxs, dual_idxs) = base.abs.antiprism_and_vertices();
+        let lateral = abs[2].iter().find_map(|edge| {
+            let (v, w) = (edge.subs[0], edge.subs[1]);
+            let v_pos = base_idxs.iter().position(|&idx| idx == v);
+            let w_pos = dual_idxs.iter().position(|&idx| idx == w);
+            v_pos.zip(w_pos).or_else(|| {
+                base_idxs
+                    .iter()
+                    .position(|&idx| idx == w)
+                    .zip(dual_idxs.iter().position(|&idx| idx == v))
+            })
+        })?;
+
+        let offset = (&base.vertices[lateral.0] - &dual_vertices[lateral.1]).norm();
+        let squared_height = edge_len * edge_len - offset * offset;
+        if squared_height < 0.0 {
+            return None;
+        }
+
+        let shift = normal * squared_height.fsqrt();
+        let antiprism = base.antiprism_with_vertices(
+            base.vertices.iter().cloned(),
+            dual_vertices.into_iter().map(|v| v + &shift),
+        );
+
+        self.glue(facet, &antiprism)
+    }
+
+    /// Augments a facet of a polytope by attaching a pyramid onto it, with its
+    /// apex placed so that the lateral edges are as long as the facet's first
+    /// edge.
+    ///
+    /// Returns `None` if the polytope isn't full-dimensional, if the facet
+    /// doesn't exist, or if the lateral edges can't have the required length.
+    pub fn augment(&self, facet: usize) -> Option<Self> {
+        let rank = self.rank();
+        let normal = self.facet_normal(facet)?;
+        let base = self.element(rank - 1, facet)?;
+        let edge_len = base.edge_len(0)?;
+        let center = base.gravicenter()?;
+
+        let radius = (&base.vertices[0] - &center).norm();
+        let squared_height = edge_len * edge_len - radius * radius;
+        if squared_height < 0.0 {
+            return None;
+        }
+
+        // The apex of a pyramid is its last vertex.
+        let mut vertices = base.vertices.clone();
+        vertices.push(center + normal * squared_height.fsqrt());
+        let pyramid = Self::new(vertices, base.abs.pyramid());
+
+        self.glue(facet, &pyramid)
+    }
+
+    /// Augments a polygonal facet with an even number of sides by attaching a
+    /// cupola onto it, with all of its edges as long as the facet's first edge.
+    /// The cupola is placed in the ortho orientation, as explained in
+    /// [`Self::augment_cap`].
+    ///
+    /// Returns `None` if the polytope isn't a full-dimensional polyhedron, if
+    /// the facet isn't a polygon with an even number of sides, or if the
+    /// cupola can't be built.
+    pub fn augment_cupola(&self, facet: usize) -> Option<Self> {
+        self.augment_cap(facet, Cap::Cupola, false)
+    }
+
+    /// Augments a polygonal facet with an even number of sides by attaching a
+    /// cupola or a rotunda onto it, with all of its edges as long as the
+    /// facet's first edge.
+    ///
+    /// Both kinds of caps have triangles on every other edge of their base. In
+    /// the ortho orientation, these are placed on the edges of the facet that
+    /// are closest to a translate of an edge of a triangle along the facet's
+    /// normal. This includes the edges that a triangle is directly attached
+    /// to, as well as those at the other end of the prism of an elongated
+    /// bicupola. In the gyro orientation, they're placed on the other edges.
+    /// If no edge is the translate of an edge of a triangle, the orientation
+    /// is arbitrary.
+    ///
+    /// Returns `None` if the polytope isn't a full-dimensional polyhedron, if
+    /// the facet isn't a polygon with an even number of sides, or if the cap
+    /// can't be built.
+    pub fn augment_cap(&self, facet: usize, cap: Cap, gyro: bool) -> Option<Self> {
+        if self.rank() != 4 {
+            return None;
+        }
+
+        let normal = self.facet_normal(facet)?;
+        let base = self.element(3, facet)?;
+        let edge_len = base.edge_len(0)?;
+        let center = base.gravicenter()?;
+
+        let cycles = CycleList::from_edges(base.abs[2].iter().map(|edge| &edge.subs));
+        if cycles.len() != 1 || cycles[0].len() % 2 != 0 || cycles[0].len() < 6 {
+            return None;
+        }
+
+        // The bottom vertices, in cyclic order.
+        let bottom: Vec<_> = cycles[0]
+            .iter()
+            .map(|&v| base.vertices[v].clone())
+            .collect();
+        let n = bottom.len() / 2;
+        let local = match cap {
+            Cap::Cupola => Self::cupola(n, 1),
+            Cap::Rotunda => Self::rotunda(n, 1),
+        }?;
+
+        // How far an edge of the facet is from the closest translate of an edge
+        // of a triangle along the normal, if there's any.
+        let triangles: Vec<_> = (0..self.facet_count())
+            .filter_map(|idx| self.element_vertices_ref(3, idx))
+            .filter(|tri| tri.len() == 3)
+            .collect();
+        let triangle_distance = |v: &Point<f64>, w: &Point<f64>| {
+            triangles
+                .iter()
+                .flat_map(|tri| (0..3).map(move |i| (tri[i], tri[(i + 1) % 3])))
+                .flat_map(|(a, b)| vec![(a, b), (b, a)])
+                .filter_map(|(a, b)| {
+                    let shift = a - v;
+                    ((&shift - (b - w)).norm() < f64::eps()
+                        && (&shift - &normal * shift.dot(&normal)).norm() < f64::eps())
+                        .then(|| shift.norm())
+                })
+                .fold(None, |min: Option<f64>, d| {
+                    Some(min.map_or(d, |m| m.min(d)))
+                })
+        };
+
+        // The triangles of the cap are on the edges of its base that start at
+        // an odd vertex. We choose which vertex of the facet the first vertex
+        // of the cap goes on accordingly.
+        let odd = match (
+            triangle_distance(&bottom[1], &bottom[2]),
+            triangle_distance(&bottom[0], &bottom[1]),
+        ) {
+            (Some(odd), Some(even)) => Some(odd < even),
+            (Some(_), None) => Some(true),
+            (None, Some(_)) => Some(false),
+            (None, None) => None,
+        };
+        let start = match odd {
+            Some(odd) if odd == gyro => 1,
+            _ => 0,
+        };
+
+        // The first two vertices of the cap's base determine the plane of the
+        // facet.
+        let u = (&bottom[start] - &center).try_normalize(f64::eps())?;
+        let next = &bottom[(start + 1) % (2 * n)] - &center;
+        let w = (&next - &u * next.dot(&u)).try_normalize(f64::eps())?;
+
+        let vertices = local
+            .vertices
+            .iter()
+            .map(|v| &center + (&u * v[0] + &w * v[1] + &normal * v[2]) * edge_len)
+            .collect();
+
+        self.glue(facet, &Self::new(vertices, local.abs))
+    }
+
+    /// Diminishes a polytope by removing a set of vertices, along with every
+    /// element that contains any of them. The resulting hole is closed by a
+    /// single new facet, which is placed last.
+    ///
+    /// Returns `None` if the polytope isn't full-dimensional, if no vertices
+    /// are removed, or if the hole can't be closed by a single flat facet.
+    pub fn diminish(&self, removed: &[usize]) -> Option<Self> {
+        let rank = self.rank();
+        if rank < 3 || self.dim() != Some(rank - 1) {
+            return None;
+        }
+
+        let removed: HashSet<_> = removed.iter().copied().collect();
+        let sets = vertex_sets(&self.abs);
+
+        // Maps the indices of the elements we keep into their new indices.
+        let mut maps = vec![HashMap::new(); rank];
+        for r in 1..rank {
+            for (idx, set) in sets[r].iter().enumerate() {
+                if set.iter().all(|v| !removed.contains(v)) {
+                    let new_idx = maps[r].len();
+                    maps[r].insert(idx, new_idx);
+                }
+            }
+        }
+
+        if maps[1].len() == self.vertex_count() {
+            return None;
+        }
+
+        // The ridges that are now on a single facet close the hole.
+        let mut ridge_counts = HashMap::new();
+        for &idx in maps[rank - 1].keys() {
+            for &sub in &self.abs[(rank - 1, idx)].subs {
+                *ridge_counts.entry(sub).or_insert(0) += 1;
+            }
+        }
+
+        let mut boundary: Vec<_> = ridge_counts
+            .into_iter()
+            .filter(|&(_, count)| count == 1)
+            .map(|(idx, _)| idx)
+            .collect();
+        boundary.sort_unstable();
+
+        let boundary_vertices: HashSet<_> = boundary
+            .iter()
+            .flat_map(|&idx| sets[rank - 2][idx].iter().copied())
+            .collect();
+        let subspace = Subspace::from_points(boundary_vertices.iter().map(|&v| &self.vertices[v]));
+        if boundary.is_empty() || !subspace.is_hyperplane() {
+            return None;
+        }
+
+        let mut vertices = Vec::with_capacity(maps[1].len());
+        for (idx, v) in self.vertices.iter().enumerate() {
+            if maps[1].contains_key(&idx) {
+                vertices.push(v.clone());
+            }
+        }
+
+        let mut builder = AbstractBuilder::with_rank_capacity(rank);
+        builder.push_min();
+        builder.push_vertices(vertices.len());
+
+        for r in 2..rank {
+            let mut list = SubelementList::new();
+
+            for (idx, el) in self.abs[r].iter().enumerate() {
+                if maps[r].contains_key(&idx) {
+                    list.push(
+                        el.subs
+                            .iter()
+                            .map(|s| maps[r - 1][s])
+                            .collect::<Subelements>(),
+                    );
+                }
+            }
+
+            if r == rank - 1 {
+                list.push(
+                    boundary
+                        .iter()
+                        .map(|s| maps[r - 1][s])
+                        .collect::<Subelements>(),
+                );
+            }
+
+            builder.push(list);
+        }
+
+        builder.push_max();
+
+        // The hole might not be closed by a single facet.
+        builder.ranks().is_valid().ok()?;
+
+        // Safety: we just checked that the polytope is valid.
+        Some(Self::new(vertices, unsafe { builder.build() }))
+    }
+
+    /// Gyrates the cap of a polytope with a given set of vertices. The cap is
+    /// cut off as in [`Self::diminish`], rotated about the center of the new
+    /// facet by the smallest angle that maps the facet's vertices into
+    /// themselves, and attached back. This turns the cupolae and rotundae of
+    /// the Johnson solids.
+    ///
+    /// Returns `None` if the cap can't be cut off, or if its base isn't regular
+    /// enough for the rotated cap to fit back.
+    pub fn gyrate(&self, cap: &[usize]) -> Option<Self> {
+        let rank = self.rank();
+        let diminished = self.diminish(cap)?;
+        let facet = diminished.facet_count() - 1;
+        let base = diminished.element(rank - 1, facet)?;
+        let center = base.gravicenter()?;
+
+        // Everything that's neither on the cap nor on its base is cut off, so
+        // that only the cap remains.
+        let cap_set: HashSet<_> = cap.iter().copied().collect();
+        let rest: Vec<_> = (0..self.vertex_count())
+            .filter(|v| {
+                !cap_set.contains(v)
+                    && base
+                        .vertices
+                        .iter()
+                        .all(|w| (&self.vertices[*v] - w).norm() >= f64::eps())
+            })
+            .collect();
+        let mut cap = self.diminish(&rest)?;
+
+        // Finds two orthonormal directions on the plane of rotation.
+        let u = (&base.vertices[0] - &center).try_normalize(f64::eps())?;
+        let w = base.vertices.iter().find_map(|v| {
+            let v = v - &center;
+            (&v - &u * v.dot(&u)).try_normalize(f64::eps())
+        })?;
+
+        let angle = f64::TAU / base.vertex_count() as f64;
+        let (sin, cos) = angle.fsin_cos();
+        for v in cap.vertices.iter_mut() {
+            let p = &*v - &center;
+            let (x, y) = (p.dot(&u), p.dot(&w));
+            *v = &*v + &u * (x * cos - y * sin - x) + &w * (x * sin + y * cos - y);
+        }
+
+        diminished.glue(facet, &cap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the element counts of a polytope.
+    fn counts(poly: &Concrete) -> Vec<usize> {
+        poly.el_count_iter().collect()
+    }
+
+    /// Builds a pyramid with unit edges on a regular polygon. The base of a
+    /// pyramid lies at height 1/2.
+    fn unit_pyramid(n: usize) -> Concrete {
+        let base = Concrete::star_polygon_with_edge(n, 1, 1.0);
+        let radius = base.vertices[0].norm();
+        let height = (1.0 - radius * radius).fsqrt();
+
+        base.pyramid_with(vec![0.0, 0.0, 0.5 + height].into())
+    }
+
+    #[test]
+    fn elongate() {
+        // An elongated square pyramid.
+        let pyramid = unit_pyramid(4);
+        let base = pyramid.base_facet().unwrap();
+        let elongated = pyramid.elongate(base).unwrap();
+        elongated.assert_valid();
+        assert_eq!(counts(&elongated), vec![1, 9, 16, 9, 1]);
+        assert!(elongated.is_equilateral());
+
+        // Elongating a cube on any facet gives a cuboid.
+        let cube = Concrete::hypercube(4);
+        assert_eq!(cube.base_facet(), None);
+        let mut cuboid = cube.elongate(0).unwrap();
+        assert_eq!(counts(&cuboid), vec![1, 12, 20, 10, 1]);
+        cuboid.element_sort();
+        assert!((cuboid.volume().value().unwrap() - 2.0).abs() < f64::eps());
+    }
+
+    /// Returns the index of the first facet of a polytope with a given number
+    /// of vertices.
+    fn facet_with(poly: &Concrete, vertex_count: usize) -> usize {
+        (0..poly.facet_count())
+            .find(|&idx| poly.abs.element_vertices(3, idx).unwrap().len() == vertex_count)
+            .unwrap()
+    }
+
+    #[test]
+    fn augment() {
+        // An augmented triangular prism.
+        let prism = Concrete::uniform_prism(3, 1);
+        let augmented = prism.augment(facet_with(&prism, 4)).unwrap();
+        augmented.assert_valid();
+        assert_eq!(counts(&augmented), vec![1, 7, 13, 8, 1]);
+        assert!(augmented.is_equilateral());
+
+        // An elongated triangular cupola.
+        let prism = Concrete::uniform_prism(6, 1);
+        let augmented = prism.augment_cupola(facet_with(&prism, 6)).unwrap();
+        augmented.assert_valid();
+        assert_eq!(counts(&augmented), vec![1, 15, 27, 14, 1]);
+        assert!(augmented.is_equilateral());
+        assert!(prism.augment_cupola(facet_with(&prism, 4)).is_none());
+    }
+
+    #[test]
+    fn diminish() {
+        // Removing the apex of an augmented prism gives back the prism.
+        let prism = Concrete::uniform_prism(3, 1);
+        let augmented = prism.augment(facet_with(&prism, 4)).unwrap();
+        let diminished = augmented.diminish(&[6]).unwrap();
+        diminished.assert_valid();
+        assert_eq!(counts(&diminished), counts(&prism));
+
+        // The hole left by a vertex of a cube isn't closed by any facet.
+        assert!(Concrete::hypercube(4).diminish(&[0]).is_none());
+    }
+
+    #[test]
+    fn gyrate() {
+        // An elongated triangular bicupola.
+        let prism = Concrete::uniform_prism(6, 1);
+        let hexagon = facet_with(&prism, 6);
+        let cupola = prism.augment_cupola(hexagon).unwrap();
+        let bicupola = cupola.augment_cupola(facet_with(&cupola, 6)).unwrap();
+
+        // Gyrating a cupola keeps the element counts and edge lengths, but
+        // moves its top vertices.
+        let gyrated = bicupola.gyrate(&[15, 16, 17]).unwrap();
+        gyrated.assert_valid();
+        assert_eq!(counts(&gyrated), counts(&bicupola));
+        assert!(gyrated.is_equilateral());
+        assert!(bicupola.vertices[15..]
+            .iter()
+            .any(|v| gyrated.vertices.iter().all(|w| (v - w).norm() > f64::eps())));
+    }
+
+    #[test]
+    fn gyroelongate() {
+        // A gyroelongated pentagonal pyramid.
+        let pyramid = unit_pyramid(5);
+        let base = pyramid.base_facet().unwrap();
+        let gyroelongated = pyramid.gyroelongate(base).unwrap();
+        gyroelongated.assert_valid();
+        assert_eq!(counts(&gyroelongated), vec![1, 11, 25, 16, 1]);
+        assert!(gyroelongated.is_equilateral());
+    }
+}
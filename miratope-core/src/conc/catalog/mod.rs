@@ -0,0 +1,888 @@
+//! Contains the built-in catalogs of the uniform polyhedra, the Johnson
+//! solids, some semiregular polytopes, and some exotic abstract polytopes, so
+//! that these can be loaded without any external files.
+//!
+//! The uniform polyhedra are stored as the OFF files from Miratope's library.
+//! Most Johnson solids are then built from these, or from pyramids, cupolae
+//! and rotundae, through the operations in the [`augment`](super::augment)
+//! module. The few that can't be built this way are stored as OFF files.
+//!
+//! The semiregular polytopes, including the Gosset polytopes of the E-family,
+//! are built through the Wythoff construction as convex hulls, except for the
+//! snub icositetrachoron, which is built from its coordinates.
+//!
+//! The exotic polytopes are built from the presentations of their
+//! automorphism groups, and are realized through the force-directed layout in
+//! the [`layout`](super::layout) module.
+
+use super::{augment::Cap, layout::DEFAULT_ITERATIONS, Concrete, ConcretePolytope};
+use crate::{
+    abs::{Abstract, Ranked},
+    cox::cd::Cd,
+    file::FromFile,
+    float::Float,
+    geometry::{Point, Vector},
+};
+
+/// The number of uniform polyhedra in the catalog.
+pub const UNIFORM_COUNT: usize = 75;
+
+/// The number of Johnson solids.
+pub const JOHNSON_COUNT: usize = 92;
+
+/// The number of exotic polytopes.
+pub const EXOTIC_COUNT: usize = 8;
+
+/// The number of semiregular polytopes.
+pub const SEMIREGULAR_COUNT: usize = 10;
+
+/// The angle between two faces of a dodecahedron that are neither adjacent
+/// nor opposite, as seen from its center. This is also the angle between two
+/// such vertices of an icosahedron.
+const META: f64 = 2.034_443_935_795_702_7;
+
+/// The non-prismatic uniform polyhedra, together with their OFF files, in the
+/// order of the folders of the library. The files are copies of those in the
+/// library, so that the crate doesn't depend on anything outside of it.
+const UNIFORM: [(&str, &str); UNIFORM_COUNT] = [
+    ("cube", include_str!("uniform/cube.off")),
+    ("doe", include_str!("uniform/doe.off")),
+    ("gad", include_str!("uniform/gad.off")),
+    ("gike", include_str!("uniform/gike.off")),
+    ("gissid", include_str!("uniform/gissid.off")),
+    ("ike", include_str!("uniform/ike.off")),
+    ("oct", include_str!("uniform/oct.off")),
+    ("sissid", include_str!("uniform/sissid.off")),
+    ("tet", include_str!("uniform/tet.off")),
+    ("cho", include_str!("uniform/cho.off")),
+    ("co", include_str!("uniform/co.off")),
+    ("did", include_str!("uniform/did.off")),
+    ("ditdid", include_str!("uniform/ditdid.off")),
+    ("geihid", include_str!("uniform/geihid.off")),
+    ("gid", include_str!("uniform/gid.off")),
+    ("gidhei", include_str!("uniform/gidhei.off")),
+    ("gidhid", include_str!("uniform/gidhid.off")),
+    ("gidtid", include_str!("uniform/gidtid.off")),
+    ("id", include_str!("uniform/id.off")),
+    ("oho", include_str!("uniform/oho.off")),
+    ("seihid", include_str!("uniform/seihid.off")),
+    ("sidhei", include_str!("uniform/sidhei.off")),
+    ("sidhid", include_str!("uniform/sidhid.off")),
+    ("sidtid", include_str!("uniform/sidtid.off")),
+    ("thah", include_str!("uniform/thah.off")),
+    ("quit gissid", include_str!("uniform/quit gissid.off")),
+    ("quit sissid", include_str!("uniform/quit sissid.off")),
+    ("quith", include_str!("uniform/quith.off")),
+    ("ti", include_str!("uniform/ti.off")),
+    ("tic", include_str!("uniform/tic.off")),
+    ("tid", include_str!("uniform/tid.off")),
+    ("tiggy", include_str!("uniform/tiggy.off")),
+    ("tigid", include_str!("uniform/tigid.off")),
+    ("toe", include_str!("uniform/toe.off")),
+    ("tut", include_str!("uniform/tut.off")),
+    ("gaddid", include_str!("uniform/gaddid.off")),
+    ("gidditdid", include_str!("uniform/gidditdid.off")),
+    ("giddy", include_str!("uniform/giddy.off")),
+    ("giid", include_str!("uniform/giid.off")),
+    ("gird", include_str!("uniform/gird.off")),
+    ("gocco", include_str!("uniform/gocco.off")),
+    ("groh", include_str!("uniform/groh.off")),
+    ("ided", include_str!("uniform/ided.off")),
+    ("qrid", include_str!("uniform/qrid.off")),
+    ("querco", include_str!("uniform/querco.off")),
+    ("raded", include_str!("uniform/raded.off")),
+    ("ri", include_str!("uniform/ri.off")),
+    ("saddid", include_str!("uniform/saddid.off")),
+    ("sidditdid", include_str!("uniform/sidditdid.off")),
+    ("siddy", include_str!("uniform/siddy.off")),
+    ("siid", include_str!("uniform/siid.off")),
+    ("sirco", include_str!("uniform/sirco.off")),
+    ("sird", include_str!("uniform/sird.off")),
+    ("socco", include_str!("uniform/socco.off")),
+    ("srid", include_str!("uniform/srid.off")),
+    ("sroh", include_str!("uniform/sroh.off")),
+    ("cotco", include_str!("uniform/cotco.off")),
+    ("gaquatid", include_str!("uniform/gaquatid.off")),
+    ("girco", include_str!("uniform/girco.off")),
+    ("grid", include_str!("uniform/grid.off")),
+    ("idtid", include_str!("uniform/idtid.off")),
+    ("quitco", include_str!("uniform/quitco.off")),
+    ("quitdid", include_str!("uniform/quitdid.off")),
+    ("gidrid", include_str!("uniform/gidrid.off")),
+    ("girsid", include_str!("uniform/girsid.off")),
+    ("gisdid", include_str!("uniform/gisdid.off")),
+    ("gisid", include_str!("uniform/gisid.off")),
+    ("gosid", include_str!("uniform/gosid.off")),
+    ("isdid", include_str!("uniform/isdid.off")),
+    ("seside", include_str!("uniform/seside.off")),
+    ("siddid", include_str!("uniform/siddid.off")),
+    ("sided", include_str!("uniform/sided.off")),
+    ("sirsid", include_str!("uniform/sirsid.off")),
+    ("snic", include_str!("uniform/snic.off")),
+    ("snid", include_str!("uniform/snid.off")),
+];
+
+/// The names of the Johnson solids, in order.
+const JOHNSON: [&str; JOHNSON_COUNT] = [
+    "Square pyramid",
+    "Pentagonal pyramid",
+    "Triangular cupola",
+    "Square cupola",
+    "Pentagonal cupola",
+    "Pentagonal rotunda",
+    "Elongated triangular pyramid",
+    "Elongated square pyramid",
+    "Elongated pentagonal pyramid",
+    "Gyroelongated square pyramid",
+    "Gyroelongated pentagonal pyramid",
+    "Triangular bipyramid",
+    "Pentagonal bipyramid",
+    "Elongated triangular bipyramid",
+    "Elongated square bipyramid",
+    "Elongated pentagonal bipyramid",
+    "Gyroelongated square bipyramid",
+    "Elongated triangular cupola",
+    "Elongated square cupola",
+    "Elongated pentagonal cupola",
+    "Elongated pentagonal rotunda",
+    "Gyroelongated triangular cupola",
+    "Gyroelongated square cupola",
+    "Gyroelongated pentagonal cupola",
+    "Gyroelongated pentagonal rotunda",
+    "Gyrobifastigium",
+    "Triangular orthobicupola",
+    "Square orthobicupola",
+    "Square gyrobicupola",
+    "Pentagonal orthobicupola",
+    "Pentagonal gyrobicupola",
+    "Pentagonal orthocupolarotunda",
+    "Pentagonal gyrocupolarotunda",
+    "Pentagonal orthobirotunda",
+    "Elongated triangular orthobicupola",
+    "Elongated triangular gyrobicupola",
+    "Elongated square gyrobicupola",
+    "Elongated pentagonal orthobicupola",
+    "Elongated pentagonal gyrobicupola",
+    "Elongated pentagonal orthocupolarotunda",
+    "Elongated pentagonal gyrocupolarotunda",
+    "Elongated pentagonal orthobirotunda",
+    "Elongated pentagonal gyrobirotunda",
+    "Gyroelongated triangular bicupola",
+    "Gyroelongated square bicupola",
+    "Gyroelongated pentagonal bicupola",
+    "Gyroelongated pentagonal cupolarotunda",
+    "Gyroelongated pentagonal birotunda",
+    "Augmented triangular prism",
+    "Biaugmented triangular prism",
+    "Triaugmented triangular prism",
+    "Augmented pentagonal prism",
+    "Biaugmented pentagonal prism",
+    "Augmented hexagonal prism",
+    "Parabiaugmented hexagonal prism",
+    "Metabiaugmented hexagonal prism",
+    "Triaugmented hexagonal prism",
+    "Augmented dodecahedron",
+    "Parabiaugmented dodecahedron",
+    "Metabiaugmented dodecahedron",
+    "Triaugmented dodecahedron",
+    "Metabidiminished icosahedron",
+    "Tridiminished icosahedron",
+    "Augmented tridiminished icosahedron",
+    "Augmented truncated tetrahedron",
+    "Augmented truncated cube",
+    "Biaugmented truncated cube",
+    "Augmented truncated dodecahedron",
+    "Parabiaugmented truncated dodecahedron",
+    "Metabiaugmented truncated dodecahedron",
+    "Triaugmented truncated dodecahedron",
+    "Gyrate rhombicosidodecahedron",
+    "Parabigyrate rhombicosidodecahedron",
+    "Metabigyrate rhombicosidodecahedron",
+    "Trigyrate rhombicosidodecahedron",
+    "Diminished rhombicosidodecahedron",
+    "Paragyrate diminished rhombicosidodecahedron",
+    "Metagyrate diminished rhombicosidodecahedron",
+    "Bigyrate diminished rhombicosidodecahedron",
+    "Parabidiminished rhombicosidodecahedron",
+    "Metabidiminished rhombicosidodecahedron",
+    "Gyrate bidiminished rhombicosidodecahedron",
+    "Tridiminished rhombicosidodecahedron",
+    "Snub disphenoid",
+    "Snub square antiprism",
+    "Sphenocorona",
+    "Augmented sphenocorona",
+    "Sphenomegacorona",
+    "Hebesphenomegacorona",
+    "Disphenocingulum",
+    "Bilunabirotunda",
+    "Triangular hebesphenorotunda",
+];
+
+/// The Johnson solids that can't be built from simpler polyhedra, stored as OFF
+/// files, together with their indices.
+const STORED: [(usize, &str); 8] = [
+    (84, include_str!("j84.off")),
+    (85, include_str!("j85.off")),
+    (86, include_str!("j86.off")),
+    (88, include_str!("j88.off")),
+    (89, include_str!("j89.off")),
+    (90, include_str!("j90.off")),
+    (91, include_str!("j91.off")),
+    (92, include_str!("j92.off")),
+];
+
+/// Some famous regular polytopes that can't be faithfully realized in any
+/// Euclidean space, together with the presentations of their automorphism
+/// groups as string C-groups.
+const EXOTIC: [(&str, &str); EXOTIC_COUNT] = [
+    ("Hemicube", "{4,3} (012)^3"),
+    ("Hemi-octahedron", "{3,4} (012)^3"),
+    ("Hemi-dodecahedron", "{5,3} (012)^5"),
+    ("Hemi-icosahedron", "{3,5} (012)^5"),
+    ("Dyck's map", "{3,8} (012)^6"),
+    ("Klein's quartic", "{3,7} (012)^8"),
+    ("11-cell", "{3,5,3} (012)^5 (123)^5"),
+    ("57-cell", "{5,3,5} (012)^5 (123)^5"),
+];
+
+/// Gosset's semiregular polytopes in four or more dimensions, together with
+/// the other Gosset polytopes up to eight dimensions that have a manageable
+/// number of elements. Each comes with the Coxeter diagram it's built from,
+/// if any.
+const SEMIREGULAR: [(&str, Option<&str>); SEMIREGULAR_COUNT] = [
+    ("Rectified pentachoron (0₂₁)", Some("o3x3o3o")),
+    ("Rectified hexacosichoron", Some("o3x3o5o")),
+    ("Snub icositetrachoron", None),
+    ("Demipenteract (1₂₁)", Some("x3o3o *b3o3o")),
+    ("2₂₁ polytope", Some("x3o3o3o3o *c3o")),
+    ("1₂₂ polytope", Some("o3o3o3o3o *c3x")),
+    ("3₂₁ polytope", Some("o3o3o3o3o3x *c3o")),
+    ("2₃₁ polytope", Some("x3o3o3o3o3o *c3o")),
+    ("1₃₂ polytope", Some("o3o3o3o3o3o *c3x")),
+    ("4₂₁ polytope", Some("o3o3o3o3o3o3x *c3o")),
+];
+
+/// Returns the name of the uniform polyhedron with a given index, starting
+/// from 1, or `None` if there's no such polyhedron.
+pub fn uniform_name(idx: usize) -> Option<&'static str> {
+    Some(UNIFORM.get(idx.checked_sub(1)?)?.0)
+}
+
+/// Returns the name of the Johnson solid with a given index, starting from 1,
+/// or `None` if there's no such solid.
+pub fn johnson_name(idx: usize) -> Option<&'static str> {
+    JOHNSON.get(idx.checked_sub(1)?).copied()
+}
+
+/// Returns the name of the exotic polytope with a given index, starting from
+/// 1, or `None` if there's no such polytope.
+pub fn exotic_name(idx: usize) -> Option<&'static str> {
+    Some(EXOTIC.get(idx.checked_sub(1)?)?.0)
+}
+
+/// Returns the name of the semiregular polytope with a given index, starting
+/// from 1, or `None` if there's no such polytope.
+pub fn semiregular_name(idx: usize) -> Option<&'static str> {
+    Some(SEMIREGULAR.get(idx.checked_sub(1)?)?.0)
+}
+
+/// Builds the snub icositetrachoron with unit edges. Its vertices are those
+/// of a hexacosichoron that aren't vertices of its inscribed
+/// icositetrachoron, namely the even permutations of (0, ±1, ±φ, ±1/φ) / 2.
+fn snub_icositetrachoron() -> Option<Concrete> {
+    let phi = (1.0 + f64::SQRT_5) / 2.0;
+    let even_perms = [
+        [0, 1, 2, 3],
+        [0, 2, 3, 1],
+        [0, 3, 1, 2],
+        [1, 0, 3, 2],
+        [1, 2, 0, 3],
+        [1, 3, 2, 0],
+        [2, 0, 1, 3],
+        [2, 1, 3, 0],
+        [2, 3, 0, 1],
+        [3, 0, 2, 1],
+        [3, 1, 0, 2],
+        [3, 2, 1, 0],
+    ];
+
+    let mut vertices = Vec::with_capacity(96);
+    for perm in &even_perms {
+        for signs in 0..8 {
+            let sign = |i: i32| if signs & (1 << i) == 0 { 1.0 } else { -1.0 };
+
+            // The edge length of these vertices is 1 / φ, so we scale them by φ.
+            let coords = [0.0, sign(0), sign(1) * phi, sign(2) / phi];
+            vertices.push(Point::from_iterator(
+                4,
+                perm.iter().map(|&i| coords[i] * phi / 2.0),
+            ));
+        }
+    }
+
+    Concrete::convex_hull(&vertices)
+}
+
+/// Loads a uniform polyhedron from the catalog by the name of its file.
+fn uniform_named(name: &str) -> Option<Concrete> {
+    let (_, src) = UNIFORM.iter().find(|(n, _)| *n == name)?;
+    Concrete::from_off(src).ok()
+}
+
+/// Builds a pyramid with unit edges on a regular polygon, with its apex
+/// pointing upwards.
+fn pyramid(n: usize) -> Concrete {
+    let base = Concrete::star_polygon_with_edge(n, 1, 1.0);
+    let radius = base.vertices[0].norm();
+    let height = (1.0 - radius * radius).fsqrt();
+
+    // The base of a pyramid lies at height 1/2.
+    base.pyramid_with(vec![0.0, 0.0, 0.5 + height].into())
+}
+
+/// Returns the gravicenters of the facets of a polyhedron with a given number
+/// of vertices, together with their indices.
+fn facet_centers(poly: &Concrete, n: usize) -> Vec<(usize, Point<f64>)> {
+    (0..poly.facet_count())
+        .filter_map(|idx| {
+            let vertices = poly.element_vertices_ref(3, idx)?;
+            if vertices.len() != n {
+                return None;
+            }
+
+            let sum = vertices.iter().fold(Point::zeros(3), |acc, &v| acc + v);
+            Some((idx, sum / n as f64))
+        })
+        .collect()
+}
+
+/// Returns the index of the facet of a polyhedron with a given number of
+/// vertices that lies the furthest in a given direction.
+fn facet_towards(poly: &Concrete, n: usize, direction: &Vector<f64>) -> Option<usize> {
+    facet_centers(poly, n)
+        .into_iter()
+        .max_by(|(_, p), (_, q)| p.dot(direction).partial_cmp(&q.dot(direction)).unwrap())
+        .map(|(idx, _)| idx)
+}
+
+/// Returns the index of the bottom facet of a polyhedron with a given number
+/// of vertices.
+fn bottom(poly: &Concrete, n: usize) -> Option<usize> {
+    facet_towards(poly, n, &vec![0.0, 0.0, -1.0].into())
+}
+
+/// Returns the index of the facet of a polyhedron with a given number of
+/// vertices whose gravicenter is the closest to a given point.
+fn facet_near(poly: &Concrete, n: usize, point: &Point<f64>) -> Option<usize> {
+    facet_centers(poly, n)
+        .into_iter()
+        .min_by(|(_, p), (_, q)| (p - point).norm().partial_cmp(&(q - point).norm()).unwrap())
+        .map(|(idx, _)| idx)
+}
+
+/// Returns the index of the vertex of a polytope that's the closest to a given
+/// point.
+fn vertex_near(poly: &Concrete, point: &Point<f64>) -> Option<usize> {
+    (0..poly.vertex_count()).min_by(|&v, &w| {
+        let (v, w) = (&poly.vertices[v] - point, &poly.vertices[w] - point);
+        v.norm().partial_cmp(&w.norm()).unwrap()
+    })
+}
+
+/// Picks some of a set of points. The first one is the first point in the set,
+/// and every other one makes the given angle with all of the previous ones, as
+/// seen from a given center.
+///
+/// Returns `None` if there's no point with the required angles.
+fn spread(points: &[Point<f64>], center: &Point<f64>, angles: &[f64]) -> Option<Vec<Point<f64>>> {
+    let mut picked = vec![points.first()?.clone()];
+
+    for &angle in angles {
+        // How far a point is from making the right angle with every previous
+        // point.
+        let error = |p: &Point<f64>| {
+            picked
+                .iter()
+                .map(|q| {
+                    let (u, v) = (p - center, q - center);
+                    let cos = u.dot(&v) / (u.norm() * v.norm());
+                    (cos.clamp(-1.0, 1.0).acos() - angle).abs()
+                })
+                .fold(0.0, f64::max)
+        };
+
+        let best = points
+            .iter()
+            .min_by(|p, q| error(p).partial_cmp(&error(q)).unwrap())?;
+        if error(best) > f64::eps().fsqrt() {
+            return None;
+        }
+        picked.push(best.clone());
+    }
+
+    Some(picked)
+}
+
+/// Picks some facets of a polyhedron with a given number of vertices as in
+/// [`spread`], and returns their gravicenters.
+fn spread_facets(poly: &Concrete, n: usize, angles: &[f64]) -> Option<Vec<Point<f64>>> {
+    let centers: Vec<_> = facet_centers(poly, n).into_iter().map(|(_, p)| p).collect();
+    spread(&centers, &poly.gravicenter()?, angles)
+}
+
+/// Augments a polyhedron with pyramids on the facets with a given number of
+/// vertices closest to some points.
+fn augment_at(mut poly: Concrete, n: usize, points: &[Point<f64>]) -> Option<Concrete> {
+    for point in points {
+        poly = poly.augment(facet_near(&poly, n, point)?)?;
+    }
+
+    Some(poly)
+}
+
+/// Augments a polyhedron with cupolae on the facets with a given number of
+/// vertices closest to some points, in the gyro orientation.
+fn augment_cupolae_at(mut poly: Concrete, n: usize, points: &[Point<f64>]) -> Option<Concrete> {
+    for point in points {
+        poly = poly.augment_cap(facet_near(&poly, n, point)?, Cap::Cupola, true)?;
+    }
+
+    Some(poly)
+}
+
+/// Removes the vertices of a polytope closest to some points, one by one.
+fn diminish_at(mut poly: Concrete, points: &[Point<f64>]) -> Option<Concrete> {
+    for point in points {
+        poly = poly.diminish(&[vertex_near(&poly, point)?])?;
+    }
+
+    Some(poly)
+}
+
+/// Modifies the pentagonal cupolae of a rhombicosidodecahedron whose top faces
+/// are closest to some points. Every cupola is removed if the corresponding
+/// entry of `gyrate` is `false`, and is gyrated otherwise.
+fn rhombicosidodecahedron(points: &[Point<f64>], gyrate: &[bool]) -> Option<Concrete> {
+    let mut poly = uniform_named("srid")?;
+
+    for (point, &gyrate) in points.iter().zip(gyrate) {
+        let facet = facet_near(&poly, 5, point)?;
+        let cap = poly.abs.element_vertices(3, facet)?;
+        poly = if gyrate {
+            poly.gyrate(&cap)?
+        } else {
+            poly.diminish(&cap)?
+        };
+    }
+
+    Some(poly)
+}
+
+/// Builds the gyrobifastigium, by attaching a triangular prism onto a square
+/// of another, rotated by a quarter turn.
+fn gyrobifastigium() -> Option<Concrete> {
+    let prism = Concrete::uniform_prism(3, 1);
+    let (facet, center) = facet_centers(&prism, 4).into_iter().next()?;
+    let normal = (&center - prism.gravicenter()?).try_normalize(f64::eps())?;
+    let u = (&prism.vertices[prism.abs.element_vertices(3, facet)?[0]] - &center)
+        .try_normalize(f64::eps())?;
+    let w = normal.cross(&u);
+
+    // Reflects the prism through the plane of the square, and rotates it
+    // about the square's center.
+    let mut cap = prism.clone();
+    for v in cap.vertices.iter_mut() {
+        let p = &*v - &center;
+        let (x, y, z) = (p.dot(&u), p.dot(&w), p.dot(&normal));
+        *v = &center - &u * y + &w * x - &normal * z;
+    }
+
+    prism.glue(facet, &cap)
+}
+
+impl Concrete {
+    /// Loads the uniform polyhedron with a given index from the catalog,
+    /// starting from 1. These are ordered as in the folders of the library,
+    /// and have unit edge length.
+    ///
+    /// Returns `None` if there's no such polyhedron.
+    pub fn uniform(idx: usize) -> Option<Self> {
+        let (_, src) = UNIFORM.get(idx.checked_sub(1)?)?;
+        Self::from_off(src).ok()
+    }
+
+    /// Builds the Johnson solid with a given index, starting from 1, with
+    /// unit edge length.
+    ///
+    /// Returns `None` if there's no such solid.
+    pub fn johnson(idx: usize) -> Option<Self> {
+        use std::f64::consts::PI;
+
+        if let Some((_, src)) = STORED.iter().find(|(j, _)| *j == idx) {
+            return Self::from_off(src).ok();
+        }
+
+        let cupola = |n| Self::cupola(n, 1);
+        let rotunda = |n| Self::rotunda(n, 1);
+
+        // Attaches a cap onto the bottom of a polyhedron.
+        let cap = |poly: Self, n: usize, cap: Cap, gyro: bool| {
+            let facet = bottom(&poly, n)?;
+            poly.augment_cap(facet, cap, gyro)
+        };
+
+        // Elongates or gyroelongates the bottom of a polyhedron.
+        let elongate = |poly: Self, n| poly.elongate(bottom(&poly, n)?);
+        let gyroelongate = |poly: Self, n| poly.gyroelongate(bottom(&poly, n)?);
+        let augment = |poly: Self, n| poly.augment(bottom(&poly, n)?);
+
+        // Augments the squares of a prism.
+        let prism = |n, angles: &[f64]| {
+            let prism = Self::uniform_prism(n, 1);
+            let points = spread_facets(&prism, 4, angles)?;
+            augment_at(prism, 4, &points)
+        };
+
+        // Augments the pentagons of a dodecahedron.
+        let dodecahedron = |angles: &[f64]| {
+            let doe = uniform_named("doe")?;
+            let points = spread_facets(&doe, 5, angles)?;
+            augment_at(doe, 5, &points)
+        };
+
+        // Removes vertices of an icosahedron.
+        let icosahedron = |angles: &[f64]| {
+            let ike = uniform_named("ike")?;
+            let points = spread(&ike.vertices, &ike.gravicenter()?, angles)?;
+            diminish_at(ike, &points).zip(Some(points))
+        };
+
+        // Augments the largest faces of a truncated polyhedron.
+        let truncate = |name, n, angles: &[f64]| {
+            let poly = uniform_named(name)?;
+            let points = spread_facets(&poly, n, angles)?;
+            augment_cupolae_at(poly, n, &points)
+        };
+
+        // Gyrates or removes pentagonal cupolae of a rhombicosidodecahedron.
+        let srid = |angles: &[f64], gyrate: &[bool]| {
+            let points = spread_facets(&uniform_named("srid")?, 5, angles)?;
+            rhombicosidodecahedron(&points, gyrate)
+        };
+
+        let (para, tri) = (&[PI], &[META, META]);
+        match idx {
+            // Pyramids, cupolae, and the pentagonal rotunda.
+            1 => Some(pyramid(4)),
+            2 => Some(pyramid(5)),
+            3 => cupola(3),
+            4 => cupola(4),
+            5 => cupola(5),
+            6 => rotunda(5),
+
+            // Modified pyramids and dipyramids.
+            7 => elongate(pyramid(3), 3),
+            8 => elongate(pyramid(4), 4),
+            9 => elongate(pyramid(5), 5),
+            10 => gyroelongate(pyramid(4), 4),
+            11 => gyroelongate(pyramid(5), 5),
+            12 => augment(pyramid(3), 3),
+            13 => augment(pyramid(5), 5),
+            14 => augment(elongate(pyramid(3), 3)?, 3),
+            15 => augment(elongate(pyramid(4), 4)?, 4),
+            16 => augment(elongate(pyramid(5), 5)?, 5),
+            17 => augment(gyroelongate(pyramid(4), 4)?, 4),
+
+            // Modified cupolae and rotundae.
+            18 => elongate(cupola(3)?, 6),
+            19 => elongate(cupola(4)?, 8),
+            20 => elongate(cupola(5)?, 10),
+            21 => elongate(rotunda(5)?, 10),
+            22 => gyroelongate(cupola(3)?, 6),
+            23 => gyroelongate(cupola(4)?, 8),
+            24 => gyroelongate(cupola(5)?, 10),
+            25 => gyroelongate(rotunda(5)?, 10),
+            26 => gyrobifastigium(),
+            27 => cap(cupola(3)?, 6, Cap::Cupola, false),
+            28 => cap(cupola(4)?, 8, Cap::Cupola, false),
+            29 => cap(cupola(4)?, 8, Cap::Cupola, true),
+            30 => cap(cupola(5)?, 10, Cap::Cupola, false),
+            31 => cap(cupola(5)?, 10, Cap::Cupola, true),
+            32 => cap(cupola(5)?, 10, Cap::Rotunda, false),
+            33 => cap(cupola(5)?, 10, Cap::Rotunda, true),
+            34 => cap(rotunda(5)?, 10, Cap::Rotunda, false),
+            35 => cap(elongate(cupola(3)?, 6)?, 6, Cap::Cupola, false),
+            36 => cap(elongate(cupola(3)?, 6)?, 6, Cap::Cupola, true),
+            37 => cap(elongate(cupola(4)?, 8)?, 8, Cap::Cupola, true),
+            38 => cap(elongate(cupola(5)?, 10)?, 10, Cap::Cupola, false),
+            39 => cap(elongate(cupola(5)?, 10)?, 10, Cap::Cupola, true),
+            40 => cap(elongate(cupola(5)?, 10)?, 10, Cap::Rotunda, false),
+            41 => cap(elongate(cupola(5)?, 10)?, 10, Cap::Rotunda, true),
+            42 => cap(elongate(rotunda(5)?, 10)?, 10, Cap::Rotunda, false),
+            43 => cap(elongate(rotunda(5)?, 10)?, 10, Cap::Rotunda, true),
+            44 => cap(gyroelongate(cupola(3)?, 6)?, 6, Cap::Cupola, false),
+            45 => cap(gyroelongate(cupola(4)?, 8)?, 8, Cap::Cupola, false),
+            46 => cap(gyroelongate(cupola(5)?, 10)?, 10, Cap::Cupola, false),
+            47 => cap(gyroelongate(cupola(5)?, 10)?, 10, Cap::Rotunda, false),
+            48 => cap(gyroelongate(rotunda(5)?, 10)?, 10, Cap::Rotunda, false),
+
+            // Augmented prisms.
+            49 => prism(3, &[]),
+            50 => prism(3, &[2.0 * PI / 3.0]),
+            51 => prism(3, &[2.0 * PI / 3.0, 2.0 * PI / 3.0]),
+            52 => prism(5, &[]),
+            53 => prism(5, &[4.0 * PI / 5.0]),
+            54 => prism(6, &[]),
+            55 => prism(6, para),
+            56 => prism(6, &[2.0 * PI / 3.0]),
+            57 => prism(6, &[2.0 * PI / 3.0, 2.0 * PI / 3.0]),
+
+            // Modified Platonic solids.
+            58 => dodecahedron(&[]),
+            59 => dodecahedron(para),
+            60 => dodecahedron(&[META]),
+            61 => dodecahedron(tri),
+            62 => icosahedron(&[META]).map(|(poly, _)| poly),
+            63 => icosahedron(tri).map(|(poly, _)| poly),
+            64 => {
+                // The triangle surrounded by the three pentagons is augmented.
+                let (poly, points) = icosahedron(tri)?;
+                let center = points.iter().fold(Point::zeros(3), |acc, p| acc + p) / 3.0;
+                poly.augment(facet_near(&poly, 3, &center)?)
+            }
+
+            // Modified Archimedean solids.
+            65 => truncate("tut", 6, &[]),
+            66 => truncate("tic", 8, &[]),
+            67 => truncate("tic", 8, para),
+            68 => truncate("tid", 10, &[]),
+            69 => truncate("tid", 10, para),
+            70 => truncate("tid", 10, &[META]),
+            71 => truncate("tid", 10, tri),
+            72 => srid(&[], &[true]),
+            73 => srid(para, &[true, true]),
+            74 => srid(&[META], &[true, true]),
+            75 => srid(tri, &[true, true, true]),
+            76 => srid(&[], &[false]),
+            77 => srid(para, &[false, true]),
+            78 => srid(&[META], &[false, true]),
+            79 => srid(tri, &[false, true, true]),
+            80 => srid(para, &[false, false]),
+            81 => srid(&[META], &[false, false]),
+            82 => srid(tri, &[false, false, true]),
+            83 => srid(tri, &[false, false, false]),
+
+            // The elementary solids that can be built from others.
+            87 => {
+                let poly = Self::johnson(86)?;
+                let square = facet_centers(&poly, 4).first()?.0;
+                poly.augment(square)
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Builds the exotic polytope with a given index, starting from 1. Its
+    /// vertices are placed by a force-directed layout in the space whose
+    /// dimension matches its rank.
+    ///
+    /// Returns `None` if there's no such polytope.
+    pub fn exotic(idx: usize) -> Option<Self> {
+        let (_, presentation) = EXOTIC.get(idx.checked_sub(1)?)?;
+        let abs = Abstract::from_c_group(presentation).ok()?;
+        let dim = abs.rank() - 1;
+        Some(Self::force_directed(abs, dim, DEFAULT_ITERATIONS))
+    }
+
+    /// Builds the semiregular polytope with a given index, starting from 1,
+    /// with unit edge length. The larger Gosset polytopes can take a while.
+    ///
+    /// Returns `None` if there's no such polytope.
+    pub fn semiregular(idx: usize) -> Option<Self> {
+        match SEMIREGULAR.get(idx.checked_sub(1)?)? {
+            (_, Some(cd)) => Self::wythoff(&Cd::parse(cd).ok()?),
+            (_, None) => snub_icositetrachoron(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Subspace;
+
+    use vec_like::*;
+
+    /// The vertex counts of the Johnson solids.
+    const JOHNSON_VERTICES: [usize; JOHNSON_COUNT] = [
+        5, 6, 9, 12, 15, 20, 7, 9, 11, 9, 11, 5, 7, 8, 10, 12, 10, 15, 20, 25, 30, 15, 20, 25, 30,
+        8, 12, 16, 16, 20, 20, 25, 25, 30, 18, 18, 24, 30, 30, 35, 35, 40, 40, 18, 24, 30, 35, 40,
+        7, 8, 9, 11, 12, 13, 14, 14, 15, 21, 22, 22, 23, 10, 9, 10, 15, 28, 32, 65, 70, 70, 75, 60,
+        60, 60, 60, 55, 55, 55, 55, 50, 50, 50, 45, 8, 16, 10, 11, 12, 14, 16, 14, 18,
+    ];
+
+    /// Checks that a polyhedron is convex, and that no two of its faces lie on
+    /// the same plane.
+    fn is_strictly_convex(poly: &Concrete) -> bool {
+        (0..poly.facet_count()).all(|idx| {
+            let face = poly.abs.element_vertices(3, idx).unwrap();
+            let (a, b, c) = (
+                &poly.vertices[face[0]],
+                &poly.vertices[face[1]],
+                &poly.vertices[face[2]],
+            );
+            let normal = (b - a).cross(&(c - a));
+            let sides: Vec<_> = (0..poly.vertex_count())
+                .filter(|v| !face.contains(v))
+                .map(|v| (&poly.vertices[v] - a).dot(&normal))
+                .collect();
+
+            sides.iter().all(|&d| d > f64::eps()) || sides.iter().all(|&d| d < -f64::eps())
+        })
+    }
+
+    /// Checks that every face of a polyhedron is a regular polygon, assuming
+    /// that its edges all have the same length.
+    fn has_regular_faces(poly: &Concrete) -> bool {
+        (0..poly.facet_count()).all(|idx| {
+            let vertices = poly.element_vertices_ref(3, idx).unwrap();
+            let center =
+                vertices.iter().fold(Point::zeros(3), |acc, &v| acc + v) / vertices.len() as f64;
+            let radius = (vertices[0] - &center).norm();
+
+            Subspace::from_points(vertices.iter().copied()).rank() == 2
+                && vertices
+                    .iter()
+                    .all(|&v| ((v - &center).norm() - radius).abs() < f64::eps())
+        })
+    }
+
+    #[test]
+    fn uniform() {
+        for idx in 1..=UNIFORM_COUNT {
+            let poly = Concrete::uniform(idx).unwrap();
+            poly.assert_valid();
+            assert!(
+                poly.is_equilateral_with(1.0),
+                "{}",
+                uniform_name(idx).unwrap()
+            );
+        }
+
+        assert_eq!(uniform_name(1), Some("cube"));
+        assert!(Concrete::uniform(0).is_none());
+        assert!(Concrete::uniform(UNIFORM_COUNT + 1).is_none());
+    }
+
+    #[test]
+    fn johnson() {
+        for idx in 1..=JOHNSON_COUNT {
+            let name = johnson_name(idx).unwrap();
+            let poly = Concrete::johnson(idx).unwrap_or_else(|| panic!("{}", name));
+            poly.assert_valid();
+            assert_eq!(poly.vertex_count(), JOHNSON_VERTICES[idx - 1], "{}", name);
+            assert!(poly.is_equilateral_with(1.0), "{}", name);
+            assert!(has_regular_faces(&poly), "{}", name);
+            assert!(is_strictly_convex(&poly), "{}", name);
+        }
+
+        assert!(Concrete::johnson(0).is_none());
+        assert!(Concrete::johnson(JOHNSON_COUNT + 1).is_none());
+    }
+
+    #[test]
+    fn exotic() {
+        let counts: [&[usize]; EXOTIC_COUNT] = [
+            &[1, 4, 6, 3, 1],
+            &[1, 3, 6, 4, 1],
+            &[1, 10, 15, 6, 1],
+            &[1, 6, 15, 10, 1],
+            &[1, 12, 48, 32, 1],
+            &[1, 24, 84, 56, 1],
+            &[1, 11, 55, 55, 11, 1],
+            &[1, 57, 171, 171, 57, 1],
+        ];
+
+        for (idx, count) in (1..=EXOTIC_COUNT).zip(counts.iter()) {
+            let poly = Concrete::exotic(idx).unwrap();
+            crate::test(&poly, count.iter().copied());
+            assert_eq!(poly.dim(), Some(poly.rank() - 1));
+        }
+
+        assert_eq!(exotic_name(7), Some("11-cell"));
+        assert!(Concrete::exotic(0).is_none());
+        assert!(Concrete::exotic(EXOTIC_COUNT + 1).is_none());
+    }
+
+    #[test]
+    fn semiregular() {
+        let counts: [&[usize]; 6] = [
+            &[1, 10, 30, 30, 10, 1],
+            &[1, 720, 3600, 3600, 720, 1],
+            &[1, 96, 432, 480, 144, 1],
+            &[1, 16, 80, 160, 120, 26, 1],
+            &[1, 27, 216, 720, 1080, 648, 99, 1],
+            &[1, 72, 720, 2160, 2160, 702, 54, 1],
+        ];
+
+        for (idx, count) in (1..).zip(counts.iter()) {
+            let name = semiregular_name(idx).unwrap();
+            let poly = Concrete::semiregular(idx).unwrap();
+            poly.assert_valid();
+            crate::test(&poly, count.iter().copied());
+            assert!(poly.is_equilateral_with(1.0), "{}", name);
+        }
+
+        assert_eq!(semiregular_name(10), Some("4₂₁ polytope"));
+        assert!(Concrete::semiregular(0).is_none());
+        assert!(Concrete::semiregular(SEMIREGULAR_COUNT + 1).is_none());
+    }
+
+    /// Returns how many pairs of triangles of a polyhedron share an edge.
+    fn adjacent_triangles(poly: &Concrete) -> usize {
+        let triangles: Vec<_> = (0..poly.facet_count())
+            .filter(|&idx| poly.abs[(3, idx)].subs.len() == 3)
+            .collect();
+
+        triangles
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &t)| triangles[i + 1..].iter().map(move |&u| (t, u)))
+            .filter(|&(t, u)| {
+                poly.abs[(3, t)]
+                    .subs
+                    .iter()
+                    .any(|e| poly.abs[(3, u)].subs.contains(e))
+            })
+            .count()
+    }
+
+    /// Checks whether a polyhedron is symmetric under the reflection that
+    /// swaps its top and bottom.
+    fn is_mirror_symmetric(poly: &Concrete) -> bool {
+        let heights = poly.vertices.iter().map(|v| v[2]);
+        let middle = heights.clone().fold(f64::MIN, f64::max) + heights.fold(f64::MAX, f64::min);
+
+        poly.vertices.iter().all(|v| {
+            let mut w = v.clone();
+            w[2] = middle - v[2];
+            poly.vertices.iter().any(|u| (u - &w).norm() < f64::eps())
+        })
+    }
+
+    #[test]
+    fn orientation() {
+        // The triangles of the two cupolae meet in the orthobicupolae, but not
+        // in the gyrobicupolae.
+        assert_eq!(adjacent_triangles(&Concrete::johnson(27).unwrap()), 3);
+        assert_eq!(adjacent_triangles(&Concrete::johnson(29).unwrap()), 0);
+
+        // An orthobicupola is its own mirror image through its middle, even
+        // when elongated.
+        for &(ortho, gyro) in &[(27, 29), (30, 31), (35, 36), (38, 39), (42, 43)] {
+            assert!(is_mirror_symmetric(&Concrete::johnson(ortho).unwrap()));
+            assert!(!is_mirror_symmetric(&Concrete::johnson(gyro).unwrap()));
+        }
+    }
+}
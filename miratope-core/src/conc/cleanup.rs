@@ -0,0 +1,231 @@
+//! Cleans up polytopes whose vertices coincide, as often happens with imported
+//! meshes or with the results of projections.
+//!
+//! Coincident vertices are merged, and the lattice is rebuilt from the bottom
+//! up. Elements whose vertices are merged together are merged as well, and
+//! elements that become degenerate are removed, together with anything that's
+//! only contained in them.
+
+use std::collections::{BTreeSet, HashMap};
+
+use super::Concrete;
+use crate::{
+    abs::{AbstractBuilder, Ranked, SubelementList},
+    geometry::{Point, Subspace},
+};
+
+use vec_like::VecLike;
+
+/// Returns, for every vertex, the index of the first vertex that lies closer
+/// to it than a given distance, together with the list of these first
+/// vertices.
+pub(super) fn vertex_clusters(vertices: &[Point<f64>], eps: f64) -> (Vec<usize>, Vec<Point<f64>>) {
+    let mut fused: Vec<Point<f64>> = Vec::new();
+    let map = vertices
+        .iter()
+        .map(|v| match fused.iter().position(|w| (v - w).norm() < eps) {
+            Some(idx) => idx,
+            None => {
+                fused.push(v.clone());
+                fused.len() - 1
+            }
+        })
+        .collect();
+
+    (map, fused)
+}
+
+impl Concrete {
+    /// Merges the vertices of the polytope that lie closer than a given
+    /// distance, and repairs the rest of the lattice. Returns the number of
+    /// vertices removed.
+    ///
+    /// Elements with the same vertices after merging are merged. An element of
+    /// rank `r` (with the vertices having rank 0) is removed if it's left with
+    /// less than `r + 1` subelements, or if its vertices span less than `r`
+    /// dimensions. This gets rid of edges of zero length and of degenerate
+    /// faces. Elements that are no longer contained in any element of the next
+    /// rank are then removed.
+    pub fn fuse_vertices(&mut self, eps: f64) -> usize {
+        let rank = self.rank();
+        let vertex_count = self.vertices.len();
+        if rank < 2 {
+            return 0;
+        }
+
+        let (map, vertices) = vertex_clusters(&self.vertices, eps);
+        let mut map: Vec<Option<usize>> = map.into_iter().map(Some).collect();
+        let mut vertex_sets: Vec<BTreeSet<usize>> = (0..vertices.len())
+            .map(|v| std::iter::once(v).collect())
+            .collect();
+
+        // The subelements of the elements we keep, from the edges up to the
+        // facets.
+        let mut levels: Vec<Vec<Vec<usize>>> = Vec::new();
+
+        for r in 2..rank {
+            let mut subs_list = Vec::new();
+            let mut new_vertex_sets = Vec::new();
+            let mut index = HashMap::new();
+
+            map = self[r]
+                .iter()
+                .map(|el| {
+                    let subs: BTreeSet<_> = el.subs.iter().filter_map(|&sub| map[sub]).collect();
+                    let vertex_set: BTreeSet<_> = subs
+                        .iter()
+                        .flat_map(|&sub| vertex_sets[sub].iter().copied())
+                        .collect();
+
+                    if subs.len() < r
+                        || Subspace::from_points(vertex_set.iter().map(|&v| &vertices[v])).rank()
+                            < r - 1
+                    {
+                        return None;
+                    }
+
+                    let key: Vec<_> = vertex_set.iter().copied().collect();
+                    Some(match index.get(&key) {
+                        Some(&idx) => {
+                            let merged: &mut Vec<usize> = &mut subs_list[idx];
+                            merged.extend(subs);
+                            merged.sort_unstable();
+                            merged.dedup();
+                            idx
+                        }
+                        None => {
+                            let idx = subs_list.len();
+                            index.insert(key, idx);
+                            subs_list.push(subs.into_iter().collect());
+                            new_vertex_sets.push(vertex_set);
+                            idx
+                        }
+                    })
+                })
+                .collect();
+
+            levels.push(subs_list);
+            vertex_sets = new_vertex_sets;
+        }
+
+        // Removes the elements that aren't contained in anything else, from
+        // the top down. Every facet is contained in the maximal element.
+        let mut kept: Vec<Vec<bool>> = levels.iter().map(|l| vec![false; l.len()]).collect();
+        kept.insert(0, vec![false; vertices.len()]);
+        for k in kept.last_mut().unwrap() {
+            *k = true;
+        }
+
+        for r in (1..levels.len() + 1).rev() {
+            for (idx, subs) in levels[r - 1].iter().enumerate() {
+                if kept[r][idx] {
+                    for &sub in subs {
+                        kept[r - 1][sub] = true;
+                    }
+                }
+            }
+        }
+
+        // The new index of every element that's kept.
+        let new_indices: Vec<Vec<usize>> = kept
+            .iter()
+            .map(|k| {
+                let mut count = 0;
+                k.iter()
+                    .map(|&k| {
+                        let idx = count;
+                        if k {
+                            count += 1;
+                        }
+                        idx
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let vertices: Vec<_> = vertices
+            .into_iter()
+            .zip(&kept[0])
+            .filter(|(_, &k)| k)
+            .map(|(v, _)| v)
+            .collect();
+
+        let mut builder = AbstractBuilder::with_rank_capacity(rank);
+        builder.push_min();
+        builder.push_vertices(vertices.len());
+
+        for (r, subs_list) in levels.into_iter().enumerate() {
+            let mut list = SubelementList::new();
+            for (idx, subs) in subs_list.into_iter().enumerate() {
+                if kept[r + 1][idx] {
+                    list.push(
+                        subs.into_iter()
+                            .map(|sub| new_indices[r][sub])
+                            .collect::<Vec<_>>()
+                            .into(),
+                    );
+                }
+            }
+            builder.push(list);
+        }
+        builder.push_max();
+
+        // Safety: not really. Merging vertices can leave all sorts of
+        // structures that aren't polytopes.
+        let removed = vertex_count - vertices.len();
+        let scheme = self.colors.scheme;
+        *self = Self::new(vertices, unsafe { builder.build() });
+        self.colors.scheme = scheme;
+        removed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test, Polytope};
+
+    /// Checks that a compound of a polygon with itself becomes a single
+    /// polygon.
+    #[test]
+    fn doubled_polygon() {
+        let mut square = Concrete::polygon(4);
+        square.comp_append(Concrete::polygon(4));
+        test(&square, [1, 8, 8, 1]);
+
+        assert_eq!(square.fuse_vertices(1e-9), 4);
+        test(&square, [1, 4, 4, 1]);
+    }
+
+    /// Checks that edges of zero length are removed.
+    #[test]
+    fn zero_length_edge() {
+        let mut pentagon = Concrete::polygon(5);
+        pentagon.vertices[1] = pentagon.vertices[0].clone() * (1.0 + 1e-12);
+
+        assert_eq!(pentagon.fuse_vertices(1e-9), 1);
+        test(&pentagon, [1, 4, 4, 1]);
+    }
+
+    /// Checks that faces with no area are removed.
+    #[test]
+    fn degenerate_face() {
+        let mut tetrahedron = Concrete::tetrahedron();
+        tetrahedron.vertices[3] = (&tetrahedron.vertices[0] + &tetrahedron.vertices[1]) / 2.0;
+
+        assert_eq!(tetrahedron.fuse_vertices(1e-9), 0);
+        test(&tetrahedron, [1, 4, 6, 3, 1]);
+    }
+
+    /// Checks that a prism whose bases coincide becomes its base.
+    #[test]
+    fn collapsed_prism() {
+        let mut prism = Concrete::polygon(3).prism();
+        for v in &mut prism.vertices {
+            v[2] = 0.0;
+        }
+
+        assert_eq!(prism.fuse_vertices(1e-9), 3);
+        test(&prism, [1, 3, 3, 1, 1]);
+    }
+}
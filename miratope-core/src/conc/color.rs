@@ -0,0 +1,233 @@
+//! Contains the colors that can be attached to the elements of a polytope,
+//! such as those read from an OFF file.
+
+/// A color, given by its red, green, blue and alpha components, each of them
+/// between 0 and 1.
+pub type Color = [f32; 4];
+
+/// The colors in which the faces, edges and vertices of a whole polytope are
+/// drawn. Any of them can be left as `None` to use the default.
+///
+/// These are stored in OFF files as a block of comments, so that other
+/// programs ignore them:
+///
+/// ```txt
+/// # Color scheme
+/// # faces: 1 0.5 0 1
+/// # edges: 0 0 0 1
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ColorScheme {
+    /// The color of the faces.
+    pub faces: Option<Color>,
+
+    /// The color of the edges.
+    pub edges: Option<Color>,
+
+    /// The color of the vertices.
+    pub vertices: Option<Color>,
+}
+
+impl ColorScheme {
+    /// The comment that starts the block of a color scheme in an OFF file.
+    pub const HEADER: &'static str = "Color scheme";
+
+    /// Returns whether every color is left as the default.
+    pub fn is_empty(&self) -> bool {
+        self.faces.is_none() && self.edges.is_none() && self.vertices.is_none()
+    }
+
+    /// Returns the name of every color together with a mutable reference to
+    /// it, in the order they're written.
+    pub fn colors_mut(&mut self) -> [(&'static str, &mut Option<Color>); 3] {
+        [
+            ("faces", &mut self.faces),
+            ("edges", &mut self.edges),
+            ("vertices", &mut self.vertices),
+        ]
+    }
+
+    /// Writes the color scheme as a block of OFF comments, or returns an
+    /// empty string if it's empty.
+    pub fn to_comments(mut self) -> String {
+        let mut comments = String::new();
+        if self.is_empty() {
+            return comments;
+        }
+
+        comments.push_str(&format!("# {}\n", Self::HEADER));
+        for (name, color) in self.colors_mut() {
+            if let Some([r, g, b, a]) = *color {
+                comments.push_str(&format!("# {}: {} {} {} {}\n", name, r, g, b, a));
+            }
+        }
+
+        comments
+    }
+
+    /// Reads the color scheme from the comments of an OFF file. Whatever
+    /// can't be read is left as the default.
+    pub fn from_comments(src: &str) -> Self {
+        let mut scheme = Self::default();
+
+        // The comment on each line, if it has nothing else. The block ends
+        // at the first line that isn't one.
+        let mut comments = src
+            .lines()
+            .map(|line| line.trim_start().strip_prefix('#').map(str::trim));
+
+        if comments.any(|comment| comment == Some(Self::HEADER)) {
+            for comment in comments {
+                let (name, components) = match comment.and_then(|c| c.split_once(':')) {
+                    Some(pair) => pair,
+                    None => break,
+                };
+
+                let components: Vec<f32> =
+                    match components.split_whitespace().map(str::parse).collect() {
+                        Ok(components) => components,
+                        Err(_) => break,
+                    };
+
+                for (other, color) in scheme.colors_mut() {
+                    if other == name.trim() {
+                        *color = ColorData::color_from_components(&components);
+                    }
+                }
+            }
+        }
+
+        scheme
+    }
+}
+
+/// The colors attached to the vertices and faces of a polytope. Elements
+/// without a color are set to `None`, and these lists might be shorter than
+/// the number of elements.
+///
+/// Operations that renumber the vertices or faces of a polytope in place
+/// forget their colors, while keeping the color scheme. Colors should still
+/// be read through [`Self::vertex`] and [`Self::face`], since other elements
+/// might be added after the colored ones.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ColorData {
+    /// The colors of the vertices.
+    pub vertices: Vec<Option<Color>>,
+
+    /// The colors of the faces.
+    pub faces: Vec<Option<Color>>,
+
+    /// The colors of the polytope as a whole.
+    pub scheme: ColorScheme,
+}
+
+impl ColorData {
+    /// Returns whether no element has a color, and the polytope has no color
+    /// scheme.
+    pub fn is_empty(&self) -> bool {
+        self.vertices.iter().chain(&self.faces).all(Option::is_none) && self.scheme.is_empty()
+    }
+
+    /// Forgets the colors of the vertices and faces, keeping the color scheme.
+    /// This must be called whenever these are renumbered.
+    pub fn clear_elements(&mut self) {
+        self.vertices.clear();
+        self.faces.clear();
+    }
+
+    /// Appends the colors of the vertices and faces of another polytope after
+    /// those of this one, as when building a compound out of both. The counts
+    /// are the numbers of vertices and faces of this polytope.
+    pub fn append(&mut self, mut other: Self, vertex_count: usize, face_count: usize) {
+        if !other.vertices.is_empty() {
+            self.vertices.resize(vertex_count, None);
+            self.vertices.append(&mut other.vertices);
+        }
+
+        if !other.faces.is_empty() {
+            self.faces.resize(face_count, None);
+            self.faces.append(&mut other.faces);
+        }
+    }
+
+    /// Returns the color of the vertex with a given index, if any.
+    pub fn vertex(&self, idx: usize) -> Option<Color> {
+        self.vertices.get(idx).copied().flatten()
+    }
+
+    /// Returns the color of the face with a given index, if any.
+    pub fn face(&self, idx: usize) -> Option<Color> {
+        self.faces.get(idx).copied().flatten()
+    }
+
+    /// Builds a color from a list of 3 or 4 components. These are read as
+    /// values between 0 and 255 if any of them is larger than 1, and as values
+    /// between 0 and 1 otherwise. Any other number of components gives `None`.
+    pub fn color_from_components(components: &[f32]) -> Option<Color> {
+        let scale = if components.iter().any(|&c| c > 1.0) {
+            255.0
+        } else {
+            1.0
+        };
+
+        let mut color = match *components {
+            [r, g, b] => [r, g, b, scale],
+            [r, g, b, a] => [r, g, b, a],
+            _ => return None,
+        };
+
+        for c in &mut color {
+            *c = (*c / scale).clamp(0.0, 1.0);
+        }
+        Some(color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks that colors are read from their components.
+    #[test]
+    fn color_from_components() {
+        assert_eq!(
+            ColorData::color_from_components(&[1.0, 0.5, 0.0]),
+            Some([1.0, 0.5, 0.0, 1.0])
+        );
+        assert_eq!(
+            ColorData::color_from_components(&[255.0, 0.0, 51.0, 255.0]),
+            Some([1.0, 0.0, 0.2, 1.0])
+        );
+        assert_eq!(ColorData::color_from_components(&[3.0]), None);
+    }
+
+    /// Checks that color schemes round-trip through OFF comments.
+    #[test]
+    fn color_scheme() {
+        let scheme = ColorScheme {
+            faces: Some([1.0, 0.5, 0.0, 1.0]),
+            edges: None,
+            vertices: Some([0.0, 0.0, 0.25, 0.5]),
+        };
+
+        let comments = scheme.to_comments();
+        assert_eq!(
+            comments,
+            "# Color scheme\n# faces: 1 0.5 0 1\n# vertices: 0 0 0.25 0.5\n"
+        );
+        assert_eq!(ColorScheme::from_comments(&comments), scheme);
+
+        // Other comments and unknown colors are ignored.
+        let src = "# Vertices\n# Color scheme\n# edges: 255 0 0\n# glow: 1 1 1\n\n# faces: 1 1 1";
+        assert_eq!(
+            ColorScheme::from_comments(src),
+            ColorScheme {
+                edges: Some([1.0, 0.0, 0.0, 1.0]),
+                ..Default::default()
+            }
+        );
+
+        assert!(ColorScheme::from_comments("# faces: 1 1 1").is_empty());
+        assert!(ColorScheme::default().to_comments().is_empty());
+    }
+}
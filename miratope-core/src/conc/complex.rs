@@ -0,0 +1,373 @@
+//! Contains polytopal complexes, that is, collections of polytopes glued
+//! together along common faces.
+//!
+//! A [`Complex`] stores its elements rank by rank just like a polytope does,
+//! but it has no maximal element. Its maximal elements, the ones that aren't
+//! contained in any other, can thus have different ranks. Nets, Voronoi
+//! diagrams and patches of tilings are all complexes, and so is any polytope
+//! with its maximal element taken out.
+//!
+//! Complexes are shown and exported as polytopes through
+//! [`Complex::to_polytope`], which pads every maximal element up to the top
+//! rank with ditopes. These are flat, so they don't change how the complex
+//! looks.
+
+use std::collections::HashMap;
+
+use super::{cleanup::vertex_clusters, Concrete};
+use crate::{
+    abs::{AbstractBuilder, AbstractResult, Ranked, Ranks, SubelementList, Subelements},
+    float::Float,
+    geometry::Point,
+    Polytope,
+};
+
+use vec_like::VecLike;
+
+/// A polytopal complex, whose maximal elements can have different ranks.
+#[derive(Clone, Debug)]
+pub struct Complex {
+    /// The positions of the vertices.
+    pub vertices: Vec<Point<f64>>,
+
+    /// The elements of the complex, from the minimal element up to the cells of
+    /// the top rank. Unlike in a polytope, there's no maximal element.
+    ranks: Ranks,
+}
+
+impl Default for Complex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Complex {
+    /// Builds the empty complex.
+    pub fn new() -> Self {
+        Self::from_subelements(Vec::new(), Vec::new())
+    }
+
+    /// Builds a complex from its vertices and the subelements of the elements
+    /// of every rank, starting from the edges.
+    fn from_subelements(vertices: Vec<Point<f64>>, lists: Vec<SubelementList>) -> Self {
+        let mut builder = AbstractBuilder::with_rank_capacity(lists.len() + 1);
+        builder.push_min();
+        if !vertices.is_empty() {
+            builder.push_vertices(vertices.len());
+        }
+        for list in lists {
+            builder.push(list);
+        }
+
+        Self {
+            vertices,
+            ranks: builder.into_ranks(),
+        }
+    }
+
+    /// Builds the complex made out of the facets of a polytope, together with
+    /// all of their elements.
+    pub fn from_polytope(poly: &Concrete) -> Self {
+        let rank = poly.rank();
+        if rank < 2 {
+            return Self::new();
+        }
+
+        let lists = (2..rank)
+            .map(|r| poly[r].iter().map(|el| el.subs.clone()).collect())
+            .collect();
+        Self::from_subelements(poly.vertices.clone(), lists)
+    }
+
+    /// Glues a set of polytopes together into a complex. Vertices that lie
+    /// closer than [`Float::eps`] are merged, and so are the elements that are
+    /// left with the same subelements. Every polytope becomes an element of
+    /// the complex, so the maximal elements can have any rank.
+    pub fn from_cells<I: IntoIterator<Item = Concrete>>(cells: I) -> Self {
+        let cells: Vec<_> = cells.into_iter().filter(|cell| cell.rank() > 0).collect();
+        let rank = match cells.iter().map(|cell| cell.rank()).max() {
+            Some(rank) => rank,
+            None => return Self::new(),
+        };
+
+        let points: Vec<_> = cells
+            .iter()
+            .flat_map(|cell| cell.vertices.iter().cloned())
+            .collect();
+        let (vertex_map, vertices) = vertex_clusters(&points, f64::eps());
+
+        let mut lists: Vec<SubelementList> = (2..=rank).map(|_| SubelementList::new()).collect();
+        let mut indices: Vec<HashMap<Subelements, usize>> =
+            (2..=rank).map(|_| HashMap::new()).collect();
+        let mut offset = 0;
+
+        for cell in &cells {
+            // The indices in the complex of the elements of the cell, rank by
+            // rank.
+            let mut map = vec![
+                Vec::new(),
+                vertex_map[offset..offset + cell.vertices.len()].to_vec(),
+            ];
+            offset += cell.vertices.len();
+
+            for r in 2..=cell.rank() {
+                let mut row = Vec::with_capacity(cell.el_count(r));
+
+                for el in cell[r].iter() {
+                    let mut subs: Subelements =
+                        el.subs.iter().map(|&sub| map[r - 1][sub]).collect();
+                    subs.sort_unstable();
+
+                    let list = &mut lists[r - 2];
+                    let idx = *indices[r - 2].entry(subs.clone()).or_insert_with(|| {
+                        list.push(subs);
+                        list.len() - 1
+                    });
+                    row.push(idx);
+                }
+
+                map.push(row);
+            }
+        }
+
+        Self::from_subelements(vertices, lists)
+    }
+
+    /// Returns the ranks of the complex.
+    pub fn ranks(&self) -> &Ranks {
+        &self.ranks
+    }
+
+    /// Returns the rank of the top cells of the complex, or 0 if it's empty.
+    pub fn rank(&self) -> usize {
+        self.ranks.rank()
+    }
+
+    /// Returns the number of elements of a given rank. Returns 0 if the rank is
+    /// out of bounds.
+    pub fn el_count(&self, rank: usize) -> usize {
+        self.ranks.el_count(rank)
+    }
+
+    /// Returns an iterator over the element counts of the complex, starting
+    /// from the minimal element.
+    pub fn el_count_iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.ranks.el_count_iter()
+    }
+
+    /// Returns the maximal elements of the complex, that is, the ones that
+    /// aren't contained in any other, as pairs of their rank and index.
+    pub fn maximal_elements(&self) -> Vec<(usize, usize)> {
+        let mut maximal = Vec::new();
+
+        for r in 1..=self.rank() {
+            for (idx, el) in self.ranks[r].iter().enumerate() {
+                if el.sups.is_empty() {
+                    maximal.push((r, idx));
+                }
+            }
+        }
+
+        maximal
+    }
+
+    /// Returns whether all of the maximal elements of the complex have the
+    /// top rank.
+    pub fn is_pure(&self) -> bool {
+        let rank = self.rank();
+        self.maximal_elements().iter().all(|&(r, _)| r == rank)
+    }
+
+    /// Gets the element with a given rank and index as a polytope, if it
+    /// exists.
+    pub fn element(&self, rank: usize, idx: usize) -> Option<Concrete> {
+        self.ranks.get_element(rank, idx)?;
+        if rank == 0 {
+            return Some(Concrete::nullitope());
+        }
+
+        // The indices of the elements below, rank by rank, from the top down.
+        let mut levels = vec![vec![idx]];
+        for r in (2..=rank).rev() {
+            let mut below: Vec<usize> = levels
+                .last()
+                .unwrap()
+                .iter()
+                .flat_map(|&i| self.ranks[(r, i)].subs.iter().copied())
+                .collect();
+            below.sort_unstable();
+            below.dedup();
+            levels.push(below);
+        }
+        levels.reverse();
+
+        let mut builder = AbstractBuilder::with_rank_capacity(rank);
+        builder.push_min();
+        builder.push_vertices(levels[0].len());
+        for r in 2..=rank {
+            let positions: HashMap<_, _> = levels[r - 2]
+                .iter()
+                .enumerate()
+                .map(|(pos, &i)| (i, pos))
+                .collect();
+
+            builder.push(
+                levels[r - 1]
+                    .iter()
+                    .map(|&i| {
+                        self.ranks[(r, i)]
+                            .subs
+                            .iter()
+                            .map(|sub| positions[sub])
+                            .collect()
+                    })
+                    .collect(),
+            );
+        }
+
+        let vertices = levels[0]
+            .iter()
+            .map(|&v| self.vertices[v].clone())
+            .collect();
+
+        // Safety: every element of a complex is a polytope.
+        Some(Concrete::new(vertices, unsafe { builder.build() }))
+    }
+
+    /// Turns the complex into a polytope whose facets are its top cells, so
+    /// that it can be shown or exported.
+    ///
+    /// Every other maximal element is first padded up to the top rank by
+    /// taking ditopes over and over. Each of these adds a copy of an element
+    /// that's glued to it along its boundary, so the polytope looks just like
+    /// the complex.
+    pub fn to_polytope(&self) -> Concrete {
+        let rank = self.rank();
+        if rank == 0 {
+            return Concrete::nullitope();
+        }
+
+        let mut vertices = self.vertices.clone();
+        let mut lists: Vec<SubelementList> = (2..=rank)
+            .map(|r| self.ranks[r].iter().map(|el| el.subs.clone()).collect())
+            .collect();
+
+        for (r, mut idx) in self.maximal_elements() {
+            for r in r..rank {
+                let copy = if r == 1 {
+                    vertices.push(vertices[idx].clone());
+                    vertices.len() - 1
+                } else {
+                    let subs = lists[r - 2][idx].clone();
+                    lists[r - 2].push(subs);
+                    lists[r - 2].len() - 1
+                };
+
+                lists[r - 1].push(vec![idx, copy].into());
+                idx = lists[r - 1].len() - 1;
+            }
+        }
+
+        let mut builder = AbstractBuilder::with_rank_capacity(rank + 1);
+        builder.push_min();
+        builder.push_vertices(vertices.len());
+        for list in lists {
+            builder.push(list);
+        }
+        builder.push_max();
+
+        // Safety: the ditope of a polytope is a polytope, and the top cells of
+        // a complex meet face to face.
+        Concrete::new(vertices, unsafe { builder.build() })
+    }
+
+    /// Checks whether every element of the complex is a valid polytope.
+    pub fn is_valid(&self) -> AbstractResult<()> {
+        self.to_polytope().abs.ranks().is_valid()
+    }
+}
+
+impl From<&Concrete> for Complex {
+    fn from(poly: &Concrete) -> Self {
+        Self::from_polytope(poly)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{conc::ConcretePolytope, geometry::Vector};
+
+    use nalgebra::dvector;
+
+    /// Checks the element counts of a complex, and that it's valid.
+    fn test(complex: &Complex, element_counts: &[usize]) {
+        assert_eq!(complex.el_count_iter().collect::<Vec<_>>(), element_counts);
+        complex.is_valid().unwrap();
+    }
+
+    /// Builds complexes out of the facets of polytopes.
+    #[test]
+    fn from_polytope() {
+        let cube = Concrete::hypercube(4);
+        let complex = Complex::from_polytope(&cube);
+        test(&complex, &[1, 8, 12, 6]);
+        assert_eq!(complex.maximal_elements().len(), 6);
+        assert!(complex.is_pure());
+        crate::test(&complex.to_polytope(), [1, 8, 12, 6, 1]);
+        crate::test(&complex.element(3, 0).unwrap(), [1, 4, 4, 1]);
+
+        test(&Complex::from_polytope(&Concrete::point()), &[1]);
+        test(&Complex::new(), &[1]);
+        crate::test(&Complex::new().to_polytope(), [1]);
+    }
+
+    /// Glues polytopes of different ranks together.
+    #[test]
+    fn from_cells() {
+        let square = Concrete::hypercube(3);
+        let mut other = square.clone();
+        other.translate(&Vector::from_vec(vec![1.0, 0.0]));
+
+        let mut segment = Concrete::dyad();
+        segment.vertices = vec![dvector![1.5, 0.5], dvector![2.5, 0.5]];
+        let mut point = Concrete::point();
+        point.vertices = vec![dvector![5.0, 5.0]];
+
+        // The squares share an edge, and the segment touches one of them.
+        let complex = Complex::from_cells(vec![square, other, segment, point]);
+        test(&complex, &[1, 8, 8, 2]);
+        assert!(!complex.is_pure());
+
+        let maximal = complex.maximal_elements();
+        assert_eq!(maximal.len(), 4);
+        assert_eq!(
+            maximal.iter().filter(|(r, _)| *r == 1).count(),
+            1,
+            "the point should be a maximal element"
+        );
+        assert_eq!(maximal.iter().filter(|(r, _)| *r == 2).count(), 1);
+
+        // The point and the segment are padded with ditopes.
+        crate::test(&complex.to_polytope(), [1, 9, 11, 4, 1]);
+
+        let (_, idx) = maximal[0];
+        let point = complex.element(1, idx).unwrap();
+        crate::test(&point, [1, 1]);
+        assert_eq!(point.vertices, vec![dvector![5.0, 5.0]]);
+
+        test(&Complex::from_cells(Vec::new()), &[1]);
+    }
+
+    /// Gets the complex of a Voronoi diagram.
+    #[test]
+    fn voronoi() {
+        let grid: Vec<Point<f64>> = (0..16)
+            .map(|i| dvector![(i % 4) as f64, (i / 4) as f64])
+            .collect();
+        let voronoi = Concrete::voronoi(&grid).unwrap();
+        let complex = Complex::from(&voronoi);
+        test(&complex, &[1, 9, 12, 4]);
+        assert!(complex.is_pure());
+    }
+}
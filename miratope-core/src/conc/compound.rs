@@ -0,0 +1,377 @@
+//! Contains the built-in catalog of regular compounds, such as the stella
+//! octangula or the compound of five cubes.
+//!
+//! Every compound is built as the orbit of a seed polytope under the symmetry
+//! group of a host polytope it's inscribed in, keeping only one copy of every
+//! image. Some compounds are chiral, and are built from only the rotations of
+//! the host instead. The same construction is available for any polytope and
+//! group through [`Concrete::orbit_compound`].
+
+use std::collections::BTreeSet;
+
+use super::{Concrete, ConcretePolytope};
+use crate::{
+    geometry::{Matrix, Point, PointOrd},
+    group::Group,
+    Polytope,
+};
+
+/// The golden ratio.
+const PHI: f64 = 1.618_033_988_749_895;
+
+/// The number of regular compounds in the catalog.
+pub const COMPOUND_COUNT: usize = 11;
+
+/// A regular compound, built as the orbit of a seed under the symmetries of a
+/// host polytope.
+struct CompoundData {
+    /// The name of the compound.
+    name: &'static str,
+
+    /// The vertices of the host, whose symmetries are applied to the seed.
+    host: fn() -> Vec<Point<f64>>,
+
+    /// The vertices of the convex polytope that's copied around.
+    seed: fn() -> Vec<Point<f64>>,
+
+    /// Whether only the rotations of the host are applied to the seed.
+    chiral: bool,
+}
+
+/// The regular compounds in the catalog, first the polyhedra and then the
+/// polychora.
+const COMPOUNDS: [CompoundData; COMPOUND_COUNT] = [
+    CompoundData {
+        name: "Stella octangula",
+        host: cube,
+        seed: tetrahedron,
+        chiral: false,
+    },
+    CompoundData {
+        name: "Compound of five tetrahedra",
+        host: dodecahedron,
+        seed: tetrahedron,
+        chiral: true,
+    },
+    CompoundData {
+        name: "Compound of ten tetrahedra",
+        host: dodecahedron,
+        seed: tetrahedron,
+        chiral: false,
+    },
+    CompoundData {
+        name: "Compound of five cubes",
+        host: dodecahedron,
+        seed: cube,
+        chiral: false,
+    },
+    CompoundData {
+        name: "Compound of five octahedra",
+        host: dodecahedron,
+        seed: octahedron,
+        chiral: false,
+    },
+    CompoundData {
+        name: "Compound of two hexadecachora",
+        host: tesseract,
+        seed: hexadecachoron,
+        chiral: false,
+    },
+    CompoundData {
+        name: "Compound of three hexadecachora",
+        host: icositetrachoron,
+        seed: hexadecachoron_in_icositetrachoron,
+        chiral: false,
+    },
+    CompoundData {
+        name: "Compound of three tesseracts",
+        host: icositetrachoron,
+        seed: tesseract_in_icositetrachoron,
+        chiral: false,
+    },
+    CompoundData {
+        name: "Compound of 25 icositetrachora",
+        host: hexacosichoron,
+        seed: icositetrachoron_in_hexacosichoron,
+        chiral: false,
+    },
+    CompoundData {
+        name: "Compound of 75 hexadecachora",
+        host: hexacosichoron,
+        seed: orthoplex_vertices,
+        chiral: false,
+    },
+    CompoundData {
+        name: "Compound of 75 tesseracts",
+        host: hexacosichoron,
+        seed: half_tesseract,
+        chiral: false,
+    },
+];
+
+/// Returns the name of the regular compound with a given index, starting from
+/// 1, or `None` if there's no such compound.
+pub fn compound_name(idx: usize) -> Option<&'static str> {
+    Some(COMPOUNDS.get(idx.checked_sub(1)?)?.name)
+}
+
+/// Returns every permutation of the indices up to `n`, or only the even ones.
+fn permutations(n: usize, even: bool) -> Vec<Vec<usize>> {
+    if n == 0 {
+        return vec![Vec::new()];
+    }
+
+    let mut perms = Vec::new();
+    for perm in permutations(n - 1, false) {
+        // Inserts the last index at every position, which swaps it with the
+        // indices after it.
+        for pos in 0..n {
+            if !even || (inversions(&perm) + n - 1 - pos) % 2 == 0 {
+                let mut perm = perm.clone();
+                perm.insert(pos, n - 1);
+                perms.push(perm);
+            }
+        }
+    }
+
+    perms
+}
+
+/// Returns the number of inversions of a permutation.
+fn inversions(perm: &[usize]) -> usize {
+    perm.iter()
+        .enumerate()
+        .map(|(i, a)| perm[i + 1..].iter().filter(|&b| b < a).count())
+        .sum()
+}
+
+/// Returns every point whose coordinates are an (even) permutation of the
+/// given ones, with any signs.
+pub(super) fn orbit_points(coords: &[f64], even: bool) -> Vec<Point<f64>> {
+    let mut found = BTreeSet::new();
+    let mut points = Vec::new();
+
+    for perm in permutations(coords.len(), even) {
+        for signs in 0..(1 << coords.len()) {
+            let point = Point::from_iterator(
+                coords.len(),
+                perm.iter().enumerate().map(|(i, &j)| {
+                    if signs & (1 << i) == 0 {
+                        coords[j]
+                    } else {
+                        -coords[j]
+                    }
+                }),
+            );
+
+            if found.insert(PointOrd::new(point.clone())) {
+                points.push(point);
+            }
+        }
+    }
+
+    points
+}
+
+/// The vertices of a cube with edge length 2.
+fn cube() -> Vec<Point<f64>> {
+    orbit_points(&[1.0; 3], false)
+}
+
+/// The vertices of a tetrahedron inscribed in [`cube`].
+fn tetrahedron() -> Vec<Point<f64>> {
+    cube()
+        .into_iter()
+        .filter(|p| p.iter().product::<f64>() > 0.0)
+        .collect()
+}
+
+/// The vertices of an octahedron inscribed in [`dodecahedron`]'s
+/// icosidodecahedron.
+fn octahedron() -> Vec<Point<f64>> {
+    orbit_points(&[PHI, 0.0, 0.0], false)
+}
+
+/// The vertices of a dodecahedron with [`cube`] inscribed in it.
+fn dodecahedron() -> Vec<Point<f64>> {
+    let mut vertices = cube();
+    vertices.append(&mut orbit_points(&[0.0, 1.0 / PHI, PHI], true));
+    vertices
+}
+
+/// The vertices of a tesseract with edge length 2.
+fn tesseract() -> Vec<Point<f64>> {
+    orbit_points(&[1.0; 4], false)
+}
+
+/// The vertices of a hexadecachoron inscribed in [`tesseract`].
+fn hexadecachoron() -> Vec<Point<f64>> {
+    tesseract()
+        .into_iter()
+        .filter(|p| p.iter().product::<f64>() > 0.0)
+        .collect()
+}
+
+/// The vertices of an icositetrachoron, with two non-zero coordinates each.
+fn icositetrachoron() -> Vec<Point<f64>> {
+    orbit_points(&[1.0, 1.0, 0.0, 0.0], false)
+}
+
+/// Returns whether the non-zero coordinates of a point lie on the first two
+/// or on the last two axes.
+fn is_split(p: &Point<f64>) -> bool {
+    p[0] == 0.0 && p[1] == 0.0 || p[2] == 0.0 && p[3] == 0.0
+}
+
+/// The vertices of a hexadecachoron inscribed in [`icositetrachoron`].
+fn hexadecachoron_in_icositetrachoron() -> Vec<Point<f64>> {
+    icositetrachoron().into_iter().filter(is_split).collect()
+}
+
+/// The vertices of a tesseract inscribed in [`icositetrachoron`], made out of
+/// those not in [`hexadecachoron_in_icositetrachoron`].
+fn tesseract_in_icositetrachoron() -> Vec<Point<f64>> {
+    icositetrachoron()
+        .into_iter()
+        .filter(|p| !is_split(p))
+        .collect()
+}
+
+/// The vertices of a tesseract with edge length 1.
+fn half_tesseract() -> Vec<Point<f64>> {
+    orbit_points(&[0.5; 4], false)
+}
+
+/// The vertices of a hexadecachoron with unit circumradius.
+fn orthoplex_vertices() -> Vec<Point<f64>> {
+    orbit_points(&[1.0, 0.0, 0.0, 0.0], false)
+}
+
+/// The vertices of an icositetrachoron inscribed in [`hexacosichoron`], made
+/// out of [`half_tesseract`] and [`orthoplex_vertices`].
+fn icositetrachoron_in_hexacosichoron() -> Vec<Point<f64>> {
+    let mut vertices = half_tesseract();
+    vertices.append(&mut orthoplex_vertices());
+    vertices
+}
+
+/// The vertices of a hexacosichoron with unit circumradius.
+fn hexacosichoron() -> Vec<Point<f64>> {
+    let mut vertices = icositetrachoron_in_hexacosichoron();
+    vertices.append(&mut orbit_points(&[PHI / 2.0, 0.5, 0.5 / PHI, 0.0], true));
+    vertices
+}
+
+impl Concrete {
+    /// Builds the compound of the distinct images of a polytope under a group
+    /// of isometries, such as the symmetry group of some other polytope or one
+    /// of its subgroups. Two images are considered the same whenever they
+    /// have the same vertices, so that coincident copies are only kept once.
+    ///
+    /// Returns `None` if the matrices of the group don't match the dimension
+    /// of the polytope.
+    pub fn orbit_compound<I: Iterator<Item = Matrix<f64>>>(&self, group: Group<I>) -> Option<Self> {
+        let dim = self.dim_or();
+        let mut found = BTreeSet::new();
+        let mut components = Vec::new();
+
+        for isometry in group {
+            if isometry.ncols() != dim || isometry.nrows() != dim {
+                return None;
+            }
+
+            let mut vertices: Vec<_> = self
+                .vertices
+                .iter()
+                .map(|v| PointOrd::new(&isometry * v))
+                .collect();
+            vertices.sort();
+
+            if found.insert(vertices) {
+                components.push(self.clone().apply(&isometry));
+            }
+        }
+
+        Some(Self::compound(components.into_iter()))
+    }
+
+    /// Builds the regular compound with a given index, starting from 1, with
+    /// unit edge length. The compounds with many components can take a while.
+    ///
+    /// Returns `None` if there's no such compound.
+    pub fn regular_compound(idx: usize) -> Option<Self> {
+        let data = COMPOUNDS.get(idx.checked_sub(1)?)?;
+        let mut host = Self::convex_hull(&(data.host)())?;
+        let seed = Self::convex_hull(&(data.seed)())?;
+
+        let mut compound = if data.chiral {
+            seed.orbit_compound(host.get_rotation_group().0)?
+        } else {
+            seed.orbit_compound(host.get_symmetry_group().0)?
+        };
+
+        let edge_len = compound.edge_len(0)?;
+        compound.scale(1.0 / edge_len);
+        Some(compound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn permutations() {
+        assert_eq!(super::permutations(4, false).len(), 24);
+        assert_eq!(super::permutations(4, true).len(), 12);
+        assert!(super::permutations(3, true)
+            .iter()
+            .all(|perm| inversions(perm) % 2 == 0));
+    }
+
+    #[test]
+    fn hosts() {
+        for (vertices, count) in [
+            (dodecahedron(), vec![1, 20, 30, 12, 1]),
+            (icositetrachoron(), vec![1, 24, 96, 96, 24, 1]),
+            (hexacosichoron(), vec![1, 120, 720, 1200, 600, 1]),
+        ] {
+            let poly = Concrete::convex_hull(&vertices).unwrap();
+            crate::test(&poly, count);
+            assert!(poly.is_equilateral());
+        }
+    }
+
+    #[test]
+    fn regular_compound() {
+        let components = [2, 5, 10, 5, 5, 2, 3, 3, 25, 75, 75];
+
+        for (idx, &count) in (1..=COMPOUND_COUNT).zip(components.iter()) {
+            let name = compound_name(idx).unwrap();
+            let poly = Concrete::regular_compound(idx).unwrap();
+            assert_eq!(poly.components().len(), count, "{}", name);
+            assert!(poly.is_equilateral_with(1.0), "{}", name);
+        }
+
+        assert_eq!(compound_name(1), Some("Stella octangula"));
+        assert!(Concrete::regular_compound(0).is_none());
+        assert!(Concrete::regular_compound(COMPOUND_COUNT + 1).is_none());
+    }
+
+    #[test]
+    fn orbit_compound() {
+        // The images of a cube under the symmetries of a square prism are the
+        // same cube, but those under an icosahedral group are five cubes.
+        let poly = Concrete::cube();
+        let group = Group::hypercube(2).pad(1);
+        crate::test(&poly.orbit_compound(group).unwrap(), [1, 8, 12, 6, 1]);
+
+        let mut host = Concrete::convex_hull(&dodecahedron()).unwrap();
+        let seed = Concrete::convex_hull(&cube()).unwrap();
+        let compound = seed.orbit_compound(host.get_rotation_group().0).unwrap();
+        assert_eq!(compound.components().len(), 5);
+
+        // Groups of the wrong dimension are rejected.
+        assert!(poly.orbit_compound(Group::hypercube(4)).is_none());
+    }
+}
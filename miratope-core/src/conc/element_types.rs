@@ -207,6 +207,28 @@ impl Concrete {
         self.element_types_common().1
     }
 
+    /// Returns the indices of the elements of each type, indexed by rank and
+    /// then by the index of the type, in the same order as
+    /// [`Self::element_types`].
+    pub fn elements_by_type(&self) -> Vec<Vec<Vec<usize>>> {
+        let (types, type_of_element) = self.element_types_common();
+
+        types
+            .iter()
+            .enumerate()
+            .map(|(r, types)| {
+                // The minimal and maximal elements aren't given types.
+                let mut elements = vec![Vec::new(); types.len()];
+                if !types.is_empty() {
+                    for (idx, &ty) in type_of_element[r].iter().enumerate() {
+                        elements[ty].push(idx);
+                    }
+                }
+                elements
+            })
+            .collect()
+    }
+
     /// Prints all element types of a polytope into the console.
     pub fn print_element_types(&self) {
         for (r, types) in self.element_types().into_iter().enumerate().skip(1) {
@@ -231,3 +253,33 @@ impl Concrete {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{conc::Concrete, Polytope};
+
+    /// Checks that the elements of each type are listed in full.
+    #[test]
+    fn elements_by_type() {
+        let prism = Concrete::polygon(3).prism();
+        let types = prism.element_types();
+        let elements = prism.elements_by_type();
+
+        for (types, elements) in types.iter().zip(&elements) {
+            assert_eq!(types.len(), elements.len());
+            for (ty, elements) in types.iter().zip(elements) {
+                assert_eq!(ty.count, elements.len());
+                assert!(elements.contains(&ty.example));
+            }
+        }
+
+        let counts = |r: usize| {
+            let mut counts: Vec<_> = elements[r].iter().map(Vec::len).collect();
+            counts.sort_unstable();
+            counts
+        };
+        assert_eq!(counts(1), vec![6]);
+        assert_eq!(counts(2), vec![3, 6]);
+        assert_eq!(counts(3), vec![2, 3]);
+    }
+}
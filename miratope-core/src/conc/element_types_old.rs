@@ -150,7 +150,7 @@ impl Concrete {
 
             // Searches for the greatest length smaller than the current one.
             if let Some((prev_len, indices)) = edge_lengths.range_mut(..len).next_back() {
-                if abs_diff_eq!(prev_len.0, len.0, epsilon = f64::EPS) {
+                if abs_diff_eq!(prev_len.0, len.0, epsilon = f64::eps()) {
                     indices.push(edge_idx);
                     continue;
                 }
@@ -158,7 +158,7 @@ impl Concrete {
 
             // Searches for the smallest length greater than the current one.
             if let Some((next_len, indices)) = edge_lengths.range_mut(len..).next() {
-                if abs_diff_eq!(next_len.0, len.0, epsilon = f64::EPS) {
+                if abs_diff_eq!(next_len.0, len.0, epsilon = f64::eps()) {
                     indices.push(edge_idx);
                     continue;
                 }
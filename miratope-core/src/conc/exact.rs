@@ -0,0 +1,471 @@
+//! Recognizes the lengths and coordinates of a polytope as exact values, so
+//! that they can be copied into the infobox of a wiki article.
+//!
+//! Values are recognized as quadratic surds, numbers of the form
+//! (a + b√r) / d, by searching for integer relations between their powers
+//! with the [PSLQ algorithm](https://en.wikipedia.org/wiki/Integer_relation_algorithm).
+
+use std::fmt::Display;
+
+use super::{Concrete, ConcretePolytope};
+use crate::{abs::Ranked, float::Float};
+
+/// The largest coefficient that an integer relation may have.
+const MAX_COEFF: f64 = 100.0;
+
+/// The largest residue that an integer relation between values normalized to
+/// unit length may have.
+const RELATION_EPS: f64 = 1e-10;
+
+/// The number of iterations after which the PSLQ algorithm gives up.
+const MAX_ITERATIONS: usize = 200;
+
+/// Finds a non-trivial integer relation between some real numbers, that is, a
+/// list of integers, none greater than `max_coeff` in absolute value, whose dot
+/// product with the numbers is zero up to floating-point error.
+///
+/// Returns `None` if no relation was found. A relation found might not be the
+/// one with the smallest coefficients.
+pub fn integer_relation(xs: &[f64], max_coeff: f64) -> Option<Vec<i64>> {
+    let n = xs.len();
+    if n < 2 {
+        return None;
+    }
+
+    let norm = xs.iter().map(|x| x * x).sum::<f64>().sqrt();
+    if norm == 0.0 {
+        return None;
+    }
+    let x: Vec<f64> = xs.iter().map(|x| x / norm).collect();
+
+    // A zero value gives a trivial relation, which breaks the algorithm below.
+    if let Some(i) = x.iter().position(|x| x.abs() < RELATION_EPS) {
+        let mut relation = vec![0; n];
+        relation[i] = 1;
+        return Some(relation);
+    }
+
+    // The partial norms of the vector.
+    let mut s = vec![0.0; n];
+    let mut sum = 0.0;
+    for k in (0..n).rev() {
+        sum += x[k] * x[k];
+        s[k] = sum.sqrt();
+    }
+
+    // The lower trapezoidal matrix whose columns span the complement of x.
+    let mut h = vec![vec![0.0; n - 1]; n];
+    for i in 0..n {
+        for j in 0..(n - 1).min(i + 1) {
+            h[i][j] = if i == j {
+                s[j + 1] / s[j]
+            } else {
+                -x[i] * x[j] / (s[j] * s[j + 1])
+            };
+        }
+    }
+
+    let mut y = x;
+    let mut a = vec![vec![0.0; n]; n];
+    let mut b = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        a[i][i] = 1.0;
+        b[i][i] = 1.0;
+    }
+
+    reduce(&mut h, &mut y, &mut a, &mut b, 1, n);
+    let gamma = (4.0f64 / 3.0).sqrt();
+
+    for _ in 0..MAX_ITERATIONS {
+        // Swaps the rows where the diagonal entry of H is the largest.
+        let m = (0..n - 1)
+            .max_by(|&i, &j| {
+                let hi = gamma.powi(i as i32 + 1) * h[i][i].abs();
+                let hj = gamma.powi(j as i32 + 1) * h[j][j].abs();
+                hi.partial_cmp(&hj).unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .unwrap();
+
+        y.swap(m, m + 1);
+        a.swap(m, m + 1);
+        h.swap(m, m + 1);
+        for row in &mut b {
+            row.swap(m, m + 1);
+        }
+
+        // Restores H to be lower trapezoidal.
+        if m + 2 < n {
+            let t0 = h[m][m].hypot(h[m][m + 1]);
+            let (t1, t2) = (h[m][m] / t0, h[m][m + 1] / t0);
+            for row in h.iter_mut().skip(m) {
+                let (t3, t4) = (row[m], row[m + 1]);
+                row[m] = t1 * t3 + t2 * t4;
+                row[m + 1] = t1 * t4 - t2 * t3;
+            }
+        }
+
+        reduce(&mut h, &mut y, &mut a, &mut b, m + 1, m + 2);
+
+        // A small entry of y means that the matching column of B is a
+        // relation.
+        if let Some(j) = (0..n).find(|&j| y[j].abs() < RELATION_EPS) {
+            let relation: Vec<_> = (0..n).map(|i| b[i][j]).collect();
+            let max = relation.iter().fold(0.0f64, |max, c| max.max(c.abs()));
+            let residue = relation.iter().zip(xs).map(|(c, x)| c * x).sum::<f64>();
+
+            return (max <= max_coeff && residue.abs() < RELATION_EPS * norm * max)
+                .then(|| relation.into_iter().map(|c| c as i64).collect());
+        }
+
+        // Any relation must have a coefficient larger than this bound.
+        let bound = h
+            .iter()
+            .take(n - 1)
+            .enumerate()
+            .fold(0.0f64, |max, (i, row)| max.max(row[i].abs()));
+        if bound == 0.0 || 1.0 / bound > max_coeff {
+            return None;
+        }
+    }
+
+    None
+}
+
+/// Reduces the entries of the matrix H below its diagonal, from a given row
+/// onwards and up to a given column, updating the other matrices of the PSLQ
+/// algorithm to match.
+fn reduce(
+    h: &mut [Vec<f64>],
+    y: &mut [f64],
+    a: &mut [Vec<f64>],
+    b: &mut [Vec<f64>],
+    start: usize,
+    end: usize,
+) {
+    let n = y.len();
+
+    for i in start..n {
+        for j in (0..i.min(end)).rev() {
+            let t = (h[i][j] / h[j][j]).round();
+            if t == 0.0 {
+                continue;
+            }
+
+            y[j] += t * y[i];
+            let (top, bottom) = h.split_at_mut(i);
+            for (hik, hjk) in bottom[0].iter_mut().zip(&top[j]).take(j + 1) {
+                *hik -= t * hjk;
+            }
+            for k in 0..n {
+                a[i][k] -= t * a[j][k];
+                b[k][j] += t * b[k][i];
+            }
+        }
+    }
+}
+
+/// Returns the greatest common divisor of two integers.
+fn gcd(mut a: i64, mut b: i64) -> i64 {
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+
+    a.abs()
+}
+
+/// A quadratic surd, a number of the form (a + b√r) / d, with r squarefree
+/// and d positive. Rational numbers have b = 0.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Surd {
+    /// The rational part of the numerator.
+    pub a: i64,
+
+    /// The coefficient of the square root in the numerator.
+    pub b: i64,
+
+    /// The squarefree number under the square root.
+    pub r: i64,
+
+    /// The denominator.
+    pub d: i64,
+}
+
+impl Surd {
+    /// Builds a surd in lowest terms.
+    pub fn new(mut a: i64, mut b: i64, mut r: i64, mut d: i64) -> Self {
+        // Takes the square factors out of the root.
+        let mut k = 2;
+        while k * k <= r {
+            while r % (k * k) == 0 {
+                r /= k * k;
+                b *= k;
+            }
+            k += 1;
+        }
+        if r <= 1 {
+            a += b * r;
+            b = 0;
+        }
+        if b == 0 {
+            r = 1;
+        }
+
+        let g = gcd(gcd(a, b), d);
+        if g != 0 {
+            a /= g;
+            b /= g;
+            d /= g;
+        }
+        if d < 0 {
+            a = -a;
+            b = -b;
+            d = -d;
+        }
+
+        Self { a, b, r, d }
+    }
+
+    /// Returns the value of the surd.
+    pub fn value(self) -> f64 {
+        (self.a as f64 + self.b as f64 * (self.r as f64).sqrt()) / self.d as f64
+    }
+
+    /// Returns whether the surd is a rational number.
+    pub fn is_rational(self) -> bool {
+        self.b == 0
+    }
+
+    /// Recognizes a number as a quadratic surd, or returns `None` if it isn't
+    /// one with small enough coefficients.
+    pub fn recognize(x: f64) -> Option<Self> {
+        if !x.is_finite() {
+            return None;
+        }
+        if x.abs() < f64::eps() {
+            return Some(Self::new(0, 0, 1, 1));
+        }
+
+        // Rational numbers.
+        if let Some(rel) = integer_relation(&[1.0, x], MAX_COEFF) {
+            if rel[1] != 0 {
+                return Some(Self::new(-rel[0], 0, 1, rel[1]));
+            }
+        }
+
+        // Roots of quadratic polynomials c + bx + ax².
+        let rel = integer_relation(&[1.0, x, x * x], MAX_COEFF)?;
+        let (c, b, a) = (rel[0], rel[1], rel[2]);
+        if a == 0 {
+            return None;
+        }
+
+        let disc = b * b - 4 * a * c;
+        if disc < 0 {
+            return None;
+        }
+
+        // Picks the root that's closest to the number.
+        [1, -1]
+            .iter()
+            .map(|sign| Self::new(-b, *sign, disc, 2 * a))
+            .find(|surd| (surd.value() - x).abs() < f64::eps() * x.abs().max(1.0))
+    }
+}
+
+impl Display for Surd {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self { a, b, r, d } = *self;
+
+        // Writes the numerator.
+        let parenthesize = d != 1 && a != 0 && b != 0;
+        if parenthesize {
+            write!(f, "(")?;
+        }
+        if a != 0 || b == 0 {
+            write!(f, "{}", a)?;
+        }
+        if b != 0 {
+            if b < 0 {
+                write!(f, "-")?;
+            } else if a != 0 {
+                write!(f, "+")?;
+            }
+            if b.abs() != 1 {
+                write!(f, "{}", b.abs())?;
+            }
+            write!(f, "√{}", r)?;
+        }
+        if parenthesize {
+            write!(f, ")")?;
+        }
+
+        if d != 1 {
+            write!(f, "/{}", d)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A value measured from a polytope, along with its exact form if it could be
+/// recognized.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ExactValue {
+    /// The floating-point value.
+    pub value: f64,
+
+    /// The value as a quadratic surd, if it was recognized as one.
+    pub surd: Option<Surd>,
+}
+
+impl ExactValue {
+    /// Recognizes a value.
+    pub fn new(value: f64) -> Self {
+        Self {
+            value,
+            surd: Surd::recognize(value),
+        }
+    }
+}
+
+impl Display for ExactValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.surd {
+            Some(surd) if surd.is_rational() => write!(f, "{}", surd),
+            Some(surd) => write!(f, "{} ≈ {:.6}", surd, self.value),
+            None => write!(f, "{}", self.value),
+        }
+    }
+}
+
+/// A report of the edge lengths, circumradius and vertex coordinates of a
+/// polytope, written as exact values where possible.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExactReport {
+    /// The distinct edge lengths, from shortest to longest.
+    pub edge_lengths: Vec<ExactValue>,
+
+    /// The circumradius, if the polytope has a circumsphere.
+    pub circumradius: Option<ExactValue>,
+
+    /// The coordinates of every vertex.
+    pub vertices: Vec<Vec<ExactValue>>,
+}
+
+impl Display for ExactReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Edge lengths: ")?;
+        if self.edge_lengths.is_empty() {
+            write!(f, "none")?;
+        }
+        for (i, len) in self.edge_lengths.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", len)?;
+        }
+        writeln!(f)?;
+
+        match &self.circumradius {
+            Some(radius) => writeln!(f, "Circumradius: {}", radius)?,
+            None => writeln!(f, "Circumradius: none")?,
+        }
+
+        write!(f, "Vertices:")?;
+        for vertex in &self.vertices {
+            write!(f, "\n(")?;
+            for (i, x) in vertex.iter().enumerate() {
+                if i != 0 {
+                    write!(f, ", ")?;
+                }
+
+                // Approximations would clutter the coordinates.
+                match x.surd {
+                    Some(surd) => write!(f, "{}", surd)?,
+                    None => write!(f, "{}", x.value)?,
+                }
+            }
+            write!(f, ")")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Concrete {
+    /// Measures the edge lengths, circumradius and vertex coordinates of a
+    /// polytope, and attempts to recognize them as exact values.
+    pub fn exact_report(&self) -> ExactReport {
+        let mut lengths: Vec<f64> = (0..self.edge_count())
+            .filter_map(|idx| self.edge_len(idx))
+            .collect();
+        lengths.sort_by(|x, y| x.partial_cmp(y).unwrap_or(std::cmp::Ordering::Equal));
+        lengths.dedup_by(|x, y| (*x - *y).abs() < f64::eps());
+
+        ExactReport {
+            edge_lengths: lengths.into_iter().map(ExactValue::new).collect(),
+            circumradius: self
+                .circumsphere()
+                .map(|sphere| ExactValue::new(sphere.radius())),
+            vertices: self
+                .vertices
+                .iter()
+                .map(|v| v.iter().map(|&x| ExactValue::new(x)).collect())
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Polytope;
+
+    /// Checks that a number is recognized as the expected surd.
+    fn assert_surd(x: f64, expected: &str) {
+        match Surd::recognize(x) {
+            Some(surd) => assert_eq!(surd.to_string(), expected, "wrong surd for {}", x),
+            None => panic!("{} wasn't recognized as {}", x, expected),
+        }
+    }
+
+    /// Checks that some common values are recognized.
+    #[test]
+    fn recognize() {
+        assert_surd(0.0, "0");
+        assert_surd(3.0, "3");
+        assert_surd(-0.5, "-1/2");
+        assert_surd(2.0f64.sqrt(), "√2");
+        assert_surd(2.0f64.sqrt() / 2.0, "√2/2");
+        assert_surd(-3.0 * 3.0f64.sqrt(), "-3√3");
+        assert_surd((1.0 + 5.0f64.sqrt()) / 2.0, "(1+√5)/2");
+        assert_surd((1.0 - 5.0f64.sqrt()) / 2.0, "(1-√5)/2");
+        assert_surd(1.0 + 2.0f64.sqrt(), "1+√2");
+
+        assert_eq!(Surd::recognize(std::f64::consts::PI), None);
+        assert_eq!(Surd::recognize(2.0f64.cbrt()), None);
+    }
+
+    /// Checks the report of a few polytopes.
+    #[test]
+    fn exact_report() {
+        let report = Concrete::cube().exact_report();
+        assert_eq!(report.edge_lengths.len(), 1);
+        assert_eq!(report.edge_lengths[0].to_string(), "1");
+        assert_eq!(report.circumradius.unwrap().to_string(), "√3/2 ≈ 0.866025");
+        assert_eq!(report.vertices.len(), 8);
+
+        let report = Concrete::polygon(5).exact_report();
+        assert_eq!(report.circumradius.unwrap().to_string(), "1");
+        assert_eq!(
+            report.edge_lengths[0].surd, None,
+            "the edge length of a pentagon isn't a quadratic surd"
+        );
+
+        let text = Concrete::polygon(4).exact_report().to_string();
+        assert!(text.starts_with("Edge lengths: √2 ≈ 1.414214\nCircumradius: 1\n"));
+        assert!(text.contains("(0, 1)") && text.contains("(-1, 0)"));
+    }
+}
@@ -244,7 +244,7 @@ fn faceting_subdim(
         for vertex in 0..total_vert_count {
             if vertex != rep && !checked[rep][vertex] {
                 if let Some(e_l) = edge_length {
-                    if ((&points[vertex].0-&points[rep].0).norm() - e_l).abs() > f64::EPS {
+                    if ((&points[vertex].0-&points[rep].0).norm() - e_l).abs() > f64::eps() {
                         continue
                     }
                 }
@@ -279,7 +279,7 @@ fn faceting_subdim(
             'c: loop {
                 if let Some(e_l) = edge_length {
                     for v in &new_vertices {
-                        if ((&points[*v].0-&points[rep[0]].0).norm() - e_l).abs() > f64::EPS {
+                        if ((&points[*v].0-&points[rep[0]].0).norm() - e_l).abs() > f64::eps() {
                             break 'c;
                         }
                     }
@@ -298,7 +298,7 @@ fn faceting_subdim(
 
                     let mut hyperplane_vertices = Vec::new();
                     for (idx, v) in flat_points.iter().enumerate() {
-                        if hyperplane.distance(&v.0) < f64::EPS {
+                        if hyperplane.distance(&v.0) < f64::eps() {
                             hyperplane_vertices.push(idx);
                         }
                     }
@@ -682,7 +682,12 @@ fn faceting_subdim(
 impl Concrete {
     /// Enumerates the facetings of a polytope under a provided symmetry group or vertex map.
     /// If the symmetry group is not provided, it uses the full symmetry of the polytope.
-    pub fn faceting(
+    ///
+    /// Throughout each step, `progress` is called with the amount of work done
+    /// and the total amount of work, or 0 if it's unknown. The enumeration
+    /// stops and returns nothing if it returns `false`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn faceting<F: FnMut(usize, usize) -> bool>(
         &mut self,
         symmetry: GroupEnum,
         edge_length: Option<f64>,
@@ -691,6 +696,7 @@ impl Concrete {
         include_compounds: bool,
         save: bool,
         save_facets: bool,
+        mut progress: F,
     ) -> Vec<(Concrete, Option<String>)> {
         let rank = self.rank();
 
@@ -762,7 +768,7 @@ impl Concrete {
             for vertex in 0..vertices.len() {
                 if vertex != rep && !checked[rep][vertex] {
                     if let Some(e_l) = edge_length {
-                        if ((&self.vertices[vertex]-&self.vertices[rep]).norm() - e_l).abs() > f64::EPS {
+                        if ((&self.vertices[vertex]-&self.vertices[rep]).norm() - e_l).abs() > f64::eps() {
                             continue
                         }
                     }
@@ -785,7 +791,12 @@ impl Concrete {
         let mut checked = HashSet::new();
         let mut hyperplanes_vertices = Vec::new();
 
-        for pair_orbit in pair_orbits {
+        let pair_orbit_count = pair_orbits.len();
+        for (done, pair_orbit) in pair_orbits.into_iter().enumerate() {
+            if !progress(done, pair_orbit_count) {
+                return Vec::new();
+            }
+
             let rep = &pair_orbit[0];
 
             let mut new_vertices = vec![0; rank-3];
@@ -794,7 +805,7 @@ impl Concrete {
                 'c: loop {
                     if let Some(e_l) = edge_length {
                         for v in &new_vertices {
-                            if ((&self.vertices[*v]-&self.vertices[rep[0]]).norm() - e_l).abs() > f64::EPS {
+                            if ((&self.vertices[*v]-&self.vertices[rep[0]]).norm() - e_l).abs() > f64::eps() {
                                 break 'c;
                             }
                         }
@@ -812,7 +823,7 @@ impl Concrete {
                     if hyperplane.is_hyperplane() {
                         let mut hyperplane_vertices = Vec::new();
                         for (idx, v) in self.vertices.iter().enumerate() {
-                            if hyperplane.distance(&v) < f64::EPS {
+                            if hyperplane.distance(v) < f64::eps() {
                                 hyperplane_vertices.push(idx);
                             }
                         }
@@ -876,6 +887,10 @@ impl Concrete {
         let mut ff_counts = Vec::new();
 
         for (idx, orbit) in hyperplane_orbits.iter().enumerate() {
+            if !progress(idx, hyperplane_orbits.len()) {
+                return Vec::new();
+            }
+
             let (hp, hp_v) = (orbit[0].clone(), hyperplanes_vertices[idx][0].clone());
             let mut stabilizer = Vec::new();
             for row in &vertex_map {
@@ -1017,6 +1032,11 @@ impl Concrete {
         let mut facets = vec![(0, 0)];
 
         'l: loop {
+            // We can't tell how many combinations are left.
+            if !progress(output_facets.len(), 0) {
+                return Vec::new();
+            }
+
             loop {
                 let t = facets.last_mut().unwrap();
                 if t.0 >= possible_facets.len() {
@@ -1151,7 +1171,12 @@ impl Concrete {
         let mut used_facets = HashMap::new(); // used for outputting the facets at the end if `save_facets` is `true`.
         let mut faceting_idx = 0; // We used to use `output.len()` but this doesn't work if you skip outputting the polytopes.
 
-        for facets in output_facets {
+        let faceting_count = output_facets.len();
+        for (done, facets) in output_facets.into_iter().enumerate() {
+            if !progress(done, faceting_count) {
+                return Vec::new();
+            }
+
             if !save && !save_facets {
                 let mut facets_fmt = String::new();
                 for facet in &facets {
@@ -1213,7 +1238,7 @@ impl Concrete {
                     for edge in &mut edges {
                         edge.subs.sort();
                     }
-                    edges.0.sort_by(|a, b| a.subs.cmp(&b.subs));
+                    edges.as_inner_mut().sort_by(|a, b| a.subs.cmp(&b.subs));
                     if let Some(_) = of_this_orbit.get(&edges) {
                         continue;
                     }
@@ -1323,10 +1348,7 @@ impl Concrete {
                         new_vertices.push(self.vertices[i].clone());
                     }
 
-                    let poly = Concrete {
-                        vertices: new_vertices,
-                        abs,
-                    };
+                    let poly = Concrete::new(new_vertices, abs);
 
                     let mut facets_fmt = String::new();
                     for facet in &facets {
@@ -1374,4 +1396,81 @@ impl Concrete {
         println!("\nFaceting complete\n");
         return output
     }
-}
\ No newline at end of file
+
+    /// Searches for the noble polytopes, that is, the vertex- and
+    /// facet-transitive ones, among the facetings of a polytope whose vertices
+    /// form a single orbit under the provided symmetry group or vertex map.
+    ///
+    /// Returns `None` if the vertices split into several orbits. Progress is
+    /// reported and the search is stopped as in [`Self::faceting`].
+    pub fn noble_facetings<F: FnMut(usize, usize) -> bool>(
+        &mut self,
+        symmetry: GroupEnum,
+        edge_length: Option<f64>,
+        max_per_hyperplane: Option<usize>,
+        progress: F,
+    ) -> Option<Vec<(Concrete, Option<String>)>> {
+        let vertex_map = match symmetry {
+            GroupEnum::ConcGroup(group) => self.get_vertex_map(group),
+            GroupEnum::VertexMap(a) => a,
+            GroupEnum::Chiral(true) => self.get_rotation_group().1,
+            GroupEnum::Chiral(false) => self.get_symmetry_group().1,
+        };
+
+        // Every faceting uses whole vertex orbits, so this makes every one of
+        // them vertex-transitive.
+        let orbit: HashSet<_> = vertex_map.iter().map(|row| row[0]).collect();
+        if orbit.len() != self.vertices.len() {
+            return None;
+        }
+
+        // A single facet orbit makes every faceting facet-transitive.
+        let facetings = self.faceting(
+            GroupEnum::VertexMap(vertex_map),
+            edge_length,
+            Some(1),
+            max_per_hyperplane,
+            false,
+            true,
+            false,
+            progress,
+        );
+
+        Some(
+            facetings
+                .into_iter()
+                .enumerate()
+                .map(|(idx, (poly, _))| (poly, Some(format!("noble {}", idx))))
+                .collect(),
+        )
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The noble facetings of the cube are the cube itself and the stella
+    /// octangula.
+    #[test]
+    fn noble_cube() {
+        let mut cube = Concrete::cube();
+        let mut nobles = cube
+            .noble_facetings(GroupEnum::Chiral(false), None, None, |_, _| true)
+            .unwrap();
+        nobles.sort_by_key(|(poly, _)| poly.facet_count());
+
+        assert_eq!(nobles.len(), 2);
+        assert_eq!(nobles[0].0.facet_count(), 6);
+        assert_eq!(nobles[1].0.facet_count(), 8);
+    }
+
+    /// A polytope with two vertex orbits can't be searched for noble
+    /// facetings.
+    #[test]
+    fn noble_vertex_orbits() {
+        assert!(Concrete::polygon(4)
+            .pyramid()
+            .noble_facetings(GroupEnum::Chiral(false), None, None, |_, _| true)
+            .is_none());
+    }
+}
@@ -0,0 +1,334 @@
+//! Builds polytopes from their names, as in "pentagonal antiprism prism".
+//!
+//! A name is read from right to left. Its last word is either the name of a
+//! regular polytope, or an operation like "prism" that's applied to whatever
+//! precedes it. A single word before an operation is read as an adjective, so
+//! that "pentagonal" stands for the pentagon.
+
+use std::fmt::Display;
+
+use super::{gosset, recognize::SchlafliSymbol, Concrete, ConcretePolytope};
+use crate::{DualError, Polytope};
+
+/// The endings of adjectives, together with the endings of the nouns they come
+/// from. For instance, "pentagonal" comes from "pentagon".
+const ADJECTIVE_ENDINGS: [(&str, &str); 7] = [
+    ("gonal", "gon"),
+    ("angular", "angle"),
+    ("grammic", "gram"),
+    ("hedral", "hedron"),
+    ("choric", "choron"),
+    ("cubic", "cube"),
+    ("ic", ""),
+];
+
+/// Represents an error when building a polytope from its name.
+#[derive(Clone, Debug)]
+pub enum NameError {
+    /// The name was empty.
+    Empty,
+
+    /// A word in the name wasn't understood.
+    Unknown(String),
+
+    /// The polytope with the given name can't be built yet.
+    Unconstructible(String),
+
+    /// Some dual couldn't be taken.
+    Dual(DualError),
+}
+
+impl Display for NameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "the name is empty"),
+            Self::Unknown(word) => write!(f, "unknown name \"{}\"", word),
+            Self::Unconstructible(name) => write!(f, "the {} can't be built yet", name),
+            Self::Dual(err) => write!(f, "dual failed: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for NameError {}
+
+impl From<DualError> for NameError {
+    fn from(err: DualError) -> Self {
+        Self::Dual(err)
+    }
+}
+
+/// The products of two polytopes that can be named.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Product {
+    /// A duoprism.
+    Prism,
+
+    /// A duopyramid.
+    Pyramid,
+
+    /// A duotegum.
+    Tegum,
+
+    /// A duocomb.
+    Comb,
+}
+
+/// A polytope as described by its name, before it's built.
+#[derive(Clone, Debug, PartialEq)]
+enum Construction {
+    /// A point.
+    Point,
+
+    /// A dyad.
+    Dyad,
+
+    /// A regular polytope.
+    Regular(SchlafliSymbol),
+
+    /// A demihypercube with a given rank.
+    Demihypercube(usize),
+
+    /// The polytope p_qr, in Coxeter's notation.
+    PQr(usize, usize, usize),
+
+    /// The prism of a polytope.
+    Prism(Box<Construction>),
+
+    /// The pyramid of a polytope.
+    Pyramid(Box<Construction>),
+
+    /// The tegum of a polytope.
+    Tegum(Box<Construction>),
+
+    /// The antiprism of a polytope.
+    Antiprism(Box<Construction>),
+
+    /// The antitegum of a polytope.
+    Antitegum(Box<Construction>),
+
+    /// The dual of a polytope.
+    Dual(Box<Construction>),
+
+    /// The product of two polytopes.
+    Product(Product, Box<Construction>, Box<Construction>),
+}
+
+impl Construction {
+    /// Parses a name, given as a list of lowercase words.
+    fn parse(words: &[&str]) -> Result<Self, NameError> {
+        let (&last, rest) = words.split_last().ok_or(NameError::Empty)?;
+
+        if let Some((&"dual", rest)) = words.split_first() {
+            return Ok(Self::Dual(Box::new(Self::parse(rest)?)));
+        }
+
+        let product = match last {
+            "duoprism" => Some(Product::Prism),
+            "duopyramid" => Some(Product::Pyramid),
+            "duotegum" => Some(Product::Tegum),
+            "duocomb" => Some(Product::Comb),
+            _ => None,
+        };
+
+        if let Some(product) = product {
+            let (p, q) = Self::parse_factors(rest)?;
+            return Ok(Self::Product(product, Box::new(p), Box::new(q)));
+        }
+
+        let operation: Option<fn(Box<Self>) -> Self> = match last {
+            "prism" => Some(Self::Prism),
+            "pyramid" => Some(Self::Pyramid),
+            "tegum" | "bipyramid" => Some(Self::Tegum),
+            "antiprism" => Some(Self::Antiprism),
+            "antitegum" | "trapezohedron" => Some(Self::Antitegum),
+            _ => None,
+        };
+
+        if let Some(operation) = operation {
+            return Ok(operation(Box::new(Self::parse_adjective(rest)?)));
+        }
+
+        match last {
+            "point" => Ok(Self::Point),
+            "dyad" | "segment" => Ok(Self::Dyad),
+            _ => {
+                let name = words.join(" ");
+                SchlafliSymbol::from_name(&name)
+                    .map(Self::Regular)
+                    .or_else(|| gosset::demihypercube_from_name(&name).map(Self::Demihypercube))
+                    .or_else(|| gosset::p_qr_from_name(&name).map(|(p, q, r)| Self::PQr(p, q, r)))
+                    .ok_or(NameError::Unknown(name))
+            }
+        }
+    }
+
+    /// Parses the base of an operation. This can either be a single adjective
+    /// or a longer name.
+    fn parse_adjective(words: &[&str]) -> Result<Self, NameError> {
+        match words {
+            [] => Err(NameError::Empty),
+            [word] => Self::parse_adjective_word(word),
+            _ => Self::parse(words),
+        }
+    }
+
+    /// Parses a single adjective, or a noun used as an adjective.
+    fn parse_adjective_word(word: &str) -> Result<Self, NameError> {
+        if let Ok(construction) = Self::parse(&[word]) {
+            return Ok(construction);
+        }
+
+        for (adjective, noun) in ADJECTIVE_ENDINGS.iter() {
+            if let Some(stem) = word.strip_suffix(adjective) {
+                if let Ok(construction) = Self::parse(&[&format!("{}{}", stem, noun)]) {
+                    return Ok(construction);
+                }
+            }
+        }
+
+        Err(NameError::Unknown(word.to_string()))
+    }
+
+    /// Parses the factors of a product, as in "triangular-pentagonal". A single
+    /// factor is taken twice.
+    fn parse_factors(words: &[&str]) -> Result<(Self, Self), NameError> {
+        if let [word] = words {
+            for (idx, _) in word.match_indices('-') {
+                if let (Ok(p), Ok(q)) = (
+                    Self::parse_adjective_word(&word[..idx]),
+                    Self::parse_adjective_word(&word[idx + 1..]),
+                ) {
+                    return Ok((p, q));
+                }
+            }
+        }
+
+        let p = Self::parse_adjective(words)?;
+        Ok((p.clone(), p))
+    }
+
+    /// Builds the polytope.
+    fn build(&self) -> Result<Concrete, NameError> {
+        Ok(match self {
+            Self::Point => Concrete::point(),
+            Self::Dyad => Concrete::dyad(),
+            Self::Regular(symbol) => symbol
+                .build()
+                .ok_or_else(|| NameError::Unconstructible(symbol.to_string()))?,
+            Self::Demihypercube(rank) => Concrete::demihypercube(*rank),
+            Self::PQr(p, q, r) => Concrete::p_qr(*p, *q, *r)
+                .ok_or_else(|| NameError::Unconstructible(gosset::p_qr_name(*p, *q, *r)))?,
+            Self::Prism(base) => base.build()?.prism(),
+            Self::Pyramid(base) => base.build()?.pyramid(),
+            Self::Tegum(base) => base.build()?.tegum(),
+
+            // Polygonal antiprisms can be made uniform.
+            Self::Antiprism(base) => match **base {
+                Self::Regular(SchlafliSymbol(ref entries)) if entries.len() == 1 => {
+                    let (n, d) = entries[0];
+                    Concrete::uniform_antiprism(n, d)
+                }
+                _ => base.build()?.try_antiprism()?,
+            },
+
+            Self::Antitegum(base) => base.build()?.try_antitegum()?,
+
+            Self::Dual(base) => base.build()?.try_dual()?,
+            Self::Product(product, p, q) => {
+                let (p, q) = (p.build()?, q.build()?);
+
+                match product {
+                    Product::Prism => p.duoprism(&q),
+                    Product::Pyramid => p.duopyramid(&q),
+                    Product::Tegum => p.duotegum(&q),
+                    Product::Comb => p.duocomb(&q),
+                }
+            }
+        })
+    }
+}
+
+impl Concrete {
+    /// Builds a polytope from its name, like "pentagonal antiprism prism" or
+    /// "triangular-pentagonal duoprism".
+    ///
+    /// Names are made out of regular polytopes, which can be given by their
+    /// full names (including ones like "5-cube") or by their Bowers-style
+    /// acronyms, of demihypercubes like "demipenteract" or "5-demicube", of
+    /// polytopes like "2_21" in Coxeter's notation, and of the operations
+    /// "prism", "pyramid", "tegum", "antiprism", "antitegum", "dual",
+    /// "duoprism", "duopyramid", "duotegum", and "duocomb".
+    pub fn from_name(name: &str) -> Result<Self, NameError> {
+        let name = name.to_lowercase();
+        let words: Vec<_> = name.split_whitespace().collect();
+        Construction::parse(&words)?.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abs::Ranked;
+
+    /// Returns the element counts of the polytope with a given name.
+    fn counts(name: &str) -> Vec<usize> {
+        Concrete::from_name(name)
+            .unwrap_or_else(|err| panic!("{} couldn't be built: {}", name, err))
+            .el_count_iter()
+            .collect()
+    }
+
+    #[test]
+    fn regular() {
+        assert_eq!(counts("pentagon"), vec![1, 5, 5, 1]);
+        assert_eq!(counts("icositetragon"), vec![1, 24, 24, 1]);
+        assert_eq!(counts("Pentagram"), vec![1, 5, 5, 1]);
+        assert_eq!(counts("tesseract"), vec![1, 16, 32, 24, 8, 1]);
+        assert_eq!(counts("tac"), vec![1, 10, 40, 80, 80, 32, 1]);
+        assert_eq!(counts("5-simplex"), vec![1, 6, 15, 20, 15, 6, 1]);
+    }
+
+    #[test]
+    fn demihypercubes() {
+        assert_eq!(counts("demipenteract"), vec![1, 16, 80, 160, 120, 26, 1]);
+        assert_eq!(counts("5-demicube"), counts("hin"));
+        assert_eq!(counts("2_21"), vec![1, 27, 216, 720, 1080, 648, 99, 1]);
+        assert_eq!(counts("demipenteract prism")[1], 32);
+        assert!(matches!(
+            Concrete::from_name("5_21"),
+            Err(NameError::Unconstructible(_))
+        ));
+    }
+
+    #[test]
+    fn operations() {
+        assert_eq!(counts("pentagonal prism"), vec![1, 10, 15, 7, 1]);
+        assert_eq!(
+            counts("pentagonal antiprism prism"),
+            vec![1, 20, 50, 44, 14, 1]
+        );
+        assert_eq!(counts("cubic pyramid"), vec![1, 9, 20, 18, 7, 1]);
+        assert_eq!(counts("triangular tegum"), vec![1, 5, 9, 6, 1]);
+        assert_eq!(counts("pentagonal antitegum"), vec![1, 12, 20, 10, 1]);
+        assert_eq!(counts("square trapezohedron"), vec![1, 10, 16, 8, 1]);
+        assert_eq!(counts("dual cube"), counts("octahedron"));
+        assert_eq!(
+            counts("triangular-pentagonal duoprism"),
+            vec![1, 15, 30, 23, 8, 1]
+        );
+        assert_eq!(counts("square duoprism"), counts("tesseract"));
+    }
+
+    #[test]
+    fn errors() {
+        assert!(matches!(Concrete::from_name(""), Err(NameError::Empty)));
+        assert!(matches!(
+            Concrete::from_name("frobnicated prism"),
+            Err(NameError::Unknown(_))
+        ));
+        assert!(matches!(
+            Concrete::from_name("icosahedron"),
+            Err(NameError::Unconstructible(_))
+        ));
+    }
+}
@@ -0,0 +1,234 @@
+//! Builds the polytopes *p*<sub>*qr*</sub> of Coxeter's notation, whose
+//! Coxeter diagrams consist of three arms of lengths *p*, *q*, and *r* joined
+//! at a node, with the node at the end of the first arm ringed. These include
+//! the [demihypercubes](https://polytope.miraheze.org/wiki/Demihypercube)
+//! 1<sub>*k*1</sub> and the [Gosset polytopes](https://polytope.miraheze.org/wiki/Gosset_polytope)
+//! *k*<sub>21</sub>, along with the families 2<sub>*k*1</sub> and
+//! 1<sub>*k*2</sub>.
+
+use super::Concrete;
+use crate::{
+    cox::cd::{Cd, Edge, Node},
+    float::Float,
+};
+
+/// The full names of the demihypercubes from 5 to 10 dimensions. The 3D and 4D
+/// demihypercubes are the tetrahedron and the hexadecachoron.
+const DEMICUBE_NAMES: [&str; 6] = [
+    "demipenteract",
+    "demihexeract",
+    "demihepteract",
+    "demiocteract",
+    "demienneract",
+    "demidekeract",
+];
+
+/// The Bowers-style acronyms of the demihypercubes from 5 to 10 dimensions.
+const DEMICUBE_ACRONYMS: [&str; 6] = ["hin", "hax", "hesa", "hocto", "henne", "hede"];
+
+/// The digits used as subscripts in the names of the polytopes
+/// *p*<sub>*qr*</sub>.
+const SUBSCRIPTS: [char; 10] = ['₀', '₁', '₂', '₃', '₄', '₅', '₆', '₇', '₈', '₉'];
+
+/// Writes a number with subscript digits.
+fn subscript(n: usize) -> String {
+    n.to_string()
+        .chars()
+        .map(|c| SUBSCRIPTS[c.to_digit(10).unwrap() as usize])
+        .collect()
+}
+
+/// Returns the name of the polytope *p*<sub>*qr*</sub>, as in "4₂₁".
+pub fn p_qr_name(p: usize, q: usize, r: usize) -> String {
+    format!("{}{}{}", p, subscript(q), subscript(r))
+}
+
+/// Reads the name of a polytope *p*<sub>*qr*</sub> written like "4_21", or
+/// like "4₂₁". Both subscripts must be single digits.
+pub fn p_qr_from_name(name: &str) -> Option<(usize, usize, usize)> {
+    let (p, qr) = match name.split_once('_') {
+        Some((p, qr)) => (p, qr.to_string()),
+        None => {
+            let idx = name.find(|c| SUBSCRIPTS.contains(&c))?;
+            let qr = name[idx..]
+                .chars()
+                .map(|c| SUBSCRIPTS.iter().position(|&s| s == c))
+                .collect::<Option<Vec<_>>>()?
+                .into_iter()
+                .map(|d| char::from(b'0' + d as u8))
+                .collect();
+
+            (&name[..idx], qr)
+        }
+    };
+
+    let p = p.parse().ok()?;
+    let digits: Vec<_> = qr.chars().map(|c| c.to_digit(10)).collect::<Option<_>>()?;
+    match digits[..] {
+        [q, r] => Some((p, q as usize, r as usize)),
+        _ => None,
+    }
+}
+
+/// Returns the name of the demihypercube with a given rank, or `None` if the
+/// rank is less than 4.
+pub fn demihypercube_name(rank: usize) -> Option<String> {
+    let dim = rank.checked_sub(1)?;
+
+    Some(match dim {
+        0..=2 => return None,
+        3 => "tetrahedron".to_string(),
+        4 => "hexadecachoron".to_string(),
+        _ => DEMICUBE_NAMES
+            .get(dim - 5)
+            .map_or_else(|| format!("{}-demicube", dim), |name| name.to_string()),
+    })
+}
+
+/// Reads the name or Bowers-style acronym of a demihypercube, or a name like
+/// "5-demicube", and returns its rank. The tetrahedron and the hexadecachoron
+/// aren't recognized, as they're regular.
+pub fn demihypercube_from_name(name: &str) -> Option<usize> {
+    if let Some((dim, family)) = name.split_once('-') {
+        if let ("demicube" | "demihypercube", Ok(dim)) = (family, dim.parse::<usize>()) {
+            return (dim >= 3).then(|| dim + 1);
+        }
+    }
+
+    DEMICUBE_NAMES
+        .iter()
+        .position(|&demicube| demicube == name)
+        .or_else(|| {
+            DEMICUBE_ACRONYMS
+                .iter()
+                .position(|&acronym| acronym == name)
+        })
+        .map(|idx| idx + 6)
+}
+
+/// Builds the Coxeter diagram of the polytope *p*<sub>*qr*</sub>. The central
+/// node is ringed when *p* is zero.
+fn p_qr_cd(p: usize, q: usize, r: usize) -> Cd {
+    let ringed = Node::ringed(f64::ONE);
+    let three = Edge { num: 3, den: 1 };
+
+    let mut cd = Cd::new();
+    let center = cd.add_node(if p == 0 { ringed } else { Node::Unringed });
+
+    for (arm, len) in [p, q, r].iter().enumerate() {
+        let mut prev = center;
+
+        for i in 0..*len {
+            let node = if arm == 0 && i + 1 == p {
+                ringed
+            } else {
+                Node::Unringed
+            };
+
+            let next = cd.add_node(node);
+            cd.add_edge(prev, next, three)
+                .expect("every edge joins a new node");
+            prev = next;
+        }
+    }
+
+    cd
+}
+
+impl Concrete {
+    /// Builds the polytope *p*<sub>*qr*</sub> with unit edge length through
+    /// the Wythoff construction. Returns `None` if its symmetry group isn't
+    /// finite, which happens when 1 / (*p* + 1) + 1 / (*q* + 1) + 1 / (*r* +
+    /// 1) ≤ 1.
+    pub fn p_qr(p: usize, q: usize, r: usize) -> Option<Self> {
+        Self::wythoff(&p_qr_cd(p, q, r))
+    }
+
+    /// Builds the Gosset polytope *k*<sub>21</sub>, which exists for *k* up
+    /// to 4.
+    pub fn k_21(k: usize) -> Option<Self> {
+        Self::p_qr(k, 2, 1)
+    }
+
+    /// Builds the polytope 2<sub>*k*1</sub>, which exists for *k* up to 4.
+    pub fn two_k1(k: usize) -> Option<Self> {
+        Self::p_qr(2, k, 1)
+    }
+
+    /// Builds the polytope 1<sub>*k*2</sub>, which exists for *k* up to 4.
+    pub fn one_k2(k: usize) -> Option<Self> {
+        Self::p_qr(1, k, 2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{abs::Ranked, conc::ConcretePolytope, Polytope};
+
+    /// Checks the demihypercubes against the polytopes 1<sub>*k*1</sub>.
+    #[test]
+    fn demihypercube() {
+        crate::test(&Concrete::demihypercube(4), [1, 4, 6, 4, 1]);
+        crate::test(&Concrete::demihypercube(5), [1, 8, 24, 32, 16, 1]);
+
+        for rank in 4..=7 {
+            let demicube = Concrete::demihypercube(rank);
+            demicube.assert_valid();
+            assert!(demicube.is_equilateral_with(1.0));
+
+            let p_qr = Concrete::p_qr(1, rank - 4, 1).unwrap();
+            assert_eq!(
+                demicube.el_count_iter().collect::<Vec<_>>(),
+                p_qr.el_count_iter().collect::<Vec<_>>()
+            );
+        }
+
+        crate::test(
+            &crate::abs::Abstract::demihypercube(6),
+            [1, 16, 80, 160, 120, 26, 1],
+        );
+    }
+
+    /// Builds some members of each family.
+    #[test]
+    fn families() {
+        crate::test(&Concrete::k_21(0).unwrap(), [1, 10, 30, 30, 10, 1]);
+        crate::test(&Concrete::k_21(1).unwrap(), [1, 16, 80, 160, 120, 26, 1]);
+        crate::test(
+            &Concrete::k_21(2).unwrap(),
+            [1, 27, 216, 720, 1080, 648, 99, 1],
+        );
+        crate::test(
+            &Concrete::two_k1(2).unwrap(),
+            [1, 27, 216, 720, 1080, 648, 99, 1],
+        );
+        crate::test(
+            &Concrete::one_k2(2).unwrap(),
+            [1, 72, 720, 2160, 2160, 702, 54, 1],
+        );
+
+        // These live in Euclidean space.
+        assert!(Concrete::k_21(5).is_none());
+        assert!(Concrete::two_k1(5).is_none());
+        assert!(Concrete::one_k2(5).is_none());
+    }
+
+    #[test]
+    fn names() {
+        assert_eq!(p_qr_name(4, 2, 1), "4₂₁");
+        assert_eq!(p_qr_from_name("4_21"), Some((4, 2, 1)));
+        assert_eq!(p_qr_from_name("1₂₂"), Some((1, 2, 2)));
+        assert_eq!(p_qr_from_name("1_222"), None);
+
+        assert_eq!(demihypercube_name(5).as_deref(), Some("hexadecachoron"));
+        assert_eq!(demihypercube_name(6).as_deref(), Some("demipenteract"));
+        assert_eq!(demihypercube_name(13).as_deref(), Some("12-demicube"));
+        assert_eq!(demihypercube_name(3), None);
+
+        assert_eq!(demihypercube_from_name("demipenteract"), Some(6));
+        assert_eq!(demihypercube_from_name("hax"), Some(7));
+        assert_eq!(demihypercube_from_name("12-demicube"), Some(13));
+        assert_eq!(demihypercube_from_name("2-demicube"), None);
+    }
+}
@@ -0,0 +1,388 @@
+//! Contains the built-in catalog of Euclidean honeycombs, such as the cubic or
+//! the demitesseractic honeycomb.
+//!
+//! Honeycombs are infinite, so only patches of them are ever built. Every
+//! honeycomb in the catalog is given by a basis of the translations that map
+//! it to itself, together with the cells in a fundamental domain of these
+//! translations. A patch is made out of the copies of these cells under every
+//! combination of a number of steps along each vector of the basis, which are
+//! then glued together as a [`Complex`].
+//!
+//! Any other periodic structure can be described as a [`Honeycomb`] in the
+//! same way, and stored in a
+//! [periodic OFF file](crate::file::periodic).
+
+use super::{
+    cleanup::vertex_clusters, complex::Complex, compound::orbit_points, Concrete, ConcretePolytope,
+};
+use crate::{
+    float::Float,
+    geometry::{Point, Vector},
+};
+
+/// The number of honeycombs in the catalog.
+pub const HONEYCOMB_COUNT: usize = 10;
+
+/// A honeycomb, given by a fundamental domain of its translations.
+struct HoneycombData {
+    /// The name of the honeycomb.
+    name: &'static str,
+
+    /// A basis of the translations of the honeycomb.
+    translations: fn() -> Vec<Point<f64>>,
+
+    /// The vertices of every cell in a fundamental domain of the
+    /// translations. Each cell is the convex hull of its vertices.
+    cells: fn() -> Vec<Vec<Point<f64>>>,
+}
+
+/// The honeycombs in the catalog, first the tilings of the plane and then
+/// those of 3D and 4D space.
+const HONEYCOMBS: [HoneycombData; HONEYCOMB_COUNT] = [
+    HoneycombData {
+        name: "Square tiling",
+        translations: || identity(2),
+        cells: || vec![orbit_points(&[0.5; 2], false)],
+    },
+    HoneycombData {
+        name: "Triangular tiling",
+        translations: || triangular_lattice().to_vec(),
+        cells: || {
+            let (o, [a, b]) = (Point::zeros(2), triangular_lattice());
+            let c = &a + &b;
+            vec![vec![o, a.clone(), b.clone()], vec![a, b, c]]
+        },
+    },
+    HoneycombData {
+        name: "Hexagonal tiling",
+        translations: || {
+            let h = 0.75f64.sqrt();
+            vec![
+                Point::from_vec(vec![1.5, h]),
+                Point::from_vec(vec![0.0, 2.0 * h]),
+            ]
+        },
+        cells: || {
+            let hexagon = (0..6)
+                .map(|k| {
+                    let angle = k as f64 * std::f64::consts::FRAC_PI_3;
+                    Point::from_vec(vec![angle.cos(), angle.sin()])
+                })
+                .collect();
+            vec![hexagon]
+        },
+    },
+    HoneycombData {
+        name: "Cubic honeycomb",
+        translations: || identity(3),
+        cells: || vec![orbit_points(&[0.5; 3], false)],
+    },
+    HoneycombData {
+        name: "Tetrahedral-octahedral honeycomb",
+        translations: face_centered_lattice,
+        cells: || {
+            let tetrahedron = |sign: f64| {
+                face_centered_lattice()
+                    .into_iter()
+                    .map(|v| v * sign)
+                    .chain(std::iter::once(Point::zeros(3)))
+                    .collect()
+            };
+
+            vec![
+                shift(orbit_points(&[1.0, 0.0, 0.0], false), &[1.0, 0.0, 0.0]),
+                tetrahedron(1.0),
+                tetrahedron(-1.0),
+            ]
+        },
+    },
+    HoneycombData {
+        name: "Bitruncated cubic honeycomb",
+        translations: || {
+            vec![
+                Point::from_vec(vec![2.0, 0.0, 0.0]),
+                Point::from_vec(vec![0.0, 2.0, 0.0]),
+                Point::from_vec(vec![1.0, 1.0, 1.0]),
+            ]
+        },
+        cells: || vec![orbit_points(&[0.0, 0.5, 1.0], false)],
+    },
+    HoneycombData {
+        name: "Rhombic dodecahedral honeycomb",
+        translations: face_centered_lattice,
+        cells: || {
+            let mut vertices = orbit_points(&[1.0, 0.0, 0.0], false);
+            vertices.append(&mut orbit_points(&[0.5; 3], false));
+            vec![vertices]
+        },
+    },
+    HoneycombData {
+        name: "Tesseractic honeycomb",
+        translations: || identity(4),
+        cells: || vec![orbit_points(&[0.5; 4], false)],
+    },
+    HoneycombData {
+        name: "Demitesseractic honeycomb",
+        translations: demitesseractic_lattice,
+        cells: || {
+            // The halves of the tesseract around each center with an even
+            // coordinate sum.
+            let half = |center: [f64; 4]| {
+                shift(orbit_points(&[0.5; 4], false), &center)
+                    .into_iter()
+                    .filter(|p| p.sum().round() as i64 % 2 == 0)
+                    .collect()
+            };
+
+            vec![
+                shift(
+                    orbit_points(&[1.0, 0.0, 0.0, 0.0], false),
+                    &[1.0, 0.0, 0.0, 0.0],
+                ),
+                half([0.5, 0.5, 0.5, 0.5]),
+                half([0.5, 0.5, 0.5, -0.5]),
+            ]
+        },
+    },
+    HoneycombData {
+        name: "Icositetrachoric honeycomb",
+        translations: demitesseractic_lattice,
+        cells: || {
+            let mut vertices = orbit_points(&[1.0, 0.0, 0.0, 0.0], false);
+            vertices.append(&mut orbit_points(&[0.5; 4], false));
+            vec![vertices]
+        },
+    },
+];
+
+/// Returns the name of the honeycomb with a given index, starting from 1, or
+/// `None` if there's no such honeycomb.
+pub fn honeycomb_name(idx: usize) -> Option<&'static str> {
+    Some(HONEYCOMBS.get(idx.checked_sub(1)?)?.name)
+}
+
+/// The unit vectors along the axes of a space with a given dimension.
+fn identity(dim: usize) -> Vec<Point<f64>> {
+    (0..dim)
+        .map(|i| {
+            let mut v = Point::zeros(dim);
+            v[i] = 1.0;
+            v
+        })
+        .collect()
+}
+
+/// A basis of the lattice of the triangular tiling with unit edge length.
+fn triangular_lattice() -> [Point<f64>; 2] {
+    [
+        Point::from_vec(vec![1.0, 0.0]),
+        Point::from_vec(vec![0.5, 0.75f64.sqrt()]),
+    ]
+}
+
+/// A basis of the face-centered cubic lattice, made out of the points with
+/// integer coordinates and an even coordinate sum.
+fn face_centered_lattice() -> Vec<Point<f64>> {
+    vec![
+        Point::from_vec(vec![0.0, 1.0, 1.0]),
+        Point::from_vec(vec![1.0, 0.0, 1.0]),
+        Point::from_vec(vec![1.0, 1.0, 0.0]),
+    ]
+}
+
+/// A basis of the lattice of points in 4D with integer coordinates and an even
+/// coordinate sum, whose Delaunay cells are hexadecachora.
+fn demitesseractic_lattice() -> Vec<Point<f64>> {
+    vec![
+        Point::from_vec(vec![1.0, -1.0, 0.0, 0.0]),
+        Point::from_vec(vec![0.0, 1.0, -1.0, 0.0]),
+        Point::from_vec(vec![0.0, 0.0, 1.0, -1.0]),
+        Point::from_vec(vec![0.0, 0.0, 1.0, 1.0]),
+    ]
+}
+
+/// Translates a list of points by a given vector.
+fn shift(points: Vec<Point<f64>>, v: &[f64]) -> Vec<Point<f64>> {
+    let v = Point::from_column_slice(v);
+    points.into_iter().map(|p| p + &v).collect()
+}
+
+/// A periodic tiling or honeycomb, given by a basis of its translations and by
+/// the cells in a fundamental domain of them. Every cell is the convex hull of
+/// some of the vertices.
+///
+/// There can be less translations than dimensions, in which case the
+/// structure only repeats along some directions, like a prism over a tiling.
+#[derive(Clone, Debug)]
+pub struct Honeycomb {
+    /// A basis of the translations.
+    pub translations: Vec<Vector<f64>>,
+
+    /// The vertices of the cells in the fundamental domain.
+    pub vertices: Vec<Point<f64>>,
+
+    /// The indices of the vertices of each cell.
+    pub cells: Vec<Vec<usize>>,
+}
+
+impl Honeycomb {
+    /// Builds a honeycomb from a basis of its translations and the vertices of
+    /// each of its cells. The vertices shared by several cells are only stored
+    /// once.
+    pub fn new(translations: Vec<Vector<f64>>, cells: Vec<Vec<Point<f64>>>) -> Self {
+        let points: Vec<_> = cells.iter().flatten().cloned().collect();
+        let (map, vertices) = vertex_clusters(&points, f64::eps());
+
+        let mut offset = 0;
+        let cells = cells
+            .iter()
+            .map(|cell| {
+                offset += cell.len();
+                map[offset - cell.len()..offset].to_vec()
+            })
+            .collect();
+
+        Self {
+            translations,
+            vertices,
+            cells,
+        }
+    }
+
+    /// Returns the honeycomb with a given index in the catalog, starting from
+    /// 1, or `None` if there's no such honeycomb.
+    pub fn catalog(idx: usize) -> Option<Self> {
+        let data = HONEYCOMBS.get(idx.checked_sub(1)?)?;
+        Some(Self::new((data.translations)(), (data.cells)()))
+    }
+
+    /// Returns the dimension of the space the honeycomb lives in, or `None` if
+    /// it has no vertices.
+    pub fn dim(&self) -> Option<usize> {
+        self.vertices.first().map(Point::len)
+    }
+
+    /// Builds a patch of the honeycomb, centered at the origin. The patch is
+    /// made out of the cells of a number of copies of the fundamental domain
+    /// along each of the translations. The patches with many copies can take a
+    /// while, especially in 4D.
+    ///
+    /// Returns `None` if no copies are asked for, if there's no cells, or if
+    /// some cell or translation doesn't fit in the space of the vertices.
+    pub fn patch(&self, copies: usize) -> Option<Concrete> {
+        let dim = self.dim()?;
+        if copies == 0 || self.translations.iter().any(|v| v.len() != dim) {
+            return None;
+        }
+
+        let cells = self
+            .cells
+            .iter()
+            .map(|cell| {
+                let vertices: Vec<_> = cell.iter().map(|&v| self.vertices[v].clone()).collect();
+                Concrete::convex_hull(&vertices)
+            })
+            .collect::<Option<Vec<_>>>()?;
+        if cells.is_empty() {
+            return None;
+        }
+
+        let mut patch = Vec::new();
+        for mut k in 0..copies.pow(self.translations.len() as u32) {
+            let mut offset = Vector::zeros(dim);
+            for v in &self.translations {
+                offset += v * (k % copies) as f64;
+                k /= copies;
+            }
+
+            for cell in &cells {
+                let mut cell = cell.clone();
+                cell.translate(&offset);
+                patch.push(cell);
+            }
+        }
+
+        let mut patch = Complex::from_cells(patch).to_polytope();
+        let center = patch.gravicenter()?;
+        patch.translate(&-center);
+        Some(patch)
+    }
+}
+
+impl Concrete {
+    /// Builds a patch of the honeycomb with a given index in the catalog,
+    /// starting from 1, with unit edge length. See [`Honeycomb::patch`].
+    ///
+    /// Returns `None` if there's no such honeycomb, or if no copies are asked
+    /// for.
+    pub fn honeycomb(idx: usize, copies: usize) -> Option<Self> {
+        let mut honeycomb = Honeycomb::catalog(idx)?.patch(copies)?;
+        let edge_len = honeycomb.edge_len(0)?;
+        honeycomb.scale(1.0 / edge_len);
+        Some(honeycomb)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{abs::Ranked, conc::Volume, geometry::Matrix};
+
+    use approx::assert_abs_diff_eq;
+    use vec_like::VecLike;
+
+    /// Builds patches of every honeycomb.
+    #[test]
+    fn honeycombs() {
+        for idx in 1..=HONEYCOMB_COUNT {
+            let data = &HONEYCOMBS[idx - 1];
+            let dim = (data.translations)().len();
+            let honeycomb = Concrete::honeycomb(idx, 2).unwrap();
+            honeycomb.assert_valid();
+
+            assert_eq!(
+                honeycomb.facet_count(),
+                (data.cells)().len() << dim,
+                "wrong number of cells in the {}",
+                data.name
+            );
+            assert!(honeycomb.is_equilateral_with(1.0), "{}", data.name);
+
+            // The cells don't overlap, so no ridge is in more than two of them.
+            let rank = honeycomb.rank();
+            assert!(
+                honeycomb[rank - 2]
+                    .iter()
+                    .all(|ridge| ridge.sups.len() <= 2),
+                "overlapping cells in the {}",
+                data.name
+            );
+
+            // The cells fill up a fundamental domain.
+            let volume: f64 = (data.cells)()
+                .iter()
+                .map(
+                    |vertices| match Concrete::convex_hull(vertices).unwrap().volume_mut() {
+                        Volume::Measure(volume) => volume,
+                        _ => 0.0,
+                    },
+                )
+                .sum();
+            let domain = Matrix::from_columns(&(data.translations)()).determinant();
+            assert_abs_diff_eq!(volume, domain.abs(), epsilon = 1e-9);
+        }
+
+        assert!(Concrete::honeycomb(0, 2).is_none());
+        assert!(Concrete::honeycomb(HONEYCOMB_COUNT + 1, 2).is_none());
+        assert!(Concrete::honeycomb(1, 0).is_none());
+    }
+
+    /// Builds some patches of the square tiling and the cubic honeycomb.
+    #[test]
+    fn cubic() {
+        crate::test(&Concrete::honeycomb(1, 3).unwrap(), [1, 16, 24, 9, 1]);
+        let cubic = Concrete::honeycomb(4, 2).unwrap();
+        crate::test(&cubic, [1, 27, 54, 36, 8, 1]);
+        assert_abs_diff_eq!(cubic.gravicenter().unwrap().norm(), 0.0, epsilon = 1e-9);
+    }
+}
@@ -0,0 +1,576 @@
+//! Computes convex hulls of point sets in any number of dimensions, and builds
+//! convex Wythoffian polytopes from them.
+//!
+//! The hull is found by gift wrapping. Starting from a single facet, we rotate
+//! a supporting hyperplane around each of its ridges until it hits the
+//! adjacent facet. The ridges are themselves found by wrapping the facets one
+//! dimension lower. Since every face is memoized by the points on it, the
+//! whole face lattice comes out of this process.
+//!
+//! Delaunay subdivisions come out of the same process, as the lower hull of
+//! the points lifted onto a paraboloid.
+
+use std::{
+    collections::{BTreeSet, HashMap, HashSet, VecDeque},
+    iter,
+};
+
+use super::Concrete;
+use crate::{
+    abs::{Abstract, AbstractBuilder, SubelementList, Subelements},
+    cox::{
+        cd::{Cd, Node},
+        Cox,
+    },
+    float::Float,
+    geometry::{Point, PointOrd, Subspace, Vector},
+    Polytope,
+};
+
+use vec_like::*;
+
+/// A face of a convex hull, given by the sorted indices of the points on it.
+type Face = Vec<usize>;
+
+/// Returns a unit vector orthogonal to every vector in an orthonormal set,
+/// which must not span the entire space.
+fn normal_to(basis: Vec<Vector<f64>>, dim: usize) -> Vector<f64> {
+    let mut subspace = Subspace::new(Point::zeros(dim));
+    subspace.basis = basis;
+    subspace.orthogonal_complement().swap_remove(0)
+}
+
+/// Rotates the hyperplane with a given normal around the subspace spanned by
+/// the `axis` points, in the direction of a unit vector `u` orthogonal to both,
+/// until it hits another point. Returns the normal of the rotated hyperplane.
+fn rotate(
+    points: &[Point<f64>],
+    axis: &[usize],
+    normal: &Vector<f64>,
+    u: &Vector<f64>,
+) -> Vector<f64> {
+    let origin = &points[axis[0]];
+    let eps = f64::eps();
+
+    // A point whose projection onto the plane spanned by u and the normal is
+    // at an angle φ from u is hit after rotating by φ.
+    let mut angle = f64::PI;
+    for p in points {
+        let w = p - origin;
+        let (a, b) = (w.dot(u), w.dot(normal));
+
+        // Points on the hyperplane must count as lying at an angle π rather
+        // than -π, so we avoid a negative zero.
+        if a * a + b * b > eps * eps {
+            let depth = if b < 0.0 { -b } else { 0.0 };
+            angle = angle.min(depth.atan2(a));
+        }
+    }
+
+    let (sin, cos) = angle.fsin_cos();
+    normal * cos + u * sin
+}
+
+/// Returns the points that lie on the supporting hyperplane with a given
+/// normal.
+fn supported(points: &[Point<f64>], normal: &Vector<f64>) -> Face {
+    let heights: Vec<_> = points.iter().map(|p| p.dot(normal)).collect();
+    let max = heights.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    (0..points.len())
+        .filter(|&i| heights[i] >= max - f64::eps())
+        .collect()
+}
+
+/// Computes the faces of the convex hull of a set of points, memoizing the
+/// facets of every face found along the way.
+struct Hull<'a> {
+    /// The points whose hull we're computing. These must be distinct.
+    points: &'a [Point<f64>],
+
+    /// The facets of every face computed so far.
+    facets: HashMap<Face, Vec<Face>>,
+}
+
+impl<'a> Hull<'a> {
+    /// Initializes a new hull computation for a set of distinct points.
+    fn new(points: &'a [Point<f64>]) -> Self {
+        Self {
+            points,
+            facets: HashMap::new(),
+        }
+    }
+
+    /// Returns the facets of a face with a given dimension.
+    fn facets(&mut self, face: &[usize], dim: usize) -> Vec<Face> {
+        if let Some(facets) = self.facets.get(face) {
+            return facets.clone();
+        }
+
+        // The facets of a simplex are found by leaving out each vertex.
+        let facets = if face.len() == dim + 1 {
+            (0..face.len())
+                .map(|i| {
+                    let mut facet = face.to_vec();
+                    facet.remove(i);
+                    facet
+                })
+                .collect()
+        } else {
+            self.wrap(face, dim)
+        };
+
+        self.facets.insert(face.to_vec(), facets.clone());
+        facets
+    }
+
+    /// Finds the facets of a face with a given dimension by gift wrapping.
+    fn wrap(&mut self, face: &[usize], dim: usize) -> Vec<Face> {
+        // Works in coordinates on the affine hull of the face.
+        let subspace = Subspace::from_points(face.iter().map(|&i| &self.points[i]));
+        let points: Vec<_> = face
+            .iter()
+            .map(|&i| subspace.flatten(&self.points[i]))
+            .collect();
+        let center = points.iter().sum::<Point<f64>>() / points.len() as f64;
+
+        // The facets of a segment are its two endpoints.
+        if dim == 1 {
+            let mut order: Vec<_> = (0..points.len()).collect();
+            order.sort_by(|&i, &j| points[i][0].partial_cmp(&points[j][0]).unwrap());
+            return vec![vec![face[order[0]]], vec![face[order[points.len() - 1]]]];
+        }
+
+        // Finds a first facet by rotating a supporting hyperplane around the
+        // points on it, until they span a hyperplane.
+        let mut normal = Vector::zeros(dim);
+        normal[0] = 1.0;
+        let mut first = supported(&points, &normal);
+
+        loop {
+            let span = Subspace::from_points(first.iter().map(|&i| &points[i]));
+            if span.rank() + 1 == dim {
+                break;
+            }
+
+            let mut basis = span.basis;
+            basis.push(normal.clone());
+            let u = normal_to(basis, dim);
+
+            normal = rotate(&points, &first, &normal, &u);
+            first = supported(&points, &normal);
+        }
+
+        // Walks through the facets, rotating around each of their ridges.
+        let mut facets = vec![first.clone()];
+        let mut found: HashSet<_> = facets.iter().cloned().collect();
+        let mut queue: VecDeque<_> = facets.iter().cloned().collect();
+
+        while let Some(facet) = queue.pop_front() {
+            // Recomputes the outer normal from the facet, for stability.
+            let span = Subspace::from_points(facet.iter().map(|&i| &points[i]));
+            let mut normal = normal_to(span.basis.clone(), dim);
+            if (&center - &points[facet[0]]).dot(&normal) > 0.0 {
+                normal = -normal;
+            }
+
+            let global: Face = facet.iter().map(|&i| face[i]).collect();
+            for ridge in self.facets(&global, dim - 1) {
+                let ridge: Face = ridge
+                    .iter()
+                    .map(|v| face.binary_search(v).unwrap())
+                    .collect();
+
+                // The direction within the facet orthogonal to the ridge,
+                // pointing away from the facet.
+                let ridge_span = Subspace::from_points(ridge.iter().map(|&i| &points[i]));
+                let mut basis = ridge_span.basis;
+                basis.push(normal.clone());
+                let mut u = normal_to(basis, dim);
+
+                let inner = facet.iter().find(|i| !ridge.contains(i)).unwrap();
+                if (&points[*inner] - &points[ridge[0]]).dot(&u) > 0.0 {
+                    u = -u;
+                }
+
+                let neighbor = supported(&points, &rotate(&points, &ridge, &normal, &u));
+                if found.insert(neighbor.clone()) {
+                    facets.push(neighbor.clone());
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        facets
+            .into_iter()
+            .map(|facet| facet.into_iter().map(|i| face[i]).collect())
+            .collect()
+    }
+}
+
+/// Returns the distinct points in a list, in the order they first show up.
+fn distinct(points: &[Point<f64>]) -> Vec<Point<f64>> {
+    let mut seen = BTreeSet::new();
+    points
+        .iter()
+        .filter(|&p| seen.insert(PointOrd::new(p.clone())))
+        .cloned()
+        .collect()
+}
+
+/// Finds the faces of each dimension below some faces of a hull with a given
+/// dimension, along with their facets. Returns the subelement lists of these
+/// faces from the top down, and the indices of the points on the vertices.
+fn face_lists(
+    hull: &mut Hull<'_>,
+    mut faces: Vec<Face>,
+    dim: usize,
+) -> (Vec<SubelementList>, Vec<usize>) {
+    let mut lists = Vec::with_capacity(dim);
+
+    for d in (1..=dim).rev() {
+        let mut indices = HashMap::new();
+        let mut next = Vec::new();
+        let mut list = SubelementList::with_capacity(faces.len());
+
+        for face in &faces {
+            let mut subs = Subelements::new();
+
+            for facet in hull.facets(face, d) {
+                let idx = *indices.entry(facet.clone()).or_insert_with(|| {
+                    next.push(facet);
+                    next.len() - 1
+                });
+                subs.push(idx);
+            }
+
+            list.push(subs);
+        }
+
+        lists.push(list);
+        faces = next;
+    }
+
+    (lists, faces.into_iter().map(|v| v[0]).collect())
+}
+
+impl Concrete {
+    /// Builds a polytope with the given vertices and no other elements besides
+    /// the minimal and maximal ones. This is only a valid polytope for up to
+    /// two points, but it's how a bare point cloud is stored until its
+    /// [convex hull](Self::convex_hull) or its [Delaunay
+    /// subdivision](Self::delaunay) is taken.
+    pub fn point_cloud(points: Vec<Point<f64>>) -> Self {
+        match points.len() {
+            0 => Self::nullitope(),
+            1 => Self::new(points, Abstract::point()),
+            count => {
+                let mut builder = AbstractBuilder::with_rank_capacity(2);
+                builder.push_min();
+                builder.push_vertices(count);
+                builder.push_max();
+
+                // Safety: this is a dyad if there's two vertices. Otherwise,
+                // the structure is only used as a container for the points.
+                Self::new(points, unsafe { builder.build() })
+            }
+        }
+    }
+
+    /// Builds the convex hull of a set of points. The hull lives in the affine
+    /// subspace spanned by the points, so its rank is one more than the
+    /// dimension of this subspace. Points that coincide are merged, and points
+    /// that don't lie on any vertex of the hull are discarded.
+    ///
+    /// Returns `None` if the set of points is empty.
+    pub fn convex_hull(points: &[Point<f64>]) -> Option<Self> {
+        let points = distinct(points);
+        if points.is_empty() {
+            return None;
+        }
+
+        let dim = Subspace::from_points(points.iter()).rank();
+        if dim == 0 {
+            return Some(Self::new(points, Abstract::point()));
+        }
+
+        let mut hull = Hull::new(&points);
+        let (lists, vertices) = face_lists(&mut hull, vec![(0..points.len()).collect()], dim);
+
+        let mut builder = AbstractBuilder::with_rank_capacity(dim + 1);
+        builder.push_min();
+        builder.push_vertices(vertices.len());
+        for list in lists.into_iter().rev() {
+            builder.push(list);
+        }
+
+        let vertices = vertices.into_iter().map(|v| points[v].clone()).collect();
+
+        // Safety: the faces of a convex polytope form an abstract polytope.
+        Some(Self::new(vertices, unsafe { builder.build() }))
+    }
+
+    /// Builds the [Delaunay subdivision](https://en.wikipedia.org/wiki/Delaunay_triangulation)
+    /// of a set of points, whose cells are those convex polytopes with
+    /// vertices on a common sphere that contains none of the other points.
+    /// Like the convex hull, it lives in the affine subspace spanned by the
+    /// points, and the cells are the facets of the result. Points that
+    /// coincide are merged.
+    ///
+    /// The points are lifted onto a paraboloid one dimension up, and the cells
+    /// are the projections of the lower facets of their hull. Cells with more
+    /// vertices than a simplex, like the squares in a square grid, are kept
+    /// as they are, rather than split into simplices.
+    ///
+    /// Returns `None` if the set of points is empty.
+    pub fn delaunay(points: &[Point<f64>]) -> Option<Self> {
+        let points = distinct(points);
+        if points.is_empty() {
+            return None;
+        }
+
+        let span = Subspace::from_points(points.iter());
+        let dim = span.rank();
+
+        // Works on the affine span of the points, scaled down to the unit
+        // ball to keep the lifted coordinates in check.
+        let flat: Vec<_> = points.iter().map(|p| span.flatten(p)).collect();
+        let center = flat.iter().sum::<Point<f64>>() / flat.len() as f64;
+        let radius = flat
+            .iter()
+            .map(|p| (p - &center).norm())
+            .fold(0.0, f64::max);
+        let lifted: Vec<_> = flat
+            .iter()
+            .map(|p| {
+                let q = (p - &center) / radius;
+                let height = q.norm_squared();
+                Point::from_iterator(dim + 1, q.iter().copied().chain(iter::once(height)))
+            })
+            .collect();
+
+        // A single point, or points on a common sphere, which make up a
+        // single cell.
+        if dim == 0 || Subspace::from_points(lifted.iter()).rank() == dim {
+            return Self::convex_hull(&points);
+        }
+
+        // The lower facets are those with the interior of the hull right
+        // above them.
+        let mut hull = Hull::new(&lifted);
+        let inner = lifted.iter().sum::<Point<f64>>() / lifted.len() as f64;
+        let cells: Vec<Face> = hull
+            .facets(&(0..lifted.len()).collect::<Face>(), dim + 1)
+            .into_iter()
+            .filter(|facet| {
+                let base = &lifted[facet[0]];
+                let mut above = base.clone();
+                above[dim] += 1.0;
+
+                match Subspace::from_points(facet.iter().map(|&i| &lifted[i])).normal(&above) {
+                    Some(n) => (&inner - base).dot(&n) > f64::eps(),
+                    None => false,
+                }
+            })
+            .collect();
+
+        let (lists, vertices) = face_lists(&mut hull, cells, dim);
+
+        let mut builder = AbstractBuilder::with_rank_capacity(dim + 2);
+        builder.push_min();
+        builder.push_vertices(vertices.len());
+        for list in lists.into_iter().rev() {
+            builder.push(list);
+        }
+        builder.push_max();
+
+        let vertices = vertices.into_iter().map(|v| points[v].clone()).collect();
+
+        // Safety: every cell is a convex polytope, and the cells meet face to
+        // face. As with a net, the ridges on the boundary only lie on a
+        // single cell.
+        Some(Self::new(vertices, unsafe { builder.build() }))
+    }
+
+    /// Builds the convex polytope described by a Coxeter diagram through the
+    /// Wythoff construction, as the convex hull of the orbit of its generator
+    /// point.
+    ///
+    /// Returns `None` if the diagram has snub nodes, or if its group isn't
+    /// finite.
+    pub fn wythoff(cd: &Cd) -> Option<Self> {
+        if cd.node_iter().any(|node| matches!(node, Node::Snub(_))) {
+            return None;
+        }
+
+        let generator = cd.generator()?;
+        let orbit = cd.cox().orbit(&generator)?;
+        Self::convex_hull(&orbit)
+    }
+
+    /// Builds the convex hull of the orbit of the point at the given distances
+    /// from the mirrors of a Coxeter group, as in
+    /// [`Cox::point_at_distances`]. Unlike [`Self::wythoff`], the distances
+    /// can take any values, which gives isogonal polytopes that needn't be
+    /// uniform.
+    ///
+    /// Returns `None` if the group isn't finite, or if the number of
+    /// distances doesn't match its rank.
+    pub fn wythoff_at(cox: &Cox<f64>, distances: &[f64]) -> Option<Self> {
+        let generator = cox.point_at_distances(distances)?;
+        let orbit = cox.orbit(&generator)?;
+        Self::convex_hull(&orbit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{abs::Ranked, conc::ConcretePolytope};
+
+    /// Checks that the hull of the vertices of some polytopes gives back the
+    /// same polytopes.
+    #[test]
+    fn hulls() {
+        for poly in [
+            Concrete::polygon(7),
+            Concrete::cube(),
+            Concrete::hypercube(5),
+            Concrete::orthoplex(5),
+            Concrete::simplex(6),
+            Concrete::duoprism(&Concrete::polygon(3), &Concrete::polygon(5)),
+        ] {
+            let hull = Concrete::convex_hull(&poly.vertices).unwrap();
+            hull.assert_valid();
+            crate::test(&hull, poly.el_count_iter().collect::<Vec<_>>());
+        }
+    }
+
+    /// Checks that interior, repeated and coplanar points are handled, and
+    /// that the hull lives in the affine span of the points.
+    #[test]
+    fn degenerate() {
+        let mut points = Concrete::cube().vertices;
+        points.push(Point::zeros(3));
+        points.push(points[0].clone());
+        points.push((&points[0] + &points[1]) / 2.0);
+        crate::test(&Concrete::convex_hull(&points).unwrap(), [1, 8, 12, 6, 1]);
+
+        let square: Vec<Point<f64>> = Concrete::polygon(4)
+            .vertices
+            .iter()
+            .map(|v| vec![v[0], v[1], 1.0].into())
+            .collect();
+        let hull = Concrete::convex_hull(&square).unwrap();
+        crate::test(&hull, [1, 4, 4, 1]);
+        assert_eq!(hull.dim(), Some(3));
+
+        crate::test(&Concrete::convex_hull(&square[..1]).unwrap(), [1, 1]);
+        assert!(Concrete::convex_hull(&[]).is_none());
+    }
+
+    /// Builds the Delaunay subdivisions of some point sets.
+    #[test]
+    fn delaunay() {
+        let grid: Vec<Point<f64>> = (0..9)
+            .map(|i| vec![(i % 3) as f64, (i / 3) as f64].into())
+            .collect();
+        let squares = Concrete::delaunay(&grid).unwrap();
+        crate::test(&squares, [1, 9, 12, 4, 1]);
+
+        // The center of a square splits it into triangles.
+        let mut points = Concrete::polygon(4).vertices;
+        points.push(Point::zeros(2));
+        crate::test(&Concrete::delaunay(&points).unwrap(), [1, 5, 8, 4, 1]);
+
+        // The center of a cube splits it into square pyramids.
+        let mut points = Concrete::cube().vertices;
+        crate::test(&Concrete::delaunay(&points).unwrap(), [1, 8, 12, 6, 1]);
+        points.push(Point::zeros(3));
+        let pyramids = Concrete::delaunay(&points).unwrap();
+        crate::test(&pyramids, [1, 9, 20, 18, 6, 1]);
+
+        // Points in general position are triangulated, which gives a disk.
+        let points: Vec<Point<f64>> = (2..30)
+            .map(|k| {
+                let k = k as f64;
+                vec![(7.0 * k.sqrt()).fract(), (11.0 * (k + 1.0).sqrt()).fract()].into()
+            })
+            .collect();
+        let triangles = Concrete::delaunay(&points).unwrap();
+        let counts: Vec<_> = triangles.el_count_iter().collect();
+        assert_eq!(counts[1] + counts[3], counts[2] + 1);
+        assert!(triangles.abs()[3].iter().all(|cell| cell.subs.len() == 3));
+
+        // Collinear points are split into segments.
+        let line: Vec<Point<f64>> = [0.0, 2.0, 1.0, 1.0]
+            .iter()
+            .map(|&x| vec![x, x, 0.0].into())
+            .collect();
+        let segments = Concrete::delaunay(&line).unwrap();
+        crate::test(&segments, [1, 3, 2, 1]);
+        assert_eq!(segments.dim(), Some(3));
+
+        assert!(Concrete::delaunay(&[]).is_none());
+    }
+
+    /// Stores some point clouds.
+    #[test]
+    fn point_cloud() {
+        let points = Concrete::cube().vertices;
+        let cloud = Concrete::point_cloud(points.clone());
+        crate::test(&cloud, [1, 8, 1]);
+        crate::test(
+            &Concrete::convex_hull(&cloud.vertices).unwrap(),
+            [1, 8, 12, 6, 1],
+        );
+
+        crate::test(&Concrete::point_cloud(points[..2].to_vec()), [1, 2, 1]);
+        crate::test(&Concrete::point_cloud(points[..1].to_vec()), [1, 1]);
+        crate::test(&Concrete::point_cloud(Vec::new()), [1]);
+    }
+
+    /// Builds some Wythoffian polytopes.
+    #[test]
+    fn wythoff() {
+        for (cd, count) in [
+            ("x3o3o", vec![1, 4, 6, 4, 1]),
+            ("x3x3o", vec![1, 12, 18, 8, 1]),
+            ("o3x4o", vec![1, 12, 24, 14, 1]),
+            ("x5o3o", vec![1, 20, 30, 12, 1]),
+            ("o3x3o3o", vec![1, 10, 30, 30, 10, 1]),
+            ("x3o3o *b3o3o", vec![1, 16, 80, 160, 120, 26, 1]),
+        ] {
+            let poly = Concrete::wythoff(&Cd::parse(cd).unwrap()).unwrap();
+            poly.assert_valid();
+            crate::test(&poly, count);
+            assert!(poly.is_equilateral_with(1.0), "{}", cd);
+        }
+
+        assert!(Concrete::wythoff(&Cd::parse("x3o3o3o3o3o3o3o *c3o").unwrap()).is_none());
+        assert!(Concrete::wythoff(&Cd::parse("s3s4o").unwrap()).is_none());
+    }
+
+    /// Builds some isogonal polytopes from generator points in the
+    /// fundamental region.
+    #[test]
+    fn wythoff_at() {
+        let cox = Cox::parse("o4o3o").unwrap();
+
+        let cube = Concrete::wythoff_at(&cox, &[0.5, 0.0, 0.0]).unwrap();
+        crate::test(&cube, [1, 8, 12, 6, 1]);
+
+        let great_rhombicubocta = Concrete::wythoff_at(&cox, &[0.5, 0.5, 0.5]).unwrap();
+        crate::test(&great_rhombicubocta, [1, 48, 72, 26, 1]);
+        assert!(great_rhombicubocta.is_equilateral_with(1.0));
+
+        // Moving the point around doesn't change the combinatorics, as long
+        // as it doesn't land on a mirror.
+        let poly = Concrete::wythoff_at(&cox, &[0.2, 0.5, 0.3]).unwrap();
+        poly.assert_valid();
+        crate::test(&poly, [1, 48, 72, 26, 1]);
+        assert!(!poly.is_equilateral());
+
+        assert!(Concrete::wythoff_at(&cox, &[0.5, 0.5]).is_none());
+    }
+}
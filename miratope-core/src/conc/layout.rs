@@ -0,0 +1,186 @@
+//! Places the vertices of an abstract polytope in space, so that polytopes
+//! without any faithful realization can still be displayed.
+//!
+//! We use a force-directed layout: every pair of vertices is joined by a
+//! spring whose natural length is their distance in the edge graph of the
+//! polytope, and the vertices are moved so as to minimize the total stress on
+//! these springs. Adjacent vertices thus end up at roughly unit distance.
+
+use std::collections::VecDeque;
+
+use super::Concrete;
+use crate::{
+    abs::{Abstract, Ranked},
+    geometry::Point,
+};
+
+use vec_like::VecLike;
+
+/// The default number of iterations for the layout.
+pub const DEFAULT_ITERATIONS: usize = 300;
+
+/// Returns the distances between every pair of vertices in the edge graph of a
+/// polytope. Vertices in different components are placed one step further
+/// apart than the furthest vertices in the graph.
+fn graph_distances(abs: &Abstract) -> Vec<Vec<usize>> {
+    let n = abs.vertex_count();
+    let mut adj = vec![Vec::new(); n];
+
+    if abs.rank() > 2 {
+        for edge in &abs[2] {
+            if let [a, b] = edge.subs.as_slice() {
+                adj[*a].push(*b);
+                adj[*b].push(*a);
+            }
+        }
+    }
+
+    let mut dist = vec![vec![usize::MAX; n]; n];
+    for (start, row) in dist.iter_mut().enumerate() {
+        let mut queue = VecDeque::new();
+        row[start] = 0;
+        queue.push_back(start);
+
+        while let Some(v) = queue.pop_front() {
+            for &w in &adj[v] {
+                if row[w] == usize::MAX {
+                    row[w] = row[v] + 1;
+                    queue.push_back(w);
+                }
+            }
+        }
+    }
+
+    let max = dist
+        .iter()
+        .flatten()
+        .filter(|&&d| d != usize::MAX)
+        .max()
+        .copied()
+        .unwrap_or(0);
+    for d in dist.iter_mut().flatten() {
+        if *d == usize::MAX {
+            *d = max + 1;
+        }
+    }
+
+    dist
+}
+
+/// Returns the initial positions of the vertices. These are pseudorandom but
+/// fixed, so that the layout of a polytope is always the same.
+fn initial_positions(n: usize, dim: usize) -> Vec<Point<f64>> {
+    let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+    let mut next = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state >> 11) as f64 / (1u64 << 53) as f64 * 2.0 - 1.0
+    };
+
+    (0..n)
+        .map(|_| Point::from_iterator(dim, (0..dim).map(|_| next())))
+        .collect()
+}
+
+impl Concrete {
+    /// Realizes an abstract polytope in a space of a given dimension through
+    /// a force-directed layout of its edge graph, running a given number of
+    /// iterations. The result is centered at the origin.
+    ///
+    /// This realization is generally not faithful, and its faces generally
+    /// aren't flat. It's meant to display polytopes that don't have a better
+    /// realization.
+    pub fn force_directed(abs: Abstract, dim: usize, iterations: usize) -> Self {
+        let n = abs.vertex_count();
+        let dist = graph_distances(&abs);
+        let mut vertices = initial_positions(n, dim);
+
+        // Each step moves every vertex to the point that best satisfies its
+        // springs, with the others fixed. This never increases the stress.
+        for _ in 0..iterations {
+            for i in 0..n {
+                let mut sum = Point::zeros(dim);
+                let mut weights = 0.0;
+
+                for j in 0..n {
+                    if i == j {
+                        continue;
+                    }
+
+                    let d = dist[i][j] as f64;
+                    let w = 1.0 / (d * d);
+                    let diff = &vertices[i] - &vertices[j];
+                    let norm = diff.norm();
+
+                    let target = if norm > 0.0 {
+                        &vertices[j] + diff * (d / norm)
+                    } else {
+                        vertices[j].clone()
+                    };
+
+                    sum += target * w;
+                    weights += w;
+                }
+
+                if weights > 0.0 {
+                    vertices[i] = sum / weights;
+                }
+            }
+        }
+
+        let mut poly = Self::new(vertices, abs);
+        if n != 0 {
+            let center = poly
+                .vertices
+                .iter()
+                .fold(Point::zeros(dim), |acc, v| acc + v)
+                / n as f64;
+            for v in &mut poly.vertices {
+                *v -= &center;
+            }
+        }
+
+        poly
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{float::Float, Polytope};
+
+    /// Checks that the layout of a cube has distinct vertices and roughly
+    /// unit edges.
+    #[test]
+    fn cube() {
+        let cube = Abstract::cube();
+        let poly = Concrete::force_directed(cube.clone(), 3, DEFAULT_ITERATIONS);
+        assert_eq!(poly.vertex_count(), 8);
+        assert!(poly.vertices.iter().all(|v| v.len() == 3));
+
+        for (i, v) in poly.vertices.iter().enumerate() {
+            for w in &poly.vertices[i + 1..] {
+                assert!((v - w).norm() > f64::eps());
+            }
+        }
+
+        for edge in &cube[2] {
+            let len = (&poly.vertices[edge.subs[0]] - &poly.vertices[edge.subs[1]]).norm();
+            assert!(len > 0.5 && len < 1.5, "{}", len);
+        }
+
+        // The layout is deterministic.
+        let again = Concrete::force_directed(cube, 3, DEFAULT_ITERATIONS);
+        assert_eq!(poly.vertices, again.vertices);
+    }
+
+    /// Checks that small polytopes don't cause any issues.
+    #[test]
+    fn small() {
+        for poly in [Abstract::nullitope(), Abstract::point(), Abstract::dyad()] {
+            let count = poly.vertex_count();
+            assert_eq!(Concrete::force_directed(poly, 2, 10).vertex_count(), count);
+        }
+    }
+}
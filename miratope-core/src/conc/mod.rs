@@ -1,29 +1,47 @@
 //! Declares the [`Concrete`] polytope type and all associated data structures.
 
+pub mod augment;
+pub mod catalog;
+pub mod cleanup;
+pub mod color;
+pub mod complex;
+pub mod compound;
 pub mod cycle;
 pub mod element_types;
+pub mod exact;
 pub mod faceting;
+pub mod from_name;
+pub mod gosset;
+pub mod honeycomb;
+pub mod hull;
+pub mod layout;
+pub mod pipeline;
+pub mod prismatoid;
+pub mod recognize;
+pub mod script;
 pub mod symmetry;
 
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     ops::{Index, IndexMut}, iter,
 };
 
 use super::{
     abs::{
-        flag::{Flag, FlagChanges, FlagEvent, OrientedFlagIter},
+        flag::{Flag, FlagChanges, FlagEvent, OrientedFlag, OrientedFlagIter},
         Abstract, ElementList, Ranked, SubelementList,
     },
-    DualError, Polytope,
+    DualError, Polytope, SectionError,
 };
 use crate::{
     abs::{AbstractBuilder, Element, ElementMap, Subelements, Superelements},
+    conc::color::ColorData,
     float::Float,
     geometry::*,
 };
 
 use approx::{abs_diff_eq, abs_diff_ne};
+#[cfg(feature = "parallel")]
 use rayon::prelude::*;
 use vec_like::*;
 
@@ -38,6 +56,9 @@ pub struct Concrete {
 
     /// The underlying abstract polytope.
     pub abs: Abstract,
+
+    /// The colors of the elements of the polytope, if any were given.
+    pub colors: ColorData,
 }
 
 impl Index<usize> for Concrete {
@@ -93,7 +114,237 @@ impl Concrete {
         }
 
         // With no further info, we create a generic name for the polytope.
-        Self { vertices, abs }
+        Self {
+            vertices,
+            abs,
+            colors: ColorData::default(),
+        }
+    }
+
+    /// Computes the vertices of the dual of a polytope of positive rank with a
+    /// given reciprocation sphere. If `infinity` is set, the dual vertices of
+    /// the facets through the reciprocation center are sent to infinity with
+    /// [`Self::point_at_infinity`], and their indices are returned. Otherwise,
+    /// or if this fails, returns the index of the first such facet.
+    fn dual_vertices(
+        &self,
+        sphere: &Hypersphere<f64>,
+        infinity: Option<f64>,
+    ) -> Result<(Vec<Point<f64>>, Vec<usize>), DualError> {
+        let rank = self.rank();
+
+        // We project the sphere's center onto the polytope's hyperplane to
+        // avoid skew weirdness.
+        let h = Subspace::from_points(self.vertices.iter());
+        let o = h.project(&sphere.center);
+
+        // We project our inversion center onto each of the facets.
+        let mut projections: Vec<_> = if rank >= 2 {
+            #[cfg(feature = "parallel")]
+            let facets = (0..self.facet_count()).into_par_iter();
+            #[cfg(not(feature = "parallel"))]
+            let facets = 0..self.facet_count();
+
+            facets
+                .map(|idx| {
+                    Subspace::from_points(
+                        self.element_vertices_ref(rank - 1, idx)
+                            .unwrap()
+                            .into_iter(),
+                    )
+                    .project(&o)
+                })
+                .collect()
+        } else {
+            self.vertices.clone()
+        };
+
+        // Reciprocates the projected points.
+        let mut at_infinity = Vec::new();
+        for (idx, v) in projections.iter_mut().enumerate() {
+            if !sphere.reciprocate_mut(v) && rank != 1 {
+                match infinity.and_then(|dist| self.point_at_infinity(idx, &o, dist)) {
+                    Some(p) => {
+                        *v = p;
+                        at_infinity.push(idx);
+                    }
+                    None => return Err(DualError(idx)),
+                }
+            }
+        }
+
+        Ok((projections, at_infinity))
+    }
+
+    /// Returns a point standing in for the point at infinity dual to a facet
+    /// through a given center. It's placed at a given distance from the
+    /// center, in the direction normal to the facet that points away from the
+    /// rest of the polytope.
+    ///
+    /// Returns `None` if every vertex lies on the facet.
+    fn point_at_infinity(&self, idx: usize, center: &Point<f64>, dist: f64) -> Option<Point<f64>> {
+        let rank = self.rank();
+        let facet = self.abs.element_vertices(rank - 1, idx)?;
+        let hyperplane = Subspace::from_points(facet.iter().map(|&v| &self.vertices[v]));
+
+        self.vertices
+            .iter()
+            .find_map(|v| hyperplane.normal(v).map(|normal| center - normal * dist))
+    }
+
+    /// Builds the dual of a polytope with a given reciprocation sphere in
+    /// place. Unlike [`ConcretePolytope::try_dual_mut_with`], facets through
+    /// the reciprocation center don't make this fail. Instead, their dual
+    /// vertices are sent to infinity, which we model by placing them at the
+    /// given distance from the center, in the direction normal to the facet
+    /// that points away from the rest of the polytope. This makes the duals of
+    /// tilings and of some star polytopes representable.
+    ///
+    /// Returns the indices of the vertices at infinity. This only fails if a
+    /// facet through the center contains every vertex, in which case the
+    /// polytope is left unchanged and the index of the facet is returned.
+    pub fn dual_mut_with_infinity(
+        &mut self,
+        sphere: &Hypersphere<f64>,
+        infinity: f64,
+    ) -> Result<Vec<usize>, DualError> {
+        if self.rank() == 0 {
+            return Ok(Vec::new());
+        }
+
+        let (vertices, at_infinity) = self.dual_vertices(sphere, Some(infinity))?;
+        self.vertices = vertices;
+        self.abs.dual_mut();
+        self.colors.clear_elements();
+        Ok(at_infinity)
+    }
+
+    /// Returns the dual of a polytope with a given reciprocation sphere,
+    /// sending the dual vertices of the facets through the center to infinity,
+    /// as in [`Self::dual_mut_with_infinity`]. Also returns the indices of
+    /// the vertices at infinity.
+    pub fn dual_with_infinity(
+        &self,
+        sphere: &Hypersphere<f64>,
+        infinity: f64,
+    ) -> Result<(Self, Vec<usize>), DualError> {
+        let mut clone = self.clone();
+        clone
+            .dual_mut_with_infinity(sphere, infinity)
+            .map(|at_infinity| (clone, at_infinity))
+    }
+
+    /// Identifies the antipodal elements of a centrally symmetric polytope,
+    /// building a hemi-polytope, as in [`Abstract::antipodal_quotient`].
+    ///
+    /// The result is a projective model of the quotient: each pair of
+    /// antipodal vertices is represented by the one whose first nonzero
+    /// coordinate, measured from the center, is positive. Returns `None` if
+    /// the polytope isn't centrally symmetric, or if its quotient isn't a
+    /// valid polytope, which includes every polytope up to a dyad.
+    pub fn antipodal_quotient(&self) -> Option<Self> {
+        let rank = self.rank();
+        if rank < 3 {
+            return None;
+        }
+
+        let center = self.gravicenter()?;
+        let vertex_of: BTreeMap<_, _> = self
+            .vertices
+            .iter()
+            .map(|v| PointOrd::new(v.clone()))
+            .zip(0..)
+            .collect();
+
+        // The antipode of every vertex.
+        let mut antipodes = ElementMap::new();
+        antipodes.push(vec![0]);
+        antipodes.push(
+            self.vertices
+                .iter()
+                .map(|v| {
+                    let antipode = PointOrd::new(&center * 2.0 - v);
+                    vertex_of.get(&antipode).copied()
+                })
+                .collect::<Option<Vec<_>>>()?,
+        );
+
+        // The antipodes of every other element are found through their
+        // vertex sets.
+        for r in 2..rank {
+            let mut element_of = HashMap::new();
+            let mut images = Vec::with_capacity(self.el_count(r));
+
+            for idx in 0..self.el_count(r) {
+                let mut vertices = self.abs.element_vertices(r, idx)?;
+                let mut image: Vec<_> = vertices.iter().map(|&v| antipodes[(1, v)]).collect();
+                vertices.sort_unstable();
+                image.sort_unstable();
+
+                element_of.insert(vertices, idx);
+                images.push(image);
+            }
+
+            antipodes.push(
+                images
+                    .into_iter()
+                    .map(|image| element_of.get(&image).copied())
+                    .collect::<Option<Vec<_>>>()?,
+            );
+        }
+        antipodes.push(vec![0]);
+
+        let abs = self.abs.antipodal_quotient(&antipodes)?;
+
+        // The abstract quotient lists vertex pairs in the order of their
+        // vertex of lower index.
+        let vertices = (0..self.vertices.len())
+            .filter(|&v| v < antipodes[(1, v)])
+            .map(|v| {
+                let vertex = &self.vertices[v];
+                let upper = !(vertex - &center)
+                    .iter()
+                    .find(|x| x.abs() > f64::eps())
+                    .is_some_and(|&x| x < 0.0);
+
+                if upper {
+                    vertex.clone()
+                } else {
+                    self.vertices[antipodes[(1, v)]].clone()
+                }
+            })
+            .collect();
+
+        Some(Self::new(vertices, abs))
+    }
+
+    /// Returns the omnitruncate of a polytope, as in
+    /// [`Polytope::omnitruncate`].
+    ///
+    /// Every so often, `progress` is called with an estimate of the work done
+    /// and of the total work. The construction stops and returns `None` if it
+    /// returns `false`.
+    ///
+    /// # Panics
+    /// You must call [`Polytope::element_sort`] before calling this method.
+    pub fn omnitruncate_with<F: FnMut(usize, usize) -> bool>(&self, progress: F) -> Option<Self> {
+        let (abs, flags) = self.abs.omnitruncate_and_flags_with(progress)?;
+        let element_vertices = self.avg_vertex_map();
+        let dim = self.dim_or();
+
+        Some(Self::new(
+            flags
+                .into_iter()
+                .map(|flag| {
+                    let mut vertex = Point::zeros(dim);
+                    for el in flag.into_iter().enumerate().skip(1).take(self.rank()) {
+                        vertex += &element_vertices[el];
+                    }
+                    vertex
+                })
+                .collect(),
+            abs,
+        ))
     }
 }
 
@@ -153,7 +404,11 @@ impl Polytope for Concrete {
     /// polytope in place. If unsuccessful, leaves the polytope unchanged and
     /// returns `false`.
     fn petrial_mut(&mut self) -> bool {
-        self.abs.petrial_mut()
+        let petrial = self.abs.petrial_mut();
+        if petrial {
+            self.colors.clear_elements();
+        }
+        petrial
     }
 
     /// Builds the Petrie polygon of a polytope from a given flag, or returns
@@ -176,6 +431,8 @@ impl Polytope for Concrete {
     /// # Panics
     /// This method will panic if the polytopes have different ranks.
     fn comp_append(&mut self, mut p: Self) {
+        let (vertex_count, face_count) = (self.vertex_count(), self.el_count(3));
+        self.colors.append(p.colors, vertex_count, face_count);
         self.abs.comp_append(p.abs);
         self.vertices.append(&mut p.vertices);
     }
@@ -194,45 +451,54 @@ impl Polytope for Concrete {
         ))
     }
 
+    /// Gets the polytope whose facets are the elements with a given rank and
+    /// given indices, if they all exist. The vertices are reindexed.
+    fn elements(&self, rank: usize, idxs: &[usize]) -> Option<Self> {
+        let (vertices, abs) = self.abs.elements_and_vertices(rank, idxs)?;
+
+        Some(Self::new(
+            vertices
+                .into_iter()
+                .map(|idx| self.vertices[idx].clone())
+                .collect(),
+            abs,
+        ))
+    }
+
     /// Gets the element figure with a given rank and index as a polytope.
-    fn element_fig(&self, rank: usize, idx: usize) -> Result<Option<Self>, Self::DualError> {
-        if rank <= self.rank() {
-            // todo: this is quite inefficient for a small element figure since
-            // we take the dual of the entire thing.
-            if let Some(mut element_fig) = self.try_dual()?.element(self.rank() - rank, idx) {
-                let subspace = Subspace::from_points(element_fig.vertices.iter());
-                element_fig.flatten();
-                element_fig.recenter_with(
-                    &subspace.flatten(&subspace.project(&Point::zeros(self.dim().unwrap()))),
-                );
-                element_fig.try_dual_mut()?;
-                return Ok(Some(element_fig));
-            }
+    fn element_fig(&self, rank: usize, idx: usize) -> Result<Self, SectionError<Self::DualError>> {
+        crate::check_element(self, rank, idx)?;
+        let fig_rank = self.rank() - rank;
+
+        // These cases would otherwise require us to take the dual of a
+        // nullitope or to project onto an empty subspace.
+        if fig_rank == 0 {
+            return Ok(Self::nullitope());
+        } else if rank == 0 {
+            return Ok(self.clone());
         }
 
-        Ok(None)
+        // todo: this is quite inefficient for a small element figure since
+        // we take the dual of the entire thing.
+        let mut element_fig = self
+            .try_dual()
+            .map_err(SectionError::Dual)?
+            .element(fig_rank, idx)
+            .expect("the element was checked to exist");
+
+        let subspace = Subspace::from_points(element_fig.vertices.iter());
+        element_fig.flatten();
+        element_fig
+            .recenter_with(&subspace.flatten(&subspace.project(&Point::zeros(self.dim_or()))));
+        element_fig.try_dual_mut().map_err(SectionError::Dual)?;
+        Ok(element_fig)
     }
 
     // TODO: A method that builds an omnitruncate together with a map from flags
     // to vertices? We got some math details to figure out.
     fn omnitruncate(&self) -> Self {
-        let (abs, flags) = self.abs.omnitruncate_and_flags();
-        let element_vertices = self.avg_vertex_map();
-
-        Self::new(
-            flags
-                .into_iter()
-                .map(|flag| {
-                    flag.into_iter()
-                        .enumerate()
-                        .skip(1)
-                        .take(self.rank())
-                        .map(|el| &element_vertices[el])
-                        .sum()
-                })
-                .collect(),
-            abs,
-        )
+        self.omnitruncate_with(|_, _| true)
+            .expect("the omnitruncate was never stopped")
     }
 
     /// Builds a [duopyramid](https://polytope.miraheze.org/wiki/Pyramid_product)
@@ -277,24 +543,34 @@ impl Polytope for Concrete {
 
     /// Builds a [ditope](https://polytope.miraheze.org/wiki/Ditope) of a given
     /// polytope in place.
+    ///
+    /// Both facets of the ditope have the same vertices as the original
+    /// polytope. In spherical space, these would be the two hemispheres
+    /// bounded by the original polytope. In flat space, they coincide.
     fn ditope_mut(&mut self) {
-        self.abs.ditope_mut();
-    }
+        // The ditope of a point is a dyad with two coincident vertices.
+        if self.rank() == 1 {
+            let vertex = self.vertices[0].clone();
+            self.vertices.push(vertex);
+        }
 
-    /// Builds a [hosotope](https://polytope.miraheze.org/wiki/hosotope) of a
-    /// given polytope.
-    fn hosotope(&self) -> Self {
-        Self::new(
-            vec![vec![-0.5].into(), vec![0.5].into()],
-            self.abs.hosotope(),
-        )
+        self.abs.ditope_mut();
     }
 
     /// Builds a [hosotope](https://polytope.miraheze.org/wiki/hosotope) of a
     /// given polytope in place.
+    ///
+    /// The two vertices of the hosotope are placed at the poles of the
+    /// circumsphere of the original polytope, along a new axis. In spherical
+    /// space, the edges would be the meridians through each of the original
+    /// vertices, and the faces would be lunes. In flat space, all of them
+    /// collapse onto the segment between both poles.
     fn hosotope_mut(&mut self) {
-        self.vertices = vec![vec![-0.5].into(), vec![0.5].into()];
-        self.abs.hosotope_mut();
+        if self.rank() != 0 {
+            self.vertices = hosotope_poles(self);
+            self.abs.hosotope_mut();
+            self.colors.clear_elements();
+        }
     }
 
     /// Attempts to build an antiprism based on a given polytope. Uses the unit
@@ -308,6 +584,17 @@ impl Polytope for Concrete {
         Self::try_antiprism_with(self, &Hypersphere::unit(self.dim().unwrap_or(1)), 1.0)
     }
 
+    /// Attempts to build an antitegum based on a given polytope, as the dual
+    /// of the antiprism built by [`Self::try_antiprism`]. If it fails, it
+    /// returns the index of a facet of the antiprism through the inversion
+    /// center.
+    ///
+    /// If you want more control over the arguments, you can use
+    /// [`Self::try_antitegum_with`].
+    fn try_antitegum(&self) -> Result<Self, Self::DualError> {
+        Self::try_antitegum_with(self, &Hypersphere::unit(self.dim().unwrap_or(1)), 1.0)
+    }
+
     /// Builds a [simplex](https://polytope.miraheze.org/wiki/Simplex) with a
     /// given rank.
     fn simplex(rank: usize) -> Self {
@@ -346,6 +633,31 @@ impl Polytope for Concrete {
         }
     }
 
+    /// Builds a [demihypercube](https://polytope.miraheze.org/wiki/Demihypercube)
+    /// with a given rank and unit edge length, as the convex hull of alternate
+    /// vertices of a hypercube. The rank must be at least 4.
+    fn demihypercube(rank: usize) -> Self {
+        assert!(rank >= 4, "A demihypercube must have rank at least 4.");
+        let dim = rank - 1;
+        let coord = f64::HALF_SQRT_2 / 2.0;
+
+        // The vertices with an even number of negative coordinates.
+        let vertices: Vec<_> = (0..1usize << dim)
+            .filter(|mask| mask.count_ones() % 2 == 0)
+            .map(|mask| {
+                Point::from_fn(dim, |i, _| {
+                    if mask & (1 << i) == 0 {
+                        coord
+                    } else {
+                        -coord
+                    }
+                })
+            })
+            .collect();
+
+        Self::convex_hull(&vertices).expect("the vertex set is nonempty")
+    }
+
     /// Splits compound faces into their components.
     fn untangle_faces(&mut self) {
         self.abs.untangle_faces();
@@ -353,7 +665,9 @@ impl Polytope for Concrete {
 }
 
 /// Generates the vertices for either a tegum or a pyramid product with two
-/// given vertex sets and a given height.
+/// given vertex sets. In the case of a pyramid product, the vertices of `p` are
+/// placed at height `p_height` and those of `q` are placed at height
+/// `-q_height`.
 ///
 /// The vertices are the padded vertices of `p`, followed by the padded
 /// vertices of `q`.
@@ -362,7 +676,8 @@ fn duopyramid_vertices(
     q: &[Point<f64>],
     p_pad: &Point<f64>,
     q_pad: &Point<f64>,
-    height: f64,
+    p_height: f64,
+    q_height: f64,
     tegum: bool,
 ) -> Vec<Point<f64>> {
     // Duotegums with points should just return the original polytopes.
@@ -374,14 +689,12 @@ fn duopyramid_vertices(
         }
     }
 
-    let half_height = height / 2.0;
-
     // To every point in p, we append zeros to the right.
     p.iter()
         .map(|vp| {
             let mut v: Vec<_> = vp.iter().copied().chain(q_pad.iter().copied()).collect();
             if !tegum {
-                v.push(half_height);
+                v.push(p_height);
             }
             v.into()
         })
@@ -389,7 +702,7 @@ fn duopyramid_vertices(
         .chain(q.iter().map(|vq| {
             let mut v: Vec<_> = p_pad.iter().copied().chain(vq.iter().copied()).collect();
             if !tegum {
-                v.push(-half_height);
+                v.push(-q_height);
             }
             v.into()
         }))
@@ -422,6 +735,431 @@ fn duoprism_vertices(p: &[Point<f64>], q: &[Point<f64>]) -> Vec<Point<f64>> {
         .collect::<Vec<_>>()
 }
 
+/// Generates the two vertices of the hosotope of a polytope. These are the
+/// poles of its circumsphere along a new axis. If the polytope has no
+/// circumsphere, or if its circumradius is zero, we use a sphere of radius 1/2
+/// around its gravicenter instead.
+fn hosotope_poles(p: &Concrete) -> Vec<Point<f64>> {
+    let (center, radius) = match p.circumsphere() {
+        Some(sphere) if sphere.radius() > f64::eps() => {
+            let radius = sphere.radius();
+            (sphere.center, radius)
+        }
+        _ => (
+            p.gravicenter().unwrap_or_else(|| Point::zeros(p.dim_or())),
+            0.5,
+        ),
+    };
+
+    [-radius, radius]
+        .iter()
+        .map(|&height| {
+            center
+                .iter()
+                .copied()
+                .chain(iter::once(height))
+                .collect::<Vec<_>>()
+                .into()
+        })
+        .collect()
+}
+
+/// Keeps the closed part of a polytope on one side of a hyperplane, and
+/// patches the cut into a new facet. If `below` is set, we keep the part
+/// opposite to the normal of the hyperplane. Vertices on the hyperplane belong
+/// to either part, and each of them is used as the cut through every element
+/// that touches the hyperplane only at it, so that splitting a polytope in two
+/// gives the same cut face on both sides.
+fn clip(p: &Concrete, hyperplane: &Hyperplane<f64>, below: bool) -> Concrete {
+    let rank = p.rank();
+    let sign = if below { 1.0 } else { -1.0 };
+    let dist: Vec<_> = p
+        .vertices
+        .iter()
+        .map(|v| hyperplane.distance(v) * sign)
+        .collect();
+    let kept: Vec<_> = dist.iter().map(|&d| d < -f64::eps()).collect();
+    let removed: Vec<_> = dist.iter().map(|&d| d > f64::eps()).collect();
+
+    if !removed.iter().any(|&r| r) {
+        return p.clone();
+    } else if !kept.iter().any(|&k| k) {
+        return Concrete::nullitope();
+    }
+
+    // Whether each element has vertices on the kept and on the removed side.
+    // Elements with neither lie on the hyperplane.
+    let mut has_kept = vec![Vec::new(), kept];
+    let mut has_removed = vec![Vec::new(), removed];
+    for r in 2..=rank {
+        let (k, l) = p[r]
+            .iter()
+            .map(|el| {
+                (
+                    el.subs.iter().any(|&sub| has_kept[r - 1][sub]),
+                    el.subs.iter().any(|&sub| has_removed[r - 1][sub]),
+                )
+            })
+            .unzip();
+        has_kept.push(k);
+        has_removed.push(l);
+    }
+    let on_plane = |r: usize, idx: usize| !has_kept[r][idx] && !has_removed[r][idx];
+
+    // Whether each element belongs to the kept part. Elements on the
+    // hyperplane belong to it whenever they bound an element that does.
+    let mut in_part = vec![Vec::new(); rank + 1];
+    in_part[rank] = vec![true; p.el_count(rank)];
+    for r in (1..rank).rev() {
+        let row = p[r]
+            .iter()
+            .enumerate()
+            .map(|(idx, el)| {
+                has_kept[r][idx]
+                    || (on_plane(r, idx) && el.sups.iter().any(|&sup| in_part[r + 1][sup]))
+            })
+            .collect();
+        in_part[r] = row;
+    }
+
+    // The index of the kept part of each element, and the indices of the
+    // pieces of the cut through each element, in the new polytope.
+    let mut vertices: Vec<Point<f64>> = Vec::new();
+    let mut clipped = vec![Vec::new(), Vec::new()];
+    let mut cut: Vec<Vec<Vec<usize>>> = vec![Vec::new(), Vec::new()];
+    for (v, &k) in in_part[1].iter().enumerate() {
+        if k {
+            clipped[1].push(Some(vertices.len()));
+            vertices.push(p.vertices[v].clone());
+        } else {
+            clipped[1].push(None);
+        }
+    }
+
+    let mut ranks = vec![SubelementList::new(); rank + 1];
+    for r in 2..=rank {
+        let mut cut_row = Vec::with_capacity(p.el_count(r));
+
+        for (idx, el) in p[r].iter().enumerate() {
+            if !(has_kept[r][idx] && has_removed[r][idx]) {
+                cut_row.push(Vec::new());
+                continue;
+            }
+
+            // The cut through an edge with an endpoint on each side is a new
+            // vertex.
+            if r == 2 {
+                let (i, j) = (el.subs[0], el.subs[1]);
+                let (v0, v1) = (&p.vertices[i], &p.vertices[j]);
+                let point = v0 + (v1 - v0) * (dist[i] / (dist[i] - dist[j]));
+                cut_row.push(vec![vertices.len()]);
+                vertices.push(point);
+                continue;
+            }
+
+            let mut subs: Vec<_> = el
+                .subs
+                .iter()
+                .flat_map(|&sub| cut[r - 1][sub].iter().copied())
+                .collect();
+
+            // The elements two ranks below that lie on the hyperplane are
+            // also part of the cut, if they separate a subelement on the kept
+            // side from one that isn't.
+            let mut kept_sups = BTreeMap::new();
+            for &sub in &el.subs {
+                for &low in &p[(r - 1, sub)].subs {
+                    if on_plane(r - 2, low) {
+                        *kept_sups.entry(low).or_insert(0) += usize::from(has_kept[r - 1][sub]);
+                    }
+                }
+            }
+            subs.extend(
+                kept_sups
+                    .into_iter()
+                    .filter(|&(_, count)| count == 1)
+                    .filter_map(|(low, _)| clipped[r - 2][low]),
+            );
+
+            // The cut through a non-convex face can consist of many edges.
+            // These pair up the intersection points in order along the line.
+            if r == 3 {
+                subs.sort_unstable_by_key(|&v| PointOrd::new(vertices[v].clone()));
+                let mut edges = Vec::new();
+                for pair in subs.chunks(2) {
+                    edges.push(ranks[2].len());
+                    ranks[2].push(pair.to_vec().into());
+                }
+                cut_row.push(edges);
+            } else {
+                cut_row.push(vec![ranks[r - 1].len()]);
+                ranks[r - 1].push(subs.into());
+            }
+        }
+        cut.push(cut_row);
+
+        // The maximal element is added by the builder.
+        if r == rank {
+            break;
+        }
+
+        let mut clipped_row = Vec::with_capacity(p.el_count(r));
+        for (idx, el) in p[r].iter().enumerate() {
+            if !in_part[r][idx] {
+                clipped_row.push(None);
+                continue;
+            }
+
+            let subs: Subelements = el
+                .subs
+                .iter()
+                .filter_map(|&sub| clipped[r - 1][sub])
+                .chain(cut[r][idx].iter().copied())
+                .collect();
+            clipped_row.push(Some(ranks[r].len()));
+            ranks[r].push(subs);
+        }
+        clipped.push(clipped_row);
+    }
+
+    let mut builder = AbstractBuilder::with_rank_capacity(rank);
+    builder.push_min();
+    builder.push_vertices(vertices.len());
+    for subelements in ranks.into_iter().take(rank).skip(2) {
+        builder.push(subelements);
+    }
+    builder.push_max();
+
+    // Safety: cutting an element by a hyperplane and closing it with the
+    // section gives a valid element. Elements on the hyperplane are shared by
+    // both, rather than copied, so this also holds for hyperplanes through
+    // vertices or other elements.
+    Concrete::new(vertices, unsafe { builder.build() })
+}
+
+/// The volume of a polytope, or the reason why it doesn't have one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Volume {
+    /// The polytope has a positive volume.
+    Measure(f64),
+
+    /// The polytope spans less dimensions than its rank would require, so its
+    /// volume is zero.
+    Degenerate,
+
+    /// The polytope spans as many dimensions as its rank would require, but
+    /// the contributions to its volume cancel out.
+    ZeroMeasure,
+
+    /// Some component of the polytope is non-orientable, so its volume is
+    /// undefined.
+    NonOrientable,
+
+    /// Either the polytope is the nullitope, or some component of it is skew,
+    /// that is, it spans more dimensions than its rank would allow. Either way,
+    /// its volume is undefined.
+    Undefined,
+}
+
+impl Volume {
+    /// Returns the volume as a number, or `None` if it's undefined. Degenerate
+    /// polytopes and polytopes of zero measure have volume 0.
+    pub fn value(self) -> Option<f64> {
+        match self {
+            Self::Measure(volume) => Some(volume),
+            Self::Degenerate | Self::ZeroMeasure => Some(0.0),
+            Self::NonOrientable | Self::Undefined => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Volume {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Measure(volume) => write!(f, "{}", volume),
+            Self::Degenerate => write!(f, "0, since the polytope is degenerate"),
+            Self::ZeroMeasure => write!(f, "0, since the contributions cancel out"),
+            Self::NonOrientable => write!(f, "undefined, since the polytope is non-orientable"),
+            Self::Undefined => write!(f, "undefined"),
+        }
+    }
+}
+
+/// A point that a polytope can be centered at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Center {
+    /// The center of mass of the interior of the polytope. See
+    /// [`ConcretePolytope::centroid`].
+    Centroid,
+
+    /// The barycenter of the vertices of the polytope. See
+    /// [`ConcretePolytope::gravicenter`].
+    Gravicenter,
+
+    /// The center of the circumsphere of the polytope. See
+    /// [`ConcretePolytope::circumsphere`].
+    Circumcenter,
+}
+
+/// A length that a polytope can be rescaled by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Scale {
+    /// The greatest distance from the center to a vertex. This is the
+    /// circumradius whenever the polytope is centered at its circumcenter.
+    Circumradius,
+
+    /// The average length of the edges.
+    EdgeLength,
+}
+
+/// The contribution of a single component of a polytope to its volume.
+struct ComponentVolume {
+    /// The signed volume of the component.
+    volume: f64,
+
+    /// The sum of the centroids of the simplices the component is made out of,
+    /// weighted by their signed volumes.
+    moment: Point<f64>,
+
+    /// Whether the component spans as many dimensions as its rank requires.
+    full_rank: bool,
+}
+
+/// Splits the flags of a polytope into its components, each of them given as a
+/// list of consistently oriented flags. Returns `None` if the polytope isn't
+/// orientable.
+///
+/// # Panics
+/// You must call [`Polytope::element_sort`] before calling this method.
+fn oriented_components(p: &Concrete) -> Option<Vec<Vec<OrientedFlag>>> {
+    let rank = p.rank();
+    let mut components = Vec::new();
+
+    // All of the flags we've found so far.
+    let mut all_flags = HashSet::new();
+
+    // We iterate over all flags in the polytope.
+    for flag in p.flags() {
+        // If this flag forms a new component of the polytope, we iterate over
+        // the oriented flags in this component.
+        if all_flags.contains(&flag) {
+            continue;
+        }
+
+        let mut flags = Vec::new();
+        for flag_event in OrientedFlagIter::with_flags(p.abs(), FlagChanges::all(rank), flag.into())
+        {
+            if let FlagEvent::Flag(oriented_flag) = flag_event {
+                let new = all_flags.insert(oriented_flag.flag.clone());
+                debug_assert!(new, "A flag is in two different components.");
+                flags.push(oriented_flag);
+            } else {
+                return None;
+            }
+        }
+
+        components.push(flags);
+    }
+
+    Some(components)
+}
+
+/// Returns the vertices of a flag of a polytope with a given rank, excluding
+/// the minimal and maximal elements, as given by a map from every element to
+/// one of its vertices.
+fn flag_vertices(vertex_map: &ElementMap<usize>, flag: &Flag, rank: usize) -> Vec<usize> {
+    flag.iter()
+        .enumerate()
+        .skip(1)
+        .take(rank - 1)
+        .map(|(r, &idx)| vertex_map[(r, idx)])
+        .collect()
+}
+
+/// Computes the signed volumes of every component of a polytope. If any of them
+/// are undefined, returns the reason why.
+///
+/// # Panics
+/// You must call [`Polytope::element_sort`] before calling this method.
+fn component_volumes(p: &Concrete) -> Result<Vec<ComponentVolume>, Volume> {
+    let rank = p.rank();
+
+    // We leave the nullitope's volume undefined.
+    if rank == 0 {
+        return Err(Volume::Undefined);
+    }
+
+    // Maps every element of the polytope to one of its vertices.
+    let vertex_map = p.vertex_map();
+    let dim = p.dim_or();
+    let factorial = f64::u32(crate::factorial(rank - 1));
+    let mut components = Vec::new();
+
+    // A non-orientable polytope doesn't have a volume.
+    for flags in oriented_components(p).ok_or(Volume::NonOrientable)? {
+        // The subspace spanned by this component.
+        let vertices: HashSet<_> = flags
+            .iter()
+            .map(|oriented_flag| oriented_flag.flag[1])
+            .collect();
+        let subspace = Subspace::from_points(vertices.iter().map(|&v| &p.vertices[v]));
+
+        match subspace.rank().cmp(&(rank - 1)) {
+            // Degenerate components have volume 0.
+            std::cmp::Ordering::Less => {
+                components.push(ComponentVolume {
+                    volume: 0.0,
+                    moment: Point::zeros(dim),
+                    full_rank: false,
+                });
+                continue;
+            }
+            // Skew components don't have a defined volume.
+            std::cmp::Ordering::Greater => return Err(Volume::Undefined),
+            _ => {}
+        }
+
+        // For each flag, there's a simplex defined by any vertices in its
+        // elements and the origin of the subspace. We add up the volumes of all
+        // of these simplices times the sign of the flag that generated them.
+        let mut volume = 0.0;
+        let mut moment = Point::zeros(dim);
+
+        for oriented_flag in flags {
+            let indices = flag_vertices(&vertex_map, &oriented_flag.flag, rank);
+            let flat_vertices: Vec<_> = indices
+                .iter()
+                .map(|&v| subspace.flatten(&p.vertices[v]))
+                .collect();
+
+            let simplex_volume = oriented_flag.orientation.sign()
+                * Matrix::from_iterator(
+                    rank - 1,
+                    rank - 1,
+                    flat_vertices.iter().flatten().copied(),
+                )
+                .determinant()
+                / factorial;
+
+            let centroid = indices
+                .iter()
+                .fold(subspace.offset.clone(), |acc, &v| acc + &p.vertices[v])
+                / f64::usize(rank);
+
+            volume += simplex_volume;
+            moment += centroid * simplex_volume;
+        }
+
+        components.push(ComponentVolume {
+            volume,
+            moment,
+            full_rank: true,
+        });
+    }
+
+    Ok(components)
+}
+
 /// A trait for concrete polytopes.
 ///
 /// This trait exists so that we can reuse this code for `miratope_lang`. The
@@ -525,6 +1263,113 @@ pub trait ConcretePolytope: Polytope {
         }
     }
 
+    /// Returns the center of a polytope of a given kind, or `None` if it
+    /// doesn't have one.
+    ///
+    /// # Panics
+    /// You must call [`Polytope::element_sort`] before asking for the
+    /// [centroid](Center::Centroid).
+    fn center(&self, center: Center) -> Option<Point<f64>> {
+        match center {
+            Center::Centroid => self.centroid(),
+            Center::Gravicenter => self.gravicenter(),
+            Center::Circumcenter => self.circumsphere().map(|sphere| sphere.center),
+        }
+    }
+
+    /// Recenters a polytope so that its center of a given kind is at the
+    /// origin. Returns `false` and leaves the polytope untouched if it doesn't
+    /// have such a center.
+    fn recenter_by(&mut self, center: Center) -> bool {
+        if center == Center::Centroid && !self.abs().sorted() {
+            self.element_sort();
+        }
+
+        match self.center(center) {
+            Some(p) => {
+                self.recenter_with(&p);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Recenters a polytope at its center of a given kind, and rescales it so
+    /// that a given length becomes 1. Returns `false` and leaves the polytope
+    /// untouched if it doesn't have such a center, or if the length is zero.
+    fn normalize_with(&mut self, center: Center, scale: Scale) -> bool {
+        if center == Center::Centroid && !self.abs().sorted() {
+            self.element_sort();
+        }
+
+        let center = match self.center(center) {
+            Some(center) => center,
+            None => return false,
+        };
+
+        let len = match scale {
+            Scale::Circumradius => self
+                .vertices()
+                .iter()
+                .map(|v| (v - &center).norm())
+                .fold(0.0, f64::max),
+            Scale::EdgeLength => {
+                let edge_count = self.edge_count();
+                (0..edge_count)
+                    .filter_map(|idx| self.edge_len(idx))
+                    .sum::<f64>()
+                    / f64::usize(edge_count.max(1))
+            }
+        };
+
+        if abs_diff_eq!(len, 0.0, epsilon = f64::eps()) {
+            return false;
+        }
+
+        self.recenter_with(&center);
+        self.scale(1.0 / len);
+        true
+    }
+
+    /// Rescales a polytope to unit circumradius and recenters it at its
+    /// centroid, so that files of wildly different scales can be compared.
+    /// Polytopes without a centroid, like flat or non-orientable ones, are
+    /// recentered at their gravicenter instead. Returns `false` and leaves the
+    /// polytope untouched if neither works.
+    fn normalize(&mut self) -> bool {
+        self.normalize_with(Center::Centroid, Scale::Circumradius)
+            || self.normalize_with(Center::Gravicenter, Scale::Circumradius)
+    }
+
+    /// Translates a polytope by a given vector.
+    fn translate(&mut self, v: &Vector<f64>) {
+        for w in self.vertices_mut() {
+            *w += v;
+        }
+    }
+
+    /// Rotates a polytope by a given angle in radians about a center, within
+    /// the plane spanned by the `i`-th and `j`-th coordinate axes. The
+    /// rotation takes the `i`-th axis towards the `j`-th one.
+    ///
+    /// # Panics
+    /// Will panic if either axis is out of bounds.
+    fn rotate_about(&mut self, center: &Point<f64>, i: usize, j: usize, angle: f64) {
+        self.transform(&Affine::axis_rotation(center, i, j, angle));
+    }
+
+    /// Reflects a polytope through a hyperplane.
+    fn reflect_through(&mut self, hyperplane: &Hyperplane<f64>) {
+        self.transform(&Affine::reflection(hyperplane));
+    }
+
+    /// Applies an affine transformation to all vertices of a polytope.
+    fn transform(&mut self, affine: &Affine<f64>) {
+        for v in self.vertices_mut() {
+            *v = affine.apply(v);
+        }
+    }
+
     /// Applies a linear transformation to all vertices of a polytope.
     fn apply(mut self, m: &Matrix<f64>) -> Self {
         for v in self.vertices_mut() {
@@ -540,35 +1385,10 @@ pub trait ConcretePolytope: Polytope {
     /// Calculates the circumsphere of a polytope. Returns `None` if the
     /// polytope isn't circumscribable.
     fn circumsphere(&self) -> Option<Hypersphere<f64>> {
-        let mut vertices = self.vertices().iter();
-
-        let first_vertex = vertices.next()?.clone();
-        let mut center = first_vertex.clone();
-        let mut subspace = Subspace::new(first_vertex.clone());
-
-        for vertex in vertices {
-            // If the new vertex does not lie on the hyperplane of the others:
-            if let Some(basis_vector) = subspace.add(vertex) {
-                // Calculates the new circumcenter.
-                let distance: f64 = ((&center - vertex).norm_squared()
-                    - (&center - &first_vertex).norm_squared())
-                    / (2.0 * (vertex - &first_vertex).dot(basis_vector));
-
-                center += basis_vector * distance;
-            }
-            // If the new vertex lies on the others' hyperplane, but is not at
-            // the correct distance from the first vertex:
-            else if abs_diff_ne!(
-                (&center - &first_vertex).norm(),
-                (&center - vertex).norm(),
-                epsilon = f64::EPS
-            ) {
-                return None;
-            }
-        }
+        let center = Hypersphere::circumcenter(self.vertices().iter())?;
 
         Some(Hypersphere {
-            squared_radius: (&center - first_vertex).norm(),
+            squared_radius: (&center - &self.vertices()[0]).norm(),
             center,
         })
     }
@@ -649,7 +1469,7 @@ pub trait ConcretePolytope: Polytope {
     /// a specified edge length.
     fn is_equilateral_with(&self, len: f64) -> bool {
         (0..self.edge_count())
-            .all(|idx| abs_diff_eq!(self.edge_len(idx).unwrap(), len, epsilon = f64::EPS))
+            .all(|idx| abs_diff_eq!(self.edge_len(idx).unwrap(), len, epsilon = f64::eps()))
     }
 
     /// Checks whether a polytope is equilateral to a fixed precision.
@@ -690,6 +1510,28 @@ pub trait ConcretePolytope: Polytope {
         Self::star_polygon_with_edge(n, d, 1.0).prism()
     }
 
+    /// Builds a [duocomb](https://polytope.miraheze.org/wiki/Honeycomb_product)
+    /// of an `{n₁ / d₁}` and an `{n₂ / d₂}` polygon, realized on the Clifford
+    /// torus of the unit 3-sphere.
+    ///
+    /// Both polygons are given circumradius `1 / √2`, so that every vertex is
+    /// at unit distance from the origin. The square faces of the duocomb are
+    /// then skew quadrilaterals wrapping around the torus, which can be
+    /// projected and rendered like any other 4D polytope.
+    fn clifford_duocomb(n1: usize, d1: usize, n2: usize, d2: usize) -> Self {
+        let mut p1 = Self::star_polygon(n1, d1);
+        p1.scale(f64::SQRT_2 / 2.0);
+
+        // Avoids duplicate work if possible.
+        if n1 == n2 && d1 == d2 {
+            p1.duocomb(&p1)
+        } else {
+            let mut p2 = Self::star_polygon(n2, d2);
+            p2.scale(f64::SQRT_2 / 2.0);
+            p1.duocomb(&p2)
+        }
+    }
+
     /// Builds a tegum with two specified apices.
     fn tegum_with(&self, apex1: Point<f64>, apex2: Point<f64>) -> Self;
 
@@ -721,6 +1563,42 @@ pub trait ConcretePolytope: Polytope {
         Ok(self.antiprism_with_vertices(vertices, dual_vertices))
     }
 
+    /// Builds an [antiprism](https://polytope.miraheze.org/wiki/Antiprism)
+    /// like [`Self::try_antiprism_with`], but rotates the dual base by `twist`
+    /// radians on the plane of the first two coordinates.
+    ///
+    /// If `cross` is set, the dual base is also reflected through the center
+    /// of the hypersphere. This turns the antiprism into a retrograde (crossed)
+    /// one, whose lateral edges pass through its axis.
+    fn try_antiprism_with_twist(
+        &self,
+        sphere: &Hypersphere<f64>,
+        height: f64,
+        twist: f64,
+        cross: bool,
+    ) -> Result<Self, Self::DualError> {
+        let half_height = height / 2.0;
+        let vertices = self.vertices().iter().map(|v| v.push(-half_height));
+
+        let dual = self.try_dual_with(sphere)?;
+        let (sin, cos) = twist.fsin_cos();
+        let sign = if cross { -1.0 } else { 1.0 };
+
+        let dual_vertices = dual.vertices().iter().map(|v| {
+            let mut v = (v - &sphere.center) * sign;
+
+            if v.len() >= 2 {
+                let (x, y) = (v[0], v[1]);
+                v[0] = x * cos - y * sin;
+                v[1] = x * sin + y * cos;
+            }
+
+            (v + &sphere.center).push(half_height)
+        });
+
+        Ok(self.antiprism_with_vertices(vertices, dual_vertices))
+    }
+
     /// Builds an antiprism, using a specified hypersphere to take a dual, and
     /// with a given height.
     ///
@@ -731,8 +1609,32 @@ pub trait ConcretePolytope: Polytope {
         self.try_antiprism_with(sphere, height).unwrap()
     }
 
+    /// Builds an [antitegum](https://polytope.miraheze.org/wiki/Antitegum)
+    /// as the dual of the antiprism built by [`Self::try_antiprism_with`]. The
+    /// dual is taken about the point halfway between both bases, on a
+    /// hypersphere with the same radius as the given one.
+    fn try_antitegum_with(
+        &self,
+        sphere: &Hypersphere<f64>,
+        height: f64,
+    ) -> Result<Self, Self::DualError> {
+        let antiprism = self.try_antiprism_with(sphere, height)?;
+        let center = sphere.center.push(0.0);
+        antiprism.try_dual_with(&Hypersphere::with_squared_radius(
+            center,
+            sphere.squared_radius,
+        ))
+    }
+
     /// Builds a uniform antiprism of unit edge length.
     fn uniform_antiprism(n: usize, d: usize) -> Self {
+        Self::twisted_antiprism(n, d, 0.0)
+    }
+
+    /// Builds an `{n / d}` antiprism with the base and height of the uniform
+    /// one, but whose top base is rotated by an additional `twist` radians.
+    /// The retrograde antiprisms are those with `n / 2 < d`.
+    fn twisted_antiprism(n: usize, d: usize, twist: f64) -> Self {
         let polygon = Self::star_polygon(n, d);
 
         // Appropriately scaled antiprism.
@@ -741,10 +1643,14 @@ pub trait ConcretePolytope: Polytope {
             let cos = angle.fcos();
             let height = ((cos - (2.0 * angle).fcos()) * 2.0).fsqrt();
 
-            let mut antiprism = polygon.antiprism_with(
-                &Hypersphere::with_squared_radius(Point::zeros(2), cos),
-                height,
-            );
+            let mut antiprism = polygon
+                .try_antiprism_with_twist(
+                    &Hypersphere::with_squared_radius(Point::zeros(2), cos),
+                    height,
+                    twist,
+                    false,
+                )
+                .unwrap();
             antiprism.scale(0.5 / (f64::PI * d as f64 / n as f64).fsin());
 
             antiprism
@@ -752,11 +1658,12 @@ pub trait ConcretePolytope: Polytope {
         // Digon compounds are a special case.
         else {
             let half_height = f64::HALF_SQRT_2;
-            let vertices = polygon.vertices().iter().map(|v| v.push(-half_height));
-            let dual_vertices = polygon
-                .vertices()
-                .iter()
-                .map(|v| vec![v[1], -v[0], half_height].into());
+            let (sin, cos) = twist.fsin_cos();
+            let vertices = polygon.vertices().iter().map(|v| v.push(-half_height));
+            let dual_vertices = polygon.vertices().iter().map(|v| {
+                let (x, y) = (v[1], -v[0]);
+                vec![x * cos - y * sin, x * sin + y * cos, half_height].into()
+            });
 
             let mut antiprism = polygon.antiprism_with_vertices(vertices, dual_vertices);
             antiprism.scale(0.5);
@@ -787,96 +1694,248 @@ pub trait ConcretePolytope: Polytope {
         height: f64,
     ) -> Self;
 
+    /// Generates a duopyramid from two given polytopes, where each base is
+    /// placed at its own height and offset.
+    ///
+    /// The vertices of `self` are placed at height `self_height` along the new
+    /// axis, and the vertices of `other` are placed at height `-other_height`.
+    /// Taking both heights as `height / 2.0` is the same as calling
+    /// [`Self::duopyramid_with`]. The abstract structure doesn't depend on any
+    /// of these parameters.
+    fn duopyramid_with_heights(
+        &self,
+        other: &Self,
+        self_offset: &Point<f64>,
+        other_offset: &Point<f64>,
+        self_height: f64,
+        other_height: f64,
+    ) -> Self;
+
     /// Generates a duopyramid from two given polytopes with a given offset.
     fn duotegum_with(p: &Self, q: &Self, p_offset: &Point<f64>, q_offset: &Point<f64>) -> Self;
 
+    /// Returns a copy of an equilateral polytope, rescaled to unit edge length
+    /// and moved so that its circumcenter is at the origin. Factors placed in
+    /// this way give isogonal products whenever they're isogonal themselves.
+    ///
+    /// Returns `None` if the polytope isn't equilateral or circumscribable.
+    fn unit_centered(&self) -> Option<Self> {
+        let sphere = self.circumsphere()?;
+        if !self.is_equilateral() {
+            return None;
+        }
+
+        let mut p = self.clone();
+        for v in p.vertices_mut() {
+            *v -= &sphere.center;
+        }
+
+        // Points and polytopes without edges are left at their size.
+        if let Some(len) = p.edge_len(0) {
+            if abs_diff_eq!(len, 0.0, epsilon = f64::eps()) {
+                return None;
+            }
+            p.scale(1.0 / len);
+        }
+
+        Some(p)
+    }
+
+    /// Generates a duoprism from two given polytopes, first rescaling them to
+    /// unit edge length and centering them at their circumcenters. The
+    /// duoprism of two uniform polytopes is then uniform.
+    ///
+    /// Returns `None` if either polytope isn't equilateral or circumscribable.
+    fn duoprism_unit(&self, other: &Self) -> Option<Self> {
+        Some(self.unit_centered()?.duoprism(&other.unit_centered()?))
+    }
+
+    /// Generates a duopyramid from two given polytopes, placing them so that
+    /// every edge joining a vertex of `self` to a vertex of `other` has unit
+    /// length. Both bases are placed at the same distance from the origin.
+    ///
+    /// Returns `None` if either polytope isn't circumscribable, or if their
+    /// circumradii are too large for this to be possible.
+    fn duopyramid_unit(&self, other: &Self) -> Option<Self> {
+        let (p, q) = (self.circumsphere()?, other.circumsphere()?);
+        let squared_height = 1.0 - p.radius().powi(2) - q.radius().powi(2);
+
+        if squared_height < -f64::eps() {
+            return None;
+        }
+
+        // Every vertex of a base is at the same distance from the projection
+        // of the other base's circumcenter.
+        let half_height = squared_height.max(0.0).sqrt() / 2.0;
+        Some(self.duopyramid_with_heights(other, &p.center, &q.center, half_height, half_height))
+    }
+
+    /// Generates a duotegum from two given polytopes, placing them so that
+    /// every edge joining a vertex of `p` to a vertex of `q` has unit length.
+    ///
+    /// Returns `None` if either polytope isn't circumscribable, or if their
+    /// circumradii don't allow for this.
+    fn duotegum_unit(p: &Self, q: &Self) -> Option<Self> {
+        let (p_sphere, q_sphere) = (p.circumsphere()?, q.circumsphere()?);
+        let squared_len = p_sphere.radius().powi(2) + q_sphere.radius().powi(2);
+
+        abs_diff_eq!(squared_len, 1.0, epsilon = f64::eps())
+            .then(|| Self::duotegum_with(p, q, &p_sphere.center, &q_sphere.center))
+    }
+
     /// Computes the volume of a polytope by adding up the contributions of all
-    /// flags. Returns `None` if the volume is undefined.
+    /// flags. The volumes of the components of a compound are computed
+    /// separately, each within its own subspace, and then added up.
     ///
     /// # Panics
     /// You must call [`Polytope::element_sort`] before calling this method.
-    fn volume(&self) -> Option<f64> {
-        let rank = self.rank();
+    fn volume(&self) -> Volume {
+        let components = match component_volumes(self.con()) {
+            Ok(components) => components,
+            Err(err) => return err,
+        };
+
+        let volume: f64 = components.iter().map(|c| c.volume.fabs()).sum();
+        if abs_diff_ne!(volume, 0.0, epsilon = f64::eps()) {
+            Volume::Measure(volume)
+        } else if components.iter().any(|c| c.full_rank) {
+            Volume::ZeroMeasure
+        } else {
+            Volume::Degenerate
+        }
+    }
 
-        // We leave the nullitope's volume undefined.
-        if rank == 0 {
+    /// Computes the volume of a polytope by adding up the contributions of all
+    /// flags. The volumes of the components of a compound are computed
+    /// separately, each within its own subspace, and then added up.
+    fn volume_mut(&mut self) -> Volume {
+        self.element_sort();
+        self.volume()
+    }
+
+    /// Computes the centroid of a polytope, that is, the center of mass of its
+    /// interior. The components of a compound are weighted by their volumes.
+    /// Returns `None` if the polytope doesn't have a positive volume.
+    ///
+    /// Not to be confused with the [gravicenter](Self::gravicenter), which is
+    /// the average of the vertices.
+    ///
+    /// # Panics
+    /// You must call [`Polytope::element_sort`] before calling this method.
+    fn centroid(&self) -> Option<Point<f64>> {
+        let components = component_volumes(self.con()).ok()?;
+        let volume: f64 = components.iter().map(|c| c.volume.fabs()).sum();
+
+        if abs_diff_eq!(volume, 0.0, epsilon = f64::eps()) {
             return None;
         }
 
-        // The flattened vertices (may possibly be the original vertices).
-        let subspace = Subspace::from_points(self.vertices().iter());
-        let flat_vertices = subspace.flatten_vec(self.vertices());
+        // Components with a negative orientation have negative moments too.
+        let moment = components
+            .into_iter()
+            .fold(Point::zeros(self.dim_or()), |acc, c| {
+                if c.volume < 0.0 {
+                    acc - c.moment
+                } else {
+                    acc + c.moment
+                }
+            });
 
-        match flat_vertices.get(0)?.len().cmp(&(rank - 1)) {
-            // Degenerate polytopes have volume 0.
-            std::cmp::Ordering::Less => {
-                return Some(0.0);
-            }
-            // Skew polytopes don't have a defined volume.
-            std::cmp::Ordering::Greater => {
+        Some(moment / volume)
+    }
+
+    /// Computes the density of the polytope around a given point, that is, the
+    /// number of times its surface winds around it. Each component of a
+    /// compound is oriented so that its volume is positive, and their
+    /// densities are added up. The result is meaningless for points on the
+    /// surface of the polytope.
+    ///
+    /// Returns `None` if the polytope isn't orientable, or if any of its
+    /// components isn't of full rank.
+    ///
+    /// # Panics
+    /// You must call [`Polytope::element_sort`] before calling this method.
+    fn density(&self, point: &Point<f64>) -> Option<isize> {
+        let p = self.con();
+        let rank = p.rank();
+        if rank < 2 {
+            return None;
+        }
+
+        let vertex_map = p.vertex_map();
+        let dim = rank - 1;
+
+        // The direction in which we cast rays. This shouldn't be parallel to
+        // any element of a reasonable polytope.
+        let direction = Point::from_iterator(
+            dim,
+            (0..dim).map(|i| (f64::usize(i) + 2.0).sqrt().fract() + 0.1),
+        )
+        .normalize();
+
+        let mut density = 0;
+        for flags in oriented_components(p)? {
+            let vertices: HashSet<_> = flags
+                .iter()
+                .map(|oriented_flag| oriented_flag.flag[1])
+                .collect();
+            let subspace = Subspace::from_points(vertices.iter().map(|&v| &p.vertices[v]));
+            if subspace.rank() != dim {
                 return None;
             }
-            _ => {}
-        }
 
-        // Maps every element of the polytope to one of its vertices.
-        let vertex_map = self.vertex_map();
-        let mut volume = 0.0;
+            // We build a cone over every flag from a point far away, and count
+            // how many of these contain the point. This is the number of times
+            // the surface crosses a ray from the point, counted with signs.
+            let point = subspace.flatten(point);
+            let radius = vertices
+                .iter()
+                .map(|&v| (subspace.flatten(&p.vertices[v]) - &point).norm())
+                .fold(0.0, f64::max);
+            let apex = &point + &direction * (2.0 * radius + 1.0);
+            let target = &point - &apex;
+
+            let mut volume = 0.0;
+            let mut winding = 0.0;
+            for oriented_flag in flags {
+                let columns: Vec<_> = flag_vertices(&vertex_map, &oriented_flag.flag, rank)
+                    .into_iter()
+                    .map(|v| subspace.flatten(&p.vertices[v]) - &apex)
+                    .collect();
+                let matrix = Matrix::from_iterator(dim, dim, columns.iter().flatten().copied());
+
+                let det = matrix.determinant();
+                if det.abs() < f64::eps() {
+                    continue;
+                }
 
-        // All of the flags we've found so far.
-        let mut all_flags = HashSet::new();
-
-        // We iterate over all flags in the polytope.
-        for flag in self.flags() {
-            // If this flag forms a new component of the polytope, we iterate
-            // over the oriented flags in this component.
-            if !all_flags.contains(&flag) {
-                let mut component_volume = 0.0;
-
-                for flag_event in
-                    OrientedFlagIter::with_flags(self.abs(), FlagChanges::all(rank), flag.into())
-                {
-                    if let FlagEvent::Flag(oriented_flag) = flag_event {
-                        let new = all_flags.insert(oriented_flag.flag.clone());
-                        debug_assert!(new, "A flag is in two different components.");
-
-                        // For each flag, there's a simplex defined by any vertices in its
-                        // elements and the origin. We add up the volumes of all of these
-                        // simplices times the sign of the flag that generated them.
-                        component_volume += oriented_flag.orientation.sign()
-                            * Matrix::from_iterator(
-                                rank - 1,
-                                rank - 1,
-                                oriented_flag
-                                    .into_iter()
-                                    .enumerate()
-                                    .skip(1)
-                                    .take(rank - 1)
-                                    .map(|(rank, idx)| &flat_vertices[vertex_map[(rank, idx)]])
-                                    .flatten()
-                                    .copied(),
-                            )
-                            .determinant();
-                    }
-                    // A non-orientable polytope doesn't have a volume.
-                    else {
-                        return None;
+                let sign = oriented_flag.orientation.sign() * det.signum();
+                volume += oriented_flag.orientation.sign() * det;
+
+                if let Some(coords) = matrix.lu().solve(&target) {
+                    if coords.iter().all(|&c| c >= 0.0) && coords.sum() <= 1.0 {
+                        winding += sign;
                     }
                 }
-
-                // We add up the volumes of all components.
-                volume += component_volume.fabs();
             }
+
+            let winding = winding.round() as isize;
+            density += if volume < 0.0 { -winding } else { winding };
         }
 
-        Some(volume / f64::u32(crate::factorial(rank - 1)))
+        Some(density)
     }
 
-    /// Computes the volume of a polytope by adding up the contributions of all
-    /// flags. Returns `None` if the volume is undefined.
-    fn volume_mut(&mut self) -> Option<f64> {
-        self.element_sort();
-        self.volume()
+    /// Computes the density of the polytope around its gravicenter. For a star
+    /// polytope, this is what's usually meant by its density.
+    ///
+    /// Returns `None` if the polytope isn't orientable, or if any of its
+    /// components isn't of full rank.
+    ///
+    /// # Panics
+    /// You must call [`Polytope::element_sort`] before calling this method.
+    fn central_density(&self) -> Option<isize> {
+        self.density(&self.gravicenter()?)
     }
 
     /// Projects the vertices of the polytope into the lowest dimension possible.
@@ -888,6 +1947,31 @@ pub trait ConcretePolytope: Polytope {
 
     /// Slices the polytope through a given plane.
     fn cross_section(&self, slice: &Hyperplane<f64>) -> Self;
+
+    /// Cuts the polytope with a hyperplane, keeping the closed part opposite
+    /// to its normal. The cross-section becomes a new facet.
+    fn cut_with_hyperplane(&self, hyperplane: &Hyperplane<f64>) -> Self;
+
+    /// Splits the polytope by a hyperplane into two closed pieces, with the
+    /// cross-section as a facet of each. The first piece lies opposite to the
+    /// normal of the hyperplane, and the second lies on its side.
+    fn split(&self, hyperplane: &Hyperplane<f64>) -> (Self, Self);
+
+    /// Returns the closed part of the polytope between two hyperplanes
+    /// parallel to a given one, each half the thickness away from it. Both
+    /// cross-sections become new facets.
+    fn slab(&self, hyperplane: &Hyperplane<f64>, thickness: f64) -> Self;
+
+    /// Truncates a single vertex of the polytope. The cut is perpendicular to
+    /// the line from the gravicenter to the vertex, at a given distance from
+    /// the vertex. Returns `None` if the vertex doesn't exist or lies on the
+    /// gravicenter.
+    fn truncate_vertex(&self, idx: usize, depth: f64) -> Option<Self> {
+        let vertex = self.vertices().get(idx)?;
+        let normal = (vertex - self.gravicenter()?).try_normalize(f64::eps())?;
+        let hyperplane = Hyperplane::new(normal.clone(), vertex.dot(&normal) - depth);
+        Some(self.cut_with_hyperplane(&hyperplane))
+    }
 }
 
 impl ConcretePolytope for Concrete {
@@ -938,47 +2022,13 @@ impl ConcretePolytope for Concrete {
     /// This method shouldn't panic. If it does, please file a bug.
     fn try_dual_mut_with(&mut self, sphere: &Hypersphere<f64>) -> Result<(), Self::DualError> {
         // If we're dealing with a nullitope, the dual is itself.
-        let rank = self.rank();
-        if rank == 0 {
+        if self.rank() == 0 {
             return Ok(());
         }
 
-        // We project the sphere's center onto the polytope's hyperplane to
-        // avoid skew weirdness.
-        let h = Subspace::from_points(self.vertices.iter());
-        let o = h.project(&sphere.center);
-
-        let mut projections;
-
-        // We project our inversion center onto each of the facets.
-        if rank >= 2 {
-            let facet_count = self.facet_count();
-            projections = Vec::with_capacity(facet_count);
-
-            (0..facet_count)
-                .into_par_iter()
-                .map(|idx| {
-                    Subspace::from_points(
-                        self.element_vertices_ref(rank - 1, idx)
-                            .unwrap()
-                            .into_iter(),
-                    )
-                    .project(&o)
-                })
-                .collect_into_vec(&mut projections);
-        } else {
-            projections = self.vertices.clone();
-        }
-
-        // Reciprocates the projected points.
-        for (idx, v) in projections.iter_mut().enumerate() {
-            if !sphere.reciprocate_mut(v) && rank != 1 {
-                return Err(DualError(idx));
-            }
-        }
-
-        self.vertices = projections;
+        self.vertices = self.dual_vertices(sphere, None)?.0;
         self.abs.dual_mut();
+        self.colors.clear_elements();
         Ok(())
     }
 
@@ -1039,6 +2089,20 @@ impl ConcretePolytope for Concrete {
         self_offset: &Point<f64>,
         other_offset: &Point<f64>,
         height: f64,
+    ) -> Self {
+        let half_height = height / 2.0;
+        self.duopyramid_with_heights(other, self_offset, other_offset, half_height, half_height)
+    }
+
+    /// Generates a duopyramid from two given polytopes, where each base is
+    /// placed at its own height and offset.
+    fn duopyramid_with_heights(
+        &self,
+        other: &Self,
+        self_offset: &Point<f64>,
+        other_offset: &Point<f64>,
+        self_height: f64,
+        other_height: f64,
     ) -> Self {
         Self::new(
             duopyramid_vertices(
@@ -1046,7 +2110,8 @@ impl ConcretePolytope for Concrete {
                 &other.vertices,
                 self_offset,
                 other_offset,
-                height,
+                self_height,
+                other_height,
                 false,
             ),
             Abstract::duopyramid(&self.abs, &other.abs),
@@ -1057,7 +2122,7 @@ impl ConcretePolytope for Concrete {
     /// from two polytopes.
     fn duotegum_with(p: &Self, q: &Self, p_offset: &Point<f64>, q_offset: &Point<f64>) -> Self {
         Self::new(
-            duopyramid_vertices(&p.vertices, &q.vertices, p_offset, q_offset, 0.0, true),
+            duopyramid_vertices(&p.vertices, &q.vertices, p_offset, q_offset, 0.0, 0.0, true),
             p.abs.duotegum(&q.abs),
         )
     }
@@ -1072,11 +2137,7 @@ impl ConcretePolytope for Concrete {
 
     /// Flattens the vertices of a polytope into a specified subspace.
     fn flatten_into(&mut self, subspace: &Subspace<f64>) {
-        if !subspace.is_full_rank() {
-            for v in &mut self.vertices {
-                *v = subspace.flatten(v);
-            }
-        }
+        self.project_onto(subspace, Coincidence::Preserve);
     }
 
     /// Takes the cross-section of a polytope through a given hyperplane.
@@ -1210,6 +2271,31 @@ impl ConcretePolytope for Concrete {
         }
     }
 
+    /// Cuts the polytope with a hyperplane, keeping the closed part opposite
+    /// to its normal. The cross-section becomes a new facet.
+    fn cut_with_hyperplane(&self, hyperplane: &Hyperplane<f64>) -> Self {
+        clip(self, hyperplane, true)
+    }
+
+    /// Splits the polytope by a hyperplane into two closed pieces, with the
+    /// cross-section as a facet of each. The first piece lies opposite to the
+    /// normal of the hyperplane, and the second lies on its side.
+    fn split(&self, hyperplane: &Hyperplane<f64>) -> (Self, Self) {
+        (clip(self, hyperplane, true), clip(self, hyperplane, false))
+    }
+
+    /// Returns the closed part of the polytope between two hyperplanes
+    /// parallel to a given one, each half the thickness away from it. Both
+    /// cross-sections become new facets.
+    fn slab(&self, hyperplane: &Hyperplane<f64>, thickness: f64) -> Self {
+        let normal = hyperplane.normal();
+        let pos = -hyperplane.distance(&Point::zeros(normal.len()));
+        let half = thickness / 2.0;
+
+        let below = clip(self, &Hyperplane::new(normal.clone(), pos + half), true);
+        clip(&below, &Hyperplane::new(-normal, half - pos), true)
+    }
+
     fn truncate_with(&self, truncate_type: Vec<usize>, depth: Vec<f64>) -> Self {
         let (abs, subflags) = self.abs().truncate_and_flags(truncate_type.clone());
         let element_vertices = self.avg_vertex_map();
@@ -1230,8 +2316,14 @@ impl ConcretePolytope for Concrete {
 
 #[cfg(test)]
 mod tests {
-    use super::{Concrete, ConcretePolytope};
-    use crate::{float::Float, Polytope};
+    use super::{Center, Concrete, ConcretePolytope, Scale, Volume};
+    use crate::{
+        abs::{Abstract, Ranked},
+        file::FromFile,
+        float::Float,
+        geometry::{Hyperplane, Hypersphere, Point},
+        test, Polytope,
+    };
 
     use approx::abs_diff_eq;
 
@@ -1239,14 +2331,14 @@ mod tests {
     fn test_volume(mut poly: Concrete, volume: Option<f64>) {
         poly.element_sort();
 
-        if let Some(poly_volume) = poly.volume() {
+        if let Some(poly_volume) = poly.volume().value() {
             let volume = volume.expect(&format!(
                 "Expected no volume for {}, found volume {}!",
                 "TBA: name", poly_volume
             ));
 
             assert!(
-                abs_diff_eq!(poly_volume, volume, epsilon = f64::EPS),
+                abs_diff_eq!(poly_volume, volume, epsilon = f64::eps()),
                 "Expected volume {} for {}, found volume {}.",
                 volume,
                 "TBA: name",
@@ -1294,6 +2386,53 @@ mod tests {
         test_compound(Concrete::hypercube(4), Some(2.0));
     }
 
+    #[test]
+    fn components() {
+        let square = Concrete::polygon(4);
+        let mut moved = square.clone();
+        for v in &mut moved.vertices {
+            v[0] += 3.0;
+        }
+
+        let mut compound = square.clone();
+        compound.comp_append(moved.clone());
+
+        let components = compound.components();
+        assert_eq!(components.len(), 2);
+        for (component, left) in components.iter().zip([true, false]) {
+            assert_eq!(component.vertices.len(), 4);
+            assert!(component.vertices.iter().all(|v| (v[0] < 2.0) == left));
+        }
+    }
+
+    #[test]
+    fn density() {
+        let mut cube = Concrete::hypercube(4);
+        cube.element_sort();
+        assert_eq!(cube.central_density(), Some(1));
+        assert_eq!(cube.density(&vec![2.0, 0.0, 0.0].into()), Some(0));
+
+        for (n, d) in [(5, 2), (7, 3), (8, 3)] {
+            let mut polygon = Concrete::star_polygon(n, d);
+            polygon.element_sort();
+            assert_eq!(polygon.central_density(), Some(d as isize));
+        }
+
+        let mut prism = Concrete::star_polygon(5, 2).prism();
+        prism.element_sort();
+        assert_eq!(prism.central_density(), Some(2));
+
+        // The hexagram is a compound of two triangles.
+        let mut hexagram = Concrete::polygon(3);
+        let mut rotated = hexagram.clone();
+        for v in &mut rotated.vertices {
+            *v = -v.clone();
+        }
+        hexagram.comp_append(rotated);
+        hexagram.element_sort();
+        assert_eq!(hexagram.central_density(), Some(2));
+    }
+
     #[test]
     fn polygon() {
         for n in 2..=10 {
@@ -1330,6 +2469,472 @@ mod tests {
         }
     }
 
+    #[test]
+    fn skew_duopyramid() {
+        let (polygons, areas) = polygons_areas();
+
+        // The volume only depends on the distance between both bases.
+        for m in 0..polygons.len() {
+            for n in 0..polygons.len() {
+                let p_offset = Point::from_vec(vec![0.3, -0.2]);
+                let q_offset = Point::from_vec(vec![-0.1, 0.4]);
+
+                test_volume(
+                    polygons[m].duopyramid_with_heights(
+                        &polygons[n],
+                        &p_offset,
+                        &q_offset,
+                        0.25,
+                        1.75,
+                    ),
+                    Some(areas[m] * areas[n] / 15.0),
+                )
+            }
+        }
+    }
+
+    #[test]
+    fn elements() {
+        let mut tesseract = Concrete::hypercube(5);
+        tesseract.element_sort();
+
+        // A single element is the same as that element.
+        let cube = tesseract.elements(4, &[0]).unwrap();
+        assert_eq!(
+            cube.el_count_iter().collect::<Vec<_>>(),
+            vec![1, 8, 12, 6, 1]
+        );
+        assert_eq!(cube.vertices.len(), 8);
+
+        // The 2-skeleton of the tesseract has all of its squares as facets.
+        let skeleton = tesseract.skeleton(3).unwrap();
+        assert_eq!(
+            skeleton.el_count_iter().collect::<Vec<_>>(),
+            vec![1, 16, 32, 24, 1]
+        );
+        assert_eq!(skeleton.vertices.len(), 16);
+
+        // Repeated indices are ignored, and missing ones give nothing.
+        assert_eq!(tesseract.elements(3, &[5, 5]).unwrap().vertices.len(), 4);
+        assert!(tesseract.elements(3, &[24]).is_none());
+        assert!(tesseract.elements(3, &[]).is_none());
+    }
+
+    #[test]
+    fn ditope_hosotope() {
+        // The ditope of a point is a degenerate dyad.
+        let dyad = Concrete::point().ditope();
+        dyad.assert_valid();
+        assert_eq!(dyad.vertices.len(), 2);
+
+        // The dihedron has the vertices of the base polygon.
+        let square = Concrete::star_polygon_with_edge(4, 1, 1.0);
+        let dihedron = square.ditope();
+        dihedron.assert_valid();
+        assert_eq!(dihedron.vertices, square.vertices);
+
+        // The vertices of the hosotope lie at the poles of the circumsphere.
+        let cube = Concrete::hypercube(4);
+        let hosotope = cube.hosotope();
+        hosotope.assert_valid();
+        assert_eq!(hosotope.dim(), Some(4));
+        let radius = cube.circumsphere().unwrap().radius();
+        assert!(abs_diff_eq!(
+            hosotope.vertices[0][3],
+            -radius,
+            epsilon = f64::eps()
+        ));
+        assert!(abs_diff_eq!(
+            hosotope.vertices[1][3],
+            radius,
+            epsilon = f64::eps()
+        ));
+
+        // The hosotope of a point is still a unit dyad.
+        test_volume(Concrete::point().hosotope(), Some(1.0));
+
+        // The hosotope of the nullitope is the nullitope.
+        assert!(Concrete::nullitope().hosotope().is_nullitope());
+
+        // Iterated ditopes and hosotopes stay valid, and the hosotope adds a
+        // dimension every time.
+        let (mut ditope, mut hosotope) = (Concrete::polygon(4), Concrete::polygon(4));
+        for _ in 0..3 {
+            ditope.ditope_mut();
+            hosotope.hosotope_mut();
+            ditope.assert_valid();
+            hosotope.assert_valid();
+        }
+        test(&ditope, [1, 4, 4, 2, 2, 2, 1]);
+        test(&hosotope, [1, 2, 2, 2, 4, 4, 1]);
+        assert_eq!(ditope.dim(), Some(2));
+        assert_eq!(hosotope.dim(), Some(5));
+    }
+
+    #[test]
+    fn compound_volume() {
+        let square = Concrete::star_polygon_with_edge(4, 1, 1.0);
+        let embed = |swap: bool| {
+            let mut square = square.clone();
+            for v in &mut square.vertices {
+                *v = if swap {
+                    vec![v[0], 0.0, v[1]]
+                } else {
+                    vec![v[0], v[1], 0.0]
+                }
+                .into();
+            }
+            square
+        };
+
+        // Two squares on different planes have their areas added up.
+        let mut compound = embed(false);
+        compound.comp_append(embed(true));
+        test_volume(compound, Some(2.0));
+
+        // A skew polygon has no area.
+        let mut skew = Concrete::new(
+            vec![
+                vec![1.0, 0.0, 0.0].into(),
+                vec![0.0, 1.0, 1.0].into(),
+                vec![-1.0, 0.0, 0.0].into(),
+                vec![0.0, -1.0, 1.0].into(),
+            ],
+            Abstract::polygon(4),
+        );
+        assert_eq!(skew.volume_mut(), Volume::Undefined);
+
+        // A polygon on a line is degenerate.
+        let mut line = Concrete::new(
+            (0..4).map(|x| vec![x as f64, 0.0].into()).collect(),
+            Abstract::polygon(4),
+        );
+        assert_eq!(line.volume_mut(), Volume::Degenerate);
+
+        // The tetrahemihexahedron is non-orientable.
+        let mut thah = Concrete::from_off(
+            "OFF
+            6 7 12
+            1 0 0
+            -1 0 0
+            0 1 0
+            0 -1 0
+            0 0 1
+            0 0 -1
+            3 0 2 4
+            3 0 3 5
+            3 1 2 5
+            3 1 3 4
+            4 0 2 1 3
+            4 2 4 3 5
+            4 0 4 1 5",
+        )
+        .unwrap();
+        assert_eq!(thah.volume_mut(), Volume::NonOrientable);
+        assert_eq!(Concrete::nullitope().volume(), Volume::Undefined);
+    }
+
+    #[test]
+    fn centroid() {
+        let mut cube = Concrete::hypercube(4);
+        cube.element_sort();
+        assert!(cube.centroid().unwrap().norm() < f64::eps());
+
+        // The centroid of a pyramid is a quarter of the way from the base to
+        // the apex.
+        let square = Concrete::star_polygon_with_edge(4, 1, 1.0);
+        let mut pyramid = square.pyramid_with(vec![0.0, 0.0, 1.0].into());
+        pyramid.element_sort();
+        let base_height = pyramid.vertices[0][2];
+        let centroid = pyramid.centroid().unwrap();
+        assert!(abs_diff_eq!(
+            centroid[2],
+            base_height + (1.0 - base_height) / 4.0,
+            epsilon = f64::eps()
+        ));
+
+        // Degenerate polytopes have no centroid.
+        let mut flat = square.pyramid_with(vec![0.0, 0.0, base_height].into());
+        flat.element_sort();
+        assert!(flat.centroid().is_none());
+    }
+
+    /// Recenters and rescales polytopes in different ways.
+    #[test]
+    fn normalize() {
+        // A big cube far from the origin.
+        let mut cube = Concrete::hypercube(4);
+        cube.scale(7.0);
+        cube.translate(&vec![1.0, 2.0, 3.0].into());
+        assert!(cube.normalize());
+        assert!(cube.gravicenter().unwrap().norm() < f64::eps());
+        assert!(abs_diff_eq!(
+            cube.circumsphere().unwrap().radius(),
+            1.0,
+            epsilon = f64::eps()
+        ));
+
+        let mut triangle = Concrete::star_polygon_with_edge(3, 1, 5.0);
+        assert!(triangle.normalize_with(Center::Circumcenter, Scale::EdgeLength));
+        assert!(triangle.is_equilateral_with(1.0));
+
+        // The centroid of a pyramid isn't its gravicenter.
+        let square = Concrete::star_polygon_with_edge(4, 1, 1.0);
+        let mut pyramid = square.pyramid_with(vec![0.0, 0.0, 1.0].into());
+        let base_height = pyramid.vertices[0][2];
+        let mut other = pyramid.clone();
+        assert!(pyramid.recenter_by(Center::Centroid));
+        assert!(other.recenter_by(Center::Gravicenter));
+        assert!(pyramid.centroid().unwrap().norm() < f64::eps());
+        assert!(other.gravicenter().unwrap().norm() < f64::eps());
+        assert!(pyramid.vertices[0][2] != other.vertices[0][2]);
+
+        // Flat polytopes fall back to the gravicenter.
+        let mut flat = square.pyramid_with(vec![0.0, 0.0, base_height].into());
+        assert!(flat.normalize());
+        assert!(!flat.recenter_by(Center::Centroid));
+
+        let mut point = Concrete::point();
+        assert!(!point.normalize());
+        assert!(!point.normalize_with(Center::Gravicenter, Scale::EdgeLength));
+    }
+
+    /// Checks that all edges of a polytope have unit length.
+    fn assert_unit_edges(poly: &Concrete) {
+        for idx in 0..poly.edge_count() {
+            let len = poly.edge_len(idx).unwrap();
+            assert!(
+                abs_diff_eq!(len, 1.0, epsilon = f64::eps()),
+                "Expected unit edges, found edge of length {}.",
+                len
+            );
+        }
+    }
+
+    #[test]
+    fn unit_duopyramid() {
+        let triangle = Concrete::star_polygon_with_edge(3, 1, 1.0);
+        let hexagon = Concrete::star_polygon_with_edge(6, 1, 1.0);
+
+        // The triangle-dyad duopyramid is the 5-cell.
+        let pen = triangle.duopyramid_unit(&Concrete::dyad()).unwrap();
+        assert_unit_edges(&pen);
+        test_volume(pen, Some(5f64.sqrt() / 96.0));
+
+        // Pyramids are duopyramids with a point.
+        let tet = triangle.duopyramid_unit(&Concrete::point()).unwrap();
+        assert_unit_edges(&tet);
+        test_volume(tet, Some(2f64.sqrt() / 12.0));
+
+        // Two hexagons are too large to be laced with unit edges.
+        assert!(hexagon.duopyramid_unit(&hexagon).is_none());
+    }
+
+    #[test]
+    fn unit_duotegum() {
+        let square = Concrete::star_polygon_with_edge(4, 1, 1.0);
+
+        // Two squares make up a 16-cell.
+        let hexadecachoron = Concrete::duotegum_unit(&square, &square).unwrap();
+        assert_unit_edges(&hexadecachoron);
+        test_volume(hexadecachoron, Some(1.0 / 6.0));
+
+        let triangle = Concrete::star_polygon_with_edge(3, 1, 1.0);
+        assert!(Concrete::duotegum_unit(&triangle, &triangle).is_none());
+    }
+
+    #[test]
+    fn twisted_antiprism() {
+        // Uniform antiprisms and retroprisms.
+        for &(n, d) in &[(3, 1), (5, 1), (5, 2), (5, 3), (7, 4), (4, 2)] {
+            assert_unit_edges(&Concrete::uniform_antiprism(n, d));
+        }
+
+        // Twisting the top base by a full turn gives back a uniform antiprism,
+        // while any other twist changes the edge lengths.
+        assert_unit_edges(&Concrete::twisted_antiprism(5, 1, f64::TAU));
+        let twisted = Concrete::twisted_antiprism(5, 1, 0.1);
+        assert!(!twisted.is_equilateral());
+
+        // On the plane, crossing the dual base is a half turn.
+        let square = Concrete::polygon(4);
+        let sphere = Hypersphere::with_squared_radius(Point::zeros(2), 0.5);
+        let crossed = square
+            .try_antiprism_with_twist(&sphere, 1.0, 0.0, true)
+            .unwrap();
+        let turned = square
+            .try_antiprism_with_twist(&sphere, 1.0, f64::PI, false)
+            .unwrap();
+
+        for v in &crossed.vertices {
+            assert!(turned.vertices.iter().any(|w| abs_diff_eq!(
+                (v - w).norm(),
+                0.0,
+                epsilon = f64::eps()
+            )));
+        }
+    }
+
+    /// Checks that antitegums are dualized about the center of their
+    /// antiprisms, even when the base is off-center.
+    #[test]
+    fn antitegum() {
+        test(&Abstract::polygon(5).antitegum(), [1, 12, 20, 10, 1]);
+
+        let mut square = Concrete::polygon(4);
+        let center: Point<f64> = vec![1.0, 2.0].into();
+        square.translate(&center);
+        let sphere = Hypersphere::with_squared_radius(center.clone(), 0.5);
+
+        let antitegum = square.try_antitegum_with(&sphere, 1.0).unwrap();
+        test(&antitegum, [1, 10, 16, 8, 1]);
+
+        // The two apices lie on the axis through the center of the square, at
+        // opposite heights.
+        let apices: Vec<_> = antitegum
+            .vertices
+            .iter()
+            .filter(|v| abs_diff_eq!((v.rows(0, 2) - &center).norm(), 0.0, epsilon = f64::eps()))
+            .collect();
+        assert_eq!(apices.len(), 2);
+        assert!(abs_diff_eq!(
+            apices[0][2] + apices[1][2],
+            0.0,
+            epsilon = f64::eps()
+        ));
+    }
+
+    /// Checks that facets through the reciprocation center have their dual
+    /// vertices sent to infinity.
+    #[test]
+    fn dual_with_infinity() {
+        let triangle = Concrete::polygon(3);
+        let edge = &triangle[(2, 0)].subs;
+        let (v0, v1) = (edge[0], edge[1]);
+        let center = (&triangle.vertices[v0] + &triangle.vertices[v1]) / 2.0;
+        let sphere = Hypersphere::with_radius(center.clone(), 1.0);
+
+        assert!(triangle.try_dual_with(&sphere).is_err());
+        let (dual, at_infinity) = triangle.dual_with_infinity(&sphere, 100.0).unwrap();
+        assert_eq!(at_infinity, vec![0]);
+        assert_eq!(dual.vertices.len(), 3);
+
+        // The vertex at infinity lies away from the opposite vertex.
+        let far = &dual.vertices[0] - &center;
+        let apex = (0..3).find(|&v| v != v0 && v != v1).unwrap();
+        assert!(abs_diff_eq!(far.norm(), 100.0, epsilon = f64::eps()));
+        assert!(far.dot(&(&triangle.vertices[apex] - &center)) < 0.0);
+    }
+
+    /// Checks the antipodal quotients of some centrally symmetric polytopes.
+    #[test]
+    fn antipodal_quotient() {
+        let hemicube = Concrete::cube().antipodal_quotient().unwrap();
+        test(&hemicube, [1, 4, 6, 3, 1]);
+
+        let hemioctahedron = Concrete::octahedron().antipodal_quotient().unwrap();
+        test(&hemioctahedron, [1, 3, 6, 4, 1]);
+
+        let hexagon = Concrete::polygon(6).antipodal_quotient().unwrap();
+        test(&hexagon, [1, 3, 3, 1]);
+
+        // Every vertex of the projective model lies in the same half.
+        for v in &hemicube.vertices {
+            assert!(v.iter().find(|x| x.abs() > f64::eps()).unwrap() > &0.0);
+        }
+
+        assert!(Concrete::polygon(5).antipodal_quotient().is_none());
+        assert!(Concrete::dyad().antipodal_quotient().is_none());
+        assert!(Concrete::point().antipodal_quotient().is_none());
+        assert!(Concrete::nullitope().antipodal_quotient().is_none());
+        assert!(Concrete::polygon(4)
+            .pyramid()
+            .antipodal_quotient()
+            .is_none());
+    }
+
+    #[test]
+    fn cut_with_hyperplane() {
+        let cube = Concrete::cube();
+        let half =
+            cube.cut_with_hyperplane(&Hyperplane::new(Point::from_vec(vec![0.0, 0.0, 1.0]), 0.0));
+        test(&half, [1, 8, 12, 6, 1]);
+        test_volume(half, Some(0.5));
+
+        let corner = cube.cut_with_hyperplane(&Hyperplane::new(
+            Point::from_vec(vec![1.0, 1.0, 1.0]) / 3f64.sqrt(),
+            -0.5,
+        ));
+        test(&corner, [1, 4, 6, 4, 1]);
+
+        // A hyperplane touching a single vertex leaves the cube as it is.
+        let diagonal = Point::from_vec(vec![1.0, 1.0, 1.0]) / 3f64.sqrt();
+        let touching =
+            cube.cut_with_hyperplane(&Hyperplane::new(diagonal.clone(), 3f64.sqrt() / 2.0));
+        test(&touching, [1, 8, 12, 6, 1]);
+
+        // A cut along the diagonals of three faces. Each vertex on the
+        // hyperplane is shared by every edge through it.
+        let faces = cube.cut_with_hyperplane(&Hyperplane::new(diagonal, 0.5 / 3f64.sqrt()));
+        test(&faces, [1, 7, 12, 7, 1]);
+        test_volume(faces, Some(5.0 / 6.0));
+    }
+
+    #[test]
+    fn split() {
+        let cube = Concrete::cube();
+        let (below, above) =
+            cube.split(&Hyperplane::new(Point::from_vec(vec![0.0, 0.0, 1.0]), 0.25));
+        test(&below, [1, 8, 12, 6, 1]);
+        test(&above, [1, 8, 12, 6, 1]);
+        test_volume(below, Some(0.75));
+        test_volume(above, Some(0.25));
+
+        // Vertices on the hyperplane belong to both pieces.
+        let (below, above) =
+            Concrete::polygon(4).split(&Hyperplane::new(Point::from_vec(vec![1.0, 0.0]), 0.0));
+        test(&below, [1, 3, 3, 1]);
+        test(&above, [1, 3, 3, 1]);
+
+        let diagonal = Point::from_vec(vec![1.0, 1.0, 0.0]) / 2f64.sqrt();
+        let (below, above) = cube.split(&Hyperplane::new(diagonal, 0.0));
+        test(&below, [1, 6, 9, 5, 1]);
+        test(&above, [1, 6, 9, 5, 1]);
+    }
+
+    #[test]
+    fn slab() {
+        let cube = Concrete::cube();
+        let z = Point::from_vec(vec![0.0, 0.0, 1.0]);
+
+        let slab = cube.slab(&Hyperplane::new(z.clone(), 0.1), 0.5);
+        test(&slab, [1, 8, 12, 6, 1]);
+        test_volume(slab, Some(0.5));
+
+        // A slab sticking out of the polytope is only cut once.
+        let slab = cube.slab(&Hyperplane::new(z.clone(), 0.5), 0.5);
+        test(&slab, [1, 8, 12, 6, 1]);
+        test_volume(slab, Some(0.25));
+
+        // A slab through the vertex-first section of a cube.
+        let diagonal = Point::from_vec(vec![1.0, 1.0, 1.0]) / 3f64.sqrt();
+        let slab = cube.slab(&Hyperplane::new(diagonal, 0.0), 0.2);
+        test(&slab, [1, 12, 18, 8, 1]);
+
+        assert!(cube.slab(&Hyperplane::new(z, 2.0), 0.5).is_nullitope());
+    }
+
+    #[test]
+    fn truncate_vertex() {
+        let cube = Concrete::cube().truncate_vertex(0, 0.1).unwrap();
+        test(&cube, [1, 10, 15, 7, 1]);
+
+        let pentagon = Concrete::polygon(5).truncate_vertex(2, 0.1).unwrap();
+        test(&pentagon, [1, 6, 6, 1]);
+
+        assert!(Concrete::polygon(5).truncate_vertex(5, 0.1).is_none());
+    }
+
     #[test]
     fn duoprism() {
         let (polygons, areas) = polygons_areas();
@@ -1344,6 +2949,65 @@ mod tests {
         }
     }
 
+    /// Checks that a polytope has the given vertices, in order.
+    fn assert_vertices(poly: &Concrete, vertices: &[[f64; 2]]) {
+        assert_eq!(poly.vertex_count(), vertices.len());
+        for (v, w) in poly.vertices().iter().zip(vertices) {
+            assert!(abs_diff_eq!(
+                v,
+                &Point::from_row_slice(w),
+                epsilon = f64::eps()
+            ));
+        }
+    }
+
+    #[test]
+    fn transforms() {
+        let mut square = Concrete::star_polygon(4, 1);
+        square.rotate_about(&Point::zeros(2), 0, 1, f64::PI / 4.0);
+        let h = f64::SQRT_2 / 2.0;
+        let mut vertices = vec![[-h, h], [h, h], [h, -h], [-h, -h]];
+        assert_vertices(&square, &vertices);
+
+        // Translating moves every vertex.
+        square.translate(&Point::from_row_slice(&[1.0, 0.0]));
+        for v in vertices.iter_mut() {
+            v[0] += 1.0;
+        }
+        assert_vertices(&square, &vertices);
+
+        // Rotating about the new center is the same as rotating back.
+        let center = Point::from_row_slice(&[1.0, 0.0]);
+        square.rotate_about(&center, 0, 1, -f64::PI / 4.0);
+        assert_vertices(&square, &[[1.0, 1.0], [2.0, 0.0], [1.0, -1.0], [0.0, 0.0]]);
+
+        // Reflecting through the line x = 1 swaps the left and right vertices.
+        square.reflect_through(&Hyperplane::new(Point::from_row_slice(&[1.0, 0.0]), 1.0));
+        assert_vertices(&square, &[[1.0, 1.0], [0.0, 0.0], [1.0, -1.0], [2.0, 0.0]]);
+    }
+
+    #[test]
+    fn duoprism_unit() {
+        // A pentagon with the wrong size and position, and a square.
+        let mut pentagon = Concrete::star_polygon_with_edge(5, 1, 3.0);
+        for v in pentagon.vertices_mut() {
+            v[0] += 2.0;
+        }
+        let square = Concrete::star_polygon_with_edge(4, 1, 1.0);
+
+        let duoprism = pentagon.duoprism_unit(&square).unwrap();
+        assert_unit_edges(&duoprism);
+        assert!(abs_diff_eq!(
+            duoprism.circumsphere().unwrap().center.norm(),
+            0.0,
+            epsilon = f64::eps()
+        ));
+
+        // Non-equilateral factors can't be rescaled.
+        let rectangle = Concrete::duoprism(&Concrete::dyad_with(1.0), &Concrete::dyad_with(2.0));
+        assert!(rectangle.duoprism_unit(&square).is_none());
+    }
+
     #[test]
     fn duotegum() {
         let (polygons, areas) = polygons_areas();
@@ -1362,16 +3026,39 @@ mod tests {
     fn duocomb() {
         let (polygons, _) = polygons_areas();
 
+        // The digon and the compound of two digons give degenerate duocombs,
+        // and their compounds.
+        let degenerate = |idx| idx == 0 || idx == 3;
+
         for m in 0..polygons.len() {
             for n in 0..polygons.len() {
                 test_volume(
                     Concrete::duocomb(&polygons[m], &polygons[n]),
-                    (m == 0 || n == 0).then(|| 0.0),
+                    (degenerate(m) || degenerate(n)).then_some(0.0),
                 )
             }
         }
     }
 
+    #[test]
+    fn clifford_duocomb() {
+        let duocomb = Concrete::clifford_duocomb(5, 1, 4, 1);
+        assert_eq!(
+            duocomb.el_count_iter().collect::<Vec<_>>(),
+            vec![1, 20, 40, 20, 1]
+        );
+
+        // Every vertex lies on the Clifford torus.
+        for v in duocomb.vertices() {
+            assert!(abs_diff_eq!(v.norm(), 1.0, epsilon = f64::eps()));
+            assert!(abs_diff_eq!(
+                v.rows(0, 2).norm(),
+                f64::SQRT_2 / 2.0,
+                epsilon = f64::eps()
+            ));
+        }
+    }
+
     #[test]
     fn simplex() {
         for n in 1..=6 {
@@ -0,0 +1,372 @@
+//! Records the operations applied to a polytope as a pipeline, which can then
+//! be replayed on any other polytope.
+//!
+//! Each step of a pipeline is a line of the [expression language](super::script)
+//! in which the variable `p` stands for the polytope built so far, as in
+//! `dual(p)` or `duoprism(p, polygon(5))`. A step can also be an assignment
+//! like `q = polygon(5)`, which stores a value for later steps without changing
+//! the polytope.
+//!
+//! As text, a pipeline is written with one step per line. Empty lines and lines
+//! starting with `#` are ignored.
+//!
+//! A pipeline can also be applied to every OFF file in a folder at once, with
+//! [`Pipeline::apply_to_folder`].
+
+use std::{
+    ffi::OsStr,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use super::{
+    script::{self, Interpreter, ScriptError, ScriptResult, Value},
+    Concrete,
+};
+use crate::file::FromFile;
+
+use serde::{Deserialize, Serialize};
+
+/// The variable that stands for the polytope built so far.
+pub const VARIABLE: &str = "p";
+
+/// Represents an error while reading or applying a pipeline.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PipelineError {
+    /// A step couldn't be parsed or run.
+    Script {
+        /// The index of the step.
+        step: usize,
+
+        /// The error in the step.
+        err: ScriptError,
+    },
+
+    /// A step evaluated to something other than a polytope.
+    NotPolytope(usize),
+}
+
+impl Display for PipelineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Script { step, err } => write!(f, "step {}: {}", step + 1, err),
+            Self::NotPolytope(step) => {
+                write!(f, "step {} doesn't evaluate to a polytope", step + 1)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PipelineError {}
+
+/// The outcome of applying a pipeline to every file in a folder.
+#[derive(Clone, Debug, Default)]
+pub struct BatchSummary {
+    /// The files that were written.
+    pub written: Vec<PathBuf>,
+
+    /// The files that couldn't be processed, along with the reason why.
+    pub failed: Vec<(PathBuf, String)>,
+
+    /// Whether the batch was stopped before going through every file.
+    pub stopped: bool,
+}
+
+impl Display for BatchSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} files written, {} failed",
+            self.written.len(),
+            self.failed.len()
+        )?;
+
+        if self.stopped {
+            write!(f, " (stopped early)")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A sequence of operations that can be applied to a polytope.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Pipeline {
+    /// The steps of the pipeline, in the order they're applied.
+    steps: Vec<String>,
+}
+
+impl Pipeline {
+    /// Initializes a pipeline with no steps.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Returns the steps of the pipeline.
+    pub fn steps(&self) -> &[String] {
+        &self.steps
+    }
+
+    /// Returns the number of steps in the pipeline.
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Returns whether the pipeline has no steps.
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Adds a step to the end of the pipeline, after checking that it parses.
+    pub fn push<T: ToString>(&mut self, step: T) -> ScriptResult<()> {
+        let step = step.to_string();
+        script::parse(&step)?;
+        self.steps.push(step);
+        Ok(())
+    }
+
+    /// Removes the last step of the pipeline, and returns it.
+    pub fn pop(&mut self) -> Option<String> {
+        self.steps.pop()
+    }
+
+    /// Removes every step of the pipeline.
+    pub fn clear(&mut self) {
+        self.steps.clear();
+    }
+
+    /// Applies every step of the pipeline to a polytope in order, and returns
+    /// the result.
+    pub fn apply(&self, base: Concrete) -> Result<Concrete, PipelineError> {
+        let mut interpreter = Interpreter::new();
+        interpreter.set(VARIABLE, Value::Polytope(base));
+
+        for (idx, step) in self.steps.iter().enumerate() {
+            match interpreter.run(step) {
+                Ok(Some(Value::Polytope(p))) => interpreter.set(VARIABLE, Value::Polytope(p)),
+                Ok(Some(_)) => return Err(PipelineError::NotPolytope(idx)),
+                Ok(None) => {}
+                Err(err) => return Err(PipelineError::Script { step: idx, err }),
+            }
+        }
+
+        match interpreter.get(VARIABLE) {
+            Some(Value::Polytope(p)) => Ok(p.clone()),
+            _ => Err(PipelineError::NotPolytope(self.len().saturating_sub(1))),
+        }
+    }
+
+    /// Returns the path where the result of applying the pipeline to a file is
+    /// written: a file next to it, with the suffix in parentheses after its
+    /// name, as in `ike (dual).off`.
+    pub fn output_path<P: AsRef<Path>>(path: P, suffix: &str) -> PathBuf {
+        let path = path.as_ref();
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        path.with_file_name(format!("{} ({}).off", stem, suffix))
+    }
+
+    /// Applies the pipeline to every OFF file in a folder, and writes each
+    /// result next to the original, as given by [`Self::output_path`].
+    /// Subfolders aren't visited.
+    ///
+    /// Before each file, `progress` is called with the number of files done
+    /// and the total number of files. The batch stops early if it returns
+    /// `false`. Files that can't be read, transformed, or written are skipped
+    /// and listed in the summary.
+    pub fn apply_to_folder<P: AsRef<Path>, F: FnMut(usize, usize) -> bool>(
+        &self,
+        folder: P,
+        suffix: &str,
+        mut progress: F,
+    ) -> io::Result<BatchSummary> {
+        // We list the files before writing anything, so that the results
+        // aren't processed again.
+        let mut files = Vec::new();
+        for entry in fs::read_dir(folder)? {
+            let path = entry?.path();
+            if path.is_file() && path.extension() == Some(OsStr::new("off")) {
+                files.push(path);
+            }
+        }
+        files.sort();
+
+        let mut summary = BatchSummary::default();
+        for (idx, path) in files.iter().enumerate() {
+            if !progress(idx, files.len()) {
+                summary.stopped = true;
+                break;
+            }
+
+            let res = Concrete::from_path(path)
+                .map_err(|err| format!("couldn't load: {}", err))
+                .and_then(|p| self.apply(p).map_err(|err| err.to_string()))
+                .and_then(|p| {
+                    let out = Self::output_path(path, suffix);
+                    p.to_path(&out, Default::default())
+                        .map(|_| out)
+                        .map_err(|err| format!("couldn't save: {}", err))
+                });
+
+            match res {
+                Ok(out) => summary.written.push(out),
+                Err(err) => summary.failed.push((path.clone(), err)),
+            }
+        }
+
+        progress(files.len(), files.len());
+        Ok(summary)
+    }
+}
+
+impl Display for Pipeline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for step in &self.steps {
+            writeln!(f, "{}", step)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Pipeline {
+    type Err = PipelineError;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        let mut pipeline = Self::new();
+
+        for line in src.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let step = pipeline.len();
+            pipeline
+                .push(line)
+                .map_err(|err| PipelineError::Script { step, err })?;
+        }
+
+        Ok(pipeline)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{abs::Ranked, Polytope};
+
+    /// Builds the pipeline used in the tests.
+    fn pipeline() -> Pipeline {
+        let mut pipeline = Pipeline::new();
+        pipeline.push("q = polygon(5)").unwrap();
+        pipeline.push("prism(p)").unwrap();
+        pipeline.push("duoprism(p, q)").unwrap();
+        pipeline.push("dual(p)").unwrap();
+        pipeline
+    }
+
+    /// Replays the same pipeline on different polytopes.
+    #[test]
+    fn apply() {
+        let pipeline = pipeline();
+        assert_eq!(pipeline.len(), 4);
+
+        for base in [Concrete::polygon(4), Concrete::simplex(4)] {
+            let expected = base
+                .prism()
+                .duoprism(&Concrete::polygon(5))
+                .try_dual()
+                .unwrap();
+            let p = pipeline.apply(base).unwrap();
+
+            p.assert_valid();
+            assert_eq!(
+                p.el_count_iter().collect::<Vec<_>>(),
+                expected.el_count_iter().collect::<Vec<_>>()
+            );
+        }
+
+        assert_eq!(Pipeline::new().apply(Concrete::point()).unwrap().rank(), 1);
+    }
+
+    /// Checks that a pipeline is written and read back as text.
+    #[test]
+    fn text() {
+        let pipeline = pipeline();
+        let src = format!("# A comment.\n\n{}", pipeline);
+        assert_eq!(src.parse::<Pipeline>(), Ok(pipeline));
+
+        assert!(matches!(
+            "dual(p)\nprism(p".parse::<Pipeline>(),
+            Err(PipelineError::Script { step: 1, .. })
+        ));
+    }
+
+    /// Applies a pipeline to a folder with a valid and an invalid file.
+    #[test]
+    fn apply_to_folder() {
+        let folder = std::env::temp_dir().join("miratope-pipeline-test");
+        let _ = fs::remove_dir_all(&folder);
+        fs::create_dir_all(&folder).unwrap();
+
+        let square = Concrete::polygon(4).to_off(Default::default()).unwrap();
+        fs::write(folder.join("square.off"), square).unwrap();
+        fs::write(folder.join("broken.off"), "not an OFF file").unwrap();
+        fs::write(folder.join("notes.txt"), "ignored").unwrap();
+
+        let mut pipeline = Pipeline::new();
+        pipeline.push("prism(p)").unwrap();
+
+        let mut calls = Vec::new();
+        let summary = pipeline
+            .apply_to_folder(&folder, "prism", |done, total| {
+                calls.push((done, total));
+                true
+            })
+            .unwrap();
+
+        assert_eq!(summary.written, vec![folder.join("square (prism).off")]);
+        assert_eq!(summary.failed.len(), 1);
+        assert_eq!(summary.failed[0].0, folder.join("broken.off"));
+        assert!(!summary.stopped);
+        assert_eq!(calls, vec![(0, 2), (1, 2), (2, 2)]);
+
+        let cube = Concrete::from_path(&summary.written[0]).unwrap();
+        crate::test(&cube, [1, 8, 12, 6, 1]);
+
+        // Stops right away.
+        let summary = pipeline
+            .apply_to_folder(&folder, "prism", |_, _| false)
+            .unwrap();
+        assert!(summary.stopped);
+        assert!(summary.written.is_empty());
+
+        fs::remove_dir_all(&folder).unwrap();
+    }
+
+    /// Checks the errors while applying a pipeline.
+    #[test]
+    fn errors() {
+        let mut pipeline = Pipeline::new();
+        assert!(pipeline.push("prism(p").is_err());
+        assert!(pipeline.is_empty());
+
+        pipeline.push("prism(p)").unwrap();
+        pipeline.push("circumradius(p)").unwrap();
+        assert_eq!(
+            pipeline.apply(Concrete::polygon(4)).unwrap_err(),
+            PipelineError::NotPolytope(1)
+        );
+
+        pipeline.pop();
+        pipeline.push("frobnicate(p)").unwrap();
+        assert_eq!(
+            pipeline.apply(Concrete::polygon(4)).unwrap_err(),
+            PipelineError::Script {
+                step: 1,
+                err: ScriptError::UnknownFunction("frobnicate".to_string())
+            }
+        );
+    }
+}
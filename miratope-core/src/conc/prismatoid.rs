@@ -0,0 +1,375 @@
+//! Builds prismatoids, the polyhedra whose vertices lie on two parallel
+//! planes, together with the cupolae and rotundae.
+
+use super::{cycle::CycleList, Concrete, ConcretePolytope};
+use crate::{
+    abs::{
+        ranked::{AbstractBuilder, SubelementList},
+        Abstract, Ranked,
+    },
+    float::Float,
+    geometry::Point,
+};
+
+use gcd::Gcd;
+use vec_like::*;
+
+/// The number of samples used to look for the radius of the middle vertices
+/// of a rotunda, before refining it by bisection.
+const ROTUNDA_SAMPLES: usize = 1000;
+
+/// The number of bisection steps used to refine the radius of the middle
+/// vertices of a rotunda.
+const ROTUNDA_STEPS: usize = 100;
+
+/// Returns the point on the plane at height `z` with a given distance to the
+/// axis and angle around it.
+fn cylindrical(radius: f64, angle: f64, z: f64) -> Point<f64> {
+    let (sin, cos) = angle.fsin_cos();
+    vec![radius * cos, radius * sin, z].into()
+}
+
+/// Returns the vertices of a planar polygon in counterclockwise order, or
+/// `None` if it isn't a single convex cycle.
+fn convex_cycle(polygon: &Concrete) -> Option<Vec<Point<f64>>> {
+    if polygon.rank() != 3 || polygon.dim() != Some(2) {
+        return None;
+    }
+
+    let cycles = CycleList::from_edges(polygon.abs[2].iter().map(|edge| &edge.subs));
+    if cycles.len() != 1 {
+        return None;
+    }
+
+    let mut cycle: Vec<_> = cycles[0]
+        .iter()
+        .map(|&v| polygon.vertices[v].clone())
+        .collect();
+    let n = cycle.len();
+    let cross = |i: usize| {
+        let (u, v, w) = (&cycle[i], &cycle[(i + 1) % n], &cycle[(i + 2) % n]);
+        (v[0] - u[0]) * (w[1] - v[1]) - (v[1] - u[1]) * (w[0] - v[0])
+    };
+
+    // All turns must go in the same direction, and they must add up to a
+    // single turn.
+    let turns: Vec<_> = (0..n).map(cross).collect();
+    if turns.iter().all(|&t| t < -f64::eps()) {
+        cycle.reverse();
+    } else if !turns.iter().all(|&t| t > f64::eps()) {
+        return None;
+    }
+
+    let winding: f64 = (0..n)
+        .map(|i| {
+            let (u, v) = (&cycle[i], &cycle[(i + 1) % n]);
+            (u[0] * v[1] - u[1] * v[0]).atan2(u.dot(v))
+        })
+        .sum();
+    if (winding.abs() - f64::TAU).abs() > f64::eps() {
+        return None;
+    }
+
+    Some(cycle)
+}
+
+/// Returns the angle of the direction of every edge of a counterclockwise
+/// cycle of points, starting from the edge with the smallest angle, so that
+/// they're increasing. Also returns the index of that edge.
+fn edge_angles(cycle: &[Point<f64>]) -> (Vec<f64>, usize) {
+    let n = cycle.len();
+    let angles: Vec<_> = (0..n)
+        .map(|i| {
+            let (u, v) = (&cycle[i], &cycle[(i + 1) % n]);
+            (v[1] - u[1]).atan2(v[0] - u[0]).rem_euclid(f64::TAU)
+        })
+        .collect();
+
+    let start = (0..n)
+        .min_by(|&i, &j| angles[i].partial_cmp(&angles[j]).unwrap())
+        .unwrap_or(0);
+    ((0..n).map(|i| angles[(start + i) % n]).collect(), start)
+}
+
+impl Concrete {
+    /// Builds the prismatoid with two convex polygons as its bases, the top
+    /// one placed at a given height over the bottom one. The lateral faces
+    /// are the triangles and quadrilaterals that join them into a convex
+    /// polyhedron.
+    ///
+    /// Returns `None` if either base isn't a convex polygon on the plane.
+    pub fn prismatoid(bottom: &Self, top: &Self, height: f64) -> Option<Self> {
+        let bottom = convex_cycle(bottom)?;
+        let top = convex_cycle(top)?;
+        let (n, m) = (bottom.len(), top.len());
+
+        // We go around both bases at once, in the order of the directions of
+        // their edges. Every step joins the current vertices by an edge, and
+        // every two consecutive edges bound a lateral face.
+        let (bottom_angles, bottom_start) = edge_angles(&bottom);
+        let (top_angles, top_start) = edge_angles(&top);
+        let (mut i, mut j) = (0, 0);
+        let mut lacing = Vec::with_capacity(n + m);
+        let mut steps = Vec::with_capacity(n + m);
+
+        while i < n || j < m {
+            lacing.push(((bottom_start + i) % n, (top_start + j) % m));
+
+            let step = if j == m {
+                (true, false)
+            } else if i == n {
+                (false, true)
+            } else if (bottom_angles[i] - top_angles[j]).abs() < f64::eps() {
+                (true, true)
+            } else {
+                (
+                    bottom_angles[i] < top_angles[j],
+                    top_angles[j] < bottom_angles[i],
+                )
+            };
+
+            steps.push(step);
+            i += step.0 as usize;
+            j += step.1 as usize;
+        }
+
+        let lateral = lacing.len();
+        let mut edges = SubelementList::with_capacity(n + m + lateral);
+        for k in 0..n {
+            edges.push(vec![k, (k + 1) % n].into());
+        }
+        for k in 0..m {
+            edges.push(vec![n + k, n + (k + 1) % m].into());
+        }
+        for &(v, w) in &lacing {
+            edges.push(vec![v, n + w].into());
+        }
+
+        let mut faces = SubelementList::with_capacity(lateral + 2);
+        faces.push((0..n).collect());
+        faces.push((n..n + m).collect());
+        for (k, (&(v, w), &(step_bottom, step_top))) in lacing.iter().zip(&steps).enumerate() {
+            let mut face = vec![n + m + k, n + m + (k + 1) % lateral];
+            if step_bottom {
+                face.push(v);
+            }
+            if step_top {
+                face.push(n + w);
+            }
+
+            faces.push(face.into());
+        }
+
+        let mut builder = AbstractBuilder::with_rank_capacity(4);
+        builder.push_min();
+        builder.push_vertices(n + m);
+        builder.push(edges);
+        builder.push(faces);
+        builder.push_max();
+
+        let vertices = bottom
+            .iter()
+            .map(|v| vec![v[0], v[1], 0.0].into())
+            .chain(top.iter().map(|v| vec![v[0], v[1], height].into()))
+            .collect();
+
+        // Safety: the lateral faces close up the two bases into a polyhedron.
+        Some(Self::new(vertices, unsafe { builder.build() }))
+    }
+
+    /// Builds the cupola whose top face is an `{n / d}` polygon with unit
+    /// edge length, and whose bottom face is a `{2n / d}` polygon. Its height
+    /// is chosen so that all of its edges have unit length.
+    ///
+    /// Returns `None` if `d` is even, if `n` and `d` aren't coprime, or if
+    /// there's no such cupola with unit edges.
+    pub fn cupola(n: usize, d: usize) -> Option<Self> {
+        if n < 3 || d == 0 || d >= n || d % 2 == 0 || n.gcd(d) != 1 {
+            return None;
+        }
+
+        // The angle between consecutive bottom vertices.
+        let step = f64::PI * d as f64 / n as f64;
+        let bottom_radius = 0.5 / (step / 2.0).fsin();
+        let top_radius = 0.5 / step.fsin();
+
+        let offset = top_radius * top_radius + bottom_radius * bottom_radius
+            - 2.0 * top_radius * bottom_radius * (step / 2.0).fcos();
+        let squared_height = 1.0 - offset;
+        if squared_height < -f64::eps() {
+            return None;
+        }
+        let height = squared_height.max(0.0).fsqrt();
+
+        // The top vertex j lies in between the bottom vertices 2j - 1 and 2j.
+        let vertices = (0..2 * n)
+            .map(|i| cylindrical(bottom_radius, i as f64 * step, 0.0))
+            .chain(
+                (0..n).map(|j| cylindrical(top_radius, (2 * j) as f64 * step - step / 2.0, height)),
+            )
+            .collect();
+
+        Some(Self::new(vertices, Abstract::cupola(n)))
+    }
+
+    /// Builds the rotunda whose top face is an `{n / d}` polygon with unit
+    /// edge length, and whose bottom face is a `{2n / d}` polygon. The middle
+    /// vertices and the top face are placed so that all of its edges have unit
+    /// length and all of its pentagons are planar. The pentagonal rotunda is
+    /// half of an icosidodecahedron.
+    ///
+    /// Returns `None` if `d` is even, if `n` and `d` aren't coprime, or if
+    /// there's no such rotunda with unit edges.
+    pub fn rotunda(n: usize, d: usize) -> Option<Self> {
+        if n < 3 || d == 0 || d >= n || d % 2 == 0 || n.gcd(d) != 1 {
+            return None;
+        }
+
+        // The angle between consecutive bottom vertices.
+        let step = f64::PI * d as f64 / n as f64;
+        let bottom_radius = 0.5 / (step / 2.0).fsin();
+        let top_radius = 0.5 / step.fsin();
+        let apothem = bottom_radius * (step / 2.0).fcos();
+
+        // Given the distance of the middle vertices to the axis, returns the
+        // heights of the middle vertices and of the top face that make the
+        // edges unit, and how far the pentagons are from being planar.
+        //
+        // Every pentagon is symmetric under a plane through the axis, so it's
+        // planar exactly when the midpoint of its bottom edge, the midpoint of
+        // its middle vertices, and its top vertex are aligned in that plane.
+        let heights = |radius: f64| -> Option<(f64, f64, f64)> {
+            let bottom_offset = radius * radius + bottom_radius * bottom_radius
+                - 2.0 * radius * bottom_radius * (step / 2.0).fcos();
+            let top_offset =
+                radius * radius + top_radius * top_radius - 2.0 * radius * top_radius * step.fcos();
+            if bottom_offset > 1.0 || top_offset > 1.0 {
+                return None;
+            }
+
+            let middle = (1.0 - bottom_offset).fsqrt();
+            let top = middle + (1.0 - top_offset).fsqrt();
+            let error = (radius * step.fcos() - apothem) * top - middle * (top_radius - apothem);
+            Some((middle, top, error))
+        };
+
+        // Looks for a sign change of the error, and then bisects it.
+        let max_radius = bottom_radius + 1.0;
+        let sample = |k: usize| max_radius * k as f64 / ROTUNDA_SAMPLES as f64;
+        let (mut lo, mut hi) = (1..=ROTUNDA_SAMPLES).find_map(|k| {
+            let (_, _, e0) = heights(sample(k - 1))?;
+            let (_, _, e1) = heights(sample(k))?;
+            if e0 * e1 <= 0.0 {
+                Some((sample(k - 1), sample(k)))
+            } else {
+                None
+            }
+        })?;
+
+        let lo_error = heights(lo)?.2;
+        for _ in 0..ROTUNDA_STEPS {
+            let mid = (lo + hi) / 2.0;
+            if heights(mid)?.2 * lo_error <= 0.0 {
+                hi = mid;
+            } else {
+                lo = mid;
+            }
+        }
+
+        let radius = (lo + hi) / 2.0;
+        let (middle, top, _) = heights(radius)?;
+
+        // The top vertex j lies over the bottom edge 2j, and the middle vertex
+        // j lies over the bottom edge 2j + 1.
+        let vertices = (0..2 * n)
+            .map(|i| cylindrical(bottom_radius, i as f64 * step, 0.0))
+            .chain((0..n).map(|j| cylindrical(top_radius, (2 * j) as f64 * step + step / 2.0, top)))
+            .chain(
+                (0..n).map(|j| cylindrical(radius, (2 * j + 1) as f64 * step + step / 2.0, middle)),
+            )
+            .collect();
+
+        Some(Self::new(vertices, Abstract::rotunda(n)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{geometry::Subspace, Polytope};
+
+    /// Returns the element counts of a polytope.
+    fn counts(poly: &Concrete) -> Vec<usize> {
+        poly.el_count_iter().collect()
+    }
+
+    /// Checks that all faces of a polyhedron are planar.
+    fn is_planar(poly: &Concrete) -> bool {
+        (0..poly.el_count(3)).all(|idx| {
+            let vertices = poly.element_vertices_ref(3, idx).unwrap();
+            Subspace::from_points(vertices.into_iter()).rank() == 2
+        })
+    }
+
+    #[test]
+    fn cupola() {
+        for &(n, d) in &[(3, 1), (4, 1), (5, 1), (5, 3)] {
+            let cupola = Concrete::cupola(n, d).unwrap();
+            cupola.assert_valid();
+            assert_eq!(counts(&cupola), vec![1, 3 * n, 5 * n, 2 * n + 2, 1]);
+            assert!(cupola.is_equilateral_with(1.0));
+            assert!(is_planar(&cupola));
+        }
+
+        // The triangular cupola is half of a cuboctahedron.
+        let height = Concrete::cupola(3, 1).unwrap().vertices[6][2];
+        assert!((height - (2.0f64 / 3.0).sqrt()).abs() < f64::eps());
+
+        assert!(Concrete::cupola(7, 1).is_none());
+        assert!(Concrete::cupola(5, 2).is_none());
+    }
+
+    #[test]
+    fn rotunda() {
+        let rotunda = Concrete::rotunda(5, 1).unwrap();
+        rotunda.assert_valid();
+        assert_eq!(counts(&rotunda), vec![1, 20, 35, 17, 1]);
+        assert!(rotunda.is_equilateral_with(1.0));
+        assert!(is_planar(&rotunda));
+
+        // The pentagonal rotunda is half of an icosidodecahedron.
+        let height = rotunda.vertices[10][2];
+        assert!((height - (1.0 + 2.0 / 5f64.sqrt()).sqrt()).abs() < f64::eps());
+    }
+
+    #[test]
+    fn prismatoid() {
+        // A square over a larger square gives a frustum.
+        let square = Concrete::polygon(4);
+        let mut small = square.clone();
+        small.scale(0.5);
+        let frustum = Concrete::prismatoid(&square, &small, 1.0).unwrap();
+        frustum.assert_valid();
+        assert_eq!(counts(&frustum), vec![1, 8, 12, 6, 1]);
+
+        // A square over an octagon whose edges aren't parallel to it.
+        let octagon = Concrete::polygon(8);
+        let prismatoid = Concrete::prismatoid(&octagon, &small, 1.0).unwrap();
+        prismatoid.assert_valid();
+        assert_eq!(counts(&prismatoid), vec![1, 12, 24, 14, 1]);
+
+        // Two identical bases give a prism.
+        let triangle = Concrete::polygon(3);
+        let prism = Concrete::prismatoid(&triangle, &triangle, 1.0).unwrap();
+        prism.assert_valid();
+        assert_eq!(counts(&prism), vec![1, 6, 9, 5, 1]);
+
+        // A triangle over an opposite triangle gives an octahedron.
+        let mut opposite = triangle.clone();
+        opposite.scale(-1.0);
+        let octahedron = Concrete::prismatoid(&triangle, &opposite, 1.0).unwrap();
+        assert_eq!(counts(&octahedron), vec![1, 6, 12, 8, 1]);
+
+        assert!(Concrete::prismatoid(&Concrete::star_polygon(5, 2), &triangle, 1.0).is_none());
+    }
+}
@@ -0,0 +1,610 @@
+//! Recognizes some well-known polytopes from their structure, so that they can
+//! be given proper names.
+
+use std::fmt::Display;
+
+use super::{cycle::CycleList, Concrete, ConcretePolytope};
+use crate::{
+    abs::{flag::OrientedFlagIter, Ranked},
+    float::Float,
+    prefix, Polytope,
+};
+
+use vec_like::*;
+
+/// The largest product of the number of flags and the number of vertices of a
+/// polytope that [`Concrete::recognize`] looks at. Finding the symmetry group
+/// takes about this many steps, and recognition runs whenever a file is
+/// loaded, so larger polytopes would stall the application.
+pub const MAX_RECOGNITION_COST: usize = 1 << 24;
+
+/// The [Schläfli symbol](https://polytope.miraheze.org/wiki/Schläfli_symbol)
+/// of a regular polytope. Every entry `(n, d)` stands for the star polygon
+/// `{n/d}`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SchlafliSymbol(pub Vec<(usize, usize)>);
+
+impl Display for SchlafliSymbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{{")?;
+
+        for (i, &(n, d)) in self.0.iter().enumerate() {
+            if i != 0 {
+                write!(f, ",")?;
+            }
+
+            if d == 1 {
+                write!(f, "{}", n)?;
+            } else {
+                write!(f, "{}/{}", n, d)?;
+            }
+        }
+
+        write!(f, "}}")
+    }
+}
+
+impl SchlafliSymbol {
+    /// Returns the entry of [`NAMED_REGULARS`] for this Schläfli symbol, if
+    /// there's one.
+    fn named_regular(&self) -> Option<&'static NamedRegular> {
+        NAMED_REGULARS
+            .iter()
+            .find(|named| named.symbol == self.0.as_slice())
+    }
+
+    /// Returns the number of dimensions of the polytope, along with the family
+    /// it belongs to, if it belongs to any.
+    fn family(&self) -> Option<(usize, Family)> {
+        let entries = &self.0;
+        let dim = entries.len() + 1;
+        let threes = |entries: &[(usize, usize)]| entries.iter().all(|&e| e == (3, 1));
+
+        match entries[..] {
+            [(3, 1), ..] if threes(entries) => Some((dim, Family::Simplex)),
+            [(4, 1), ref rest @ ..] if threes(rest) => Some((dim, Family::Cube)),
+            [ref rest @ .., (4, 1)] if threes(rest) => Some((dim, Family::Orthoplex)),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the regular polytope with this Schläfli symbol, if
+    /// it's one we know of.
+    pub fn name(&self) -> Option<String> {
+        // Polygons.
+        if let [(n, d)] = self.0[..] {
+            return polygon_name(n, d).map(|(name, _)| name);
+        }
+
+        if let Some(named) = self.named_regular() {
+            return Some(named.name.to_string());
+        }
+
+        // The infinite families of regular polytopes.
+        self.family().map(|(dim, family)| {
+            let suffix = match family {
+                Family::Simplex => "simplex",
+                Family::Cube => "cube",
+                Family::Orthoplex => "orthoplex",
+            };
+
+            format!("{}-{}", dim, suffix)
+        })
+    }
+
+    /// Returns the [Bowers-style acronym](https://polytope.miraheze.org/wiki/Bowers_style_acronym)
+    /// of the regular polytope with this Schläfli symbol, if it's one we know
+    /// of.
+    pub fn acronym(&self) -> Option<String> {
+        // Polygons.
+        if let [(n, d)] = self.0[..] {
+            return polygon_name(n, d)?.1.map(str::to_string);
+        }
+
+        if let Some(named) = self.named_regular() {
+            return Some(named.acronym.to_string());
+        }
+
+        // The infinite families of regular polytopes.
+        let (dim, family) = self.family()?;
+        let acronyms = match family {
+            Family::Simplex => &SIMPLEX_ACRONYMS,
+            Family::Cube => &CUBE_ACRONYMS,
+            Family::Orthoplex => &ORTHOPLEX_ACRONYMS,
+        };
+
+        acronyms
+            .get(dim.checked_sub(5)?)
+            .map(|acronym| acronym.to_string())
+    }
+}
+
+impl SchlafliSymbol {
+    /// The largest number of sides of a polygon that will be recognized by
+    /// [`Self::from_name`].
+    const MAX_POLYGON: usize = 1000;
+
+    /// Returns the Schläfli symbol of the regular polytope with a given full
+    /// name or Bowers-style acronym, or of a family member named like
+    /// `5-cube`. This is the inverse of [`Self::name`] and [`Self::acronym`].
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().to_lowercase();
+
+        // Family members, as in "5-cube".
+        if let Some((dim, family)) = name.split_once('-') {
+            if let Ok(dim) = dim.parse::<usize>() {
+                let family = match family {
+                    "simplex" => Family::Simplex,
+                    "cube" | "hypercube" => Family::Cube,
+                    "orthoplex" => Family::Orthoplex,
+                    "gon" => return (dim >= 2).then(|| Self(vec![(dim, 1)])),
+                    _ => return None,
+                };
+
+                return Self::from_family(dim, family);
+            }
+        }
+
+        // Regular polygons. Only star polygons with up to 12 sides are named.
+        for n in 2..=Self::MAX_POLYGON {
+            let max_d = if n <= 12 { n / 2 } else { 1 };
+
+            for d in 1..=max_d {
+                if let Some((full, acronym)) = polygon_name(n, d) {
+                    if full == name || acronym == Some(name.as_str()) {
+                        return Some(Self(vec![(n, d)]));
+                    }
+                }
+            }
+        }
+
+        if let Some(named) = NAMED_REGULARS
+            .iter()
+            .find(|named| named.name == name || named.acronym == name)
+        {
+            return Some(Self(named.symbol.to_vec()));
+        }
+
+        // Acronyms of family members.
+        for (family, acronyms) in [
+            (Family::Simplex, &SIMPLEX_ACRONYMS),
+            (Family::Cube, &CUBE_ACRONYMS),
+            (Family::Orthoplex, &ORTHOPLEX_ACRONYMS),
+        ]
+        .iter()
+        {
+            if let Some(idx) = acronyms.iter().position(|&acronym| acronym == name) {
+                return Self::from_family(idx + 5, *family);
+            }
+        }
+
+        None
+    }
+
+    /// Returns the Schläfli symbol of the member of a family with a given
+    /// number of dimensions.
+    fn from_family(dim: usize, family: Family) -> Option<Self> {
+        if dim < 2 {
+            return None;
+        }
+
+        let mut entries = vec![(3, 1); dim - 1];
+        match family {
+            Family::Simplex => {}
+            Family::Cube => entries[0] = (4, 1),
+            Family::Orthoplex => entries[dim - 2] = (4, 1),
+        }
+
+        Some(Self(entries))
+    }
+
+    /// Builds the regular polytope with this Schläfli symbol with unit edge
+    /// length, if it's a polygon or belongs to an infinite family. Other
+    /// regular polytopes can't yet be built from their symbols.
+    pub fn build(&self) -> Option<Concrete> {
+        if let [(n, d)] = self.0[..] {
+            return (n >= 2 && d >= 1 && 2 * d <= n)
+                .then(|| Concrete::star_polygon_with_edge(n, d, 1.0));
+        }
+
+        let (dim, family) = self.family()?;
+        let mut poly = match family {
+            Family::Simplex => Concrete::simplex(dim + 1),
+            Family::Cube => Concrete::hypercube(dim + 1),
+            Family::Orthoplex => Concrete::orthoplex(dim + 1),
+        };
+
+        let edge_len = poly.edge_len(0)?;
+        poly.scale(1.0 / edge_len);
+        Some(poly)
+    }
+}
+
+/// The three infinite families of regular polytopes that exist in every
+/// dimension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Family {
+    /// The simplices `{3, 3, ..., 3}`.
+    Simplex,
+
+    /// The hypercubes `{4, 3, ..., 3}`.
+    Cube,
+
+    /// The orthoplices `{3, ..., 3, 4}`.
+    Orthoplex,
+}
+
+/// The Bowers-style acronyms of the simplices from 5 to 10 dimensions.
+const SIMPLEX_ACRONYMS: [&str; 6] = ["hix", "hop", "oca", "ene", "day", "ux"];
+
+/// The Bowers-style acronyms of the hypercubes from 5 to 10 dimensions.
+const CUBE_ACRONYMS: [&str; 6] = ["pent", "ax", "hept", "octo", "enne", "deker"];
+
+/// The Bowers-style acronyms of the orthoplices from 5 to 10 dimensions.
+const ORTHOPLEX_ACRONYMS: [&str; 6] = ["tac", "gee", "zee", "ek", "vee", "ka"];
+
+/// A regular polytope with a special name.
+struct NamedRegular {
+    /// The full name of the polytope.
+    name: &'static str,
+
+    /// The Bowers-style acronym of the polytope.
+    acronym: &'static str,
+
+    /// The Schläfli symbol of the polytope.
+    symbol: &'static [(usize, usize)],
+}
+
+impl NamedRegular {
+    /// Initializes a new entry of the table of named regular polytopes.
+    const fn new(
+        name: &'static str,
+        acronym: &'static str,
+        symbol: &'static [(usize, usize)],
+    ) -> Self {
+        Self {
+            name,
+            acronym,
+            symbol,
+        }
+    }
+}
+
+/// The regular polyhedra and polychora, together with their Schläfli symbols.
+/// These are the only dimensions with regular polytopes outside of the infinite
+/// families.
+const NAMED_REGULARS: [NamedRegular; 25] = [
+    NamedRegular::new("tetrahedron", "tet", &[(3, 1), (3, 1)]),
+    NamedRegular::new("cube", "cube", &[(4, 1), (3, 1)]),
+    NamedRegular::new("octahedron", "oct", &[(3, 1), (4, 1)]),
+    NamedRegular::new("dodecahedron", "doe", &[(5, 1), (3, 1)]),
+    NamedRegular::new("icosahedron", "ike", &[(3, 1), (5, 1)]),
+    NamedRegular::new("small stellated dodecahedron", "sissid", &[(5, 2), (5, 1)]),
+    NamedRegular::new("great dodecahedron", "gad", &[(5, 1), (5, 2)]),
+    NamedRegular::new("great stellated dodecahedron", "gissid", &[(5, 2), (3, 1)]),
+    NamedRegular::new("great icosahedron", "gike", &[(3, 1), (5, 2)]),
+    NamedRegular::new("pentachoron", "pen", &[(3, 1), (3, 1), (3, 1)]),
+    NamedRegular::new("tesseract", "tes", &[(4, 1), (3, 1), (3, 1)]),
+    NamedRegular::new("hexadecachoron", "hex", &[(3, 1), (3, 1), (4, 1)]),
+    NamedRegular::new("icositetrachoron", "ico", &[(3, 1), (4, 1), (3, 1)]),
+    NamedRegular::new("hecatonicosachoron", "hi", &[(5, 1), (3, 1), (3, 1)]),
+    NamedRegular::new("hexacosichoron", "ex", &[(3, 1), (3, 1), (5, 1)]),
+    NamedRegular::new("faceted hexacosichoron", "fix", &[(3, 1), (5, 1), (5, 2)]),
+    NamedRegular::new(
+        "small stellated hecatonicosachoron",
+        "sishi",
+        &[(5, 2), (5, 1), (3, 1)],
+    ),
+    NamedRegular::new(
+        "great hecatonicosachoron",
+        "gohi",
+        &[(5, 1), (5, 2), (5, 1)],
+    ),
+    NamedRegular::new(
+        "grand hecatonicosachoron",
+        "gahi",
+        &[(5, 1), (3, 1), (5, 2)],
+    ),
+    NamedRegular::new(
+        "great stellated hecatonicosachoron",
+        "gishi",
+        &[(5, 2), (3, 1), (5, 1)],
+    ),
+    NamedRegular::new(
+        "grand stellated hecatonicosachoron",
+        "gashi",
+        &[(5, 2), (5, 1), (5, 2)],
+    ),
+    NamedRegular::new(
+        "great grand hecatonicosachoron",
+        "gaghi",
+        &[(5, 1), (5, 2), (3, 1)],
+    ),
+    NamedRegular::new(
+        "great icosahedral hecatonicosachoron",
+        "gofix",
+        &[(3, 1), (5, 2), (5, 1)],
+    ),
+    NamedRegular::new("grand hexacosichoron", "gax", &[(3, 1), (3, 1), (5, 2)]),
+    NamedRegular::new(
+        "great grand stellated hecatonicosachoron",
+        "gogishi",
+        &[(5, 2), (3, 1), (3, 1)],
+    ),
+];
+
+/// The Bowers-style acronyms of the polygons with up to 12 sides.
+const POLYGON_ACRONYMS: [&str; 11] = [
+    "dig", "trig", "square", "peg", "hig", "heg", "og", "een", "dec", "hendec", "dog",
+];
+
+/// Returns the name and Bowers-style acronym of the star polygon `{n/d}`, if
+/// we know of it. Convex polygons are always named, though they only have
+/// acronyms up to 12 sides.
+fn polygon_name(n: usize, d: usize) -> Option<(String, Option<&'static str>)> {
+    let (name, acronym) = match (n, d) {
+        (_, 1) => {
+            return Some((
+                prefix::polygon_name(n),
+                POLYGON_ACRONYMS.get(n.checked_sub(2)?).copied(),
+            ))
+        }
+        (5, 2) => ("pentagram", "stip"),
+        (7, 2) => ("heptagram", "shig"),
+        (7, 3) => ("great heptagram", "giseg"),
+        (8, 3) => ("octagram", "stog"),
+        (9, 2) => ("enneagram", "stean"),
+        (9, 4) => ("great enneagram", "gisen"),
+        (10, 3) => ("decagram", "stedg"),
+        _ => return None,
+    };
+
+    Some((name.to_string(), Some(acronym)))
+}
+
+/// Returns the number of times a polygon winds around its gravicenter, or
+/// `None` if it's not a single cycle on a plane.
+fn winding_number(polygon: &Concrete) -> Option<usize> {
+    let cycles = CycleList::from_edges(polygon.abs[2].iter().map(|edge| &edge.subs));
+    if cycles.len() != 1 {
+        return None;
+    }
+
+    let mut polygon = polygon.clone();
+    polygon.flatten();
+    polygon.recenter();
+    if polygon.dim() != Some(2) {
+        return None;
+    }
+
+    // We add up the angles between consecutive vertices.
+    let cycle = &cycles[0];
+    let mut angle = 0.0;
+    for (&i, &j) in cycle.iter().zip(cycle.iter().cycle().skip(1)) {
+        let (v, w) = (&polygon.vertices[i], &polygon.vertices[j]);
+        let cross = v[0] * w[1] - v[1] * w[0];
+        angle += cross.atan2(v.dot(w));
+    }
+
+    let winding = (angle.abs() / f64::TAU).round() as usize;
+    if winding == 0 {
+        None
+    } else {
+        Some(winding)
+    }
+}
+
+impl Concrete {
+    /// Returns whether the polytope is connected, in the sense that all of its
+    /// flags can be reached from any other by flag changes. Compounds aren't
+    /// connected.
+    fn is_connected(&self) -> bool {
+        OrientedFlagIter::new(&self.abs)
+            .filter_map(|flag_event| flag_event.flag())
+            .count()
+            == self.abs.flag_count()
+    }
+
+    /// Returns whether a polytope is regular, that is, whether its symmetries
+    /// act transitively on its flags.
+    pub fn is_regular(&self) -> bool {
+        let rank = self.rank();
+        if rank < 2 {
+            return true;
+        }
+
+        // The symmetry group is computed on a centered copy of the polytope in
+        // as few dimensions as possible.
+        let mut poly = self.clone();
+        poly.flatten();
+        poly.recenter();
+        if poly.dim() != Some(rank - 1) {
+            return false;
+        }
+
+        poly.element_sort();
+        poly.get_symmetry_group().0.count() == poly.abs.flag_count()
+    }
+
+    /// Returns the Schläfli symbol of a polytope, or `None` if it's not a
+    /// connected regular polytope.
+    pub fn schlafli_symbol(&self) -> Option<SchlafliSymbol> {
+        let rank = self.rank();
+        if rank < 3 {
+            return None;
+        }
+
+        let mut poly = self.clone();
+        poly.element_sort();
+        if !poly.is_connected() || !poly.is_regular() {
+            return None;
+        }
+
+        // Every entry of the symbol comes from a polygonal section through the
+        // first flag.
+        let flag = poly.first_flag();
+        let mut entries = Vec::with_capacity(rank - 2);
+
+        for lo in 0..rank - 2 {
+            let section = poly.section(lo, flag[lo], lo + 3, flag[lo + 3]).ok()?;
+            entries.push((section.vertex_count(), winding_number(&section)?));
+        }
+
+        Some(SchlafliSymbol(entries))
+    }
+
+    /// Attempts to recognize a polytope from its element counts, regularity,
+    /// and symmetry. Returns its name if successful.
+    ///
+    /// For now, this only recognizes regular polytopes. Polytopes too large
+    /// to check quickly, as given by [`MAX_RECOGNITION_COST`], are never
+    /// recognized.
+    pub fn recognize(&self) -> Option<String> {
+        // A quick check that rules out most non-regular polytopes without
+        // computing their symmetry group.
+        if self.rank() >= 3 {
+            let edges_per_face = self.abs[3].iter().map(|face| face.subs.len());
+            let edges_per_vertex = self.abs[1].iter().map(|vertex| vertex.sups.len());
+
+            if !all_equal(edges_per_face) || !all_equal(edges_per_vertex) {
+                return None;
+            }
+        }
+
+        let cost = self.abs.flag_count().saturating_mul(self.vertex_count());
+        if cost > MAX_RECOGNITION_COST {
+            return None;
+        }
+
+        self.schlafli_symbol()?.name()
+    }
+}
+
+/// Returns whether all values in an iterator are equal.
+fn all_equal<T: PartialEq, I: Iterator<Item = T>>(mut iter: I) -> bool {
+    match iter.next() {
+        Some(first) => iter.all(|x| x == first),
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::FromFile;
+
+    /// Builds the small stellated dodecahedron on the vertices of an
+    /// icosahedron.
+    fn small_stellated_dodecahedron() -> Concrete {
+        let phi = (1.0 + 5f64.sqrt()) / 2.0;
+        let mut vertices = Vec::new();
+        for &a in &[-1.0, 1.0] {
+            for &b in &[-phi, phi] {
+                vertices.push([0.0, a, b]);
+                vertices.push([a, b, 0.0]);
+                vertices.push([b, 0.0, a]);
+            }
+        }
+
+        let dist =
+            |v: &[f64; 3], w: &[f64; 3]| (0..3).map(|i| (v[i] - w[i]).powi(2)).sum::<f64>().sqrt();
+
+        // Every face is a pentagram on the neighbors of a vertex.
+        let mut src = String::from("OFF\n12 12 30\n");
+        for v in &vertices {
+            src += &format!("{} {} {}\n", v[0], v[1], v[2]);
+        }
+
+        for v in &vertices {
+            let mut neighbors: Vec<_> = (0..12)
+                .filter(|&i| (dist(v, &vertices[i]) - 2.0).abs() < 1e-6)
+                .collect();
+
+            // We sort the neighbors by their angle around the vertex.
+            let first = vertices[neighbors[0]];
+            let angle = |w: &[f64; 3]| {
+                let cross = [
+                    first[1] * w[2] - first[2] * w[1],
+                    first[2] * w[0] - first[0] * w[2],
+                    first[0] * w[1] - first[1] * w[0],
+                ];
+                let sin: f64 = (0..3).map(|i| cross[i] * v[i]).sum();
+                let cos: f64 = (0..3).map(|i| first[i] * w[i]).sum();
+                sin.atan2(cos - phi * phi)
+            };
+            neighbors.sort_by(|&i, &j| {
+                angle(&vertices[i])
+                    .partial_cmp(&angle(&vertices[j]))
+                    .unwrap()
+            });
+
+            let n = &neighbors;
+            src += &format!("5 {} {} {} {} {}\n", n[0], n[2], n[4], n[1], n[3]);
+        }
+
+        Concrete::from_off(&src).unwrap()
+    }
+
+    #[test]
+    fn regular() {
+        let name = |p: Concrete| p.recognize();
+
+        assert_eq!(name(Concrete::polygon(5)).as_deref(), Some("pentagon"));
+        assert_eq!(
+            name(Concrete::star_polygon(5, 2)).as_deref(),
+            Some("pentagram")
+        );
+        assert_eq!(name(Concrete::hypercube(4)).as_deref(), Some("cube"));
+        assert_eq!(name(Concrete::hypercube(5)).as_deref(), Some("tesseract"));
+        assert_eq!(name(Concrete::hypercube(6)).as_deref(), Some("5-cube"));
+        assert_eq!(name(Concrete::orthoplex(4)).as_deref(), Some("octahedron"));
+        assert_eq!(name(Concrete::simplex(6)).as_deref(), Some("5-simplex"));
+
+        let sissid = small_stellated_dodecahedron();
+        assert_eq!(
+            sissid.schlafli_symbol().unwrap().to_string(),
+            "{5/2,5}".to_string()
+        );
+        assert_eq!(
+            name(sissid).as_deref(),
+            Some("small stellated dodecahedron")
+        );
+    }
+
+    #[test]
+    fn acronyms() {
+        let acronym = |symbol: &[(usize, usize)]| SchlafliSymbol(symbol.to_vec()).acronym();
+
+        assert_eq!(acronym(&[(5, 2)]).as_deref(), Some("stip"));
+        assert_eq!(acronym(&[(5, 2), (5, 1)]).as_deref(), Some("sissid"));
+        assert_eq!(
+            acronym(&[(5, 2), (3, 1), (3, 1)]).as_deref(),
+            Some("gogishi")
+        );
+        assert_eq!(acronym(&[(3, 1); 4]).as_deref(), Some("hix"));
+        assert_eq!(
+            acronym(&[(4, 1), (3, 1), (3, 1), (3, 1), (3, 1)]).as_deref(),
+            Some("ax")
+        );
+        assert_eq!(
+            acronym(&[(3, 1), (3, 1), (3, 1), (4, 1)]).as_deref(),
+            Some("tac")
+        );
+        assert_eq!(acronym(&[(3, 1); 12]), None);
+    }
+
+    #[test]
+    fn not_regular() {
+        // A prism isn't regular.
+        let prism = Concrete::polygon(6).prism();
+        assert!(prism.recognize().is_none());
+
+        // A compound of regular polytopes isn't regular either.
+        let mut compound = Concrete::hypercube(4);
+        compound.comp_append(Concrete::hypercube(4).try_dual().unwrap());
+        assert!(compound.recognize().is_none());
+
+        // The 8-cube is regular, but too large to check on load.
+        assert!(Concrete::hypercube(9).recognize().is_none());
+    }
+}
@@ -0,0 +1,787 @@
+//! A small expression language to build polytopes, as in
+//! `dual(prism(load("ike.off")))` or `duoprism(polygon(5), polygon(3))`.
+//!
+//! A line is either an expression, or an assignment like `p = polygon(5)` that
+//! stores the result of an expression in a variable for later lines. An
+//! expression is a number, a string in double quotes, a variable, or a
+//! function called on a list of expressions. Dimensions are given as in
+//! geometry, so that `simplex(3)` is the tetrahedron.
+
+use std::{collections::HashMap, fmt::Display};
+
+use super::{Concrete, ConcretePolytope};
+use crate::{abs::Ranked, file::FromFile, float::Float, Polytope};
+
+/// Represents an error while parsing or running a line.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ScriptError {
+    /// A character at a given position wasn't expected.
+    Unexpected {
+        /// The position of the character, in bytes.
+        pos: usize,
+
+        /// The character found, or `None` if the line ended.
+        found: Option<char>,
+    },
+
+    /// A string wasn't closed.
+    UnclosedString(usize),
+
+    /// A function with the given name doesn't exist.
+    UnknownFunction(String),
+
+    /// A variable with the given name doesn't exist.
+    UnknownVariable(String),
+
+    /// A function was called with the wrong number of arguments.
+    ArgumentCount {
+        /// The name of the function.
+        function: String,
+
+        /// The number of arguments the function takes.
+        expected: usize,
+
+        /// The number of arguments it was called with.
+        found: usize,
+    },
+
+    /// A function was called with an argument of the wrong type.
+    ArgumentType {
+        /// The name of the function.
+        function: String,
+
+        /// The type the argument should have had.
+        expected: &'static str,
+    },
+
+    /// A function couldn't be applied.
+    Failed {
+        /// The name of the function.
+        function: String,
+
+        /// The reason the function failed.
+        reason: String,
+    },
+}
+
+impl Display for ScriptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unexpected {
+                pos,
+                found: Some(c),
+            } => write!(f, "unexpected '{}' at position {}", c, pos),
+            Self::Unexpected { pos, found: None } => {
+                write!(f, "unexpected end of line at position {}", pos)
+            }
+            Self::UnclosedString(pos) => write!(f, "string at position {} isn't closed", pos),
+            Self::UnknownFunction(name) => write!(f, "unknown function \"{}\"", name),
+            Self::UnknownVariable(name) => write!(f, "unknown variable \"{}\"", name),
+            Self::ArgumentCount {
+                function,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{} takes {} arguments, but {} were given",
+                function, expected, found
+            ),
+            Self::ArgumentType { function, expected } => {
+                write!(f, "{} expected {} as argument", function, expected)
+            }
+            Self::Failed { function, reason } => write!(f, "{} failed: {}", function, reason),
+        }
+    }
+}
+
+impl std::error::Error for ScriptError {}
+
+/// The result of parsing or running a line.
+pub type ScriptResult<T> = Result<T, ScriptError>;
+
+/// A parsed expression.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr {
+    /// A number.
+    Number(f64),
+
+    /// A string.
+    Str(String),
+
+    /// A variable.
+    Var(String),
+
+    /// A function called on some arguments.
+    Call(String, Vec<Expr>),
+}
+
+/// A parsed line.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Statement {
+    /// An expression to evaluate.
+    Expr(Expr),
+
+    /// An expression whose value is stored in a variable.
+    Assign(String, Expr),
+}
+
+/// The value of an expression.
+#[derive(Clone, Debug)]
+pub enum Value {
+    /// A number.
+    Number(f64),
+
+    /// A string.
+    Str(String),
+
+    /// A polytope.
+    Polytope(Concrete),
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(x) => write!(f, "{}", x),
+            Self::Str(s) => write!(f, "{:?}", s),
+            Self::Polytope(p) => write!(
+                f,
+                "polytope with element counts {:?}",
+                p.el_count_iter().collect::<Vec<_>>()
+            ),
+        }
+    }
+}
+
+/// Reads the tokens of a line, and parses them into a [`Statement`].
+struct Parser<'a> {
+    /// The line being parsed.
+    src: &'a str,
+
+    /// The current position in the line, in bytes.
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    /// Initializes a parser for a line.
+    fn new(src: &'a str) -> Self {
+        Self { src, pos: 0 }
+    }
+
+    /// Skips any whitespace, and returns the next character without consuming
+    /// it.
+    fn peek(&mut self) -> Option<char> {
+        let rest = &self.src[self.pos..];
+        let trimmed = rest.trim_start();
+        self.pos += rest.len() - trimmed.len();
+        trimmed.chars().next()
+    }
+
+    /// The error for the character at the current position.
+    fn unexpected(&mut self) -> ScriptError {
+        let found = self.peek();
+        ScriptError::Unexpected {
+            pos: self.pos,
+            found,
+        }
+    }
+
+    /// Consumes a given character, or returns an error if it's not the next
+    /// one.
+    fn expect(&mut self, c: char) -> ScriptResult<()> {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    /// Consumes the longest run of characters satisfying a predicate.
+    fn take_while<F: Fn(char) -> bool>(&mut self, f: F) -> &'a str {
+        let rest = &self.src[self.pos..];
+        let len = rest.find(|c| !f(c)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    /// Parses a whole line.
+    fn statement(&mut self) -> ScriptResult<Statement> {
+        let res = match self.expr()? {
+            Expr::Var(name) if self.peek() == Some('=') => {
+                self.pos += 1;
+                Statement::Assign(name, self.expr()?)
+            }
+            expr => Statement::Expr(expr),
+        };
+
+        if self.peek().is_some() {
+            return Err(self.unexpected());
+        }
+        Ok(res)
+    }
+
+    /// Parses an expression.
+    fn expr(&mut self) -> ScriptResult<Expr> {
+        match self.peek() {
+            Some('"') => {
+                let start = self.pos;
+                self.pos += 1;
+                let s = self.take_while(|c| c != '"');
+                if self.peek_raw() != Some('"') {
+                    return Err(ScriptError::UnclosedString(start));
+                }
+                self.pos += 1;
+                Ok(Expr::Str(s.to_string()))
+            }
+
+            Some(c) if c.is_ascii_digit() || c == '-' || c == '.' => {
+                let start = self.pos;
+                self.pos += c.len_utf8();
+                self.take_while(|c| c.is_ascii_digit() || c == '.');
+                self.src[start..self.pos]
+                    .parse()
+                    .map(Expr::Number)
+                    .map_err(|_| ScriptError::Unexpected {
+                        pos: start,
+                        found: Some(c),
+                    })
+            }
+
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                let name = self
+                    .take_while(|c| c.is_alphanumeric() || c == '_')
+                    .to_string();
+
+                if self.peek() != Some('(') {
+                    return Ok(Expr::Var(name));
+                }
+                self.pos += 1;
+
+                let mut args = Vec::new();
+                if self.peek() == Some(')') {
+                    self.pos += 1;
+                    return Ok(Expr::Call(name, args));
+                }
+
+                loop {
+                    args.push(self.expr()?);
+                    match self.peek() {
+                        Some(',') => self.pos += 1,
+                        _ => {
+                            self.expect(')')?;
+                            return Ok(Expr::Call(name, args));
+                        }
+                    }
+                }
+            }
+
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    /// Returns the next character without skipping whitespace.
+    fn peek_raw(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+}
+
+/// Parses a line into a [`Statement`].
+pub fn parse(src: &str) -> ScriptResult<Statement> {
+    Parser::new(src).statement()
+}
+
+/// The arguments of a function being evaluated.
+struct Args<'a> {
+    /// The name of the function.
+    function: &'a str,
+
+    /// The values of the arguments.
+    values: Vec<Value>,
+}
+
+impl<'a> Args<'a> {
+    /// Checks that there's exactly a given number of arguments.
+    fn count(&self, expected: usize) -> ScriptResult<()> {
+        if self.values.len() == expected {
+            Ok(())
+        } else {
+            Err(ScriptError::ArgumentCount {
+                function: self.function.to_string(),
+                expected,
+                found: self.values.len(),
+            })
+        }
+    }
+
+    /// The error for an argument of the wrong type.
+    fn wrong_type(&self, expected: &'static str) -> ScriptError {
+        ScriptError::ArgumentType {
+            function: self.function.to_string(),
+            expected,
+        }
+    }
+
+    /// The error for a function that couldn't be applied.
+    fn failed<T: Display>(&self, reason: T) -> ScriptError {
+        ScriptError::Failed {
+            function: self.function.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Takes the argument with a given index as a polytope.
+    fn polytope(&mut self, idx: usize) -> ScriptResult<Concrete> {
+        match std::mem::replace(&mut self.values[idx], Value::Number(0.0)) {
+            Value::Polytope(p) => Ok(p),
+            _ => Err(self.wrong_type("a polytope")),
+        }
+    }
+
+    /// Reads the argument with a given index as a number.
+    fn number(&self, idx: usize) -> ScriptResult<f64> {
+        match self.values[idx] {
+            Value::Number(x) => Ok(x),
+            _ => Err(self.wrong_type("a number")),
+        }
+    }
+
+    /// Reads the argument with a given index as a natural number between two
+    /// bounds, inclusive.
+    fn natural(&self, idx: usize, min: usize, max: usize) -> ScriptResult<usize> {
+        let x = self.number(idx)?;
+        if x.fract() == 0.0 && x >= min as f64 && x <= max as f64 {
+            Ok(x as usize)
+        } else {
+            Err(self.failed(format!(
+                "{} isn't an integer between {} and {}",
+                x, min, max
+            )))
+        }
+    }
+
+    /// Takes the only argument as a polytope, which must have at least some
+    /// given rank.
+    fn unary_with_rank(&mut self, rank: usize, reason: &str) -> ScriptResult<Concrete> {
+        let p = self.unary()?;
+        if p.rank() >= rank {
+            Ok(p)
+        } else {
+            Err(self.failed(reason))
+        }
+    }
+
+    /// Reads the argument with a given index as a string.
+    fn string(&self, idx: usize) -> ScriptResult<&str> {
+        match &self.values[idx] {
+            Value::Str(s) => Ok(s),
+            _ => Err(self.wrong_type("a string")),
+        }
+    }
+
+    /// Takes the only argument as a polytope.
+    fn unary(&mut self) -> ScriptResult<Concrete> {
+        self.count(1)?;
+        self.polytope(0)
+    }
+
+    /// Takes the only two arguments as polytopes.
+    fn binary(&mut self) -> ScriptResult<(Concrete, Concrete)> {
+        self.count(2)?;
+        Ok((self.polytope(0)?, self.polytope(1)?))
+    }
+}
+
+/// Runs lines of the expression language, keeping track of the variables
+/// assigned so far.
+#[derive(Clone, Debug, Default)]
+pub struct Interpreter {
+    /// The values of the variables.
+    variables: HashMap<String, Value>,
+}
+
+impl Interpreter {
+    /// Initializes an interpreter with no variables.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sets the value of a variable.
+    pub fn set<T: ToString>(&mut self, name: T, value: Value) {
+        self.variables.insert(name.to_string(), value);
+    }
+
+    /// Returns the value of a variable, if it exists.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.variables.get(name)
+    }
+
+    /// Parses and runs a line. Returns the value of the expression, or `None`
+    /// if the line was an assignment.
+    pub fn run(&mut self, src: &str) -> ScriptResult<Option<Value>> {
+        match parse(src)? {
+            Statement::Expr(expr) => self.eval(&expr).map(Some),
+            Statement::Assign(name, expr) => {
+                let value = self.eval(&expr)?;
+                self.variables.insert(name, value);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Evaluates an expression.
+    pub fn eval(&self, expr: &Expr) -> ScriptResult<Value> {
+        match expr {
+            Expr::Number(x) => Ok(Value::Number(*x)),
+            Expr::Str(s) => Ok(Value::Str(s.clone())),
+            Expr::Var(name) => self
+                .variables
+                .get(name)
+                .cloned()
+                .ok_or_else(|| ScriptError::UnknownVariable(name.clone())),
+            Expr::Call(function, args) => {
+                let values = args
+                    .iter()
+                    .map(|arg| self.eval(arg))
+                    .collect::<ScriptResult<_>>()?;
+
+                call(Args { function, values })
+            }
+        }
+    }
+}
+
+/// Calls a function on some arguments.
+fn call(mut args: Args<'_>) -> ScriptResult<Value> {
+    let p = match args.function {
+        // Polytopes built from scratch.
+        "point" => {
+            args.count(0)?;
+            Concrete::point()
+        }
+        "dyad" => {
+            args.count(0)?;
+            Concrete::dyad()
+        }
+        "polygon" => {
+            args.count(1)?;
+            Concrete::polygon(args.natural(0, 2, MAX_SIDES)?)
+        }
+        "star" => {
+            args.count(2)?;
+            let n = args.natural(0, 2, MAX_SIDES)?;
+            Concrete::star_polygon(n, args.natural(1, 1, n - 1)?)
+        }
+        "simplex" => {
+            args.count(1)?;
+            Concrete::simplex(args.natural(0, 0, MAX_DIM)? + 1)
+        }
+        "hypercube" => {
+            args.count(1)?;
+            Concrete::hypercube(args.natural(0, 0, MAX_DIM)? + 1)
+        }
+        "orthoplex" => {
+            args.count(1)?;
+            Concrete::orthoplex(args.natural(0, 0, MAX_DIM)? + 1)
+        }
+        "name" => {
+            args.count(1)?;
+            Concrete::from_name(args.string(0)?).map_err(|err| args.failed(err))?
+        }
+        "load" => {
+            args.count(1)?;
+            Concrete::from_path(&args.string(0)?).map_err(|err| args.failed(err))?
+        }
+
+        // Operations on a single polytope.
+        "dual" => args.unary()?.try_dual().map_err(|err| args.failed(err))?,
+        "pyramid" => args.unary()?.pyramid(),
+        "prism" => args.unary()?.prism(),
+        "tegum" => args.unary()?.tegum(),
+        "antiprism" => args
+            .unary_with_rank(2, "the base must be at least a dyad")?
+            .try_antiprism()
+            .map_err(|err| args.failed(err))?,
+        "antitegum" => args
+            .unary_with_rank(2, "the base must be at least a dyad")?
+            .try_antitegum()
+            .map_err(|err| args.failed(err))?,
+        "petrial" => args
+            .unary()?
+            .petrial()
+            .ok_or_else(|| args.failed("the Petrial isn't a polytope"))?,
+        "antipodal" => args
+            .unary()?
+            .antipodal_quotient()
+            .ok_or_else(|| args.failed("the polytope isn't centrally symmetric"))?,
+        "ditope" => args.unary()?.ditope(),
+        "hosotope" => args.unary()?.hosotope(),
+        "omnitruncate" => {
+            let mut p = args.unary()?;
+            if !p.abs().sorted() {
+                p.element_sort();
+            }
+            p.omnitruncate()
+        }
+        "recenter" => {
+            let mut p = args.unary()?;
+            p.recenter();
+            p
+        }
+        "normalize" => {
+            let mut p = args.unary()?;
+            if !p.normalize() {
+                return Err(args.failed("the polytope has no size"));
+            }
+            p
+        }
+        "fuse" => {
+            let mut p = args.unary()?;
+            p.fuse_vertices(f64::eps());
+            p
+        }
+        "hull" => Concrete::convex_hull(&args.unary()?.vertices)
+            .ok_or_else(|| args.failed("the polytope has no vertices"))?,
+        "delaunay" => Concrete::delaunay(&args.unary()?.vertices)
+            .ok_or_else(|| args.failed("the polytope has no vertices"))?,
+        "scale" => {
+            args.count(2)?;
+            let mut p = args.polytope(0)?;
+            p.scale(args.number(1)?);
+            p
+        }
+
+        // Operations on more than one polytope.
+        "duopyramid" => {
+            let (p, q) = args.binary()?;
+            p.duopyramid(&q)
+        }
+        "duoprism" => {
+            let (p, q) = args.binary()?;
+            p.duoprism(&q)
+        }
+        "duotegum" => {
+            let (p, q) = args.binary()?;
+            p.duotegum(&q)
+        }
+        "duocomb" => {
+            let (p, q) = args.binary()?;
+            if p.rank() < 2 || q.rank() < 2 {
+                return Err(args.failed("the factors must be at least dyads"));
+            }
+            p.duocomb(&q)
+        }
+        "compound" => {
+            if args.values.is_empty() {
+                return Err(args.failed("no components were given"));
+            }
+
+            let components = (0..args.values.len())
+                .map(|idx| args.polytope(idx))
+                .collect::<ScriptResult<Vec<_>>>()?;
+            Concrete::compound(components.into_iter())
+        }
+
+        // Numbers computed from a polytope.
+        "volume" => {
+            let volume = args.unary()?.volume();
+            return volume
+                .value()
+                .map(Value::Number)
+                .ok_or_else(|| args.failed(format!("the volume is {}", volume)));
+        }
+        "circumradius" => {
+            let p = args.unary()?;
+            return p
+                .circumsphere()
+                .map(|sphere| Value::Number(sphere.radius()))
+                .ok_or_else(|| args.failed("the polytope has no circumsphere"));
+        }
+
+        function => return Err(ScriptError::UnknownFunction(function.to_string())),
+    };
+
+    Ok(Value::Polytope(p))
+}
+
+/// The largest number of sides of a polygon that a script may build.
+const MAX_SIDES: usize = 1 << 16;
+
+/// The largest dimension of a simplex, hypercube or orthoplex that a script
+/// may build.
+const MAX_DIM: usize = 12;
+
+/// The names of the functions of the language, to show as help.
+pub const FUNCTIONS: [&str; 33] = [
+    "point()",
+    "dyad()",
+    "polygon(n)",
+    "star(n, d)",
+    "simplex(dim)",
+    "hypercube(dim)",
+    "orthoplex(dim)",
+    "name(\"...\")",
+    "load(\"path\")",
+    "dual(p)",
+    "pyramid(p)",
+    "prism(p)",
+    "tegum(p)",
+    "antiprism(p)",
+    "antitegum(p)",
+    "petrial(p)",
+    "antipodal(p)",
+    "ditope(p)",
+    "hosotope(p)",
+    "omnitruncate(p)",
+    "recenter(p)",
+    "normalize(p)",
+    "fuse(p)",
+    "hull(p)",
+    "delaunay(p)",
+    "scale(p, k)",
+    "duopyramid(p, q)",
+    "duoprism(p, q)",
+    "duotegum(p, q)",
+    "duocomb(p, q)",
+    "compound(p, q, ...)",
+    "volume(p)",
+    "circumradius(p)",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+
+    use approx::abs_diff_eq;
+
+    /// Runs a line and returns the polytope it evaluates to.
+    fn run(interpreter: &mut Interpreter, src: &str) -> Concrete {
+        match interpreter.run(src) {
+            Ok(Some(Value::Polytope(p))) => p,
+            res => panic!("{} didn't evaluate to a polytope: {:?}", src, res),
+        }
+    }
+
+    /// Checks that lines are parsed correctly.
+    #[test]
+    fn parse() {
+        assert_eq!(
+            super::parse("p = duoprism(polygon(5), load(\"a b.off\"))"),
+            Ok(Statement::Assign(
+                "p".to_string(),
+                Expr::Call(
+                    "duoprism".to_string(),
+                    vec![
+                        Expr::Call("polygon".to_string(), vec![Expr::Number(5.0)]),
+                        Expr::Call("load".to_string(), vec![Expr::Str("a b.off".to_string())]),
+                    ]
+                )
+            ))
+        );
+
+        assert_eq!(
+            super::parse("prism(p"),
+            Err(ScriptError::Unexpected {
+                pos: 7,
+                found: None
+            })
+        );
+        assert_eq!(
+            super::parse("prism(p))"),
+            Err(ScriptError::Unexpected {
+                pos: 8,
+                found: Some(')')
+            })
+        );
+        assert_eq!(
+            super::parse("load(\"ike.off)"),
+            Err(ScriptError::UnclosedString(5))
+        );
+    }
+
+    /// Checks that expressions build the right polytopes.
+    #[test]
+    fn eval() {
+        let mut interpreter = Interpreter::new();
+        test(
+            &run(&mut interpreter, "duoprism(polygon(5), polygon(3))"),
+            [1, 15, 30, 23, 8, 1],
+        );
+        test(
+            &run(&mut interpreter, "dual(prism(simplex(2)))"),
+            [1, 5, 9, 6, 1],
+        );
+        test(
+            &run(&mut interpreter, "compound(hypercube(3), orthoplex(3))"),
+            [1, 14, 24, 14, 1],
+        );
+        test(
+            &run(
+                &mut interpreter,
+                "hull(compound(hypercube(3), orthoplex(3)))",
+            ),
+            [1, 14, 36, 24, 1],
+        );
+        test(&run(&mut interpreter, "delaunay(polygon(6))"), [1, 6, 6, 1]);
+
+        let cube = run(&mut interpreter, "normalize(scale(hypercube(4), 10))");
+        assert!(cube
+            .vertices
+            .iter()
+            .all(|v| abs_diff_eq!(v.norm(), 1.0, epsilon = f64::eps())));
+    }
+
+    /// Checks that variables can be assigned and used.
+    #[test]
+    fn variables() {
+        let mut interpreter = Interpreter::new();
+        assert!(interpreter.run("p = polygon(4)").unwrap().is_none());
+        test(&run(&mut interpreter, "pyramid(p)"), [1, 5, 8, 5, 1]);
+
+        assert_eq!(
+            interpreter.run("prism(q)").unwrap_err(),
+            ScriptError::UnknownVariable("q".to_string())
+        );
+    }
+
+    /// Checks that wrong calls give errors.
+    #[test]
+    fn errors() {
+        let mut interpreter = Interpreter::new();
+        assert_eq!(
+            interpreter.run("frobnicate(1)").unwrap_err(),
+            ScriptError::UnknownFunction("frobnicate".to_string())
+        );
+        assert_eq!(
+            interpreter.run("duoprism(polygon(3))").unwrap_err(),
+            ScriptError::ArgumentCount {
+                function: "duoprism".to_string(),
+                expected: 2,
+                found: 1
+            }
+        );
+        assert_eq!(
+            interpreter.run("prism(3)").unwrap_err(),
+            ScriptError::ArgumentType {
+                function: "prism".to_string(),
+                expected: "a polytope"
+            }
+        );
+        for src in [
+            "polygon(2.5)",
+            "polygon(1000000000000000)",
+            "star(5, 5)",
+            "star(5, 0)",
+            "hypercube(100)",
+            "antiprism(point())",
+            "antitegum(point())",
+            "duocomb(point(), point())",
+            "duocomb(polygon(3), point())",
+        ] {
+            assert!(matches!(
+                interpreter.run(src),
+                Err(ScriptError::Failed { .. })
+            ));
+        }
+    }
+}
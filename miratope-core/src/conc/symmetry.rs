@@ -1,11 +1,11 @@
 //! The code used to get the symmetry of a polytope and do operations based on that.
 
-use std::{collections::BTreeMap, vec, iter::FromIterator};
+use std::{collections::{BTreeMap, HashMap}, fmt::Display, vec, iter::FromIterator};
 
 use crate::{
     abs::{Ranked, flag::{FlagIter, Flag}},
-    conc::Concrete,
-    group::{Group, GenIter}, geometry::{Matrix, PointOrd}, Polytope,
+    conc::{element_types::EL_NAMES, Concrete, ConcretePolytope},
+    group::{burnside::CycleIndex, Group, GenIter}, geometry::{Matrix, Point, PointOrd}, Polytope,
 };
 
 use vec_like::*;
@@ -32,6 +32,144 @@ impl Flag {
     }
 }
 
+/// A summary of the elements of a polytope that its symmetries act
+/// transitively on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransitivityReport {
+    /// The number of orbits of the elements of each rank, from the vertices up
+    /// to the facets.
+    pub orbits: Vec<usize>,
+
+    /// The number of orbits of flags.
+    pub flag_orbits: usize,
+
+    /// The order of the symmetry group.
+    pub order: usize,
+
+    /// Whether the abstract automorphism group was used, rather than the
+    /// geometric symmetry group.
+    pub combinatorial: bool,
+}
+
+impl TransitivityReport {
+    /// Returns whether the symmetries act transitively on the elements of a
+    /// given rank, where the vertices have rank 1.
+    pub fn is_transitive(&self, rank: usize) -> bool {
+        rank >= 1 && self.orbits.get(rank - 1) == Some(&1)
+    }
+
+    /// Returns whether the polytope is vertex-transitive.
+    pub fn is_isogonal(&self) -> bool {
+        self.is_transitive(1)
+    }
+
+    /// Returns whether the polytope is edge-transitive.
+    pub fn is_isotoxal(&self) -> bool {
+        self.is_transitive(2)
+    }
+
+    /// Returns whether the polytope is facet-transitive.
+    pub fn is_isohedral(&self) -> bool {
+        self.orbits.last() == Some(&1)
+    }
+
+    /// Returns whether the polytope is both vertex- and facet-transitive.
+    pub fn is_noble(&self) -> bool {
+        self.is_isogonal() && self.is_isohedral()
+    }
+
+    /// Returns whether the polytope is flag-transitive.
+    pub fn is_regular(&self) -> bool {
+        self.flag_orbits == 1
+    }
+}
+
+impl Display for TransitivityReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.combinatorial {
+            writeln!(f, "Automorphism group order: {}", self.order)?;
+        } else {
+            writeln!(f, "Symmetry order: {}", self.order)?;
+        }
+
+        for (r, &orbits) in self.orbits.iter().enumerate() {
+            let plural = if orbits == 1 { "" } else { "s" };
+            writeln!(f, "{}: {} orbit{}", EL_NAMES[r + 1], orbits, plural)?;
+        }
+        let plural = if self.flag_orbits == 1 { "" } else { "s" };
+        writeln!(f, "Flags: {} orbit{}", self.flag_orbits, plural)?;
+
+        let properties: Vec<_> = [
+            (self.is_isogonal(), "isogonal"),
+            (self.is_isotoxal(), "isotoxal"),
+            (self.is_isohedral(), "isohedral"),
+            (self.is_noble(), "noble"),
+            (self.is_regular(), "regular"),
+        ]
+        .iter()
+        .filter(|(holds, _)| *holds)
+        .map(|&(_, name)| name)
+        .collect();
+
+        if properties.is_empty() {
+            write!(f, "The polytope isn't isogonal, isotoxal, or isohedral.")
+        } else {
+            write!(f, "The polytope is {}.", properties.join(", "))
+        }
+    }
+}
+
+/// An error while snapping a polytope to a symmetry group.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SymmetrizeError {
+    /// The polytope isn't full-dimensional, so its symmetry group can't be
+    /// detected.
+    NotFullDimensional,
+
+    /// An element of the group has the wrong size for the polytope.
+    WrongDimension {
+        /// The dimension of the polytope.
+        expected: usize,
+
+        /// The size of the group element.
+        found: usize,
+    },
+
+    /// An element of the group doesn't send the vertices of the polytope
+    /// close to its vertices.
+    NotASymmetry {
+        /// The index of the group element.
+        idx: usize,
+    },
+}
+
+impl Display for SymmetrizeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFullDimensional => write!(f, "the polytope isn't full-dimensional"),
+            Self::WrongDimension { expected, found } => write!(
+                f,
+                "expected {}×{} matrices, found a {}×{} one",
+                expected, expected, found, found
+            ),
+            Self::NotASymmetry { idx } => {
+                write!(f, "group element {} isn't a symmetry of the polytope", idx)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SymmetrizeError {}
+
+/// Returns the orthogonal matrix closest to a given one. Isometries computed
+/// from vertices that have drifted are only approximately orthogonal.
+fn orthogonalize(m: Matrix<f64>) -> Matrix<f64> {
+    let svd = m.svd(true, true);
+
+    // Safety: we asked for both of these to be computed.
+    svd.u.unwrap() * svd.v_t.unwrap()
+}
+
 impl Concrete {
     /// Computes the symmetry group of a polytope, along with a list of vertex mappings.
     pub fn get_symmetry_group(&mut self) -> (Group<vec::IntoIter<Matrix<f64>>>, Vec<Vec<usize>>) {
@@ -135,4 +273,421 @@ impl Concrete {
         }
         vertex_map
     }
-}
\ No newline at end of file
+
+    /// Whether the polytope is full-dimensional, so that its geometric
+    /// symmetry group can be computed.
+    pub fn is_full_dimensional(&mut self) -> bool {
+        self.element_sort();
+        let rank = self.rank();
+        rank >= 2
+            && self.dim() == Some(rank - 1)
+            && self
+                .first_flag()
+                .vertex_sequence(self)
+                .try_inverse()
+                .is_some()
+    }
+
+    /// Moves every vertex to the average of its images under the inverses of a
+    /// list of isometries, each of which sends the `i`-th vertex close to the
+    /// `map[i]`-th one.
+    fn average_vertices(&mut self, isometries: &[Matrix<f64>], maps: &[Vec<usize>]) {
+        let order = isometries.len() as f64;
+        let vertices = (0..self.vertices.len())
+            .map(|i| {
+                let mut sum = Point::zeros(self.vertices[i].len());
+                for (isometry, map) in isometries.iter().zip(maps) {
+                    sum += isometry.tr_mul(&self.vertices[map[i]]);
+                }
+                sum / order
+            })
+            .collect();
+
+        self.vertices = vertices;
+    }
+
+    /// Snaps a polytope to its own symmetry group, removing the floating-point
+    /// drift that builds up after long chains of operations. Every vertex is
+    /// moved to the average of its images under the symmetries, which keeps
+    /// the combinatorics fixed.
+    ///
+    /// Only the symmetries that survived the drift can be detected. Use
+    /// [`Self::symmetrize_with`] to snap to a known group instead.
+    pub fn symmetrize(&mut self) -> Result<(), SymmetrizeError> {
+        if !self.is_full_dimensional() {
+            return Err(SymmetrizeError::NotFullDimensional);
+        }
+
+        let (group, maps) = self.get_symmetry_group();
+        let isometries: Vec<_> = group.map(orthogonalize).collect();
+        self.average_vertices(&isometries, &maps);
+        Ok(())
+    }
+
+    /// Snaps a polytope to a given group of isometries, as in
+    /// [`Self::symmetrize`]. Each isometry must send every vertex closer to
+    /// some vertex than half the distance between any two vertices.
+    pub fn symmetrize_with<I: Iterator<Item = Matrix<f64>>>(
+        &mut self,
+        group: Group<I>,
+    ) -> Result<(), SymmetrizeError> {
+        let dim = self.dim_or();
+        let vertices = &self.vertices;
+
+        // The distance within which two points are taken as the same vertex.
+        let mut min_distance = f64::INFINITY;
+        for (i, v) in vertices.iter().enumerate() {
+            for w in &vertices[i + 1..] {
+                min_distance = min_distance.min((v - w).norm());
+            }
+        }
+        let tolerance = min_distance / 2.0;
+
+        let mut isometries = Vec::new();
+        let mut maps = Vec::new();
+        for (idx, isometry) in group.enumerate() {
+            if isometry.nrows() != dim || isometry.ncols() != dim {
+                return Err(SymmetrizeError::WrongDimension {
+                    expected: dim,
+                    found: isometry.nrows(),
+                });
+            }
+
+            let mut map = Vec::with_capacity(vertices.len());
+            for v in vertices {
+                let image = &isometry * v;
+                let (j, distance) = vertices
+                    .iter()
+                    .map(|w| (&image - w).norm())
+                    .enumerate()
+                    .min_by(|(_, x), (_, y)| x.total_cmp(y))
+                    .ok_or(SymmetrizeError::NotASymmetry { idx })?;
+
+                if distance >= tolerance {
+                    return Err(SymmetrizeError::NotASymmetry { idx });
+                }
+                map.push(j);
+            }
+
+            isometries.push(orthogonalize(isometry));
+            maps.push(map);
+        }
+
+        self.average_vertices(&isometries, &maps);
+        Ok(())
+    }
+
+    /// Summarizes which elements of the polytope its symmetries act
+    /// transitively on.
+    ///
+    /// This uses the geometric symmetry group whenever the polytope is
+    /// full-dimensional, and falls back to the abstract automorphism group
+    /// otherwise.
+    pub fn transitivity_report(&mut self) -> TransitivityReport {
+        self.element_sort();
+        let rank = self.rank();
+        let flag_count = self.abs.flag_count();
+
+        // The maps induced on the elements from the vertices to the facets.
+        let mut maps: Vec<Vec<Vec<usize>>> = Vec::new();
+        let full_dim = self.is_full_dimensional();
+
+        let combinatorial = if full_dim {
+            // Elements are identified by their vertex sets.
+            let vertex_sets: Vec<Vec<Vec<usize>>> = (1..rank)
+                .map(|r| {
+                    (0..self.el_count(r))
+                        .map(|idx| {
+                            let mut set = self.abs.element_vertices(r, idx).unwrap();
+                            set.sort_unstable();
+                            set
+                        })
+                        .collect()
+                })
+                .collect();
+            let element_of: Vec<HashMap<_, _>> = vertex_sets
+                .iter()
+                .map(|sets| sets.iter().cloned().zip(0..).collect())
+                .collect();
+
+            // We skip any isometry that maps the vertices but not the other
+            // elements into the polytope.
+            'rows: for row in self.get_symmetry_group().1 {
+                let mut map = Vec::new();
+                for (sets, element_of) in vertex_sets.iter().zip(&element_of) {
+                    let mut map_row = Vec::new();
+                    for set in sets {
+                        let mut image: Vec<_> = set.iter().map(|&v| row[v]).collect();
+                        image.sort_unstable();
+                        match element_of.get(&image) {
+                            Some(&idx) => map_row.push(idx),
+                            None => continue 'rows,
+                        }
+                    }
+                    map.push(map_row);
+                }
+                maps.push(map);
+            }
+
+            false
+        } else {
+            for map in self.abs.automorphisms() {
+                maps.push(
+                    map.into_iter()
+                        .skip(1)
+                        .take(rank.saturating_sub(1))
+                        .collect(),
+                );
+            }
+
+            true
+        };
+
+        let orbits = (0..rank.saturating_sub(1))
+            .map(|r| {
+                let mut found = vec![false; self.el_count(r + 1)];
+                let mut count = 0;
+                for idx in 0..found.len() {
+                    if !found[idx] {
+                        count += 1;
+                        for map in &maps {
+                            found[map[r][idx]] = true;
+                        }
+                    }
+                }
+                count
+            })
+            .collect();
+
+        // Symmetries act freely on flags.
+        let order = maps.len();
+        TransitivityReport {
+            orbits,
+            flag_orbits: flag_count / order.max(1),
+            order,
+            combinatorial,
+        }
+    }
+
+    /// Returns the permutations induced on the elements of a given rank by the
+    /// symmetries of the polytope, or by only its rotations. Each permutation
+    /// sends the `i`-th element to the `perm[i]`-th one.
+    ///
+    /// As in [`Self::transitivity_report`], the abstract automorphism group is
+    /// used when the polytope isn't full-dimensional, in which case there's no
+    /// rotations to single out.
+    pub fn element_permutations(&mut self, rank: usize, chiral: bool) -> Vec<Vec<usize>> {
+        if !self.is_full_dimensional() {
+            return self
+                .abs
+                .automorphisms()
+                .into_iter()
+                .map(|map| map.into_iter().nth(rank).unwrap_or_default())
+                .collect();
+        }
+
+        let vertex_maps = if chiral {
+            self.get_rotation_group().1
+        } else {
+            self.get_symmetry_group().1
+        };
+
+        // Elements are identified by their vertex sets.
+        let vertex_sets: Vec<_> = (0..self.el_count(rank))
+            .map(|idx| {
+                let mut set = self.abs.element_vertices(rank, idx).unwrap();
+                set.sort_unstable();
+                set
+            })
+            .collect();
+        let element_of: HashMap<_, _> = vertex_sets.iter().cloned().zip(0..).collect();
+
+        // We skip any isometry that maps the vertices but not these elements
+        // into the polytope.
+        vertex_maps
+            .into_iter()
+            .filter_map(|row| {
+                vertex_sets
+                    .iter()
+                    .map(|set| {
+                        let mut image: Vec<_> = set.iter().map(|&v| row[v]).collect();
+                        image.sort_unstable();
+                        element_of.get(&image).copied()
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns the cycle index of the action of the symmetries of the
+    /// polytope, or of only its rotations, on the elements of a given rank.
+    /// This counts the colorings of these elements up to symmetry.
+    pub fn cycle_index(&mut self, rank: usize, chiral: bool) -> CycleIndex {
+        CycleIndex::new(self.element_permutations(rank, chiral))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abs::Element;
+
+    use std::collections::HashSet;
+
+    /// Checks the transitivity of a few polytopes.
+    #[test]
+    fn transitivity() {
+        let report = Concrete::cube().transitivity_report();
+        assert_eq!(report.order, 48);
+        assert!(report.is_regular() && report.is_noble());
+        assert!(!report.combinatorial);
+
+        let report = Concrete::polygon(4).pyramid().transitivity_report();
+        assert_eq!(report.orbits, vec![2, 2, 2]);
+        assert!(!report.is_isogonal() && !report.is_isotoxal() && !report.is_isohedral());
+
+        let report = Concrete::polygon(6).prism().transitivity_report();
+        assert!(report.is_isogonal());
+        assert!(!report.is_isohedral() && !report.is_regular());
+    }
+
+    /// Checks that degenerate polytopes fall back to the abstract automorphism
+    /// group.
+    #[test]
+    fn transitivity_combinatorial() {
+        let mut square = Concrete::polygon(4);
+        square.vertices.iter_mut().for_each(|v| v[1] = 0.0);
+        let report = square.transitivity_report();
+
+        assert!(report.combinatorial);
+        assert_eq!(report.order, 8);
+        assert!(report.is_regular());
+    }
+
+    /// Moves the vertices of a polytope by small, fixed amounts.
+    fn drift(p: &mut Concrete, amount: f64) {
+        for (i, v) in p.vertices.iter_mut().enumerate() {
+            for (j, x) in v.iter_mut().enumerate() {
+                *x += amount * ((3 * i + j) % 5) as f64;
+            }
+        }
+    }
+
+    /// Checks that a cube is centered, with all of its vertices at the same
+    /// distance from the center and all of its edges of the same length. The
+    /// cube may have been slightly rotated.
+    fn assert_ideal_cube(cube: &Concrete) {
+        let spread = |lengths: Vec<f64>| {
+            let max = lengths.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            let min = lengths.iter().copied().fold(f64::INFINITY, f64::min);
+            max - min
+        };
+
+        assert!(cube.gravicenter().unwrap().norm() < 1e-12);
+        assert!(spread(cube.vertices.iter().map(|v| v.norm()).collect()) < 1e-12);
+
+        let edge_length =
+            |edge: &Element| (&cube.vertices[edge.subs[0]] - &cube.vertices[edge.subs[1]]).norm();
+        assert!(spread(cube[2].iter().map(edge_length).collect()) < 1e-12);
+    }
+
+    /// Returns the number of orbits of the subsets of every size of some
+    /// elements under some permutations, by going through all of them.
+    fn subset_orbits(perms: &[Vec<usize>]) -> Vec<u64> {
+        let n = perms[0].len();
+        let mut found = HashSet::new();
+        let mut counts = vec![0; n + 1];
+
+        for subset in 0..1u32 << n {
+            if found.contains(&subset) {
+                continue;
+            }
+
+            counts[subset.count_ones() as usize] += 1;
+            for perm in perms {
+                let image = (0..n)
+                    .filter(|&i| subset & (1 << i) != 0)
+                    .fold(0, |acc, i| acc | 1 << perm[i]);
+                found.insert(image);
+            }
+        }
+
+        counts
+    }
+
+    /// Checks the counts of colorings of a cube against those found by brute
+    /// force.
+    #[test]
+    fn colorings() {
+        let mut cube = Concrete::cube();
+
+        for &chiral in &[false, true] {
+            for rank in 1..=3 {
+                let perms = cube.element_permutations(rank, chiral);
+                let index = cube.cycle_index(rank, chiral);
+                assert_eq!(index.order(), if chiral { 24 } else { 48 });
+                assert_eq!(index.orbit_count(), 1);
+
+                let counts = subset_orbits(&perms);
+                for (size, &count) in counts.iter().enumerate() {
+                    assert_eq!(index.subsets(size).to_u64(), Some(count));
+                }
+                assert_eq!(index.colorings(2).to_u64(), Some(counts.iter().sum()));
+            }
+        }
+
+        // The classical counts for the vertices and faces of a cube.
+        let index = cube.cycle_index(1, false);
+        assert_eq!(index.colorings(2).to_u64(), Some(22));
+        assert_eq!(index.subsets(4).to_u64(), Some(6));
+        assert_eq!(cube.cycle_index(1, true).subsets(4).to_u64(), Some(7));
+        assert_eq!(cube.cycle_index(3, true).colorings(3).to_u64(), Some(57));
+    }
+
+    /// Checks that symmetrizing a cube removes any drift.
+    #[test]
+    fn symmetrize() {
+        let mut cube = Concrete::cube();
+        drift(&mut cube, 1e-9);
+        cube.symmetrize().unwrap();
+        assert_ideal_cube(&cube);
+        assert_eq!(
+            cube.abs.el_count_iter().collect::<Vec<_>>(),
+            vec![1, 8, 12, 6, 1]
+        );
+
+        // Drift too large to detect the symmetries can still be removed with a
+        // known group.
+        let mut cube = Concrete::cube();
+        drift(&mut cube, 1e-3);
+        cube.symmetrize_with(Group::hypercube(3)).unwrap();
+        assert_ideal_cube(&cube);
+    }
+
+    /// Checks that symmetrizing fails when it should.
+    #[test]
+    fn symmetrize_errors() {
+        let mut square = Concrete::polygon(4);
+        square.vertices.iter_mut().for_each(|v| v[1] = 0.0);
+        assert_eq!(
+            square.symmetrize(),
+            Err(SymmetrizeError::NotFullDimensional)
+        );
+
+        let mut cube = Concrete::cube();
+        assert_eq!(
+            cube.symmetrize_with(Group::hypercube(4)),
+            Err(SymmetrizeError::WrongDimension {
+                expected: 3,
+                found: 4
+            })
+        );
+
+        let mut rectangle =
+            Concrete::duoprism(&Concrete::dyad_with(1.0), &Concrete::dyad_with(2.0));
+        assert_eq!(
+            rectangle.symmetrize_with(Group::hypercube(2)),
+            Err(SymmetrizeError::NotASymmetry { idx: 1 })
+        );
+    }
+}
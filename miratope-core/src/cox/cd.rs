@@ -448,12 +448,9 @@ impl Cd {
     /// Returns a point in the position specified by the Coxeter diagram,
     /// using the set of mirrors generated by [`Cox::normals`].    
     pub fn generator(&self) -> Option<Point<f64>> {
-        let mut vector = self.node_vector();
-
-        self.cox()
-            .normals()?
-            .solve_upper_triangular_mut(&mut vector)
-            .then(|| vector)
+        // Each node value is twice the distance to the corresponding mirror.
+        let distances = self.node_vector() / 2.0;
+        self.cox().point_at_distances(distances.as_slice())
     }
 }
 
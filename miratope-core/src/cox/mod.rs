@@ -4,13 +4,17 @@ pub mod cd;
 pub mod parse;
 
 use std::{
+    collections::BTreeSet,
     iter,
     ops::{Index, IndexMut},
 };
 
 use crate::float::Float;
 use crate::group::Group;
-use crate::{geometry::Matrix, group::GenIter};
+use crate::{
+    geometry::{Matrix, Point, PointOrd},
+    group::GenIter,
+};
 
 use nalgebra::dmatrix;
 
@@ -141,7 +145,9 @@ impl Cox<f64> {
     }
 
     /// Returns an upper triangular matrix whose columns are unit normal vectors
-    /// for the hyperplanes described by the Coxeter matrix.
+    /// for the hyperplanes described by the Coxeter matrix. Two normals whose
+    /// mirrors meet at an angle of π / m have a dot product of -cos(π / m), so
+    /// that they point out of a common fundamental region.
     pub fn normals(&self) -> Option<Matrix<f64>> {
         let dim = self.dim();
         let mut mat = Matrix::zeros(dim, dim);
@@ -154,12 +160,12 @@ impl Cox<f64> {
             for (j, n_j) in prev_gens.column_iter().enumerate() {
                 // All other entries in the dot product between columns are zero.
                 let dot = n_i.rows_range(0..=j).dot(&n_j.rows_range(0..=j));
-                n_i[j] = ((f64::PI / self[(i, j)]).fcos() - dot) / n_j[j];
+                n_i[j] = (-(f64::PI / self[(i, j)]).fcos() - dot) / n_j[j];
             }
 
             // If the vector doesn't fit in spherical space.
             let norm_sq: f64 = n_i.norm_squared();
-            if norm_sq >= 1.0 - f64::EPS {
+            if norm_sq >= 1.0 - f64::eps() {
                 return None;
             } else {
                 n_i[i] = (1.0 - norm_sq).fsqrt();
@@ -193,6 +199,58 @@ impl Cox<f64> {
         ))
     }
 
+    /// Returns the point whose distance to the `i`-th mirror from
+    /// [`Self::normals`] is the `i`-th entry of `distances`. Any point in the
+    /// fundamental region can be written like this, and its orbit gives an
+    /// isogonal polytope, which is uniform when all distances are 0 or 1/2.
+    ///
+    /// Returns `None` if the group isn't finite, or if the number of
+    /// distances doesn't match the number of mirrors.
+    pub fn point_at_distances(&self, distances: &[f64]) -> Option<Point<f64>> {
+        if distances.len() != self.dim() {
+            return None;
+        }
+
+        let mut vector = Point::from_column_slice(distances);
+        if self
+            .normals()?
+            .transpose()
+            .solve_lower_triangular_mut(&mut vector)
+        {
+            Some(vector)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the orbit of a point under the Coxeter group, or `None` if the
+    /// group isn't finite.
+    ///
+    /// The orbit is found by reflecting the point through the mirrors until no
+    /// new points come up. Unlike going through [`Self::group`], this only
+    /// takes time proportional to the size of the orbit.
+    pub fn orbit(&self, p: &Point<f64>) -> Option<Vec<Point<f64>>> {
+        let normals = self.normals()?;
+        let mut found = BTreeSet::new();
+        found.insert(PointOrd::new(p.clone()));
+        let mut orbit = vec![p.clone()];
+
+        let mut idx = 0;
+        while let Some(q) = orbit.get(idx).cloned() {
+            for n in normals.column_iter() {
+                let r = &q - n * (2.0 * q.dot(&n));
+
+                if found.insert(PointOrd::new(r.clone())) {
+                    orbit.push(r);
+                }
+            }
+
+            idx += 1;
+        }
+
+        Some(orbit)
+    }
+
     /// Returns the associated Coxeter [`Group`].
     pub fn group(&self) -> Option<Group<GenIter<Matrix<f64>>>> {
         self.gen_iter().map(Into::into)
@@ -0,0 +1,140 @@
+//! Contains [`MiratopeError`], the error type used to report any failure of an
+//! operation requested by the user, together with the context in which it
+//! happened.
+
+use std::path::{Path, PathBuf};
+
+/// Any error caused by an operation requested by the user, such as loading a
+/// file or taking the dual of a polytope.
+///
+/// The original errors come in many different types, some of which borrow
+/// data, so we only store their messages.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MiratopeError {
+    /// A polytope couldn't be loaded from a file.
+    Load {
+        /// The path to the file.
+        path: PathBuf,
+
+        /// The reason the file couldn't be loaded.
+        reason: String,
+    },
+
+    /// A polytope or an image couldn't be saved to a file.
+    Save {
+        /// The path to the file.
+        path: PathBuf,
+
+        /// The reason the file couldn't be saved.
+        reason: String,
+    },
+
+    /// An operation on a polytope failed.
+    Operation {
+        /// The name of the operation.
+        operation: &'static str,
+
+        /// The reason the operation failed, if known.
+        reason: Option<String>,
+    },
+}
+
+impl MiratopeError {
+    /// An error loading the file at a given path.
+    pub fn load<P: AsRef<Path>, E: std::fmt::Display>(path: P, err: E) -> Self {
+        Self::Load {
+            path: path.as_ref().to_path_buf(),
+            reason: err.to_string(),
+        }
+    }
+
+    /// An error saving to the file at a given path.
+    pub fn save<P: AsRef<Path>, E: std::fmt::Display>(path: P, err: E) -> Self {
+        Self::Save {
+            path: path.as_ref().to_path_buf(),
+            reason: err.to_string(),
+        }
+    }
+
+    /// An operation that failed for a given reason.
+    pub fn operation<E: std::fmt::Display>(operation: &'static str, err: E) -> Self {
+        Self::Operation {
+            operation,
+            reason: Some(err.to_string()),
+        }
+    }
+
+    /// An operation that failed for an unknown reason.
+    pub fn failed(operation: &'static str) -> Self {
+        Self::Operation {
+            operation,
+            reason: None,
+        }
+    }
+
+    /// A short title for the error, without its reason.
+    pub fn title(&self) -> String {
+        match self {
+            Self::Load { path, .. } => format!("Could not load {}", path.display()),
+            Self::Save { path, .. } => format!("Could not save {}", path.display()),
+            Self::Operation { operation, .. } => format!("{} failed", operation),
+        }
+    }
+
+    /// The reason for the error, if known.
+    pub fn reason(&self) -> Option<&str> {
+        match self {
+            Self::Load { reason, .. } | Self::Save { reason, .. } => Some(reason),
+            Self::Operation { reason, .. } => reason.as_deref(),
+        }
+    }
+}
+
+impl std::fmt::Display for MiratopeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.reason() {
+            Some(reason) => write!(f, "{}: {}", self.title(), reason),
+            None => write!(f, "{}", self.title()),
+        }
+    }
+}
+
+impl std::error::Error for MiratopeError {}
+
+/// The result of an operation requested by the user.
+pub type MiratopeResult<T> = Result<T, MiratopeError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{conc::Concrete, file::FromFile};
+
+    /// Checks that errors keep the context they were created in.
+    #[test]
+    fn display() {
+        assert_eq!(
+            MiratopeError::failed("Petrial").to_string(),
+            "Petrial failed"
+        );
+        assert_eq!(
+            MiratopeError::operation("Antiprism", "facet 3 passes through inversion center")
+                .to_string(),
+            "Antiprism failed: facet 3 passes through inversion center"
+        );
+        assert_eq!(
+            MiratopeError::save("out.off", "permission denied").to_string(),
+            "Could not save out.off: permission denied"
+        );
+    }
+
+    /// Checks that a bad OFF file gives a load error.
+    #[test]
+    fn load() {
+        let err = Concrete::from_off("OFF\n3 1 0\n0 0\n")
+            .map_err(|err| MiratopeError::load("bad.off", err))
+            .unwrap_err();
+
+        assert_eq!(err.title(), "Could not load bad.off");
+        assert!(err.reason().is_some());
+    }
+}
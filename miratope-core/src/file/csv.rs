@@ -0,0 +1,215 @@
+//! Reads plain lists of coordinates, like CSV or TSV files, as point clouds.
+//!
+//! Every line holds the coordinates of a single point, separated by commas,
+//! semicolons, tabs or spaces. Blank lines and lines starting with `#` are
+//! skipped, and so is a header on the first line, as long as none of its
+//! entries are numbers. Lines with commas or semicolons can't have empty
+//! entries between them.
+
+use std::fmt::Display;
+
+use crate::geometry::Point;
+
+/// Any error encountered while reading a list of coordinates.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CsvError {
+    /// An entry on a given line isn't a number.
+    Number {
+        /// The line number, starting at 1.
+        line: usize,
+
+        /// The entry that couldn't be read.
+        value: String,
+    },
+
+    /// An entry on a given line is empty.
+    Empty {
+        /// The line number, starting at 1.
+        line: usize,
+
+        /// The position of the entry within the line, starting at 1.
+        column: usize,
+    },
+
+    /// An entry on a given line is infinite or NaN.
+    NonFinite {
+        /// The line number, starting at 1.
+        line: usize,
+
+        /// The entry that was read.
+        value: String,
+    },
+
+    /// A line has a different number of coordinates than the first point.
+    Length {
+        /// The line number, starting at 1.
+        line: usize,
+
+        /// The number of coordinates of the first point.
+        expected: usize,
+
+        /// The number of coordinates on the line.
+        found: usize,
+    },
+}
+
+impl Display for CsvError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number { line, value } => {
+                write!(f, "\"{}\" on line {} isn't a number", value, line)
+            }
+            Self::Empty { line, column } => {
+                write!(f, "entry {} on line {} is empty", column, line)
+            }
+            Self::NonFinite { line, value } => {
+                write!(f, "\"{}\" on line {} isn't finite", value, line)
+            }
+            Self::Length {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {} has {} coordinates, expected {}",
+                line, found, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CsvError {}
+
+/// The result of reading a list of coordinates.
+pub type CsvResult<T> = Result<T, CsvError>;
+
+/// Splits a line into its entries. If the line has any commas or semicolons,
+/// these are the only separators, and the entries between them may be empty.
+/// Otherwise, the entries are separated by whitespace.
+fn entries(line: &str) -> Box<dyn Iterator<Item = &str> + '_> {
+    if line.contains([',', ';']) {
+        Box::new(line.split([',', ';']).map(str::trim))
+    } else {
+        Box::new(line.split_whitespace())
+    }
+}
+
+/// Reads a list of points, one per line.
+pub fn parse_points(src: &str) -> CsvResult<Vec<Point<f64>>> {
+    let mut points: Vec<Point<f64>> = Vec::new();
+    let mut first = true;
+
+    for (idx, line) in src.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        // Skips the header, if any.
+        let header = first && entries(line).all(|s| s.parse::<f64>().is_err());
+        first = false;
+        if header {
+            continue;
+        }
+
+        let coords = entries(line)
+            .enumerate()
+            .map(|(column, s)| {
+                if s.is_empty() {
+                    return Err(CsvError::Empty {
+                        line: idx + 1,
+                        column: column + 1,
+                    });
+                }
+
+                match s.parse::<f64>() {
+                    Ok(x) if x.is_finite() => Ok(x),
+                    Ok(_) => Err(CsvError::NonFinite {
+                        line: idx + 1,
+                        value: s.to_string(),
+                    }),
+                    Err(_) => Err(CsvError::Number {
+                        line: idx + 1,
+                        value: s.to_string(),
+                    }),
+                }
+            })
+            .collect::<CsvResult<Vec<f64>>>()?;
+
+        if let Some(p) = points.first() {
+            if p.len() != coords.len() {
+                return Err(CsvError::Length {
+                    line: idx + 1,
+                    expected: p.len(),
+                    found: coords.len(),
+                });
+            }
+        }
+
+        points.push(coords.into());
+    }
+
+    Ok(points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reads some lists of points.
+    #[test]
+    fn parse() {
+        let points = parse_points("x,y,z\n1,0,0\n\n# A comment.\n0;1;0\n0\t0 1\n").unwrap();
+        assert_eq!(
+            points,
+            vec![
+                Point::from_vec(vec![1.0, 0.0, 0.0]),
+                Point::from_vec(vec![0.0, 1.0, 0.0]),
+                Point::from_vec(vec![0.0, 0.0, 1.0]),
+            ]
+        );
+        assert!(parse_points("").unwrap().is_empty());
+    }
+
+    /// Checks that malformed lists give errors.
+    #[test]
+    fn errors() {
+        assert_eq!(
+            parse_points("1, 2\n3, x"),
+            Err(CsvError::Number {
+                line: 2,
+                value: "x".to_string()
+            })
+        );
+        assert_eq!(
+            parse_points("1,,2"),
+            Err(CsvError::Empty { line: 1, column: 2 })
+        );
+        assert_eq!(
+            parse_points("x;y\n1; 2;"),
+            Err(CsvError::Empty { line: 2, column: 3 })
+        );
+        assert_eq!(
+            parse_points("1 2\n3 inf"),
+            Err(CsvError::NonFinite {
+                line: 2,
+                value: "inf".to_string()
+            })
+        );
+        assert_eq!(
+            parse_points("NaN, 0"),
+            Err(CsvError::NonFinite {
+                line: 1,
+                value: "NaN".to_string()
+            })
+        );
+        assert_eq!(
+            parse_points("1 2\n\n3 4 5"),
+            Err(CsvError::Length {
+                line: 3,
+                expected: 2,
+                found: 3
+            })
+        );
+    }
+}
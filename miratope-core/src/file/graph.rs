@@ -0,0 +1,364 @@
+//! Writes the flag graph or the Hasse diagram of a polytope in the DOT and
+//! GraphML formats, so that they can be studied in external graph tools.
+
+use std::{fmt::Write, path::Path};
+
+use crate::abs::{Abstract, Ranked};
+
+use vec_like::VecLike;
+
+/// The colors used for the edges of a flag graph in a DOT file, in order of
+/// the flag change they represent.
+const COLORS: [&str; 8] = [
+    "red", "blue", "green", "orange", "purple", "cyan", "magenta", "brown",
+];
+
+/// The formats a graph can be written in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GraphFormat {
+    /// The DOT format, as used by Graphviz.
+    Dot,
+
+    /// The XML-based GraphML format.
+    GraphMl,
+}
+
+impl GraphFormat {
+    /// Returns the usual extension of a file in this format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Dot => "dot",
+            Self::GraphMl => "graphml",
+        }
+    }
+
+    /// Returns the format with a given file extension, if any.
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        match ext {
+            "dot" | "gv" => Some(Self::Dot),
+            "graphml" => Some(Self::GraphMl),
+            _ => None,
+        }
+    }
+}
+
+/// A node in a [`Graph`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Node {
+    /// The label of the node.
+    pub label: String,
+
+    /// The rank of the element the node represents, using the usual
+    /// mathematical convention that the nullitope has rank -1. This is only
+    /// set for the nodes of a Hasse diagram.
+    pub rank: Option<isize>,
+}
+
+/// An edge in a [`Graph`], between two nodes given by their indices.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Edge {
+    /// The index of the first node. In a directed graph, the edge goes out of
+    /// this node.
+    pub source: usize,
+
+    /// The index of the second node.
+    pub target: usize,
+
+    /// The color of the edge. In a flag graph, this is the rank of the
+    /// element in which the two flags differ, using the usual mathematical
+    /// convention for ranks.
+    pub color: Option<usize>,
+}
+
+/// A graph built from a polytope, ready to be written into a file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Graph {
+    /// The name of the graph.
+    pub name: &'static str,
+
+    /// Whether the edges of the graph have a direction.
+    pub directed: bool,
+
+    /// The nodes of the graph.
+    pub nodes: Vec<Node>,
+
+    /// The edges of the graph.
+    pub edges: Vec<Edge>,
+}
+
+impl Graph {
+    /// Returns the flag graph of a polytope. Every flag is labeled by the
+    /// indices of its proper elements, from the vertex upwards.
+    pub fn flag_graph(poly: &Abstract) -> Self {
+        let graph = poly.flag_graph();
+        let rank = poly.rank();
+
+        let nodes = graph
+            .flags
+            .iter()
+            .map(|flag| Node {
+                label: flag
+                    .iter()
+                    .take(rank)
+                    .skip(1)
+                    .map(usize::to_string)
+                    .collect::<Vec<_>>()
+                    .join(","),
+                rank: None,
+            })
+            .collect();
+
+        let mut edges = Vec::new();
+        for (source, neighbors) in graph.adj.iter().enumerate() {
+            for (color, &target) in neighbors.iter().enumerate() {
+                if source < target {
+                    edges.push(Edge {
+                        source,
+                        target,
+                        color: Some(color),
+                    });
+                }
+            }
+        }
+
+        Self {
+            name: "flag_graph",
+            directed: false,
+            nodes,
+            edges,
+        }
+    }
+
+    /// Returns the Hasse diagram of the face lattice of a polytope. Every
+    /// element is labeled by its rank and its index, and every edge goes from
+    /// an element to one of its superelements.
+    pub fn hasse_diagram(poly: &Abstract) -> Self {
+        // The index of the first node of every rank.
+        let mut offsets = Vec::with_capacity(poly.rank() + 1);
+        let mut nodes = Vec::new();
+
+        for (r, elements) in poly.iter().enumerate() {
+            offsets.push(nodes.len());
+            let rank = r as isize - 1;
+
+            for idx in 0..elements.len() {
+                nodes.push(Node {
+                    label: format!("{}:{}", rank, idx),
+                    rank: Some(rank),
+                });
+            }
+        }
+
+        let mut edges = Vec::new();
+        for (r, elements) in poly.iter().enumerate().skip(1) {
+            for (idx, el) in elements.iter().enumerate() {
+                for &sub in &el.subs {
+                    edges.push(Edge {
+                        source: offsets[r - 1] + sub,
+                        target: offsets[r] + idx,
+                        color: None,
+                    });
+                }
+            }
+        }
+
+        Self {
+            name: "hasse_diagram",
+            directed: true,
+            nodes,
+            edges,
+        }
+    }
+
+    /// Writes the graph in the DOT format. Edges are colored by their color,
+    /// and nodes of the same rank are placed on the same level.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        let (keyword, arrow) = if self.directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+
+        // Writing into a string never fails.
+        writeln!(dot, "{} {} {{", keyword, self.name).unwrap();
+        if self.directed {
+            writeln!(dot, "    rankdir=BT;").unwrap();
+        }
+
+        for (idx, node) in self.nodes.iter().enumerate() {
+            writeln!(dot, "    {} [label=\"{}\"];", idx, node.label).unwrap();
+        }
+
+        // Groups the nodes by rank.
+        let mut ranks: Vec<(isize, Vec<usize>)> = Vec::new();
+        for (idx, node) in self.nodes.iter().enumerate() {
+            if let Some(rank) = node.rank {
+                match ranks.iter_mut().find(|(r, _)| *r == rank) {
+                    Some((_, nodes)) => nodes.push(idx),
+                    None => ranks.push((rank, vec![idx])),
+                }
+            }
+        }
+
+        for (_, nodes) in ranks {
+            let nodes: Vec<_> = nodes.iter().map(usize::to_string).collect();
+            writeln!(dot, "    {{ rank=same; {}; }}", nodes.join("; ")).unwrap();
+        }
+
+        for edge in &self.edges {
+            write!(dot, "    {} {} {}", edge.source, arrow, edge.target).unwrap();
+            if let Some(color) = edge.color {
+                write!(
+                    dot,
+                    " [label=\"{}\", color=\"{}\"]",
+                    color,
+                    COLORS[color % COLORS.len()]
+                )
+                .unwrap();
+            }
+            writeln!(dot, ";").unwrap();
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Writes the graph in the GraphML format. The labels and ranks of the
+    /// nodes and the colors of the edges are stored as attributes.
+    pub fn to_graphml(&self) -> String {
+        let mut xml = String::new();
+
+        xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+        xml.push_str(
+            "  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n",
+        );
+        xml.push_str("  <key id=\"rank\" for=\"node\" attr.name=\"rank\" attr.type=\"int\"/>\n");
+        xml.push_str("  <key id=\"color\" for=\"edge\" attr.name=\"color\" attr.type=\"int\"/>\n");
+
+        let edgedefault = if self.directed {
+            "directed"
+        } else {
+            "undirected"
+        };
+        writeln!(
+            xml,
+            "  <graph id=\"{}\" edgedefault=\"{}\">",
+            self.name, edgedefault
+        )
+        .unwrap();
+
+        for (idx, node) in self.nodes.iter().enumerate() {
+            write!(
+                xml,
+                "    <node id=\"n{}\"><data key=\"label\">{}</data>",
+                idx, node.label
+            )
+            .unwrap();
+            if let Some(rank) = node.rank {
+                write!(xml, "<data key=\"rank\">{}</data>", rank).unwrap();
+            }
+            xml.push_str("</node>\n");
+        }
+
+        for edge in &self.edges {
+            write!(
+                xml,
+                "    <edge source=\"n{}\" target=\"n{}\">",
+                edge.source, edge.target
+            )
+            .unwrap();
+            if let Some(color) = edge.color {
+                write!(xml, "<data key=\"color\">{}</data>", color).unwrap();
+            }
+            xml.push_str("</edge>\n");
+        }
+
+        xml.push_str("  </graph>\n</graphml>\n");
+        xml
+    }
+
+    /// Writes the graph in a given format.
+    pub fn to_format(&self, format: GraphFormat) -> String {
+        match format {
+            GraphFormat::Dot => self.to_dot(),
+            GraphFormat::GraphMl => self.to_graphml(),
+        }
+    }
+
+    /// Writes the graph in a specified file path, in the format given by its
+    /// extension. Defaults to the DOT format if the extension isn't
+    /// recognized.
+    pub fn to_path<P: AsRef<Path>>(&self, fp: P) -> std::io::Result<()> {
+        let format = fp
+            .as_ref()
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(GraphFormat::from_extension)
+            .unwrap_or(GraphFormat::Dot);
+
+        std::fs::write(fp, self.to_format(format))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Polytope;
+
+    /// Checks the flag graph of a triangle, which is a hexagon.
+    #[test]
+    fn triangle_flags() {
+        let graph = Graph::flag_graph(&Abstract::polygon(3));
+        assert_eq!(graph.nodes.len(), 6);
+        assert_eq!(graph.edges.len(), 6);
+        assert!(!graph.directed);
+
+        for color in 0..2 {
+            let count = graph
+                .edges
+                .iter()
+                .filter(|e| e.color == Some(color))
+                .count();
+            assert_eq!(count, 3);
+        }
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("graph flag_graph {"));
+        assert_eq!(dot.matches(" -- ").count(), 6);
+    }
+
+    /// Checks the Hasse diagram of a square.
+    #[test]
+    fn square_hasse() {
+        let graph = Graph::hasse_diagram(&Abstract::polygon(4));
+
+        // The nullitope, 4 vertices, 4 edges and the square.
+        assert_eq!(graph.nodes.len(), 10);
+        assert_eq!(graph.edges.len(), 4 + 8 + 4);
+        assert_eq!(graph.nodes[0].rank, Some(-1));
+        assert_eq!(graph.nodes[9].label, "2:0");
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph hasse_diagram {"));
+        assert_eq!(dot.matches("rank=same").count(), 4);
+
+        let xml = graph.to_graphml();
+        assert_eq!(xml.matches("<node ").count(), 10);
+        assert_eq!(xml.matches("<edge ").count(), 16);
+        assert!(xml.contains("edgedefault=\"directed\""));
+    }
+
+    /// Checks that formats are read from extensions.
+    #[test]
+    fn extensions() {
+        for &format in &[GraphFormat::Dot, GraphFormat::GraphMl] {
+            assert_eq!(
+                GraphFormat::from_extension(format.extension()),
+                Some(format)
+            );
+        }
+        assert_eq!(GraphFormat::from_extension("off"), None);
+    }
+}
@@ -1,19 +1,29 @@
 //! Reading from and writing to files in various different formats.
 
+pub mod csv;
 pub mod ggb;
+pub mod graph;
 pub mod off;
+pub mod periodic;
 
 use self::{
+    csv::{CsvError, CsvResult},
     ggb::{GgbError, GgbResult},
     off::{OffParseResult, OffReader},
+    periodic::{PoffError, PoffResult},
 };
-use crate::conc::Concrete;
+use crate::conc::{honeycomb::Honeycomb, Concrete};
 
 use off::OffParseError;
 use zip::result::ZipError;
 
 pub use std::io::Error as IoError;
-use std::{fs::File, string::FromUtf8Error};
+use std::{
+    fs::File,
+    io::{Cursor, Read, Seek},
+    str::Utf8Error,
+    string::FromUtf8Error,
+};
 
 /// Any error encountered while trying to load a polytope.
 #[derive(Debug)]
@@ -24,11 +34,17 @@ pub enum FileError<'a> {
     /// An error while reading a GGB file.
     GgbError(GgbError),
 
+    /// An error while reading a list of coordinates.
+    CsvError(CsvError),
+
+    /// An error while reading a periodic OFF file.
+    PoffError(PoffError),
+
     /// Some generic I/O error occured.
     IoError(IoError),
 
     /// The file couldn't be parsed as UTF-8.
-    InvalidFile(FromUtf8Error),
+    InvalidFile(Utf8Error),
 
     /// An error while opening the GGB file (which is really a ZIP file in
     /// disguise).
@@ -43,6 +59,8 @@ impl<'a> std::fmt::Display for FileError<'a> {
         match self {
             Self::OffError(err) => write!(f, "OFF error: {}", err),
             Self::GgbError(err) => write!(f, "GGB error: {}", err),
+            Self::CsvError(err) => write!(f, "CSV error: {}", err),
+            Self::PoffError(err) => write!(f, "periodic OFF error: {}", err),
             Self::IoError(err) => write!(f, "IO error: {}", err),
             Self::ZipError(err) => write!(f, "ZIP error while opening GGB: {}", err),
             Self::InvalidFile(err) => write!(f, "invalid file: {}", err),
@@ -67,10 +85,31 @@ impl<'a> From<GgbError> for FileError<'a> {
     }
 }
 
+/// [`CsvError`] is a type of [`FileError`].
+impl<'a> From<CsvError> for FileError<'a> {
+    fn from(err: CsvError) -> Self {
+        Self::CsvError(err)
+    }
+}
+
+/// [`PoffError`] is a type of [`FileError`].
+impl<'a> From<PoffError> for FileError<'a> {
+    fn from(err: PoffError) -> Self {
+        Self::PoffError(err)
+    }
+}
+
+/// [`Utf8Error`] is a type of [`FileError`].
+impl<'a> From<Utf8Error> for FileError<'a> {
+    fn from(err: Utf8Error) -> Self {
+        Self::InvalidFile(err)
+    }
+}
+
 /// [`FromUtf8Error`] is a type of [`FileError`].
 impl<'a> From<FromUtf8Error> for FileError<'a> {
     fn from(err: FromUtf8Error) -> Self {
-        Self::InvalidFile(err)
+        Self::InvalidFile(err.utf8_error())
     }
 }
 
@@ -101,7 +140,37 @@ pub trait FromFile: Sized {
 
     /// Attempts to read a GGB file. If succesful, outputs a polytope in at most
     /// 3D.
-    fn from_ggb(file: File) -> GgbResult<Self>;
+    fn from_ggb<R: Read + Seek>(file: R) -> GgbResult<Self>;
+
+    /// Reads a list of coordinates as a point cloud, with no elements other
+    /// than its vertices.
+    fn from_csv(src: &str) -> CsvResult<Self>;
+
+    /// Reads a periodic OFF file, and builds a finite patch of the tiling or
+    /// honeycomb it describes.
+    fn from_poff(src: &str) -> PoffResult<Self>;
+
+    /// Loads a polytope from the contents of a file, given its extension. This
+    /// doesn't touch the file system, so it also works on targets that don't
+    /// have one, like the web.
+    fn from_bytes<'a>(bytes: &[u8], ext: &'a str) -> FileResult<'a, Self> {
+        match ext {
+            // Reads the file as an OFF file.
+            "off" => Ok(Self::from_off(std::str::from_utf8(bytes)?)?),
+
+            // Reads the file as a GGB file.
+            "ggb" => Ok(Self::from_ggb(Cursor::new(bytes))?),
+
+            // Reads the file as a list of coordinates.
+            "csv" | "tsv" => Ok(Self::from_csv(std::str::from_utf8(bytes)?)?),
+
+            // Reads the file as a periodic OFF file.
+            "poff" => Ok(Self::from_poff(std::str::from_utf8(bytes)?)?),
+
+            // Could not recognize the file extension.
+            ext => Err(FileError::InvalidExtension(ext)),
+        }
+    }
 
     /// Loads a polytope from a file path.
     fn from_path<U: AsRef<std::path::Path>>(fp: &U) -> FileResult<'_, Self> {
@@ -114,17 +183,9 @@ pub trait FromFile: Sized {
             .unwrap_or_default();
 
         match ext {
-            // Reads the file as an OFF file.
-            "off" => match String::from_utf8(fs::read(fp)?) {
-                Ok(src) => Ok(Self::from_off(&src)?),
-                Err(err) => Err(err.into()),
-            },
-
-            // Reads the file as a GGB file.
-            "ggb" => Ok(Self::from_ggb(fs::File::open(fp)?)?),
-
-            // Could not recognize the file extension.
-            ext => Err(FileError::InvalidExtension(ext)),
+            // Reads the file as a GGB file, without loading it all at once.
+            "ggb" => Ok(Self::from_ggb(File::open(fp)?)?),
+            ext => Self::from_bytes(&fs::read(fp)?, ext),
         }
     }
 }
@@ -136,9 +197,7 @@ impl FromFile for Concrete {
 
     /// Attempts to read a GGB file. If succesful, outputs a polytope in at most
     /// 3D.
-    fn from_ggb(mut file: File) -> GgbResult<Self> {
-        use std::io::Read;
-
+    fn from_ggb<R: Read + Seek>(mut file: R) -> GgbResult<Self> {
         if let Ok(xml) = String::from_utf8(
             zip::read::ZipArchive::new(&mut file)?
                 .by_name("geogebra.xml")?
@@ -151,4 +210,61 @@ impl FromFile for Concrete {
             Err(GgbError::InvalidGgb)
         }
     }
+
+    fn from_csv(src: &str) -> CsvResult<Self> {
+        Ok(Self::point_cloud(csv::parse_points(src)?))
+    }
+
+    /// Reads a periodic OFF file, and builds a patch with
+    /// [`PATCH_COPIES`](periodic::PATCH_COPIES) copies of the fundamental
+    /// domain along every translation.
+    fn from_poff(src: &str) -> PoffResult<Self> {
+        Honeycomb::from_poff(src)?
+            .patch(periodic::PATCH_COPIES)
+            .ok_or(PoffError::Patch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+
+    /// Checks that polytopes can be loaded from the contents of a file.
+    #[test]
+    fn from_bytes() {
+        let src = b"OFF\n4 4 6\n1 1 1\n1 -1 -1\n-1 1 -1\n-1 -1 1\n\
+            3 0 1 2\n3 0 1 3\n3 0 2 3\n3 1 2 3";
+        let tetrahedron = Concrete::from_bytes(src, "off").unwrap();
+        test(&tetrahedron, [1, 4, 6, 4, 1]);
+
+        assert!(matches!(
+            Concrete::from_bytes(b"OFF\xff", "off"),
+            Err(FileError::InvalidFile(_))
+        ));
+        assert!(matches!(
+            Concrete::from_bytes(b"", "obj"),
+            Err(FileError::InvalidExtension("obj"))
+        ));
+
+        let cloud = Concrete::from_bytes(b"x\ty\n0\t0\n1\t0\n0\t1", "tsv").unwrap();
+        test(&cloud, [1, 3, 1]);
+        assert!(matches!(
+            Concrete::from_bytes(b"0, 0\n1", "csv"),
+            Err(FileError::CsvError(_))
+        ));
+        assert!(matches!(
+            Concrete::from_bytes(b"not a zip file", "ggb"),
+            Err(FileError::GgbError(_))
+        ));
+
+        let tiling = Concrete::from_bytes(b"2POFF 2 0 0  1 0 0 1", "poff");
+        assert!(matches!(
+            tiling,
+            Err(FileError::PoffError(PoffError::Patch))
+        ));
+        let tiling =
+            Concrete::from_bytes(b"2POFF 2 4 1  1 0 0 1  0 0 1 0 0 1 1 1  4 0 1 2 3", "poff");
+        test(&tiling.unwrap(), [1, 16, 24, 9, 1]);
+    }
 }
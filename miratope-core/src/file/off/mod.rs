@@ -3,8 +3,12 @@
 use std::{collections::HashMap, fmt::Display, io::Error as IoError, path::Path, str::FromStr};
 
 use crate::{
-    abs::{AbstractBuilder, Ranked, SubelementList, Subelements},
-    conc::{cycle::CycleList, Concrete},
+    abs::{AbstractBuilder, ElementList, Ranked, SubelementList, Subelements},
+    conc::{
+        color::{Color, ColorData, ColorScheme},
+        cycle::CycleList,
+        Concrete,
+    },
     geometry::Point,
     Polytope, COMPONENTS, ELEMENT_NAMES,
 };
@@ -64,6 +68,66 @@ pub enum OffParseError {
 
     /// Didn't find the OFF magic word.
     MagicWord(Position),
+
+    /// An element had no subelements.
+    EmptyElement(Position),
+
+    /// An element referred to a subelement that doesn't exist.
+    Index {
+        /// The position of the index.
+        pos: Position,
+
+        /// The index that was read.
+        idx: usize,
+
+        /// The number of subelements that could've been referred to.
+        count: usize,
+    },
+
+    /// The section with the facets was omitted, so the polytope can't be
+    /// rebuilt.
+    MissingFacets,
+
+    /// Some sections were omitted, and the sections around them don't
+    /// determine them.
+    OmittedSections,
+}
+
+impl OffParseError {
+    /// Returns the position in the file where the error was found, if any.
+    pub fn position(&self) -> Option<Position> {
+        match *self {
+            Self::UnexpectedEnding(pos)
+            | Self::Parsing(pos)
+            | Self::Rank(pos)
+            | Self::MagicWord(pos)
+            | Self::EmptyElement(pos)
+            | Self::Index { pos, .. } => Some(pos),
+            Self::Empty | Self::MissingFacets | Self::OmittedSections => None,
+        }
+    }
+
+    /// Describes the error together with the line of the file where it was
+    /// found, pointing out the column.
+    pub fn diagnostic(&self, src: &str) -> String {
+        let mut diagnostic = self.to_string();
+
+        if let Some(pos) = self.position() {
+            if let Some(line) = src.lines().nth(pos.row as usize) {
+                let row = (pos.row + 1).to_string();
+                let margin = " ".repeat(row.len());
+
+                diagnostic.push_str(&format!("\n{} | {}", row, line.trim_end()));
+                diagnostic.push_str(&format!(
+                    "\n{} | {}^",
+                    margin,
+                    " ".repeat(pos.column as usize)
+                ));
+            }
+        }
+
+        diagnostic
+    }
 }
 
 impl Display for OffParseError {
@@ -74,6 +138,14 @@ impl Display for OffParseError {
             Self::Parsing(pos) => write!(f, "could not parse number at {}", pos),
             Self::Rank(pos) => write!(f, "could not read rank at {}", pos),
             Self::MagicWord(pos) => write!(f, "no \"OFF\" detected at {}", pos),
+            Self::EmptyElement(pos) => write!(f, "element with no subelements at {}", pos),
+            Self::Index { pos, idx, count } => write!(
+                f,
+                "index {} at {} is out of range for {} subelements",
+                idx, pos, count
+            ),
+            Self::MissingFacets => write!(f, "the facets are missing"),
+            Self::OmittedSections => write!(f, "the omitted sections can't be rebuilt"),
         }
     }
 }
@@ -91,6 +163,164 @@ fn element_name(rank: usize) -> String {
     }
 }
 
+/// Rebuilds the sections that were omitted from an OFF file.
+///
+/// We're given the elements of the first section read after the omitted ones,
+/// as lists of indices into the last section read before them, whose elements
+/// are given by `lower`. We return the `missing` omitted sections, followed by
+/// the given section with its indices updated, in increasing rank order. If
+/// the given section is that of the facets, `facets` must be set.
+///
+/// We identify each element with the set of lower elements it contains, and
+/// take the elements right below a given one to be the maximal intersections
+/// of it with the other elements of its rank. This works in lattices, where
+/// every element is the intersection of two elements containing it. Other
+/// polytopes, like ditopes and hemi-polytopes, can have elements that share
+/// no subelements or several of them. We detect these, since the rebuilt
+/// elements then break the diamond property or are disconnected, and return
+/// [`OffParseError::OmittedSections`].
+fn rebuild_sections(
+    els: SubelementList,
+    missing: usize,
+    lower: &ElementList,
+    facets: bool,
+) -> OffParseResult<Vec<SubelementList>> {
+    let mut sets: Vec<Vec<usize>> = els
+        .into_iter()
+        .map(|subs| {
+            let mut set = subs.into_inner();
+            set.sort_unstable();
+            set.dedup();
+            set
+        })
+        .collect();
+    let mut sections = Vec::with_capacity(missing + 1);
+
+    for _ in 0..missing {
+        let mut next_sets = Vec::new();
+        let mut indices = HashMap::new();
+        let mut section = SubelementList::with_capacity(sets.len());
+
+        for (i, set) in sets.iter().enumerate() {
+            // The proper, non-empty intersections with the other elements.
+            let mut candidates: Vec<Vec<usize>> = sets
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| i != j)
+                .map(|(_, other)| {
+                    set.iter()
+                        .copied()
+                        .filter(|x| other.binary_search(x).is_ok())
+                        .collect::<Vec<_>>()
+                })
+                .filter(|int| !int.is_empty() && int.len() < set.len())
+                .collect();
+            candidates.sort_unstable();
+            candidates.dedup();
+
+            // We only keep the maximal intersections.
+            let mut subs = Subelements::new();
+            for int in candidates.iter().filter(|&int| {
+                !candidates.iter().any(|other| {
+                    other.len() > int.len() && int.iter().all(|x| other.binary_search(x).is_ok())
+                })
+            }) {
+                let idx = *indices.entry(int.clone()).or_insert_with(|| {
+                    next_sets.push(int.clone());
+                    next_sets.len() - 1
+                });
+
+                subs.push(idx);
+            }
+
+            section.push(subs);
+        }
+
+        sections.push(section);
+        sets = next_sets;
+    }
+
+    // The lowest rebuilt section refers directly to the last section read.
+    sections.push(
+        sets.into_iter()
+            .map(Subelements::from)
+            .collect::<Vec<_>>()
+            .into(),
+    );
+    sections.reverse();
+
+    // Every ridge is contained in exactly two facets.
+    if facets && missing != 0 {
+        let mut sup_counts = vec![0; sections[missing - 1].len()];
+        for subs in sections[missing].iter() {
+            for &sub in subs.iter() {
+                sup_counts[sub] += 1;
+            }
+        }
+
+        if sup_counts.iter().any(|&count| count != 2) {
+            return Err(OffParseError::OmittedSections);
+        }
+    }
+
+    // Every element we rebuilt, and every element built on top of them, must
+    // be a valid element of a polytope.
+    let mut below: Vec<&Subelements> = lower.iter().map(|el| &el.subs).collect();
+    for section in &sections {
+        if !section.iter().all(|subs| is_valid_element(subs, &below)) {
+            return Err(OffParseError::OmittedSections);
+        }
+
+        below = section.iter().collect();
+    }
+
+    Ok(sections)
+}
+
+/// Returns whether an element with the given subelements could belong to a
+/// polytope, given the subelements of each of these. It must have some
+/// subelement, every element right below its subelements must be contained
+/// in exactly two of them, and its subelements must be connected through
+/// these.
+fn is_valid_element(subs: &Subelements, below: &[&Subelements]) -> bool {
+    if subs.is_empty() {
+        return false;
+    }
+
+    // The subelements that contain each element right below them.
+    let mut containing: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (i, &sub) in subs.iter().enumerate() {
+        for &el in below[sub].iter() {
+            containing.entry(el).or_default().push(i);
+        }
+    }
+
+    if containing.values().any(|sups| sups.len() != 2) {
+        return false;
+    }
+
+    // We walk through the subelements, moving between any two that share an
+    // element.
+    let mut visited = vec![false; subs.len()];
+    let mut stack = vec![0];
+    visited[0] = true;
+    let mut count = 1;
+
+    while let Some(i) = stack.pop() {
+        for el in below[subs[i]].iter() {
+            for &j in &containing[el] {
+                if !visited[j] {
+                    visited[j] = true;
+                    count += 1;
+                    stack.push(j);
+                }
+            }
+        }
+    }
+
+    count == subs.len()
+}
+
 /// The result of trying to read the next token from an OFF file.
 enum OffNext<'a> {
     /// We've read a token from the OFF file. We don't directly store a
@@ -120,25 +350,48 @@ impl<'a> Token<'a> {
             .map_err(|_| OffParseError::Parsing(self.pos))
     }
 
-    /// Reads the rank from a token of the form `(-?\d+)?OFF`. If the rank is
-    /// omitted, we use a default value of 4.
-    fn rank(&self) -> OffParseResult<usize> {
-        let rank = self
+    /// Reads the header from a token of the form `C?(-?\d+|n)?OFF`. If the
+    /// rank is omitted, we use a default value of 4. An `n` means that the
+    /// dimension is given by the next token. A leading `C` means that every
+    /// vertex is followed by its color.
+    fn header(&self) -> OffParseResult<Header> {
+        let prefix = self
             .slice
             .strip_suffix("OFF")
             .ok_or(OffParseError::MagicWord(self.pos))?;
 
-        if rank.is_empty() {
-            Ok(4)
-        } else {
-            match rank.parse::<isize>() {
-                Ok(r) => Ok((r + 1) as usize),
-                Err(_) => Err(OffParseError::Rank(self.pos)),
-            }
-        }
+        let (vertex_colors, rank) = match prefix.strip_prefix('C') {
+            Some(rank) => (true, rank),
+            None => (false, prefix),
+        };
+
+        let rank = match rank {
+            "" => Some(4),
+            "n" => None,
+            rank => match rank.parse::<isize>() {
+                Ok(r) if r >= -1 => Some((r + 1) as usize),
+                _ => return Err(OffParseError::Rank(self.pos)),
+            },
+        };
+
+        Ok(Header {
+            rank,
+            vertex_colors,
+        })
     }
 }
 
+/// The information stored in the magic word of an OFF file.
+struct Header {
+    /// The rank of the polytope, or `None` if the dimension is given by the
+    /// next token, as in the `nOFF` variant.
+    rank: Option<usize>,
+
+    /// Whether every vertex is followed by its color, as in the `COFF`
+    /// variant.
+    vertex_colors: bool,
+}
+
 /// An iterator over the tokens in an OFF file. It excludes whitespace and
 /// comments. It also keeps track of position.
 struct TokenIter<'a> {
@@ -153,6 +406,12 @@ struct TokenIter<'a> {
 
     /// The row and column in the file.
     position: Position,
+
+    /// A token that was read ahead of time.
+    peeked: Option<Token<'a>>,
+
+    /// The row of the last token returned.
+    row: u32,
 }
 
 impl<'a> TokenIter<'a> {
@@ -164,6 +423,8 @@ impl<'a> TokenIter<'a> {
             iter: src.char_indices(),
             comment: false,
             position: Default::default(),
+            peeked: None,
+            row: 0,
         }
     }
 
@@ -219,11 +480,86 @@ impl<'a> TokenIter<'a> {
         })
     }
 
+    /// Reads the next token from the file, skipping any garbage.
+    fn read_token(&mut self) -> Option<Token<'a>> {
+        loop {
+            let pos = self.position;
+            if let OffNext::Token(slice) = self.try_next()? {
+                return Some(Token { slice, pos });
+            }
+        }
+    }
+
+    /// Returns the next token from the OFF file, or an error if the file has
+    /// ended.
+    fn next_token(&mut self) -> OffParseResult<Token<'a>> {
+        self.next()
+            .ok_or(OffParseError::UnexpectedEnding(self.position))
+    }
+
     /// Reads and parses the next token from the OFF file.
     pub fn parse_next<U: FromStr>(&mut self) -> OffParseResult<U> {
-        self.next()
-            .ok_or(OffParseError::UnexpectedEnding(self.position))?
-            .parse()
+        self.next_token()?.parse()
+    }
+
+    /// Reads the next token from the OFF file as the index of one of `count`
+    /// subelements.
+    fn parse_index(&mut self, count: usize) -> OffParseResult<usize> {
+        let token = self.next_token()?;
+        let idx = token.parse()?;
+
+        if idx < count {
+            Ok(idx)
+        } else {
+            Err(OffParseError::Index {
+                pos: token.pos,
+                idx,
+                count,
+            })
+        }
+    }
+
+    /// Returns the number of bytes left to read in the file. Every entry takes
+    /// up at least one of these, so this bounds any count of entries that the
+    /// file can still hold.
+    fn remaining(&self) -> usize {
+        let peeked = self.peeked.as_ref().map_or(0, |token| token.slice.len());
+        self.iter.as_str().len() + peeked
+    }
+
+    /// Returns how much space to reserve for a count of entries read from the
+    /// file. This is bounded by what the rest of the file can hold, so that an
+    /// absurd count gives an error when the file runs out, instead of an
+    /// absurd allocation.
+    fn capacity(&self, count: usize) -> usize {
+        count.min(self.remaining())
+    }
+
+    /// Reads the number of subelements of an element, which must be positive.
+    fn parse_sub_count(&mut self) -> OffParseResult<usize> {
+        let token = self.next_token()?;
+        match token.parse()? {
+            0 => Err(OffParseError::EmptyElement(token.pos)),
+            count => Ok(count),
+        }
+    }
+
+    /// Reads all of the remaining tokens in the row of the last token read.
+    fn rest_of_line(&mut self) -> Vec<Token<'a>> {
+        let mut tokens = Vec::new();
+
+        loop {
+            if self.peeked.is_none() {
+                self.peeked = self.read_token();
+            }
+
+            match &self.peeked {
+                Some(token) if token.pos.row == self.row => {
+                    tokens.extend(self.next());
+                }
+                _ => return tokens,
+            }
+        }
     }
 }
 
@@ -231,12 +567,13 @@ impl<'a> Iterator for TokenIter<'a> {
     type Item = Token<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            let pos = self.position;
-            if let OffNext::Token(slice) = self.try_next()? {
-                return Some(Token { slice, pos });
-            }
-        }
+        let token = match self.peeked.take() {
+            Some(token) => token,
+            None => self.read_token()?,
+        };
+
+        self.row = token.pos.row;
+        Some(token)
     }
 }
 
@@ -248,6 +585,9 @@ pub struct OffReader<'a> {
 
     /// The underlying abstract polytope.
     abs: AbstractBuilder,
+
+    /// The colors read from the file.
+    colors: ColorData,
 }
 
 impl<'a> OffReader<'a> {
@@ -256,6 +596,7 @@ impl<'a> OffReader<'a> {
         Self {
             iter: TokenIter::new(src),
             abs: AbstractBuilder::new(),
+            colors: ColorData::default(),
         }
     }
 
@@ -269,9 +610,42 @@ impl<'a> OffReader<'a> {
         self.iter.next()
     }
 
-    /// Reads the first token from the OFF file, returns the polytope's rank.
-    fn rank(&mut self) -> OffParseResult<usize> {
-        self.next().ok_or(OffParseError::Empty)?.rank()
+    /// Reads the header of the OFF file. Returns the polytope's rank, and
+    /// whether the vertices are followed by their colors.
+    fn header(&mut self) -> OffParseResult<(usize, bool)> {
+        let token = self.next().ok_or(OffParseError::Empty)?;
+        let header = token.header()?;
+
+        let (rank, pos) = match header.rank {
+            Some(rank) => (rank, token.pos),
+            None => {
+                let token = self.iter.next_token()?;
+                let dim: usize = token.parse().map_err(|_| OffParseError::Rank(token.pos))?;
+                let rank = dim.checked_add(1).ok_or(OffParseError::Rank(token.pos))?;
+                (rank, token.pos)
+            }
+        };
+
+        // Every rank past the second needs an element count, which the rest
+        // of the file must be able to hold.
+        if rank > 2 && rank - 1 > self.iter.remaining() {
+            return Err(OffParseError::Rank(pos));
+        }
+
+        Ok((rank, header.vertex_colors))
+    }
+
+    /// Reads the color given in the rest of the current line, if any. Lines
+    /// with anything other than 3 or 4 numbers are taken to have no color.
+    fn parse_color(&mut self) -> OffParseResult<Option<Color>> {
+        let components = self
+            .iter
+            .rest_of_line()
+            .iter()
+            .map(Token::parse)
+            .collect::<OffParseResult<Vec<f32>>>()?;
+
+        Ok(ColorData::color_from_components(&components))
     }
 
     /// Gets the number of elements from the OFF file from rank 1 up to rank
@@ -299,14 +673,16 @@ impl<'a> OffReader<'a> {
         Ok(el_nums)
     }
 
-    /// Parses all vertex coordinates from the OFF file.
+    /// Parses all vertex coordinates from the OFF file, as well as their
+    /// colors if these are given.
     fn parse_vertices(
         &mut self,
         count: usize,
         dim: usize,
+        colors: bool,
     ) -> OffParseResult<Vec<Point<f64>>> {
         // Reads all vertices.
-        let mut vertices = Vec::with_capacity(count);
+        let mut vertices = Vec::with_capacity(self.iter.capacity(count));
 
         // Add each vertex to the vector.
         for _ in 0..count {
@@ -317,6 +693,11 @@ impl<'a> OffReader<'a> {
             }
 
             vertices.push(v.into());
+
+            if colors {
+                let color = self.parse_color()?;
+                self.colors.vertices.push(color);
+            }
         }
 
         Ok(vertices)
@@ -328,22 +709,23 @@ impl<'a> OffReader<'a> {
     fn parse_edges_and_faces(
         &mut self,
         rank: usize,
+        num_vertices: usize,
         num_edges: usize,
         num_faces: usize,
     ) -> OffParseResult<(SubelementList, SubelementList)> {
-        let mut edges = SubelementList::with_capacity(num_edges);
-        let mut faces = SubelementList::with_capacity(num_faces);
+        let mut edges = SubelementList::with_capacity(self.iter.capacity(num_edges));
+        let mut faces = SubelementList::with_capacity(self.iter.capacity(num_faces));
         let mut hash_edges = HashMap::new();
 
         // Add each face to the element list.
         for _ in 0..num_faces {
-            let face_sub_num = self.iter.parse_next()?;
+            let face_sub_num = self.iter.parse_sub_count()?;
             let mut face = Subelements::new();
-            let mut face_verts = Vec::with_capacity(face_sub_num + 1);
+            let mut face_verts = Vec::with_capacity(self.iter.capacity(face_sub_num) + 1);
 
             // Reads all vertices of the face.
             for _ in 0..face_sub_num {
-                face_verts.push(self.iter.parse_next()?);
+                face_verts.push(self.iter.parse_index(num_vertices)?);
             }
 
             // We add the first vertex to the end for simplicity.
@@ -369,15 +751,14 @@ impl<'a> OffReader<'a> {
                 }
             }
 
+            // Reads the color of the face.
+            let color = self.parse_color()?;
+
             // If these are truly faces and not just components, we add them.
             // Hopefully the compiler can optimize this better, I'm lazy.
             if rank != 3 {
                 faces.push(face);
-            }
-
-            // Goes to the end of the line in order to ignore things like colour info.
-            if self.iter.position.column != 0 {
-                self.iter.comment = true;
+                self.colors.faces.push(color);
             }
         }
 
@@ -395,26 +776,25 @@ impl<'a> OffReader<'a> {
         Ok((edges, faces))
     }
 
-    /// Parses the next set of d-elements from the OFF file.
-    fn parse_els(&mut self, num_el: usize) -> OffParseResult<SubelementList> {
-        let mut els_subs = SubelementList::with_capacity(num_el);
+    /// Parses the next set of d-elements from the OFF file, given the number
+    /// of elements in the last section read.
+    fn parse_els(&mut self, num_el: usize, num_subs: usize) -> OffParseResult<SubelementList> {
+        let mut els_subs = SubelementList::with_capacity(self.iter.capacity(num_el));
 
         // Adds every d-element to the element list.
         for _ in 0..num_el {
-            let el_sub_num = self.iter.parse_next()?;
-            let mut subs = Subelements::with_capacity(el_sub_num);
+            let el_sub_num = self.iter.parse_sub_count()?;
+            let mut subs = Subelements::with_capacity(self.iter.capacity(el_sub_num));
 
             // Reads all sub-elements of the d-element.
             for _ in 0..el_sub_num {
-                subs.push(self.iter.parse_next()?);
+                subs.push(self.iter.parse_index(num_subs)?);
             }
 
             els_subs.push(subs);
 
-            // Goes to the end of the line in order to ignore things like colour info.
-            if self.iter.position.column != 0 {
-                self.iter.comment = true;
-            }
+            // Ignores things like colour info.
+            self.iter.rest_of_line();
         }
 
         Ok(els_subs)
@@ -433,7 +813,7 @@ impl<'a> OffReader<'a> {
     /// Builds a concrete polytope from the OFF reader.
     pub fn build(mut self) -> OffParseResult<Concrete> {
         // Reads the rank of the polytope.
-        let rank = self.rank()?;
+        let (rank, vertex_colors) = self.header()?;
 
         // Deals with dumb degenerate cases.
         match rank {
@@ -445,7 +825,7 @@ impl<'a> OffReader<'a> {
 
         // Reads the element numbers and vertices.
         let num_elems = self.el_nums(rank)?;
-        let vertices = self.parse_vertices(num_elems[0], rank - 1)?;
+        let vertices = self.parse_vertices(num_elems[0], rank - 1, vertex_colors)?;
 
         // Adds nullitope and vertices.
         self.abs.reserve(rank + 2);
@@ -453,16 +833,41 @@ impl<'a> OffReader<'a> {
         self.abs.push_vertices(vertices.len());
 
         // Reads edges and faces.
+        // The number of elements in the last section read.
+        let mut num_subs = vertices.len();
         if rank >= 3 {
-            let (edges, faces) = self.parse_edges_and_faces(rank, num_elems[1], num_elems[2])?;
+            let (edges, faces) =
+                self.parse_edges_and_faces(rank, num_subs, num_elems[1], num_elems[2])?;
+            num_subs = faces.len();
             self.abs.push(edges);
             self.abs.push(faces);
         }
 
-        // Adds all higher elements.
-        for &num_el in num_elems.iter().take(rank - 1).skip(3) {
-            let subelements = self.parse_els(num_el)?;
-            self.abs.push(subelements);
+        // Adds all higher elements. Sections with no elements are taken to be
+        // omitted, in which case we rebuild them from the next section read.
+        let mut missing = 0;
+        for (r, &num_el) in num_elems.iter().enumerate().take(rank - 1).skip(3) {
+            if num_el == 0 {
+                missing += 1;
+                continue;
+            }
+
+            let subelements = self.parse_els(num_el, num_subs)?;
+            num_subs = num_el;
+            if missing == 0 {
+                self.abs.push(subelements);
+            } else {
+                let lower = self.abs.ranks().last().unwrap();
+                for section in rebuild_sections(subelements, missing, lower, r == rank - 2)? {
+                    self.abs.push(section);
+                }
+                missing = 0;
+            }
+        }
+
+        // We can't rebuild the facets from anything.
+        if missing != 0 {
+            return Err(OffParseError::MissingFacets);
         }
 
         // Caps the abstract polytope.
@@ -473,7 +878,10 @@ impl<'a> OffReader<'a> {
         // Builds the concrete polytope.
 
         // Safety: TODO this isn't actually safe. We need to do some checking.
-        Ok(Concrete::new(vertices, unsafe { self.abs.build() }))
+        let mut poly = Concrete::new(vertices, unsafe { self.abs.build() });
+        poly.colors = self.colors;
+        poly.colors.scheme = ColorScheme::from_comments(self.iter.src);
+        Ok(poly)
     }
 }
 
@@ -624,9 +1032,27 @@ impl<'a> OffWriter<'a> {
         self.push_str(data.to_string())
     }
 
+    /// Appends the components of a color to the OFF file, each of them
+    /// preceded by a space, if there's a color at all.
+    fn push_color(&mut self, color: Option<Color>) {
+        for c in color.iter().flatten() {
+            self.push(' ');
+            self.push_to_str(c);
+        }
+    }
+
+    /// Returns whether any vertex has a color, in which case these are written
+    /// as in the `COFF` variant.
+    fn vertex_colors(&self) -> bool {
+        self.poly.colors.vertices.iter().any(Option::is_some)
+    }
+
     /// Writes the OFF format header.
     fn write_rank(&mut self) {
         let rank = self.rank();
+        if self.vertex_colors() {
+            self.push('C');
+        }
         if rank != 4 {
             self.push_to_str(rank as isize - 1);
         }
@@ -722,12 +1148,13 @@ impl<'a> OffWriter<'a> {
             self.push('\n');
         }
 
-        // Adds the coordinates.
-        for v in &self.poly.vertices {
+        // Adds the coordinates, followed by the colors if there are any.
+        for (idx, v) in self.poly.vertices.iter().enumerate() {
             for c in v {
                 self.push_to_str(c);
                 self.push(' ');
             }
+            self.push_color(self.poly.colors.vertex(idx));
             self.push('\n');
         }
     }
@@ -771,6 +1198,7 @@ impl<'a> OffWriter<'a> {
                     self.push(' ');
                     self.push_to_str(v);
                 }
+                self.push_color(self.poly.colors.face(idx));
                 self.push('\n');
             }
         }
@@ -818,6 +1246,10 @@ impl<'a> OffWriter<'a> {
             self.push('\n');
         }
 
+        // The colors of the polytope are written even without comments, since
+        // they can't be recovered otherwise.
+        self.push_str(self.poly.colors.scheme.to_comments());
+
         // Writes header.
         self.write_rank();
 
@@ -978,6 +1410,125 @@ mod tests {
         test_off!("comments", [1, 4, 6, 4, 1])
     }
 
+    /// Checks that polytopes of high rank survive a round-trip.
+    #[test]
+    fn high_rank() {
+        for rank in 5..=7 {
+            let hypercube = Concrete::hypercube(rank);
+            let off = hypercube.to_off(Default::default()).unwrap();
+            test(
+                &Concrete::from_off(&off).unwrap(),
+                (0..=rank).map(|r| hypercube.el_count(r)),
+            );
+        }
+    }
+
+    /// Checks that omitted sections are rebuilt.
+    #[test]
+    fn omitted_sections() {
+        let mut hypercube = Concrete::hypercube(7);
+        hypercube.element_sort();
+        let counts: Vec<_> = (0..=7).map(|r| hypercube.el_count(r)).collect();
+
+        // We write the OFF file by hand, omitting the cells and the tera.
+        let off = hypercube.to_off(OffOptions { comments: false }).unwrap();
+        let mut lines = off.lines();
+        let mut src = String::from("6OFF\n");
+        lines.next();
+        lines.next();
+        src.push_str(&format!(
+            "{} {} {} 0 0 {}\n",
+            counts[1], counts[3], counts[2], counts[6]
+        ));
+
+        // Vertices and faces are copied verbatim.
+        for line in lines.take(counts[1] + counts[3]) {
+            src.push_str(line);
+            src.push('\n');
+        }
+
+        // Writes each facet as a list of faces.
+        for facet in &hypercube[6] {
+            let mut faces: Vec<_> = facet
+                .subs
+                .iter()
+                .flat_map(|&tera| &hypercube[(5, tera)].subs)
+                .flat_map(|&cell| &hypercube[(4, cell)].subs)
+                .copied()
+                .collect();
+            faces.sort_unstable();
+            faces.dedup();
+
+            src.push_str(&faces.len().to_string());
+            for face in faces {
+                src.push_str(&format!(" {}", face));
+            }
+            src.push('\n');
+        }
+
+        let poly = Concrete::from_off(&src).unwrap();
+        poly.assert_valid();
+        test(&poly, counts);
+    }
+
+    /// Omitting sections that the others don't determine should fail, rather
+    /// than give an invalid polytope. Both tera of a ditope have the same
+    /// faces, so its cells can't be rebuilt from them.
+    #[test]
+    #[should_panic(expected = "OmittedSections")]
+    fn omitted_ditope_cells() {
+        let mut ditope = Concrete::hypercube(5).ditope();
+        ditope.element_sort();
+        let counts: Vec<_> = (0..=6).map(|r| ditope.el_count(r)).collect();
+
+        // We write the OFF file by hand, padding the vertices to five
+        // dimensions and omitting the cells.
+        let mut src = format!(
+            "5OFF\n{} {} {} 0 {}\n",
+            counts[1], counts[3], counts[2], counts[5]
+        );
+        for v in &ditope.vertices {
+            for c in v {
+                src.push_str(&format!("{} ", c));
+            }
+            src.push_str("0\n");
+        }
+
+        // Faces are copied verbatim.
+        let off = ditope.to_off(OffOptions { comments: false }).unwrap();
+        for line in off.lines().skip(2 + counts[1]).take(counts[3]) {
+            src.push_str(line);
+            src.push('\n');
+        }
+
+        // Writes each teron as a list of faces.
+        for teron in &ditope[5] {
+            let mut faces: Vec<_> = teron
+                .subs
+                .iter()
+                .flat_map(|&cell| &ditope[(4, cell)].subs)
+                .copied()
+                .collect();
+            faces.sort_unstable();
+            faces.dedup();
+
+            src.push_str(&faces.len().to_string());
+            for face in faces {
+                src.push_str(&format!(" {}", face));
+            }
+            src.push('\n');
+        }
+
+        unwrap_off(&src)
+    }
+
+    /// Omitting the facets should fail.
+    #[test]
+    #[should_panic(expected = "MissingFacets")]
+    fn missing_facets() {
+        unwrap_off("4OFF\n3 1 3 0\n0 0 0 0\n1 0 0 0\n0 1 0 0\n3 0 1 2")
+    }
+
     /// Attempts to parse an OFF file, unwraps it.
     fn unwrap_off(src: &str) {
         Concrete::from_off(src).unwrap();
@@ -1010,4 +1561,136 @@ mod tests {
     fn parse() {
         unwrap_off("OFF\n10 foo bar")
     }
+
+    /// Checks that the dimension can be given after the magic word.
+    #[test]
+    fn n_off() {
+        let poly = Concrete::from_off(
+            "nOFF # comment\n3\n4 4 6\n1 1 1\n1 -1 -1\n-1 1 -1\n-1 -1 1\n\
+            3 0 1 2\n3 3 0 2\n3 0 1 3\n3 3 1 2",
+        )
+        .unwrap();
+        test(&poly, [1, 4, 6, 4, 1]);
+        assert!(poly.colors.is_empty());
+    }
+
+    /// Checks that vertex and face colors are read.
+    #[test]
+    fn colors() {
+        let poly = Concrete::from_off(
+            "COFF\n4 4 6\n\
+            1 1 1 1 0 0 1\n1 -1 -1 0 1 0 1 # green\n-1 1 -1 0 0 1 1\n-1 -1 1 1 1 1 0.5\n\
+            3 0 1 2 255 0 0\n3 3 0 2\n3 0 1 3 0 0 255 # blue\n3 3 1 2 7",
+        )
+        .unwrap();
+        test(&poly, [1, 4, 6, 4, 1]);
+
+        assert_eq!(poly.colors.vertex(1), Some([0.0, 1.0, 0.0, 1.0]));
+        assert_eq!(poly.colors.vertex(3), Some([1.0, 1.0, 1.0, 0.5]));
+        assert_eq!(poly.colors.face(0), Some([1.0, 0.0, 0.0, 1.0]));
+        assert_eq!(poly.colors.face(1), None);
+        assert_eq!(poly.colors.face(2), Some([0.0, 0.0, 1.0, 1.0]));
+        assert_eq!(poly.colors.face(3), None);
+    }
+
+    /// Checks that vertex and face colors round-trip through OFF files.
+    #[test]
+    fn colors_round_trip() {
+        let mut cube = Concrete::cube();
+        cube.colors.vertices = vec![None, Some([0.0, 1.0, 0.0, 1.0])];
+        cube.colors.faces = vec![Some([1.0, 0.0, 0.0, 0.5]), None, Some([0.0, 0.0, 1.0, 1.0])];
+
+        for comments in [true, false] {
+            let off = cube.to_off(OffOptions { comments }).unwrap();
+            assert!(off.contains("COFF"));
+
+            let poly = Concrete::from_off(&off).unwrap();
+            test(&poly, [1, 8, 12, 6, 1]);
+            for idx in 0..8 {
+                assert_eq!(poly.colors.vertex(idx), cube.colors.vertex(idx));
+            }
+            for idx in 0..6 {
+                assert_eq!(poly.colors.face(idx), cube.colors.face(idx));
+            }
+        }
+
+        // Taking the dual renumbers the elements, so it forgets their colors.
+        cube.try_dual_mut().unwrap();
+        assert!(cube.colors.is_empty());
+        let off = cube.to_off(Default::default()).unwrap();
+        assert!(!off.contains("COFF"));
+    }
+
+    /// Checks that color schemes round-trip through OFF files.
+    #[test]
+    fn color_scheme() {
+        let mut cube = Concrete::cube();
+        cube.colors.scheme = ColorScheme {
+            faces: Some([0.25, 0.5, 1.0, 1.0]),
+            edges: Some([1.0, 1.0, 1.0, 1.0]),
+            vertices: None,
+        };
+
+        for comments in [true, false] {
+            let off = cube.to_off(OffOptions { comments }).unwrap();
+            let poly = Concrete::from_off(&off).unwrap();
+            test(&poly, [1, 8, 12, 6, 1]);
+            assert_eq!(poly.colors.scheme, cube.colors.scheme);
+        }
+
+        let off = Concrete::cube().to_off(Default::default()).unwrap();
+        assert!(!off.contains(ColorScheme::HEADER));
+    }
+
+    /// A file referring to a nonexistent vertex should fail.
+    #[test]
+    #[should_panic(expected = "Index { pos: Position { row: 5, column: 6 }, idx: 5, count: 3 }")]
+    fn index() {
+        unwrap_off("OFF\n3 1 3\n0 0 0\n1 0 0\n0 1 0\n3 0 1 5")
+    }
+
+    /// A file with an element without subelements should fail.
+    #[test]
+    #[should_panic(expected = "EmptyElement(Position { row: 5, column: 0 })")]
+    fn empty_element() {
+        unwrap_off("OFF\n3 1 3\n0 0 0\n1 0 0\n0 1 0\n0")
+    }
+
+    /// Absurd ranks and counts should fail without allocating space for them.
+    #[test]
+    fn huge_counts() {
+        for src in ["nOFF\n100000000000", "nOFF\n18446744073709551615"] {
+            assert_eq!(
+                Concrete::from_off(src).unwrap_err().to_string(),
+                "could not read rank at row 2, column 1"
+            );
+        }
+
+        for src in [
+            "OFF\n1000000000000 1 0",
+            "OFF\n3 1000000000000 0\n0 0 0\n1 0 0\n0 1 0",
+            "OFF\n3 1 3\n0 0 0\n1 0 0\n0 1 0\n18446744073709551615 0 1 2",
+            "4OFF\n4 1 1 1000000000000\n0 0 0 0\n1 0 0 0\n0 1 0 0\n0 0 1 0\n\
+            3 0 1 2\n18446744073709551615 0",
+        ] {
+            assert!(matches!(
+                Concrete::from_off(src).unwrap_err(),
+                OffParseError::UnexpectedEnding(_)
+            ));
+        }
+
+        // The edge count isn't obligatory, so it's only used as a hint.
+        unwrap_off("OFF\n3 1 1000000000000\n0 0 0\n1 0 0\n0 1 0\n3 0 1 2");
+    }
+
+    /// Checks that errors point at the offending line.
+    #[test]
+    fn diagnostic() {
+        let src = "OFF\n10 foo bar";
+        let err = Concrete::from_off(src).unwrap_err();
+        assert_eq!(
+            err.diagnostic(src),
+            "could not parse number at row 2, column 4\n2 | 10 foo bar\n  |    ^"
+        );
+    }
 }
@@ -0,0 +1,378 @@
+//! Reads and writes periodic OFF files, which store tilings and honeycombs as
+//! a [`Honeycomb`]: a basis of their translations together with the cells in a
+//! fundamental domain of them.
+//!
+//! The format follows OFF files closely. Comments start with `#`, and the
+//! magic word `POFF` can be prefixed by the dimension, which defaults to 3. It's
+//! followed by the number of translations, vertices and cells, then by the
+//! coordinates of the translations and of the vertices, and finally by the
+//! cells, each given by its number of vertices and their indices. Every cell
+//! is the convex hull of its vertices.
+//!
+//! ```txt
+//! 2POFF
+//! # Translations, vertices, cells
+//! 2 4 1
+//!
+//! # Translations
+//! 1 0
+//! 0 1
+//!
+//! # Vertices
+//! 0 0
+//! 1 0
+//! 0 1
+//! 1 1
+//!
+//! # Cells
+//! 4 0 1 2 3
+//! ```
+
+use std::{fmt::Display, str::FromStr};
+
+use crate::{conc::honeycomb::Honeycomb, geometry::Point};
+
+/// The number of copies of the fundamental domain along every translation in
+/// the patches built from periodic OFF files.
+pub const PATCH_COPIES: usize = 3;
+
+/// Any error encountered while reading a periodic OFF file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PoffError {
+    /// The file has no contents.
+    Empty,
+
+    /// The file doesn't start with the magic word, or its dimension can't be
+    /// read.
+    MagicWord {
+        /// The line number, starting at 1.
+        line: usize,
+    },
+
+    /// The file ended unexpectedly.
+    UnexpectedEnding,
+
+    /// An entry on a given line isn't a number.
+    Number {
+        /// The line number, starting at 1.
+        line: usize,
+
+        /// The entry that couldn't be read.
+        value: String,
+    },
+
+    /// There's more translations than dimensions.
+    Translations {
+        /// The number of translations.
+        count: usize,
+
+        /// The dimension of the space.
+        dim: usize,
+    },
+
+    /// A cell refers to a vertex that doesn't exist.
+    Index {
+        /// The line number, starting at 1.
+        line: usize,
+
+        /// The index that was read.
+        idx: usize,
+
+        /// The number of vertices.
+        count: usize,
+    },
+
+    /// The cells couldn't be built into a patch.
+    Patch,
+}
+
+impl Display for PoffError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "the file is empty"),
+            Self::MagicWord { line } => write!(f, "no POFF magic word on line {}", line),
+            Self::UnexpectedEnding => write!(f, "the file ended unexpectedly"),
+            Self::Number { line, value } => {
+                write!(f, "\"{}\" on line {} isn't a number", value, line)
+            }
+            Self::Translations { count, dim } => {
+                write!(f, "{} translations were given in {} dimensions", count, dim)
+            }
+            Self::Index { line, idx, count } => write!(
+                f,
+                "vertex {} on line {} doesn't exist, there's only {} vertices",
+                idx, line, count
+            ),
+            Self::Patch => write!(f, "the cells couldn't be built"),
+        }
+    }
+}
+
+impl std::error::Error for PoffError {}
+
+/// The result of reading a periodic OFF file.
+pub type PoffResult<T> = Result<T, PoffError>;
+
+/// Reads through the entries of a periodic OFF file, skipping comments.
+struct Tokens<'a> {
+    /// The entries, together with their line numbers, starting at 1.
+    tokens: std::vec::IntoIter<(usize, &'a str)>,
+}
+
+impl<'a> Tokens<'a> {
+    /// Splits a file into its entries.
+    fn new(src: &'a str) -> Self {
+        let tokens: Vec<_> = src
+            .lines()
+            .enumerate()
+            .flat_map(|(idx, line)| {
+                let line = line.split('#').next().unwrap_or_default();
+                line.split_whitespace().map(move |s| (idx + 1, s))
+            })
+            .collect();
+
+        Self {
+            tokens: tokens.into_iter(),
+        }
+    }
+
+    /// Reads the next entry, together with its line number.
+    fn next(&mut self) -> PoffResult<(usize, &'a str)> {
+        self.tokens.next().ok_or(PoffError::UnexpectedEnding)
+    }
+
+    /// Reads the next entry as a number, together with its line number.
+    fn parse<T: FromStr>(&mut self) -> PoffResult<(usize, T)> {
+        let (line, value) = self.next()?;
+        match value.parse() {
+            Ok(x) => Ok((line, x)),
+            Err(_) => Err(PoffError::Number {
+                line,
+                value: value.to_string(),
+            }),
+        }
+    }
+
+    /// Reads a point with a given number of coordinates.
+    fn point(&mut self, dim: usize) -> PoffResult<Point<f64>> {
+        let coords = (0..dim)
+            .map(|_| Ok(self.parse()?.1))
+            .collect::<PoffResult<Vec<f64>>>()?;
+        Ok(coords.into())
+    }
+}
+
+/// Writes the coordinates of a point, separated by spaces.
+fn write_point(poff: &mut String, p: &Point<f64>) {
+    let coords: Vec<_> = p.iter().map(ToString::to_string).collect();
+    poff.push_str(&coords.join(" "));
+    poff.push('\n');
+}
+
+impl Honeycomb {
+    /// Reads a honeycomb from a periodic OFF file.
+    pub fn from_poff(src: &str) -> PoffResult<Self> {
+        let mut tokens = Tokens::new(src);
+
+        let (line, magic) = tokens.next().map_err(|_| PoffError::Empty)?;
+        let dim = match magic.strip_suffix("POFF") {
+            Some("") => 3,
+            Some(dim) => dim.parse().map_err(|_| PoffError::MagicWord { line })?,
+            None => return Err(PoffError::MagicWord { line }),
+        };
+
+        let translation_count = tokens.parse()?.1;
+        let vertex_count = tokens.parse()?.1;
+        let cell_count: usize = tokens.parse()?.1;
+        if translation_count > dim {
+            return Err(PoffError::Translations {
+                count: translation_count,
+                dim,
+            });
+        }
+
+        let translations = (0..translation_count)
+            .map(|_| tokens.point(dim))
+            .collect::<PoffResult<_>>()?;
+        let vertices = (0..vertex_count)
+            .map(|_| tokens.point(dim))
+            .collect::<PoffResult<_>>()?;
+
+        // The counts come straight from the file, so we don't preallocate
+        // anything from them.
+        let mut cells = Vec::new();
+        for _ in 0..cell_count {
+            let len: usize = tokens.parse()?.1;
+            let mut cell = Vec::new();
+
+            for _ in 0..len {
+                let (line, idx) = tokens.parse()?;
+                if idx >= vertex_count {
+                    return Err(PoffError::Index {
+                        line,
+                        idx,
+                        count: vertex_count,
+                    });
+                }
+
+                cell.push(idx);
+            }
+
+            cells.push(cell);
+        }
+
+        Ok(Self {
+            translations,
+            vertices,
+            cells,
+        })
+    }
+
+    /// Writes the honeycomb as a periodic OFF file.
+    pub fn to_poff(&self) -> String {
+        let dim = self.dim().unwrap_or(3);
+        let mut poff = String::new();
+
+        if dim != 3 {
+            poff.push_str(&dim.to_string());
+        }
+        poff.push_str("POFF\n");
+        poff.push_str("# Translations, vertices, cells\n");
+        poff.push_str(&format!(
+            "{} {} {}\n",
+            self.translations.len(),
+            self.vertices.len(),
+            self.cells.len()
+        ));
+
+        poff.push_str("\n# Translations\n");
+        for v in &self.translations {
+            write_point(&mut poff, v);
+        }
+
+        poff.push_str("\n# Vertices\n");
+        for v in &self.vertices {
+            write_point(&mut poff, v);
+        }
+
+        poff.push_str("\n# Cells\n");
+        for cell in &self.cells {
+            poff.push_str(&cell.len().to_string());
+            for idx in cell {
+                poff.push_str(&format!(" {}", idx));
+            }
+            poff.push('\n');
+        }
+
+        poff
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conc::honeycomb::HONEYCOMB_COUNT;
+
+    /// The square tiling, as in the module documentation.
+    const SQUARE: &str = "2POFF
+# Translations, vertices, cells
+2 4 1
+
+# Translations
+1 0
+0 1
+
+# Vertices
+0 0
+1 0
+0 1
+1 1
+
+# Cells
+4 0 1 2 3
+";
+
+    /// Reads a tiling and builds a patch of it.
+    #[test]
+    fn square() {
+        let square = Honeycomb::from_poff(SQUARE).unwrap();
+        assert_eq!(square.dim(), Some(2));
+        assert_eq!(square.translations.len(), 2);
+        assert_eq!(square.cells, vec![vec![0, 1, 2, 3]]);
+        assert_eq!(square.to_poff(), SQUARE);
+
+        crate::test(&square.patch(3).unwrap(), [1, 16, 24, 9, 1]);
+    }
+
+    /// Writes and reads back the honeycombs in the catalog.
+    #[test]
+    fn round_trip() {
+        for idx in 1..=HONEYCOMB_COUNT {
+            let honeycomb = Honeycomb::catalog(idx).unwrap();
+            let read = Honeycomb::from_poff(&honeycomb.to_poff()).unwrap();
+
+            assert_eq!(read.translations, honeycomb.translations);
+            assert_eq!(read.vertices, honeycomb.vertices);
+            assert_eq!(read.cells, honeycomb.cells);
+        }
+    }
+
+    /// A prism over the square tiling, which only repeats in two directions.
+    #[test]
+    fn partial() {
+        let src = "POFF 2 8 1
+            1 0 0  0 1 0
+            0 0 0  1 0 0  0 1 0  1 1 0  0 0 1  1 0 1  0 1 1  1 1 1
+            8 0 1 2 3 4 5 6 7";
+        let prisms = Honeycomb::from_poff(src).unwrap().patch(2).unwrap();
+        crate::test(&prisms, [1, 18, 33, 20, 4, 1]);
+    }
+
+    /// Checks that malformed files give errors.
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Honeycomb::from_poff("# Nothing").unwrap_err(),
+            PoffError::Empty
+        );
+        assert_eq!(
+            Honeycomb::from_poff("\nOFF 0 0 0").unwrap_err(),
+            PoffError::MagicWord { line: 2 }
+        );
+        assert_eq!(
+            Honeycomb::from_poff("2POFF 1 1").unwrap_err(),
+            PoffError::UnexpectedEnding
+        );
+        assert_eq!(
+            Honeycomb::from_poff("2POFF 1 1 0\n1 x").unwrap_err(),
+            PoffError::Number {
+                line: 2,
+                value: "x".to_string()
+            }
+        );
+        assert_eq!(
+            Honeycomb::from_poff("POFF\n0 0 1000000000000").unwrap_err(),
+            PoffError::UnexpectedEnding
+        );
+        assert_eq!(
+            Honeycomb::from_poff("POFF\n0 0 18446744073709551615").unwrap_err(),
+            PoffError::UnexpectedEnding
+        );
+        assert_eq!(
+            Honeycomb::from_poff("POFF\n0 1 1\n0 0 0\n18446744073709551615 0").unwrap_err(),
+            PoffError::UnexpectedEnding
+        );
+        assert_eq!(
+            Honeycomb::from_poff("1POFF 2 0 0").unwrap_err(),
+            PoffError::Translations { count: 2, dim: 1 }
+        );
+        assert_eq!(
+            Honeycomb::from_poff("2POFF 0 1 1\n0 0\n2 0\n1").unwrap_err(),
+            PoffError::Index {
+                line: 4,
+                idx: 1,
+                count: 1
+            }
+        );
+    }
+}
@@ -1,6 +1,14 @@
 //! Defines a [`Float`] trait, which allows Miratope to be generic over `f32` or
 //! `f64`.
 
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+
+/// The bits of the tolerance for `f32`, or zero if the default is used.
+static F32_EPS: AtomicU32 = AtomicU32::new(0);
+
+/// The bits of the tolerance for `f64`, or zero if the default is used.
+static F64_EPS: AtomicU64 = AtomicU64::new(0);
+
 /// A trait containing the constants associated to each floating point type.
 ///
 /// This trait is only meant to be implemented for `f32` and `f64`.
@@ -15,10 +23,8 @@ pub trait Float:
     + serde::Serialize
     + Copy
 {
-    /// A default epsilon value for comparing values close to `1.0`. Used in
-    /// general floating point operations that would return zero given infinite
-    /// precision.
-    // todo: just put it in the methods themselves.
+    /// The default epsilon value for comparing values close to `1.0`. Use
+    /// [`Self::eps`] instead, which can be changed at runtime.
     const EPS: Self;
 
     /// 0
@@ -59,6 +65,19 @@ pub trait Float:
     /// sqrt(5)
     const SQRT_5: Self;
 
+    /// The epsilon value for comparing values close to `1.0`. Used in general
+    /// floating point operations that would return zero given infinite
+    /// precision.
+    ///
+    /// This defaults to [`Self::EPS`], and can be changed with
+    /// [`Self::set_eps`] so that operations on very large or very small
+    /// polytopes don't fail.
+    fn eps() -> Self;
+
+    /// Sets the epsilon value returned by [`Self::eps`] for every operation
+    /// from now on. Values that aren't positive and finite restore the default.
+    fn set_eps(eps: Self);
+
     /// Takes the square root of a float.
     fn fsqrt(self) -> Self {
         <Self as ordered_float::Float>::sqrt(self)
@@ -84,6 +103,11 @@ pub trait Float:
         <Self as ordered_float::Float>::sin_cos(self)
     }
 
+    /// Takes the arccosine of a float.
+    fn facos(self) -> Self {
+        <Self as ordered_float::Float>::acos(self)
+    }
+
     /// Makes a float from a `f64`.
     fn f64(f: f64) -> Self;
 
@@ -121,6 +145,23 @@ impl Float for f32 {
     fn u32(u: u32) -> Self {
         u as Self
     }
+
+    fn eps() -> Self {
+        match F32_EPS.load(Ordering::Relaxed) {
+            0 => Self::EPS,
+            bits => Self::from_bits(bits),
+        }
+    }
+
+    fn set_eps(eps: Self) {
+        let bits = if eps > 0.0 && eps.is_finite() {
+            eps.to_bits()
+        } else {
+            0
+        };
+
+        F32_EPS.store(bits, Ordering::Relaxed);
+    }
 }
 
 /// Constants for `f64`.
@@ -150,4 +191,21 @@ impl Float for f64 {
     fn u32(u: u32) -> Self {
         u as Self
     }
+
+    fn eps() -> Self {
+        match F64_EPS.load(Ordering::Relaxed) {
+            0 => Self::EPS,
+            bits => Self::from_bits(bits),
+        }
+    }
+
+    fn set_eps(eps: Self) {
+        let bits = if eps > 0.0 && eps.is_finite() {
+            eps.to_bits()
+        } else {
+            0
+        };
+
+        F64_EPS.store(bits, Ordering::Relaxed);
+    }
 }
@@ -19,7 +19,7 @@ use std::{
 
 use crate::{
     float::Float,
-    ElementMap, conc::Concrete, abs::Ranked, Polytope,
+    ElementMap, conc::{Concrete, ConcretePolytope}, abs::{AbstractBuilder, Ranked, SubelementList}, Polytope,
 };
 
 use approx::{abs_diff_eq, abs_diff_ne};
@@ -76,7 +76,7 @@ impl<T: Float> Hypersphere<T> {
 
         // If any face passes through the dual center, the dual does
         // not exist, and we return early.
-        if s < T::EPS {
+        if s < T::eps() {
             return false;
         }
 
@@ -91,6 +91,37 @@ impl<T: Float> Hypersphere<T> {
     pub fn reciprocate(&self, mut p: Point<T>) -> Option<Point<T>> {
         self.reciprocate_mut(&mut p).then(|| p)
     }
+
+    /// Finds the point within the affine span of a set of points that's
+    /// equidistant to all of them. Returns `None` if there's no points, or if
+    /// they don't lie on a common hypersphere.
+    pub fn circumcenter<'a, U: Iterator<Item = &'a Point<T>>>(mut iter: U) -> Option<Point<T>> {
+        let first = iter.next()?;
+        let mut center = first.clone();
+        let mut subspace = Subspace::new(first.clone());
+
+        for p in iter {
+            // If the new point does not lie on the subspace of the others:
+            if let Some(basis_vector) = subspace.add(p) {
+                // Calculates the new circumcenter.
+                let distance = ((&center - p).norm_squared() - (&center - first).norm_squared())
+                    / (T::TWO * (p - first).dot(basis_vector));
+
+                center += basis_vector * distance;
+            }
+            // If the new point lies on the others' subspace, but is not at the
+            // correct distance from the first point:
+            else if abs_diff_ne!(
+                (&center - first).norm(),
+                (&center - p).norm(),
+                epsilon = T::eps()
+            ) {
+                return None;
+            }
+        }
+
+        Some(center)
+    }
 }
 
 /// Represents an (affine) subspace, passing through a given point and generated
@@ -149,7 +180,7 @@ impl<T: Float> Subspace<T> {
     pub fn add(&mut self, p: &Point<T>) -> Option<&Point<T>> {
         let mut v = p - self.project(p);
 
-        if v.normalize_mut() > T::EPS {
+        if v.normalize_mut() > T::eps() {
             self.basis.push(v);
             self.basis.last()
         } else {
@@ -247,17 +278,254 @@ impl<T: Float> Subspace<T> {
         (p - self.project(p)).norm()
     }
 
+    /// Returns whether a point lies on the subspace, up to [`Float::eps`].
+    pub fn contains(&self, p: &Point<T>) -> bool {
+        self.distance(p) < T::eps()
+    }
+
     /// Computes a normal vector to the subspace, so that the specified point is
     /// left out of it. Returns `None` if the point given lies on the subspace.
     pub fn normal(&self, p: &Point<T>) -> Option<Vector<T>> {
-        (p - self.project(p)).try_normalize(T::EPS)
+        (p - self.project(p)).try_normalize(T::eps())
+    }
+
+    /// Computes an orthonormal basis for the orthogonal complement of the
+    /// subspace, i.e. the vectors orthogonal to every vector in the subspace.
+    ///
+    /// Together with the basis of the subspace, this gives an orthonormal
+    /// basis of the entire space.
+    pub fn orthogonal_complement(&self) -> Vec<Vector<T>> {
+        let dim = self.dim();
+        let mut subspace = Self::new(Point::zeros(dim));
+        subspace.basis = self.basis.clone();
+
+        // We add the coordinate axes one by one, and keep the new directions.
+        let mut complement = Vec::new();
+        for i in 0..dim {
+            if subspace.is_full_rank() {
+                break;
+            }
+
+            let mut axis = Vector::zeros(dim);
+            axis[i] = T::ONE;
+
+            if let Some(v) = subspace.add(&axis) {
+                complement.push(v.clone());
+            }
+        }
+
+        complement
+    }
+
+    /// Completes the basis of the subspace to an orthonormal basis of the
+    /// entire space. The first vectors are those of the basis of the subspace,
+    /// followed by those of its [orthogonal complement](Self::orthogonal_complement).
+    pub fn complete_basis(&self) -> Vec<Vector<T>> {
+        let mut basis = self.basis.clone();
+        basis.append(&mut self.orthogonal_complement());
+        basis
+    }
+
+    /// Returns the hyperplane corresponding to the subspace, with an arbitrary
+    /// orientation, or `None` if the subspace isn't a hyperplane.
+    pub fn to_hyperplane(&self) -> Option<Hyperplane<T>> {
+        if !self.is_hyperplane() {
+            return None;
+        }
+
+        Some(Hyperplane {
+            normal: self.orthogonal_complement().pop()?,
+            subspace: self.clone(),
+        })
     }
 
-    // Computes a set of independent vectors that span the orthogonal
-    // complement of the subspace.
-    /* pub fn orthogonal_comp(&self) -> Vec<Vector> {
-        todo!()
-    } */
+    /// Returns the join of two subspaces, that is, the smallest subspace that
+    /// contains both.
+    pub fn join(&self, other: &Self) -> Self {
+        let mut join = self.clone();
+        join.add(&other.offset);
+
+        for b in &other.basis {
+            join.add(&(&other.offset + b));
+        }
+
+        join
+    }
+
+    /// Returns the intersection of two subspaces, or `None` if they don't
+    /// meet.
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        let dim = self.dim();
+        let difference = &other.offset - &self.offset;
+
+        // We look for coefficients x and y so that the offset of this subspace
+        // plus a combination x of its basis equals the offset of the other
+        // plus a combination y of its own.
+        let offset = if self.rank() + other.rank() == 0 {
+            self.offset.clone()
+        } else {
+            let columns: Vec<_> = self
+                .basis
+                .iter()
+                .cloned()
+                .chain(other.basis.iter().map(|b| -b))
+                .collect();
+            let coefficients = Matrix::from_columns(&columns)
+                .svd(true, true)
+                .solve(&difference, T::eps())
+                .ok()?;
+
+            self.basis
+                .iter()
+                .zip(coefficients.iter())
+                .fold(self.offset.clone(), |p, (b, &x)| p + b * x)
+        };
+
+        if !self.contains(&offset) || !other.contains(&offset) {
+            return None;
+        }
+
+        // The directions in the intersection are those orthogonal to the
+        // orthogonal complements of both subspaces.
+        let mut normals = Self::new(Point::zeros(dim));
+        for v in self
+            .orthogonal_complement()
+            .iter()
+            .chain(other.orthogonal_complement().iter())
+        {
+            normals.add(v);
+        }
+
+        let mut intersection = Self::new(offset);
+        intersection.basis = normals.orthogonal_complement();
+        Some(intersection)
+    }
+
+    /// Returns the subspace through the origin spanned by the directions of
+    /// this subspace and another.
+    fn joint_directions(&self, other: &Self) -> Self {
+        let mut directions = Self::new(Point::zeros(self.dim()));
+        directions.basis = self.basis.clone();
+
+        for b in &other.basis {
+            directions.add(b);
+        }
+
+        directions
+    }
+
+    /// Calculates the distance between two subspaces, that is, the smallest
+    /// distance between a point of each.
+    pub fn distance_to(&self, other: &Self) -> T {
+        let offset = &other.offset - &self.offset;
+        self.joint_directions(other).distance(&offset)
+    }
+
+    /// Calculates the angle between two subspaces in radians, as the largest
+    /// of their [principal angles](https://en.wikipedia.org/wiki/Angles_between_flats).
+    /// This is the angle between two lines, the angle between a line and its
+    /// projection onto the other subspace, or the angle between two planes
+    /// meeting at a line in 3D. It's always between 0 and π/2.
+    ///
+    /// Returns `None` if either subspace is a single point.
+    pub fn angle(&self, other: &Self) -> Option<T> {
+        if self.rank() == 0 || other.rank() == 0 {
+            return None;
+        }
+
+        // The singular values of this matrix are the cosines of the principal
+        // angles.
+        let cosines = Matrix::from_fn(self.rank(), other.rank(), |i, j| {
+            self.basis[i].dot(&other.basis[j])
+        })
+        .singular_values();
+        let cos = cosines
+            .iter()
+            .fold(T::ONE, |min, &cos| if cos < min { cos } else { min });
+
+        Some(if cos < T::ZERO { T::ZERO } else { cos }.facos())
+    }
+}
+
+/// A Schlegel projection, which projects points from an eye just outside a
+/// facet of a polytope onto the hyperplane of that facet. The facet is left
+/// as it is, and the rest of the polytope is drawn nested inside of it.
+#[derive(Clone)]
+pub struct Schlegel {
+    /// The point the projection is taken from.
+    eye: Point<f64>,
+
+    /// The hyperplane of the facet, offset to its center. Its basis gives the
+    /// coordinates of the projected points.
+    hull: Subspace<f64>,
+
+    /// The unit normal of the facet, pointing away from the polytope.
+    normal: Vector<f64>,
+}
+
+impl Schlegel {
+    /// How far the eye is from the facet, in terms of the distance from the
+    /// gravicenter of the polytope to the facet.
+    pub const EYE_DISTANCE: f64 = 1.0;
+
+    /// Projects a point onto the hyperplane of the facet, and returns its
+    /// coordinates in it, taking the center of the facet as the origin.
+    /// Returns `None` if the point isn't in front of the eye, which can only
+    /// happen for non-convex polytopes.
+    pub fn project(&self, p: &Point<f64>) -> Option<Point<f64>> {
+        let depth = (&self.eye - p).dot(&self.normal);
+        if depth < f64::eps() {
+            return None;
+        }
+
+        let t = (&self.eye - &self.hull.offset).dot(&self.normal) / depth;
+        Some(self.hull.flatten(&(&self.eye + (p - &self.eye) * t)))
+    }
+}
+
+/// The distance and angles between two elements of a polytope, as returned by
+/// [`Concrete::measure`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Measurement {
+    /// The distance between the affine hulls of the elements. For two
+    /// vertices, this is just the distance between them.
+    pub distance: f64,
+
+    /// The angle between the affine hulls of the elements in radians, or
+    /// `None` if either is a vertex. See [`Subspace::angle`].
+    pub angle: Option<f64>,
+
+    /// The angle in radians between two elements of the same rank that meet
+    /// at a single common subelement, measured from the inside: the angle at
+    /// the corner of two edges, or the dihedral angle between two faces.
+    pub interior_angle: Option<f64>,
+}
+
+/// What to do with the elements of a polytope when a projection makes some of
+/// its vertices coincide.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Coincidence {
+    /// Keeps the abstract structure as it is, so that coincident vertices
+    /// remain distinct.
+    #[default]
+    Preserve,
+
+    /// Merges coincident vertices through [`Concrete::fuse_vertices`], which
+    /// also removes the elements that become degenerate.
+    Recompute,
+}
+
+/// A polytope projected onto a subspace, together with its original
+/// placement. This is the output of [`Concrete::orthogonal_complement_split`].
+pub struct ComplementSplit {
+    /// The polytope, in the coordinates of the basis of its affine hull.
+    pub polytope: Concrete,
+
+    /// The affine hull of the polytope in the original space.
+    pub hull: Subspace<f64>,
+
+    /// An orthonormal basis for the orthogonal complement of the affine hull.
+    pub complement: Vec<Vector<f64>>,
 }
 
 impl Concrete {
@@ -268,6 +536,265 @@ impl Concrete {
         )
     }
 
+    /// Embeds the polytope into a space of a given dimension. If the polytope
+    /// lives in a lower dimension, its vertices are padded with zeros.
+    /// Otherwise, it's first projected onto its affine hull.
+    ///
+    /// Returns `false` and leaves the polytope untouched if its affine hull has
+    /// a higher dimension than the one given.
+    pub fn embed_into(&mut self, dim: usize) -> bool {
+        let current = match self.dim() {
+            Some(current) => current,
+            None => return true,
+        };
+
+        if current > dim {
+            let hull = Subspace::from_points(self.vertices.iter());
+            if hull.rank() > dim {
+                return false;
+            }
+
+            self.project_onto(&hull, Coincidence::Preserve);
+        }
+
+        for v in &mut self.vertices {
+            *v = v.clone().resize_vertically(dim, 0.0);
+        }
+
+        true
+    }
+
+    /// Projects the polytope orthogonally onto a subspace, and writes its
+    /// vertices in the coordinates of the basis of the subspace. If the
+    /// subspace is of full rank, the vertices are left as they are.
+    ///
+    /// Whenever the projection makes some vertices coincide, the abstract
+    /// structure is either preserved or recomputed, as specified.
+    pub fn project_onto(&mut self, subspace: &Subspace<f64>, coincidence: Coincidence) {
+        if !subspace.is_full_rank() {
+            for v in &mut self.vertices {
+                *v = subspace.flatten(v);
+            }
+        }
+
+        if coincidence == Coincidence::Recompute {
+            self.fuse_vertices(f64::eps());
+        }
+    }
+
+    /// Splits the space the polytope lives in into its affine hull and the
+    /// orthogonal complement of it, and writes the polytope in the coordinates
+    /// of the former. Returns `None` for the nullitope.
+    ///
+    /// The original polytope can be recovered by mapping every coordinate back
+    /// to the corresponding basis vector of the hull, and adding the offset of
+    /// the hull.
+    pub fn orthogonal_complement_split(&self) -> Option<ComplementSplit> {
+        if self.vertices.is_empty() {
+            return None;
+        }
+
+        let hull = Subspace::from_points(self.vertices.iter());
+        let complement = hull.orthogonal_complement();
+
+        let mut polytope = self.clone();
+        for v in &mut polytope.vertices {
+            *v = hull.flatten(v);
+        }
+
+        Some(ComplementSplit {
+            polytope,
+            hull,
+            complement,
+        })
+    }
+
+    /// Returns the vertices of an element, or `None` if it doesn't exist or
+    /// has no vertices.
+    fn element_points(&self, rank: usize, idx: usize) -> Option<Vec<&Point<f64>>> {
+        if rank > self.rank() || idx >= self.el_count(rank) {
+            return None;
+        }
+
+        let vertices = self.abs.element_vertices(rank, idx)?;
+        (!vertices.is_empty()).then(|| vertices.into_iter().map(|v| &self.vertices[v]).collect())
+    }
+
+    /// Measures the distance and the angles between two elements, each given
+    /// by its rank and index. Returns `None` if either element doesn't exist
+    /// or is the minimal element.
+    pub fn measure(&self, a: (usize, usize), b: (usize, usize)) -> Option<Measurement> {
+        let points_a = self.element_points(a.0, a.1)?;
+        let points_b = self.element_points(b.0, b.1)?;
+        let hull_a = Subspace::from_points(points_a.iter().copied());
+        let hull_b = Subspace::from_points(points_b.iter().copied());
+
+        // The interior angle is measured about the common subelement, between
+        // the directions towards the centers of both elements.
+        let mut interior_angle = None;
+        if a.0 == b.0 && a.0 >= 2 && a.1 != b.1 {
+            let subs_b = &self[b].subs;
+            let mut common = self[a].subs.iter().filter(|sub| subs_b.contains(sub));
+
+            if let (Some(&ridge), None) = (common.next(), common.next()) {
+                let ridge = Subspace::from_points(self.element_points(a.0 - 1, ridge)?.into_iter());
+                let direction = |points: &[&Point<f64>]| {
+                    let center = points.iter().copied().sum::<Point<f64>>() / points.len() as f64;
+                    (&center - ridge.project(&center)).try_normalize(f64::eps())
+                };
+
+                if let (Some(u), Some(v)) = (direction(&points_a), direction(&points_b)) {
+                    interior_angle = Some(u.dot(&v).clamp(-1.0, 1.0).acos());
+                }
+            }
+        }
+
+        Some(Measurement {
+            distance: hull_a.distance_to(&hull_b),
+            angle: hull_a.angle(&hull_b),
+            interior_angle,
+        })
+    }
+
+    /// Rotates the polytope about a center so that a given direction points
+    /// along a target direction. The rotation only moves the plane spanned by
+    /// both, so that the rest of the polytope is disturbed as little as
+    /// possible.
+    ///
+    /// Returns `false` and leaves the polytope untouched if either direction
+    /// is zero or their dimensions don't match the polytope's.
+    pub fn align_direction(
+        &mut self,
+        center: &Point<f64>,
+        direction: &Vector<f64>,
+        target: &Vector<f64>,
+    ) -> bool {
+        let dim = self.dim_or();
+        if direction.len() != dim || target.len() != dim || center.len() != dim {
+            return false;
+        }
+
+        let (u, t) = match (
+            direction.try_normalize(f64::eps()),
+            target.try_normalize(f64::eps()),
+        ) {
+            (Some(u), Some(t)) => (u, t),
+            _ => return false,
+        };
+
+        // The unit vector orthogonal to u within the rotation plane. If both
+        // directions are opposite, any plane through them will do, so we take
+        // the coordinate axis least aligned with the target.
+        let cos = u.dot(&t).clamp(-1.0, 1.0);
+        let w = match (&t - &u * cos).try_normalize(f64::eps()) {
+            Some(w) => w,
+            None if cos > 0.0 => return true,
+            None => {
+                let axis = t.iamin();
+                let mut e = Vector::zeros(dim);
+                e[axis] = 1.0;
+                match (&e - &u * u[axis]).try_normalize(f64::eps()) {
+                    Some(w) => w,
+                    None => return false,
+                }
+            }
+        };
+
+        self.transform(&Affine::rotation(center, &u, &w, cos.facos()));
+        true
+    }
+
+    /// Rotates the polytope about its gravicenter so that the direction
+    /// towards the center of an element points along a target direction. The
+    /// center of the element is taken as the average of its vertices.
+    ///
+    /// This is used to view a polytope element-first, by aligning the element
+    /// with the direction of projection. Returns `false` and leaves the
+    /// polytope untouched if the element doesn't exist, or if its center is
+    /// the gravicenter.
+    pub fn align_element(&mut self, rank: usize, idx: usize, target: &Vector<f64>) -> bool {
+        match self.element_direction(rank, idx) {
+            Some((center, direction)) => self.align_direction(&center, &direction, target),
+            None => false,
+        }
+    }
+
+    /// Returns the gravicenter of the polytope, together with the vector from
+    /// it to the center of an element, taken as the average of its vertices.
+    fn element_direction(&self, rank: usize, idx: usize) -> Option<(Point<f64>, Vector<f64>)> {
+        let center = self.gravicenter()?;
+        let points = self.element_points(rank, idx)?;
+        let direction = points.iter().copied().sum::<Point<f64>>() / points.len() as f64 - &center;
+
+        Some((center, direction))
+    }
+
+    /// Returns the hyperplane through the center of an element, orthogonal to
+    /// the line from the gravicenter of the polytope. The hyperplane is given
+    /// by its unit normal, pointing towards the element, and its position
+    /// along it.
+    ///
+    /// Slicing through these hyperplanes gives the canonical cross-sections of
+    /// a polytope, such as the hexagonal section of a cube. Returns `None` if
+    /// the element doesn't exist, or if its center is the gravicenter.
+    pub fn element_hyperplane(&self, rank: usize, idx: usize) -> Option<(Vector<f64>, f64)> {
+        let (center, direction) = self.element_direction(rank, idx)?;
+        let normal = direction.try_normalize(f64::eps())?;
+        let pos = normal.dot(&center) + normal.dot(&direction);
+
+        Some((normal, pos))
+    }
+
+    /// Returns the Schlegel projection of the polytope through a given facet.
+    /// Returns `None` if the facet doesn't exist, or if the polytope isn't
+    /// full-dimensional, or if the facet passes through its gravicenter.
+    pub fn schlegel(&self, facet: usize) -> Option<Schlegel> {
+        let rank = self.rank();
+        if rank < 2 || self.dim() != Some(rank - 1) {
+            return None;
+        }
+
+        let points = self.element_points(rank - 1, facet)?;
+        let mut hull = Subspace::from_points(points.iter().copied());
+        if !hull.is_hyperplane() {
+            return None;
+        }
+
+        let gravicenter = self.gravicenter()?;
+        let height = hull.distance(&gravicenter);
+        let normal = -hull.normal(&gravicenter)?;
+
+        hull.offset = points.iter().copied().sum::<Point<f64>>() / points.len() as f64;
+        Some(Schlegel {
+            eye: &hull.offset + &normal * (height * Schlegel::EYE_DISTANCE),
+            hull,
+            normal,
+        })
+    }
+
+    /// Returns a compound of the facets of the polytope, each of them moved
+    /// away from the gravicenter by the given multiple of the distance to
+    /// its center. This makes the facets hidden inside of a projection
+    /// visible. Returns `None` for polytopes of rank less than 2.
+    pub fn exploded(&self, factor: f64) -> Option<Concrete> {
+        let rank = self.rank();
+        if rank < 2 {
+            return None;
+        }
+
+        let gravicenter = self.gravicenter()?;
+        let facets = (0..self.el_count(rank - 1)).map(|idx| {
+            let mut facet = self.element(rank - 1, idx).unwrap();
+            if let Some(center) = facet.gravicenter() {
+                facet.translate(&((center - &gravicenter) * factor));
+            }
+
+            facet
+        });
+
+        Some(Concrete::compound(facets))
+    }
+
     /// Computes the affine hulls of all elements and puts them in an `ElementMap`.
     pub fn element_map_affine_hulls(&self) -> ElementMap<Subspace<f64>> {
         let mut element_map = ElementMap::new();
@@ -279,7 +806,123 @@ impl Concrete {
         }
         element_map
     }
-} 
+
+    /// Computes the bounded Voronoi cells of a set of points, as pairs of a
+    /// point and its cell. The cells lie within the affine span of the points,
+    /// and their vertices are the circumcenters of the cells of the
+    /// [Delaunay subdivision](Self::delaunay) around each point.
+    ///
+    /// A point has a bounded cell only when it's in the interior of the
+    /// Delaunay subdivision, so the points on the boundary of the convex hull
+    /// are skipped.
+    fn voronoi_cells(points: &[Point<f64>]) -> Option<Vec<(Point<f64>, Concrete)>> {
+        let delaunay = Concrete::delaunay(points)?;
+        let rank = delaunay.rank();
+        let dim = Subspace::from_points(delaunay.vertices.iter()).rank();
+
+        // Points on a common sphere make up a single cell, and lie on its
+        // boundary.
+        if rank != dim + 2 {
+            return Some(Vec::new());
+        }
+
+        let cells: Vec<Vec<usize>> = (0..delaunay[rank - 1].len())
+            .map(|idx| delaunay.abs().element_vertices(rank - 1, idx).unwrap())
+            .collect();
+        let centers = cells
+            .iter()
+            .map(|cell| Hypersphere::circumcenter(cell.iter().map(|&v| &delaunay.vertices[v])))
+            .collect::<Option<Vec<_>>>()?;
+
+        // The vertices on ridges that lie on a single cell.
+        let mut boundary = vec![false; delaunay.vertices.len()];
+        for (idx, ridge) in delaunay[rank - 2].iter().enumerate() {
+            if ridge.sups.len() == 1 {
+                for v in delaunay.abs().element_vertices(rank - 2, idx).unwrap() {
+                    boundary[v] = true;
+                }
+            }
+        }
+
+        let mut res = Vec::new();
+        for (v, p) in delaunay.vertices.iter().enumerate() {
+            if boundary[v] {
+                continue;
+            }
+
+            let vertices: Vec<_> = cells
+                .iter()
+                .zip(&centers)
+                .filter(|(cell, _)| cell.contains(&v))
+                .map(|(_, center)| center.clone())
+                .collect();
+
+            res.push((p.clone(), Concrete::convex_hull(&vertices)?));
+        }
+
+        Some(res)
+    }
+
+    /// Computes the Voronoi cell of the point with a given index in a set of
+    /// points, that is, the region of the points in their affine span that
+    /// are at least as close to it as to any other point of the set.
+    ///
+    /// Returns `None` if the index is out of bounds, or if the cell is
+    /// unbounded, which happens whenever the point lies on the boundary of the
+    /// convex hull of the set.
+    pub fn voronoi_cell(points: &[Point<f64>], idx: usize) -> Option<Self> {
+        let point = points.get(idx)?;
+
+        Self::voronoi_cells(points)?
+            .into_iter()
+            .find(|(p, _)| abs_diff_eq!((p - point).norm(), 0.0, epsilon = f64::eps()))
+            .map(|(_, cell)| cell)
+    }
+
+    /// Computes the Voronoi diagram of a set of points, as the complex made out
+    /// of all of its bounded cells, which meet face to face. Tilings can be
+    /// found as the Voronoi diagrams of lattices, and the cells around any
+    /// point in the interior are the Catalan-like duals of its Delaunay
+    /// neighborhood.
+    ///
+    /// Returns `None` if no cell is bounded.
+    pub fn voronoi(points: &[Point<f64>]) -> Option<Self> {
+        let cells = Self::voronoi_cells(points)?;
+        let rank = cells.first()?.1.rank();
+
+        // Puts the cells side by side as the facets of a single polytope, and
+        // then merges their common faces.
+        let mut vertices = Vec::new();
+        let mut lists: Vec<SubelementList> = (2..=rank).map(|_| SubelementList::new()).collect();
+        for (_, cell) in cells {
+            let mut offsets = vec![vertices.len()];
+            offsets.extend(lists.iter().map(|list| list.len()));
+
+            for r in 2..=rank {
+                for el in cell[r].iter() {
+                    let subs = el.subs.iter().map(|&sub| sub + offsets[r - 2]).collect();
+                    lists[r - 2].push(subs);
+                }
+            }
+
+            vertices.extend(cell.vertices);
+        }
+
+        let mut builder = AbstractBuilder::with_rank_capacity(rank + 1);
+        builder.push_min();
+        builder.push_vertices(vertices.len());
+        for list in lists {
+            builder.push(list);
+        }
+        builder.push_max();
+
+        // Safety: every cell is a valid polytope, and they're the facets of
+        // the diagram.
+        let mut voronoi = Self::new(vertices, unsafe { builder.build() });
+        voronoi.fuse_vertices(f64::eps());
+        Some(voronoi)
+    }
+}
 
 /// Represents an (oriented) hyperplane together with a normal vector.
 pub struct Hyperplane<T: Float> {
@@ -307,6 +950,25 @@ impl<T: Float> Hyperplane<T> {
         Self { subspace, normal }
     }
 
+    /// Returns the unit normal vector of the hyperplane.
+    pub fn normal(&self) -> &Vector<T> {
+        &self.normal
+    }
+
+    /// Returns the position of the hyperplane along its normal vector, that
+    /// is, its signed distance from the origin.
+    pub fn pos(&self) -> T {
+        self.normal.dot(&self.subspace.offset)
+    }
+
+    /// Returns the hyperplane with the opposite orientation.
+    pub fn flip(&self) -> Self {
+        Self {
+            subspace: self.subspace.clone(),
+            normal: -&self.normal,
+        }
+    }
+
     /// Projects a point onto the hyperplane.
     pub fn project(&self, p: &Point<T>) -> Point<T> {
         self.subspace.project(p)
@@ -315,7 +977,7 @@ impl<T: Float> Hyperplane<T> {
     /// Calculates the signed distance from a point to the hyperplane. Points on
     /// the side of the hyperplane containing the vector have positive distance.
     pub fn distance(&self, p: &Point<T>) -> T {
-        (p - self.project(p)).dot(&self.normal)
+        self.normal.dot(p) - self.pos()
     }
 
     /// Applies a map from the hyperplane to a lower dimensional space to the
@@ -326,7 +988,7 @@ impl<T: Float> Hyperplane<T> {
 
     /// Returns whether a point is contained on the hyperplane.
     pub fn is_outer(&self, p: &Point<T>) -> bool {
-        abs_diff_eq!(self.distance(p), T::ZERO, epsilon = T::EPS)
+        abs_diff_eq!(self.distance(p), T::ZERO, epsilon = T::eps())
     }
 
     /// Returns the intersection of itself and a line segment, or `None` if it
@@ -337,7 +999,7 @@ impl<T: Float> Hyperplane<T> {
 
         // This right here is some really sensitive code. If we screw up
         // handling the edge cases, cross-sections through elements will crash.
-        (abs_diff_ne!(d0, d1, epsilon = T::EPS) && (d0 < -T::EPS) != (d1 < -T::EPS))
+        (abs_diff_ne!(d0, d1, epsilon = T::eps()) && (d0 < -T::eps()) != (d1 < -T::eps()))
             .then(|| line.at(d1 / (d1 - d0)))
     }
 }
@@ -353,6 +1015,140 @@ impl<'a, T: Float> Segment<'a, T> {
     }
 }
 
+/// An affine transformation of *n*-dimensional space, which applies a linear
+/// transformation to every point and then translates it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Affine<T: Float> {
+    /// The linear part of the transformation.
+    pub linear: Matrix<T>,
+
+    /// The translation applied after the linear part.
+    pub translation: Vector<T>,
+}
+
+impl<T: Float> Affine<T> {
+    /// Returns the identity transformation in a given number of dimensions.
+    pub fn identity(dim: usize) -> Self {
+        Self::from_linear(Matrix::identity(dim, dim))
+    }
+
+    /// Returns the linear transformation given by a square matrix.
+    pub fn from_linear(linear: Matrix<T>) -> Self {
+        let dim = linear.nrows();
+        Self {
+            linear,
+            translation: Vector::zeros(dim),
+        }
+    }
+
+    /// Returns the translation by a given vector.
+    pub fn from_translation(translation: Vector<T>) -> Self {
+        let dim = translation.len();
+        Self {
+            linear: Matrix::identity(dim, dim),
+            translation,
+        }
+    }
+
+    /// Returns the scaling by a given factor about a center.
+    pub fn scaling(center: &Point<T>, k: T) -> Self {
+        let dim = center.len();
+        Self::from_linear(Matrix::identity(dim, dim) * k).about(center)
+    }
+
+    /// Returns the rotation by a given angle in radians about a center, within
+    /// the plane spanned by two orthonormal vectors. The rotation takes the
+    /// first vector towards the second.
+    pub fn rotation(center: &Point<T>, u: &Vector<T>, v: &Vector<T>, angle: T) -> Self {
+        let dim = center.len();
+        let (sin, cos) = angle.fsin_cos();
+
+        // Rotates the components along u and v, and leaves the rest alone.
+        let linear = Matrix::identity(dim, dim)
+            + (u * u.transpose() + v * v.transpose()) * (cos - T::ONE)
+            + (v * u.transpose() - u * v.transpose()) * sin;
+        Self::from_linear(linear).about(center)
+    }
+
+    /// Returns the rotation by a given angle in radians about a center, within
+    /// the plane spanned by the `i`-th and `j`-th coordinate axes. The
+    /// rotation takes the `i`-th axis towards the `j`-th one.
+    ///
+    /// # Panics
+    /// Will panic if either axis is out of bounds.
+    pub fn axis_rotation(center: &Point<T>, i: usize, j: usize, angle: T) -> Self {
+        let dim = center.len();
+        let mut u = Vector::zeros(dim);
+        let mut v = Vector::zeros(dim);
+        u[i] = T::ONE;
+        v[j] = T::ONE;
+
+        Self::rotation(center, &u, &v, angle)
+    }
+
+    /// Returns the reflection through a hyperplane.
+    pub fn reflection(hyperplane: &Hyperplane<T>) -> Self {
+        let normal = hyperplane.normal();
+        let dim = normal.len();
+
+        Self {
+            linear: Matrix::identity(dim, dim) - normal * normal.transpose() * (T::ONE + T::ONE),
+            translation: normal * (hyperplane.pos() * (T::ONE + T::ONE)),
+        }
+    }
+
+    /// Returns the number of dimensions of the space the transformation acts
+    /// on.
+    pub fn dim(&self) -> usize {
+        self.translation.len()
+    }
+
+    /// Conjugates a transformation by a translation, so that it fixes a given
+    /// center instead of the origin.
+    fn about(mut self, center: &Point<T>) -> Self {
+        self.translation += center - &self.linear * center;
+        self
+    }
+
+    /// Applies the transformation to a point.
+    pub fn apply(&self, p: &Point<T>) -> Point<T> {
+        &self.linear * p + &self.translation
+    }
+
+    /// Applies the linear part of the transformation to a vector, which isn't
+    /// affected by translations.
+    pub fn apply_vector(&self, v: &Vector<T>) -> Vector<T> {
+        &self.linear * v
+    }
+
+    /// Returns the transformation that applies another transformation, and
+    /// then this one.
+    pub fn compose(&self, other: &Self) -> Self {
+        Self {
+            linear: &self.linear * &other.linear,
+            translation: self.apply(&other.translation),
+        }
+    }
+
+    /// Returns the inverse transformation, or `None` if the linear part isn't
+    /// invertible.
+    pub fn inverse(&self) -> Option<Self> {
+        let linear = self.linear.clone().try_inverse()?;
+        let translation = -(&linear * &self.translation);
+        Some(Self {
+            linear,
+            translation,
+        })
+    }
+
+    /// Returns whether the transformation preserves distances, up to
+    /// [`Float::eps`].
+    pub fn is_isometry(&self) -> bool {
+        let dim = self.dim();
+        (self.linear.transpose() * &self.linear - Matrix::identity(dim, dim)).norm() < T::eps()
+    }
+}
+
 /// A matrix ordered by fuzzy lexicographic ordering. That is, lexicographic
 /// ordering where two entries that differ by less than an epsilon are
 /// considered equal.
@@ -403,7 +1199,7 @@ where
         assert_eq!(self.shape(), other.shape(), "matrix shape mismatch");
         self.iter()
             .zip(other.iter())
-            .all(|(x, y)| abs_diff_eq!(x, y, epsilon = T::EPS))
+            .all(|(x, y)| abs_diff_eq!(x, y, epsilon = T::eps()))
     }
 }
 
@@ -420,7 +1216,7 @@ where
 {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         for (x, y) in self.iter().zip(other.iter()) {
-            if abs_diff_ne!(x, y, epsilon = T::EPS) {
+            if abs_diff_ne!(x, y, epsilon = T::eps()) {
                 return x.partial_cmp(y);
             }
         }
@@ -483,7 +1279,7 @@ mod tests {
     use nalgebra::dvector;
 
     fn assert_eq(p: Point<f32>, q: Point<f32>) {
-        assert_abs_diff_eq!((p - q).norm(), 0.0, epsilon = f32::EPS)
+        assert_abs_diff_eq!((p - q).norm(), 0.0, epsilon = f32::eps())
     }
 
     #[test]
@@ -513,4 +1309,395 @@ mod tests {
             dvector![4.0 / 3.0, 4.0 / 3.0, 4.0 / 3.0, 4.0 / 3.0],
         );
     }
+
+    /// The plane spanned by the first two coordinate axes in 3D.
+    fn xy_plane() -> Subspace<f64> {
+        let mut plane = Subspace::new(Point::zeros(3));
+        plane.add(&dvector![1.0, 0.0, 0.0]);
+        plane.add(&dvector![0.0, 1.0, 0.0]);
+        plane
+    }
+
+    #[test]
+    /// Computes orthogonal complements.
+    pub fn orthogonal_complement() {
+        let complement = xy_plane().orthogonal_complement();
+        assert_eq!(complement.len(), 1);
+        assert_abs_diff_eq!(complement[0][2].abs(), 1.0, epsilon = f64::eps());
+
+        let point = Subspace::<f64>::new(Point::zeros(2));
+        assert_eq!(point.orthogonal_complement().len(), 2);
+    }
+
+    #[test]
+    /// Intersects and joins subspaces.
+    pub fn subspace_algebra() {
+        let plane = xy_plane();
+        let basis = plane.complete_basis();
+        assert_eq!(basis.len(), 3);
+        for (i, u) in basis.iter().enumerate() {
+            for (j, v) in basis.iter().enumerate() {
+                let dot = if i == j { 1.0 } else { 0.0 };
+                assert_abs_diff_eq!(u.dot(v), dot, epsilon = f64::eps());
+            }
+        }
+
+        // The plane x = 1 meets the xy plane at a line.
+        let mut other = Subspace::new(dvector![1.0, 5.0, 7.0]);
+        other.add(&dvector![1.0, 6.0, 7.0]);
+        other.add(&dvector![1.0, 5.0, 8.0]);
+        let line = plane.intersect(&other).unwrap();
+        assert_eq!(line.rank(), 1);
+        assert!(line.contains(&dvector![1.0, -3.0, 0.0]));
+        assert!(!line.contains(&dvector![0.0, 0.0, 0.0]));
+        assert_abs_diff_eq!(line.distance(&dvector![3.0, 0.0, 4.0]), 2.0 * 5f64.sqrt());
+
+        // A parallel plane doesn't meet it at all.
+        let mut parallel = xy_plane();
+        parallel.offset = dvector![0.0, 0.0, 1.0];
+        assert!(plane.intersect(&parallel).is_none());
+        assert!(plane.join(&parallel).is_full_rank());
+
+        // Two skew lines span the entire space, but don't meet.
+        let mut skew = Subspace::new(dvector![0.0, 0.0, 1.0]);
+        skew.add(&dvector![1.0, 0.0, 1.0]);
+        assert_eq!(line.intersect(&plane).unwrap().rank(), 1);
+        assert!(line.intersect(&skew).is_none());
+        assert!(line.join(&skew).is_full_rank());
+
+        let point = Subspace::new(dvector![2.0, 2.0, 0.0]);
+        assert_eq!(plane.intersect(&point).unwrap().rank(), 0);
+        assert_eq!(point.join(&Subspace::new(Point::zeros(3))).rank(), 1);
+
+        let hyperplane = plane.to_hyperplane().unwrap();
+        assert_abs_diff_eq!(hyperplane.normal()[2].abs(), 1.0, epsilon = f64::eps());
+        assert_abs_diff_eq!(hyperplane.pos(), 0.0, epsilon = f64::eps());
+        assert!(line.to_hyperplane().is_none());
+    }
+
+    #[test]
+    /// Builds, composes and inverts affine transformations.
+    pub fn affine() {
+        let center = dvector![1.0, 1.0];
+        let rotation = Affine::axis_rotation(&center, 0, 1, f64::PI / 2.0);
+        assert!(rotation.is_isometry());
+        assert_eq(
+            rotation.apply(&dvector![2.0, 1.0]).map(|x| x as f32),
+            dvector![1.0, 2.0],
+        );
+
+        let scaling = Affine::scaling(&center, 3.0);
+        assert!(!scaling.is_isometry());
+        assert_eq(
+            scaling.apply(&dvector![2.0, 1.0]).map(|x| x as f32),
+            dvector![4.0, 1.0],
+        );
+
+        let composed = scaling.compose(&rotation);
+        assert_eq(
+            composed.apply(&dvector![2.0, 1.0]).map(|x| x as f32),
+            dvector![1.0, 4.0],
+        );
+        let inverse = composed.inverse().unwrap();
+        assert_eq(
+            inverse.apply(&dvector![1.0, 4.0]).map(|x| x as f32),
+            dvector![2.0, 1.0],
+        );
+        assert!(Affine::scaling(&center, 0.0).inverse().is_none());
+
+        // Reflecting through the line x = 1.
+        let reflection = Affine::reflection(&Hyperplane::new(dvector![1.0, 0.0], 1.0));
+        assert!(reflection.is_isometry());
+        assert_eq(
+            reflection.apply(&dvector![3.0, 5.0]).map(|x| x as f32),
+            dvector![-1.0, 5.0],
+        );
+        assert_eq(
+            reflection.apply_vector(&dvector![3.0, 5.0]).map(|x| x as f32),
+            dvector![-3.0, 5.0],
+        );
+
+        let translation = Affine::from_translation(dvector![1.0, 2.0]);
+        assert_eq!(
+            translation.compose(&translation.inverse().unwrap()),
+            Affine::identity(2)
+        );
+    }
+
+    #[test]
+    /// Embeds polytopes into other dimensions.
+    pub fn embed_into() {
+        let mut square = Concrete::polygon(4);
+        assert!(square.embed_into(4));
+        assert_eq!(square.dim(), Some(4));
+
+        // The square can be brought back down, but not any further.
+        assert!(square.embed_into(2));
+        assert_eq!(square.dim(), Some(2));
+        assert!(!square.embed_into(1));
+        assert_eq!(square.dim(), Some(2));
+
+        let mut cube = Concrete::cube();
+        assert!(!cube.embed_into(2));
+    }
+
+    #[test]
+    /// Projects polytopes onto subspaces.
+    pub fn project_onto() {
+        let cube = Concrete::cube();
+
+        let mut preserved = cube.clone();
+        preserved.project_onto(&xy_plane(), Coincidence::Preserve);
+        assert_eq!(preserved.dim(), Some(2));
+        crate::test(&preserved, [1, 8, 12, 6, 1]);
+
+        // The side faces and vertical edges collapse, and the top and bottom
+        // faces are merged.
+        let mut recomputed = cube;
+        recomputed.project_onto(&xy_plane(), Coincidence::Recompute);
+        crate::test(&recomputed, [1, 4, 4, 1, 1]);
+    }
+
+    #[test]
+    /// Measures distances and angles between subspaces.
+    pub fn subspace_measures() {
+        let mut line = Subspace::new(dvector![0.0, 0.0, 1.0]);
+        line.add(&dvector![1.0, 1.0, 1.0]);
+
+        assert_abs_diff_eq!(xy_plane().distance_to(&line), 1.0, epsilon = f64::eps());
+        assert_abs_diff_eq!(line.distance_to(&xy_plane()), 1.0, epsilon = f64::eps());
+        assert_abs_diff_eq!(xy_plane().angle(&line).unwrap(), 0.0, epsilon = f64::eps());
+
+        let mut diagonal = Subspace::new(Point::zeros(3));
+        diagonal.add(&dvector![1.0, 0.0, 1.0]);
+        assert_abs_diff_eq!(
+            xy_plane().angle(&diagonal).unwrap(),
+            f64::PI / 4.0,
+            epsilon = f64::eps()
+        );
+        assert_abs_diff_eq!(xy_plane().distance_to(&diagonal), 0.0, epsilon = f64::eps());
+        assert!(Subspace::new(Point::zeros(3)).angle(&line).is_none());
+    }
+
+    #[test]
+    /// Measures distances and angles between elements.
+    pub fn measure() {
+        // Two edges of a pentagon at a corner.
+        let pentagon = Concrete::polygon(5);
+        let edge = pentagon[(2, 0)].subs[0];
+        let other = pentagon[(1, edge)].sups.iter().copied().find(|&e| e != 0).unwrap();
+        let measurement = pentagon.measure((2, 0), (2, other)).unwrap();
+        assert_abs_diff_eq!(measurement.distance, 0.0, epsilon = f64::eps());
+        assert_abs_diff_eq!(measurement.angle.unwrap(), 0.4 * f64::PI, epsilon = f64::eps());
+        assert_abs_diff_eq!(
+            measurement.interior_angle.unwrap(),
+            0.6 * f64::PI,
+            epsilon = f64::eps()
+        );
+
+        // Two vertices of a cube.
+        let cube = Concrete::cube();
+        let measurement = cube.measure((1, 0), (1, 7)).unwrap();
+        assert_abs_diff_eq!(
+            measurement.distance,
+            (&cube.vertices[0] - &cube.vertices[7]).norm(),
+            epsilon = f64::eps()
+        );
+        assert!(measurement.angle.is_none());
+
+        // Adjacent and opposite faces of a cube.
+        let shares_edge = |f: usize| cube[(3, f)].subs.iter().any(|e| cube[(3, 0)].subs.contains(e));
+        let neighbor = (1..6).find(|&f| shares_edge(f)).unwrap();
+        let opposite = (1..6).find(|&f| !shares_edge(f)).unwrap();
+
+        let measurement = cube.measure((3, 0), (3, neighbor)).unwrap();
+        assert_abs_diff_eq!(measurement.angle.unwrap(), f64::PI / 2.0, epsilon = f64::eps());
+        assert_abs_diff_eq!(
+            measurement.interior_angle.unwrap(),
+            f64::PI / 2.0,
+            epsilon = f64::eps()
+        );
+
+        let measurement = cube.measure((3, 0), (3, opposite)).unwrap();
+        assert_abs_diff_eq!(measurement.angle.unwrap(), 0.0, epsilon = f64::eps());
+        assert!(measurement.interior_angle.is_none());
+        assert!(measurement.distance > 0.0);
+
+        assert!(cube.measure((0, 0), (1, 0)).is_none());
+        assert!(cube.measure((1, 8), (1, 0)).is_none());
+    }
+
+    #[test]
+    /// Aligns the elements of a tesseract with the fourth axis.
+    pub fn align_element() {
+        let w = dvector![0.0, 0.0, 0.0, -1.0];
+
+        for rank in 1..5 {
+            let mut tesseract = Concrete::hypercube(5);
+            let edge = tesseract.edge_len(0).unwrap();
+            assert!(tesseract.align_element(rank, 0, &w));
+
+            // The element is now centered on the axis, on the negative side.
+            let points = tesseract.element_points(rank, 0).unwrap();
+            let center = points.iter().copied().sum::<Point<f64>>() / points.len() as f64;
+            for i in 0..3 {
+                assert_abs_diff_eq!(center[i], 0.0, epsilon = f64::eps());
+            }
+            assert!(center[3] < 0.0);
+
+            // The rotation preserves the shape.
+            assert_abs_diff_eq!(tesseract.edge_len(0).unwrap(), edge, epsilon = f64::eps());
+            assert!(tesseract.is_equilateral());
+        }
+
+        // Opposite directions are handled too.
+        let mut square = Concrete::polygon(4);
+        let v = square.vertices[0].clone();
+        assert!(square.align_direction(&Point::zeros(2), &v, &-&v));
+        assert_abs_diff_eq!(square.vertices[0], -v, epsilon = f64::eps());
+
+        assert!(!Concrete::polygon(4).align_element(1, 4, &dvector![1.0, 0.0]));
+        assert!(!Concrete::polygon(4).align_element(3, 0, &dvector![1.0, 0.0]));
+    }
+
+    #[test]
+    /// Slices a cube through the centers of its elements.
+    pub fn element_hyperplane() {
+        let cube = Concrete::hypercube(4);
+
+        for rank in 1..4 {
+            let (normal, pos) = cube.element_hyperplane(rank, 0).unwrap();
+            assert_abs_diff_eq!(normal.norm(), 1.0, epsilon = f64::eps());
+
+            // Every vertex of the element lies on the hyperplane, and every
+            // other vertex lies behind it.
+            let vertices = cube.abs.element_vertices(rank, 0).unwrap();
+            for (v, p) in cube.vertices.iter().enumerate() {
+                let dist = normal.dot(p) - pos;
+                if vertices.contains(&v) {
+                    assert_abs_diff_eq!(dist, 0.0, epsilon = f64::eps());
+                } else {
+                    assert!(dist < 0.0);
+                }
+            }
+        }
+
+        // The vertex-first section through the center is a hexagon.
+        let (normal, _) = cube.element_hyperplane(1, 0).unwrap();
+        let hexagon = cube.cross_section(&Hyperplane::new(normal, 0.0));
+        assert_eq!(hexagon.vertex_count(), 6);
+
+        // The cube itself is centered at its gravicenter.
+        assert!(cube.element_hyperplane(4, 0).is_none());
+        assert!(cube.element_hyperplane(1, 8).is_none());
+    }
+
+    #[test]
+    /// Takes the Schlegel projection of a tesseract through a cell.
+    pub fn schlegel() {
+        let tesseract = Concrete::hypercube(5);
+        let schlegel = tesseract.schlegel(0).unwrap();
+        let cell = tesseract.abs.element_vertices(4, 0).unwrap();
+        let edge = tesseract.edge_len(0).unwrap();
+
+        // The vertices of the cell are left in place, and the opposite ones
+        // are shrunk by a third.
+        for (idx, v) in tesseract.vertices.iter().enumerate() {
+            let p = schlegel.project(v).unwrap();
+            assert_eq!(p.len(), 3);
+
+            let expected = if cell.contains(&idx) { 1.0 } else { 1.0 / 3.0 };
+            assert_abs_diff_eq!(p.norm(), expected * edge * 0.75f64.sqrt(), epsilon = f64::eps());
+        }
+
+        assert!(tesseract.schlegel(8).is_none());
+        assert!(Concrete::hypercube(4).schlegel(0).is_some());
+
+        let mut square = Concrete::polygon(4);
+        square.embed_into(3);
+        assert!(square.schlegel(0).is_none());
+    }
+
+    #[test]
+    /// Explodes a tesseract into its cubes.
+    pub fn exploded() {
+        let tesseract = Concrete::hypercube(5);
+        let exploded = tesseract.exploded(1.0).unwrap();
+        crate::test(&exploded, [1, 64, 96, 48, 1]);
+
+        // Every cube is twice as far away, and keeps its size.
+        let edge = tesseract.edge_len(0).unwrap();
+        assert!(exploded.is_equilateral_with(edge));
+        for cube in exploded.vertices.chunks(8) {
+            let center = cube.iter().sum::<Point<f64>>() / 8.0;
+            assert_abs_diff_eq!(center.norm(), edge, epsilon = f64::eps());
+        }
+
+        assert!(Concrete::point().exploded(1.0).is_none());
+    }
+
+    #[test]
+    /// Splits the space of a polytope into its hull and its complement.
+    pub fn orthogonal_complement_split() {
+        let mut square = Concrete::polygon(4);
+        square.embed_into(3);
+
+        let split = square.orthogonal_complement_split().unwrap();
+        assert_eq!(split.hull.rank(), 2);
+        assert_eq!(split.complement.len(), 1);
+        assert_eq!(split.polytope.dim(), Some(2));
+
+        let nullitope = Concrete::nullitope();
+        assert!(nullitope.orthogonal_complement_split().is_none());
+    }
+
+    /// A square grid of points with a given side.
+    fn grid(side: usize) -> Vec<Point<f64>> {
+        (0..side * side)
+            .map(|i| dvector![(i % side) as f64, (i / side) as f64])
+            .collect()
+    }
+
+    #[test]
+    /// Computes the Voronoi cells of some points.
+    pub fn voronoi_cell() {
+        // The cell of the center of a grid is a unit square.
+        let grid = grid(3);
+        let square = Concrete::voronoi_cell(&grid, 4).unwrap();
+        crate::test(&square, [1, 4, 4, 1]);
+        assert!(square.is_equilateral_with(1.0));
+        assert_abs_diff_eq!(square.gravicenter().unwrap(), grid[4], epsilon = f64::eps());
+        assert!(Concrete::voronoi_cell(&grid, 0).is_none());
+        assert!(Concrete::voronoi_cell(&grid, 9).is_none());
+
+        // The cell of the center of a cube is an octahedron.
+        let mut points = Concrete::cube().vertices;
+        points.push(Point::zeros(3));
+        let octahedron = Concrete::voronoi_cell(&points, 8).unwrap();
+        crate::test(&octahedron, [1, 6, 12, 8, 1]);
+        assert!(octahedron.is_equilateral_with(0.75 * f64::SQRT_2));
+
+        // Cells of collinear points are segments.
+        let line: Vec<Point<f64>> = [0.0, 1.0, 3.0]
+            .iter()
+            .map(|&x| dvector![x, 0.0, 0.0])
+            .collect();
+        let segment = Concrete::voronoi_cell(&line, 1).unwrap();
+        crate::test(&segment, [1, 2, 1]);
+        assert!(segment.is_equilateral_with(1.5));
+
+        // Points on a sphere have no bounded cells.
+        assert!(Concrete::voronoi_cell(&Concrete::cube().vertices, 0).is_none());
+    }
+
+    #[test]
+    /// Computes the Voronoi diagrams of some points.
+    pub fn voronoi() {
+        let tiling = Concrete::voronoi(&grid(5)).unwrap();
+        crate::test(&tiling, [1, 16, 24, 9, 1]);
+        assert!(tiling.is_equilateral_with(1.0));
+
+        assert!(Concrete::voronoi(&grid(2)).is_none());
+        assert!(Concrete::voronoi(&[]).is_none());
+    }
 }
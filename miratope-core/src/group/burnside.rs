@@ -0,0 +1,316 @@
+//! Counts the ways to color the elements of a polytope up to symmetry, through
+//! [Burnside's lemma](https://en.wikipedia.org/wiki/Burnside%27s_lemma) and
+//! [Pólya enumeration](https://en.wikipedia.org/wiki/P%C3%B3lya_enumeration_theorem).
+//!
+//! Everything here only depends on how the group permutes the elements, which
+//! is summed up by the [`CycleIndex`] of the action.
+
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    ops::{AddAssign, MulAssign},
+};
+
+/// An arbitrarily large natural number. The number of colorings up to
+/// symmetry quickly outgrows any machine integer: there's more than 10³¹ ways
+/// to color the vertices of a hexacosichoron in two colors.
+///
+/// Only the few operations needed for counting are supported.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Count {
+    /// The digits of the number in base 2³², from least to most significant,
+    /// without any trailing zeros.
+    digits: Vec<u32>,
+}
+
+impl From<u64> for Count {
+    fn from(n: u64) -> Self {
+        let mut count = Self {
+            digits: vec![n as u32, (n >> 32) as u32],
+        };
+        count.trim();
+        count
+    }
+}
+
+impl AddAssign<&Count> for Count {
+    fn add_assign(&mut self, other: &Count) {
+        if self.digits.len() < other.digits.len() {
+            self.digits.resize(other.digits.len(), 0);
+        }
+
+        let mut carry = 0;
+        for (i, digit) in self.digits.iter_mut().enumerate() {
+            let sum = *digit as u64 + other.digits.get(i).copied().unwrap_or(0) as u64 + carry;
+            *digit = sum as u32;
+            carry = sum >> 32;
+        }
+
+        if carry != 0 {
+            self.digits.push(carry as u32);
+        }
+    }
+}
+
+impl MulAssign<u64> for Count {
+    fn mul_assign(&mut self, k: u64) {
+        let mut carry = 0;
+        for digit in &mut self.digits {
+            let product = *digit as u128 * k as u128 + carry;
+            *digit = product as u32;
+            carry = product >> 32;
+        }
+
+        while carry != 0 {
+            self.digits.push(carry as u32);
+            carry >>= 32;
+        }
+
+        self.trim();
+    }
+}
+
+impl Count {
+    /// Returns whether the number is zero.
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    /// Returns a number raised to some power.
+    pub fn pow(base: u64, exp: usize) -> Self {
+        let mut count = Self::from(1);
+        for _ in 0..exp {
+            count *= base;
+        }
+        count
+    }
+
+    /// Returns the number if it fits in a `u64`.
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.digits[..] {
+            [] => Some(0),
+            [a] => Some(a as u64),
+            [a, b] => Some(a as u64 | (b as u64) << 32),
+            _ => None,
+        }
+    }
+
+    /// Divides the number by a non-zero divisor in place, and returns the
+    /// remainder.
+    fn div_rem(&mut self, divisor: u64) -> u64 {
+        let mut rem = 0;
+        for digit in self.digits.iter_mut().rev() {
+            let cur = (rem as u128) << 32 | *digit as u128;
+            *digit = (cur / divisor as u128) as u32;
+            rem = (cur % divisor as u128) as u64;
+        }
+
+        self.trim();
+        rem
+    }
+
+    /// Removes the trailing zeros.
+    fn trim(&mut self) {
+        while self.digits.last() == Some(&0) {
+            self.digits.pop();
+        }
+    }
+}
+
+impl Display for Count {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const CHUNK: u64 = 1_000_000_000;
+
+        // Splits the number into chunks of nine decimal digits.
+        let mut n = self.clone();
+        let mut chunks = Vec::new();
+        while !n.is_zero() {
+            chunks.push(n.div_rem(CHUNK));
+        }
+
+        match chunks.split_last() {
+            None => write!(f, "0"),
+            Some((first, rest)) => {
+                write!(f, "{}", first)?;
+                for chunk in rest.iter().rev() {
+                    write!(f, "{:09}", chunk)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The cycle index of a permutation group: how many of its permutations
+/// have every possible cycle type. This is all that's needed to count orbits
+/// of colorings.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CycleIndex {
+    /// The number of permutations with every cycle type, given by the sorted
+    /// lengths of the cycles.
+    terms: BTreeMap<Vec<usize>, usize>,
+
+    /// The number of permuted points.
+    degree: usize,
+}
+
+impl CycleIndex {
+    /// Builds the cycle index of a permutation group, given all of its
+    /// elements. Each permutation sends the `i`-th point to the `perm[i]`-th
+    /// one.
+    ///
+    /// # Panics
+    /// Panics if the permutations don't all act on the same points.
+    pub fn new<P: AsRef<[usize]>, I: IntoIterator<Item = P>>(perms: I) -> Self {
+        let mut index = Self::default();
+
+        for (i, perm) in perms.into_iter().enumerate() {
+            let perm = perm.as_ref();
+            if i == 0 {
+                index.degree = perm.len();
+            }
+            assert_eq!(perm.len(), index.degree, "permutations of different sizes");
+
+            let mut visited = vec![false; perm.len()];
+            let mut cycle_type = Vec::new();
+            for start in 0..perm.len() {
+                let mut len = 0;
+                let mut point = start;
+                while !visited[point] {
+                    visited[point] = true;
+                    point = perm[point];
+                    len += 1;
+                }
+
+                if len != 0 {
+                    cycle_type.push(len);
+                }
+            }
+
+            cycle_type.sort_unstable();
+            *index.terms.entry(cycle_type).or_default() += 1;
+        }
+
+        index
+    }
+
+    /// Returns the order of the group.
+    pub fn order(&self) -> usize {
+        self.terms.values().sum()
+    }
+
+    /// Returns the number of permuted points.
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    /// Sums some count over the cycle types, weighted by how many permutations
+    /// have each, and divides by the order of the group. Burnside's lemma
+    /// makes this the number of orbits whenever the count is that of the
+    /// objects fixed by the permutations.
+    fn average<F: FnMut(&[usize]) -> Count>(&self, mut fixed: F) -> Count {
+        let mut sum = Count::default();
+        for (cycle_type, &count) in &self.terms {
+            let mut term = fixed(cycle_type);
+            term *= count as u64;
+            sum += &term;
+        }
+
+        if !sum.is_zero() {
+            let rem = sum.div_rem(self.order() as u64);
+            debug_assert_eq!(rem, 0, "the group isn't closed");
+        }
+
+        sum
+    }
+
+    /// Returns the number of orbits of the points.
+    pub fn orbit_count(&self) -> usize {
+        self.average(|cycle_type| {
+            Count::from(cycle_type.iter().filter(|&&len| len == 1).count() as u64)
+        })
+        .to_u64()
+        .unwrap_or_default() as usize
+    }
+
+    /// Returns the number of ways to color the points in a given number of
+    /// colors, up to symmetry.
+    pub fn colorings(&self, colors: usize) -> Count {
+        self.average(|cycle_type| Count::pow(colors as u64, cycle_type.len()))
+    }
+
+    /// Returns the number of ways to pick a given number of the points, up to
+    /// symmetry. These are the two-colorings with that many points of the
+    /// first color.
+    pub fn subsets(&self, size: usize) -> Count {
+        self.average(|cycle_type| {
+            // The coefficients of the product of 1 + x^len over the cycles.
+            let mut coeffs = vec![Count::default(); size + 1];
+            coeffs[0] = Count::from(1);
+
+            for &len in cycle_type {
+                for j in (len..=size).rev() {
+                    let (low, high) = coeffs.split_at_mut(j);
+                    high[0] += &low[j - len];
+                }
+            }
+
+            coeffs.pop().unwrap()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns all permutations of a square's vertices given by its
+    /// symmetries.
+    fn square() -> Vec<Vec<usize>> {
+        let rotation = [1, 2, 3, 0];
+        let reflection = [1, 0, 3, 2];
+
+        let mut perms = vec![vec![0, 1, 2, 3]];
+        for _ in 0..3 {
+            let last = perms.last().unwrap();
+            perms.push(last.iter().map(|&i| rotation[i]).collect());
+        }
+        for i in 0..4 {
+            let perm = perms[i].iter().map(|&i| reflection[i]).collect();
+            perms.push(perm);
+        }
+
+        perms
+    }
+
+    #[test]
+    fn count() {
+        assert_eq!(Count::default().to_string(), "0");
+        assert_eq!(Count::from(1_000_000_000).to_string(), "1000000000");
+        assert_eq!(
+            Count::pow(2, 100).to_string(),
+            "1267650600228229401496703205376"
+        );
+        assert_eq!(Count::pow(3, 40).to_u64(), Some(12_157_665_459_056_928_801));
+        assert_eq!(Count::pow(2, 64).to_u64(), None);
+
+        let mut count = Count::pow(10, 30);
+        assert_eq!(count.div_rem(7), 1);
+        count += &Count::from(1);
+        assert_eq!(count.to_string(), "142857142857142857142857142858");
+    }
+
+    #[test]
+    fn cycle_index() {
+        let index = CycleIndex::new(square());
+        assert_eq!(index.order(), 8);
+        assert_eq!(index.degree(), 4);
+        assert_eq!(index.orbit_count(), 1);
+
+        // The necklaces with four beads, which can be turned over.
+        assert_eq!(index.colorings(2).to_u64(), Some(6));
+        assert_eq!(index.colorings(3).to_u64(), Some(21));
+        let subsets: Vec<_> = (0..=4).map(|k| index.subsets(k).to_u64()).collect();
+        assert_eq!(subsets, [1, 1, 2, 1, 1].map(Some));
+    }
+}
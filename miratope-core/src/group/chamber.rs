@@ -0,0 +1,255 @@
+//! Finds the fundamental domains of reflection groups.
+//!
+//! The mirrors of a finite reflection group cut space into congruent cones
+//! called chambers, which the group permutes simply transitively. Any chamber
+//! is thus a fundamental domain for the group. Its walls lie on the mirrors of
+//! the generating reflections of the group, and a point placed inside of it
+//! can be copied around by the group to get a Wythoffian polytope.
+
+use std::collections::BTreeSet;
+
+use super::{
+    identify::{label, mirror_normals},
+    Group,
+};
+use crate::{
+    float::Float,
+    geometry::{Matrix, Point, PointOrd, Vector},
+};
+
+/// A chamber of a finite reflection group, which is a simplicial cone
+/// whenever the group has full rank.
+#[derive(Clone, Debug)]
+pub struct FundamentalDomain {
+    /// The unit normals to the walls of the chamber, pointing inwards. These
+    /// are the simple roots of the group.
+    pub normals: Vec<Vector<f64>>,
+
+    /// The unit vectors along the edges of the chamber. The `i`-th of them
+    /// lies on every wall but the `i`-th.
+    ///
+    /// If the group doesn't have full rank, these only span the same subspace
+    /// as the normals, and the chamber also contains every direction
+    /// orthogonal to it.
+    pub vertices: Vec<Vector<f64>>,
+}
+
+impl FundamentalDomain {
+    /// Finds a chamber of the reflection group with the given mirrors, by
+    /// picking one side of every mirror according to a point in general
+    /// position. The walls of the chamber are those mirrors whose reflection
+    /// takes the point past no other mirror.
+    fn new(normals: &[Vector<f64>], dim: usize) -> Option<Self> {
+        let p = generic_point(normals, dim)?;
+
+        // Orients every mirror so that the point lies on its positive side.
+        let normals: Vec<_> = normals
+            .iter()
+            .map(|n| if n.dot(&p) > 0.0 { n.clone() } else { -n })
+            .collect();
+
+        let walls: Vec<_> = normals
+            .iter()
+            .filter(|n| {
+                let q = &p - *n * (2.0 * n.dot(&p));
+                normals.iter().filter(|m| m.dot(&q) < 0.0).count() == 1
+            })
+            .cloned()
+            .collect();
+
+        // The edges are the columns of N (NᵀN)⁻¹, normalized.
+        let vertices = if walls.is_empty() {
+            Vec::new()
+        } else {
+            let n = Matrix::from_columns(&walls);
+            let gram_inv = n.tr_mul(&n).try_inverse()?;
+            (n * gram_inv)
+                .column_iter()
+                .map(|v| v.normalize())
+                .collect()
+        };
+
+        Some(Self {
+            normals: walls,
+            vertices,
+        })
+    }
+
+    /// Returns the rank of the group, which is the number of walls of the
+    /// chamber.
+    pub fn rank(&self) -> usize {
+        self.normals.len()
+    }
+
+    /// Returns whether a point lies inside the chamber or on its walls.
+    pub fn contains(&self, p: &Point<f64>) -> bool {
+        self.normals.iter().all(|n| n.dot(p) > -f64::eps())
+    }
+
+    /// Returns the point in the span of the walls' normals whose distance to
+    /// the `i`-th wall is the `i`-th entry of `distances`. This is how a
+    /// generator point is placed for the Wythoff construction: setting all
+    /// distances to 0 or 1/2 gives a polytope with unit edges.
+    ///
+    /// Returns `None` if the number of distances doesn't match the rank.
+    pub fn point_at_distances(&self, distances: &[f64]) -> Option<Point<f64>> {
+        if distances.len() != self.rank() {
+            return None;
+        }
+
+        let n = Matrix::from_columns(&self.normals);
+        let gram_inv = n.tr_mul(&n).try_inverse()?;
+        Some(n * (gram_inv * Vector::from_column_slice(distances)))
+    }
+}
+
+/// Returns a point that lies on none of the mirrors, or `None` if none of the
+/// points tried works.
+fn generic_point(normals: &[Vector<f64>], dim: usize) -> Option<Point<f64>> {
+    (1..=16).map(|k| k as f64).find_map(|k| {
+        let p = Point::from_fn(dim, |i, _| (i as f64 + k).fsqrt().fract() + 0.1 * k);
+        let margin = 1e-3 * p.norm();
+        if normals.iter().all(|n| n.dot(&p).abs() > margin) {
+            Some(p)
+        } else {
+            None
+        }
+    })
+}
+
+impl<I: Iterator<Item = Matrix<f64>>> Group<I> {
+    /// Returns a chamber of a finite reflection group, which is a fundamental
+    /// domain for it.
+    ///
+    /// Returns `None` if the group isn't generated by its reflections.
+    pub fn fundamental_domain(self) -> Option<FundamentalDomain> {
+        let elements: Vec<_> = self.collect();
+        let dim = elements.first()?.nrows();
+        let normals = mirror_normals(&elements);
+        label(&normals, elements.len())?;
+        FundamentalDomain::new(&normals, dim)
+    }
+
+    /// Returns the arcs where the mirrors of a finite reflection group meet
+    /// the unit sphere, as the pairs of unit vectors at their ends. These are
+    /// the edges of the images of a chamber under the group. In 3D, they make
+    /// up the great circles of the mirrors.
+    ///
+    /// Returns `None` if the group isn't generated by its reflections, or if
+    /// it doesn't have full rank, in which case the chambers aren't simplices.
+    pub fn kaleidoscope(self) -> Option<Vec<[Point<f64>; 2]>> {
+        let elements: Vec<_> = self.collect();
+        let dim = elements.first()?.nrows();
+        let normals = mirror_normals(&elements);
+        label(&normals, elements.len())?;
+
+        let domain = FundamentalDomain::new(&normals, dim)?;
+        if domain.rank() != dim {
+            return None;
+        }
+
+        let mut found = BTreeSet::new();
+        let mut arcs = Vec::new();
+        for el in &elements {
+            let images: Vec<_> = domain.vertices.iter().map(|v| el * v).collect();
+
+            for (i, v) in images.iter().enumerate() {
+                for w in &images[i + 1..] {
+                    let (v_ord, w_ord) = (PointOrd::new(v.clone()), PointOrd::new(w.clone()));
+                    let key = if v_ord < w_ord {
+                        (v_ord, w_ord)
+                    } else {
+                        (w_ord, v_ord)
+                    };
+
+                    if found.insert(key) {
+                        arcs.push([v.clone(), w.clone()]);
+                    }
+                }
+            }
+        }
+
+        Some(arcs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks that the chamber of a group has the expected number of walls,
+    /// and that its edges lie on the right walls.
+    fn test<I: Iterator<Item = Matrix<f64>>>(group: Group<I>, rank: usize) -> FundamentalDomain {
+        let domain = group.fundamental_domain().unwrap();
+        assert_eq!(domain.rank(), rank);
+
+        for (i, v) in domain.vertices.iter().enumerate() {
+            assert!(domain.contains(v));
+            for (j, n) in domain.normals.iter().enumerate() {
+                assert_eq!(n.dot(v).abs() < f64::EPS, i != j);
+            }
+        }
+
+        domain
+    }
+
+    #[test]
+    fn fundamental_domain() {
+        test(Group::simplex(3), 3);
+        test(Group::hypercube(4), 4);
+        test(Group::pentagonal(4), 4);
+        test(Group::dihedral_2(5).direct_product(Group::dihedral_2(3)), 4);
+
+        // The angles between the walls come from the Coxeter diagram.
+        let domain = test(Group::pentagonal(3), 3);
+        let mut cosines: Vec<_> = (0..3)
+            .flat_map(|i| (i + 1..3).map(move |j| (i, j)))
+            .map(|(i, j)| -domain.normals[i].dot(&domain.normals[j]))
+            .collect();
+        cosines.sort_by(f64::total_cmp);
+        let expected = [0.0, (f64::PI / 3.0).cos(), (f64::PI / 5.0).cos()];
+        for (x, y) in cosines.iter().zip(expected) {
+            assert!((x - y).abs() < f64::EPS);
+        }
+
+        // Groups of lower rank have smaller chambers.
+        let prism = Group::dihedral_2(6).direct_product(Group::trivial(1));
+        test(prism, 2);
+
+        assert!(Group::pentagonal(3)
+            .rotations()
+            .fundamental_domain()
+            .is_none());
+    }
+
+    #[test]
+    fn point_at_distances() {
+        // The generator of a unit-edged truncated cube.
+        let domain = Group::hypercube(3).fundamental_domain().unwrap();
+        let p = domain.point_at_distances(&[0.5, 0.5, 0.0]).unwrap();
+        assert!(domain.contains(&p));
+
+        let orbit: BTreeSet<_> = Group::hypercube(3).map(|m| PointOrd::new(m * &p)).collect();
+        assert_eq!(orbit.len(), 24);
+
+        assert!(domain.point_at_distances(&[1.0]).is_none());
+    }
+
+    #[test]
+    fn kaleidoscope() {
+        // The mirrors of an icosahedral group split the sphere into 120
+        // triangles, with 62 vertices and 180 edges.
+        let arcs = Group::pentagonal(3).kaleidoscope().unwrap();
+        assert_eq!(arcs.len(), 180);
+        for [v, w] in &arcs {
+            assert!((v.norm() - 1.0).abs() < f64::EPS);
+            assert!((w.norm() - 1.0).abs() < f64::EPS);
+        }
+
+        assert_eq!(Group::simplex(3).kaleidoscope().unwrap().len(), 36);
+        assert!(Group::dihedral_2(6)
+            .direct_product(Group::trivial(1))
+            .kaleidoscope()
+            .is_none());
+    }
+}
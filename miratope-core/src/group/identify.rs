@@ -0,0 +1,355 @@
+//! Recognizes the finite reflection groups from the matrices of their
+//! elements, as products of the irreducible Coxeter groups in the
+//! [classification](https://en.wikipedia.org/wiki/Coxeter_group#Classification).
+//!
+//! The mirrors of a reflection group split into orthogonal families, each of
+//! which generates an irreducible group. Every family is told apart by the
+//! rank of its mirrors and how many there are, which is all that's needed,
+//! save for B6 and E6, which both have 36 mirrors.
+
+use std::{cmp::Reverse, fmt::Display};
+
+use super::Group;
+use crate::{
+    float::Float,
+    geometry::{Matrix, Vector},
+};
+
+use approx::abs_diff_eq;
+
+/// One of the irreducible finite Coxeter groups.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CoxeterType {
+    /// The symmetry group of the n-simplex.
+    A(usize),
+
+    /// The symmetry group of the n-cube.
+    B(usize),
+
+    /// The symmetry group of the n-demicube.
+    D(usize),
+
+    /// The symmetry group of the Gosset polytopes, for n = 6, 7, 8.
+    E(usize),
+
+    /// The symmetry group of the icositetrachoron.
+    F4,
+
+    /// The symmetry group of the icosahedron.
+    H3,
+
+    /// The symmetry group of the hexacosichoron.
+    H4,
+
+    /// The symmetry group of the p-gon, when it's not A2 or B2.
+    I2(usize),
+}
+
+impl Display for CoxeterType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::A(n) => write!(f, "A{}", n),
+            Self::B(n) => write!(f, "B{}", n),
+            Self::D(n) => write!(f, "D{}", n),
+            Self::E(n) => write!(f, "E{}", n),
+            Self::F4 => write!(f, "F4"),
+            Self::H3 => write!(f, "H3"),
+            Self::H4 => write!(f, "H4"),
+            Self::I2(p) => write!(f, "I2({})", p),
+        }
+    }
+}
+
+/// Returns n!, or `None` if it doesn't fit in a `usize`.
+fn factorial(n: usize) -> Option<usize> {
+    (2..=n).try_fold(1usize, |acc, k| acc.checked_mul(k))
+}
+
+impl CoxeterType {
+    /// Returns the number of generating mirrors of the group.
+    pub fn rank(self) -> usize {
+        match self {
+            Self::A(n) | Self::B(n) | Self::D(n) | Self::E(n) => n,
+            Self::F4 | Self::H4 => 4,
+            Self::H3 => 3,
+            Self::I2(_) => 2,
+        }
+    }
+
+    /// Returns the number of reflections in the group.
+    pub fn reflection_count(self) -> usize {
+        match self {
+            Self::A(n) => n * (n + 1) / 2,
+            Self::B(n) => n * n,
+            Self::D(n) => n * (n - 1),
+            Self::E(6) => 36,
+            Self::E(7) => 63,
+            Self::E(_) => 120,
+            Self::F4 => 24,
+            Self::H3 => 15,
+            Self::H4 => 60,
+            Self::I2(p) => p,
+        }
+    }
+
+    /// Returns the order of the group, or `None` if it doesn't fit in a
+    /// `usize`.
+    pub fn order(self) -> Option<usize> {
+        match self {
+            Self::A(n) => factorial(n + 1),
+            Self::B(n) => factorial(n)?.checked_mul(1usize.checked_shl(n as u32)?),
+            Self::D(n) => factorial(n)?.checked_mul(1usize.checked_shl(n as u32 - 1)?),
+            Self::E(6) => Some(51_840),
+            Self::E(7) => Some(2_903_040),
+            Self::E(_) => Some(696_729_600),
+            Self::F4 => Some(1152),
+            Self::H3 => Some(120),
+            Self::H4 => Some(14400),
+            Self::I2(p) => Some(2 * p),
+        }
+    }
+
+    /// Finds the irreducible group with a given rank and number of
+    /// reflections. Only B6 and E6 share both of these, and are told apart by
+    /// whether two of the mirrors meet at 45°.
+    fn classify(rank: usize, reflections: usize, meet_at_45: bool) -> Option<Self> {
+        let candidate = match (rank, reflections) {
+            (1, 1) => Self::A(1),
+            (2, 3) => Self::A(2),
+            (2, 4) => Self::B(2),
+            (2, p) => Self::I2(p),
+            (3, 15) => Self::H3,
+            (4, 24) => Self::F4,
+            (4, 60) => Self::H4,
+            (6, 36) if !meet_at_45 => Self::E(6),
+            (7, 63) => Self::E(7),
+            (8, 120) => Self::E(8),
+            (n, r) if r == n * (n + 1) / 2 => Self::A(n),
+            (n, r) if r == n * n => Self::B(n),
+            (n, r) if n >= 4 && r == n * (n - 1) => Self::D(n),
+            _ => return None,
+        };
+
+        Some(candidate)
+    }
+}
+
+/// The recognized structure of a finite reflection group, as a product of
+/// irreducible Coxeter groups.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GroupLabel {
+    /// The irreducible factors of the group, from the highest rank to the
+    /// lowest.
+    pub components: Vec<CoxeterType>,
+
+    /// The order of the group.
+    pub order: usize,
+}
+
+impl GroupLabel {
+    /// Returns the name of the group, like `H4` or `B3×A1`.
+    pub fn name(&self) -> String {
+        if self.components.is_empty() {
+            return "trivial".to_string();
+        }
+
+        self.components
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("×")
+    }
+
+    /// Returns the total rank of the group.
+    pub fn rank(&self) -> usize {
+        self.components.iter().map(|c| c.rank()).sum()
+    }
+}
+
+impl Display for GroupLabel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} symmetry, order {}", self.name(), self.order)
+    }
+}
+
+/// Returns a unit normal to the mirror of an orthogonal matrix, if it's a
+/// reflection.
+fn mirror_normal(m: &Matrix<f64>) -> Option<Vector<f64>> {
+    let dim = m.nrows();
+
+    // An orthogonal matrix is a reflection exactly when it has a single
+    // eigenvalue of -1, and all others are 1.
+    if m.determinant() > 0.0 || !abs_diff_eq!(m.trace(), dim as f64 - 2.0, epsilon = f64::eps()) {
+        return None;
+    }
+
+    // I - M is twice the projection onto the normal.
+    let proj = Matrix::identity(dim, dim) - m;
+    let col = proj
+        .column_iter()
+        .max_by(|a, b| a.norm().total_cmp(&b.norm()))?;
+    Some(col.normalize())
+}
+
+/// Returns the representative of the family of some mirror, and points every
+/// mirror along the way straight to it.
+fn find(family: &mut [usize], i: usize) -> usize {
+    if family[i] != i {
+        family[i] = find(family, family[i]);
+    }
+    family[i]
+}
+
+/// Returns the unit normals to the mirrors of the reflections in a list of
+/// orthogonal matrices.
+pub(super) fn mirror_normals<'a, I: IntoIterator<Item = &'a Matrix<f64>>>(
+    elements: I,
+) -> Vec<Vector<f64>> {
+    elements.into_iter().filter_map(mirror_normal).collect()
+}
+
+/// Recognizes the reflection group with the given mirrors, and checks that it
+/// has a given order.
+pub(super) fn label(normals: &[Vector<f64>], order: usize) -> Option<GroupLabel> {
+    // Splits the mirrors into families of non-orthogonal ones.
+    let count = normals.len();
+    let mut family: Vec<_> = (0..count).collect();
+    for i in 0..count {
+        for j in i + 1..count {
+            if !abs_diff_eq!(normals[i].dot(&normals[j]), 0.0, epsilon = f64::eps()) {
+                let (a, b) = (find(&mut family, i), find(&mut family, j));
+                family[a] = b;
+            }
+        }
+    }
+
+    let mut components = Vec::new();
+    let mut product = 1usize;
+    for root in 0..count {
+        if find(&mut family, root) != root {
+            continue;
+        }
+
+        let members: Vec<_> = (0..count)
+            .filter(|&i| find(&mut family, i) == root)
+            .collect();
+        let span = Matrix::from_columns(
+            &members
+                .iter()
+                .map(|&i| normals[i].clone())
+                .collect::<Vec<_>>(),
+        );
+        let rank = span.rank(f64::eps());
+
+        let cos_45 = (f64::PI / 4.0).fcos();
+        let meet_at_45 = members.iter().any(|&i| {
+            members.iter().any(|&j| {
+                abs_diff_eq!(
+                    normals[i].dot(&normals[j]).abs(),
+                    cos_45,
+                    epsilon = f64::eps()
+                )
+            })
+        });
+
+        let component = CoxeterType::classify(rank, members.len(), meet_at_45)?;
+        product = product.checked_mul(component.order()?)?;
+        components.push(component);
+    }
+
+    // The reflections must generate the whole group.
+    if product != order {
+        return None;
+    }
+
+    components.sort_by_key(|c| (Reverse(c.rank()), Reverse(c.reflection_count())));
+    Some(GroupLabel { components, order })
+}
+
+impl<I: Iterator<Item = Matrix<f64>>> Group<I> {
+    /// Recognizes a finite reflection group from its elements, and returns
+    /// its irreducible factors along with its order.
+    ///
+    /// Returns `None` if the group isn't generated by its reflections, as is
+    /// the case for rotation groups.
+    pub fn identify(self) -> Option<GroupLabel> {
+        let elements: Vec<_> = self.collect();
+        label(&mirror_normals(&elements), elements.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Identifies a group and returns its name and order.
+    fn identify<I: Iterator<Item = Matrix<f64>>>(group: Group<I>) -> Option<(String, usize)> {
+        group.identify().map(|label| (label.name(), label.order))
+    }
+
+    #[test]
+    fn irreducible() {
+        for n in 1..=5 {
+            let order = factorial(n + 1).unwrap();
+            assert_eq!(
+                identify(Group::simplex(n)),
+                Some((format!("A{}", n), order))
+            );
+        }
+
+        assert_eq!(identify(Group::hypercube(3)), Some(("B3".to_string(), 48)));
+        assert_eq!(
+            identify(Group::hypercube(6)),
+            Some(("B6".to_string(), 46080))
+        );
+        assert_eq!(
+            identify(Group::demihypercube(4)),
+            Some(("D4".to_string(), 192))
+        );
+        assert_eq!(identify(Group::gosset(6)), Some(("E6".to_string(), 51840)));
+        assert_eq!(
+            identify(Group::pentagonal(3)),
+            Some(("H3".to_string(), 120))
+        );
+        assert_eq!(
+            identify(Group::pentagonal(4)),
+            Some(("H4".to_string(), 14400))
+        );
+
+        let f4 = Group::parse("o3o4o3o").unwrap().unwrap();
+        assert_eq!(identify(f4), Some(("F4".to_string(), 1152)));
+        assert_eq!(
+            identify(Group::dihedral_2(7)),
+            Some(("I2(7)".to_string(), 14))
+        );
+    }
+
+    #[test]
+    fn products() {
+        let group = Group::pentagonal(3).direct_product(Group::reflection_at(1, 0));
+        assert_eq!(identify(group), Some(("H3×A1".to_string(), 240)));
+
+        let group = Group::dihedral_2(5).direct_product(Group::dihedral_2(7));
+        assert_eq!(identify(group), Some(("I2(7)×I2(5)".to_string(), 140)));
+
+        assert_eq!(
+            identify(Group::trivial(3)),
+            Some(("trivial".to_string(), 1))
+        );
+    }
+
+    #[test]
+    fn not_reflection_groups() {
+        assert_eq!(identify(Group::pentagonal(3).rotations()), None);
+        assert_eq!(identify(Group::central_inv(3)), None);
+    }
+
+    #[test]
+    fn label() {
+        let label = Group::pentagonal(4).identify().unwrap();
+        assert_eq!(label.to_string(), "H4 symmetry, order 14400");
+        assert_eq!(label.rank(), 4);
+        assert_eq!(CoxeterType::E(8).order(), Some(696_729_600));
+        assert_eq!(CoxeterType::D(5).reflection_count(), 20);
+    }
+}
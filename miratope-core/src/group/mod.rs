@@ -1,10 +1,14 @@
 //! Contains methods to generate many symmetry groups.
 
+pub mod burnside;
+pub mod chamber;
 pub mod cyclic;
 pub mod gen_iter;
+pub mod identify;
 pub mod group_item;
 pub mod pairs;
 pub mod permutation;
+pub mod saved;
 
 pub use gen_iter::*;
 
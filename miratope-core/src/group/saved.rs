@@ -0,0 +1,333 @@
+//! Saves symmetry groups to files and loads them back, so that the symmetry
+//! group of a large polytope only ever needs to be computed once.
+//!
+//! A group is stored through a small set of generators, which are enough to
+//! rebuild every element through a [`GenIter`]. The order of the group is
+//! stored along with them, and is checked when the group is loaded.
+//!
+//! Groups can be written either as [RON](https://github.com/ron-rs/ron) or as
+//! JSON, depending on the extension of the file.
+
+use std::{collections::BTreeSet, ffi::OsStr, fmt::Display, fs, path::Path};
+
+use super::{GenIter, Group};
+use crate::{
+    file::IoError,
+    float::Float,
+    geometry::{Matrix, MatrixOrd},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Represents an error while reading, writing or rebuilding a saved group.
+#[derive(Debug)]
+pub enum SavedGroupError {
+    /// The file couldn't be read or written.
+    Io(IoError),
+
+    /// The file isn't valid RON.
+    Ron(ron::Error),
+
+    /// The file isn't valid JSON.
+    Json(serde_json::Error),
+
+    /// The generator with a given index isn't a square matrix of the right
+    /// dimension.
+    Shape(usize),
+
+    /// The generator with a given index isn't an orthogonal matrix.
+    NotOrthogonal(usize),
+
+    /// The generators don't build a group of the stated order.
+    Order {
+        /// The order stored in the file.
+        expected: usize,
+
+        /// The order of the group built from the generators, or one more than
+        /// the expected order if it was even larger.
+        found: usize,
+    },
+}
+
+impl Display for SavedGroupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "IO error: {}", err),
+            Self::Ron(err) => write!(f, "RON error: {}", err),
+            Self::Json(err) => write!(f, "JSON error: {}", err),
+            Self::Shape(idx) => write!(f, "generator {} has the wrong shape", idx + 1),
+            Self::NotOrthogonal(idx) => write!(f, "generator {} isn't orthogonal", idx + 1),
+            Self::Order { expected, found } => {
+                if found > expected {
+                    write!(f, "the group has more than {} elements", expected)
+                } else {
+                    write!(f, "expected {} elements, found {}", expected, found)
+                }
+            }
+        }
+    }
+}
+
+impl std::error::Error for SavedGroupError {}
+
+/// [`IoError`] is a type of [`SavedGroupError`].
+impl From<IoError> for SavedGroupError {
+    fn from(err: IoError) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// [`ron::Error`] is a type of [`SavedGroupError`].
+impl From<ron::Error> for SavedGroupError {
+    fn from(err: ron::Error) -> Self {
+        Self::Ron(err)
+    }
+}
+
+/// [`serde_json::Error`] is a type of [`SavedGroupError`].
+impl From<serde_json::Error> for SavedGroupError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
+
+/// The result of reading, writing or rebuilding a saved group.
+pub type SavedGroupResult<T> = Result<T, SavedGroupError>;
+
+/// A finite group of isometries, stored through a set of generators.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SavedGroup {
+    /// The name of the group, if it's been recognized.
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// The number of dimensions the group acts on.
+    pub dim: usize,
+
+    /// The number of elements of the group.
+    pub order: usize,
+
+    /// The generators of the group, each written as a list of rows.
+    pub generators: Vec<Vec<Vec<f64>>>,
+}
+
+impl SavedGroup {
+    /// Stores a group of isometries, picking out a set of generators for it.
+    /// The generators are taken greedily from the elements of the group, so
+    /// there's usually only a few of them.
+    ///
+    /// # Panics
+    /// Panics if the group is empty, which can't happen for a valid group.
+    pub fn new<I: Iterator<Item = Matrix<f64>>>(group: Group<I>) -> Self {
+        let elements: Vec<_> = group.collect();
+        let dim = elements.first().expect("groups can't be empty").nrows();
+
+        // The elements generated so far, which always include the identity.
+        let mut found = BTreeSet::new();
+        found.insert(MatrixOrd::new(Matrix::identity(dim, dim)));
+        let mut gens = Vec::new();
+
+        for el in &elements {
+            if !found.contains(&MatrixOrd::new(el.clone())) {
+                gens.push(el.clone());
+                found.extend(GenIter::new(dim, gens.clone()).map(MatrixOrd::new));
+            }
+        }
+
+        Self {
+            name: None,
+            dim,
+            order: elements.len(),
+            generators: gens
+                .iter()
+                .map(|gen| {
+                    gen.row_iter()
+                        .map(|row| row.iter().copied().collect())
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+
+    /// Sets the name of the group.
+    pub fn with_name<T: ToString>(mut self, name: T) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Returns the generators of the group as matrices, after checking that
+    /// they're orthogonal matrices of the right dimension.
+    pub fn generator_matrices(&self) -> SavedGroupResult<Vec<Matrix<f64>>> {
+        let dim = self.dim;
+        let id = Matrix::identity(dim, dim);
+
+        self.generators
+            .iter()
+            .enumerate()
+            .map(|(idx, rows)| {
+                if rows.len() != dim || rows.iter().any(|row| row.len() != dim) {
+                    return Err(SavedGroupError::Shape(idx));
+                }
+
+                let gen = Matrix::from_fn(dim, dim, |i, j| rows[i][j]);
+                if (gen.tr_mul(&gen) - &id).amax() > f64::eps() {
+                    return Err(SavedGroupError::NotOrthogonal(idx));
+                }
+
+                Ok(gen)
+            })
+            .collect()
+    }
+
+    /// Rebuilds the group from its generators, and checks that it has the
+    /// stated order. The trivial group needs no generators.
+    pub fn group(&self) -> SavedGroupResult<Group<GenIter<Matrix<f64>>>> {
+        let mut gens = self.generator_matrices()?;
+        if gens.is_empty() {
+            gens.push(Matrix::identity(self.dim, self.dim));
+        }
+
+        let group: Group<_> = GenIter::new(self.dim, gens).into();
+        let found = group.clone().take(self.order + 1).count();
+        if found != self.order {
+            return Err(SavedGroupError::Order {
+                expected: self.order,
+                found,
+            });
+        }
+
+        Ok(group)
+    }
+
+    /// Writes the group as RON.
+    pub fn to_ron(&self) -> String {
+        ron::ser::to_string_pretty(self, Default::default())
+            .expect("saved groups can always be serialized")
+    }
+
+    /// Writes the group as JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("saved groups can always be serialized")
+    }
+
+    /// Reads a group from RON.
+    pub fn from_ron(src: &str) -> SavedGroupResult<Self> {
+        Ok(ron::from_str(src)?)
+    }
+
+    /// Reads a group from JSON.
+    pub fn from_json(src: &str) -> SavedGroupResult<Self> {
+        Ok(serde_json::from_str(src)?)
+    }
+
+    /// Returns whether a path should be read or written as JSON, rather than
+    /// as RON.
+    fn is_json(path: &Path) -> bool {
+        path.extension().and_then(OsStr::to_str) == Some("json")
+    }
+
+    /// Saves the group to a file, as JSON if its extension is `.json` and as
+    /// RON otherwise.
+    pub fn to_path<P: AsRef<Path>>(&self, path: P) -> SavedGroupResult<()> {
+        let path = path.as_ref();
+        let src = if Self::is_json(path) {
+            self.to_json()
+        } else {
+            self.to_ron()
+        };
+
+        Ok(fs::write(path, src)?)
+    }
+
+    /// Loads a group from a file, as JSON if its extension is `.json` and as
+    /// RON otherwise. The group itself is only checked by [`Self::group`].
+    pub fn from_path<P: AsRef<Path>>(path: P) -> SavedGroupResult<Self> {
+        let path = path.as_ref();
+        let src = fs::read_to_string(path)?;
+
+        if Self::is_json(path) {
+            Self::from_json(&src)
+        } else {
+            Self::from_ron(&src)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks that two saved groups are the same, up to the last digits of
+    /// the entries, which might get rounded off when written as text.
+    fn assert_close(saved: &SavedGroup, loaded: &SavedGroup) {
+        assert_eq!(
+            (&loaded.name, loaded.dim, loaded.order),
+            (&saved.name, saved.dim, saved.order)
+        );
+
+        let gens = saved.generator_matrices().unwrap();
+        let loaded_gens = loaded.generator_matrices().unwrap();
+        assert_eq!(gens.len(), loaded_gens.len());
+        for (gen, loaded_gen) in gens.iter().zip(loaded_gens) {
+            assert!((gen - loaded_gen).amax() < f64::EPS);
+        }
+    }
+
+    /// Checks that a group survives being saved in both formats, and returns
+    /// the saved group.
+    fn round_trip<I: Iterator<Item = Matrix<f64>>>(group: Group<I>) -> SavedGroup {
+        let saved = SavedGroup::new(group);
+        assert_close(&saved, &SavedGroup::from_ron(&saved.to_ron()).unwrap());
+        assert_close(&saved, &SavedGroup::from_json(&saved.to_json()).unwrap());
+        assert_eq!(saved.group().unwrap().count(), saved.order);
+        saved
+    }
+
+    #[test]
+    fn save() {
+        let saved = round_trip(Group::hypercube(3));
+        assert_eq!((saved.dim, saved.order), (3, 48));
+        assert!(saved.generators.len() <= 3);
+
+        let saved = round_trip(Group::parse("o5o3o3o").unwrap().unwrap());
+        assert_eq!(saved.order, 14400);
+
+        let saved = round_trip(Group::trivial(2));
+        assert!(saved.generators.is_empty());
+
+        let saved = round_trip(Group::simplex(3).rotations()).with_name("A3+");
+        assert_eq!(saved.order, 12);
+        assert_eq!(
+            SavedGroup::from_ron(&saved.to_ron()).unwrap().name.unwrap(),
+            "A3+"
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let mut saved = SavedGroup::new(Group::cyclic(5));
+        saved.order = 4;
+        assert!(matches!(
+            saved.group(),
+            Err(SavedGroupError::Order {
+                expected: 4,
+                found: 5
+            })
+        ));
+
+        saved.generators[0][0][0] = 2.0;
+        assert!(matches!(
+            saved.group(),
+            Err(SavedGroupError::NotOrthogonal(0))
+        ));
+
+        saved.generators[0].pop();
+        assert!(matches!(saved.group(), Err(SavedGroupError::Shape(0))));
+
+        // Files written by hand may leave out the name.
+        let saved = SavedGroup::from_ron("(dim: 1, order: 2, generators: [[[-1.0]]])").unwrap();
+        assert_eq!(saved.name, None);
+        assert_eq!(saved.group().unwrap().count(), 2);
+        assert!(SavedGroup::from_json("{ \"dim\": 1 }").is_err());
+    }
+}
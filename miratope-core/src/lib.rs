@@ -26,19 +26,27 @@
 pub mod abs;
 pub mod conc;
 pub mod cox;
+pub mod error;
 pub mod file;
 pub mod float;
 pub mod geometry;
 pub mod group;
+pub mod prefix;
 
-use std::{collections::HashSet, error::Error, iter, ops::IndexMut};
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    iter,
+    ops::IndexMut,
+};
 
 use abs::{
     flag::{Flag, FlagIter, OrientedFlag, OrientedFlagIter},
     ranked::Ranks,
-    Abstract, Element, ElementList, ElementMap, Ranked,
+    Abstract, Element, ElementHash, ElementList, ElementMap, Ranked,
 };
 
+use partitions::PartitionVec;
 use vec_like::VecLike;
 
 /// The names for 0-elements, 1-elements, 2-elements, and so on.
@@ -63,6 +71,145 @@ impl std::fmt::Display for DualError {
 
 impl Error for DualError {}
 
+/// Represents an error when getting an element figure or a section of a
+/// polytope.
+#[derive(Clone, Copy, Debug)]
+pub enum SectionError<E> {
+    /// The polytope has no elements of the given rank.
+    Rank {
+        /// The requested rank.
+        rank: usize,
+
+        /// The rank of the polytope.
+        max_rank: usize,
+    },
+
+    /// The polytope has no element with the given rank and index.
+    Index {
+        /// The rank of the element.
+        rank: usize,
+
+        /// The requested index.
+        idx: usize,
+
+        /// The number of elements of the given rank.
+        count: usize,
+    },
+
+    /// The lower element of a section isn't a subelement of the upper one.
+    NotIncident,
+
+    /// Taking some dual failed.
+    Dual(E),
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for SectionError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Rank { rank, max_rank } => write!(
+                f,
+                "rank {} is out of range for a polytope of rank {}",
+                rank, max_rank
+            ),
+            Self::Index { rank, idx, count } => write!(
+                f,
+                "index {} is out of range for the {} elements of rank {}",
+                idx, count, rank
+            ),
+            Self::NotIncident => write!(f, "the elements are not incident"),
+            Self::Dual(err) => write!(f, "dual failed: {}", err),
+        }
+    }
+}
+
+impl<E: Error> Error for SectionError<E> {}
+
+/// The topological type of the surface of a polyhedron, as given by
+/// [`Polytope::surface`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Surface {
+    /// An orientable surface, given by its genus, i.e. its number of handles.
+    /// A sphere has genus 0, and a torus has genus 1.
+    Orientable {
+        /// The number of handles of the surface.
+        genus: usize,
+    },
+
+    /// A non-orientable surface, given by its genus, i.e. its number of
+    /// crosscaps. A projective plane has genus 1, and a Klein bottle has genus
+    /// 2.
+    NonOrientable {
+        /// The number of crosscaps of the surface.
+        genus: usize,
+    },
+}
+
+impl Surface {
+    /// Returns the surface with a given orientability and Euler
+    /// characteristic, if it exists.
+    pub fn new(orientable: bool, euler_characteristic: isize) -> Option<Self> {
+        if orientable {
+            let double_genus = 2 - euler_characteristic;
+            (double_genus >= 0 && double_genus % 2 == 0).then_some(Self::Orientable {
+                genus: double_genus as usize / 2,
+            })
+        } else {
+            let genus = 2 - euler_characteristic;
+            (genus >= 1).then_some(Self::NonOrientable {
+                genus: genus as usize,
+            })
+        }
+    }
+
+    /// Returns whether the surface is orientable.
+    pub fn orientable(self) -> bool {
+        matches!(self, Self::Orientable { .. })
+    }
+
+    /// Returns the genus of the surface.
+    pub fn genus(self) -> usize {
+        match self {
+            Self::Orientable { genus } | Self::NonOrientable { genus } => genus,
+        }
+    }
+
+    /// Returns the Euler characteristic of the surface.
+    pub fn euler_characteristic(self) -> isize {
+        match self {
+            Self::Orientable { genus } => 2 - 2 * genus as isize,
+            Self::NonOrientable { genus } => 2 - genus as isize,
+        }
+    }
+}
+
+impl std::fmt::Display for Surface {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Orientable { genus } => write!(f, "orientable, genus {}", genus),
+            Self::NonOrientable { genus } => write!(f, "non-orientable, genus {}", genus),
+        }
+    }
+}
+
+/// Checks that a polytope has an element with a given rank and index.
+pub(crate) fn check_element<T: Polytope>(
+    poly: &T,
+    rank: usize,
+    idx: usize,
+) -> Result<(), SectionError<T::DualError>> {
+    let max_rank = poly.rank();
+    if rank > max_rank {
+        return Err(SectionError::Rank { rank, max_rank });
+    }
+
+    let count = poly.el_count(rank);
+    if idx >= count {
+        return Err(SectionError::Index { rank, idx, count });
+    }
+
+    Ok(())
+}
+
 /// Gets the precalculated value for n!.
 fn factorial(n: usize) -> u32 {
     /// Precalculated factorials from 0! to 13!.
@@ -104,14 +251,15 @@ pub trait Polytope:
     /// Sorts the subelements and superelements of the entire polytope. This is
     /// usually called before iterating over the flags of the polytope.
     ///
-    /// This will do nothing if the polytope is already sorted.
+    /// This will do nothing if the polytope is already sorted, and will only
+    /// sort the ranks that have been modified since they were last sorted
+    /// otherwise.
     fn element_sort(&mut self) {
         if !self.abs().sorted() {
             // Safety: changing the order of the indices in an element does not
             // change whether the polytope is valid.
             unsafe {
                 self.ranks_mut().element_sort();
-                self.abs_mut().set_sorted(true);
             }
         }
     }
@@ -245,6 +393,10 @@ pub trait Polytope:
         Self::orthoplex(4)
     }
 
+    /// Builds a [demihypercube](https://polytope.miraheze.org/wiki/Demihypercube)
+    /// with a given rank, which must be at least 4.
+    fn demihypercube(rank: usize) -> Self;
+
     /// Returns the dual of a polytope. Never fails for an abstract polytope. In
     /// case of failing on a concrete polytope, returns the index of a facet
     /// through the inversion center.
@@ -268,24 +420,47 @@ pub trait Polytope:
     /// Gets the element with a given rank and index as a polytope, if it exists.
     fn element(&self, rank: usize, idx: usize) -> Option<Self>;
 
+    /// Gets the polytope whose facets are the elements with a given rank and
+    /// given indices, if they all exist. If a single index is given, this is
+    /// the same as [`Self::element`].
+    fn elements(&self, rank: usize, idxs: &[usize]) -> Option<Self>;
+
+    /// Gets the polytope whose facets are all of the elements of a given rank,
+    /// that is, the skeleton of the polytope up to that rank.
+    fn skeleton(&self, rank: usize) -> Option<Self> {
+        let idxs: Vec<_> = (0..self.el_count(rank)).collect();
+        self.elements(rank, &idxs)
+    }
+
     /// Gets the element figure with a given rank and index as a polytope.
-    fn element_fig(&self, rank: usize, idx: usize) -> Result<Option<Self>, Self::DualError>;
+    ///
+    /// The figure of the minimal element is the polytope itself, while the
+    /// figure of the maximal element is the nullitope.
+    fn element_fig(&self, rank: usize, idx: usize) -> Result<Self, SectionError<Self::DualError>>;
 
     /// Gets the section defined by two elements with given ranks and indices as
-    /// a polytope, or returns `None` in case no section is defined by these
-    /// elements.
+    /// a polytope. Returns an error if either element doesn't exist, or if the
+    /// lower element isn't a subelement of the upper one.
     fn section(
         &self,
         lo_rank: usize,
         lo_idx: usize,
         hi_rank: usize,
         hi_idx: usize,
-    ) -> Result<Option<Self>, Self::DualError> {
-        if let Some(el) = self.element(hi_rank, hi_idx) {
-            el.element_fig(lo_rank, lo_idx)
-        } else {
-            Ok(None)
-        }
+    ) -> Result<Self, SectionError<Self::DualError>> {
+        check_element(self, lo_rank, lo_idx)?;
+        check_element(self, hi_rank, hi_idx)?;
+
+        // The lower element has a different index within the upper element.
+        let hash = ElementHash::new(self.abs(), hi_rank, hi_idx)
+            .expect("the element was checked to exist");
+        let new_idx = hash
+            .index(lo_rank, lo_idx)
+            .ok_or(SectionError::NotIncident)?;
+
+        self.element(hi_rank, hi_idx)
+            .expect("the element was checked to exist")
+            .element_fig(lo_rank, new_idx)
     }
 
     /// Gets the facet associated to the element of a given index as a polytope.
@@ -295,7 +470,7 @@ pub trait Polytope:
     }
 
     /// Gets the verf associated to the element of a given index as a polytope.
-    fn verf(&self, idx: usize) -> Result<Option<Self>, Self::DualError> {
+    fn verf(&self, idx: usize) -> Result<Self, SectionError<Self::DualError>> {
         self.element_fig(1, idx)
     }
 
@@ -311,6 +486,66 @@ pub trait Polytope:
         }
     }
 
+    /// Splits a compound polytope into its components, in the order they were
+    /// appended. This undoes [`Self::comp_append`].
+    ///
+    /// The components are the connected components of the face lattice once
+    /// the minimal and maximal elements are removed. The dyad and the
+    /// polytopes below it are returned as they are.
+    fn components(&self) -> Vec<Self> {
+        let rank = self.rank();
+        if rank <= 2 {
+            return vec![self.clone()];
+        }
+
+        // Joins the vertices along the edges.
+        let abs = self.abs();
+        let mut partition = PartitionVec::new();
+        for v in 0..abs.vertex_count() {
+            partition.push(v);
+        }
+        for edge in &abs[2] {
+            for pair in edge.subs.as_slice().windows(2) {
+                partition.union(pair[0], pair[1]);
+            }
+        }
+
+        let mut component = vec![0; abs.vertex_count()];
+        for (i, set) in partition.all_sets().enumerate() {
+            for (v, _) in set {
+                component[v] = i;
+            }
+        }
+
+        // Groups the facets by the component of any of their vertices.
+        let mut groups = HashMap::new();
+        let mut facets: Vec<Vec<usize>> = Vec::new();
+        for idx in 0..abs.facet_count() {
+            let mut v = idx;
+            for r in (2..rank).rev() {
+                v = abs[(r, v)].subs[0];
+            }
+
+            let group = *groups.entry(component[v]).or_insert_with(|| {
+                facets.push(Vec::new());
+                facets.len() - 1
+            });
+            facets[group].push(idx);
+        }
+
+        if facets.len() <= 1 {
+            return vec![self.clone()];
+        }
+
+        facets
+            .iter()
+            .map(|idxs| {
+                self.elements(rank - 1, idxs)
+                    .expect("the facets of a polytope exist")
+            })
+            .collect()
+    }
+
     /// Builds a Petrial in place. Returns `true` if successful. Does not modify
     /// the original polytope otherwise.
     fn petrial_mut(&mut self) -> bool;
@@ -366,6 +601,74 @@ pub trait Polytope:
     /// `None` if this Petrie polygon is invalid.
     fn petrie_polygon_with(&mut self, flag: Flag) -> Option<Self>;
 
+    /// Returns the number of vertices of the Petrie polygon through the first
+    /// flag of the polytope, or `None` if it self-intersects. In a regular
+    /// polytope, all Petrie polygons have this same length.
+    ///
+    /// # Panics
+    /// Panics if the polytope is not sorted.
+    fn petrie_polygon_length(&self) -> Option<usize> {
+        self.petrie_polygon_vertices(self.first_flag())
+            .map(|vertices| vertices.len())
+    }
+
+    /// Returns the indices of the vertices of every Petrie polygon of the
+    /// polytope, in cyclic order. Each polygon is only returned once, no matter
+    /// how many flags it goes through. Petrie polygons that self-intersect are
+    /// skipped.
+    ///
+    /// # Panics
+    /// Panics if the polytope is not sorted.
+    fn petrie_polygons(&self) -> Vec<Vec<usize>> {
+        let rank = self.rank();
+        let mut polygons = Vec::new();
+
+        // Polytopes of rank less than 2 don't have edges.
+        if rank < 2 {
+            return polygons;
+        }
+
+        let mut visited = HashSet::new();
+        let mut edge_sets = HashSet::new();
+
+        for flag in self.flags() {
+            if visited.contains(&flag) {
+                continue;
+            }
+
+            // Every flag we get to by applying the same sequence of flag changes
+            // determines the same Petrie polygon.
+            let mut new_flag = flag.clone();
+            loop {
+                visited.insert(new_flag.clone());
+                for idx in 1..rank {
+                    new_flag.change_mut(self.abs(), idx);
+                }
+
+                if new_flag == flag {
+                    break;
+                }
+            }
+
+            if let Some(vertices) = self.petrie_polygon_vertices(flag) {
+                // Traversing a Petrie polygon backwards goes through different
+                // flags, so we also compare the edges.
+                let mut edges: Vec<_> = vertices
+                    .iter()
+                    .zip(vertices.iter().cycle().skip(1))
+                    .map(|(&v, &w)| (v.min(w), v.max(w)))
+                    .collect();
+                edges.sort_unstable();
+
+                if edge_sets.insert(edges) {
+                    polygons.push(vertices);
+                }
+            }
+        }
+
+        polygons
+    }
+
     /// Returns the first [`Flag`] of a polytope. This is the flag built when we
     /// start at the maximal element and repeatedly take the first subelement.
     fn first_flag(&self) -> Flag {
@@ -436,6 +739,12 @@ pub trait Polytope:
     /// through the inversion center.
     fn try_antiprism(&self) -> Result<Self, Self::DualError>;
 
+    /// Attempts to build an [antitegum](https://polytope.miraheze.org/wiki/Antitegum)
+    /// based on a given polytope, as the dual of its antiprism. If it fails, it
+    /// returns the index of a facet of the antiprism through the inversion
+    /// center.
+    fn try_antitegum(&self) -> Result<Self, Self::DualError>;
+
     /// Splits compound faces into their components.
     fn untangle_faces(&mut self);
 
@@ -454,6 +763,48 @@ pub trait Polytope:
         self.element_sort();
         self.orientable()
     }
+
+    /// Returns the [Euler characteristic](https://polytope.miraheze.org/wiki/Euler_characteristic)
+    /// of the polytope, that is, the alternating sum of the number of proper
+    /// elements of each rank, starting with the vertices.
+    ///
+    /// This is 2 for convex polyhedra, and 0 for convex polytopes of even
+    /// rank.
+    fn euler_characteristic(&self) -> isize {
+        let rank = self.rank();
+        (1..rank)
+            .map(|r| {
+                let count = self.el_count(r) as isize;
+                if r % 2 == 1 {
+                    count
+                } else {
+                    -count
+                }
+            })
+            .sum()
+    }
+
+    /// Returns the topological type of the surface of a polyhedron, as
+    /// determined by its orientability and its Euler characteristic. Returns
+    /// `None` if the polytope isn't a polyhedron, or if it's a compound.
+    ///
+    /// # Panics
+    /// You must call [`Polytope::element_sort`] before calling this method.
+    fn surface(&self) -> Option<Surface> {
+        if self.rank() != 4 || self.components().len() != 1 {
+            return None;
+        }
+
+        Surface::new(self.orientable(), self.euler_characteristic())
+    }
+
+    /// Returns the topological type of the surface of a polyhedron, as
+    /// determined by its orientability and its Euler characteristic. Returns
+    /// `None` if the polytope isn't a polyhedron, or if it's a compound.
+    fn surface_mut(&mut self) -> Option<Surface> {
+        self.element_sort();
+        self.surface()
+    }
 }
 
 /// Returns the multiproduct (i.e. multipyramid, multiprism, multitegum, or
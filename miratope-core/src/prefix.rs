@@ -0,0 +1,152 @@
+//! Generates the Greek numerical prefixes used to name polytopes, following the
+//! conventions on the [Polytope Wiki](https://polytope.miraheze.org/wiki/Greek_numerical_prefixes).
+//!
+//! Numbers are written from the largest place value to the smallest, so that
+//! for instance a 342-gon is a *tri·hecta·tetraconta·di·gon*. This works for
+//! any number, as the count of myriads is itself written with a prefix.
+
+/// The prefixes for the numbers from 1 to 9 when they're used as units.
+const UNITS: [&str; 10] = [
+    "", "hena", "di", "tri", "tetra", "penta", "hexa", "hepta", "octa", "ennea",
+];
+
+/// The prefixes for the multiples of ten from 30 to 90. The prefixes for 10
+/// and 20 are irregular, and handled separately.
+const TENS: [&str; 10] = [
+    "",
+    "",
+    "",
+    "triaconta",
+    "tetraconta",
+    "pentaconta",
+    "hexaconta",
+    "heptaconta",
+    "octaconta",
+    "enneaconta",
+];
+
+/// Returns the prefix for a number from 1 to 99, as it's written at the end
+/// of a longer prefix.
+fn below_hundred(n: usize) -> String {
+    let (tens, units) = (n / 10, n % 10);
+
+    match (tens, units) {
+        (0, _) => UNITS[units].to_string(),
+        (1, 0) => "deca".to_string(),
+        (1, 1) => "hendeca".to_string(),
+        (1, 2) => "dodeca".to_string(),
+        (1, _) => format!("{}deca", UNITS[units]),
+        (2, 0) => "icosa".to_string(),
+        (2, _) => format!("icosi{}", UNITS[units]),
+        _ => format!("{}{}", TENS[tens], UNITS[units]),
+    }
+}
+
+/// Returns the word that multiplies a thousand or a myriad, like *dis* in
+/// *dischilia*, or the empty string for 1.
+fn multiplier(n: usize) -> String {
+    match n {
+        1 => String::new(),
+        2 => "dis".to_string(),
+        3 => "tris".to_string(),
+        _ => format!("{}kis", greek_prefix(n)),
+    }
+}
+
+/// Returns the prefix for a number from 1 to 9999, as it's written at the end
+/// of a longer prefix.
+fn below_myriad(n: usize) -> String {
+    let (thousands, hundreds, rest) = (n / 1000, n / 100 % 10, n % 100);
+    let mut prefix = String::new();
+
+    if thousands != 0 {
+        prefix += &multiplier(thousands);
+        prefix += "chilia";
+    }
+
+    if hundreds != 0 {
+        if hundreds != 1 {
+            prefix += UNITS[hundreds];
+        }
+
+        prefix += if rest == 0 { "hecto" } else { "hecta" };
+    }
+
+    if rest != 0 {
+        prefix += &below_hundred(rest);
+    }
+
+    prefix
+}
+
+/// Returns the Greek numerical prefix for a number, as in *icositetra* for 24.
+/// We use *mono* for 1 and *nulli* for 0.
+pub fn greek_prefix(n: usize) -> String {
+    match n {
+        0 => "nulli".to_string(),
+        1 => "mono".to_string(),
+        2..=9999 => below_myriad(n),
+        _ => {
+            let (myriads, rest) = (n / 10000, n % 10000);
+            let mut prefix = multiplier(myriads) + "myria";
+
+            if rest != 0 {
+                prefix += &below_myriad(rest);
+            }
+
+            prefix
+        }
+    }
+}
+
+/// Returns the name of a polygon with a given number of sides, as in
+/// *icositetragon* for 24. The triangle and square are named as such.
+pub fn polygon_name(n: usize) -> String {
+    match n {
+        3 => "triangle".to_string(),
+        4 => "square".to_string(),
+        _ => format!("{}gon", greek_prefix(n)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefixes() {
+        let cases = [
+            (1, "mono"),
+            (2, "di"),
+            (9, "ennea"),
+            (11, "hendeca"),
+            (13, "trideca"),
+            (20, "icosa"),
+            (21, "icosihena"),
+            (24, "icositetra"),
+            (35, "triacontapenta"),
+            (100, "hecto"),
+            (101, "hectahena"),
+            (342, "trihectatetracontadi"),
+            (1000, "chilia"),
+            (2000, "dischilia"),
+            (4012, "tetrakischiliadodeca"),
+            (10000, "myria"),
+            (20000, "dismyria"),
+            (50000, "pentakismyria"),
+            (123_456, "dodecakismyriatrischiliatetrahectapentacontahexa"),
+        ];
+
+        for &(n, prefix) in cases.iter() {
+            assert_eq!(greek_prefix(n), prefix, "wrong prefix for {}", n);
+        }
+    }
+
+    #[test]
+    fn polygons() {
+        assert_eq!(polygon_name(2), "digon");
+        assert_eq!(polygon_name(4), "square");
+        assert_eq!(polygon_name(21), "icosihenagon");
+        assert_eq!(polygon_name(342), "trihectatetracontadigon");
+    }
+}
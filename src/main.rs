@@ -18,8 +18,10 @@ use bevy_egui::EguiPlugin;
 use miratope_core::file::FromFile;
 use no_cull_pipeline::PbrNoBackfaceBundle;
 
+use miratope_core::conc::color::ColorScheme;
 use ui::{
     camera::{CameraInputEvent, ProjectionType},
+    main_window::VertexMarker,
     MiratopePlugins,
 };
 
@@ -27,7 +29,11 @@ use crate::mesh::Renderable;
 
 mod mesh;
 mod no_cull_pipeline;
+mod screenshot;
 mod ui;
+mod vector;
+#[cfg(target_arch = "wasm32")]
+mod web;
 
 /// The link to the [Polytope Wiki](https://polytope.miraheze.org/wiki/).
 pub const WIKI_LINK: &str = "https://polytope.miraheze.org/wiki/";
@@ -56,13 +62,23 @@ type Hypersphere = miratope_core::geometry::Hypersphere<f64>;
 /// floating type for the application.
 type Hyperplane = miratope_core::geometry::Hyperplane<f64>;
 
-/// The default epsilon value throughout the application.
-const EPS: Float = <Float as miratope_core::float::Float>::EPS;
+/// The epsilon value throughout the application, as set in the tolerance
+/// settings.
+fn eps() -> Float {
+    <Float as miratope_core::float::Float>::eps()
+}
 
 /// Loads all of the necessary systems for the application to run.
 fn main() {
-    App::new()
-        .insert_resource(Msaa { samples: 4 })
+    let mut app = App::new();
+
+    // On the web, Miratope is drawn on a canvas of the page, which sends it
+    // the polytopes to show.
+    #[cfg(target_arch = "wasm32")]
+    app.insert_resource(web::window_descriptor())
+        .add_plugin(web::WebPlugin);
+
+    app.insert_resource(Msaa { samples: 4 })
         .add_plugins(DefaultPlugins)
         .add_plugin(EguiPlugin)
         .add_plugins(MiratopePlugins)
@@ -79,7 +95,8 @@ fn setup(
     mut pipelines: ResMut<'_, Assets<PipelineDescriptor>>,
 ) {
     // Default polytope.
-    let poly = Concrete::from_off(include_str!("default.off")).unwrap();
+    let poly = Concrete::from_off(include_str!("default.off"))
+        .expect("the default polytope should be a valid OFF file");
 
     // Disables backface culling.
     pipelines.set_untracked(
@@ -96,6 +113,9 @@ fn setup(
     // Wireframe material.
     let wf_material = materials.set(WIREFRAME_UNSELECTED_MATERIAL, Color::rgb_u8(0, 0, 0).into());
 
+    // Vertex material.
+    let vertex_material = materials.add(Color::rgb_u8(0, 0, 0).into());
+
     // Mesh material.
     let mesh_material = materials.add(StandardMaterial {
         base_color: Color::rgb_u8(255, 255, 255),
@@ -123,9 +143,20 @@ fn setup(
                 material: wf_material,
                 ..Default::default()
             });
+
+            // Vertices
+            cb.spawn()
+                .insert_bundle(PbrNoBackfaceBundle {
+                    mesh: meshes
+                        .add(poly.vertex_mesh(ProjectionType::Perspective, Default::default())),
+                    material: vertex_material,
+                    ..Default::default()
+                })
+                .insert(VertexMarker);
         })
         // Polytope
-        .insert(poly);
+        .insert(poly)
+        .insert(ColorScheme::default());
 
     // Camera anchor
     commands
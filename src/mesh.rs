@@ -1,20 +1,25 @@
 //! Contains the methods that take a polytope and turn it into a mesh.
 
-use std::collections::HashMap;
+use std::{collections::HashMap, ops::Range};
 
 use crate::ui::camera::ProjectionType;
-use crate::{Concrete, Float, Point, EPS};
+use crate::{eps, Concrete, Float, Point};
 
 use bevy::{
+    math::Vec3,
     prelude::Mesh,
-    render::{mesh::Indices, pipeline::PrimitiveTopology},
+    render::{
+        mesh::{Indices, VertexAttributeValues},
+        pipeline::PrimitiveTopology,
+    },
 };
 use lyon::{math::point, path::Path, tessellation::*};
-use miratope_core::conc::cycle::CycleList;
+use miratope_core::conc::cycle::{Cycle, CycleList};
 use miratope_core::{
-    abs::{ElementList, Ranked},
+    abs::{Element, ElementList, Ranked},
     conc::ConcretePolytope,
     geometry::{Subspace, Vector},
+    Polytope,
 };
 
 use vec_like::*;
@@ -97,6 +102,103 @@ struct Triangulation {
 
     /// Indices of the vertices that make up the triangles.
     triangles: Vec<u32>,
+
+    /// The faces that were triangulated.
+    faces: Vec<TriangulatedFace>,
+
+    /// Whether the faces were oriented consistently.
+    oriented: bool,
+}
+
+/// A face of a polytope, together with the triangles it was split into.
+struct TriangulatedFace {
+    /// The cycles of vertex indices that bound the face. If the polytope was
+    /// oriented, these go around the face in the direction of its orientation.
+    cycles: CycleList,
+
+    /// The range of entries of [`Triangulation::triangles`] corresponding to
+    /// the triangles of this face.
+    triangles: Range<usize>,
+
+    /// The index of the component of the polytope containing this face.
+    component: usize,
+
+    /// The index of this face in the polytope.
+    index: usize,
+}
+
+/// Reads off the cycles of a face whose edges have been put in order by
+/// [`Abstract::orient`](miratope_core::abs::Abstract::orient).
+fn oriented_cycles(face: &Element, edges: &ElementList) -> CycleList {
+    let mut cycles = CycleList::new();
+    let mut cycle = Cycle::new();
+
+    for (i, &idx) in face.subs.iter().enumerate() {
+        let edge = &edges[idx].subs;
+
+        if let Some(&last) = cycle.last() {
+            let head = if edge[0] == last { edge[1] } else { edge[0] };
+
+            // We closed the cycle.
+            if head == cycle[0] {
+                cycles.push(std::mem::replace(&mut cycle, Cycle::new()));
+            } else {
+                cycle.push(head);
+            }
+        } else {
+            // The first edge of a cycle points towards the vertex it shares
+            // with the next one.
+            let points_forward = face
+                .subs
+                .get(i + 1)
+                .map_or(true, |&next| edges[next].subs.contains(&edge[1]));
+
+            if points_forward {
+                cycle.push(edge[0]);
+                cycle.push(edge[1]);
+            } else {
+                cycle.push(edge[1]);
+                cycle.push(edge[0]);
+            }
+        }
+    }
+
+    if !cycle.is_empty() {
+        cycles.push(cycle);
+    }
+
+    cycles
+}
+
+/// Labels every face of a polytope with the index of the component it belongs
+/// to, where two faces are in the same component whenever they share an edge.
+fn face_components(edges: &ElementList, faces: &ElementList) -> Vec<usize> {
+    let mut components = vec![usize::MAX; faces.len()];
+    let mut count = 0;
+
+    for start in 0..faces.len() {
+        if components[start] != usize::MAX {
+            continue;
+        }
+
+        components[start] = count;
+        let mut stack = vec![start];
+
+        while let Some(face) = stack.pop() {
+            for &edge in &faces[face].subs {
+                for &other in &edges[edge].sups {
+                    if components[other] == usize::MAX {
+                        components[other] = count;
+                        stack.push(other);
+                    }
+                }
+            }
+        }
+
+        count += 1;
+    }
+
+    components
 }
 
 impl Triangulation {
@@ -104,22 +206,36 @@ impl Triangulation {
     fn new(polytope: &Concrete) -> Self {
         let mut extra_vertices = Vec::new();
         let mut triangles = Vec::new();
+        let mut triangulated_faces = Vec::new();
         let empty_els = ElementList::new();
 
+        // We orient the faces of polyhedra, so that we know which way they're
+        // facing. This is cached on the polytope until it changes.
+        let oriented_faces = polytope.abs.oriented_faces();
+        let oriented = oriented_faces.is_some();
+
         // Either returns a reference to the element list of a given rank, or
         // returns a reference to an empty element list.
-        let elements_or = |r| polytope.get_element_list(r).unwrap_or(&empty_els);
+        let elements_or = |r| polytope.abs.get_element_list(r).unwrap_or(&empty_els);
 
         let edges = elements_or(2);
-        let faces = elements_or(3);
+        let faces = oriented_faces.as_deref().unwrap_or_else(|| elements_or(3));
+        let components = face_components(edges, faces);
 
         let concrete_vertex_len = polytope.vertices.len() as u32;
 
         // We render each face separately.
-        for face in faces {
+        for (face_idx, face) in faces.iter().enumerate() {
             // We tesselate this path.
-            let cycles = CycleList::from_edges(face.subs.iter().map(|&i| &edges[i].subs));
+            let cycles = if oriented {
+                oriented_cycles(face, edges)
+            } else {
+                CycleList::from_edges(face.subs.iter().map(|&i| &edges[i].subs))
+            };
+
             if let Some(path) = path(&cycles, &polytope.vertices) {
+                let first_triangle = triangles.len();
+
                 let mut geometry: VertexBuffers<_, u32> = VertexBuffers::new();
 
                 // Configures all of the options of the tessellator.
@@ -129,7 +245,7 @@ impl Triangulation {
                         &path,
                         None,
                         &FillOptions::with_fill_rule(Default::default(), FillRule::NonZero)
-                            .with_tolerance(EPS as f32),
+                            .with_tolerance(eps() as f32),
                         &mut BuffersBuilder::new(&mut geometry, |vertex: FillVertex<'_>| {
                             vertex.sources().next().unwrap()
                         }),
@@ -181,14 +297,38 @@ impl Triangulation {
                 {
                     triangles.push(new_idx);
                 }
+
+                triangulated_faces.push(TriangulatedFace {
+                    cycles,
+                    triangles: first_triangle..triangles.len(),
+                    component: components[face_idx],
+                    index: face_idx,
+                });
             }
         }
 
         Self {
             extra_vertices,
             triangles,
+            faces: triangulated_faces,
+            oriented,
+        }
+    }
+}
+
+/// Computes a normal vector to a face using Newell's method. Its length is
+/// twice the area of the face, and it points towards the side from which the
+/// cycles of the face are traversed counterclockwise.
+fn newell_normal(cycles: &CycleList, vertices: &[[f32; 3]]) -> Vec3 {
+    let mut normal = Vec3::ZERO;
+
+    for cycle in cycles.iter() {
+        for (&i, &j) in cycle.iter().zip(cycle.iter().cycle().skip(1)) {
+            normal += Vec3::from(vertices[i]).cross(Vec3::from(vertices[j]));
         }
     }
+
+    normal
 }
 
 /// Generates normals from a set of vertices by just projecting radially from
@@ -198,7 +338,7 @@ fn normals(vertices: &[[f32; 3]]) -> Vec<[f32; 3]> {
         .iter()
         .map(|n| {
             let sq_norm = n[0] * n[0] + n[1] * n[1] + n[2] * n[2];
-            if sq_norm < EPS as f32 {
+            if sq_norm < eps() as f32 {
                 [0.0, 0.0, 0.0]
             } else {
                 let norm = sq_norm.sqrt();
@@ -208,6 +348,263 @@ fn normals(vertices: &[[f32; 3]]) -> Vec<[f32; 3]> {
         .collect()
 }
 
+/// The number of sides of the tubes drawn for the edges.
+const TUBE_SIDES: usize = 8;
+
+/// The number of rings of latitude of the spheres drawn for the vertices.
+const SPHERE_STACKS: usize = 6;
+
+/// The number of meridians of the spheres drawn for the vertices.
+const SPHERE_SECTORS: usize = 10;
+
+/// How the edges and vertices of a polytope are drawn.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WireframeStyle {
+    /// The edges are drawn as lines one pixel wide.
+    Lines,
+
+    /// The edges are drawn as tubes, and the vertices as spheres. Either can
+    /// be left out by setting its radius to zero.
+    Tubes {
+        /// The radius of the tubes.
+        edge_radius: f32,
+
+        /// The radius of the spheres.
+        vertex_radius: f32,
+    },
+}
+
+impl Default for WireframeStyle {
+    fn default() -> Self {
+        Self::Lines
+    }
+}
+
+/// Accumulates the triangles of the tubes and spheres of a wireframe.
+#[derive(Default)]
+struct TubeBuilder {
+    /// The positions of the vertices.
+    positions: Vec<[f32; 3]>,
+
+    /// The normals of the vertices.
+    normals: Vec<[f32; 3]>,
+
+    /// The indices of the triangles.
+    indices: Vec<u32>,
+}
+
+impl TubeBuilder {
+    /// Adds a vertex with a given normal.
+    fn vertex(&mut self, position: Vec3, normal: Vec3) {
+        self.positions.push(position.into());
+        self.normals.push(normal.into());
+    }
+
+    /// Adds a grid of quads between the last `rows * cols` vertices, given in
+    /// order row by row. Every row is closed into a loop.
+    fn grid(&mut self, rows: usize, cols: usize) {
+        let start = self.positions.len() - rows * cols;
+
+        for i in 0..rows - 1 {
+            for j in 0..cols {
+                let k = (j + 1) % cols;
+                let [a, b, c, d] = [
+                    i * cols + j,
+                    i * cols + k,
+                    (i + 1) * cols + j,
+                    (i + 1) * cols + k,
+                ]
+                .map(|idx| (start + idx) as u32);
+
+                self.indices.extend([a, c, b, b, c, d]);
+            }
+        }
+    }
+
+    /// Adds a tube between two points.
+    fn tube(&mut self, a: Vec3, b: Vec3, radius: f32) {
+        let axis = (b - a).normalize_or_zero();
+        if axis == Vec3::ZERO {
+            return;
+        }
+
+        // Two unit vectors orthogonal to the axis and to each other.
+        let helper = if axis.x.abs() < 0.9 { Vec3::X } else { Vec3::Y };
+        let u = axis.cross(helper).normalize();
+        let v = axis.cross(u);
+
+        for end in [a, b] {
+            for side in 0..TUBE_SIDES {
+                let angle = std::f32::consts::TAU * side as f32 / TUBE_SIDES as f32;
+                let normal = u * angle.cos() + v * angle.sin();
+                self.vertex(end + normal * radius, normal);
+            }
+        }
+
+        self.grid(2, TUBE_SIDES);
+    }
+
+    /// Adds a sphere about a point.
+    fn sphere(&mut self, center: Vec3, radius: f32) {
+        for stack in 0..=SPHERE_STACKS {
+            let polar = std::f32::consts::PI * stack as f32 / SPHERE_STACKS as f32;
+
+            for sector in 0..SPHERE_SECTORS {
+                let azimuth = std::f32::consts::TAU * sector as f32 / SPHERE_SECTORS as f32;
+                let normal = Vec3::new(
+                    polar.sin() * azimuth.cos(),
+                    polar.cos(),
+                    polar.sin() * azimuth.sin(),
+                );
+                self.vertex(center + normal * radius, normal);
+            }
+        }
+
+        self.grid(SPHERE_STACKS + 1, SPHERE_SECTORS);
+    }
+
+    /// Builds the mesh, or an empty one if nothing was added.
+    fn build(self) -> Mesh {
+        if self.indices.is_empty() {
+            return empty_mesh();
+        }
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.set_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0; 2]; self.positions.len()]);
+        mesh.set_attribute(Mesh::ATTRIBUTE_NORMAL, self.normals);
+        mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, self.positions);
+        mesh.set_indices(Some(Indices::U32(self.indices)));
+
+        mesh
+    }
+}
+
+/// Returns the values of a mesh attribute made out of 3D vectors, or an empty
+/// slice if there's none.
+fn vec3_attribute<'a>(mesh: &'a Mesh, name: &'static str) -> &'a [[f32; 3]] {
+    match mesh.attribute(name) {
+        Some(VertexAttributeValues::Float32x3(values)) => values.as_slice(),
+        _ => &[],
+    }
+}
+
+/// A plane that cuts away everything on its positive side, so that the inside
+/// of a polytope can be seen. It's given in the coordinates of the scene, after
+/// the polytope has been projected down into 3D.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClipPlane {
+    /// The normal of the plane, pointing towards the part that's cut away.
+    pub normal: Vec3,
+
+    /// The signed distance from the plane to the origin, measured along the
+    /// normal.
+    pub offset: f32,
+}
+
+impl Default for ClipPlane {
+    fn default() -> Self {
+        Self {
+            normal: Vec3::Z,
+            offset: 0.0,
+        }
+    }
+}
+
+impl ClipPlane {
+    /// Returns the signed distance from a point to the plane.
+    fn distance(&self, p: Vec3) -> f32 {
+        self.normal.dot(p) - self.offset
+    }
+
+    /// Clips a mesh made out of triangles or segments, keeping only the part
+    /// on the negative side of the plane. Primitives crossing the plane are
+    /// cut where they meet it. Any other mesh is returned as is.
+    pub fn clip(&self, mesh: &Mesh) -> Mesh {
+        let topology = mesh.primitive_topology();
+        let arity = match topology {
+            PrimitiveTopology::TriangleList => 3,
+            PrimitiveTopology::LineList => 2,
+            _ => return mesh.clone(),
+        };
+
+        let positions = vec3_attribute(mesh, Mesh::ATTRIBUTE_POSITION);
+        let normals = vec3_attribute(mesh, Mesh::ATTRIBUTE_NORMAL);
+        let vertex = |idx: usize| {
+            let normal = normals.get(idx).copied().unwrap_or_default();
+            (Vec3::from(positions[idx]), Vec3::from(normal))
+        };
+
+        let indices: Vec<usize> = mesh
+            .indices()
+            .map(Indices::iter)
+            .map_or_else(Vec::new, Iterator::collect);
+        let mut new_positions = Vec::new();
+        let mut new_normals = Vec::new();
+        let mut new_indices = Vec::new();
+
+        for primitive in indices.chunks_exact(arity) {
+            // We walk along the boundary of the primitive, keeping the
+            // vertices behind the plane and the points where it's crossed. A
+            // segment only has one side to walk along.
+            let sides = if arity == 2 { 1 } else { arity };
+            let mut kept = Vec::with_capacity(arity + 1);
+
+            for (i, &idx) in primitive.iter().enumerate().take(sides) {
+                let (p, n) = vertex(idx);
+                let (q, m) = vertex(primitive[(i + 1) % arity]);
+                let (dp, dq) = (self.distance(p), self.distance(q));
+
+                if dp <= 0.0 {
+                    kept.push((p, n));
+                }
+
+                if (dp <= 0.0) != (dq <= 0.0) {
+                    let t = dp / (dp - dq);
+                    kept.push((p.lerp(q, t), n.lerp(m, t).normalize_or_zero()));
+                }
+            }
+
+            if arity == 2 {
+                let (q, m) = vertex(primitive[1]);
+                if self.distance(q) <= 0.0 {
+                    kept.push((q, m));
+                }
+            }
+
+            if kept.len() < arity {
+                continue;
+            }
+
+            // Triangulates whatever is left of a triangle as a fan.
+            let start = new_positions.len() as u32;
+            if arity == 2 {
+                new_indices.extend([start, start + 1]);
+            } else {
+                for i in 1..kept.len() as u32 - 1 {
+                    new_indices.extend([start, start + i, start + i + 1]);
+                }
+            }
+
+            for (p, n) in kept {
+                new_positions.push(p.into());
+                new_normals.push(n.into());
+            }
+        }
+
+        if new_indices.is_empty() {
+            return empty_mesh();
+        }
+
+        let mut mesh = Mesh::new(topology);
+        mesh.set_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0; 2]; new_positions.len()]);
+        mesh.set_attribute(Mesh::ATTRIBUTE_NORMAL, new_normals);
+        mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, new_positions);
+        mesh.set_indices(Some(Indices::U32(new_indices)));
+
+        mesh
+    }
+}
+
 /// Returns an empty mesh.
 fn empty_mesh() -> Mesh {
     let mut mesh = Mesh::new(PrimitiveTopology::LineList);
@@ -232,7 +629,24 @@ fn vertex_coords<'a, I: Iterator<Item = &'a Point>>(
 
     // If the polytope is at most 3D, we just embed it into 3D space.
     if projection_type.is_orthogonal() || dim <= 3 {
-        vertices.map(|p| [0, 1, 2].map(|i| coord(p, i) as f32)).collect()
+        vertices
+            .map(|p| [0, 1, 2].map(|i| coord(p, i) as f32))
+            .collect()
+    }
+    // Draws the Schlegel diagram of a 4D polytope. Points that can't be
+    // projected are sent to the origin.
+    else if let Some(schlegel) = projection_type
+        .schlegel_facet()
+        .filter(|_| dim == 4)
+        .and_then(|facet| poly.schlegel(facet))
+    {
+        vertices
+            .map(|p| {
+                schlegel
+                    .project(p)
+                    .map_or([0.0; 3], |q| [0, 1, 2].map(|i| q[i] as f32))
+            })
+            .collect()
     }
     // Else, we project it down.
     else {
@@ -253,10 +667,70 @@ fn vertex_coords<'a, I: Iterator<Item = &'a Point>>(
     }
 }
 
+/// The elements of a polytope that are drawn, when only part of it is shown.
+/// The part is projected down exactly like the whole polytope, so that it
+/// doesn't move around as more of it is revealed.
+#[derive(Clone, Debug, Default)]
+pub struct Revealed {
+    /// Whether each vertex is drawn.
+    pub vertices: Vec<bool>,
+
+    /// Whether each edge is drawn.
+    pub edges: Vec<bool>,
+
+    /// Whether each face is drawn.
+    pub faces: Vec<bool>,
+}
+
+impl Revealed {
+    /// Reveals the elements of a polytope of rank at least 3 that lie on any
+    /// of the given facets.
+    pub fn from_facets<I: IntoIterator<Item = usize>>(poly: &Concrete, facets: I) -> Self {
+        let rank = poly.rank();
+        let mut shown: Vec<_> = (0..rank).map(|r| vec![false; poly.el_count(r)]).collect();
+
+        if rank >= 2 {
+            for idx in facets {
+                shown[rank - 1][idx] = true;
+            }
+        }
+
+        // Every subelement of a revealed element is revealed.
+        for r in (2..rank).rev() {
+            for idx in 0..poly.el_count(r) {
+                if shown[r][idx] {
+                    for &sub in &poly[(r, idx)].subs {
+                        shown[r - 1][sub] = true;
+                    }
+                }
+            }
+        }
+
+        let mut ranks = shown.into_iter().skip(1);
+        let mut next = || ranks.next().unwrap_or_default();
+        Self {
+            vertices: next(),
+            edges: next(),
+            faces: next(),
+        }
+    }
+
+    /// Returns whether the element with a given index in a list is drawn.
+    fn shows(list: &[bool], idx: usize) -> bool {
+        list.get(idx).copied().unwrap_or_default()
+    }
+}
+
 /// A trait for a polytope for which we can build a mesh.
 pub trait Renderable: ConcretePolytope {
     /// Builds the mesh of a polytope.
     fn mesh(&self, projection_type: ProjectionType) -> Mesh {
+        self.partial_mesh(projection_type, None)
+    }
+
+    /// Builds the mesh of a polytope, only drawing the faces that are
+    /// revealed, if given.
+    fn partial_mesh(&self, projection_type: ProjectionType, revealed: Option<&Revealed>) -> Mesh {
         // If there's no vertices, returns an empty mesh.
         if self.vertex_count() == 0 {
             return empty_mesh();
@@ -273,18 +747,185 @@ pub trait Renderable: ConcretePolytope {
             projection_type,
         );
 
+        let face_normals: Vec<_> = triangulation
+            .faces
+            .iter()
+            .map(|face| newell_normal(&face.cycles, &vertices))
+            .collect();
+
+        // If the faces were oriented, we flip any component whose faces point
+        // inwards, which we detect from the sign of its volume.
+        let component_count = triangulation
+            .faces
+            .iter()
+            .map(|face| face.component + 1)
+            .max()
+            .unwrap_or_default();
+        let mut component_volumes = vec![0.0; component_count];
+
+        if triangulation.oriented {
+            for (face, normal) in triangulation.faces.iter().zip(&face_normals) {
+                let p = Vec3::from(vertices[face.cycles[0][0]]);
+                component_volumes[face.component] += p.dot(*normal);
+            }
+        }
+
+        // Every face gets its own copy of its vertices, so that it can have
+        // its own normal.
+        let mut positions = Vec::with_capacity(triangulation.triangles.len());
+        let mut normals = Vec::with_capacity(triangulation.triangles.len());
+        let mut indices = Vec::with_capacity(triangulation.triangles.len());
+
+        for (face, &normal) in triangulation.faces.iter().zip(&face_normals) {
+            if let Some(revealed) = revealed {
+                if !Revealed::shows(&revealed.faces, face.index) {
+                    continue;
+                }
+            }
+
+            let normal = if component_volumes[face.component] < 0.0 {
+                -normal
+            } else {
+                normal
+            };
+
+            for triangle in triangulation.triangles[face.triangles.clone()].chunks_exact(3) {
+                let [a, b, c] = [triangle[0], triangle[1], triangle[2]].map(|i| i as usize);
+                let (va, vb, vc) = (
+                    Vec3::from(vertices[a]),
+                    Vec3::from(vertices[b]),
+                    Vec3::from(vertices[c]),
+                );
+
+                // We wind every triangle the same way as its face.
+                let triangle = if (vb - va).cross(vc - va).dot(normal) < 0.0 {
+                    [a, c, b]
+                } else {
+                    [a, b, c]
+                };
+
+                for idx in triangle {
+                    indices.push(positions.len() as u32);
+                    positions.push(vertices[idx]);
+                    normals.push(normal.normalize_or_zero().into());
+                }
+            }
+        }
+
         // Builds the actual mesh.
         let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
-        mesh.set_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0, 1.0]; vertices.len()]);
-        mesh.set_attribute(Mesh::ATTRIBUTE_NORMAL, normals(&vertices));
-        mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, vertices);
-        mesh.set_indices(Some(Indices::U32(triangulation.triangles)));
+        mesh.set_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0, 1.0]; positions.len()]);
+        mesh.set_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.set_indices(Some(Indices::U32(indices)));
 
         mesh
     }
 
+    /// Returns the coordinates of the vertices in the scene, after projecting
+    /// them down into 3D.
+    fn projected_vertices(&self, projection_type: ProjectionType) -> Vec<[f32; 3]> {
+        vertex_coords(self.con(), self.vertices().iter(), projection_type)
+    }
+
     /// Builds the wireframe of a polytope.
     fn wireframe(&self, projection_type: ProjectionType) -> Mesh {
+        let edges = self.get_element_list(2).into_iter().flatten().map(|edge| {
+            debug_assert_eq!(
+                edge.subs.len(),
+                2,
+                "Edge must have exactly 2 elements, found {}.",
+                edge.subs.len()
+            );
+
+            [edge.subs[0], edge.subs[1]]
+        });
+
+        self.line_mesh(edges, projection_type)
+    }
+
+    /// Builds the wireframe of a polytope in a given style. The vertices are
+    /// drawn separately by [`Self::vertex_mesh`].
+    fn styled_wireframe(&self, projection_type: ProjectionType, style: WireframeStyle) -> Mesh {
+        self.partial_wireframe(projection_type, style, None)
+    }
+
+    /// Builds the wireframe of a polytope in a given style, only drawing the
+    /// edges that are revealed, if given.
+    fn partial_wireframe(
+        &self,
+        projection_type: ProjectionType,
+        style: WireframeStyle,
+        revealed: Option<&Revealed>,
+    ) -> Mesh {
+        let edges = self
+            .get_element_list(2)
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .filter(|(idx, _)| revealed.map_or(true, |r| Revealed::shows(&r.edges, *idx)))
+            .map(|(_, edge)| edge);
+
+        let edge_radius = match style {
+            WireframeStyle::Lines => {
+                return self.line_mesh(
+                    edges.map(|edge| [edge.subs[0], edge.subs[1]]),
+                    projection_type,
+                )
+            }
+            WireframeStyle::Tubes { edge_radius, .. } => edge_radius,
+        };
+
+        let vertices = self.projected_vertices(projection_type);
+        let mut builder = TubeBuilder::default();
+
+        if edge_radius > 0.0 && self.rank() >= 2 {
+            for edge in edges {
+                let [a, b] = [edge.subs[0], edge.subs[1]].map(|v| Vec3::from(vertices[v]));
+                builder.tube(a, b, edge_radius);
+            }
+        }
+
+        builder.build()
+    }
+
+    /// Builds the spheres drawn at the vertices of a polytope in a given
+    /// style. This is empty unless the edges are drawn as tubes.
+    fn vertex_mesh(&self, projection_type: ProjectionType, style: WireframeStyle) -> Mesh {
+        self.partial_vertex_mesh(projection_type, style, None)
+    }
+
+    /// Builds the spheres drawn at the vertices of a polytope in a given
+    /// style, only drawing the vertices that are revealed, if given.
+    fn partial_vertex_mesh(
+        &self,
+        projection_type: ProjectionType,
+        style: WireframeStyle,
+        revealed: Option<&Revealed>,
+    ) -> Mesh {
+        let mut builder = TubeBuilder::default();
+
+        if let WireframeStyle::Tubes { vertex_radius, .. } = style {
+            if vertex_radius > 0.0 {
+                let vertices = self.projected_vertices(projection_type);
+                for (idx, &v) in vertices.iter().enumerate() {
+                    if revealed.map_or(true, |r| Revealed::shows(&r.vertices, idx)) {
+                        builder.sphere(Vec3::from(v), vertex_radius);
+                    }
+                }
+            }
+        }
+
+        builder.build()
+    }
+
+    /// Builds a mesh made out of segments between pairs of vertices of a
+    /// polytope, given by their indices.
+    fn line_mesh<I: IntoIterator<Item = [usize; 2]>>(
+        &self,
+        segments: I,
+        projection_type: ProjectionType,
+    ) -> Mesh {
         let vertex_count = self.vertex_count();
 
         // If there's no vertices, returns an empty mesh.
@@ -292,33 +933,54 @@ pub trait Renderable: ConcretePolytope {
             return empty_mesh();
         }
 
-        let edge_count = self.edge_count();
+        let vertices = self.projected_vertices(projection_type);
+        let mut indices = Vec::new();
 
-        // We add a single vertex so that Miratope doesn't crash.
-        let vertices = vertex_coords(self.con(), self.vertices().iter(), projection_type);
-        let mut indices = Vec::with_capacity(edge_count * 2);
+        // Adds the segments to the mesh.
+        for [v, w] in segments {
+            indices.push(v as u16);
+            indices.push(w as u16);
+        }
 
-        // Adds the edges to the wireframe.
-        if let Some(edges) = self.get_element_list(2) {
-            for edge in edges {
-                debug_assert_eq!(
-                    edge.subs.len(),
-                    2,
-                    "Edge must have exactly 2 elements, found {}.",
-                    edge.subs.len()
-                );
+        // Sets the mesh attributes.
+        let mut mesh = Mesh::new(PrimitiveTopology::LineList);
+        mesh.set_attribute(Mesh::ATTRIBUTE_NORMAL, normals(&vertices));
+        mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, vertices);
+        mesh.set_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0; 2]; vertex_count]);
+        mesh.set_indices(Some(Indices::U16(indices)));
+
+        mesh
+    }
 
-                indices.push(edge.subs[0] as u16);
-                indices.push(edge.subs[1] as u16);
+    /// Builds a mesh made out of polygonal paths through arbitrary points,
+    /// which are projected down into 3D exactly like the polytope, so that
+    /// they can be drawn over it.
+    fn path_mesh(&self, paths: &[Vec<Point>], projection_type: ProjectionType) -> Mesh {
+        let points = paths.iter().flatten();
+        let vertices = vertex_coords(self.con(), points, projection_type);
+        if vertices.is_empty() {
+            return empty_mesh();
+        }
+
+        // There can be many more points than vertices, so we use larger
+        // indices than for the wireframe.
+        let mut indices = Vec::new();
+        let mut start = 0;
+        for path in paths {
+            for idx in start + 1..start + path.len() {
+                indices.push(idx as u32 - 1);
+                indices.push(idx as u32);
             }
+
+            start += path.len();
         }
 
-        // Sets the mesh attributes.
+        let vertex_count = vertices.len();
         let mut mesh = Mesh::new(PrimitiveTopology::LineList);
         mesh.set_attribute(Mesh::ATTRIBUTE_NORMAL, normals(&vertices));
         mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, vertices);
         mesh.set_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0; 2]; vertex_count]);
-        mesh.set_indices(Some(Indices::U16(indices)));
+        mesh.set_indices(Some(Indices::U32(indices)));
 
         mesh
     }
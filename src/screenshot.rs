@@ -0,0 +1,331 @@
+//! Renders the polytope on screen into an image, so that it can be saved as a
+//! screenshot or as the frames of an animated GIF turntable.
+//!
+//! We don't read back from the GPU. Instead, we rasterize the same meshes that
+//! are sent to Bevy in software, which lets us render at any resolution
+//! regardless of the size of the window.
+
+use std::{fs::File, io::BufWriter, path::Path};
+
+use crate::{
+    mesh::{Renderable, WireframeStyle},
+    ui::camera::ProjectionType,
+    Concrete,
+};
+
+use bevy::{
+    prelude::*,
+    render::mesh::{Indices, VertexAttributeValues},
+};
+use image::{
+    codecs::gif::{GifEncoder, Repeat},
+    Delay, Frame, ImageResult, Rgba, RgbaImage,
+};
+
+/// The vertical field of view of the camera. This matches the default for a
+/// [`PerspectiveProjection`](bevy::render::camera::PerspectiveProjection).
+const FOV: f32 = std::f32::consts::FRAC_PI_4;
+
+/// The near clipping plane of the camera.
+const NEAR: f32 = 0.0001;
+
+/// The color of the edges.
+const EDGE_COLOR: Rgba<u8> = Rgba([0, 0, 0, 255]);
+
+/// How much nearer an edge is drawn with respect to the faces, so that edges
+/// lying on a face aren't hidden by it.
+pub const EDGE_BIAS: f32 = 1e-3;
+
+/// How long each frame of a turntable animation is shown, in milliseconds.
+/// GIFs store delays in hundredths of a second, so this should be a multiple
+/// of 10.
+const FRAME_DELAY: u32 = 40;
+
+/// The settings with which a polytope is rendered into an image.
+#[derive(Clone, Copy)]
+pub struct RenderOptions {
+    /// The width of the image in pixels.
+    pub width: u32,
+
+    /// The height of the image in pixels.
+    pub height: u32,
+
+    /// How the polytope is projected down into 3D.
+    pub projection_type: ProjectionType,
+
+    /// The background color of the image.
+    pub background: Color,
+
+    /// Whether the faces are drawn.
+    pub faces: bool,
+
+    /// Whether the edges are drawn.
+    pub edges: bool,
+
+    /// How the edges and vertices are drawn.
+    pub wireframe: WireframeStyle,
+}
+
+/// Returns the vertex positions and the indices of a mesh.
+fn mesh_data(mesh: &Mesh) -> (Vec<[f32; 3]>, Vec<usize>) {
+    let positions = match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+        Some(VertexAttributeValues::Float32x3(positions)) => positions.clone(),
+        _ => Vec::new(),
+    };
+
+    let indices = mesh.indices().map(Indices::iter).map(Iterator::collect);
+    (positions, indices.unwrap_or_default())
+}
+
+/// Converts a Bevy color into an 8-bit sRGBA pixel, scaling its RGB channels
+/// by a given factor.
+fn pixel(color: Color, factor: f32) -> Rgba<u8> {
+    let [r, g, b, _] = color.as_rgba_f32();
+    let c = |x: f32| ((x * factor).clamp(0.0, 1.0) * 255.0).round() as u8;
+    Rgba([c(r), c(g), c(b), 255])
+}
+
+/// An image together with a depth buffer.
+pub struct Canvas {
+    /// The image being drawn.
+    image: RgbaImage,
+
+    /// The inverse depth of each pixel, or 0 if nothing has been drawn there.
+    /// We store inverse depths since these can be linearly interpolated in
+    /// screen space.
+    depth: Vec<f32>,
+
+    /// The matrix mapping world coordinates into camera coordinates.
+    view: Mat4,
+
+    /// The tangent of half the vertical field of view.
+    scale: f32,
+}
+
+impl Canvas {
+    /// Initializes a new canvas from a camera transform.
+    pub fn new(camera: Mat4, options: &RenderOptions) -> Self {
+        let RenderOptions { width, height, .. } = *options;
+
+        Self {
+            image: RgbaImage::from_pixel(width, height, pixel(options.background, 1.0)),
+            depth: vec![0.0; width as usize * height as usize],
+            view: camera.inverse(),
+            scale: (FOV / 2.0).tan(),
+        }
+    }
+
+    /// The width of the canvas.
+    fn width(&self) -> f32 {
+        self.image.width() as f32
+    }
+
+    /// The height of the canvas.
+    fn height(&self) -> f32 {
+        self.image.height() as f32
+    }
+
+    /// Maps a point into camera coordinates.
+    pub fn to_view(&self, p: [f32; 3]) -> Vec3 {
+        self.view.transform_point3(Vec3::from(p))
+    }
+
+    /// Maps a point in camera coordinates into screen coordinates, and stores
+    /// its inverse depth in the third coordinate. Returns `None` if the point
+    /// is behind the camera.
+    pub fn to_screen(&self, v: Vec3) -> Option<Vec3> {
+        let depth = -v.z;
+        if depth < NEAR {
+            return None;
+        }
+
+        let aspect = self.width() / self.height();
+        let x = v.x / (depth * self.scale * aspect);
+        let y = v.y / (depth * self.scale);
+
+        Some(Vec3::new(
+            (x + 1.0) * self.width() / 2.0,
+            (1.0 - y) * self.height() / 2.0,
+            1.0 / depth,
+        ))
+    }
+
+    /// Returns the index of a pixel in the depth buffer, or `None` if it's
+    /// out of the canvas.
+    fn index(&self, x: i64, y: i64) -> Option<usize> {
+        if x < 0 || y < 0 || x >= self.width() as i64 || y >= self.height() as i64 {
+            None
+        } else {
+            Some(y as usize * self.image.width() as usize + x as usize)
+        }
+    }
+
+    /// Returns whether a point with a given inverse depth would be in front
+    /// of whatever's already drawn on a pixel.
+    pub fn is_visible(&self, x: i64, y: i64, inv_depth: f32) -> bool {
+        self.index(x, y)
+            .map_or(true, |idx| inv_depth >= self.depth[idx])
+    }
+
+    /// Draws a pixel if it's in front of whatever's already drawn there.
+    fn plot(&mut self, x: i64, y: i64, inv_depth: f32, color: Rgba<u8>) {
+        if let Some(idx) = self.index(x, y) {
+            if inv_depth >= self.depth[idx] {
+                self.depth[idx] = inv_depth;
+                self.image.put_pixel(x as u32, y as u32, color);
+            }
+        }
+    }
+
+    /// Fills a triangle, shading it according to the angle it makes with the
+    /// camera.
+    fn triangle(&mut self, tri: [[f32; 3]; 3], color: Color) {
+        let [a, b, c] = tri.map(|p| self.to_view(p));
+
+        // We don't bother clipping triangles partially behind the camera.
+        let (p, q, r) = match (self.to_screen(a), self.to_screen(b), self.to_screen(c)) {
+            (Some(p), Some(q), Some(r)) => (p, q, r),
+            _ => return,
+        };
+
+        // Faces are double sided, so we shade them the same from both sides.
+        let normal = (b - a).cross(c - a).normalize_or_zero();
+        let color = pixel(color, 0.25 + 0.75 * normal.z.abs());
+
+        let area = (q.x - p.x) * (r.y - p.y) - (q.y - p.y) * (r.x - p.x);
+        if area.abs() < f32::EPSILON {
+            return;
+        }
+
+        let min_x = p.x.min(q.x).min(r.x).floor().max(0.0) as i64;
+        let max_x = p.x.max(q.x).max(r.x).ceil().min(self.width()) as i64;
+        let min_y = p.y.min(q.y).min(r.y).floor().max(0.0) as i64;
+        let max_y = p.y.max(q.y).max(r.y).ceil().min(self.height()) as i64;
+
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                // We sample at the center of each pixel.
+                let (sx, sy) = (x as f32 + 0.5, y as f32 + 0.5);
+
+                // Barycentric coordinates of the sample.
+                let u = ((q.x - sx) * (r.y - sy) - (q.y - sy) * (r.x - sx)) / area;
+                let v = ((r.x - sx) * (p.y - sy) - (r.y - sy) * (p.x - sx)) / area;
+                let w = 1.0 - u - v;
+
+                if u >= 0.0 && v >= 0.0 && w >= 0.0 {
+                    self.plot(x, y, u * p.z + v * q.z + w * r.z, color);
+                }
+            }
+        }
+    }
+
+    /// Draws a line segment with a given width in pixels.
+    fn line(&mut self, seg: [[f32; 3]; 2], width: i64) {
+        let [a, b] = seg.map(|p| self.to_view(p));
+        let (p, q) = match (self.to_screen(a), self.to_screen(b)) {
+            (Some(p), Some(q)) => (p, q),
+            _ => return,
+        };
+
+        let steps = (q.x - p.x).abs().max((q.y - p.y).abs()).ceil().max(1.0) as i64;
+        let radius = width / 2;
+
+        for i in 0..=steps {
+            let t = i as f32 / steps as f32;
+            let s = p.lerp(q, t);
+            let inv_depth = s.z * (1.0 + EDGE_BIAS);
+
+            for dy in -radius..=(width - 1 - radius) {
+                for dx in -radius..=(width - 1 - radius) {
+                    self.plot(s.x as i64 + dx, s.y as i64 + dy, inv_depth, EDGE_COLOR);
+                }
+            }
+        }
+    }
+
+    /// Fills the faces of a polytope.
+    pub fn faces(&mut self, poly: &Concrete, projection_type: ProjectionType) {
+        let (positions, indices) = mesh_data(&poly.mesh(projection_type));
+
+        for tri in indices.chunks_exact(3) {
+            self.triangle([0, 1, 2].map(|i| positions[tri[i]]), Color::WHITE);
+        }
+    }
+}
+
+/// Renders a polytope as seen from a camera with a given transform.
+pub fn render(poly: &Concrete, camera: Mat4, options: &RenderOptions) -> RgbaImage {
+    let mut canvas = Canvas::new(camera, options);
+
+    if options.faces {
+        canvas.faces(poly, options.projection_type);
+    }
+
+    if options.edges {
+        let (positions, indices) =
+            mesh_data(&poly.styled_wireframe(options.projection_type, options.wireframe));
+
+        match options.wireframe {
+            WireframeStyle::Lines => {
+                // Edges get thicker as the resolution increases.
+                let width = (options.height as i64 / 540).max(1);
+
+                for seg in indices.chunks_exact(2) {
+                    canvas.line([positions[seg[0]], positions[seg[1]]], width);
+                }
+            }
+
+            // Tubes and spheres are solids in their own right.
+            WireframeStyle::Tubes { .. } => {
+                let (vertex_positions, vertex_indices) =
+                    mesh_data(&poly.vertex_mesh(options.projection_type, options.wireframe));
+
+                for (positions, indices) in
+                    [(positions, indices), (vertex_positions, vertex_indices)]
+                {
+                    for tri in indices.chunks_exact(3) {
+                        canvas.triangle([0, 1, 2].map(|i| positions[tri[i]]), Color::BLACK);
+                    }
+                }
+            }
+        }
+    }
+
+    canvas.image
+}
+
+/// Renders a polytope and saves it as a PNG file.
+pub fn save_screenshot<P: AsRef<Path>>(
+    poly: &Concrete,
+    camera: Mat4,
+    options: &RenderOptions,
+    path: P,
+) -> ImageResult<()> {
+    render(poly, camera, options).save(path)
+}
+
+/// Renders a full turn of the polytope about the vertical axis, and saves it
+/// as a looping animated GIF.
+pub fn save_turntable<P: AsRef<Path>>(
+    poly: &Concrete,
+    camera: Mat4,
+    options: &RenderOptions,
+    frames: u32,
+    path: P,
+) -> ImageResult<()> {
+    let mut encoder = GifEncoder::new(BufWriter::new(File::create(path)?));
+    encoder.set_repeat(Repeat::Infinite)?;
+
+    for frame in 0..frames {
+        let angle = std::f32::consts::TAU * frame as f32 / frames as f32;
+
+        // Rotating the camera about the polytope is the same as rotating the
+        // polytope in the opposite direction.
+        let rotated = Mat4::from_rotation_y(-angle) * camera;
+        let image = render(poly, rotated, options);
+        let delay = Delay::from_numer_denom_ms(FRAME_DELAY, 1);
+        encoder.encode_frame(Frame::from_parts(image, 0, 0, delay))?;
+    }
+
+    Ok(())
+}
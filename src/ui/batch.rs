@@ -0,0 +1,223 @@
+//! Contains the window that applies an operation, or the pipeline in the
+//! [recorder](super::recorder), to every OFF file in a folder at once.
+//!
+//! The results are written next to the originals, and the files that couldn't
+//! be processed are listed once the batch is done.
+
+use std::path::PathBuf;
+
+use super::{
+    recorder::Recorder,
+    task::{BackgroundTask, TaskOutput},
+    toast::Toasts,
+    top_panel::FileDialogState,
+};
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+use miratope_core::{
+    conc::pipeline::{BatchSummary, Pipeline, VARIABLE},
+    error::MiratopeError,
+};
+
+/// The operations that can be picked, as functions of the console's language.
+const OPERATIONS: [&str; 14] = [
+    "dual",
+    "pyramid",
+    "prism",
+    "tegum",
+    "antiprism",
+    "antitegum",
+    "petrial",
+    "antipodal",
+    "ditope",
+    "hosotope",
+    "omnitruncate",
+    "recenter",
+    "normalize",
+    "fuse",
+];
+
+/// The label of the recorded pipeline in the list of operations.
+const RECORDED: &str = "recorded pipeline";
+
+/// The plugin in charge of applying operations to folders.
+pub struct BatchPlugin;
+
+impl Plugin for BatchPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BatchWindow>()
+            .add_event::<BatchEvent>()
+            .add_system(batch_events.system().before("show_top_panel"))
+            .add_system(show_batch_window.system().after("show_top_panel"));
+    }
+}
+
+/// A request to apply the chosen operation to a folder, sent once the user has
+/// picked it.
+pub struct BatchEvent(pub PathBuf);
+
+/// The state of the window that applies operations to folders.
+pub struct BatchWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The chosen operation, or `None` for the recorded pipeline.
+    operation: Option<&'static str>,
+
+    /// The suffix added to the names of the results.
+    suffix: String,
+
+    /// The outcome of the last batch, if any.
+    summary: Option<BatchSummary>,
+}
+
+impl Default for BatchWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            operation: Some(OPERATIONS[0]),
+            suffix: OPERATIONS[0].to_string(),
+            summary: None,
+        }
+    }
+}
+
+impl BatchWindow {
+    /// Opens the window.
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+
+    /// Shows the outcome of a batch.
+    pub fn set_summary(&mut self, summary: BatchSummary) {
+        self.summary = Some(summary);
+        self.open = true;
+    }
+
+    /// Returns the pipeline to apply.
+    fn pipeline(&self, recorder: &Recorder) -> Pipeline {
+        match self.operation {
+            Some(operation) => {
+                let mut pipeline = Pipeline::new();
+                pipeline
+                    .push(format!("{}({})", operation, VARIABLE))
+                    .expect("operations should always parse");
+                pipeline
+            }
+            None => recorder.pipeline().clone(),
+        }
+    }
+}
+
+/// The system that applies the chosen operation to a folder in the background.
+fn batch_events(
+    mut events: EventReader<'_, '_, BatchEvent>,
+    batch_window: Res<'_, BatchWindow>,
+    recorder: Res<'_, Recorder>,
+    mut background_task: ResMut<'_, BackgroundTask>,
+    mut toasts: ResMut<'_, Toasts>,
+) {
+    for BatchEvent(folder) in events.iter() {
+        let pipeline = batch_window.pipeline(&recorder);
+        if pipeline.is_empty() {
+            toasts.error(MiratopeError::operation(
+                "Apply to folder",
+                "the recorded pipeline is empty",
+            ));
+            continue;
+        }
+
+        let folder = folder.clone();
+        let suffix = batch_window.suffix.clone();
+
+        background_task.spawn("Apply to folder", move |progress| {
+            let res = pipeline.apply_to_folder(&folder, &suffix, |done, total| {
+                progress.set(done, total);
+                !progress.is_cancelled()
+            });
+
+            match res {
+                Ok(summary) => TaskOutput::Batch(summary),
+                Err(err) => TaskOutput::Error(err.to_string()),
+            }
+        });
+    }
+}
+
+/// The system that shows the window to apply operations to folders.
+fn show_batch_window(
+    egui_ctx: Res<'_, EguiContext>,
+    mut batch_window: ResMut<'_, BatchWindow>,
+    mut file_dialog_state: ResMut<'_, FileDialogState>,
+    background_task: Res<'_, BackgroundTask>,
+) {
+    if !batch_window.open {
+        return;
+    }
+
+    let mut open = true;
+
+    egui::Window::new("Apply to folder")
+        .open(&mut open)
+        .default_width(320.0)
+        .show(egui_ctx.ctx(), |ui| {
+            let batch_window = &mut *batch_window;
+            let previous = batch_window.operation;
+
+            egui::ComboBox::from_label("Operation")
+                .selected_text(batch_window.operation.unwrap_or(RECORDED))
+                .show_ui(ui, |ui| {
+                    for &operation in OPERATIONS.iter() {
+                        ui.selectable_value(
+                            &mut batch_window.operation,
+                            Some(operation),
+                            operation,
+                        );
+                    }
+                    ui.selectable_value(&mut batch_window.operation, None, RECORDED);
+                });
+
+            // The suffix follows the operation, unless it's changed by hand.
+            if batch_window.operation != previous {
+                batch_window.suffix = batch_window.operation.unwrap_or("pipeline").to_string();
+            }
+
+            ui.horizontal(|ui| {
+                ui.label("Suffix");
+                ui.text_edit_singleline(&mut batch_window.suffix);
+            });
+
+            ui.label("Each result is saved next to its original, as \"name (suffix).off\".");
+
+            let enabled = !background_task.is_running() && !batch_window.suffix.trim().is_empty();
+            if ui
+                .add(egui::Button::new("Choose folder...").enabled(enabled))
+                .clicked()
+            {
+                file_dialog_state.batch_folder();
+            }
+
+            if let Some(summary) = &batch_window.summary {
+                ui.separator();
+                ui.label(format!("Last batch: {}.", summary));
+
+                if !summary.failed.is_empty() {
+                    ui.collapsing("Failures", |ui| {
+                        egui::ScrollArea::auto_sized().show(ui, |ui| {
+                            for (path, err) in &summary.failed {
+                                ui.colored_label(
+                                    egui::Color32::LIGHT_RED,
+                                    format!("{}: {}", path.display(), err),
+                                );
+                            }
+                        });
+                    });
+                }
+            }
+        });
+
+    if !open {
+        batch_window.open = false;
+    }
+}
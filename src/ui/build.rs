@@ -0,0 +1,210 @@
+//! Contains the build animation, which reveals the polytope one facet at a
+//! time.
+//!
+//! The facets are revealed in the order of a breadth-first search through the
+//! facets that share a ridge, so that every new facet is attached to the ones
+//! shown before it. The animation can be played back or scrubbed through with
+//! a timeline slider.
+
+use crate::mesh::Revealed;
+use crate::Concrete;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+use miratope_core::abs::Ranked;
+
+/// The plugin in charge of the build animation.
+pub struct BuildAnimationPlugin;
+
+impl Plugin for BuildAnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BuildAnimation>()
+            .add_system(
+                show_build_window
+                    .system()
+                    .label("show_build_window")
+                    .after("show_top_panel"),
+            )
+            .add_system(advance_build_animation.system().after("show_build_window"))
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_build_order
+                    .system()
+                    .before("update_changed_polytopes"),
+            );
+    }
+}
+
+/// The state of the build animation.
+#[derive(Clone, Debug)]
+pub struct BuildAnimation {
+    /// Whether the animation window is open. The whole polytope is drawn
+    /// otherwise.
+    open: bool,
+
+    /// The order in which the facets are revealed.
+    order: Vec<usize>,
+
+    /// How many facets are revealed. This is fractional while the animation
+    /// is playing.
+    progress: f32,
+
+    /// Whether the animation is playing.
+    playing: bool,
+
+    /// How many facets are revealed every second.
+    speed: f32,
+}
+
+impl Default for BuildAnimation {
+    fn default() -> Self {
+        Self {
+            open: false,
+            order: Vec::new(),
+            progress: 0.0,
+            playing: false,
+            speed: 2.0,
+        }
+    }
+}
+
+impl BuildAnimation {
+    /// Opens the window, and plays the animation from the start.
+    pub fn open(&mut self) {
+        self.open = true;
+        self.progress = 0.0;
+        self.playing = true;
+    }
+
+    /// Returns the number of facets that are revealed.
+    fn shown(&self) -> usize {
+        (self.progress as usize).min(self.order.len())
+    }
+
+    /// Returns the elements of the polytope drawn at the current point of the
+    /// animation, or `None` if the whole polytope is drawn. Only polyhedra
+    /// and higher-rank polytopes are built facet by facet.
+    pub fn revealed(&self, poly: &Concrete) -> Option<Revealed> {
+        let rank = poly.rank();
+        if !self.open || rank < 4 || self.order.len() != poly.el_count(rank - 1) {
+            return None;
+        }
+
+        Some(Revealed::from_facets(
+            poly,
+            self.order[..self.shown()].iter().copied(),
+        ))
+    }
+}
+
+/// The system that recomputes the order of the facets whenever the polytope
+/// changes.
+fn update_build_order(
+    mut animation: ResMut<'_, BuildAnimation>,
+    polies: Query<'_, '_, &Concrete, Changed<Concrete>>,
+) {
+    if !animation.open {
+        return;
+    }
+
+    if let Some(poly) = polies.iter().next() {
+        animation.order = poly.abs.facet_bfs_order();
+    }
+}
+
+/// The system that plays the animation, and redraws the polytope whenever a
+/// facet is revealed or hidden.
+fn advance_build_animation(
+    time: Res<'_, Time>,
+    mut animation: ResMut<'_, BuildAnimation>,
+    mut polies: Query<'_, '_, &mut Concrete>,
+    mut drawn: Local<'_, Option<usize>>,
+) {
+    if animation.playing {
+        let end = animation.order.len() as f32;
+        animation.progress += animation.speed * time.delta_seconds();
+
+        if animation.progress >= end {
+            animation.progress = end;
+            animation.playing = false;
+        }
+    }
+
+    let shown = if animation.open {
+        Some(animation.shown())
+    } else {
+        None
+    };
+
+    if *drawn != shown {
+        *drawn = shown;
+
+        for mut poly in polies.iter_mut() {
+            poly.set_changed();
+        }
+    }
+}
+
+/// The system that shows the window with the controls of the animation.
+fn show_build_window(
+    egui_ctx: Res<'_, EguiContext>,
+    mut animation: ResMut<'_, BuildAnimation>,
+    polies: Query<'_, '_, &Concrete>,
+) {
+    if !animation.open {
+        return;
+    }
+
+    let mut open = true;
+
+    egui::Window::new("Build animation")
+        .open(&mut open)
+        .resizable(false)
+        .show(egui_ctx.ctx(), |ui| {
+            if polies.iter().next().map_or(true, |poly| poly.rank() < 4) {
+                ui.label("Only polyhedra and higher can be built facet by facet.");
+                return;
+            }
+
+            let len = animation.order.len();
+            let mut shown = animation.shown();
+            if ui
+                .add(egui::Slider::new(&mut shown, 0..=len).text("Facets"))
+                .changed()
+            {
+                animation.progress = shown as f32;
+                animation.playing = false;
+            }
+
+            ui.horizontal(|ui| {
+                let label = if animation.playing { "Pause" } else { "Play" };
+                if ui.button(label).clicked() {
+                    // Playing a finished animation starts it over.
+                    if !animation.playing && animation.shown() == len {
+                        animation.progress = 0.0;
+                    }
+
+                    animation.playing = !animation.playing;
+                }
+
+                if ui.button("Restart").clicked() {
+                    animation.progress = 0.0;
+                    animation.playing = true;
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::DragValue::new(&mut animation.speed)
+                        .speed(0.1)
+                        .clamp_range(0.1..=1000.0),
+                );
+                ui.label("Facets per second");
+            });
+        });
+
+    if !open {
+        animation.open = false;
+        animation.playing = false;
+    }
+}
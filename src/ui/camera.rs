@@ -17,6 +17,7 @@ impl Plugin for InputPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<CameraInputEvent>()
             .init_resource::<ProjectionType>()
+            .init_resource::<CameraMode>()
             // We register inputs after the library has been shown, so that we
             // know whether mouse input should register.
             .add_system(add_cam_input_events.system().after("show_library"))
@@ -31,6 +32,10 @@ pub enum ProjectionType {
 
     /// We're projecting from a point.
     Perspective,
+
+    /// We're drawing a Schlegel diagram through the facet with the given
+    /// index. Polytopes that aren't 4D are projected from a point instead.
+    Schlegel(usize),
 }
 
 impl Default for ProjectionType {
@@ -44,7 +49,7 @@ impl ProjectionType {
     pub fn flip(&mut self) {
         match self {
             Self::Orthogonal => *self = Self::Perspective,
-            Self::Perspective => *self = Self::Orthogonal,
+            Self::Perspective | Self::Schlegel(_) => *self = Self::Orthogonal,
         }
     }
 
@@ -52,6 +57,48 @@ impl ProjectionType {
     pub fn is_orthogonal(&self) -> bool {
         matches!(self, Self::Orthogonal)
     }
+
+    /// Returns the facet the Schlegel diagram is drawn through, if any.
+    pub fn schlegel_facet(&self) -> Option<usize> {
+        match self {
+            Self::Schlegel(facet) => Some(*facet),
+            _ => None,
+        }
+    }
+}
+
+/// How the camera moves around the polytope.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CameraMode {
+    /// The camera orbits about an anchor in front of it, and can't get any
+    /// nearer to it than a certain distance.
+    Orbit,
+
+    /// The camera sits at the anchor, so that it can fly inside of the
+    /// polytope and look around freely. Zooming moves it forwards.
+    Fly,
+}
+
+impl Default for CameraMode {
+    fn default() -> Self {
+        Self::Orbit
+    }
+}
+
+impl CameraMode {
+    /// Moves the camera onto its anchor, keeping its position in space.
+    fn fly_in(anchor_tf: &mut Transform, cam_tf: &mut Transform) {
+        anchor_tf.translation += anchor_tf.rotation * cam_tf.translation;
+        cam_tf.translation = Vec3::ZERO;
+    }
+
+    /// Moves the anchor in front of the camera, as far away as the origin is,
+    /// keeping the camera's position in space.
+    fn fly_out(anchor_tf: &mut Transform, cam_tf: &mut Transform) {
+        let dist = anchor_tf.translation.length().max(0.2);
+        cam_tf.translation = Vec3::new(0.0, 0.0, dist);
+        anchor_tf.translation -= anchor_tf.rotation * cam_tf.translation;
+    }
 }
 
 /// An input event for the camera.
@@ -136,13 +183,21 @@ impl CameraInputEvent {
         anchor_tf: &mut Transform,
         cam_tf: &mut Transform,
         cam_gtf: &GlobalTransform,
+        mode: CameraMode,
     ) {
-        match *self {
-            Self::RotateAnchor(vec) => Self::rotate(vec, anchor_tf),
-            Self::Translate(vec) => Self::translate(vec, anchor_tf, cam_gtf),
-            Self::Roll(roll) => Self::roll(roll, anchor_tf),
-            Self::Zoom(zoom) => Self::zoom(zoom, cam_tf),
-            Self::Reset => Self::reset(anchor_tf, cam_tf),
+        match (*self, mode) {
+            (Self::RotateAnchor(vec), _) => Self::rotate(vec, anchor_tf),
+            (Self::Translate(vec), _) => Self::translate(vec, anchor_tf, cam_gtf),
+            (Self::Roll(roll), _) => Self::roll(roll, anchor_tf),
+            (Self::Zoom(zoom), CameraMode::Orbit) => Self::zoom(zoom, cam_tf),
+            (Self::Zoom(zoom), CameraMode::Fly) => {
+                Self::translate(Vec3::new(0.0, 0.0, zoom), anchor_tf, cam_gtf)
+            }
+            (Self::Reset, CameraMode::Orbit) => Self::reset(anchor_tf, cam_tf),
+            (Self::Reset, CameraMode::Fly) => {
+                Self::reset(anchor_tf, cam_tf);
+                CameraMode::fly_in(anchor_tf, cam_tf);
+            }
         }
     }
 
@@ -261,6 +316,8 @@ fn add_cam_input_events(
 
 fn update_cameras_and_anchors(
     mut events: EventReader<'_, '_, CameraInputEvent>,
+    mode: Res<'_, CameraMode>,
+    mut old_mode: Local<'_, CameraMode>,
     q: Query<
         '_,
         '_,
@@ -281,11 +338,22 @@ fn update_cameras_and_anchors(
                 if let Ok(mut anchor_tf) =
                     unsafe { q.get_component_unchecked_mut::<Transform>(parent.0) }
                 {
+                    // Moves the camera onto or off of its anchor when the mode
+                    // changes.
+                    if *old_mode != *mode {
+                        match *mode {
+                            CameraMode::Orbit => CameraMode::fly_out(&mut anchor_tf, &mut cam_tf),
+                            CameraMode::Fly => CameraMode::fly_in(&mut anchor_tf, &mut cam_tf),
+                        }
+                    }
+
                     for event in events.iter() {
-                        event.update_camera_and_anchor(&mut anchor_tf, &mut cam_tf, cam_gtf);
+                        event.update_camera_and_anchor(&mut anchor_tf, &mut cam_tf, cam_gtf, *mode);
                     }
                 }
             }
         }
     }
+
+    *old_mode = *mode;
 }
@@ -0,0 +1,181 @@
+//! Contains the window that counts the ways to color the elements of the
+//! polytope up to symmetry.
+//!
+//! The symmetry group is only computed when asked for, since it can take a
+//! while for large polytopes. The counts are then read off its cycle index,
+//! and update as soon as the number of colors is changed.
+
+use crate::Concrete;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+use miratope_core::{abs::Ranked, conc::element_types::EL_NAMES, group::burnside::CycleIndex};
+
+/// The plugin in charge of the colorings window.
+pub struct ColoringsPlugin;
+
+impl Plugin for ColoringsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ColoringsWindow>()
+            .add_system(show_colorings_window.system().after("show_top_panel"))
+            .add_system_to_stage(CoreStage::PostUpdate, clear_cycle_index.system());
+    }
+}
+
+/// The state of the colorings window.
+#[derive(Clone, Debug)]
+pub struct ColoringsWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The rank of the elements that are colored.
+    rank: usize,
+
+    /// Whether only the rotations of the polytope are taken into account.
+    chiral: bool,
+
+    /// The number of colors.
+    colors: usize,
+
+    /// The number of elements of the first color, when only two are used.
+    subset_size: usize,
+
+    /// The cycle index of the action of the symmetries on the elements, once
+    /// it's been computed.
+    index: Option<CycleIndex>,
+}
+
+impl Default for ColoringsWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            rank: 1,
+            chiral: false,
+            colors: 2,
+            subset_size: 1,
+            index: None,
+        }
+    }
+}
+
+impl ColoringsWindow {
+    /// Opens the window.
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+}
+
+/// The system that forgets the cycle index whenever the polytope changes.
+fn clear_cycle_index(
+    mut window: ResMut<'_, ColoringsWindow>,
+    polies: Query<'_, '_, &Concrete, Changed<Concrete>>,
+) {
+    if window.index.is_some() && polies.iter().next().is_some() {
+        window.index = None;
+    }
+}
+
+/// The system that shows the colorings window.
+fn show_colorings_window(
+    egui_ctx: Res<'_, EguiContext>,
+    mut window: ResMut<'_, ColoringsWindow>,
+    polies: Query<'_, '_, &Concrete>,
+) {
+    if !window.open {
+        return;
+    }
+
+    let poly = match polies.iter().next() {
+        Some(poly) => poly,
+        None => return,
+    };
+    let rank = poly.rank();
+    let mut open = true;
+
+    egui::Window::new("Colorings")
+        .open(&mut open)
+        .resizable(false)
+        .show(egui_ctx.ctx(), |ui| {
+            if rank < 2 {
+                ui.label("The polytope has no elements to color.");
+                return;
+            }
+
+            // The elements that are colored.
+            let old_rank = window.rank;
+            window.rank = window.rank.clamp(1, rank - 1);
+            egui::ComboBox::from_label("Elements")
+                .selected_text(EL_NAMES[window.rank])
+                .show_ui(ui, |ui| {
+                    for r in 1..rank {
+                        ui.selectable_value(&mut window.rank, r, EL_NAMES[r]);
+                    }
+                });
+
+            let old_chiral = window.chiral;
+            ui.checkbox(&mut window.chiral, "Rotations only");
+
+            if window.rank != old_rank || window.chiral != old_chiral {
+                window.index = None;
+            }
+
+            ui.separator();
+
+            let index = match &window.index {
+                Some(index) => index.clone(),
+                None => {
+                    if ui.button("Compute symmetry").clicked() {
+                        let (rank, chiral) = (window.rank, window.chiral);
+                        window.index = Some(poly.clone().cycle_index(rank, chiral));
+                    }
+
+                    return;
+                }
+            };
+
+            let name = EL_NAMES[window.rank].to_lowercase();
+            ui.label(format!("Symmetry order: {}", index.order()));
+            ui.label(format!("Orbits of {}: {}", name, index.orbit_count()));
+
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::DragValue::new(&mut window.colors)
+                        .speed(0.05)
+                        .clamp_range(1..=100),
+                );
+                ui.label("colors:");
+                ui.label(index.colorings(window.colors).to_string());
+            })
+            .response
+            .on_hover_text("Colorings of the elements up to symmetry");
+
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::DragValue::new(&mut window.subset_size)
+                        .speed(0.05)
+                        .clamp_range(0..=index.degree()),
+                );
+                ui.label(format!("of {} {}:", index.degree(), name));
+                ui.label(index.subsets(window.subset_size).to_string());
+            })
+            .response
+            .on_hover_text("Ways to pick this many elements up to symmetry");
+
+            if ui.button("Copy").clicked() {
+                ui.output().copied_text = format!(
+                    "{} colorings of the {} in {} colors, {} ways to pick {} of them",
+                    index.colorings(window.colors),
+                    name,
+                    window.colors,
+                    index.subsets(window.subset_size),
+                    window.subset_size,
+                );
+            }
+        });
+
+    if !open {
+        window.open = false;
+    }
+}
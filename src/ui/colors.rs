@@ -0,0 +1,187 @@
+//! Contains the color scheme of the polytope: the colors its faces, edges and
+//! vertices are drawn in.
+//!
+//! Every polytope entity has a [`ColorScheme`] component, which is kept as the
+//! polytope is changed by operations. Color schemes are saved into OFF files
+//! and read back from them, in which case they replace the current one.
+
+use std::borrow::Cow;
+
+use super::main_window::VertexMarker;
+use crate::Concrete;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+use miratope_core::conc::color::{Color as RawColor, ColorScheme};
+
+/// The color of the faces when the scheme doesn't set one.
+const FACE_COLOR: Color = Color::WHITE;
+
+/// The color of the edges when the scheme doesn't set one.
+const EDGE_COLOR: Color = Color::BLACK;
+
+/// The color of the vertices when the scheme doesn't set one.
+const VERTEX_COLOR: Color = Color::BLACK;
+
+/// The plugin in charge of color schemes.
+pub struct ColorSchemePlugin;
+
+impl Plugin for ColorSchemePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ColorSchemeWindow>()
+            .add_system(update_materials.system())
+            .add_system(show_color_scheme_window.system().after("show_top_panel"))
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                load_color_scheme
+                    .system()
+                    .before("update_changed_polytopes"),
+            );
+    }
+}
+
+/// The window where the color scheme is edited.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ColorSchemeWindow {
+    /// Whether the window is open.
+    open: bool,
+}
+
+impl ColorSchemeWindow {
+    /// Opens the window.
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+}
+
+/// Converts a color from the core crate into a Bevy color.
+fn to_bevy([r, g, b, a]: RawColor) -> Color {
+    Color::rgba(r, g, b, a)
+}
+
+/// Returns a polytope with a given color scheme, so that it's saved along with
+/// it. The polytope is only cloned if its scheme differs.
+pub fn with_scheme<'a>(poly: &'a Concrete, scheme: &ColorScheme) -> Cow<'a, Concrete> {
+    if poly.colors.scheme == *scheme {
+        Cow::Borrowed(poly)
+    } else {
+        let mut poly = poly.clone();
+        poly.colors.scheme = *scheme;
+        Cow::Owned(poly)
+    }
+}
+
+/// The system that takes the color scheme read from a file, and moves it into
+/// the component of the polytope.
+///
+/// The polytope doesn't keep it, so that the component is the only place
+/// where the scheme can be changed.
+fn load_color_scheme(
+    mut query: Query<'_, '_, (&mut Concrete, &mut ColorScheme), Changed<Concrete>>,
+) {
+    for (mut poly, mut scheme) in query.iter_mut() {
+        if !poly.colors.scheme.is_empty() {
+            *scheme = std::mem::take(&mut poly.colors.scheme);
+        }
+    }
+}
+
+/// The system that updates the materials of the polytope whenever its color
+/// scheme changes.
+fn update_materials(
+    mut materials: ResMut<'_, Assets<StandardMaterial>>,
+    polies: Query<
+        '_,
+        '_,
+        (&ColorScheme, &Handle<StandardMaterial>, &Children),
+        Changed<ColorScheme>,
+    >,
+    children: Query<'_, '_, (&Handle<StandardMaterial>, Option<&VertexMarker>), Without<Concrete>>,
+) {
+    for (scheme, material, wfs) in polies.iter() {
+        if let Some(material) = materials.get_mut(material) {
+            material.base_color = scheme.faces.map_or(FACE_COLOR, to_bevy);
+        }
+
+        for &child in wfs.iter() {
+            if let Ok((material, vertices)) = children.get(child) {
+                let color = if vertices.is_some() {
+                    scheme.vertices.map_or(VERTEX_COLOR, to_bevy)
+                } else {
+                    scheme.edges.map_or(EDGE_COLOR, to_bevy)
+                };
+
+                if let Some(material) = materials.get_mut(material) {
+                    material.base_color = color;
+                }
+            }
+        }
+    }
+}
+
+/// The system that shows the window where the color scheme is edited.
+fn show_color_scheme_window(
+    egui_ctx: Res<'_, EguiContext>,
+    mut window: ResMut<'_, ColorSchemeWindow>,
+    mut schemes: Query<'_, '_, &mut ColorScheme>,
+) {
+    if !window.open {
+        return;
+    }
+
+    let mut scheme = match schemes.iter_mut().next() {
+        Some(scheme) => scheme,
+        None => return,
+    };
+
+    // We only touch the component when something changes, so that the
+    // materials aren't updated every frame.
+    let mut new_scheme = *scheme;
+    let mut open = true;
+
+    egui::Window::new("Colors")
+        .open(&mut open)
+        .resizable(false)
+        .show(egui_ctx.ctx(), |ui| {
+            for (name, color) in new_scheme.colors_mut() {
+                ui.horizontal(|ui| {
+                    let mut custom = color.is_some();
+                    if ui.checkbox(&mut custom, name).changed() {
+                        *color = custom.then(|| [1.0; 4]);
+                    }
+
+                    if let Some(rgba) = color {
+                        let [r, g, b, a] = rgba.map(|c| (c * 255.0).round() as u8);
+                        let old_color = egui::Color32::from_rgba_unmultiplied(r, g, b, a);
+
+                        let mut new_color = old_color;
+                        egui::color_picker::color_edit_button_srgba(
+                            ui,
+                            &mut new_color,
+                            egui::color_picker::Alpha::Opaque,
+                        );
+
+                        // Colors read from files might not be 8-bit.
+                        if new_color != old_color {
+                            *rgba = [new_color.r(), new_color.g(), new_color.b(), 255]
+                                .map(|c| c as f32 / 255.0);
+                        }
+                    }
+                });
+            }
+
+            ui.separator();
+
+            if ui.button("Reset").clicked() {
+                new_scheme = ColorScheme::default();
+            }
+        });
+
+    if new_scheme != *scheme {
+        *scheme = new_scheme;
+    }
+
+    if !open {
+        window.open = false;
+    }
+}
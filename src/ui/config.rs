@@ -9,7 +9,9 @@ use std::{
 
 use bevy::{app::AppExit, prelude::*};
 use bevy_egui::{egui, EguiContext};
+#[cfg(not(target_arch = "wasm32"))]
 use directories::ProjectDirs;
+use miratope_core::float::Float;
 use serde::{Deserialize, Serialize};
 
 /// The default path in which we look for the Miratope library.
@@ -32,6 +34,7 @@ impl Plugin for ConfigPlugin {
 
         // Reads the entire configuration from file.
         let config = Config::read(&config_dir, &config_path);
+        config.tolerance.apply();
 
         // Makes resources from the configuration, which may or may not
         // correspond to the actual stored values themselves.
@@ -114,6 +117,39 @@ fn update_visuals(egui_ctx: Res<'_, EguiContext>, visuals: Res<'_, egui::Visuals
     }
 }
 
+/// The tolerance used when comparing floating-point values, as set with
+/// [`Float::set_eps`].
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct Tolerance(f64);
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self(f64::EPS)
+    }
+}
+
+impl Tolerance {
+    /// Makes a new tolerance with a given value.
+    pub fn new(eps: f64) -> Self {
+        Self(eps)
+    }
+
+    /// Returns the value of the tolerance.
+    pub fn value(self) -> f64 {
+        self.0
+    }
+
+    /// Returns the tolerance currently in use.
+    pub fn current() -> Self {
+        Self(f64::eps())
+    }
+
+    /// Sets this tolerance for every operation from now on.
+    pub fn apply(self) {
+        f64::set_eps(self.0);
+    }
+}
+
 /// A monolithic struct that contains all of the configuration data for
 /// Miratope. This is used only to read and write to disk – throughout the rest
 /// of the application, each of its attributes represents a separate resource.
@@ -127,10 +163,16 @@ pub struct Config {
 
     /// Whether light mode is enabled.
     pub light_mode: LightMode,
+
+    /// The tolerance used when comparing floating-point values. Older
+    /// configuration files don't have it.
+    #[serde(default)]
+    pub tolerance: Tolerance,
 }
 
 impl Config {
     /// Returns the path to the configuration directory in Miratope.
+    #[cfg(not(target_arch = "wasm32"))]
     pub fn config_dir() -> PathBuf {
         if let Some(proj_dir) = ProjectDirs::from("rs", "Miratope", "Miratope") {
             proj_dir.config_dir().to_owned()
@@ -139,6 +181,13 @@ impl Config {
         }
     }
 
+    /// Returns the path to the configuration directory in Miratope. There's no
+    /// such thing on the web, so the default configuration is always used.
+    #[cfg(target_arch = "wasm32")]
+    pub fn config_dir() -> PathBuf {
+        PathBuf::new()
+    }
+
     /// Attempts to read the configuration from a given path.
     pub fn from_path<T: AsRef<OsStr>>(config_path: T) -> Option<Self> {
         ron::from_str(&fs::read_to_string(config_path.as_ref()).ok()?).ok()
@@ -207,6 +256,7 @@ fn save_config(
 
             background_color: BgColor::new(background_color.as_ref()),
             light_mode: LightMode(!visuals.dark_mode),
+            tolerance: Tolerance::current(),
         };
 
         config.save(&config_path.0);
@@ -0,0 +1,177 @@
+//! Contains the console, a window where the user can type expressions like
+//! `dual(prism(load("ike.off")))` to build polytopes, instead of going through
+//! a sequence of buttons.
+//!
+//! The language itself is defined in [`miratope_core::conc::script`].
+
+use crate::Concrete;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+use miratope_core::conc::script::{Interpreter, Value, FUNCTIONS};
+
+/// The maximum number of lines kept in the history of the console.
+const MAX_HISTORY: usize = 200;
+
+/// The name of the variable that always holds the loaded polytope.
+const CURRENT: &str = "current";
+
+/// The plugin in charge of the console.
+pub struct ConsolePlugin;
+
+impl Plugin for ConsolePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Console>()
+            .add_system(show_console.system().after("show_top_panel"));
+    }
+}
+
+/// A line in the history of the console.
+enum HistoryLine {
+    /// A line typed by the user.
+    Input(String),
+
+    /// The result of running a line.
+    Output(String),
+
+    /// The error from running a line.
+    Error(String),
+}
+
+/// The state of the console.
+#[derive(Default)]
+pub struct Console {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The line currently being typed.
+    input: String,
+
+    /// The lines typed so far, and their results.
+    history: Vec<HistoryLine>,
+
+    /// The interpreter, which keeps track of the variables.
+    interpreter: Interpreter,
+}
+
+impl Console {
+    /// Opens the console.
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+
+    /// Returns the lines typed so far.
+    pub fn inputs(&self) -> impl Iterator<Item = &str> {
+        self.history.iter().filter_map(|line| match line {
+            HistoryLine::Input(line) => Some(line.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Replaces the history with some lines typed in a previous session. The
+    /// lines aren't run again.
+    pub fn restore(&mut self, inputs: Vec<String>) {
+        self.history = inputs.into_iter().map(HistoryLine::Input).collect();
+    }
+
+    /// Adds a line to the history, removing the oldest one if needed.
+    fn push(&mut self, line: HistoryLine) {
+        if self.history.len() == MAX_HISTORY {
+            self.history.remove(0);
+        }
+        self.history.push(line);
+    }
+
+    /// Runs the line currently typed. Returns the polytope to load, if any.
+    fn run(&mut self, loaded: &Concrete) -> Option<Concrete> {
+        let line = std::mem::take(&mut self.input);
+        if line.trim().is_empty() {
+            return None;
+        }
+
+        self.interpreter
+            .set(CURRENT, Value::Polytope(loaded.clone()));
+        let res = self.interpreter.run(&line);
+        self.push(HistoryLine::Input(line));
+
+        match res {
+            Ok(Some(value)) => {
+                self.push(HistoryLine::Output(value.to_string()));
+                match value {
+                    Value::Polytope(p) => Some(p),
+                    _ => None,
+                }
+            }
+            Ok(None) => None,
+            Err(err) => {
+                self.push(HistoryLine::Error(err.to_string()));
+                None
+            }
+        }
+    }
+}
+
+/// The system that shows the console.
+fn show_console(
+    egui_ctx: Res<'_, EguiContext>,
+    mut query: Query<'_, '_, &mut Concrete>,
+    mut console: ResMut<'_, Console>,
+) {
+    if !console.open {
+        return;
+    }
+
+    let mut open = true;
+    let mut entered = false;
+
+    egui::Window::new("Console")
+        .open(&mut open)
+        .default_width(420.0)
+        .show(egui_ctx.ctx(), |ui| {
+            egui::ScrollArea::auto_sized().show(ui, |ui| {
+                for line in &console.history {
+                    match line {
+                        HistoryLine::Input(line) => ui.monospace(format!("> {}", line)),
+                        HistoryLine::Output(line) => ui.label(line),
+                        HistoryLine::Error(line) => {
+                            ui.colored_label(egui::Color32::LIGHT_RED, line)
+                        }
+                    };
+                }
+            });
+
+            ui.separator();
+
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut console.input)
+                    .code_editor()
+                    .hint_text("duoprism(polygon(5), polygon(3))"),
+            );
+            entered = response.lost_focus() && ui.input().key_pressed(egui::Key::Enter);
+            if entered {
+                response.request_focus();
+            }
+
+            ui.collapsing("Functions", |ui| {
+                ui.label(format!(
+                    "The loaded polytope is stored in \"{}\". Assign variables with \"p = ...\".",
+                    CURRENT
+                ));
+                for function in FUNCTIONS.iter() {
+                    ui.monospace(*function);
+                }
+            });
+        });
+
+    if entered {
+        if let Some(mut p) = query.iter_mut().next() {
+            if let Some(q) = console.run(&p) {
+                *p = q;
+            }
+        }
+    }
+
+    if !open {
+        console.open = false;
+    }
+}
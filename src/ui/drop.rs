@@ -0,0 +1,154 @@
+//! Loads the polytope files dropped onto the window.
+//!
+//! A single file replaces the loaded polytope. When several files are dropped
+//! at once, each of them is stored in its own memory slot and the first one is
+//! loaded, unless Ctrl is held, in which case they're loaded together as a
+//! compound.
+
+use std::path::{Path, PathBuf};
+
+use super::{
+    memory::Memory,
+    toast::Toasts,
+    top_panel::{advanced, ShowMemory},
+};
+use crate::Concrete;
+
+use bevy::{prelude::*, window::FileDragAndDrop};
+use miratope_core::{
+    abs::Ranked, conc::ConcretePolytope, error::MiratopeError, file::FromFile, Polytope,
+};
+
+/// The plugin in charge of dropped files.
+pub struct DropPlugin;
+
+impl Plugin for DropPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(file_drop.system().before("show_top_panel"));
+    }
+}
+
+/// The extensions of the files that can be loaded as polytopes. GGB files are
+/// left out, since their reader isn't finished and panics on most of them.
+const EXTENSIONS: [&str; 4] = ["off", "csv", "tsv", "poff"];
+
+/// Returns whether a file can be loaded as a polytope.
+fn is_polytope(path: &Path) -> bool {
+    path.extension().map_or(false, |ext| {
+        EXTENSIONS
+            .iter()
+            .any(|known| ext.eq_ignore_ascii_case(known))
+    })
+}
+
+/// Returns the name of a dropped file without its extension, used to label
+/// its memory slot.
+fn file_label(path: &Path) -> String {
+    path.file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Loads a polytope into the scene.
+fn load(p: &mut Concrete, q: Concrete, toasts: &mut Toasts) {
+    *p = q;
+    p.recenter();
+
+    if let Some(name) = p.recognize() {
+        toasts.info(format!("Recognized as {}.", name));
+    }
+}
+
+/// The system that loads the files dropped onto the window.
+fn file_drop(
+    mut events: EventReader<'_, '_, FileDragAndDrop>,
+    keyboard: Res<'_, Input<KeyCode>>,
+    mut query: Query<'_, '_, &mut Concrete>,
+    mut memory: ResMut<'_, Memory>,
+    mut show_memory: ResMut<'_, ShowMemory>,
+    mut toasts: ResMut<'_, Toasts>,
+) {
+    // Files dropped together arrive in the same frame.
+    let paths: Vec<PathBuf> = events
+        .iter()
+        .filter_map(|event| match event {
+            FileDragAndDrop::DroppedFile { path_buf, .. } => Some(path_buf.clone()),
+            _ => None,
+        })
+        .collect();
+
+    if paths.is_empty() {
+        return;
+    }
+
+    let mut loaded = Vec::with_capacity(paths.len());
+    for path in paths {
+        if !is_polytope(&path) {
+            toasts.error(MiratopeError::load(
+                &path,
+                format!("not a polytope file (expected {})", EXTENSIONS.join(", ")),
+            ));
+            continue;
+        }
+
+        match Concrete::from_path(&path) {
+            Ok(q) => loaded.push((path, q)),
+            Err(err) => toasts.error(MiratopeError::load(&path, err)),
+        }
+    }
+
+    let mut p = match query.iter_mut().next() {
+        Some(p) => p,
+        None => return,
+    };
+
+    match loaded.len() {
+        0 => {}
+
+        // A single file replaces the loaded polytope.
+        1 => {
+            let (_, q) = loaded.pop().unwrap();
+            load(&mut p, q, &mut toasts);
+        }
+
+        // Several files are combined into a compound.
+        _ if advanced(&keyboard) => {
+            let (rank, dim) = (loaded[0].1.rank(), loaded[0].1.dim_or());
+
+            if let Some((path, _)) = loaded
+                .iter()
+                .find(|(_, q)| q.rank() != rank || q.dim_or() != dim)
+            {
+                toasts.error(MiratopeError::operation(
+                    "Compound",
+                    format!(
+                        "{} doesn't have the same rank and dimension as {}",
+                        path.display(),
+                        loaded[0].0.display()
+                    ),
+                ));
+            } else {
+                let count = loaded.len();
+                load(
+                    &mut p,
+                    Concrete::compound(loaded.into_iter().map(|(_, q)| q)),
+                    &mut toasts,
+                );
+                toasts.info(format!("Loaded a compound of {} polytopes.", count));
+            }
+        }
+
+        // Otherwise, each file gets its own memory slot.
+        count => {
+            let first = loaded[0].1.clone();
+            for (path, q) in loaded {
+                memory.push((q, Some(file_label(&path))));
+            }
+
+            load(&mut p, first, &mut toasts);
+            show_memory.open();
+            toasts.info(format!("Stored {} polytopes in memory.", count));
+        }
+    }
+}
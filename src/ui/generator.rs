@@ -0,0 +1,269 @@
+//! Contains the generator point window, where the user moves the generator
+//! point of a Wythoffian polytope around the fundamental region of its
+//! symmetry group, and the polytope is rebuilt as it moves.
+//!
+//! The point is given by its distances to the mirrors of the group, which are
+//! read off a Coxeter diagram when one is loaded. Distances of 0 and 1/2 give
+//! the uniform polytopes, and any other ones give isogonal polytopes with the
+//! same symmetry. For groups of rank 3, the point can also be dragged around a
+//! triangle whose corners each ring a single node.
+
+use super::toast::Toasts;
+use crate::Concrete;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+use miratope_core::{
+    cox::{
+        cd::{Cd, Node},
+        Cox,
+    },
+    error::MiratopeError,
+};
+
+/// The side of the triangle the generator point is dragged around in.
+const PAD_SIZE: f32 = 160.0;
+
+/// The radius of the dot for the generator point.
+const POINT_RADIUS: f32 = 4.0;
+
+/// The plugin in charge of the generator point window.
+pub struct GeneratorPlugin;
+
+impl Plugin for GeneratorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GeneratorWindow>()
+            .add_system(show_generator_window.system().after("show_top_panel"));
+    }
+}
+
+/// The state of the generator point window.
+pub struct GeneratorWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The Coxeter diagram the group and the starting point are read from.
+    diagram: String,
+
+    /// The Coxeter group of the last diagram that was loaded.
+    cox: Option<Cox<f64>>,
+
+    /// The distances from the generator point to each mirror.
+    distances: Vec<f64>,
+
+    /// Whether the polytope is rebuilt as soon as the point moves.
+    live: bool,
+
+    /// Whether the last attempt at building the polytope failed.
+    failed: bool,
+}
+
+impl Default for GeneratorWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            diagram: "x4o3o".to_string(),
+            cox: None,
+            distances: Vec::new(),
+            live: true,
+            failed: false,
+        }
+    }
+}
+
+impl GeneratorWindow {
+    /// Opens the window.
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+
+    /// Reads the group and the starting point from the diagram.
+    fn load_diagram(&mut self) -> Result<(), MiratopeError> {
+        const NAME: &str = "Loading the diagram";
+
+        let cd = Cd::parse(&self.diagram).map_err(|err| MiratopeError::operation(NAME, err))?;
+        if cd.node_iter().any(|node| matches!(node, Node::Snub(_))) {
+            return Err(MiratopeError::operation(
+                NAME,
+                "snub nodes aren't supported",
+            ));
+        }
+
+        let cox = cd.cox();
+        if cox.normals().is_none() {
+            return Err(MiratopeError::operation(NAME, "the group isn't finite"));
+        }
+
+        // Each node value is twice the distance to the corresponding mirror.
+        self.distances = cd.node_iter().map(|node| node.value() / 2.0).collect();
+        self.cox = Some(cox);
+        Ok(())
+    }
+
+    /// Builds the polytope from the current generator point.
+    fn build(&self) -> Option<Concrete> {
+        if self.distances.iter().all(|&d| d == 0.0) {
+            return None;
+        }
+
+        Concrete::wythoff_at(self.cox.as_ref()?, &self.distances)
+    }
+}
+
+/// Shows a triangle whose corners stand for the three mirrors of a group of
+/// rank 3, and lets the user drag the generator point around it. The position
+/// of the point is given by the distances to the mirrors, up to scale.
+///
+/// Returns whether the point was moved.
+fn triangle_pad(ui: &mut egui::Ui, distances: &mut [f64]) -> bool {
+    let (rect, response) =
+        ui.allocate_exact_size(egui::vec2(PAD_SIZE, PAD_SIZE * 0.9), egui::Sense::drag());
+    let painter = ui.painter_at(rect);
+
+    let margin = 2.0 * POINT_RADIUS;
+    let corners = [
+        egui::pos2(rect.center().x, rect.top() + margin),
+        egui::pos2(rect.left() + margin, rect.bottom() - margin),
+        egui::pos2(rect.right() - margin, rect.bottom() - margin),
+    ];
+
+    let stroke = ui.visuals().widgets.inactive.fg_stroke;
+    for i in 0..3 {
+        painter.line_segment([corners[i], corners[(i + 1) % 3]], stroke);
+    }
+
+    // The scale of the point is kept as it's dragged around.
+    let total: f64 = distances.iter().sum();
+    let mut moved = false;
+
+    if response.dragged() {
+        if let Some(cursor) = ui.input().pointer.hover_pos() {
+            // Finds the barycentric coordinates of the cursor, and clamps
+            // them to the triangle.
+            let [a, b, c] = corners;
+            let (v0, v1, v2) = (b - a, c - a, cursor - a);
+            let denom = v0.x * v1.y - v1.x * v0.y;
+            let s = ((v2.x * v1.y - v1.x * v2.y) / denom).max(0.0);
+            let t = ((v0.x * v2.y - v2.x * v0.y) / denom).max(0.0);
+            let r = (1.0 - s - t).max(0.0);
+            let sum = r + s + t;
+
+            let scale = if total > 0.0 { total } else { 1.0 };
+            for (d, w) in distances.iter_mut().zip([r, s, t]) {
+                *d = scale * (w / sum) as f64;
+            }
+            moved = true;
+        }
+    }
+
+    // Draws the point at the weighted average of the corners.
+    let total: f64 = distances.iter().sum();
+    if total > 0.0 {
+        let mut pos = egui::Vec2::ZERO;
+        for (corner, d) in corners.iter().zip(distances.iter()) {
+            pos += corner.to_vec2() * (d / total) as f32;
+        }
+
+        painter.circle_filled(pos.to_pos2(), POINT_RADIUS, egui::Color32::YELLOW);
+    }
+
+    for corner in &corners {
+        painter.circle_filled(*corner, POINT_RADIUS / 2.0, stroke.color);
+    }
+
+    response.on_hover_text("Each corner rings a single node");
+    moved
+}
+
+/// The system that shows the generator point window, and rebuilds the
+/// polytope whenever the point moves.
+fn show_generator_window(
+    egui_ctx: Res<'_, EguiContext>,
+    mut query: Query<'_, '_, &mut Concrete>,
+    mut window: ResMut<'_, GeneratorWindow>,
+    mut toasts: ResMut<'_, Toasts>,
+) {
+    if !window.open {
+        return;
+    }
+
+    // The diagram is loaded the first time the window is opened.
+    if window.cox.is_none() {
+        if let Err(err) = window.load_diagram() {
+            toasts.error(err);
+            window.open = false;
+            return;
+        }
+    }
+
+    let mut open = true;
+    let mut load = false;
+    let mut moved = false;
+    let mut apply = false;
+
+    egui::Window::new("Generator point")
+        .open(&mut open)
+        .resizable(false)
+        .show(egui_ctx.ctx(), |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Diagram:");
+                let response = ui.text_edit_singleline(&mut window.diagram);
+                load = response.lost_focus() && ui.input().key_pressed(egui::Key::Enter);
+                load |= ui.button("Load").clicked();
+            });
+
+            ui.separator();
+
+            let GeneratorWindow {
+                distances, live, ..
+            } = window.as_mut();
+
+            if distances.len() == 3 {
+                moved |= triangle_pad(ui, distances);
+            }
+
+            ui.label("Distances to the mirrors:");
+            for (idx, d) in distances.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    moved |= ui
+                        .add(egui::DragValue::new(d).speed(0.01).clamp_range(0.0..=10.0))
+                        .changed();
+                    ui.label(format!("Node {}", idx + 1));
+                });
+            }
+
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                ui.checkbox(live, "Live");
+                apply = ui.button("Apply").clicked();
+            });
+
+            if window.failed {
+                ui.colored_label(egui::Color32::LIGHT_RED, "The polytope couldn't be built.");
+            }
+        });
+
+    if load {
+        match window.load_diagram() {
+            Ok(()) => apply = true,
+            Err(err) => toasts.error(err),
+        }
+    }
+
+    if apply || (moved && window.live) {
+        if let Some(mut p) = query.iter_mut().next() {
+            match window.build() {
+                Some(q) => {
+                    *p = q;
+                    window.failed = false;
+                }
+                None => window.failed = true,
+            }
+        }
+    }
+
+    if !open {
+        window.open = false;
+    }
+}
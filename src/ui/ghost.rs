@@ -0,0 +1,88 @@
+//! Draws the polytope that a cross-section is taken from as a translucent
+//! ghost, so that the slice can be seen in context.
+//!
+//! The ghost is only drawn while the cross-section isn't flattened, since
+//! otherwise the slice doesn't live in the same space as the original polytope.
+
+use super::{camera::ProjectionType, top_panel::SectionState};
+use crate::mesh::Renderable;
+
+use bevy::prelude::*;
+
+/// The color of the ghost, including its opacity.
+const GHOST_COLOR: Color = Color::rgba(0.8, 0.8, 0.8, 0.15);
+
+/// The plugin in charge of the ghost of the cross-section view.
+pub struct GhostPlugin;
+
+impl Plugin for GhostPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_to_stage(
+            CoreStage::PostUpdate,
+            update_ghost.system().after("update_changed_polytopes"),
+        );
+    }
+}
+
+/// Marks the entity that draws the ghost of the original polytope.
+#[derive(Clone, Copy, Debug)]
+pub struct GhostMarker;
+
+/// The system that shows or hides the ghost whenever the cross-section view
+/// asks for it.
+fn update_ghost(
+    mut commands: Commands<'_, '_>,
+    mut meshes: ResMut<'_, Assets<Mesh>>,
+    mut materials: ResMut<'_, Assets<StandardMaterial>>,
+    section_state: Res<'_, SectionState>,
+    ghosts: Query<'_, '_, Entity, With<GhostMarker>>,
+    projection_type: Res<'_, ProjectionType>,
+    mut shown: Local<'_, bool>,
+) {
+    let original = match &*section_state {
+        SectionState::Active {
+            original_polytope,
+            ghost: true,
+            flatten: false,
+            ..
+        } => Some(original_polytope),
+        _ => None,
+    };
+
+    // The original polytope doesn't change while the view is active, so we
+    // don't need to redraw the ghost as the slice moves around.
+    if original.is_some() == *shown && !projection_type.is_changed() {
+        return;
+    }
+
+    // Removes the old ghost.
+    for entity in ghosts.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    *shown = original.is_some();
+    let original = match original {
+        Some(original) => original,
+        None => return,
+    };
+
+    // Unlike the polytope, the ghost is drawn through the default pipeline,
+    // which supports transparency.
+    let material = materials.add(StandardMaterial {
+        base_color: GHOST_COLOR,
+        unlit: true,
+        ..Default::default()
+    });
+
+    commands
+        .spawn_bundle(PbrBundle {
+            mesh: meshes.add(original.mesh(*projection_type)),
+            material,
+            visible: Visible {
+                is_visible: true,
+                is_transparent: true,
+            },
+            ..Default::default()
+        })
+        .insert(GhostMarker);
+}
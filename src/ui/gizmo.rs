@@ -0,0 +1,158 @@
+//! Contains the transform gizmo, a pad on the lower left corner of the screen
+//! that moves or rotates the polytope as the mouse is dragged over it.
+//!
+//! For exact amounts, use the [`TransformWindow`](super::window::TransformWindow)
+//! instead.
+
+use super::window::axis_name;
+use crate::{Concrete, Point};
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+use miratope_core::conc::ConcretePolytope;
+
+/// The side length of the pad, in pixels.
+const PAD_SIZE: f32 = 120.0;
+
+/// The distance the polytope is moved per pixel dragged.
+const MOVE_SPEED: f64 = 0.01;
+
+/// The angle in radians the polytope is rotated per pixel dragged.
+const ROTATE_SPEED: f64 = 0.01;
+
+/// The plugin in charge of the transform gizmo.
+pub struct GizmoPlugin;
+
+impl Plugin for GizmoPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TransformGizmo>()
+            .add_system(show_gizmo.system().after("show_top_panel"));
+    }
+}
+
+/// What dragging over the gizmo does.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum GizmoMode {
+    /// Moves the polytope along the two axes.
+    Move,
+
+    /// Rotates the polytope within the plane of the two axes, about its
+    /// gravicenter.
+    Rotate,
+}
+
+impl Default for GizmoMode {
+    fn default() -> Self {
+        Self::Move
+    }
+}
+
+/// The state of the transform gizmo.
+pub struct TransformGizmo {
+    /// Whether the gizmo is shown.
+    pub open: bool,
+
+    /// What dragging over the gizmo does.
+    mode: GizmoMode,
+
+    /// The coordinate axes the gizmo acts on. Horizontal drags move along the
+    /// first one, vertical drags along the second one.
+    axes: [usize; 2],
+}
+
+impl Default for TransformGizmo {
+    fn default() -> Self {
+        Self {
+            open: false,
+            mode: Default::default(),
+            axes: [0, 1],
+        }
+    }
+}
+
+impl TransformGizmo {
+    /// Moves or rotates a polytope according to how far the mouse was dragged
+    /// over the pad.
+    fn apply(&self, p: &mut Concrete, delta: egui::Vec2) {
+        let dim = p.dim_or();
+        let [i, j] = self.axes;
+        if i >= dim || j >= dim {
+            return;
+        }
+
+        let (dx, dy) = (f64::from(delta.x), -f64::from(delta.y));
+
+        match self.mode {
+            GizmoMode::Move => {
+                let mut offset = Point::zeros(dim);
+                offset[i] += dx * MOVE_SPEED;
+                offset[j] += dy * MOVE_SPEED;
+                p.translate(&offset);
+            }
+
+            GizmoMode::Rotate => {
+                if i != j {
+                    let center = p.gravicenter().unwrap_or_else(|| Point::zeros(dim));
+                    p.rotate_about(&center, i, j, (dx + dy) * ROTATE_SPEED);
+                }
+            }
+        }
+    }
+}
+
+/// The system that shows the transform gizmo.
+fn show_gizmo(
+    egui_ctx: Res<'_, EguiContext>,
+    mut query: Query<'_, '_, &mut Concrete>,
+    mut gizmo: ResMut<'_, TransformGizmo>,
+) {
+    if !gizmo.open {
+        return;
+    }
+
+    let dim = query.iter().next().map_or(0, |p| p.dim_or());
+    let max_axis = dim.saturating_sub(1);
+    let mut delta = egui::Vec2::ZERO;
+
+    egui::Area::new("transform_gizmo")
+        .anchor(egui::Align2::LEFT_BOTTOM, egui::vec2(10.0, -10.0))
+        .show(egui_ctx.ctx(), |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                let TransformGizmo { mode, axes, .. } = gizmo.as_mut();
+
+                ui.horizontal(|ui| {
+                    ui.selectable_value(mode, GizmoMode::Move, "Move");
+                    ui.selectable_value(mode, GizmoMode::Rotate, "Rotate");
+                });
+
+                ui.horizontal(|ui| {
+                    let [i, j] = axes;
+                    ui.add(egui::DragValue::new(i).clamp_range(0..=max_axis));
+                    ui.add(egui::DragValue::new(j).clamp_range(0..=max_axis));
+                    ui.label(format!("Axes {}, {}", axis_name(*i), axis_name(*j)));
+                });
+
+                let (rect, response) =
+                    ui.allocate_exact_size(egui::vec2(PAD_SIZE, PAD_SIZE), egui::Sense::drag());
+
+                // A crosshair showing both axes.
+                let painter = ui.painter();
+                let stroke = ui.visuals().widgets.inactive.fg_stroke;
+                let center = rect.center();
+                let radius = PAD_SIZE / 2.0 - 4.0;
+                painter.circle_stroke(center, radius, stroke);
+                painter.line_segment([rect.left_center(), rect.right_center()], stroke);
+                painter.line_segment([rect.center_top(), rect.center_bottom()], stroke);
+
+                if response.dragged() {
+                    delta = response.drag_delta();
+                }
+            });
+        });
+
+    if delta != egui::Vec2::ZERO {
+        if let Some(mut p) = query.iter_mut().next() {
+            gizmo.apply(&mut p, delta);
+        }
+    }
+}
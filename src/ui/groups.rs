@@ -0,0 +1,100 @@
+//! Saves the symmetry group of the polytope to a file, and loads groups back,
+//! so that the symmetries of a large polytope don't need to be computed every
+//! time they're used.
+//!
+//! A loaded group is kept around until another one is loaded, and can be
+//! used to symmetrize any polytope of the same dimension.
+
+use std::path::PathBuf;
+
+use super::toast::Toasts;
+use crate::Concrete;
+
+use bevy::prelude::*;
+use miratope_core::{
+    error::{MiratopeError, MiratopeResult},
+    group::saved::SavedGroup,
+};
+
+/// The plugin in charge of saving and loading symmetry groups.
+pub struct GroupPlugin;
+
+impl Plugin for GroupPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LoadedGroup>()
+            .add_event::<GroupEvent>()
+            .add_system(group_events.system().before("show_top_panel"));
+    }
+}
+
+/// A request to save or load a symmetry group, sent once the user has picked
+/// a file.
+pub enum GroupEvent {
+    /// Save the symmetry group of the polytope to a path.
+    Save(PathBuf),
+
+    /// Load the group at a path, replacing the loaded one.
+    Open(PathBuf),
+}
+
+/// The symmetry group that was last loaded from a file, if any.
+#[derive(Default)]
+pub struct LoadedGroup(Option<SavedGroup>);
+
+impl LoadedGroup {
+    /// Returns the loaded group, if any.
+    pub fn get(&self) -> Option<&SavedGroup> {
+        self.0.as_ref()
+    }
+}
+
+/// The system that saves and loads symmetry groups.
+fn group_events(
+    mut events: EventReader<'_, '_, GroupEvent>,
+    mut query: Query<'_, '_, &mut Concrete>,
+    mut loaded: ResMut<'_, LoadedGroup>,
+    mut toasts: ResMut<'_, Toasts>,
+) {
+    for event in events.iter() {
+        let res: MiratopeResult<()> = match event {
+            // Reflection groups are saved along with their names.
+            GroupEvent::Save(path) => match query.iter_mut().next() {
+                Some(mut p) => {
+                    let group = p.get_symmetry_group().0;
+                    let mut saved = SavedGroup::new(group.clone());
+                    if let Some(label) = group.identify() {
+                        saved = saved.with_name(label.name());
+                    }
+
+                    saved
+                        .to_path(path)
+                        .map_err(|err| MiratopeError::save(path, err))
+                }
+                None => Ok(()),
+            },
+
+            // The group is rebuilt once, so that broken files are caught
+            // before it's used.
+            GroupEvent::Open(path) => SavedGroup::from_path(path)
+                .and_then(|group| group.group().map(|_| group))
+                .map_err(|err| MiratopeError::load(path, err))
+                .map(|group| {
+                    toasts.info(match &group.name {
+                        Some(name) => format!("Loaded {}, of order {}.", name, group.order),
+                        None => format!("Loaded a group of order {}.", group.order),
+                    });
+
+                    loaded.0 = Some(group);
+                }),
+        };
+
+        match res {
+            Ok(()) => {
+                if let GroupEvent::Save(_) = event {
+                    toasts.info("Symmetry group saved!");
+                }
+            }
+            Err(err) => toasts.error(err),
+        }
+    }
+}
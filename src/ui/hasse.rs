@@ -0,0 +1,378 @@
+//! Contains the Hasse diagram viewer, a window that draws the face lattice of
+//! the loaded polytope, and highlights the element under the cursor on the
+//! polytope itself. Clicking on a node selects the elements it stands for.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::{
+    camera::ProjectionType,
+    selection::{element_edges, Selection},
+};
+use crate::mesh::Renderable;
+use crate::no_cull_pipeline::PbrNoBackfaceBundle;
+use crate::Concrete;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+use miratope_core::{abs::Ranked, Polytope};
+
+/// The maximum number of elements for which every element gets its own node.
+/// Larger polytopes have their elements grouped by type.
+const MAX_NODES: usize = 200;
+
+/// The width of the diagram, in pixels.
+const DIAGRAM_WIDTH: f32 = 480.0;
+
+/// The height of each rank of the diagram, in pixels.
+const ROW_HEIGHT: f32 = 48.0;
+
+/// The radius of the nodes of the diagram, in pixels.
+const NODE_RADIUS: f32 = 4.0;
+
+/// The plugin in charge of the Hasse diagram viewer.
+pub struct HassePlugin;
+
+impl Plugin for HassePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HasseViewer>()
+            .init_resource::<HasseHighlight>()
+            .add_system(update_viewer.system())
+            .add_system(show_viewer.system().after("show_top_panel"))
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_highlight.system().after("update_changed_polytopes"),
+            );
+    }
+}
+
+/// Marks the entities that draw the highlighted element over the polytope.
+#[derive(Clone, Copy, Debug)]
+pub struct HasseMarker;
+
+/// A node in the Hasse diagram. This stands either for a single element, or
+/// for all of the elements of some type.
+struct HasseNode {
+    /// The indices of the elements the node stands for.
+    elements: Vec<usize>,
+
+    /// The text shown when hovering over the node.
+    label: String,
+}
+
+/// The state of the Hasse diagram viewer.
+pub struct HasseViewer {
+    /// Whether the window is open.
+    open: bool,
+
+    /// Whether the loaded polytope has changed since the diagram was last
+    /// built.
+    stale: bool,
+
+    /// Whether the elements are grouped by type.
+    collapsed: bool,
+
+    /// The nodes of the diagram of each rank.
+    nodes: Vec<Vec<HasseNode>>,
+
+    /// The edges of the diagram. Each edge joins a node to a node of the next
+    /// rank, and is given by the rank of the former and the indices of both.
+    edges: Vec<(usize, usize, usize)>,
+}
+
+impl Default for HasseViewer {
+    fn default() -> Self {
+        Self {
+            open: false,
+            stale: true,
+            collapsed: false,
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+}
+
+/// The node of the Hasse diagram under the cursor, as its rank and index.
+///
+/// This is kept apart from the [`HasseViewer`] so that the highlight is only
+/// rebuilt when the hovered node actually changes.
+#[derive(Default)]
+pub struct HasseHighlight(Option<(usize, usize)>);
+
+impl HasseViewer {
+    /// Opens the Hasse diagram viewer.
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+
+    /// Rebuilds the diagram for a polytope. Every element gets its own node,
+    /// unless there's too many of them, in which case they're grouped by
+    /// type.
+    fn refresh(&mut self, poly: &Concrete) {
+        let rank = poly.rank();
+        self.stale = false;
+        self.collapsed = poly.el_count_iter().sum::<usize>() > MAX_NODES;
+        self.nodes.clear();
+        self.edges.clear();
+
+        // The node of every element.
+        let mut node_of = Vec::with_capacity(rank + 1);
+
+        if self.collapsed {
+            let types = poly.types_of_elements();
+
+            for r in 0..=rank {
+                let mut groups = BTreeMap::new();
+                for idx in 0..poly.el_count(r) {
+                    groups
+                        .entry(types[(r, idx)])
+                        .or_insert_with(Vec::new)
+                        .push(idx);
+                }
+
+                let mut node_of_rank = vec![0; poly.el_count(r)];
+                let mut nodes = Vec::with_capacity(groups.len());
+                for (node, (_, elements)) in groups.into_iter().enumerate() {
+                    for &idx in &elements {
+                        node_of_rank[idx] = node;
+                    }
+
+                    nodes.push(HasseNode {
+                        label: format!(
+                            "{} × rank {} elements, like element {}",
+                            elements.len(),
+                            r as isize - 1,
+                            elements[0]
+                        ),
+                        elements,
+                    });
+                }
+
+                node_of.push(node_of_rank);
+                self.nodes.push(nodes);
+            }
+        } else {
+            for r in 0..=rank {
+                node_of.push((0..poly.el_count(r)).collect());
+                self.nodes.push(
+                    (0..poly.el_count(r))
+                        .map(|idx| HasseNode {
+                            elements: vec![idx],
+                            label: format!(
+                                "Rank {} element {}: {} subelements, {} superelements",
+                                r as isize - 1,
+                                idx,
+                                poly.abs[(r, idx)].subs.len(),
+                                poly.abs[(r, idx)].sups.len()
+                            ),
+                        })
+                        .collect(),
+                );
+            }
+        }
+
+        // Edges between groups are only drawn once.
+        let mut edges = BTreeSet::new();
+        for r in 1..=rank {
+            for (idx, el) in poly.abs[r].iter().enumerate() {
+                for &sub in &el.subs {
+                    edges.insert((r - 1, node_of[r - 1][sub], node_of[r][idx]));
+                }
+            }
+        }
+        self.edges = edges.into_iter().collect();
+    }
+
+    /// Returns the edges of the polytope to highlight for a given node. These
+    /// are the edges of the elements the node stands for, or the edges
+    /// through them in the case of vertices.
+    fn highlighted_edges(&self, poly: &Concrete, rank: usize, node: usize) -> Vec<[usize; 2]> {
+        match self.nodes.get(rank).and_then(|nodes| nodes.get(node)) {
+            Some(node) => element_edges(poly, rank, &node.elements),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// The system that marks the diagram as outdated whenever the polytope
+/// changes.
+fn update_viewer(
+    query: Query<'_, '_, &Concrete, Changed<Concrete>>,
+    mut viewer: ResMut<'_, HasseViewer>,
+    mut highlight: ResMut<'_, HasseHighlight>,
+) {
+    if query.iter().next().is_some() {
+        viewer.stale = true;
+        highlight.0 = None;
+    }
+}
+
+/// The system that shows the Hasse diagram viewer.
+fn show_viewer(
+    egui_ctx: Res<'_, EguiContext>,
+    query: Query<'_, '_, &Concrete>,
+    mut viewer: ResMut<'_, HasseViewer>,
+    mut highlight: ResMut<'_, HasseHighlight>,
+    mut selection: ResMut<'_, Selection>,
+) {
+    if !viewer.open {
+        if highlight.0.is_some() {
+            highlight.0 = None;
+        }
+        return;
+    }
+
+    let p = match query.iter().next() {
+        Some(p) => p,
+        None => return,
+    };
+
+    // We only build the diagram while the window is open.
+    if viewer.stale {
+        viewer.refresh(p);
+    }
+
+    let mut open = true;
+    let mut hovered = None;
+    let mut clicked = false;
+
+    egui::Window::new("Hasse diagram")
+        .open(&mut open)
+        .resizable(false)
+        .show(egui_ctx.ctx(), |ui| {
+            if viewer.collapsed {
+                ui.label("Elements are grouped by type.");
+            }
+
+            let rows = viewer.nodes.len();
+            let size = egui::vec2(DIAGRAM_WIDTH, ROW_HEIGHT * rows as f32);
+            let (rect, response) = ui.allocate_exact_size(size, egui::Sense::click());
+            clicked = response.clicked();
+            let painter = ui.painter_at(rect);
+
+            // The position of every node, with the minimal element at the
+            // bottom.
+            let pos = |r: usize, idx: usize| {
+                let count = viewer.nodes[r].len() as f32;
+                egui::pos2(
+                    rect.left() + DIAGRAM_WIDTH * (idx as f32 + 1.0) / (count + 1.0),
+                    rect.bottom() - ROW_HEIGHT * (r as f32 + 0.5),
+                )
+            };
+
+            // Finds the node closest to the cursor, if it's close enough.
+            if let Some(cursor) = ui.input().pointer.hover_pos() {
+                if rect.contains(cursor) {
+                    let mut best = 2.0 * NODE_RADIUS;
+                    for (r, nodes) in viewer.nodes.iter().enumerate() {
+                        for idx in 0..nodes.len() {
+                            let dist = pos(r, idx).distance(cursor);
+                            if dist < best {
+                                best = dist;
+                                hovered = Some((r, idx));
+                            }
+                        }
+                    }
+                }
+            }
+
+            let stroke = egui::Stroke::new(1.0, egui::Color32::GRAY);
+            let highlight_stroke = egui::Stroke::new(2.0, egui::Color32::YELLOW);
+            for &(r, lo, hi) in &viewer.edges {
+                let stroke = if hovered == Some((r, lo)) || hovered == Some((r + 1, hi)) {
+                    highlight_stroke
+                } else {
+                    stroke
+                };
+
+                painter.line_segment([pos(r, lo), pos(r + 1, hi)], stroke);
+            }
+
+            for (r, nodes) in viewer.nodes.iter().enumerate() {
+                for (idx, node) in nodes.iter().enumerate() {
+                    let color = if hovered == Some((r, idx)) {
+                        egui::Color32::YELLOW
+                    } else if node.elements.iter().any(|&el| selection.contains(r, el)) {
+                        egui::Color32::LIGHT_BLUE
+                    } else {
+                        egui::Color32::WHITE
+                    };
+
+                    painter.circle_filled(pos(r, idx), NODE_RADIUS, color);
+                }
+            }
+
+            match hovered {
+                Some((r, idx)) => ui.label(&viewer.nodes[r][idx].label),
+                None => ui.label("Hover over a node to highlight its element."),
+            };
+            ui.label(selection.description());
+        });
+
+    // Clicking on a node selects its elements, and clicking anywhere else
+    // clears the selection.
+    if clicked {
+        match hovered {
+            Some((r, idx)) => selection.toggle(r, &viewer.nodes[r][idx].elements),
+            None => {
+                if !selection.is_empty() {
+                    selection.clear();
+                }
+            }
+        }
+    }
+
+    if highlight.0 != hovered {
+        highlight.0 = hovered;
+    }
+
+    if !open {
+        viewer.open = false;
+    }
+}
+
+/// The system that draws the element hovered over in the Hasse diagram on top
+/// of the polytope.
+#[allow(clippy::too_many_arguments)]
+fn update_highlight(
+    mut commands: Commands<'_, '_>,
+    mut meshes: ResMut<'_, Assets<Mesh>>,
+    mut materials: ResMut<'_, Assets<StandardMaterial>>,
+    viewer: Res<'_, HasseViewer>,
+    highlight: Res<'_, HasseHighlight>,
+    polies: Query<'_, '_, &Concrete>,
+    markers: Query<'_, '_, Entity, With<HasseMarker>>,
+    projection_type: Res<'_, ProjectionType>,
+) {
+    if !highlight.is_changed() && !projection_type.is_changed() {
+        return;
+    }
+
+    // Removes the old highlight.
+    for entity in markers.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let ((rank, node), poly) = match (highlight.0, polies.iter().next()) {
+        (Some(hovered), Some(poly)) => (hovered, poly),
+        _ => return,
+    };
+
+    let edges = viewer.highlighted_edges(poly, rank, node);
+    if edges.is_empty() {
+        return;
+    }
+
+    let material = materials.add(StandardMaterial {
+        base_color: Color::YELLOW,
+        unlit: true,
+        ..Default::default()
+    });
+
+    commands
+        .spawn_bundle(PbrNoBackfaceBundle {
+            mesh: meshes.add(poly.line_mesh(edges, *projection_type)),
+            material,
+            ..Default::default()
+        })
+        .insert(HasseMarker);
+}
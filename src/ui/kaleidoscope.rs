@@ -0,0 +1,179 @@
+//! Contains the kaleidoscope overlay, which draws where the mirrors of the
+//! symmetry group of the polytope meet its circumsphere.
+//!
+//! The mirrors cut the sphere into copies of a fundamental domain of the
+//! group, whose edges are drawn as arcs. This only works for reflection groups
+//! of full rank, which is the case for most uniform polytopes.
+
+use super::camera::ProjectionType;
+use crate::mesh::Renderable;
+use crate::no_cull_pipeline::PbrNoBackfaceBundle;
+use crate::{eps, Concrete, Float, Point};
+
+use bevy::prelude::*;
+use bevy_egui::egui;
+use miratope_core::float::Float as Float2;
+
+/// The color of the arcs of the kaleidoscope.
+const KALEIDOSCOPE_COLOR: Color = Color::rgb(0.2, 0.8, 1.0);
+
+/// The number of segments each arc is drawn with.
+const ARC_SEGMENTS: usize = 12;
+
+/// The plugin in charge of the kaleidoscope overlay.
+pub struct KaleidoscopePlugin;
+
+impl Plugin for KaleidoscopePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<KaleidoscopeOverlay>()
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_kaleidoscope
+                    .system()
+                    .after("update_changed_polytopes"),
+            );
+    }
+}
+
+/// Marks the entity that draws the kaleidoscope, so that it isn't mistaken for
+/// the wireframe.
+#[derive(Clone, Copy, Debug)]
+pub struct KaleidoscopeMarker;
+
+/// The state of the kaleidoscope overlay.
+#[derive(Default)]
+pub struct KaleidoscopeOverlay {
+    /// Whether the kaleidoscope is shown.
+    shown: bool,
+
+    /// The arcs of the kaleidoscope on the unit sphere, once they've been
+    /// computed, or `None` inside if the symmetry group doesn't have any.
+    arcs: Option<Option<Vec<[Point; 2]>>>,
+}
+
+impl KaleidoscopeOverlay {
+    /// Shows the controls for the overlay in a menu. Returns whether the
+    /// overlay should be shown, if the user changed it.
+    ///
+    /// This doesn't take `self` mutably, so that merely showing the menu
+    /// doesn't rebuild the overlay.
+    pub fn menu(&self, ui: &mut egui::Ui) -> Option<bool> {
+        let mut shown = self.shown;
+        ui.checkbox(&mut shown, "Kaleidoscope")
+            .on_hover_text("Draws the mirrors of the symmetry group on the circumsphere");
+
+        if self.shown {
+            match &self.arcs {
+                Some(Some(arcs)) => {
+                    ui.label(format!("{} arcs", arcs.len()));
+                }
+                Some(None) => {
+                    ui.label("The symmetry group isn't a reflection group of full rank.");
+                }
+                None => {}
+            }
+        }
+
+        if shown != self.shown {
+            Some(shown)
+        } else {
+            None
+        }
+    }
+
+    /// Sets whether the kaleidoscope is shown.
+    pub fn set_shown(&mut self, shown: bool) {
+        self.shown = shown;
+    }
+}
+
+/// Returns the points along the shorter great arc between two unit vectors,
+/// scaled by a given radius.
+fn arc_points(v: &Point, w: &Point, radius: Float) -> Vec<Point> {
+    let angle = v.dot(w).clamp(-1.0, 1.0).facos();
+    let sin = angle.fsin();
+
+    (0..=ARC_SEGMENTS)
+        .map(|k| {
+            let t = k as Float / ARC_SEGMENTS as Float;
+            let p = if sin < eps() {
+                v.clone()
+            } else {
+                (v * ((1.0 - t) * angle).fsin() + w * (t * angle).fsin()) / sin
+            };
+
+            p * radius
+        })
+        .collect()
+}
+
+/// The system that rebuilds the kaleidoscope whenever the polytope or the
+/// overlay settings change.
+#[allow(clippy::too_many_arguments)]
+fn update_kaleidoscope(
+    mut commands: Commands<'_, '_>,
+    mut overlay: ResMut<'_, KaleidoscopeOverlay>,
+    mut meshes: ResMut<'_, Assets<Mesh>>,
+    mut materials: ResMut<'_, Assets<StandardMaterial>>,
+    polies: Query<'_, '_, &Concrete>,
+    changed: Query<'_, '_, (), Changed<Concrete>>,
+    markers: Query<'_, '_, Entity, With<KaleidoscopeMarker>>,
+    projection_type: Res<'_, ProjectionType>,
+) {
+    let poly_changed = changed.iter().next().is_some();
+    if poly_changed {
+        overlay.arcs = None;
+    }
+
+    if !poly_changed && !overlay.is_changed() && !projection_type.is_changed() {
+        return;
+    }
+
+    // Removes the old kaleidoscope.
+    for entity in markers.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let poly = match polies.iter().next() {
+        Some(poly) => poly,
+        None => return,
+    };
+
+    if !overlay.shown {
+        return;
+    }
+
+    // We only compute the symmetry group when it's first needed.
+    if overlay.arcs.is_none() {
+        let mut poly = poly.clone();
+        overlay.arcs = Some(if poly.is_full_dimensional() {
+            poly.get_symmetry_group().0.kaleidoscope()
+        } else {
+            None
+        });
+    }
+
+    let arcs = match overlay.arcs.as_ref().unwrap() {
+        Some(arcs) => arcs,
+        None => return,
+    };
+
+    // The mirrors go through the origin, around which the polytope is
+    // assumed to be centered.
+    let radius = poly.vertices.iter().map(|v| v.norm()).fold(0.0, Float::max);
+    let paths: Vec<_> = arcs.iter().map(|[v, w]| arc_points(v, w, radius)).collect();
+
+    let material = materials.add(StandardMaterial {
+        base_color: KALEIDOSCOPE_COLOR,
+        unlit: true,
+        ..Default::default()
+    });
+
+    commands
+        .spawn_bundle(PbrNoBackfaceBundle {
+            mesh: meshes.add(poly.path_mesh(&paths, *projection_type)),
+            material,
+            ..Default::default()
+        })
+        .insert(KaleidoscopeMarker);
+}
@@ -0,0 +1,416 @@
+//! Contains the library index, a single sidecar file at the root of the
+//! library that stores the name, rank, element counts, and tags of every
+//! polytope file in it, so that the library can be browsed and searched without
+//! opening each file.
+//!
+//! The index is built on a background thread. Entries whose files haven't
+//! changed since they were last read are kept, so that only new or modified
+//! files need to be parsed. A small thumbnail of each polytope is rendered
+//! along the way, and stored in a hidden folder at the root of the library.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ffi::OsStr,
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+    time::UNIX_EPOCH,
+};
+
+use crate::{
+    screenshot::{self, RenderOptions},
+    ui::{camera::ProjectionType, wiki},
+    Concrete,
+};
+
+use bevy::prelude::*;
+use image::ImageResult;
+use miratope_core::{abs::Ranked, file::FromFile};
+use serde::{Deserialize, Serialize};
+
+/// The name of the index file, at the root of the library.
+const INDEX_FILE: &str = ".index.ron";
+
+/// The name of the folder where the thumbnails are stored, at the root of the
+/// library.
+const THUMBNAIL_FOLDER: &str = ".thumbnails";
+
+/// The width and height of the thumbnails, in pixels.
+pub const THUMBNAIL_SIZE: u32 = 64;
+
+/// The background color of the thumbnails.
+const THUMBNAIL_BACKGROUND: Color = Color::rgb(0.3, 0.3, 0.35);
+
+/// The number of files parsed between saves of the index, so that progress
+/// isn't lost if Miratope is closed while indexing.
+const SAVE_INTERVAL: usize = 100;
+
+/// The information stored about a single polytope file.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct IndexEntry {
+    /// The name of the polytope, taken from the file name.
+    pub name: String,
+
+    /// The last modification time of the file, in seconds since the Unix
+    /// epoch.
+    modified: u64,
+
+    /// The rank of the polytope.
+    pub rank: usize,
+
+    /// The number of elements of each rank, that is, the f-vector.
+    pub el_counts: Vec<usize>,
+
+    /// The tags given to the polytope by the user.
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl IndexEntry {
+    /// Returns a short description of the polytope, to show when hovering over
+    /// its file.
+    pub fn description(&self) -> String {
+        let counts: Vec<_> = self.el_counts.iter().map(usize::to_string).collect();
+        let mut description = format!(
+            "{}D, element counts {}",
+            self.rank as isize - 1,
+            counts.join(", ")
+        );
+
+        if !self.tags.is_empty() {
+            description.push_str("\nTags: ");
+            description.push_str(&self.tags.join(", "));
+        }
+
+        description
+    }
+
+    /// Returns whether the entry matches a single word of a search. Words like
+    /// `tag:uniform` or `rank:4` filter by tag or by rank, as shown in the rank
+    /// selectors. Any other word must be part of the name.
+    fn matches(&self, word: &str) -> bool {
+        if let Some(tag) = word.strip_prefix("tag:") {
+            self.tags.iter().any(|t| t.to_lowercase() == tag)
+        } else if let Some(rank) = word.strip_prefix("rank:") {
+            rank.parse::<isize>()
+                .map_or(false, |rank| rank == self.rank as isize - 1)
+        } else {
+            self.name.to_lowercase().contains(word)
+        }
+    }
+}
+
+/// The entries of the index, indexed by their path relative to the root of
+/// the library, with forward slashes as separators.
+pub type IndexEntries = BTreeMap<String, IndexEntry>;
+
+/// The progress of the thread building the index.
+#[derive(Default)]
+struct IndexProgress {
+    /// The number of files processed.
+    done: AtomicUsize,
+
+    /// The total number of files found.
+    total: AtomicUsize,
+
+    /// Whether the index is complete.
+    finished: AtomicBool,
+
+    /// The errors found while saving the index or its thumbnails, which are
+    /// yet to be reported.
+    errors: Mutex<Vec<String>>,
+}
+
+impl IndexProgress {
+    /// Stores an error, to be reported by the main thread.
+    fn report<T: ToString>(&self, err: T) {
+        self.errors.lock().unwrap().push(err.to_string());
+    }
+}
+
+/// The index of the library, which might still be being built.
+pub struct LibraryIndex {
+    /// The root of the library.
+    root: PathBuf,
+
+    /// The entries found so far.
+    entries: Arc<Mutex<IndexEntries>>,
+
+    /// The progress of the thread building the index.
+    progress: Arc<IndexProgress>,
+}
+
+impl LibraryIndex {
+    /// Loads the index of the library at a given path, and starts updating it
+    /// on a background thread.
+    pub fn build<P: AsRef<Path>>(root: P) -> Self {
+        let root = root.as_ref().to_path_buf();
+        let entries = fs::read_to_string(root.join(INDEX_FILE))
+            .ok()
+            .and_then(|src| ron::from_str(&src).ok())
+            .unwrap_or_default();
+
+        let index = Self {
+            root: root.clone(),
+            entries: Arc::new(Mutex::new(entries)),
+            progress: Default::default(),
+        };
+
+        let (entries, progress) = (Arc::clone(&index.entries), Arc::clone(&index.progress));
+        let run = move || update_index(&root, &entries, &progress);
+
+        #[cfg(not(target_arch = "wasm32"))]
+        std::thread::spawn(run);
+        #[cfg(target_arch = "wasm32")]
+        run();
+
+        index
+    }
+
+    /// Locks the entries of the index.
+    pub fn entries(&self) -> MutexGuard<'_, IndexEntries> {
+        self.entries.lock().unwrap()
+    }
+
+    /// Returns the number of files processed and the total number of files, or
+    /// `None` if the index is complete.
+    pub fn progress(&self) -> Option<(usize, usize)> {
+        if self.progress.finished.load(Ordering::Relaxed) {
+            None
+        } else {
+            Some((
+                self.progress.done.load(Ordering::Relaxed),
+                self.progress.total.load(Ordering::Relaxed),
+            ))
+        }
+    }
+
+    /// Returns the entries that match every word of a search, ignoring case.
+    /// See [`IndexEntry::matches`] for the words that can be used.
+    pub fn search(&self, query: &str) -> Vec<(String, IndexEntry)> {
+        let query = query.to_lowercase();
+        let words: Vec<_> = query.split_whitespace().collect();
+
+        self.entries()
+            .iter()
+            .filter(|(_, entry)| words.iter().all(|word| entry.matches(word)))
+            .map(|(path, entry)| (path.clone(), entry.clone()))
+            .collect()
+    }
+
+    /// Replaces the tags of an entry, and saves the index.
+    pub fn set_tags(&self, key: &str, tags: Vec<String>) {
+        if let Some(entry) = self.entries().get_mut(key) {
+            entry.tags = tags;
+        }
+
+        if let Err(err) = save_index(&self.root, &self.entries) {
+            self.progress.report(err);
+        }
+    }
+
+    /// Returns the errors found while saving the index since the last time
+    /// this was called.
+    pub fn take_errors(&self) -> Vec<String> {
+        std::mem::take(&mut *self.progress.errors.lock().unwrap())
+    }
+
+    /// Returns the path to the thumbnail of an entry.
+    pub fn thumbnail_path(&self, key: &str) -> PathBuf {
+        thumbnail_path(&self.root, key)
+    }
+}
+
+/// Returns the path to the thumbnail of the file with a given path relative to
+/// the root of the library.
+fn thumbnail_path(root: &Path, key: &str) -> PathBuf {
+    root.join(THUMBNAIL_FOLDER).join(format!("{}.png", key))
+}
+
+/// Renders a thumbnail of a polytope and saves it to a path. The polytope is
+/// recentered, and seen from slightly above.
+fn save_thumbnail(p: &mut Concrete, path: &Path) -> ImageResult<()> {
+    p.recenter();
+    let radius = p
+        .vertices
+        .iter()
+        .map(|v| v.norm() as f32)
+        .fold(0.0, f32::max);
+    let distance = if radius > 0.0 { 3.0 * radius } else { 1.0 };
+
+    let camera = Transform::from_translation(Vec3::new(0.4, 0.5, 1.0).normalize() * distance)
+        .looking_at(Vec3::ZERO, Vec3::Y)
+        .compute_matrix();
+    let options = RenderOptions {
+        width: THUMBNAIL_SIZE,
+        height: THUMBNAIL_SIZE,
+        projection_type: ProjectionType::Perspective,
+        background: THUMBNAIL_BACKGROUND,
+        faces: true,
+        edges: true,
+        wireframe: Default::default(),
+    };
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    screenshot::render(p, camera, &options).save(path)
+}
+
+/// Returns the path of a file relative to the root of the library, as stored
+/// in the index.
+pub fn relative_path(root: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(root).ok()?;
+    let components: Vec<_> = relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect();
+
+    Some(components.join("/"))
+}
+
+/// Returns whether a file is a polytope that can be loaded.
+///
+/// GGB files are left out, since their reader isn't finished and panics on
+/// most of them.
+fn is_polytope(path: &Path) -> bool {
+    path.extension().and_then(OsStr::to_str) == Some("off")
+}
+
+/// Lists every polytope file under a folder, skipping hidden files and
+/// folders.
+fn list_files(folder: &Path, files: &mut Vec<PathBuf>) {
+    let dir = match fs::read_dir(folder) {
+        Ok(dir) => dir,
+        Err(_) => return,
+    };
+
+    for entry in dir.flatten() {
+        let path = entry.path();
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+
+        if path.is_dir() {
+            list_files(&path, files);
+        } else if is_polytope(&path) {
+            files.push(path);
+        }
+    }
+}
+
+/// Returns the last modification time of a file, in seconds since the Unix
+/// epoch.
+fn modified_time(path: &Path) -> Option<u64> {
+    let time = fs::metadata(path).ok()?.modified().ok()?;
+    Some(time.duration_since(UNIX_EPOCH).ok()?.as_secs())
+}
+
+/// Writes the index to disk.
+fn save_index(root: &Path, entries: &Mutex<IndexEntries>) -> Result<(), String> {
+    let src = ron::to_string(&*entries.lock().unwrap())
+        .map_err(|err| format!("The library index could not be serialized: {}", err))?;
+
+    fs::write(root.join(INDEX_FILE), src)
+        .map_err(|err| format!("The library index could not be saved: {}", err))
+}
+
+/// Brings the index up to date with the files in the library, parsing only
+/// the files that are new or have changed.
+fn update_index(root: &Path, entries: &Mutex<IndexEntries>, progress: &IndexProgress) {
+    let mut files = Vec::new();
+    list_files(root, &mut files);
+    progress.total.store(files.len(), Ordering::Relaxed);
+
+    let mut found = Vec::with_capacity(files.len());
+    let mut parsed = 0;
+
+    for (idx, path) in files.iter().enumerate() {
+        progress.done.store(idx, Ordering::Relaxed);
+
+        let (key, modified) = match (relative_path(root, path), modified_time(path)) {
+            (Some(key), Some(modified)) => (key, modified),
+            _ => continue,
+        };
+
+        let thumbnail = thumbnail_path(root, &key);
+        let old_entry = entries.lock().unwrap().get(&key).cloned();
+        let up_to_date = old_entry
+            .as_ref()
+            .map_or(false, |entry| entry.modified == modified)
+            && thumbnail.exists();
+
+        if !up_to_date {
+            // Files that can't be parsed are left out of the index.
+            if let Ok(mut p) = Concrete::from_path(path) {
+                if let Err(err) = save_thumbnail(&mut p, &thumbnail) {
+                    progress.report(format!(
+                        "The thumbnail of {} could not be saved: {}",
+                        key, err
+                    ));
+                }
+
+                // The tags are kept even if the file changes. Files downloaded
+                // from the wiki start with the categories of their pages.
+                let tags = match old_entry {
+                    Some(entry) => entry.tags,
+                    None => fs::read_to_string(path.with_extension(wiki::METADATA_EXTENSION))
+                        .map(|src| wiki::categories(&src))
+                        .unwrap_or_default(),
+                };
+                let entry = IndexEntry {
+                    name: path
+                        .file_stem()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                        .into_owned(),
+                    modified,
+                    rank: p.rank(),
+                    el_counts: p.el_count_iter().collect(),
+                    tags,
+                };
+
+                entries.lock().unwrap().insert(key.clone(), entry);
+                parsed += 1;
+
+                if parsed % SAVE_INTERVAL == 0 {
+                    if let Err(err) = save_index(root, entries) {
+                        progress.report(err);
+                    }
+                }
+            }
+        }
+
+        found.push(key);
+    }
+
+    // Removes the files that no longer exist.
+    let removed = {
+        let mut entries = entries.lock().unwrap();
+        let found: BTreeSet<_> = found.into_iter().collect();
+        let removed: Vec<_> = entries
+            .keys()
+            .filter(|key| !found.contains(*key))
+            .cloned()
+            .collect();
+
+        for key in &removed {
+            entries.remove(key);
+            let _ = fs::remove_file(thumbnail_path(root, key));
+        }
+
+        removed.len()
+    };
+
+    if parsed != 0 || removed != 0 {
+        if let Err(err) = save_index(root, entries) {
+            progress.report(err);
+        }
+    }
+
+    progress.done.store(files.len(), Ordering::Relaxed);
+    progress.finished.store(true, Ordering::Relaxed);
+}
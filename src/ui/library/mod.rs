@@ -1,22 +1,33 @@
 //! Loads and displays the Miratope library.
 
 use std::{
+    collections::HashMap,
     ffi::{OsStr, OsString},
-    fs, io,
-    path::PathBuf,
+    fs,
+    path::{Path, PathBuf},
 };
 
-use super::config::LibPath;
+use super::{config::LibPath, toast::Toasts};
+#[cfg(all(feature = "wiki", not(target_arch = "wasm32")))]
+use super::{task::BackgroundTask, wiki::WikiDownload};
 use crate::Concrete;
-use miratope_core::file::FromFile;
+use index::*;
+use miratope_core::{error::MiratopeError, file::FromFile};
 use special::*;
 
-use bevy::prelude::*;
+use bevy::{
+    prelude::*,
+    render::texture::{Extent3d, TextureDimension, TextureFormat},
+};
 use bevy_egui::{egui, egui::Ui, EguiContext};
 use serde::{Deserialize, Serialize};
 
+mod index;
 mod special;
 
+/// The maximum number of search results shown at once.
+const MAX_RESULTS: usize = 200;
+
 /// The plugin that loads the library.
 pub struct LibraryPlugin;
 
@@ -24,16 +35,21 @@ impl Plugin for LibraryPlugin {
     fn build(&self, app: &mut App) {
         // This must run after the Config resource has been added.
         let lib_path = app.world.get_resource::<LibPath>().unwrap();
-        let library = Library::new_folder(lib_path);
+        // Falls back to the built-in library if the folder can't be found.
+        let folder = Library::new_folder(lib_path);
+        let index = folder.as_ref().map(|_| LibraryIndex::build(lib_path));
+        let library = Some(folder.unwrap_or_else(Library::builtin));
 
         // The library must be shown after the top panel, to avoid incorrect
         // positioning.
-        app.insert_resource(library).add_system(
-            show_library
-                .system()
-                .label("show_library")
-                .after("show_top_panel"),
-        );
+        app.insert_resource(library)
+            .insert_resource(index)
+            .add_system(
+                show_library
+                    .system()
+                    .label("show_library")
+                    .after("show_top_panel"),
+            );
     }
 }
 
@@ -47,6 +63,9 @@ pub enum ShowResult {
 
     /// We asked to load a special polytope.
     Special(SpecialLibrary),
+
+    /// Something went wrong while showing the library.
+    Error(MiratopeError),
 }
 
 impl Default for ShowResult {
@@ -154,28 +173,72 @@ impl Library {
         })
     }
 
+    /// Returns the library that's shown when the library folder can't be
+    /// found, made only out of the families of polytopes generated by code.
+    pub fn builtin() -> Self {
+        Self::LoadedFolder {
+            name: String::from("Built-in"),
+            contents: vec![
+                SpecialLibrary::Polygon(5, 1),
+                SpecialLibrary::Prism(5, 1),
+                SpecialLibrary::Antiprism(5, 1, 0.0),
+                SpecialLibrary::Cupola(5, 1),
+                SpecialLibrary::Rotunda(5, 1),
+                SpecialLibrary::Uniform(1),
+                SpecialLibrary::Johnson(1),
+                SpecialLibrary::Exotic(1),
+                SpecialLibrary::Semiregular(1),
+                SpecialLibrary::Compound(1),
+                SpecialLibrary::Honeycomb(4, 3),
+                SpecialLibrary::Duoprism(5, 1, 5, 1),
+                SpecialLibrary::Duocomb(5, 1, 5, 1),
+                SpecialLibrary::AntiprismPrism(5, 1),
+                SpecialLibrary::Simplex(3),
+                SpecialLibrary::Hypercube(3),
+                SpecialLibrary::Orthoplex(3),
+                SpecialLibrary::Demihypercube(4),
+                SpecialLibrary::PQr(2, 2, 1),
+            ]
+            .into_iter()
+            .map(Self::Special)
+            .collect(),
+        }
+    }
+
     /// Reads a folder's data from the `.folder` file. If it doesn't exist, it
     /// defaults to loading the folder's name and its data in alphabetical
-    /// order. If that also fails, it returns an `Err`.
-    pub fn folder_contents<U: AsRef<OsStr>>(path: U) -> io::Result<Vec<Self>> {
+    /// order. If the `.folder` file isn't valid text or the folder can't be
+    /// read, it returns an `Err`.
+    ///
+    /// Nothing is written to disk: the information about each file is kept in
+    /// the [`LibraryIndex`] instead.
+    pub fn folder_contents<U: AsRef<OsStr>>(path: U) -> Result<Vec<Self>, MiratopeError> {
         let path = PathBuf::from(&path);
         if !path.is_dir() {
             return Ok(Vec::new());
         }
 
         // Attempts to read from the .folder file.
-        if let Some(Ok(folder)) = fs::read(path.join(".folder"))
-            .ok()
-            .map(|file| ron::from_str(&String::from_utf8(file).unwrap()))
-        {
+        let folder_file = path.join(".folder");
+        let folder = match fs::read(&folder_file) {
+            Ok(file) => {
+                let src = String::from_utf8(file)
+                    .map_err(|err| MiratopeError::load(&folder_file, err))?;
+                ron::from_str(&src).ok()
+            }
+            Err(_) => None,
+        };
+
+        if let Some(folder) = folder {
             Ok(folder)
         }
         // Otherwise, just manually goes through the files.
         else {
             let mut contents = Vec::new();
+            let read_err = |err| MiratopeError::load(&path, err);
 
-            for entry in fs::read_dir(path.clone())? {
-                let path = &entry?.path();
+            for entry in fs::read_dir(&path).map_err(read_err)? {
+                let path = &entry.map_err(read_err)?.path();
 
                 // Adds a new unloaded folder.
                 if let Some(unloaded_folder) = Self::new_folder(path) {
@@ -190,28 +253,36 @@ impl Library {
                 }
             }
 
-            // We cache these contents for future use.
-            if fs::write(path.join(".folder"), ron::to_string(&contents).unwrap()).is_ok() {
-                println!(".folder file overwritten!");
-            } else {
-                println!(".folder file could not be overwritten!");
-            }
-
+            contents.sort_by(|a, b| a.path_name().cmp(b.path_name()));
             Ok(contents)
         }
     }
 
-    /// Shows the library in a given `Ui`, starting from a given path.
-    pub fn show(&mut self, ui: &mut Ui, path: PathBuf) -> ShowResult {
+    /// Shows the library in a given `Ui`, starting from a given path. The
+    /// entries of the index are shown when hovering over each file.
+    pub fn show(
+        &mut self,
+        ui: &mut Ui,
+        path: PathBuf,
+        root: &Path,
+        entries: Option<&IndexEntries>,
+    ) -> ShowResult {
         match self {
             // Shows a collapsing drop-down, and loads the folder in case it's clicked.
+            // Folders that can't be read are shown as empty, so that the error
+            // is only reported once.
             Self::UnloadedFolder { name, .. } => {
+                let (contents, res) = match Self::folder_contents(&path) {
+                    Ok(contents) => (contents, ShowResult::None),
+                    Err(err) => (Vec::new(), ShowResult::Error(err)),
+                };
+
                 *self = Self::LoadedFolder {
                     name: name.clone(),
-                    contents: Self::folder_contents(&path).unwrap(),
+                    contents,
                 };
 
-                self.show(ui, path)
+                res | self.show(ui, path, root, entries)
             }
 
             // Shows a drop-down with all of the files and folders.
@@ -222,7 +293,7 @@ impl Library {
                     for lib in contents.iter_mut() {
                         let mut new_path = path.clone();
                         new_path.push(lib.path_name());
-                        res |= lib.show(ui, new_path);
+                        res |= lib.show(ui, new_path, root, entries);
                     }
 
                     res
@@ -238,7 +309,15 @@ impl Library {
                     .to_string_lossy()
                     .into_owned();
 
-                if ui.button(label).clicked() {
+                let mut response = ui.button(label);
+                let entry = entries
+                    .zip(relative_path(root, &path))
+                    .and_then(|(entries, key)| entries.get(&key));
+                if let Some(entry) = entry {
+                    response = response.on_hover_text(entry.description());
+                }
+
+                if response.clicked() {
                     ShowResult::Load(path.into_os_string())
                 } else {
                     ShowResult::None
@@ -251,36 +330,307 @@ impl Library {
     }
 }
 
+/// The first egui texture id used for the thumbnails, chosen so that it doesn't
+/// clash with any other texture.
+const THUMBNAIL_TEXTURE_ID: u64 = 1 << 32;
+
+/// The maximum number of thumbnails loaded in a single frame.
+const THUMBNAILS_PER_FRAME: usize = 8;
+
+/// The thumbnails of the library that have been loaded as textures.
+#[derive(Default)]
+struct Thumbnails {
+    /// The texture ids of the thumbnails, by their path relative to the library,
+    /// or `None` for the thumbnails that couldn't be loaded.
+    ids: HashMap<String, Option<u64>>,
+
+    /// The thumbnails that were asked for this frame but aren't loaded yet.
+    pending: Vec<String>,
+}
+
+impl Thumbnails {
+    /// Returns the texture of a thumbnail, or asks for it to be loaded.
+    fn get(&mut self, key: &str) -> Option<egui::TextureId> {
+        match self.ids.get(key) {
+            Some(id) => id.map(egui::TextureId::User),
+            None => {
+                self.pending.push(key.to_string());
+                None
+            }
+        }
+    }
+
+    /// Loads some of the pending thumbnails as textures.
+    fn load(
+        &mut self,
+        index: &LibraryIndex,
+        egui_ctx: &mut EguiContext,
+        textures: &mut Assets<Texture>,
+    ) {
+        let pending = std::mem::take(&mut self.pending);
+
+        for key in pending.into_iter().take(THUMBNAILS_PER_FRAME) {
+            // Thumbnails that are still being rendered are tried again later.
+            let path = index.thumbnail_path(&key);
+            if !path.exists() {
+                continue;
+            }
+
+            let id = image::open(&path).ok().map(|image| {
+                let image = image.into_rgba8();
+                let (width, height) = image.dimensions();
+                let texture = Texture::new(
+                    Extent3d::new(width, height, 1),
+                    TextureDimension::D2,
+                    image.into_raw(),
+                    TextureFormat::Rgba8UnormSrgb,
+                );
+
+                let id = THUMBNAIL_TEXTURE_ID + self.ids.len() as u64;
+                egui_ctx.set_egui_texture(id, textures.add(texture));
+                id
+            });
+
+            self.ids.insert(key, id);
+        }
+    }
+}
+
+/// The state of the search and of the tag editor in the library panel.
+#[derive(Default)]
+struct LibraryPanel {
+    /// The search being typed.
+    search: String,
+
+    /// The file of the library that was last loaded, as its path relative to
+    /// the library.
+    selected: Option<String>,
+
+    /// The comma-separated tags of the selected file, as they're being edited.
+    tags: String,
+}
+
+impl LibraryPanel {
+    /// Selects a file of the library, and starts editing its tags.
+    fn select(&mut self, index: &LibraryIndex, key: String) {
+        self.tags = index
+            .entries()
+            .get(&key)
+            .map(|entry| entry.tags.join(", "))
+            .unwrap_or_default();
+        self.selected = Some(key);
+    }
+}
+
+/// Shows a thumbnail, or an empty space of the same size if it isn't loaded.
+fn show_thumbnail(ui: &mut Ui, texture: Option<egui::TextureId>) {
+    let size = egui::Vec2::splat(THUMBNAIL_SIZE as f32 * 0.75);
+
+    match texture {
+        Some(texture) => ui.image(texture, size),
+        None => ui.allocate_response(size, egui::Sense::hover()),
+    };
+}
+
+/// Shows the files in the index that match a search, and returns the file to
+/// load, if any.
+fn show_results(
+    ui: &mut Ui,
+    index: &LibraryIndex,
+    thumbnails: &mut Thumbnails,
+    pattern: &str,
+    root: &Path,
+) -> ShowResult {
+    let results = index.search(pattern);
+    if results.is_empty() {
+        ui.label("No results.");
+    }
+
+    let mut res = ShowResult::None;
+    for (key, entry) in results.iter().take(MAX_RESULTS) {
+        ui.horizontal(|ui| {
+            show_thumbnail(ui, thumbnails.get(key));
+
+            if ui
+                .button(&entry.name)
+                .on_hover_text(format!("{}\n{}", key, entry.description()))
+                .clicked()
+            {
+                res = ShowResult::Load(root.join(key).into_os_string());
+            }
+        });
+    }
+
+    if results.len() > MAX_RESULTS {
+        ui.label(format!("{} more results.", results.len() - MAX_RESULTS));
+    }
+
+    res
+}
+
+/// Shows the thumbnail, element counts, and tags of the selected file, and lets
+/// the user edit its tags.
+fn show_selected(
+    ui: &mut Ui,
+    index: &LibraryIndex,
+    thumbnails: &mut Thumbnails,
+    panel: &mut LibraryPanel,
+) {
+    let key = match &panel.selected {
+        Some(key) => key.clone(),
+        None => return,
+    };
+    let entry = match index.entries().get(&key) {
+        Some(entry) => entry.clone(),
+        None => return,
+    };
+
+    ui.horizontal(|ui| {
+        show_thumbnail(ui, thumbnails.get(&key));
+        ui.label(format!("{}\n{}", entry.name, entry.description()));
+    });
+
+    ui.horizontal(|ui| {
+        ui.label("Tags");
+        ui.add(egui::TextEdit::singleline(&mut panel.tags).hint_text("uniform, regular"));
+
+        if ui.button("Save").clicked() {
+            let tags = panel
+                .tags
+                .split(',')
+                .map(|tag| tag.trim().to_string())
+                .filter(|tag| !tag.is_empty())
+                .collect();
+            index.set_tags(&key, tags);
+        }
+    });
+}
+
 /// The system that shows the Miratope library.
+#[allow(clippy::too_many_arguments)]
 fn show_library(
-    egui_ctx: Res<'_, EguiContext>,
+    mut egui_ctx: ResMut<'_, EguiContext>,
     mut query: Query<'_, '_, &mut Concrete>,
     mut library: ResMut<'_, Option<Library>>,
+    index: Res<'_, Option<LibraryIndex>>,
     lib_path: Res<'_, LibPath>,
+    mut textures: ResMut<'_, Assets<Texture>>,
+    mut name: Local<'_, String>,
+    mut panel: Local<'_, LibraryPanel>,
+    mut thumbnails: Local<'_, Thumbnails>,
+    mut toasts: ResMut<'_, Toasts>,
+    #[cfg(all(feature = "wiki", not(target_arch = "wasm32")))] mut wiki: Local<'_, WikiDownload>,
+    #[cfg(all(feature = "wiki", not(target_arch = "wasm32")))] mut background_task: ResMut<
+        '_,
+        BackgroundTask,
+    >,
 ) {
     // Shows the polytope library.
     if let Some(library) = library.as_mut() {
+        let root = PathBuf::from(lib_path.as_ref());
+        let panel = &mut *panel;
+        let thumbnails = &mut *thumbnails;
+
         egui::SidePanel::left("left_panel")
             .default_width(300.0)
             .max_width(450.0)
             .show(egui_ctx.ctx(), |ui| {
+                // Builds a polytope from its name.
+                ui.horizontal(|ui| {
+                    let response = ui.add(
+                        egui::TextEdit::singleline(&mut *name)
+                            .hint_text("pentagonal antiprism prism"),
+                    );
+                    let entered = response.lost_focus() && ui.input().key_pressed(egui::Key::Enter);
+
+                    if ui.button("Build").clicked() || entered {
+                        match Concrete::from_name(&name) {
+                            Ok(q) => *query.iter_mut().next().unwrap() = q,
+                            Err(err) => {
+                                toasts.error(MiratopeError::operation("Build by name", err))
+                            }
+                        }
+                    }
+                });
+
+                // Searches the library through the index, by name, tag, or
+                // rank.
+                if let Some(index) = index.as_ref() {
+                    ui.horizontal(|ui| {
+                        ui.label("Search");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut panel.search)
+                                .hint_text("cube tag:uniform rank:3"),
+                        );
+                    });
+
+                    if let Some((done, total)) = index.progress() {
+                        ui.label(format!("Indexing the library... {}/{}", done, total));
+                    }
+
+                    show_selected(ui, index, thumbnails, panel);
+                }
+
+                // Downloads files from the wiki into the library.
+                #[cfg(all(feature = "wiki", not(target_arch = "wasm32")))]
+                wiki.show(ui, &root, &mut background_task);
+
+                ui.separator();
+
+                let pattern = panel.search.trim().to_string();
                 egui::containers::ScrollArea::auto_sized().show(ui, |ui| {
-                    match library.show(ui, PathBuf::from(lib_path.as_ref())) {
+                    let res = match index.as_ref() {
+                        Some(index) if !pattern.is_empty() => {
+                            show_results(ui, index, thumbnails, &pattern, &root)
+                        }
+                        _ => {
+                            let entries = index.as_ref().map(LibraryIndex::entries);
+                            library.show(ui, root.clone(), &root, entries.as_deref())
+                        }
+                    };
+
+                    match res {
                         // No action needs to be taken.
                         ShowResult::None => {}
 
                         // Loads a selected file.
                         ShowResult::Load(file) => match Concrete::from_path(&file) {
-                            Ok(q) => *query.iter_mut().next().unwrap() = q,
-                            Err(err) => eprintln!("File open failed: {}", err),
+                            Ok(q) => {
+                                if let Some(name) = q.recognize() {
+                                    toasts.info(format!("Recognized as {}.", name));
+                                }
+
+                                *query.iter_mut().next().unwrap() = q;
+
+                                let key = relative_path(&root, Path::new(&file));
+                                if let (Some(index), Some(key)) = (index.as_ref(), key) {
+                                    panel.select(index, key);
+                                }
+                            }
+                            Err(err) => toasts.error(MiratopeError::load(&file, err)),
                         },
 
                         // Loads a special polytope.
-                        ShowResult::Special(special) => {
-                            *query.iter_mut().next().unwrap() = special.load()
-                        }
+                        ShowResult::Special(special) => match special.load() {
+                            Some(q) => *query.iter_mut().next().unwrap() = q,
+                            None => toasts.error(MiratopeError::operation(
+                                "Build",
+                                format!("{} can't be built with these values", special.label()),
+                            )),
+                        },
+
+                        // Shows what went wrong.
+                        ShowResult::Error(err) => toasts.error(err),
                     }
                 })
             });
+
+        if let Some(index) = index.as_ref() {
+            thumbnails.load(index, &mut egui_ctx, &mut textures);
+
+            for err in index.take_errors() {
+                toasts.error(err);
+            }
+        }
     }
 }
@@ -5,7 +5,16 @@ use egui::Ui;
 use serde::{Deserialize, Serialize};
 
 use crate::Concrete;
-use miratope_core::conc::ConcretePolytope;
+use miratope_core::conc::{
+    catalog::{
+        exotic_name, johnson_name, semiregular_name, uniform_name, EXOTIC_COUNT, JOHNSON_COUNT,
+        SEMIREGULAR_COUNT, UNIFORM_COUNT,
+    },
+    compound::{compound_name, COMPOUND_COUNT},
+    gosset::{demihypercube_name, p_qr_name},
+    honeycomb::{honeycomb_name, HONEYCOMB_COUNT},
+    ConcretePolytope,
+};
 use miratope_core::Polytope;
 
 use super::ShowResult;
@@ -25,15 +34,51 @@ pub enum SpecialLibrary {
     /// A (uniform 3D) prism.
     Prism(usize, usize),
 
-    /// A (uniform 3D) antiprism.
-    Antiprism(usize, usize),
+    /// A 3D antiprism, whose top base is twisted by a given angle in degrees.
+    /// It's uniform when the angle is zero. Retroprisms are those with
+    /// `n / 2 < d`.
+    Antiprism(usize, usize, f64),
 
     /// A (4D uniform) duoprism.
     Duoprism(usize, usize, usize, usize),
 
+    /// A duocomb of two polygons, realized on the Clifford torus.
+    Duocomb(usize, usize, usize, usize),
+
     /// A (4D uniform) antiprismatic prism.
     AntiprismPrism(usize, usize),
 
+    /// A 3D cupola with unit edges.
+    Cupola(usize, usize),
+
+    /// A 3D rotunda with unit edges.
+    Rotunda(usize, usize),
+
+    /// A 3D prismatoid between two polygons with unit edges, at a given
+    /// height.
+    Prismatoid(usize, usize, f64),
+
+    /// A uniform polyhedron from the built-in catalog, by its index.
+    Uniform(usize),
+
+    /// A Johnson solid from the built-in catalog, by its index.
+    Johnson(usize),
+
+    /// An exotic abstract polytope from the built-in catalog, by its index.
+    Exotic(usize),
+
+    /// A semiregular or Gosset polytope from the built-in catalog, by its
+    /// index.
+    Semiregular(usize),
+
+    /// A regular compound from the built-in catalog, by its index.
+    Compound(usize),
+
+    /// A patch of a Euclidean honeycomb from the built-in catalog, by its
+    /// index and the number of copies of a fundamental domain along each
+    /// translation.
+    Honeycomb(usize, usize),
+
     /// A simplex.
     Simplex(isize),
 
@@ -42,6 +87,34 @@ pub enum SpecialLibrary {
 
     /// An orthoplex.
     Orthoplex(isize),
+
+    /// A demihypercube.
+    Demihypercube(isize),
+
+    /// The polytope p_qr in Coxeter's notation, such as a Gosset polytope.
+    PQr(usize, usize, usize),
+}
+
+/// Shows a button for an entry of one of the built-in catalogs, together with
+/// a field for its index and its name. Returns whether the button was clicked.
+fn show_catalog(
+    ui: &mut Ui,
+    text: &str,
+    idx: &mut usize,
+    count: usize,
+    name: fn(usize) -> Option<&'static str>,
+) -> bool {
+    ui.horizontal_wrapped(|ui| {
+        let clicked = ui.button(text).clicked();
+
+        // Index in the catalog.
+        ui.label("#");
+        ui.add(egui::DragValue::new(idx).speed(0.05).clamp_range(1..=count));
+        ui.label(name(*idx).unwrap_or_default());
+
+        clicked
+    })
+    .inner
 }
 
 impl SpecialLibrary {
@@ -50,12 +123,24 @@ impl SpecialLibrary {
         match self {
             Self::Polygon(_, _) => "Polygon",
             Self::Prism(_, _) => "Prism",
-            Self::Antiprism(_, _) => "Antiprism",
+            Self::Antiprism(_, _, _) => "Antiprism",
             Self::Duoprism(_, _, _, _) => "Duoprism",
+            Self::Duocomb(_, _, _, _) => "Duocomb",
             Self::AntiprismPrism(_, _) => "Antiprism prism",
+            Self::Cupola(_, _) => "Cupola",
+            Self::Rotunda(_, _) => "Rotunda",
+            Self::Prismatoid(_, _, _) => "Prismatoid",
+            Self::Uniform(_) => "Uniform polyhedron",
+            Self::Johnson(_) => "Johnson solid",
+            Self::Exotic(_) => "Exotic polytope",
+            Self::Semiregular(_) => "Semiregular polytope",
+            Self::Compound(_) => "Regular compound",
+            Self::Honeycomb(_, _) => "Honeycomb",
             Self::Simplex(_) => "Simplex",
             Self::Hypercube(_) => "Hypercube",
             Self::Orthoplex(_) => "Orthoplex",
+            Self::Demihypercube(_) => "Demihypercube",
+            Self::PQr(_, _, _) => "Gosset polytope",
         }
     }
 
@@ -93,8 +178,171 @@ impl SpecialLibrary {
                 }
             }
 
-            // An {n / d} uniform antiprism.
-            Self::Antiprism(n, d) | Self::AntiprismPrism(n, d) => {
+            // An {n / d} antiprism, which may be twisted.
+            Self::Antiprism(n, d, twist) => {
+                let clicked = ui.horizontal_wrapped(|ui| {
+                    let clicked = ui.button(text).clicked();
+
+                    // Number of sides.
+                    ui.label("n:");
+                    ui.add(
+                        egui::DragValue::new(n)
+                            .speed(0.03)
+                            .clamp_range(2..=usize::MAX),
+                    );
+
+                    // Turning number. Past n / 2, these are retroprisms.
+                    let max_n = *n * 2 / 3;
+                    ui.label("d:");
+                    ui.add(egui::DragValue::new(d).speed(0.03).clamp_range(1..=max_n));
+
+                    // Twist of the top base.
+                    ui.label("Twist:");
+                    ui.add(
+                        egui::DragValue::new(twist)
+                            .speed(0.5)
+                            .clamp_range(-180.0..=180.0)
+                            .suffix("°"),
+                    );
+
+                    clicked
+                });
+
+                if clicked.inner {
+                    ShowResult::Special(*self)
+                } else {
+                    ShowResult::None
+                }
+            }
+
+            // An {n / d} cupola or rotunda. These only exist for odd d.
+            Self::Cupola(n, d) | Self::Rotunda(n, d) => {
+                let clicked = ui.horizontal(|ui| {
+                    let clicked = ui.button(text).clicked();
+
+                    // Number of sides on top.
+                    ui.label("n:");
+                    ui.add(
+                        egui::DragValue::new(n)
+                            .speed(0.03)
+                            .clamp_range(3..=usize::MAX),
+                    );
+
+                    // Turning number. Past n / 2, these are retrograde.
+                    let max_n = *n - 1;
+                    ui.label("d:");
+                    ui.add(egui::DragValue::new(d).speed(0.03).clamp_range(1..=max_n));
+
+                    clicked
+                });
+
+                if clicked.inner {
+                    ShowResult::Special(*self)
+                } else {
+                    ShowResult::None
+                }
+            }
+
+            // A prismatoid between two convex regular polygons.
+            Self::Prismatoid(n1, n2, height) => {
+                let clicked = ui.horizontal_wrapped(|ui| {
+                    let clicked = ui.button(text).clicked();
+
+                    // Number of sides of the bottom.
+                    ui.label("n₁:");
+                    ui.add(
+                        egui::DragValue::new(n1)
+                            .speed(0.03)
+                            .clamp_range(3..=usize::MAX),
+                    );
+
+                    // Number of sides of the top.
+                    ui.label("n₂:");
+                    ui.add(
+                        egui::DragValue::new(n2)
+                            .speed(0.03)
+                            .clamp_range(3..=usize::MAX),
+                    );
+
+                    // Height of the top over the bottom.
+                    ui.label("Height:");
+                    ui.add(
+                        egui::DragValue::new(height)
+                            .speed(0.01)
+                            .clamp_range(0.0..=f64::MAX),
+                    );
+
+                    clicked
+                });
+
+                if clicked.inner {
+                    ShowResult::Special(*self)
+                } else {
+                    ShowResult::None
+                }
+            }
+
+            // An entry from one of the built-in catalogs.
+            Self::Uniform(idx) => {
+                if show_catalog(ui, text, idx, UNIFORM_COUNT, uniform_name) {
+                    ShowResult::Special(*self)
+                } else {
+                    ShowResult::None
+                }
+            }
+            Self::Johnson(idx) => {
+                if show_catalog(ui, text, idx, JOHNSON_COUNT, johnson_name) {
+                    ShowResult::Special(*self)
+                } else {
+                    ShowResult::None
+                }
+            }
+            Self::Exotic(idx) => {
+                if show_catalog(ui, text, idx, EXOTIC_COUNT, exotic_name) {
+                    ShowResult::Special(*self)
+                } else {
+                    ShowResult::None
+                }
+            }
+            Self::Semiregular(idx) => {
+                if show_catalog(ui, text, idx, SEMIREGULAR_COUNT, semiregular_name) {
+                    ShowResult::Special(*self)
+                } else {
+                    ShowResult::None
+                }
+            }
+            Self::Compound(idx) => {
+                if show_catalog(ui, text, idx, COMPOUND_COUNT, compound_name) {
+                    ShowResult::Special(*self)
+                } else {
+                    ShowResult::None
+                }
+            }
+
+            // A patch of a honeycomb from the catalog, with some number of
+            // copies of a fundamental domain.
+            Self::Honeycomb(idx, copies) => {
+                let clicked = ui.horizontal_wrapped(|ui| {
+                    let clicked = show_catalog(ui, text, idx, HONEYCOMB_COUNT, honeycomb_name);
+
+                    ui.label("Copies:");
+                    ui.add(egui::DragValue::new(copies).speed(0.03).clamp_range(1..=8))
+                        .on_hover_text(
+                            "The number of copies of a fundamental domain along each direction",
+                        );
+
+                    clicked
+                });
+
+                if clicked.inner {
+                    ShowResult::Special(*self)
+                } else {
+                    ShowResult::None
+                }
+            }
+
+            // An {n / d} uniform antiprism prism.
+            Self::AntiprismPrism(n, d) => {
                 let clicked = ui.horizontal(|ui| {
                     let clicked = ui.button(text).clicked();
 
@@ -121,8 +369,8 @@ impl SpecialLibrary {
                 }
             }
 
-            // A uniform duoprism based on two polygons.
-            Self::Duoprism(n1, d1, n2, d2) => {
+            // A uniform duoprism or a duocomb based on two polygons.
+            Self::Duoprism(n1, d1, n2, d2) | Self::Duocomb(n1, d1, n2, d2) => {
                 let clicked = ui.horizontal_wrapped(|ui| {
                     let clicked = ui.button(text).clicked();
 
@@ -179,20 +427,62 @@ impl SpecialLibrary {
                     ShowResult::None
                 }
             }
+
+            // A demihypercube of a given rank, shown with its name.
+            Self::Demihypercube(rank) => {
+                let clicked = ui.horizontal(|ui| {
+                    let clicked = ui.button(text).clicked();
+
+                    ui.label("Rank:");
+                    ui.add(egui::DragValue::new(rank).speed(0.03).clamp_range(3..=10));
+                    ui.label(demihypercube_name((*rank + 1) as usize).unwrap_or_default());
+
+                    clicked
+                });
+
+                if clicked.inner {
+                    ShowResult::Special(*self)
+                } else {
+                    ShowResult::None
+                }
+            }
+
+            // The polytope p_qr, given by the lengths of the arms of its
+            // Coxeter diagram.
+            Self::PQr(p, q, r) => {
+                let clicked = ui.horizontal(|ui| {
+                    let clicked = ui.button(text).clicked();
+
+                    for (label, len) in [("p:", p), ("q:", q), ("r:", r)] {
+                        ui.label(label);
+                        ui.add(egui::DragValue::new(len).speed(0.03).clamp_range(0..=6));
+                    }
+                    ui.label(p_qr_name(*p, *q, *r));
+
+                    clicked
+                });
+
+                if clicked.inner {
+                    ShowResult::Special(*self)
+                } else {
+                    ShowResult::None
+                }
+            }
         }
     }
 
-    /// Loads the given special polytope from the library.
-    pub fn load(&self) -> Concrete {
-        match *self {
+    /// Loads the given special polytope from the library, or returns `None`
+    /// if it can't be built with the given values.
+    pub fn load(&self) -> Option<Concrete> {
+        Some(match *self {
             // Loads a regular star polygon.
             Self::Polygon(n, d) => Concrete::star_polygon_with_edge(n, d, 1.0),
 
             // Loads a uniform polygonal prism.
             Self::Prism(n, d) => Concrete::uniform_prism(n, d),
 
-            // Loads a uniform polygonal antiprism.
-            Self::Antiprism(n, d) => Concrete::uniform_antiprism(n, d),
+            // Loads a polygonal antiprism, twisted by the given angle.
+            Self::Antiprism(n, d, twist) => Concrete::twisted_antiprism(n, d, twist.to_radians()),
 
             // Loads a uniform polygonal duoprism.
             Self::Duoprism(n1, d1, n2, d2) => {
@@ -207,9 +497,43 @@ impl SpecialLibrary {
                 }
             }
 
+            // Loads a polygonal duocomb on the Clifford torus.
+            Self::Duocomb(n1, d1, n2, d2) => Concrete::clifford_duocomb(n1, d1, n2, d2),
+
             // Loads a uniform polygonal antiprism prism.
             Self::AntiprismPrism(n, d) => Concrete::uniform_antiprism(n, d).prism(),
 
+            // Loads a cupola with unit edges.
+            Self::Cupola(n, d) => Concrete::cupola(n, d)?,
+
+            // Loads a rotunda with unit edges.
+            Self::Rotunda(n, d) => Concrete::rotunda(n, d)?,
+
+            // Loads a prismatoid between two polygons with unit edges.
+            Self::Prismatoid(n1, n2, height) => Concrete::prismatoid(
+                &Concrete::star_polygon_with_edge(n1, 1, 1.0),
+                &Concrete::star_polygon_with_edge(n2, 1, 1.0),
+                height,
+            )?,
+
+            // Loads a uniform polyhedron from the catalog.
+            Self::Uniform(idx) => Concrete::uniform(idx)?,
+
+            // Builds a Johnson solid from the catalog.
+            Self::Johnson(idx) => Concrete::johnson(idx)?,
+
+            // Builds an exotic polytope from the catalog, laid out by forces.
+            Self::Exotic(idx) => Concrete::exotic(idx)?,
+
+            // Builds a semiregular polytope from the catalog as a convex hull.
+            Self::Semiregular(idx) => Concrete::semiregular(idx)?,
+
+            // Builds a regular compound from the catalog as an orbit.
+            Self::Compound(idx) => Concrete::regular_compound(idx)?,
+
+            // Builds a patch of a honeycomb from the catalog.
+            Self::Honeycomb(idx, copies) => Concrete::honeycomb(idx, copies)?,
+
             // Loads a simplex with a given rank.
             Self::Simplex(rank) => Concrete::simplex((rank + 1) as usize),
 
@@ -218,6 +542,12 @@ impl SpecialLibrary {
 
             // Loads an orthoplex with a given rank.
             Self::Orthoplex(rank) => Concrete::orthoplex((rank + 1) as usize),
-        }
+
+            // Loads a demihypercube with a given rank.
+            Self::Demihypercube(rank) => Concrete::demihypercube((rank + 1) as usize),
+
+            // Builds the polytope p_qr, if its symmetry group is finite.
+            Self::PQr(p, q, r) => Concrete::p_qr(p, q, r)?,
+        })
     }
 }
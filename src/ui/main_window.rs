@@ -1,8 +1,16 @@
 //! The systems that update the main window.
 
+use std::borrow::Cow;
+
+use super::build::BuildAnimation;
+use super::ghost::GhostMarker;
+use super::hasse::HasseMarker;
+use super::kaleidoscope::KaleidoscopeMarker;
+use super::petrie::PetrieMarker;
 use super::right_panel::ElementTypesRes;
-use super::{camera::ProjectionType, top_panel::SectionState};
-use crate::mesh::Renderable;
+use super::selection::SelectionMarker;
+use super::{camera::ProjectionType, top_panel::SectionState, window::RenderSettings};
+use crate::mesh::{ClipPlane, Renderable, WireframeStyle};
 use crate::Concrete;
 
 use bevy::prelude::*;
@@ -16,16 +24,89 @@ pub struct MainWindowPlugin;
 
 impl Plugin for MainWindowPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system_to_stage(CoreStage::PreUpdate, update_visible.system())
+        app.init_resource::<ExplodedView>()
+            .init_resource::<ClippingPlane>()
+            .add_system_to_stage(CoreStage::PreUpdate, update_visible.system())
             .add_system(update_scale_factor.system())
-            .add_system_to_stage(CoreStage::PostUpdate, update_changed_polytopes.system());
+            .add_system(update_wireframe_style.system())
+            .add_system(update_clipping_plane.system())
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_changed_polytopes
+                    .system()
+                    .label("update_changed_polytopes"),
+            );
+    }
+}
+
+/// Marks the child of the polytope that draws its vertices, as opposed to the
+/// one that draws its edges.
+#[derive(Clone, Copy, Debug)]
+pub struct VertexMarker;
+
+/// How far apart the facets of the polytope are drawn, as a multiple of their
+/// distance to its gravicenter. When this is nonzero, the facets are drawn
+/// separately, so that the ones inside of a projection become visible.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ExplodedView(pub f64);
+
+impl ExplodedView {
+    /// Returns whether the facets are drawn apart.
+    pub fn is_active(&self) -> bool {
+        self.0 != 0.0
+    }
+
+    /// Returns the polytope as it's drawn on screen. Only polyhedra and
+    /// higher-rank polytopes are drawn apart.
+    pub fn apply<'a>(&self, poly: &'a Concrete) -> Cow<'a, Concrete> {
+        if self.is_active() && poly.rank() >= 4 {
+            if let Some(exploded) = poly.exploded(self.0) {
+                return Cow::Owned(exploded);
+            }
+        }
+
+        Cow::Borrowed(poly)
+    }
+}
+
+/// A plane that cuts the polytope as it's drawn, so that its inside can be
+/// seen. Unlike a cross-section, this doesn't change the polytope itself.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ClippingPlane {
+    /// Whether the polytope is clipped.
+    pub enabled: bool,
+
+    /// The plane the polytope is clipped by.
+    pub plane: ClipPlane,
+}
+
+impl ClippingPlane {
+    /// Clips a mesh of the polytope, if clipping is enabled.
+    pub fn apply(&self, mesh: Mesh) -> Mesh {
+        if self.enabled {
+            self.plane.clip(&mesh)
+        } else {
+            mesh
+        }
     }
 }
 
 pub fn update_visible(
     keyboard: Res<'_, Input<KeyCode>>,
     mut polies_vis: Query<'_, '_, &mut Visible, With<Concrete>>,
-    mut wfs_vis: Query<'_, '_, &mut Visible, Without<Concrete>>,
+    mut wfs_vis: Query<
+        '_,
+        '_,
+        &mut Visible,
+        (
+            Without<Concrete>,
+            Without<PetrieMarker>,
+            Without<HasseMarker>,
+            Without<SelectionMarker>,
+            Without<GhostMarker>,
+            Without<KaleidoscopeMarker>,
+        ),
+    >,
 ) {
     if keyboard.just_pressed(KeyCode::V) {
         if let Some(mut visible) = polies_vis.iter_mut().next() {
@@ -34,8 +115,9 @@ pub fn update_visible(
         }
     }
 
+    // Toggles both the edges and the vertices.
     if keyboard.just_pressed(KeyCode::B) {
-        if let Some(mut visible) = wfs_vis.iter_mut().next() {
+        for mut visible in wfs_vis.iter_mut() {
             let vis = visible.is_visible;
             visible.is_visible = !vis;
         }
@@ -49,21 +131,63 @@ pub fn update_scale_factor(mut egui_settings: ResMut<'_, EguiSettings>, windows:
     }
 }
 
+/// Redraws the polytope whenever the style of the wireframe changes.
+pub fn update_wireframe_style(
+    render_settings: Res<'_, RenderSettings>,
+    mut style: Local<'_, WireframeStyle>,
+    mut polies: Query<'_, '_, &mut Concrete>,
+) {
+    let new_style = render_settings.wireframe_style();
+
+    if *style != new_style {
+        *style = new_style;
+
+        for mut poly in polies.iter_mut() {
+            poly.set_changed();
+        }
+    }
+}
+
+/// Redraws the polytope whenever the clipping plane changes.
+pub fn update_clipping_plane(
+    clipping_plane: Res<'_, ClippingPlane>,
+    mut old_plane: Local<'_, ClippingPlane>,
+    mut polies: Query<'_, '_, &mut Concrete>,
+) {
+    // Moving a disabled plane around doesn't change anything.
+    let changed = clipping_plane.enabled != old_plane.enabled
+        || (clipping_plane.enabled && clipping_plane.plane != old_plane.plane);
+
+    if changed {
+        for mut poly in polies.iter_mut() {
+            poly.set_changed();
+        }
+    }
+
+    *old_plane = *clipping_plane;
+}
+
 /// Updates polytopes after an operation.
 pub fn update_changed_polytopes(
     mut meshes: ResMut<'_, Assets<Mesh>>,
     mut polies: Query<'_, '_, (&mut Concrete, &Handle<Mesh>, &Children), Changed<Concrete>>,
-    wfs: Query<'_, '_, &Handle<Mesh>, Without<Concrete>>,
+    wfs: Query<'_, '_, &Handle<Mesh>, (Without<Concrete>, Without<VertexMarker>)>,
+    verts: Query<'_, '_, &Handle<Mesh>, With<VertexMarker>>,
     mut section_state: ResMut<'_, SectionState>,
     mut element_types: ResMut<'_, ElementTypesRes>,
 
     orthogonal: Res<'_, ProjectionType>,
+    exploded_view: Res<'_, ExplodedView>,
+    render_settings: Res<'_, RenderSettings>,
+    clipping_plane: Res<'_, ClippingPlane>,
+    build_animation: Res<'_, BuildAnimation>,
 ) {
     for (mut poly, mesh_handle, children) in polies.iter_mut() {
         poly.untangle_faces();
         if cfg!(debug_assertions) {
             poly.assert_valid();
         }
+        poly.abs.cache_orientation();
 
         if !element_types.main_updating {
             element_types.main = false;
@@ -71,12 +195,28 @@ pub fn update_changed_polytopes(
             element_types.main_updating = false;
         }
 
-        *meshes.get_mut(mesh_handle).unwrap() = poly.mesh(*orthogonal);
+        // The build animation only reveals part of the polytope, unless its
+        // facets are drawn apart.
+        let drawn = exploded_view.apply(&poly);
+        let revealed = if exploded_view.is_active() {
+            None
+        } else {
+            build_animation.revealed(&poly)
+        };
+        let revealed = revealed.as_ref();
+
+        *meshes.get_mut(mesh_handle).unwrap() =
+            clipping_plane.apply(drawn.partial_mesh(*orthogonal, revealed));
 
-        // Updates all wireframes.
+        // Updates all wireframes and vertices.
+        let style = render_settings.wireframe_style();
         for child in children.iter() {
             if let Ok(wf_handle) = wfs.get_component::<Handle<Mesh>>(*child) {
-                *meshes.get_mut(wf_handle).unwrap() = poly.wireframe(*orthogonal);
+                *meshes.get_mut(wf_handle).unwrap() =
+                    clipping_plane.apply(drawn.partial_wireframe(*orthogonal, style, revealed));
+            } else if let Ok(vert_handle) = verts.get_component::<Handle<Mesh>>(*child) {
+                *meshes.get_mut(vert_handle).unwrap() =
+                    clipping_plane.apply(drawn.partial_vertex_mesh(*orthogonal, style, revealed));
             }
         }
 
@@ -0,0 +1,281 @@
+//! Contains the measurement tool, a window that shows the distance and the
+//! angles between two elements of the loaded polytope.
+//!
+//! While the window is open, clicking on a vertex or an edge on screen picks
+//! it. Any other element can be picked from the [selection](super::selection).
+//! The measurements themselves are taken on the polytope, in however many
+//! dimensions it lives in, rather than on its projection.
+
+use super::{camera::ProjectionType, selection::Selection};
+use crate::mesh::Renderable;
+use crate::Concrete;
+
+use bevy::{prelude::*, render::camera::PerspectiveProjection};
+use bevy_egui::{egui, EguiContext};
+use miratope_core::{abs::Ranked, geometry::Measurement};
+
+/// How close the cursor must be to a vertex on screen to pick it, in pixels.
+const VERTEX_RADIUS: f32 = 8.0;
+
+/// How close the cursor must be to an edge on screen to pick it, in pixels.
+const EDGE_RADIUS: f32 = 5.0;
+
+/// How far the cursor can move between pressing and releasing the button for
+/// it to count as a click rather than a drag, in pixels.
+const CLICK_TOLERANCE: f32 = 4.0;
+
+/// The plugin in charge of the measurement tool.
+pub struct MeasurePlugin;
+
+impl Plugin for MeasurePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MeasureTool>()
+            .add_system(pick_elements.system().before("show_top_panel"))
+            .add_system(show_measure_window.system().after("show_top_panel"));
+    }
+}
+
+/// The state of the measurement tool.
+#[derive(Default)]
+pub struct MeasureTool {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The two picked elements, as their ranks and indices.
+    picks: [Option<(usize, usize)>; 2],
+
+    /// The slot the next picked element goes into.
+    next: usize,
+
+    /// The measurements between the picked elements, if both were picked.
+    measurement: Option<Measurement>,
+
+    /// Where the left mouse button was pressed, if it's held down.
+    press: Option<Vec2>,
+}
+
+impl MeasureTool {
+    /// Opens the window of the measurement tool.
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+
+    /// Puts an element into the next slot.
+    fn pick(&mut self, element: (usize, usize), poly: &Concrete) {
+        self.set(self.next, Some(element), poly);
+        self.next = 1 - self.next;
+    }
+
+    /// Puts an element into a given slot, and measures again.
+    fn set(&mut self, slot: usize, element: Option<(usize, usize)>, poly: &Concrete) {
+        self.picks[slot] = element;
+        self.measurement = match self.picks {
+            [Some(a), Some(b)] => poly.measure(a, b),
+            _ => None,
+        };
+    }
+
+    /// Forgets the picked elements.
+    fn clear(&mut self) {
+        self.picks = [None; 2];
+        self.next = 0;
+        self.measurement = None;
+    }
+}
+
+/// Returns the distance from a point to a segment.
+fn segment_distance(p: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let ab = b - a;
+    let len = ab.length_squared();
+    let t = if len > 0.0 {
+        ((p - a).dot(ab) / len).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+
+    p.distance(a + t * ab)
+}
+
+/// Returns the vertex or, failing that, the edge of the polytope under a given
+/// point on the screen.
+fn element_at(
+    poly: &Concrete,
+    projection_type: ProjectionType,
+    screen_pos: impl Fn(Vec3) -> Option<Vec2>,
+    cursor: Vec2,
+) -> Option<(usize, usize)> {
+    let positions: Vec<_> = poly
+        .projected_vertices(projection_type)
+        .into_iter()
+        .map(|v| screen_pos(Vec3::from(v)))
+        .collect();
+
+    // The closest vertex.
+    let mut closest = None;
+    let mut closest_dist = VERTEX_RADIUS;
+    for (idx, pos) in positions.iter().enumerate() {
+        if let Some(pos) = pos {
+            let dist = pos.distance(cursor);
+            if dist < closest_dist {
+                closest = Some((1, idx));
+                closest_dist = dist;
+            }
+        }
+    }
+
+    if closest.is_some() || poly.rank() < 2 {
+        return closest;
+    }
+
+    // The closest edge.
+    closest_dist = EDGE_RADIUS;
+    for (idx, edge) in poly.abs[2].iter().enumerate() {
+        if let (Some(a), Some(b)) = (positions[edge.subs[0]], positions[edge.subs[1]]) {
+            let dist = segment_distance(cursor, a, b);
+            if dist < closest_dist {
+                closest = Some((2, idx));
+                closest_dist = dist;
+            }
+        }
+    }
+
+    closest
+}
+
+/// The system that picks the elements clicked on while the window is open,
+/// and forgets them when the polytope changes.
+#[allow(clippy::too_many_arguments)]
+fn pick_elements(
+    mut tool: ResMut<'_, MeasureTool>,
+    polies: Query<'_, '_, &Concrete>,
+    changed: Query<'_, '_, (), Changed<Concrete>>,
+    cameras: Query<'_, '_, (&Camera, &GlobalTransform), With<PerspectiveProjection>>,
+    mouse_button: Res<'_, Input<MouseButton>>,
+    windows: Res<'_, Windows>,
+    egui_ctx: Res<'_, EguiContext>,
+    projection_type: Res<'_, ProjectionType>,
+) {
+    if changed.iter().next().is_some() && tool.picks != [None; 2] {
+        tool.clear();
+    }
+
+    if !tool.open {
+        return;
+    }
+
+    let cursor = match windows.get_primary().and_then(Window::cursor_position) {
+        Some(cursor) => cursor,
+        None => return,
+    };
+
+    if mouse_button.just_pressed(MouseButton::Left) {
+        tool.press = (!egui_ctx.ctx().wants_pointer_input()).then(|| cursor);
+    }
+
+    // Dragging rotates the camera, so only clicks pick elements.
+    if !mouse_button.just_released(MouseButton::Left) {
+        return;
+    }
+    match tool.press.take() {
+        Some(press) if press.distance(cursor) <= CLICK_TOLERANCE => {}
+        _ => return,
+    }
+
+    if let (Some(poly), Some((camera, camera_tf))) = (polies.iter().next(), cameras.iter().next()) {
+        let screen_pos = |v| camera.world_to_screen(&windows, camera_tf, v);
+
+        if let Some(element) = element_at(poly, *projection_type, screen_pos, cursor) {
+            tool.pick(element, poly);
+        }
+    }
+}
+
+/// Returns the name of an element, as shown in the window.
+fn element_name((rank, idx): (usize, usize)) -> String {
+    match rank {
+        1 => format!("vertex {}", idx),
+        2 => format!("edge {}", idx),
+        3 => format!("face {}", idx),
+        _ => format!("rank {} element {}", rank as isize - 1, idx),
+    }
+}
+
+/// The system that shows the window of the measurement tool.
+fn show_measure_window(
+    egui_ctx: Res<'_, EguiContext>,
+    polies: Query<'_, '_, &Concrete>,
+    mut tool: ResMut<'_, MeasureTool>,
+    selection: Res<'_, Selection>,
+) {
+    if !tool.open {
+        return;
+    }
+
+    let poly = match polies.iter().next() {
+        Some(poly) => poly,
+        None => return,
+    };
+
+    let mut open = true;
+
+    egui::Window::new("Measure")
+        .open(&mut open)
+        .default_width(280.0)
+        .show(egui_ctx.ctx(), |ui| {
+            let tool = &mut *tool;
+
+            ui.label("Click on two vertices or edges, or pick any element from the selection.");
+            ui.separator();
+
+            // Only a single selected element can be picked.
+            let selected = match selection.elements().iter().next() {
+                Some(&idx) if selection.elements().len() == 1 => Some((selection.rank(), idx)),
+                _ => None,
+            };
+
+            for (slot, name) in ["A", "B"].iter().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.label(format!(
+                        "{}: {}",
+                        name,
+                        tool.picks[slot].map_or_else(|| "none".to_string(), element_name)
+                    ));
+
+                    if ui
+                        .add(egui::Button::new("Use selection").enabled(selected.is_some()))
+                        .clicked()
+                    {
+                        tool.set(slot, selected, poly);
+                        tool.next = 1 - slot;
+                    }
+                });
+            }
+
+            if ui.button("Clear").clicked() {
+                tool.clear();
+            }
+
+            ui.separator();
+
+            match tool.measurement {
+                Some(measurement) => {
+                    ui.label(format!("Distance: {:.10}", measurement.distance));
+
+                    if let Some(angle) = measurement.angle {
+                        ui.label(format!("Angle: {:.6}°", angle.to_degrees()));
+                    }
+
+                    if let Some(angle) = measurement.interior_angle {
+                        ui.label(format!("Interior angle: {:.6}°", angle.to_degrees()));
+                    }
+                }
+                None => {
+                    ui.label("Pick two elements to measure them.");
+                }
+            }
+        });
+
+    if !open {
+        tool.open = false;
+    }
+}
@@ -1,14 +1,36 @@
 //! All of the code that configures the UI.
 
-use crate::{Point, EPS};
+use crate::{eps, Point};
 use approx::abs_diff_eq;
 use bevy_egui::egui::{self, Ui, Widget};
 
+pub mod batch;
+pub mod build;
 pub mod camera;
+pub mod colorings;
+pub mod colors;
 pub mod config;
+pub mod console;
+pub mod drop;
+pub mod generator;
+pub mod ghost;
+pub mod gizmo;
+pub mod groups;
+pub mod hasse;
+pub mod kaleidoscope;
 pub mod library;
 pub mod main_window;
+pub mod measure;
 pub mod memory;
+pub mod petrie;
+pub mod recorder;
+pub mod sections;
+pub mod selection;
+pub mod session;
+pub mod task;
+pub mod toast;
+pub mod vertex_table;
+pub mod wiki;
 pub mod window;
 pub mod top_panel;
 pub mod right_panel;
@@ -20,15 +42,46 @@ impl bevy::prelude::PluginGroup for MiratopePlugins {
     fn build(&mut self, group: &mut bevy::app::PluginGroupBuilder) {
         group
             .add(camera::InputPlugin)
+            .add(batch::BatchPlugin)
+            .add(build::BuildAnimationPlugin)
+            .add(colorings::ColoringsPlugin)
+            .add(colors::ColorSchemePlugin)
             .add(config::ConfigPlugin)
+            .add(console::ConsolePlugin)
+            .add(drop::DropPlugin)
+            .add(generator::GeneratorPlugin)
+            .add(ghost::GhostPlugin)
+            .add(gizmo::GizmoPlugin)
+            .add(groups::GroupPlugin)
             .add(window::WindowPlugin)
             .add(library::LibraryPlugin)
             .add(main_window::MainWindowPlugin)
+            .add(measure::MeasurePlugin)
             .add(top_panel::TopPanelPlugin)
-            .add(right_panel::RightPanelPlugin);
+            .add(right_panel::RightPanelPlugin)
+            .add(sections::SectionsPlugin)
+            .add(selection::SelectionPlugin)
+            .add(session::SessionPlugin)
+            .add(petrie::PetriePlugin)
+            .add(kaleidoscope::KaleidoscopePlugin)
+            .add(recorder::RecorderPlugin)
+            .add(hasse::HassePlugin)
+            .add(task::TaskPlugin)
+            .add(toast::ToastPlugin)
+            .add(vertex_table::VertexTablePlugin);
     }
 }
 
+/// Returns a color for the item with a given index, to tell apart things like
+/// Petrie polygons or orbits of edges. Consecutive indices are spread out
+/// around the color wheel.
+pub fn palette_color(idx: usize) -> bevy::prelude::Color {
+    // The golden angle, in degrees.
+    const GOLDEN_ANGLE: f32 = 137.507_77;
+
+    bevy::prelude::Color::hsl((idx as f32 * GOLDEN_ANGLE) % 360.0, 0.9, 0.5)
+}
+
 /// A widget that sets a point.
 pub struct PointWidget<'a> {
     label: String,
@@ -78,22 +131,22 @@ impl<'a> Widget for UnitPointWidget<'a> {
                 ui.add(egui::DragValue::new(coord).speed(0.01));
 
                 // The index of the modified coordinate.
-                if abs_diff_eq!(old_point[idx], *coord, epsilon = EPS) {
+                if abs_diff_eq!(old_point[idx], *coord, epsilon = eps()) {
                     modified_coord = idx;
                 }
 
                 // Gets rid of floating point shenanigans.
-                if abs_diff_eq!(*coord, 0.0, epsilon = EPS.sqrt()) {
+                if abs_diff_eq!(*coord, 0.0, epsilon = eps().sqrt()) {
                     *coord = 0.0;
-                } else if abs_diff_eq!(*coord, 1.0, epsilon = EPS) {
+                } else if abs_diff_eq!(*coord, 1.0, epsilon = eps()) {
                     *coord = 1.0;
-                } else if abs_diff_eq!(*coord, -1.0, epsilon = EPS) {
+                } else if abs_diff_eq!(*coord, -1.0, epsilon = eps()) {
                     *coord = -1.0;
                 }
             }
 
             // Normalizes the point.
-            if self.0.point.try_normalize_mut(EPS).is_none() {
+            if self.0.point.try_normalize_mut(eps()).is_none() {
                 // If this fails, sets it to the axis direction corresponding
                 // to the last modified coordinate.
                 for coord in self.0.point.iter_mut() {
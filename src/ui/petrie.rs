@@ -0,0 +1,225 @@
+//! Contains the Petrie polygon overlay, which highlights the Petrie polygons of
+//! the loaded polytope on its wireframe.
+
+use std::collections::HashSet;
+
+use super::{camera::ProjectionType, palette_color};
+use crate::mesh::Renderable;
+use crate::no_cull_pipeline::PbrNoBackfaceBundle;
+use crate::Concrete;
+
+use bevy::prelude::*;
+use bevy_egui::egui;
+use miratope_core::{abs::Ranked, Polytope};
+
+/// The plugin in charge of the Petrie polygon overlay.
+pub struct PetriePlugin;
+
+impl Plugin for PetriePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PetrieOverlay>().add_system_to_stage(
+            CoreStage::PostUpdate,
+            update_overlay.system().after("update_changed_polytopes"),
+        );
+    }
+}
+
+/// Marks the entities that draw a Petrie polygon over the wireframe, so that
+/// they aren't mistaken for the wireframe itself.
+#[derive(Clone, Copy, Debug)]
+pub struct PetrieMarker;
+
+/// Which Petrie polygons are highlighted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PetrieMode {
+    /// No Petrie polygon is highlighted.
+    Off,
+
+    /// Only the Petrie polygon with the given index is highlighted.
+    One(usize),
+
+    /// Every Petrie polygon is highlighted, each with a different color.
+    All,
+}
+
+impl Default for PetrieMode {
+    fn default() -> Self {
+        Self::Off
+    }
+}
+
+/// The state of the Petrie polygon overlay.
+#[derive(Default)]
+pub struct PetrieOverlay {
+    /// Which Petrie polygons are highlighted.
+    mode: PetrieMode,
+
+    /// The Petrie polygons of the loaded polytope, as lists of vertex indices,
+    /// or `None` if they haven't been computed yet.
+    polygons: Option<Vec<Vec<usize>>>,
+}
+
+impl PetrieOverlay {
+    /// Returns the indices of the Petrie polygons to highlight.
+    fn highlighted(&self) -> std::ops::Range<usize> {
+        let count = self.polygons.as_ref().map_or(0, Vec::len);
+
+        match self.mode {
+            PetrieMode::Off => 0..0,
+            PetrieMode::One(idx) if idx < count => idx..idx + 1,
+            PetrieMode::One(_) => 0..0,
+            PetrieMode::All => 0..count,
+        }
+    }
+
+    /// Shows the controls for the overlay in a menu. Returns the new mode if
+    /// the user changed it.
+    ///
+    /// This doesn't take `self` mutably, so that merely showing the menu
+    /// doesn't rebuild the overlay.
+    pub fn menu(&self, ui: &mut egui::Ui) -> Option<PetrieMode> {
+        let mut mode = self.mode;
+
+        ui.label("Petrie polygons");
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut mode, PetrieMode::Off, "Off");
+
+            let one = matches!(mode, PetrieMode::One(_));
+            if ui.radio(one, "One").clicked() && !one {
+                mode = PetrieMode::One(0);
+            }
+
+            ui.radio_value(&mut mode, PetrieMode::All, "All");
+        });
+
+        if let PetrieMode::One(idx) = &mut mode {
+            let max = self
+                .polygons
+                .as_ref()
+                .map_or(0, |p| p.len().saturating_sub(1));
+            ui.add(
+                egui::DragValue::new(idx)
+                    .clamp_range(0..=max)
+                    .prefix("Index: "),
+            );
+        }
+
+        if let Some(polygons) = &self.polygons {
+            ui.label(format!("{} Petrie polygons", polygons.len()));
+        }
+
+        (mode != self.mode).then(|| mode)
+    }
+
+    /// Sets which Petrie polygons are highlighted.
+    pub fn set_mode(&mut self, mode: PetrieMode) {
+        self.mode = mode;
+    }
+}
+
+/// The edges of a polygon given by its vertices in cyclic order.
+fn polygon_edges(polygon: &[usize]) -> impl Iterator<Item = [usize; 2]> + '_ {
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(&v, &w)| [v, w])
+}
+
+/// The system that rebuilds the Petrie polygon overlay whenever the polytope
+/// or the overlay settings change.
+#[allow(clippy::too_many_arguments)]
+fn update_overlay(
+    mut commands: Commands<'_, '_>,
+    mut overlay: ResMut<'_, PetrieOverlay>,
+    mut meshes: ResMut<'_, Assets<Mesh>>,
+    mut materials: ResMut<'_, Assets<StandardMaterial>>,
+    polies: Query<'_, '_, (&Concrete, &Children)>,
+    changed: Query<'_, '_, (), Changed<Concrete>>,
+    wfs: Query<'_, '_, &Handle<Mesh>, Without<Concrete>>,
+    markers: Query<'_, '_, Entity, With<PetrieMarker>>,
+    projection_type: Res<'_, ProjectionType>,
+) {
+    let poly_changed = changed.iter().next().is_some();
+    if poly_changed {
+        overlay.polygons = None;
+    }
+
+    if !poly_changed && !overlay.is_changed() && !projection_type.is_changed() {
+        return;
+    }
+
+    // Removes the old overlay.
+    for entity in markers.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let (poly, children) = match polies.iter().next() {
+        Some(poly) => poly,
+        None => return,
+    };
+
+    if overlay.mode == PetrieMode::Off {
+        // The wireframe might still be missing the edges of the last overlay.
+        if !poly_changed {
+            for child in children.iter() {
+                if let Ok(wf_handle) = wfs.get(*child) {
+                    *meshes.get_mut(wf_handle).unwrap() = poly.wireframe(*projection_type);
+                }
+            }
+        }
+
+        return;
+    }
+
+    // We only compute the Petrie polygons when they're first needed.
+    if overlay.polygons.is_none() {
+        let mut abs = poly.abs.clone();
+        if !abs.sorted() {
+            abs.element_sort();
+        }
+
+        overlay.polygons = Some(abs.petrie_polygons());
+    }
+
+    let overlay = &*overlay;
+    let polygons = overlay.polygons.as_ref().unwrap();
+    let highlighted = overlay.highlighted();
+    let mut highlighted_edges = HashSet::new();
+
+    for idx in highlighted {
+        let polygon = &polygons[idx];
+        for [v, w] in polygon_edges(polygon) {
+            highlighted_edges.insert((v.min(w), v.max(w)));
+        }
+
+        let material = materials.add(StandardMaterial {
+            base_color: palette_color(idx),
+            unlit: true,
+            ..Default::default()
+        });
+
+        commands
+            .spawn_bundle(PbrNoBackfaceBundle {
+                mesh: meshes.add(poly.line_mesh(polygon_edges(polygon), *projection_type)),
+                material,
+                ..Default::default()
+            })
+            .insert(PetrieMarker);
+    }
+
+    // We leave the highlighted edges out of the wireframe, so that the two
+    // don't overlap.
+    let edges = poly
+        .get_element_list(2)
+        .into_iter()
+        .flatten()
+        .map(|edge| [edge.subs[0], edge.subs[1]])
+        .filter(|&[v, w]| !highlighted_edges.contains(&(v.min(w), v.max(w))));
+    let wireframe = poly.line_mesh(edges, *projection_type);
+
+    for child in children.iter() {
+        if let Ok(wf_handle) = wfs.get(*child) {
+            *meshes.get_mut(wf_handle).unwrap() = wireframe.clone();
+        }
+    }
+}
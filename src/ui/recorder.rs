@@ -0,0 +1,221 @@
+//! Contains the operation recorder, a window that records the operations
+//! applied to the loaded polytope as a [`Pipeline`], so that they can be
+//! replayed on another polytope.
+//!
+//! Only the operations with an equivalent in the console's language are
+//! recorded. Any other step can be typed in by hand.
+
+use std::{fs, path::PathBuf};
+
+use super::{toast::Toasts, top_panel::FileDialogState};
+use crate::Concrete;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+use miratope_core::{
+    conc::pipeline::{Pipeline, VARIABLE},
+    error::{MiratopeError, MiratopeResult},
+};
+
+/// The plugin in charge of the operation recorder.
+pub struct RecorderPlugin;
+
+impl Plugin for RecorderPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Recorder>()
+            .add_event::<PipelineEvent>()
+            .add_system(pipeline_events.system().before("show_top_panel"))
+            .add_system(show_recorder.system().after("show_top_panel"));
+    }
+}
+
+/// A request to save or open a pipeline, sent once the user has picked a file.
+pub enum PipelineEvent {
+    /// Save the recorded pipeline to a path.
+    Save(PathBuf),
+
+    /// Open the pipeline at a path, replacing the recorded one.
+    Open(PathBuf),
+}
+
+/// The state of the operation recorder.
+#[derive(Default)]
+pub struct Recorder {
+    /// Whether the window is open.
+    open: bool,
+
+    /// Whether operations are currently being recorded.
+    recording: bool,
+
+    /// The operations recorded so far.
+    pipeline: Pipeline,
+
+    /// The step currently being typed.
+    input: String,
+}
+
+impl Recorder {
+    /// Opens the window of the recorder.
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+
+    /// Returns the recorded pipeline.
+    pub fn pipeline(&self) -> &Pipeline {
+        &self.pipeline
+    }
+
+    /// Replaces the recorded pipeline.
+    pub fn restore(&mut self, pipeline: Pipeline) {
+        self.pipeline = pipeline;
+    }
+
+    /// Records a step, if we're recording. The step must be a valid line of
+    /// the console's language, in terms of the variable `p`.
+    pub fn record(&mut self, step: &str) {
+        if self.recording {
+            self.pipeline
+                .push(step)
+                .expect("recorded steps should always parse");
+        }
+    }
+}
+
+/// The system that saves and opens pipelines.
+fn pipeline_events(
+    mut events: EventReader<'_, '_, PipelineEvent>,
+    mut recorder: ResMut<'_, Recorder>,
+    mut toasts: ResMut<'_, Toasts>,
+) {
+    for event in events.iter() {
+        let res: MiratopeResult<()> = match event {
+            PipelineEvent::Save(path) => fs::write(path, recorder.pipeline.to_string())
+                .map_err(|err| MiratopeError::save(path, err)),
+            PipelineEvent::Open(path) => fs::read_to_string(path)
+                .map_err(|err| MiratopeError::load(path, err))
+                .and_then(|src| {
+                    src.parse::<Pipeline>()
+                        .map_err(|err| MiratopeError::load(path, err))
+                })
+                .map(|pipeline| recorder.restore(pipeline)),
+        };
+
+        match res {
+            Ok(()) => {
+                if let PipelineEvent::Save(_) = event {
+                    toasts.info("Pipeline saved!");
+                }
+            }
+            Err(err) => toasts.error(err),
+        }
+    }
+}
+
+/// The system that shows the operation recorder.
+fn show_recorder(
+    egui_ctx: Res<'_, EguiContext>,
+    mut query: Query<'_, '_, &mut Concrete>,
+    mut recorder: ResMut<'_, Recorder>,
+    mut file_dialog_state: ResMut<'_, FileDialogState>,
+    mut toasts: ResMut<'_, Toasts>,
+) {
+    if !recorder.open {
+        return;
+    }
+
+    let mut open = true;
+    let mut replay = false;
+
+    egui::Window::new("Recorder")
+        .open(&mut open)
+        .default_width(320.0)
+        .show(egui_ctx.ctx(), |ui| {
+            let recorder = &mut *recorder;
+
+            ui.horizontal(|ui| {
+                let label = if recorder.recording {
+                    "Stop recording"
+                } else {
+                    "Record"
+                };
+                if ui.button(label).clicked() {
+                    recorder.recording = !recorder.recording;
+                }
+
+                if recorder.recording {
+                    ui.colored_label(egui::Color32::LIGHT_RED, "Recording");
+                }
+            });
+
+            ui.separator();
+
+            egui::ScrollArea::auto_sized().show(ui, |ui| {
+                if recorder.pipeline.is_empty() {
+                    ui.label("No steps recorded.");
+                }
+
+                for (idx, step) in recorder.pipeline.steps().iter().enumerate() {
+                    ui.monospace(format!("{}. {}", idx + 1, step));
+                }
+            });
+
+            // Adds a step by hand.
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut recorder.input)
+                    .code_editor()
+                    .hint_text(format!("duoprism({}, polygon(5))", VARIABLE)),
+            );
+            if response.lost_focus() && ui.input().key_pressed(egui::Key::Enter) {
+                let step = std::mem::take(&mut recorder.input);
+                let res = match step.trim() {
+                    "" => Ok(()),
+                    line => recorder.pipeline.push(line),
+                };
+                if let Err(err) = res {
+                    toasts.error(MiratopeError::operation("Adding the step", err));
+                    recorder.input = step;
+                }
+                response.request_focus();
+            }
+
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                if ui.button("Replay").clicked() {
+                    replay = true;
+                }
+
+                if ui.button("Undo").clicked() {
+                    recorder.pipeline.pop();
+                }
+
+                if ui.button("Clear").clicked() {
+                    recorder.pipeline.clear();
+                }
+            });
+
+            ui.horizontal(|ui| {
+                if ui.button("Save...").clicked() {
+                    file_dialog_state.save_pipeline("pipeline.txt".to_string());
+                }
+
+                if ui.button("Open...").clicked() {
+                    file_dialog_state.open_pipeline();
+                }
+            });
+        });
+
+    // Applies the pipeline to the loaded polytope.
+    if replay {
+        if let Some(mut p) = query.iter_mut().next() {
+            match recorder.pipeline.apply(p.clone()) {
+                Ok(q) => *p = q,
+                Err(err) => toasts.error(MiratopeError::operation("Replaying the pipeline", err)),
+            }
+        }
+    }
+
+    if !open {
+        recorder.open = false;
+    }
+}
@@ -1,5 +1,6 @@
 //! Contains all code related to the right side panel.
 
+use super::{selection::Selection, toast::Toasts};
 use crate::Concrete;
 
 use bevy::prelude::*;
@@ -7,14 +8,17 @@ use bevy_egui::{
     egui,
     EguiContext,
 };
-use miratope_core::{conc::{element_types::{EL_NAMES, EL_SUFFIXES}, ConcretePolytope}, Polytope, abs::Ranked};
+use miratope_core::{conc::{element_types::{EL_NAMES, EL_SUFFIXES}, ConcretePolytope}, error::MiratopeError, Polytope, abs::Ranked};
 use vec_like::VecLike;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 struct ElementTypeWithData {
     /// The index of the representative for this element type.
     example: usize,
 
+    /// The indices of every element of this type.
+    elements: Vec<usize>,
+
     /// The number of elements of this type.
     count: usize,
 
@@ -28,6 +32,74 @@ struct ElementTypeWithData {
     radius: Option<f64>,
 }
 
+/// The invariants of the skeleton of a polytope, the graph of its vertices and
+/// edges.
+#[derive(Clone, Debug, Default)]
+struct SkeletonData {
+    /// Every vertex degree, from greatest to least, along with how many
+    /// vertices have it.
+    degrees: Vec<(usize, usize)>,
+
+    /// The diameter of the graph, if it's connected.
+    diameter: Option<usize>,
+
+    /// The girth of the graph, if it has any cycles.
+    girth: Option<usize>,
+
+    /// The vertex connectivity of the graph.
+    vertex_connectivity: usize,
+
+    /// The edge connectivity of the graph.
+    edge_connectivity: usize,
+}
+
+impl SkeletonData {
+    /// Computes the invariants of the skeleton of a polytope.
+    fn from_poly(poly: &Concrete) -> SkeletonData {
+        let graph = poly.abs.skeleton();
+
+        let mut degrees: Vec<(usize, usize)> = Vec::new();
+        for degree in graph.degree_sequence() {
+            match degrees.last_mut() {
+                Some((last, count)) if *last == degree => *count += 1,
+                _ => degrees.push((degree, 1)),
+            }
+        }
+
+        SkeletonData {
+            degrees,
+            diameter: graph.diameter(),
+            girth: graph.girth(),
+            vertex_connectivity: graph.vertex_connectivity(),
+            edge_connectivity: graph.edge_connectivity(),
+        }
+    }
+
+    /// Shows the invariants.
+    fn show(&self, ui: &mut egui::Ui) {
+        let degrees: Vec<_> = self
+            .degrees
+            .iter()
+            .map(|(degree, count)| format!("{} × {}", count, degree))
+            .collect();
+        ui.label(format!("Degrees: {}", degrees.join(", ")))
+            .on_hover_text("How many vertices have each number of edges");
+
+        let or_none = |value: Option<usize>, none: &str| {
+            value.map_or_else(|| none.to_string(), |value| value.to_string())
+        };
+        ui.label(format!(
+            "Diameter: {}",
+            or_none(self.diameter, "disconnected")
+        ));
+        ui.label(format!("Girth: {}", or_none(self.girth, "acyclic")));
+        ui.label(format!("Vertex connectivity: {}", self.vertex_connectivity))
+            .on_hover_text("The least number of vertices whose removal disconnects the skeleton");
+        ui.label(format!("Edge connectivity: {}", self.edge_connectivity))
+            .on_hover_text("The least number of edges whose removal disconnects the skeleton");
+    }
+}
+
 #[derive(Clone)]
 pub struct ElementTypesRes {
     /// The polytope whose data we're getting.
@@ -36,6 +108,9 @@ pub struct ElementTypesRes {
     /// The element types.
     types: Vec<Vec<ElementTypeWithData>>,
 
+    /// The invariants of the skeleton.
+    skeleton: SkeletonData,
+
     /// Whether the loaded polytope matches `poly` and the buttons should be greyed out.
     pub main: bool,
 
@@ -48,6 +123,7 @@ impl Default for ElementTypesRes {
         ElementTypesRes {
             poly: Concrete::nullitope(),
             types: Vec::new(),
+            skeleton: SkeletonData::default(),
             main: true,
             main_updating: false,
         }
@@ -56,10 +132,10 @@ impl Default for ElementTypesRes {
 
 impl ElementTypesRes {
     fn from_poly(&self, poly: Mut<'_, Concrete>) -> ElementTypesRes {
-        let plain_types = poly.element_types();
+        let elements_by_type = poly.elements_by_type();
         let mut types_with_data = Vec::new();
     
-        for (r, types) in plain_types.clone().into_iter().enumerate() {
+        for (r, types) in elements_by_type.into_iter().enumerate() {
             let rank = poly.rank();
             if r == rank {
                 break;
@@ -69,8 +145,8 @@ impl ElementTypesRes {
             let dual_abs = &abs.dual();
             let mut types_with_data_this_rank = Vec::new();
             
-            for t in types {
-                let idx = t.example;
+            for elements in types {
+                let idx = elements[0];
     
                 let facets = abs[(r, idx)].subs.len();
                 let fig_facets = dual_abs.element_vertices(rank-r, idx).unwrap().len();
@@ -88,7 +164,8 @@ impl ElementTypesRes {
     
                 types_with_data_this_rank.push(ElementTypeWithData {
                     example: idx,
-                    count: t.count,
+                    count: elements.len(),
+                    elements,
                     facets,
                     fig_facets,
                     radius,
@@ -98,6 +175,7 @@ impl ElementTypesRes {
         }
     
         ElementTypesRes {
+            skeleton: SkeletonData::from_poly(&poly),
             poly: poly.clone(),
             types: types_with_data,
             main: true,
@@ -132,6 +210,8 @@ pub fn show_right_panel(
 
     // The Miratope resources controlled by the right panel.
     mut element_types: ResMut<'_, ElementTypesRes>,
+    mut selection: ResMut<'_, Selection>,
+    mut toasts: ResMut<'_, Toasts>,
 ) {
     // The right panel.
     egui::SidePanel::right("right_panel")
@@ -159,6 +239,14 @@ pub fn show_right_panel(
             ui.separator();
 
             egui::containers::ScrollArea::auto_sized().show(ui, |ui| {
+                if element_types.poly.rank() > 1 {
+                    egui::CollapsingHeader::new("Skeleton").show(ui, |ui| {
+                        element_types.skeleton.show(ui);
+                    });
+
+                    ui.separator();
+                }
+
                 for (r, types) in element_types.types.clone().into_iter().enumerate().skip(1) {
                     let poly = &element_types.poly;
                     let rank = element_types.poly.rank();
@@ -173,8 +261,19 @@ pub fn show_right_panel(
 
                         ui.horizontal(|ui| {
 
-                            // The number of elements in this orbit
-                            ui.label(format!("{} ×",t.count));
+                            // The number of elements in this orbit, which selects them
+                            // when the loaded polytope is the one shown.
+                            let count = format!("{} ×", t.count);
+                            if element_types.main {
+                                if ui.selectable_label(selection.is(r, &t.elements), count)
+                                    .on_hover_text("Select every element of this type")
+                                    .clicked()
+                                {
+                                    selection.toggle(r, &t.elements);
+                                }
+                            } else {
+                                ui.label(count);
+                            }
 
                             // Button to get the element
                             if ui.button(format!("{}-{}", 
@@ -187,7 +286,10 @@ pub fn show_right_panel(
                                         element.recenter();
                                         *p = element;
                                     } else {
-                                        eprintln!("Element failed: no element at rank {}, index {}", r, i);
+                                        toasts.error(MiratopeError::operation(
+                                            "Element",
+                                            format!("no element at rank {}, index {}", r, i),
+                                        ));
                                     }
                                 }
                             }
@@ -199,13 +301,12 @@ pub fn show_right_panel(
                             )).clicked() {
                                 if let Some(mut p) = query.iter_mut().next() {
                                     match poly.element_fig(r, i) {
-                                        Ok(Some(mut figure)) => {
+                                        Ok(mut figure) => {
                                             figure.flatten();
                                             figure.recenter();
                                             *p = figure;
                                         }
-                                        Ok(None) => eprintln!("Figure failed: no element at rank {}, index {}", r, i),
-                                        Err(err) => eprintln!("Figure failed: {}", err),
+                                        Err(err) => toasts.error(MiratopeError::operation("Figure", err)),
                                     }
                                 }
                             }
@@ -0,0 +1,312 @@
+//! Contains the sections explorer, a window that lets the user browse through
+//! the sections of the loaded polytope and load any of them.
+
+use crate::Concrete;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+use miratope_core::{
+    abs::{ElementHash, Ranked, Section},
+    conc::{element_types::EL_NAMES, ConcretePolytope},
+    Polytope,
+};
+
+/// The width and height of the preview of a section, in pixels.
+const PREVIEW_SIZE: f32 = 160.0;
+
+/// The plugin in charge of the sections explorer.
+pub struct SectionsPlugin;
+
+impl Plugin for SectionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SectionExplorer>()
+            .add_system(update_explorer.system())
+            .add_system(show_explorer.system().after("show_top_panel"));
+    }
+}
+
+/// The state of the sections explorer.
+pub struct SectionExplorer {
+    /// Whether the window is open.
+    open: bool,
+
+    /// Whether the loaded polytope has changed since the explorer was last
+    /// refreshed.
+    stale: bool,
+
+    /// The currently selected section.
+    section: Section,
+
+    /// The number of sections between each pair of ranks. The entry
+    /// `counts[hi][lo]` is the number of sections whose lowest element has rank
+    /// `lo` and whose highest element has rank `hi`.
+    counts: Vec<Vec<usize>>,
+
+    /// The indices of the elements of the lower rank that are subelements of
+    /// the selected element of the upper rank.
+    lo_choices: Vec<usize>,
+
+    /// The selected section as a polytope, or the reason it couldn't be built.
+    preview: Result<Concrete, String>,
+}
+
+impl Default for SectionExplorer {
+    fn default() -> Self {
+        Self {
+            open: false,
+            stale: true,
+            section: Section::singleton(0, 0),
+            counts: Vec::new(),
+            lo_choices: Vec::new(),
+            preview: Err(String::new()),
+        }
+    }
+}
+
+impl SectionExplorer {
+    /// Opens the sections explorer.
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+
+    /// Recomputes the section counts for a polytope, and makes sure that the
+    /// selected section is still valid.
+    fn refresh(&mut self, poly: &Concrete) {
+        self.counts = section_counts(poly);
+        self.stale = false;
+        self.select(poly, self.section);
+    }
+
+    /// Selects a section of a polytope and builds its preview. Any ranks or
+    /// indices out of range are replaced by valid ones.
+    fn select(&mut self, poly: &Concrete, section: Section) {
+        // Every rank of a polytope has at least one element.
+        let hi_rank = section.hi_rank.min(poly.rank());
+        let hi_idx = section.hi_idx.min(poly.el_count(hi_rank) - 1);
+        let lo_rank = section.lo_rank.min(hi_rank);
+
+        self.lo_choices = ElementHash::new(poly.abs(), hi_rank, hi_idx)
+            .map(|hash| hash.to_elements(lo_rank))
+            .unwrap_or_default();
+
+        let lo_idx = if self.lo_choices.contains(&section.lo_idx) {
+            section.lo_idx
+        } else {
+            self.lo_choices.first().copied().unwrap_or_default()
+        };
+
+        self.section = Section::new(lo_rank, lo_idx, hi_rank, hi_idx);
+        self.preview = poly
+            .section(lo_rank, lo_idx, hi_rank, hi_idx)
+            .map(|mut section| {
+                section.flatten();
+                section.recenter();
+                section
+            })
+            .map_err(|err| err.to_string());
+    }
+}
+
+/// Counts the sections of a polytope between each pair of ranks.
+fn section_counts(poly: &Concrete) -> Vec<Vec<usize>> {
+    let rank = poly.rank();
+    let mut counts: Vec<Vec<usize>> = (0..=rank).map(|hi| vec![0; hi + 1]).collect();
+
+    for (hi, hi_counts) in counts.iter_mut().enumerate() {
+        for idx in 0..poly.el_count(hi) {
+            if let Some(hash) = ElementHash::new(poly.abs(), hi, idx) {
+                for (lo, count) in hi_counts.iter_mut().enumerate() {
+                    *count += hash.to_elements(lo).len();
+                }
+            }
+        }
+    }
+
+    counts
+}
+
+/// The name for the elements of a given rank in a polytope of another given
+/// rank.
+fn rank_label(rank: usize, max_rank: usize) -> String {
+    if rank == 0 {
+        "Minimal element".to_string()
+    } else if rank == max_rank {
+        "Maximal element".to_string()
+    } else if let Some(name) = EL_NAMES.get(rank) {
+        name.to_string()
+    } else {
+        format!("{}-elements", rank - 1)
+    }
+}
+
+/// Draws a small projection of the vertices and edges of a polytope.
+fn draw_wireframe(ui: &mut egui::Ui, poly: &Concrete) {
+    let (rect, _) = ui.allocate_exact_size(egui::Vec2::splat(PREVIEW_SIZE), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    let dim = poly.dim_or().max(1);
+
+    // Every coordinate axis is projected onto a direction in the plane, and
+    // these directions are spread out evenly over half a turn.
+    let points: Vec<_> = poly
+        .vertices
+        .iter()
+        .map(|v| {
+            v.iter()
+                .enumerate()
+                .fold(egui::vec2(0.0, 0.0), |acc, (i, &x)| {
+                    let angle = std::f32::consts::PI * i as f32 / dim as f32;
+                    let x = x as f32;
+                    acc + egui::vec2(x * angle.cos(), -x * angle.sin())
+                })
+        })
+        .collect();
+
+    // We scale the projection so that it fits in the preview.
+    let extent = points
+        .iter()
+        .map(|p| p.x.abs().max(p.y.abs()))
+        .fold(0.0, f32::max);
+    let scale = if extent > 0.0 {
+        (PREVIEW_SIZE / 2.0 - 8.0) / extent
+    } else {
+        0.0
+    };
+    let to_screen = |p: egui::Vec2| rect.center() + p * scale;
+
+    if poly.rank() >= 2 {
+        let stroke = egui::Stroke::new(1.0, egui::Color32::GRAY);
+
+        for edge in poly.abs[2].iter() {
+            if edge.subs.len() == 2 {
+                let (a, b) = (points[edge.subs[0]], points[edge.subs[1]]);
+                painter.line_segment([to_screen(a), to_screen(b)], stroke);
+            }
+        }
+    }
+
+    for &p in &points {
+        painter.circle_filled(to_screen(p), 2.0, egui::Color32::WHITE);
+    }
+}
+
+/// The system that marks the explorer as outdated whenever the polytope
+/// changes.
+fn update_explorer(
+    query: Query<'_, '_, &Concrete, Changed<Concrete>>,
+    mut explorer: ResMut<'_, SectionExplorer>,
+) {
+    if query.iter().next().is_some() {
+        explorer.stale = true;
+    }
+}
+
+/// The system that shows the sections explorer.
+fn show_explorer(
+    egui_ctx: Res<'_, EguiContext>,
+    mut query: Query<'_, '_, &mut Concrete>,
+    mut explorer: ResMut<'_, SectionExplorer>,
+) {
+    if !explorer.open {
+        return;
+    }
+
+    let mut p = match query.iter_mut().next() {
+        Some(p) => p,
+        None => return,
+    };
+
+    // We only count sections while the window is open.
+    if explorer.stale {
+        explorer.refresh(&p);
+    }
+
+    let rank = p.rank();
+    let mut open = true;
+    let mut selected = explorer.section;
+    let mut load = false;
+
+    egui::Window::new("Sections")
+        .open(&mut open)
+        .resizable(false)
+        .show(egui_ctx.ctx(), |ui| {
+            egui::CollapsingHeader::new("Sections by rank").show(ui, |ui| {
+                for hi in 1..=rank {
+                    for lo in 0..hi {
+                        let text = format!(
+                            "{} to {}: {}",
+                            rank_label(lo, rank),
+                            rank_label(hi, rank),
+                            explorer.counts[hi][lo]
+                        );
+                        let checked = selected.lo_rank == lo && selected.hi_rank == hi;
+
+                        if ui.selectable_label(checked, text).clicked() {
+                            selected.lo_rank = lo;
+                            selected.hi_rank = hi;
+                        }
+                    }
+                }
+            });
+
+            ui.separator();
+
+            egui::ComboBox::from_label("Upper rank")
+                .selected_text(rank_label(selected.hi_rank, rank))
+                .show_ui(ui, |ui| {
+                    for r in 0..=rank {
+                        ui.selectable_value(&mut selected.hi_rank, r, rank_label(r, rank));
+                    }
+                });
+
+            egui::ComboBox::from_label("Upper element")
+                .selected_text(selected.hi_idx.to_string())
+                .show_ui(ui, |ui| {
+                    for idx in 0..p.el_count(selected.hi_rank) {
+                        ui.selectable_value(&mut selected.hi_idx, idx, idx.to_string());
+                    }
+                });
+
+            egui::ComboBox::from_label("Lower rank")
+                .selected_text(rank_label(selected.lo_rank, rank))
+                .show_ui(ui, |ui| {
+                    for r in 0..=selected.hi_rank {
+                        ui.selectable_value(&mut selected.lo_rank, r, rank_label(r, rank));
+                    }
+                });
+
+            // Only the subelements of the upper element can be picked.
+            egui::ComboBox::from_label("Lower element")
+                .selected_text(selected.lo_idx.to_string())
+                .show_ui(ui, |ui| {
+                    for &idx in &explorer.lo_choices {
+                        ui.selectable_value(&mut selected.lo_idx, idx, idx.to_string());
+                    }
+                });
+
+            ui.separator();
+
+            match &explorer.preview {
+                Ok(section) => {
+                    let counts: Vec<_> = section.el_count_iter().map(|c| c.to_string()).collect();
+                    ui.label(format!("Element counts: {}", counts.join(", ")));
+                    draw_wireframe(ui, section);
+                    load = ui.button("Load").clicked();
+                }
+                Err(err) => {
+                    ui.label(format!("No section: {}", err));
+                }
+            }
+        });
+
+    if selected != explorer.section {
+        explorer.select(&p, selected);
+    }
+
+    if load {
+        if let Ok(section) = &explorer.preview {
+            *p = section.clone();
+        }
+    }
+
+    explorer.open = open;
+}
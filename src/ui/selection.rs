@@ -0,0 +1,211 @@
+//! Contains the selection shared by every panel: a set of elements of the
+//! loaded polytope, all of the same rank.
+//!
+//! Any panel can change the selection, and they all show it the same way. The
+//! selected elements are also drawn over the polytope, so that selecting a
+//! type of element in the properties panel or a node of the Hasse diagram
+//! highlights every instance of it on screen.
+
+use std::collections::BTreeSet;
+
+use super::camera::ProjectionType;
+use crate::mesh::Renderable;
+use crate::no_cull_pipeline::PbrNoBackfaceBundle;
+use crate::Concrete;
+
+use bevy::prelude::*;
+use miratope_core::{
+    abs::{ElementHash, Ranked},
+    Polytope,
+};
+
+/// The color of the selected elements on screen.
+const SELECTION_COLOR: Color = Color::CYAN;
+
+/// The plugin in charge of the selection.
+pub struct SelectionPlugin;
+
+impl Plugin for SelectionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Selection>()
+            .add_system(clear_selection.system().before("show_top_panel"))
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_selection.system().after("update_changed_polytopes"),
+            );
+    }
+}
+
+/// Marks the entities that draw the selected elements over the polytope.
+#[derive(Clone, Copy, Debug)]
+pub struct SelectionMarker;
+
+/// The selected elements of the loaded polytope. The selection is cleared
+/// whenever the polytope changes.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Selection {
+    /// The rank of the selected elements.
+    rank: usize,
+
+    /// The indices of the selected elements.
+    elements: BTreeSet<usize>,
+}
+
+impl Selection {
+    /// Returns the rank of the selected elements.
+    pub fn rank(&self) -> usize {
+        self.rank
+    }
+
+    /// Returns the indices of the selected elements.
+    pub fn elements(&self) -> &BTreeSet<usize> {
+        &self.elements
+    }
+
+    /// Returns whether nothing is selected.
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Returns whether the element with a given rank and index is selected.
+    pub fn contains(&self, rank: usize, idx: usize) -> bool {
+        self.rank == rank && self.elements.contains(&idx)
+    }
+
+    /// Returns whether exactly the given elements are selected.
+    pub fn is(&self, rank: usize, elements: &[usize]) -> bool {
+        !self.is_empty()
+            && self.rank == rank
+            && self.elements.len() == elements.len()
+            && elements.iter().all(|idx| self.elements.contains(idx))
+    }
+
+    /// Selects the given elements, replacing the selection.
+    pub fn set<I: IntoIterator<Item = usize>>(&mut self, rank: usize, elements: I) {
+        self.rank = rank;
+        self.elements = elements.into_iter().collect();
+    }
+
+    /// Selects the given elements, or clears the selection if exactly these
+    /// were selected already.
+    pub fn toggle(&mut self, rank: usize, elements: &[usize]) {
+        if self.is(rank, elements) {
+            self.clear();
+        } else {
+            self.set(rank, elements.iter().copied());
+        }
+    }
+
+    /// Clears the selection.
+    pub fn clear(&mut self) {
+        self.elements.clear();
+    }
+
+    /// Returns a short description of the selection.
+    pub fn description(&self) -> String {
+        match self.elements.len() {
+            0 => "Nothing selected".to_string(),
+            1 => format!(
+                "Rank {} element {} selected",
+                self.rank as isize - 1,
+                self.elements.iter().next().unwrap()
+            ),
+            count => format!(
+                "{} rank {} elements selected",
+                count,
+                self.rank as isize - 1
+            ),
+        }
+    }
+}
+
+/// Returns the edges of the polytope to draw for some elements of a given
+/// rank, as pairs of vertex indices. These are the edges of the elements, or
+/// the edges through them in the case of vertices.
+pub fn element_edges<'a, I: IntoIterator<Item = &'a usize>>(
+    poly: &Concrete,
+    rank: usize,
+    elements: I,
+) -> Vec<[usize; 2]> {
+    let mut edges = BTreeSet::new();
+
+    if poly.rank() >= 2 && rank <= poly.rank() {
+        for &idx in elements {
+            if idx >= poly.el_count(rank) {
+                continue;
+            }
+
+            if rank == 1 {
+                edges.extend(poly.abs[(1, idx)].sups.iter().copied());
+            } else if rank >= 2 {
+                if let Some(hash) = ElementHash::new(poly.abs(), rank, idx) {
+                    edges.extend(hash.to_elements(2));
+                }
+            }
+        }
+    }
+
+    edges
+        .into_iter()
+        .map(|edge| {
+            let subs = &poly.abs[(2, edge)].subs;
+            [subs[0], subs[1]]
+        })
+        .collect()
+}
+
+/// The system that clears the selection whenever the polytope changes, since
+/// the indices no longer refer to the same elements.
+fn clear_selection(
+    query: Query<'_, '_, (), Changed<Concrete>>,
+    mut selection: ResMut<'_, Selection>,
+) {
+    if query.iter().next().is_some() && !selection.is_empty() {
+        selection.clear();
+    }
+}
+
+/// The system that draws the selected elements on top of the polytope.
+#[allow(clippy::too_many_arguments)]
+fn update_selection(
+    mut commands: Commands<'_, '_>,
+    mut meshes: ResMut<'_, Assets<Mesh>>,
+    mut materials: ResMut<'_, Assets<StandardMaterial>>,
+    selection: Res<'_, Selection>,
+    polies: Query<'_, '_, &Concrete>,
+    markers: Query<'_, '_, Entity, With<SelectionMarker>>,
+    projection_type: Res<'_, ProjectionType>,
+) {
+    if !selection.is_changed() && !projection_type.is_changed() {
+        return;
+    }
+
+    // Removes the old highlight.
+    for entity in markers.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let poly = match polies.iter().next() {
+        Some(poly) => poly,
+        None => return,
+    };
+
+    let edges = element_edges(poly, selection.rank, &selection.elements);
+    if edges.is_empty() {
+        return;
+    }
+
+    let material = materials.add(StandardMaterial {
+        base_color: SELECTION_COLOR,
+        unlit: true,
+        ..Default::default()
+    });
+
+    commands
+        .spawn_bundle(PbrNoBackfaceBundle {
+            mesh: meshes.add(poly.line_mesh(edges, *projection_type)),
+            material,
+            ..Default::default()
+        })
+        .insert(SelectionMarker);
+}
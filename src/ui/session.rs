@@ -0,0 +1,403 @@
+//! Contains sessions, files that store the state of Miratope so that it can be
+//! picked up where it was left off.
+//!
+//! A session captures the loaded polytope, the memory slots, the camera, the
+//! render settings, the cross-section view, the lines typed into the console
+//! and the pipeline in the operation recorder. Sessions are stored as RON files.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use super::{
+    camera::ProjectionType,
+    config::BgColor,
+    console::Console,
+    memory::Memory,
+    recorder::Recorder,
+    toast::Toasts,
+    top_panel::{SectionDirection, SectionState},
+    window::RenderSettings,
+};
+use crate::{Concrete, Float, Vector};
+
+use bevy::{prelude::*, render::camera::PerspectiveProjection};
+use miratope_core::{
+    conc::pipeline::Pipeline,
+    error::{MiratopeError, MiratopeResult},
+    file::{off::OffOptions, FromFile},
+};
+use serde::{Deserialize, Serialize};
+
+/// The plugin in charge of saving and opening sessions.
+pub struct SessionPlugin;
+
+impl Plugin for SessionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SessionEvent>()
+            .add_system(session_events.system().before("show_top_panel"));
+    }
+}
+
+/// A request to save or open a session, sent once the user has picked a file.
+pub enum SessionEvent {
+    /// Save the current session to a path.
+    Save(PathBuf),
+
+    /// Open the session at a path.
+    Open(PathBuf),
+}
+
+/// A transform, stored as plain arrays since Bevy's aren't serializable.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct SessionTransform {
+    /// The translation of the transform.
+    translation: [f32; 3],
+
+    /// The rotation of the transform, as a quaternion.
+    rotation: [f32; 4],
+
+    /// The scale of the transform.
+    scale: [f32; 3],
+}
+
+impl From<&Transform> for SessionTransform {
+    fn from(transform: &Transform) -> Self {
+        Self {
+            translation: transform.translation.into(),
+            rotation: transform.rotation.into(),
+            scale: transform.scale.into(),
+        }
+    }
+}
+
+impl From<SessionTransform> for Transform {
+    fn from(transform: SessionTransform) -> Self {
+        let [x, y, z, w] = transform.rotation;
+
+        Self {
+            translation: transform.translation.into(),
+            rotation: Quat::from_xyzw(x, y, z, w),
+            scale: transform.scale.into(),
+        }
+    }
+}
+
+/// The render settings of a session.
+#[derive(Serialize, Deserialize)]
+struct SessionRender {
+    /// The width of the rendered image in pixels.
+    width: u32,
+
+    /// The height of the rendered image in pixels.
+    height: u32,
+
+    /// The number of frames in a turntable animation.
+    frames: u32,
+
+    /// Whether to draw the faces.
+    faces: bool,
+
+    /// Whether to draw the edges.
+    edges: bool,
+
+    /// Whether we're projecting orthogonally.
+    orthogonal: bool,
+
+    /// The background color.
+    background: BgColor,
+}
+
+/// The state of the cross-section view of a session.
+#[derive(Serialize, Deserialize)]
+struct SessionSection {
+    /// The polytope from which the cross-section originates, as an OFF file.
+    original_polytope: String,
+
+    /// The ranges of the sliders.
+    minmax: Vec<(Float, Float)>,
+
+    /// The positions of the slicing hyperplanes.
+    hyperplane_pos: Vec<Float>,
+
+    /// The normals of the slicing hyperplanes.
+    directions: Vec<Vec<Float>>,
+
+    /// Whether the cross-section is flattened into a dimension lower.
+    flatten: bool,
+
+    /// Whether we're not updating the cross-section.
+    lock: bool,
+
+    /// Whether the original polytope is drawn as a ghost.
+    #[serde(default)]
+    ghost: bool,
+
+    /// The thickness of the last slice, if it's a slab.
+    #[serde(default)]
+    thickness: Float,
+}
+
+/// Everything stored in a session file.
+#[derive(Serialize, Deserialize)]
+struct Session {
+    /// The loaded polytope, as an OFF file.
+    polytope: String,
+
+    /// The memory slots, with the polytopes as OFF files.
+    memory: Vec<Option<(String, Option<String>)>>,
+
+    /// The transform of the camera anchor.
+    anchor: SessionTransform,
+
+    /// The transform of the camera, relative to its anchor.
+    camera: SessionTransform,
+
+    /// The render settings.
+    render: SessionRender,
+
+    /// The cross-section view, if active.
+    section: Option<SessionSection>,
+
+    /// The lines typed into the console.
+    history: Vec<String>,
+
+    /// The pipeline in the operation recorder. Older sessions don't have one.
+    #[serde(default)]
+    pipeline: Pipeline,
+}
+
+/// Converts a polytope into an OFF file to be stored in a session.
+fn to_off(p: &Concrete, path: &Path) -> MiratopeResult<String> {
+    p.to_off(OffOptions { comments: false })
+        .map_err(|err| MiratopeError::save(path, err))
+}
+
+/// Reads a polytope stored in a session.
+fn from_off(src: &str, path: &Path) -> MiratopeResult<Concrete> {
+    Concrete::from_off(src).map_err(|err| MiratopeError::load(path, err))
+}
+
+/// All of the resources stored in a session, as mutable resources.
+type SessionResources<'a> = (
+    ResMut<'a, Memory>,
+    ResMut<'a, SectionState>,
+    ResMut<'a, Vec<SectionDirection>>,
+    ResMut<'a, RenderSettings>,
+    ResMut<'a, ProjectionType>,
+    ResMut<'a, ClearColor>,
+    ResMut<'a, Console>,
+    ResMut<'a, Recorder>,
+);
+
+/// The system that saves and opens sessions.
+fn session_events(
+    mut events: EventReader<'_, '_, SessionEvent>,
+    mut query: Query<'_, '_, &mut Concrete>,
+    cameras: Query<'_, '_, (Entity, &Parent), With<PerspectiveProjection>>,
+    mut transforms: Query<'_, '_, &mut Transform>,
+    mut toasts: ResMut<'_, Toasts>,
+    mut resources: SessionResources<'_>,
+) {
+    for event in events.iter() {
+        let res = match event {
+            SessionEvent::Save(path) => {
+                save_session(path, &query, &cameras, &transforms, &resources)
+            }
+            SessionEvent::Open(path) => {
+                open_session(path, &mut query, &cameras, &mut transforms, &mut resources)
+            }
+        };
+
+        match res {
+            Ok(()) => {
+                if let SessionEvent::Save(_) = event {
+                    toasts.info("Session saved!");
+                }
+            }
+            Err(err) => toasts.error(err),
+        }
+    }
+}
+
+/// Saves the current session to a path.
+fn save_session(
+    path: &Path,
+    query: &Query<'_, '_, &mut Concrete>,
+    cameras: &Query<'_, '_, (Entity, &Parent), With<PerspectiveProjection>>,
+    transforms: &Query<'_, '_, &mut Transform>,
+    resources: &SessionResources<'_>,
+) -> MiratopeResult<()> {
+    let (
+        memory,
+        section_state,
+        section_direction,
+        render_settings,
+        projection_type,
+        background_color,
+        console,
+        recorder,
+    ) = resources;
+
+    let polytope = match query.iter().next() {
+        Some(p) => to_off(p, path)?,
+        None => return Err(MiratopeError::failed("Saving the session")),
+    };
+
+    let memory = memory
+        .iter()
+        .map(|slot| match slot {
+            Some((p, label)) => Ok(Some((to_off(p, path)?, label.clone()))),
+            None => Ok(None),
+        })
+        .collect::<MiratopeResult<_>>()?;
+
+    // The camera and its anchor.
+    let (camera, anchor) = match cameras
+        .iter()
+        .next()
+        .map(|(camera, parent)| (transforms.get(camera), transforms.get(parent.0)))
+    {
+        Some((Ok(camera), Ok(anchor))) => (camera.into(), anchor.into()),
+        _ => return Err(MiratopeError::failed("Saving the session")),
+    };
+
+    let section = match &**section_state {
+        SectionState::Active {
+            original_polytope,
+            minmax,
+            hyperplane_pos,
+            flatten,
+            lock,
+            ghost,
+            thickness,
+        } => Some(SessionSection {
+            original_polytope: to_off(original_polytope, path)?,
+            minmax: minmax.clone(),
+            hyperplane_pos: hyperplane_pos.clone(),
+            directions: section_direction
+                .iter()
+                .map(|direction| direction.0.iter().copied().collect())
+                .collect(),
+            flatten: *flatten,
+            lock: *lock,
+            ghost: *ghost,
+            thickness: *thickness,
+        }),
+        SectionState::Inactive => None,
+    };
+
+    let session = Session {
+        polytope,
+        memory,
+        anchor,
+        camera,
+        render: SessionRender {
+            width: render_settings.width,
+            height: render_settings.height,
+            frames: render_settings.frames,
+            faces: render_settings.faces,
+            edges: render_settings.edges,
+            orthogonal: projection_type.is_orthogonal(),
+            background: BgColor::new(background_color),
+        },
+        section,
+        history: console.inputs().map(str::to_string).collect(),
+        pipeline: recorder.pipeline().clone(),
+    };
+
+    let src = ron::ser::to_string_pretty(&session, Default::default())
+        .map_err(|err| MiratopeError::save(path, err))?;
+    fs::write(path, src).map_err(|err| MiratopeError::save(path, err))
+}
+
+/// Opens the session at a path, replacing the current one.
+fn open_session(
+    path: &Path,
+    query: &mut Query<'_, '_, &mut Concrete>,
+    cameras: &Query<'_, '_, (Entity, &Parent), With<PerspectiveProjection>>,
+    transforms: &mut Query<'_, '_, &mut Transform>,
+    resources: &mut SessionResources<'_>,
+) -> MiratopeResult<()> {
+    let (
+        memory,
+        section_state,
+        section_direction,
+        render_settings,
+        projection_type,
+        background_color,
+        console,
+        recorder,
+    ) = resources;
+
+    let src = fs::read_to_string(path).map_err(|err| MiratopeError::load(path, err))?;
+    let session: Session = ron::from_str(&src).map_err(|err| MiratopeError::load(path, err))?;
+
+    // We read every polytope before changing anything, so that a broken
+    // session doesn't get loaded halfway.
+    let polytope = from_off(&session.polytope, path)?;
+    let slots = session
+        .memory
+        .iter()
+        .map(|slot| match slot {
+            Some((p, label)) => Ok(Some((from_off(p, path)?, label.clone()))),
+            None => Ok(None),
+        })
+        .collect::<MiratopeResult<_>>()?;
+    let section = match session.section {
+        Some(section) => Some((from_off(&section.original_polytope, path)?, section)),
+        None => None,
+    };
+
+    if let Some(mut p) = query.iter_mut().next() {
+        *p = polytope;
+    }
+    memory.0 = slots;
+
+    if let Some((camera, parent)) = cameras.iter().next() {
+        if let Ok(mut tf) = transforms.get_mut(camera) {
+            *tf = session.camera.into();
+        }
+        if let Ok(mut tf) = transforms.get_mut(parent.0) {
+            *tf = session.anchor.into();
+        }
+    }
+
+    let render = session.render;
+    render_settings.width = render.width;
+    render_settings.height = render.height;
+    render_settings.frames = render.frames;
+    render_settings.faces = render.faces;
+    render_settings.edges = render.edges;
+    if projection_type.is_orthogonal() != render.orthogonal {
+        projection_type.flip();
+    }
+    **background_color = render.background.clear_color();
+
+    // Changing the section state makes the top panel recompute the
+    // cross-section.
+    match section {
+        Some((original_polytope, section)) => {
+            **section_direction = section
+                .directions
+                .into_iter()
+                .map(|direction| SectionDirection(Vector::from_vec(direction)))
+                .collect();
+            **section_state = SectionState::Active {
+                original_polytope,
+                minmax: section.minmax,
+                hyperplane_pos: section.hyperplane_pos,
+                flatten: section.flatten,
+                lock: section.lock,
+                ghost: section.ghost,
+                thickness: section.thickness,
+            };
+        }
+        None => section_state.close(),
+    }
+
+    console.restore(session.history);
+    recorder.restore(session.pipeline);
+    Ok(())
+}
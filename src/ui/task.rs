@@ -0,0 +1,293 @@
+//! Runs long operations on a background thread, so that they don't block the
+//! frame loop.
+//!
+//! Only one operation can run at a time. Its output is picked up by
+//! [`poll_task`] once it's done, and shown on screen. There are no threads on
+//! the web, so there operations run right away and block the frame.
+
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use super::{
+    batch::BatchWindow,
+    memory::Memory,
+    toast::{ToastKind, Toasts},
+};
+use crate::Concrete;
+
+use bevy::{prelude::*, utils::Instant};
+use bevy_egui::{egui, EguiContext};
+use miratope_core::{conc::pipeline::BatchSummary, error::MiratopeError};
+
+/// The plugin in charge of background operations.
+pub struct TaskPlugin;
+
+impl Plugin for TaskPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BackgroundTask>()
+            .add_system(poll_task.system())
+            .add_system(show_task.system().after("show_top_panel"));
+    }
+}
+
+/// The output of a background operation.
+pub enum TaskOutput {
+    /// The loaded polytope is replaced by a new one.
+    Polytope(Concrete),
+
+    /// A list of polytopes is added to memory.
+    Memory(Vec<(Concrete, Option<String>)>),
+
+    /// An operation was applied to every file in a folder.
+    Batch(BatchSummary),
+
+    /// The operation failed with some error message.
+    Error(String),
+}
+
+/// The progress of a background operation, shared between the UI and the
+/// thread running it.
+#[derive(Default)]
+pub struct Progress {
+    /// The number of steps done.
+    done: AtomicUsize,
+
+    /// The total number of steps, or 0 if unknown.
+    total: AtomicUsize,
+
+    /// Whether the user cancelled the operation.
+    cancelled: AtomicBool,
+
+    /// The problems that didn't stop the operation, to be shown once it's
+    /// done.
+    warnings: Mutex<Vec<String>>,
+}
+
+impl Progress {
+    /// Reports that `done` out of `total` steps have been completed.
+    pub fn set(&self, done: usize, total: usize) {
+        self.done.store(done, Ordering::Relaxed);
+        self.total.store(total, Ordering::Relaxed);
+    }
+
+    /// Returns the fraction of the operation that's been completed, if known.
+    pub fn fraction(&self) -> Option<f32> {
+        let total = self.total.load(Ordering::Relaxed);
+
+        if total == 0 {
+            None
+        } else {
+            Some(self.done.load(Ordering::Relaxed) as f32 / total as f32)
+        }
+    }
+
+    /// Returns whether the operation has been cancelled. Operations that can
+    /// stop early should check this every once in a while.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Reports a problem that doesn't stop the operation.
+    pub fn warn<T: ToString>(&self, message: T) {
+        self.warnings.lock().unwrap().push(message.to_string());
+    }
+}
+
+/// Returns the message that a thread panicked with.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        format!("panicked: {}", msg)
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        format!("panicked: {}", msg)
+    } else {
+        "panicked".to_string()
+    }
+}
+
+/// An operation running on a background thread.
+struct Task {
+    /// The name of the operation, shown on screen.
+    name: &'static str,
+
+    /// The moment at which the operation started.
+    start: Instant,
+
+    /// The progress of the operation.
+    progress: Arc<Progress>,
+
+    /// Where the thread writes its output once it's done. The thread always
+    /// writes something, even if the operation panics.
+    output: Arc<Mutex<Option<TaskOutput>>>,
+}
+
+/// The operation currently running in the background, if any.
+#[derive(Default)]
+pub struct BackgroundTask {
+    /// The operation currently running, if any.
+    task: Option<Task>,
+
+    /// The messages about the operations that are yet to be shown as toasts.
+    messages: Vec<(ToastKind, String)>,
+}
+
+impl BackgroundTask {
+    /// Returns whether an operation is currently running. A cancelled
+    /// operation still counts as running until its thread exits.
+    pub fn is_running(&self) -> bool {
+        self.task.is_some()
+    }
+
+    /// Runs an operation on a new thread. Does nothing if another operation is
+    /// already running.
+    pub fn spawn<F>(&mut self, name: &'static str, f: F)
+    where
+        F: FnOnce(&Progress) -> TaskOutput + Send + 'static,
+    {
+        if let Some(task) = &self.task {
+            self.messages.push((
+                ToastKind::Error,
+                format!("Can't start {}: {} is still running.", name, task.name),
+            ));
+            return;
+        }
+
+        let progress = Arc::new(Progress::default());
+        let output = Arc::new(Mutex::new(None));
+        let (thread_progress, thread_output) = (Arc::clone(&progress), Arc::clone(&output));
+
+        let run = move || {
+            let res = panic::catch_unwind(AssertUnwindSafe(|| f(&thread_progress)))
+                .unwrap_or_else(|payload| TaskOutput::Error(panic_message(&*payload)));
+            *thread_output.lock().unwrap() = Some(res);
+        };
+
+        #[cfg(not(target_arch = "wasm32"))]
+        std::thread::spawn(run);
+        #[cfg(target_arch = "wasm32")]
+        run();
+
+        self.task = Some(Task {
+            name,
+            start: Instant::now(),
+            progress,
+            output,
+        });
+    }
+
+    /// Asks the current operation to stop. The operation keeps counting as
+    /// running until its thread notices, and its output is then discarded.
+    pub fn cancel(&mut self) {
+        if let Some(task) = &self.task {
+            task.progress.cancelled.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Takes the output of the current operation, if it's done. The output of
+    /// a cancelled operation is thrown away.
+    fn take_output(&mut self) -> Option<(&'static str, TaskOutput)> {
+        let task = self.task.as_ref()?;
+        let output = task.output.lock().unwrap().take()?;
+        let name = task.name;
+        let cancelled = task.progress.is_cancelled();
+
+        for warning in task.progress.warnings.lock().unwrap().drain(..) {
+            self.messages.push((ToastKind::Error, warning));
+        }
+        self.task = None;
+
+        if cancelled {
+            self.messages
+                .push((ToastKind::Info, format!("{} cancelled.", name)));
+            None
+        } else {
+            Some((name, output))
+        }
+    }
+}
+
+/// The system that applies the output of a finished operation.
+fn poll_task(
+    mut task: ResMut<'_, BackgroundTask>,
+    mut query: Query<'_, '_, &mut Concrete>,
+    mut memory: ResMut<'_, Memory>,
+    mut batch_window: ResMut<'_, BatchWindow>,
+    mut toasts: ResMut<'_, Toasts>,
+) {
+    // We avoid triggering change detection every frame.
+    if !task.is_running() && task.messages.is_empty() {
+        return;
+    }
+
+    let output = task.take_output();
+    for (kind, message) in task.messages.drain(..) {
+        toasts.push(kind, message);
+    }
+
+    if let Some((name, output)) = output {
+        match output {
+            TaskOutput::Polytope(poly) => {
+                if let Some(mut p) = query.iter_mut().next() {
+                    *p = poly;
+                    toasts.info(format!("{} succeeded.", name));
+                }
+            }
+            TaskOutput::Memory(polys) => {
+                toasts.info(format!(
+                    "{} succeeded, {} polytopes added to memory.",
+                    name,
+                    polys.len()
+                ));
+                for poly in polys {
+                    memory.push(poly);
+                }
+            }
+            TaskOutput::Batch(summary) => {
+                toasts.info(format!("{}: {}.", name, summary));
+                batch_window.set_summary(summary);
+            }
+            TaskOutput::Error(err) => toasts.error(MiratopeError::operation(name, err)),
+        }
+    }
+}
+
+/// The system that shows the progress of the current operation.
+fn show_task(egui_ctx: Res<'_, EguiContext>, mut task: ResMut<'_, BackgroundTask>) {
+    let mut cancel = false;
+
+    if let Some(Task {
+        name,
+        start,
+        progress,
+        ..
+    }) = &task.task
+    {
+        egui::Window::new("Working...")
+            .collapsible(false)
+            .resizable(false)
+            .show(egui_ctx.ctx(), |ui| {
+                ui.label(*name);
+
+                if let Some(fraction) = progress.fraction() {
+                    ui.add(egui::ProgressBar::new(fraction).show_percentage());
+                }
+
+                ui.label(format!("Elapsed: {:.1}s", start.elapsed().as_secs_f32()));
+
+                if progress.is_cancelled() {
+                    ui.label("Cancelling...");
+                } else {
+                    cancel = ui.button("Cancel").clicked();
+                }
+            });
+    }
+
+    if cancel {
+        task.cancel();
+    }
+}
@@ -0,0 +1,124 @@
+//! Contains the toasts, small notifications on the lower right corner of the
+//! screen that report the failures of operations, instead of them getting lost
+//! in the console.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+
+/// The number of seconds a toast stays on screen.
+const TOAST_DURATION: f64 = 6.0;
+
+/// The maximum number of toasts on screen at once. Older toasts are removed
+/// when new ones come in.
+const MAX_TOASTS: usize = 5;
+
+/// The plugin in charge of the toasts.
+pub struct ToastPlugin;
+
+impl Plugin for ToastPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Toasts>()
+            .add_system(show_toasts.system().after("show_top_panel"));
+    }
+}
+
+/// The kind of a toast, which determines its color.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ToastKind {
+    /// An informational message.
+    Info,
+
+    /// An operation failed.
+    Error,
+}
+
+/// A single notification.
+struct Toast {
+    /// The kind of the toast.
+    kind: ToastKind,
+
+    /// The message shown.
+    message: String,
+
+    /// The time at which the toast was first shown, or `None` if it hasn't
+    /// been shown yet.
+    shown_at: Option<f64>,
+}
+
+/// The notifications currently on screen.
+#[derive(Default)]
+pub struct Toasts(VecDeque<Toast>);
+
+impl Toasts {
+    /// Adds a toast with a given message.
+    pub fn push<T: ToString>(&mut self, kind: ToastKind, message: T) {
+        if self.0.len() == MAX_TOASTS {
+            self.0.pop_front();
+        }
+
+        self.0.push_back(Toast {
+            kind,
+            message: message.to_string(),
+            shown_at: None,
+        });
+    }
+
+    /// Reports an error. Errors are also written to the console.
+    pub fn error<T: std::fmt::Display>(&mut self, err: T) {
+        eprintln!("{}", err);
+        self.push(ToastKind::Error, err);
+    }
+
+    /// Shows an informational message.
+    pub fn info<T: ToString>(&mut self, message: T) {
+        self.push(ToastKind::Info, message);
+    }
+}
+
+/// The system that shows the toasts on screen, and removes the ones that have
+/// expired or been closed.
+fn show_toasts(
+    egui_ctx: Res<'_, EguiContext>,
+    time: Res<'_, Time>,
+    mut toasts: ResMut<'_, Toasts>,
+) {
+    if toasts.0.is_empty() {
+        return;
+    }
+
+    let now = time.seconds_since_startup();
+    let mut closed = Vec::new();
+
+    egui::Area::new("toasts")
+        .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-10.0, -10.0))
+        .show(egui_ctx.ctx(), |ui| {
+            for (idx, toast) in toasts.0.iter_mut().enumerate() {
+                let shown_at = *toast.shown_at.get_or_insert(now);
+                if now - shown_at > TOAST_DURATION {
+                    closed.push(idx);
+                    continue;
+                }
+
+                let color = match toast.kind {
+                    ToastKind::Info => egui::Color32::LIGHT_BLUE,
+                    ToastKind::Error => egui::Color32::LIGHT_RED,
+                };
+
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.set_max_width(320.0);
+                    ui.horizontal(|ui| {
+                        ui.colored_label(color, &toast.message);
+                        if ui.small_button("×").clicked() {
+                            closed.push(idx);
+                        }
+                    });
+                });
+            }
+        });
+
+    for idx in closed.into_iter().rev() {
+        toasts.0.remove(idx);
+    }
+}
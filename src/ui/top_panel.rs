@@ -2,15 +2,53 @@
 
 use std::path::PathBuf;
 
-use super::{camera::ProjectionType, memory::Memory, window::*, UnitPointWidget};
-use crate::{Concrete, Float, Hyperplane, Point, Vector};
+use super::{
+    batch::{BatchEvent, BatchWindow},
+    build::BuildAnimation,
+    camera::{CameraMode, ProjectionType},
+    colorings::ColoringsWindow,
+    colors::{self, ColorSchemeWindow},
+    console::Console,
+    generator::GeneratorWindow,
+    gizmo::TransformGizmo,
+    groups::{GroupEvent, LoadedGroup},
+    hasse::HasseViewer,
+    kaleidoscope::KaleidoscopeOverlay,
+    main_window::{ClippingPlane, ExplodedView},
+    measure::MeasureTool,
+    memory::Memory,
+    petrie::PetrieOverlay,
+    recorder::{PipelineEvent, Recorder},
+    sections::SectionExplorer,
+    selection::Selection,
+    session::SessionEvent,
+    task::{BackgroundTask, TaskOutput},
+    toast::Toasts,
+    vertex_table::VertexTable,
+    window::*,
+    UnitPointWidget,
+};
+use crate::{screenshot, vector, Concrete, Float, Hyperplane, Point, Vector};
 
-use bevy::prelude::*;
+use bevy::{prelude::*, render::camera::PerspectiveProjection};
 use bevy_egui::{
     egui::{self, menu, Ui},
     EguiContext,
 };
-use miratope_core::{conc::{ConcretePolytope, faceting::GroupEnum}, file::FromFile, float::Float as Float2, Polytope};
+use miratope_core::{abs::Ranked, conc::{color::ColorScheme, Center, ConcretePolytope, faceting::GroupEnum, Scale}, error::MiratopeError, file::{graph::Graph, FromFile}, float::Float as Float2, geometry::Coincidence, Polytope};
+
+/// The presets for viewing 4D polytopes, as their names and the ranks of the
+/// elements they put in front of the viewer.
+const PROJECTION_PRESETS: [(&str, usize); 4] = [
+    ("Vertex-first", 1),
+    ("Edge-first", 2),
+    ("Face-first", 3),
+    ("Cell-first", 4),
+];
+
+/// The elements that a cross-section can be quickly taken through, as their
+/// names and ranks.
+const SLICE_PRESETS: [(&str, usize); 4] = [("Vertex", 1), ("Edge", 2), ("Face", 3), ("Cell", 4)];
 
 /// The plugin in charge of everything on the top panel.
 pub struct TopPanelPlugin;
@@ -53,6 +91,14 @@ pub enum SectionState {
 
         /// Whether we're not updating the cross-section.
         lock: bool,
+
+        /// Whether the original polytope is drawn as a ghost around the
+        /// cross-section. This requires it not to be flattened.
+        ghost: bool,
+
+        /// The thickness of the last slice. If this is positive, the slice is
+        /// taken as a slab of the polytope, rather than as a cross-section.
+        thickness: Float,
     },
 
     /// The view is inactive.
@@ -93,6 +139,37 @@ impl SectionState {
             hyperplane_pos: minmax.clone().into_iter().map(|m| (m.0 + m.1) / 2.0).collect(),
             flatten: true,
             lock: false,
+            ghost: false,
+            thickness: 0.0,
+        }
+    }
+
+    /// Places the first slicing hyperplane through the center of an element
+    /// of the original polytope, orthogonal to the line from its gravicenter.
+    /// Returns the new slicing direction, or `None` if the view is inactive
+    /// or the hyperplane doesn't exist.
+    pub fn slice_through(&mut self, rank: usize, idx: usize) -> Option<Vector> {
+        if let SectionState::Active {
+            original_polytope,
+            minmax,
+            hyperplane_pos,
+            ..
+        } = self
+        {
+            let (normal, pos) = original_polytope.element_hyperplane(rank, idx)?;
+            let (min, max) = original_polytope.minmax(normal.clone())?;
+
+            // We keep away from the ends of the range, to avoid empty slices.
+            let eps = 0.0000001;
+            if max - min <= 2.0 * eps {
+                return None;
+            }
+
+            minmax[0] = (min, max);
+            hyperplane_pos[0] = pos.clamp(min + eps, max - eps);
+            Some(normal)
+        } else {
+            None
         }
     }
 }
@@ -105,6 +182,8 @@ impl Clone for SectionState {
 				hyperplane_pos,
 				flatten,
 				lock,
+				ghost,
+				thickness,
 			} = self{
 				
 			SectionState::Active{
@@ -113,6 +192,8 @@ impl Clone for SectionState {
 				hyperplane_pos: hyperplane_pos.clone(),
 				flatten: *flatten,
 				lock: *lock,
+				ghost: *ghost,
+				thickness: *thickness,
 			}
 		}
 		else
@@ -128,7 +209,7 @@ impl Default for SectionState {
 }
 
 /// Stores the direction in which the cross-sections are taken.
-pub struct SectionDirection(Vector);
+pub struct SectionDirection(pub Vector);
 
 impl Default for SectionDirection {
     fn default() -> Self {
@@ -145,6 +226,13 @@ impl Default for ShowMemory {
     }
 }
 
+impl ShowMemory {
+    /// Shows the memory window.
+    pub fn open(&mut self) {
+        self.0 = true;
+    }
+}
+
 /// Stores whether we're exporting the memory and the index of the memory slot.
 pub struct ExportMemory(bool, usize);
 
@@ -162,6 +250,7 @@ impl Default for ExportMemory {
 #[derive(Default)]
 pub struct FileDialogToken(std::marker::PhantomData<*const ()>);
 
+#[cfg(not(target_arch = "wasm32"))]
 impl FileDialogToken {
     /// Auxiliary function to create a new file dialog.
     fn new_file_dialog() -> rfd::FileDialog {
@@ -170,15 +259,175 @@ impl FileDialogToken {
             .add_filter("GGB file", &["ggb"])
     }
 
-    /// Returns the path given by an open file dialog.
+    /// Returns the path given by an open file dialog. Lists of coordinates
+    /// and periodic OFF files can be opened, but not saved.
     fn pick_file(&self) -> Option<PathBuf> {
-        Self::new_file_dialog().pick_file()
+        Self::new_file_dialog()
+            .add_filter("CSV file", &["csv", "tsv"])
+            .add_filter("Periodic OFF file", &["poff"])
+            .pick_file()
     }
 
     /// Returns the path given by a save file dialog.
     fn save_file(&self, name: &str) -> Option<PathBuf> {
         Self::new_file_dialog().set_file_name(name).save_file()
     }
+
+    /// Returns the path given by a save file dialog for a graph.
+    fn save_graph(&self, name: &str) -> Option<PathBuf> {
+        rfd::FileDialog::new()
+            .add_filter("DOT file", &["dot", "gv"])
+            .add_filter("GraphML file", &["graphml"])
+            .set_file_name(name)
+            .save_file()
+    }
+
+    /// Returns the path given by a save file dialog for a PNG image.
+    fn save_image(&self, name: &str) -> Option<PathBuf> {
+        rfd::FileDialog::new()
+            .add_filter("PNG image", &["png"])
+            .set_file_name(name)
+            .save_file()
+    }
+
+    /// Returns the path given by a save file dialog for an animation.
+    fn save_animation(&self, name: &str) -> Option<PathBuf> {
+        rfd::FileDialog::new()
+            .add_filter("GIF animation", &["gif"])
+            .set_file_name(name)
+            .save_file()
+    }
+
+    /// Returns the path given by a save file dialog for a vector image.
+    fn save_vector(&self, name: &str) -> Option<PathBuf> {
+        rfd::FileDialog::new()
+            .add_filter("SVG image", &["svg"])
+            .add_filter("PostScript file", &["eps", "ps"])
+            .set_file_name(name)
+            .save_file()
+    }
+
+    /// Returns the path given by a folder dialog.
+    fn pick_folder(&self) -> Option<PathBuf> {
+        rfd::FileDialog::new().pick_folder()
+    }
+
+    /// Auxiliary function to create a new file dialog for sessions.
+    fn new_session_dialog() -> rfd::FileDialog {
+        rfd::FileDialog::new().add_filter("Miratope session", &["ron"])
+    }
+
+    /// Returns the path given by an open file dialog for a session.
+    fn pick_session(&self) -> Option<PathBuf> {
+        Self::new_session_dialog().pick_file()
+    }
+
+    /// Returns the path given by a save file dialog for a session.
+    fn save_session(&self, name: &str) -> Option<PathBuf> {
+        Self::new_session_dialog().set_file_name(name).save_file()
+    }
+
+    /// Auxiliary function to create a new file dialog for pipelines.
+    fn new_pipeline_dialog() -> rfd::FileDialog {
+        rfd::FileDialog::new().add_filter("Miratope pipeline", &["txt"])
+    }
+
+    /// Returns the path given by an open file dialog for a pipeline.
+    fn pick_pipeline(&self) -> Option<PathBuf> {
+        Self::new_pipeline_dialog().pick_file()
+    }
+
+    /// Returns the path given by a save file dialog for a pipeline.
+    fn save_pipeline(&self, name: &str) -> Option<PathBuf> {
+        Self::new_pipeline_dialog().set_file_name(name).save_file()
+    }
+
+    /// Auxiliary function to create a new file dialog for symmetry groups.
+    fn new_group_dialog() -> rfd::FileDialog {
+        rfd::FileDialog::new()
+            .add_filter("RON file", &["ron"])
+            .add_filter("JSON file", &["json"])
+    }
+
+    /// Returns the path given by an open file dialog for a symmetry group.
+    fn pick_group(&self) -> Option<PathBuf> {
+        Self::new_group_dialog().pick_file()
+    }
+
+    /// Returns the path given by a save file dialog for a symmetry group.
+    fn save_group(&self, name: &str) -> Option<PathBuf> {
+        Self::new_group_dialog().set_file_name(name).save_file()
+    }
+}
+
+/// There's no file system on the web, so file dialogs never return anything.
+/// Polytopes are instead loaded from the page, through [`crate::web`].
+#[cfg(target_arch = "wasm32")]
+impl FileDialogToken {
+    /// Returns the path given by an open file dialog.
+    fn pick_file(&self) -> Option<PathBuf> {
+        None
+    }
+
+    /// Returns the path given by a save file dialog.
+    fn save_file(&self, _name: &str) -> Option<PathBuf> {
+        None
+    }
+
+    /// Returns the path given by a save file dialog for a graph.
+    fn save_graph(&self, _name: &str) -> Option<PathBuf> {
+        None
+    }
+
+    /// Returns the path given by a save file dialog for a PNG image.
+    fn save_image(&self, _name: &str) -> Option<PathBuf> {
+        None
+    }
+
+    /// Returns the path given by a save file dialog for an animation.
+    fn save_animation(&self, _name: &str) -> Option<PathBuf> {
+        None
+    }
+
+    /// Returns the path given by a save file dialog for a vector image.
+    fn save_vector(&self, _name: &str) -> Option<PathBuf> {
+        None
+    }
+
+    /// Returns the path given by a folder dialog.
+    fn pick_folder(&self) -> Option<PathBuf> {
+        None
+    }
+
+    /// Returns the path given by an open file dialog for a session.
+    fn pick_session(&self) -> Option<PathBuf> {
+        None
+    }
+
+    /// Returns the path given by a save file dialog for a session.
+    fn save_session(&self, _name: &str) -> Option<PathBuf> {
+        None
+    }
+
+    /// Returns the path given by an open file dialog for a pipeline.
+    fn pick_pipeline(&self) -> Option<PathBuf> {
+        None
+    }
+
+    /// Returns the path given by a save file dialog for a pipeline.
+    fn save_pipeline(&self, _name: &str) -> Option<PathBuf> {
+        None
+    }
+
+    /// Returns the path given by an open file dialog for a symmetry group.
+    fn pick_group(&self) -> Option<PathBuf> {
+        None
+    }
+
+    /// Returns the path given by a save file dialog for a symmetry group.
+    fn save_group(&self, _name: &str) -> Option<PathBuf> {
+        None
+    }
 }
 
 /// The type of file dialog we're showing.
@@ -191,6 +440,47 @@ enum FileDialogMode {
 
     /// We're showing a file dialog to save a file.
     Save,
+
+    /// We're showing a file dialog to save a selection of elements.
+    SaveSelection,
+
+    /// We're showing a file dialog to export the flag graph of the polytope,
+    /// or the Hasse diagram of its face lattice.
+    SaveGraph {
+        /// Whether to export the Hasse diagram instead of the flag graph.
+        hasse: bool,
+    },
+
+    /// We're showing a file dialog to save a screenshot.
+    Screenshot,
+
+    /// We're showing a file dialog to save a turntable animation.
+    Turntable,
+
+    /// We're showing a file dialog to save the wireframe as a vector image.
+    SaveVector,
+
+    /// We're showing a file dialog to save the session.
+    SaveSession,
+
+    /// We're showing a file dialog to open a session.
+    OpenSession,
+
+    /// We're showing a file dialog to save the recorded pipeline.
+    SavePipeline,
+
+    /// We're showing a file dialog to open a pipeline.
+    OpenPipeline,
+
+    /// We're showing a file dialog to save the symmetry group of the polytope.
+    SaveGroup,
+
+    /// We're showing a file dialog to load a symmetry group.
+    OpenGroup,
+
+    /// We're showing a folder dialog to apply an operation to every file in
+    /// a folder.
+    BatchFolder,
 }
 
 /// The file dialog is disabled by default.
@@ -223,6 +513,82 @@ impl FileDialogState {
         self.name = Some(name);
     }
 
+    /// Changes the file dialog mode to [`FileDialogMode::SaveSelection`], and
+    /// loads the name of the file.
+    pub fn save_selection(&mut self, name: String) {
+        self.mode = FileDialogMode::SaveSelection;
+        self.name = Some(name);
+    }
+
+    /// Changes the file dialog mode to [`FileDialogMode::SaveGraph`], and
+    /// loads the name of the file.
+    pub fn save_graph(&mut self, name: String, hasse: bool) {
+        self.mode = FileDialogMode::SaveGraph { hasse };
+        self.name = Some(name);
+    }
+
+    /// Changes the file dialog mode to [`FileDialogMode::Screenshot`], and
+    /// loads the name of the file.
+    pub fn screenshot(&mut self, name: String) {
+        self.mode = FileDialogMode::Screenshot;
+        self.name = Some(name);
+    }
+
+    /// Changes the file dialog mode to [`FileDialogMode::SaveVector`], and
+    /// loads the name of the file.
+    pub fn save_vector(&mut self, name: String) {
+        self.mode = FileDialogMode::SaveVector;
+        self.name = Some(name);
+    }
+
+    /// Changes the file dialog mode to [`FileDialogMode::Turntable`], and
+    /// loads the name of the file.
+    pub fn turntable(&mut self, name: String) {
+        self.mode = FileDialogMode::Turntable;
+        self.name = Some(name);
+    }
+
+    /// Changes the file dialog mode to [`FileDialogMode::SaveSession`], and
+    /// loads the name of the file.
+    pub fn save_session(&mut self, name: String) {
+        self.mode = FileDialogMode::SaveSession;
+        self.name = Some(name);
+    }
+
+    /// Changes the file dialog mode to [`FileDialogMode::OpenSession`].
+    pub fn open_session(&mut self) {
+        self.mode = FileDialogMode::OpenSession;
+    }
+
+    /// Changes the file dialog mode to [`FileDialogMode::SavePipeline`], and
+    /// loads the name of the file.
+    pub fn save_pipeline(&mut self, name: String) {
+        self.mode = FileDialogMode::SavePipeline;
+        self.name = Some(name);
+    }
+
+    /// Changes the file dialog mode to [`FileDialogMode::OpenPipeline`].
+    pub fn open_pipeline(&mut self) {
+        self.mode = FileDialogMode::OpenPipeline;
+    }
+
+    /// Changes the file dialog mode to [`FileDialogMode::SaveGroup`], and
+    /// loads the name of the file.
+    pub fn save_group(&mut self, name: String) {
+        self.mode = FileDialogMode::SaveGroup;
+        self.name = Some(name);
+    }
+
+    /// Changes the file dialog mode to [`FileDialogMode::OpenGroup`].
+    pub fn open_group(&mut self) {
+        self.mode = FileDialogMode::OpenGroup;
+    }
+
+    /// Changes the file dialog mode to [`FileDialogMode::BatchFolder`].
+    pub fn batch_folder(&mut self) {
+        self.mode = FileDialogMode::BatchFolder;
+    }
+
     /// Gets the name of the file dialog.
     pub fn unwrap_name(&self) -> &str {
         self.name.as_ref().unwrap()
@@ -230,19 +596,79 @@ impl FileDialogState {
 }
 
 /// The system in charge of showing the file dialog.
+#[allow(clippy::too_many_arguments)]
 pub fn file_dialog(
     mut query: Query<'_, '_, &mut Concrete>,
+    schemes: Query<'_, '_, &ColorScheme>,
+    cameras: Query<'_, '_, &GlobalTransform, With<PerspectiveProjection>>,
     file_dialog_state: Res<'_, FileDialogState>,
     file_dialog: NonSend<'_, FileDialogToken>,
+    render_settings: Res<'_, RenderSettings>,
+    export_settings: Res<'_, ExportSettings>,
+    projection_type: Res<'_, ProjectionType>,
+    exploded_view: Res<'_, ExplodedView>,
+    background_color: Res<'_, ClearColor>,
+    mut toasts: ResMut<'_, Toasts>,
+    mut session_events: EventWriter<'_, '_, SessionEvent>,
+    mut pipeline_events: EventWriter<'_, '_, PipelineEvent>,
+    mut group_events: EventWriter<'_, '_, GroupEvent>,
+    mut batch_events: EventWriter<'_, '_, BatchEvent>,
 ) {
+    // The camera transform and the options for rendering the polytope.
+    let render_params = || {
+        cameras.iter().next().map(|camera| {
+            (
+                camera.compute_matrix(),
+                render_settings.options(*projection_type, background_color.0),
+            )
+        })
+    };
+
     if file_dialog_state.is_changed() {
         match file_dialog_state.mode {
             // We want to save a file.
             FileDialogMode::Save => {
                 if let Some(path) = file_dialog.save_file(file_dialog_state.unwrap_name()) {
                     if let Some(p) = query.iter_mut().next() {
-                        if let Err(err) = p.con().to_path(&path, Default::default()) {
-                            eprintln!("File saving failed: {}", err);
+                        // The color scheme is saved along with the polytope.
+                        let scheme = schemes.iter().next().copied().unwrap_or_default();
+                        let p = colors::with_scheme(p.con(), &scheme);
+
+                        if let Err(err) = p.to_path(&path, Default::default()) {
+                            toasts.error(MiratopeError::save(&path, err));
+                        }
+                    }
+                }
+            }
+
+            // We want to save some of the elements of the polytope.
+            FileDialogMode::SaveSelection => {
+                if let Some(path) = file_dialog.save_file(file_dialog_state.unwrap_name()) {
+                    if let Some(p) = query.iter_mut().next() {
+                        match export_settings.selection(&p) {
+                            Ok(q) => {
+                                if let Err(err) = q.to_path(&path, Default::default()) {
+                                    toasts.error(MiratopeError::save(&path, err));
+                                }
+                            }
+                            Err(err) => toasts.error(MiratopeError::operation("Selection", err)),
+                        }
+                    }
+                }
+            }
+
+            // We want to export the flag graph or the Hasse diagram.
+            FileDialogMode::SaveGraph { hasse } => {
+                if let Some(path) = file_dialog.save_graph(file_dialog_state.unwrap_name()) {
+                    if let Some(p) = query.iter_mut().next() {
+                        let graph = if hasse {
+                            Graph::hasse_diagram(&p.abs)
+                        } else {
+                            Graph::flag_graph(&p.abs)
+                        };
+
+                        if let Err(err) = graph.to_path(&path) {
+                            toasts.error(MiratopeError::save(&path, err));
                         }
                     }
                 }
@@ -256,13 +682,115 @@ pub fn file_dialog(
                             Ok(q) => {
                                 *p = q;
                                 p.recenter();
+
+                                if let Some(name) = p.recognize() {
+                                    toasts.info(format!("Recognized as {}.", name));
+                                }
                             }
-                            Err(err) => eprintln!("File open failed: {}", err),
+                            Err(err) => toasts.error(MiratopeError::load(&path, err)),
+                        }
+                    }
+                }
+            }
+
+            // We want to save a screenshot.
+            FileDialogMode::Screenshot => {
+                if let Some(path) = file_dialog.save_image(file_dialog_state.unwrap_name()) {
+                    if let (Some(p), Some((camera, options))) =
+                        (query.iter_mut().next(), render_params())
+                    {
+                        let p = exploded_view.apply(&p);
+                        match screenshot::save_screenshot(&p, camera, &options, &path) {
+                            Ok(()) => toasts.info("Screenshot saved!"),
+                            Err(err) => toasts.error(MiratopeError::save(&path, err)),
+                        }
+                    }
+                }
+            }
+
+            // We want to save a turntable animation.
+            FileDialogMode::Turntable => {
+                if let Some(path) = file_dialog.save_animation(file_dialog_state.unwrap_name()) {
+                    if let (Some(p), Some((camera, options))) =
+                        (query.iter_mut().next(), render_params())
+                    {
+                        let p = exploded_view.apply(&p);
+                        let frames = render_settings.frames;
+                        match screenshot::save_turntable(&p, camera, &options, frames, &path) {
+                            Ok(()) => toasts.info("Turntable saved!"),
+                            Err(err) => toasts.error(MiratopeError::save(&path, err)),
+                        }
+                    }
+                }
+            }
+
+            // We want to export the wireframe as a vector image.
+            FileDialogMode::SaveVector => {
+                if let Some(path) = file_dialog.save_vector(file_dialog_state.unwrap_name()) {
+                    if let (Some(p), Some(camera)) =
+                        (query.iter_mut().next(), cameras.iter().next())
+                    {
+                        let options =
+                            render_settings.vector_options(*projection_type, background_color.0);
+
+                        let p = exploded_view.apply(&p);
+                        match vector::save_vector(&p, camera.compute_matrix(), &options, &path) {
+                            Ok(()) => toasts.info("Vector image saved!"),
+                            Err(err) => toasts.error(MiratopeError::save(&path, err)),
                         }
                     }
                 }
             }
 
+            // We want to save the session.
+            FileDialogMode::SaveSession => {
+                if let Some(path) = file_dialog.save_session(file_dialog_state.unwrap_name()) {
+                    session_events.send(SessionEvent::Save(path));
+                }
+            }
+
+            // We want to open a session.
+            FileDialogMode::OpenSession => {
+                if let Some(path) = file_dialog.pick_session() {
+                    session_events.send(SessionEvent::Open(path));
+                }
+            }
+
+            // We want to save the recorded pipeline.
+            FileDialogMode::SavePipeline => {
+                if let Some(path) = file_dialog.save_pipeline(file_dialog_state.unwrap_name()) {
+                    pipeline_events.send(PipelineEvent::Save(path));
+                }
+            }
+
+            // We want to open a pipeline.
+            FileDialogMode::OpenPipeline => {
+                if let Some(path) = file_dialog.pick_pipeline() {
+                    pipeline_events.send(PipelineEvent::Open(path));
+                }
+            }
+
+            // We want to save the symmetry group of the polytope.
+            FileDialogMode::SaveGroup => {
+                if let Some(path) = file_dialog.save_group(file_dialog_state.unwrap_name()) {
+                    group_events.send(GroupEvent::Save(path));
+                }
+            }
+
+            // We want to load a symmetry group.
+            FileDialogMode::OpenGroup => {
+                if let Some(path) = file_dialog.pick_group() {
+                    group_events.send(GroupEvent::Open(path));
+                }
+            }
+
+            // We want to apply an operation to a folder.
+            FileDialogMode::BatchFolder => {
+                if let Some(path) = file_dialog.pick_folder() {
+                    batch_events.send(BatchEvent(path));
+                }
+            }
+
             // There's nothing to do with the file dialog this frame.
             FileDialogMode::Disabled => {}
         }
@@ -287,8 +815,19 @@ pub type EguiWindows<'a> = (
     ResMut<'a, DuocombWindow>,
     ResMut<'a, CompoundWindow>,
     ResMut<'a, TruncateWindow>,
+    ResMut<'a, CutWindow>,
     ResMut<'a, ScaleWindow>,
-    ResMut<'a, FacetingSettings>,
+    ResMut<'a, AugmentWindow>,
+    (
+        ResMut<'a, FacetingSettings>,
+        ResMut<'a, RenderSettings>,
+        ResMut<'a, ExportSettings>,
+        ResMut<'a, TransformWindow>,
+        ResMut<'a, ToleranceSettings>,
+        ResMut<'a, DitopeWindow>,
+        ResMut<'a, HosotopeWindow>,
+        ResMut<'a, OrbitCompoundWindow>,
+    ),
 );
 
 macro_rules! element_sort {
@@ -315,7 +854,49 @@ pub fn show_top_panel(
     mut memory: ResMut<'_, Memory>,
     mut show_memory: ResMut<'_, ShowMemory>,
     mut export_memory: ResMut<'_, ExportMemory>,
+    mut background_task: ResMut<'_, BackgroundTask>,
     mut background_color: ResMut<'_, ClearColor>,
+    (
+        mut section_explorer,
+        mut hasse_viewer,
+        mut toasts,
+        mut console,
+        mut gizmo,
+        mut recorder,
+        mut batch_window,
+        mut measure_tool,
+        selection,
+        mut exploded_view,
+        mut color_scheme_window,
+        mut camera_mode,
+        mut clipping_plane,
+        mut build_animation,
+        mut colorings_window,
+        loaded_group,
+    ): (
+        ResMut<'_, SectionExplorer>,
+        ResMut<'_, HasseViewer>,
+        ResMut<'_, Toasts>,
+        ResMut<'_, Console>,
+        ResMut<'_, TransformGizmo>,
+        ResMut<'_, Recorder>,
+        ResMut<'_, BatchWindow>,
+        ResMut<'_, MeasureTool>,
+        Res<'_, Selection>,
+        ResMut<'_, ExplodedView>,
+        ResMut<'_, ColorSchemeWindow>,
+        ResMut<'_, CameraMode>,
+        ResMut<'_, ClippingPlane>,
+        ResMut<'_, BuildAnimation>,
+        ResMut<'_, ColoringsWindow>,
+        Res<'_, LoadedGroup>,
+    ),
+    (mut petrie_overlay, mut kaleidoscope, mut generator_window, mut vertex_table): (
+        ResMut<'_, PetrieOverlay>,
+        ResMut<'_, KaleidoscopeOverlay>,
+        ResMut<'_, GeneratorWindow>,
+        ResMut<'_, VertexTable>,
+    ),
 
     mut visuals: ResMut<'_, egui::Visuals>,
 
@@ -332,8 +913,19 @@ pub fn show_top_panel(
         mut duocomb_window,
         mut compound_window,
         mut truncate_window,
+        mut cut_window,
         mut scale_window,
-        mut faceting_settings,
+        mut augment_window,
+        (
+            mut faceting_settings,
+            mut render_settings,
+            mut export_settings,
+            mut transform_window,
+            mut tolerance_settings,
+            mut ditope_window,
+            mut hosotope_window,
+            mut orbit_compound_window,
+        ),
     ): EguiWindows<'_>,
 ) {
     // The top bar.
@@ -357,6 +949,42 @@ pub fn show_top_panel(
                     export_memory.1 = 0;
                 }
 
+                // Saves only some of the elements of the polytope.
+                if ui.button("Export selection").clicked() {
+                    file_dialog_state.save_selection("selection".to_string());
+                }
+
+                // Saves the flag graph or the Hasse diagram of the polytope.
+                if ui.button("Export flag graph").clicked() {
+                    file_dialog_state.save_graph("flag_graph.dot".to_string(), false);
+                }
+
+                if ui.button("Export Hasse diagram").clicked() {
+                    file_dialog_state.save_graph("hasse_diagram.dot".to_string(), true);
+                }
+
+                if ui.button("Export settings...").clicked() {
+                    export_settings.open();
+                }
+
+                ui.separator();
+
+                // Saves or restores everything on screen.
+                if ui.button("Save session").clicked() {
+                    file_dialog_state.save_session("session.ron".to_string());
+                }
+
+                if ui.button("Open session").clicked() {
+                    file_dialog_state.open_session();
+                }
+
+                ui.separator();
+
+                // Applies an operation to every file in a folder.
+                if ui.button("Apply to folder...").clicked() {
+                    batch_window.open();
+                }
+
                 ui.separator();
 
                 // Quits the application.
@@ -400,6 +1028,216 @@ pub fn show_top_panel(
                         p.set_changed();
                     }
                 }
+
+                // Turns a 4D polytope so that an element faces the viewer.
+                ui.label("4D presets:");
+                for &(name, rank) in &PROJECTION_PRESETS {
+                    if ui.button(name).clicked() {
+                        if let Some(mut p) = query.iter_mut().next() {
+                            if p.dim_or() != 4 || rank >= p.rank() {
+                                toasts.error(MiratopeError::operation(
+                                    name,
+                                    "only 4D polytopes can be oriented",
+                                ));
+                            } else {
+                                // Points towards the viewer.
+                                let mut direction = Vector::zeros(4);
+                                direction[3] = -1.0;
+
+                                if !p.align_element(rank, 0, &direction) {
+                                    toasts.error(MiratopeError::failed(name));
+                                }
+                            }
+                        }
+                    }
+                }
+
+                // Draws 4D polytopes as Schlegel diagrams through one of their
+                // cells.
+                let mut schlegel = projection_type.schlegel_facet().is_some();
+                if ui.checkbox(&mut schlegel, "Schlegel diagram").clicked() {
+                    *projection_type = if schlegel {
+                        ProjectionType::Schlegel(0)
+                    } else {
+                        ProjectionType::Perspective
+                    };
+
+                    if let Some(mut p) = query.iter_mut().next() {
+                        p.set_changed();
+                    }
+                }
+
+                if let (Some(mut facet), Some(mut p)) =
+                    (projection_type.schlegel_facet(), query.iter_mut().next())
+                {
+                    let rank = p.rank();
+
+                    if rank >= 2 {
+                        // A single selected facet can be used as the cell.
+                        let selected = match selection.elements().iter().next() {
+                            Some(&idx)
+                                if selection.rank() + 1 == rank
+                                    && selection.elements().len() == 1 =>
+                            {
+                                Some(idx)
+                            }
+                            _ => None,
+                        };
+
+                        ui.horizontal(|ui| {
+                            ui.label("Cell:");
+                            let max = p.el_count(rank - 1).saturating_sub(1);
+                            let mut changed = ui
+                                .add(egui::DragValue::new(&mut facet).clamp_range(0..=max))
+                                .changed();
+
+                            if ui
+                                .add(egui::Button::new("Use selection").enabled(selected.is_some()))
+                                .clicked()
+                            {
+                                facet = selected.unwrap();
+                                changed = true;
+                            }
+
+                            if changed {
+                                *projection_type = ProjectionType::Schlegel(facet);
+                                p.set_changed();
+                            }
+                        });
+                    }
+                }
+
+                ui.separator();
+
+                // Draws the facets apart from each other.
+                ui.horizontal(|ui| {
+                    ui.label("Exploded view:");
+
+                    let mut factor = exploded_view.0;
+                    if ui.add(egui::Slider::new(&mut factor, 0.0..=2.0)).changed() {
+                        exploded_view.0 = factor;
+
+                        if let Some(mut p) = query.iter_mut().next() {
+                            p.set_changed();
+                        }
+                    }
+                });
+
+                // Reveals the polytope one facet at a time.
+                if ui.button("Build animation...").clicked() {
+                    build_animation.open();
+                }
+
+                ui.separator();
+
+                // Puts the camera at its anchor, so that it can move inside of
+                // the polytope.
+                let mut fly = *camera_mode == CameraMode::Fly;
+                if ui.checkbox(&mut fly, "Fly inside").changed() {
+                    *camera_mode = if fly {
+                        CameraMode::Fly
+                    } else {
+                        CameraMode::Orbit
+                    };
+                }
+
+                // Cuts away the part of the polytope in front of a plane. The
+                // polytope is redrawn whenever the plane changes.
+                ui.checkbox(&mut clipping_plane.enabled, "Clipping plane");
+
+                if clipping_plane.enabled {
+                    let old_normal = clipping_plane.plane.normal;
+                    let mut normal =
+                        Point::from_iterator(3, old_normal.to_array().iter().map(|&x| x as Float));
+                    ui.add(UnitPointWidget::new(&mut normal, "Normal"));
+
+                    let new_normal =
+                        Vec3::new(normal[0] as f32, normal[1] as f32, normal[2] as f32);
+                    if new_normal != old_normal {
+                        clipping_plane.plane.normal = new_normal;
+                    }
+
+                    ui.horizontal(|ui| {
+                        ui.add(egui::DragValue::new(&mut clipping_plane.plane.offset).speed(0.01));
+                        ui.label("Offset");
+                    });
+                }
+
+                ui.separator();
+
+                // Highlights the Petrie polygons on the wireframe.
+                if let Some(mode) = petrie_overlay.menu(ui) {
+                    petrie_overlay.set_mode(mode);
+                }
+
+                // Draws the mirrors of the symmetry group on the circumsphere.
+                if let Some(shown) = kaleidoscope.menu(ui) {
+                    kaleidoscope.set_shown(shown);
+                }
+
+                ui.separator();
+
+                // Opens the console, to build polytopes from expressions.
+                if ui.button("Console...").clicked() {
+                    console.open();
+                }
+
+                // Opens the recorder, to replay operations on other polytopes.
+                if ui.button("Recorder...").clicked() {
+                    recorder.open();
+                }
+
+                // Opens the measurement tool, to measure distances and angles.
+                if ui.button("Measure...").clicked() {
+                    measure_tool.open();
+                }
+
+                // Opens the generator point window, to build Wythoffians from
+                // any point in the fundamental region.
+                if ui.button("Generator point...").clicked() {
+                    generator_window.open();
+                }
+
+                // Opens the vertex table, to read and edit the coordinates.
+                if ui.button("Vertices...").clicked() {
+                    vertex_table.open();
+                }
+
+                // Sets the colors of the faces, edges and vertices.
+                if ui.button("Colors...").clicked() {
+                    color_scheme_window.open();
+                }
+
+                ui.separator();
+
+                // Sets the tolerance used when comparing floating-point values.
+                if ui.button("Tolerance...").clicked() {
+                    tolerance_settings.open();
+                }
+            });
+
+            // Renders the polytope into images.
+            menu::menu(ui, "Render", |ui| {
+                // Saves the current view as a PNG file.
+                if ui.button("Screenshot").clicked() {
+                    file_dialog_state.screenshot("polytope.png".to_string());
+                }
+
+                // Saves a full turn of the polytope as an animated GIF.
+                if ui.button("Turntable").clicked() {
+                    file_dialog_state.turntable("polytope.gif".to_string());
+                }
+
+                // Saves the wireframe as an SVG or PostScript file.
+                if ui.button("Vector image").clicked() {
+                    file_dialog_state.save_vector("polytope.svg".to_string());
+                }
+
+                ui.separator();
+
+                if ui.button("Settings...").clicked() {
+                    render_settings.open();
+                }
             });
 
             // Prints out properties about the loaded polytope.
@@ -431,15 +1269,57 @@ pub fn show_top_panel(
                     }
                 }
 
+                // Determines the surface of a polyhedron.
+                if ui.button("Surface").clicked() {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        element_sort!(p);
+
+                        match p.surface() {
+                            Some(surface) => println!("The surface is {}.", surface),
+                            None => println!("The polytope isn't a connected polyhedron."),
+                        }
+                    }
+                }
+
                 // Gets the volume of the polytope.
                 if ui.button("Volume").clicked() {
                     if let Some(mut p) = query.iter_mut().next() {
                         element_sort!(p);
 
-                        if let Some(vol) = p.volume() {
-                            println!("The volume is {}.", vol);
-                        } else {
-                            println!("The polytope has no volume.");
+                        println!("The volume is {}.", p.volume());
+                    }
+                }
+
+                // Gets the density of the polytope around its gravicenter.
+                if ui.button("Density").clicked() {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        element_sort!(p);
+
+                        match p.central_density() {
+                            Some(density) => println!("The density is {}.", density),
+                            None => println!("The polytope has no density."),
+                        }
+                    }
+                }
+
+                // Gets the Euler characteristic of the polytope.
+                if ui.button("Euler characteristic").clicked() {
+                    if let Some(p) = query.iter_mut().next() {
+                        println!(
+                            "The Euler characteristic is {}.",
+                            p.euler_characteristic()
+                        );
+                    }
+                }
+
+                // Gets the centroid of the polytope.
+                if ui.button("Centroid").clicked() {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        element_sort!(p);
+
+                        match p.centroid() {
+                            Some(centroid) => println!("The centroid is {}.", centroid),
+                            None => println!("The polytope has no centroid."),
                         }
                     }
                 }
@@ -447,15 +1327,95 @@ pub fn show_top_panel(
                 // Gets the number of flags of the polytope.
                 if ui.button("Flag count").clicked() {
                     if let Some(p) = query.iter_mut().next() {
-                        println!("The polytope has {} flags.", p.flags().count())
+                        println!("The polytope has {} flags.", p.abs.flag_count())
+                    }
+                }
+
+                // Gets the length of the Petrie polygons of the polytope.
+                if ui.button("Petrie polygon length").clicked() {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        element_sort!(p);
+
+                        match p.petrie_polygon_length() {
+                            Some(len) => println!("The Petrie polygons have {} vertices.", len),
+                            None => println!("The Petrie polygon is degenerate."),
+                        }
+                    }
+                }
+
+                // Recognizes the polytope.
+                if ui.button("Recognize").clicked() {
+                    if let Some(p) = query.iter_mut().next() {
+                        match p.schlafli_symbol() {
+                            Some(symbol) => match (symbol.name(), symbol.acronym()) {
+                                (Some(name), Some(acronym)) => println!(
+                                    "The polytope is the {} ({}) {}.",
+                                    name, acronym, symbol
+                                ),
+                                (Some(name), None) => {
+                                    println!("The polytope is the {} {}.", name, symbol)
+                                }
+                                _ => println!("The polytope is the regular {}.", symbol),
+                            },
+                            None => println!("The polytope isn't regular."),
+                        }
                     }
                 }
 
-                // Gets the order of the symmetry group of the polytope
+                // Browses through the sections of the polytope.
+                if ui.button("Sections...").clicked() {
+                    section_explorer.open();
+                }
+
+                // Shows the face lattice of the polytope.
+                if ui.button("Hasse diagram...").clicked() {
+                    hasse_viewer.open();
+                }
+
+                // Gets the order of the symmetry group of the polytope, and
+                // recognizes it if it's a reflection group.
                 if ui.button("Symmetry group").clicked() {
                     if let Some(mut p) = query.iter_mut().next() {
                         let group = p.get_symmetry_group().0;
-                        println!("Symmetry order {}", group.count());
+                        match group.clone().identify() {
+                            Some(label) => println!("{}", label),
+                            None => println!("Symmetry order {}", group.count()),
+                        }
+                    }
+                }
+
+                // Saves the symmetry group of the polytope, so that it doesn't
+                // need to be computed again.
+                if ui.button("Save symmetry group...").clicked() {
+                    file_dialog_state.save_group("group.ron".to_string());
+                }
+
+                // Loads a symmetry group that was saved before.
+                if ui.button("Load symmetry group...").clicked() {
+                    file_dialog_state.open_group();
+                }
+
+                // Reports which elements the symmetries of the polytope act
+                // transitively on.
+                if ui.button("Transitivity").clicked() {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        println!("{}", p.transitivity_report());
+                    }
+                }
+
+                // Counts the colorings of the elements up to symmetry.
+                if ui.button("Colorings...").clicked() {
+                    colorings_window.open();
+                }
+
+                // Reports the edge lengths, circumradius and coordinates of
+                // the polytope as exact values, and copies them to the
+                // clipboard.
+                if ui.button("Exact values").clicked() {
+                    if let Some(p) = query.iter_mut().next() {
+                        let report = p.exact_report().to_string();
+                        println!("{}", report);
+                        ui.output().copied_text = report;
                     }
                 }
             });
@@ -463,18 +1423,31 @@ pub fn show_top_panel(
             menu::menu(ui, "Transform", |ui| {
             
                 if ui.button("Scale to unit edge length").clicked() {
-                    let mut p = query.iter_mut().next().unwrap();
-                    let e_l = (&p.vertices[p.abs[2][0].subs[0]] - &p.vertices[p.abs[2][0].subs[1]]).norm();
-                    p.scale(1.0/e_l);
+                    if let Some(mut p) = query.iter_mut().next() {
+                        match p.abs.get_element(2, 0) {
+                            Some(edge) => {
+                                let e_l = (&p.vertices[edge.subs[0]] - &p.vertices[edge.subs[1]]).norm();
+                                p.scale(1.0/e_l);
+                            }
+                            None => toasts.error(MiratopeError::operation(
+                                "Scaling",
+                                "the polytope has no edges",
+                            )),
+                        }
+                    }
                 }
 
                 if ui.button("Scale to unit circumradius").clicked() {
-                    let mut p = query.iter_mut().next().unwrap();
-                    match p.circumsphere() {
-                        Some(sphere) => {
-                            p.scale(1.0/sphere.radius());
+                    if let Some(mut p) = query.iter_mut().next() {
+                        match p.circumsphere() {
+                            Some(sphere) => {
+                                p.scale(1.0/sphere.radius());
+                            }
+                            None => toasts.error(MiratopeError::operation(
+                                "Scaling",
+                                "the polytope has no circumsphere",
+                            )),
                         }
-                        None => println!("The polytope has no circumsphere."),
                     }
                 }
 
@@ -482,23 +1455,114 @@ pub fn show_top_panel(
                 if ui.button("Scale...").clicked() {
                     scale_window.open();
                 }
+
+                // Opens a window to translate, rotate, reflect, or scale a
+                // polytope by exact amounts.
+                if ui.button("Transform...").clicked() {
+                    transform_window.open();
+                }
+
+                // Shows a pad to move or rotate the polytope with the mouse.
+                ui.checkbox(&mut gizmo.open, "Transform gizmo");
                 
                 ui.separator();
 
                 // Moves a polytope so that the circumcenter is at the origin.
                 if ui.button("Recenter by circumcenter").clicked() {
-                    let mut p = query.iter_mut().next().unwrap();
-                    match p.circumsphere() {
-                        Some(sphere) => {
-                            p.recenter_with(&sphere.center);
+                    if let Some(mut p) = query.iter_mut().next() {
+                        if !p.recenter_by(Center::Circumcenter) {
+                            toasts.error(MiratopeError::operation(
+                                "Recentering",
+                                "the polytope has no circumsphere",
+                            ));
+                        }
+                    }
+                }
+
+                // Moves a polytope so that the centroid is at the origin.
+                if ui.button("Recenter by centroid").clicked() {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        if !p.recenter_by(Center::Centroid) {
+                            toasts.error(MiratopeError::operation(
+                                "Recentering",
+                                "the polytope has no centroid",
+                            ));
                         }
-                        None => println!("The polytope has no circumsphere."),
                     }
                 }
                 
                 // Moves a polytope so that the gravicenter is at the origin.
                 if ui.button("Recenter by gravicenter").clicked() {
-                    query.iter_mut().next().unwrap().recenter();
+                    if let Some(mut p) = query.iter_mut().next() {
+                        p.recenter();
+                        recorder.record("recenter(p)");
+                    }
+                }
+
+                // Rescales a polytope to unit circumradius, and moves its
+                // centroid to the origin.
+                if ui.button("Normalize").clicked() {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        if p.normalize() {
+                            recorder.record("normalize(p)");
+                        } else {
+                            toasts.error(MiratopeError::operation(
+                                "Normalizing",
+                                "the polytope has no size",
+                            ));
+                        }
+                    }
+                }
+
+                // Rescales a polytope to unit edge length, and moves its
+                // circumcenter to the origin.
+                if ui.button("Normalize to unit edge").clicked() {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        if !p.normalize_with(Center::Circumcenter, Scale::EdgeLength) {
+                            toasts.error(MiratopeError::operation(
+                                "Normalizing",
+                                "the polytope has no circumsphere or no edges",
+                            ));
+                        }
+                    }
+                }
+
+                ui.separator();
+
+                // Merges coincident vertices and removes degenerate elements.
+                if ui.button("Fuse vertices").clicked() {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        let removed = p.fuse_vertices(f64::eps());
+                        recorder.record("fuse(p)");
+                        println!("Fused vertices: {} removed.", removed);
+                    }
+                }
+
+                // Snaps the vertices to the symmetry group of the polytope.
+                if ui.button("Symmetrize").clicked() {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        if let Err(err) = p.symmetrize() {
+                            toasts.error(MiratopeError::operation("Symmetrizing", err));
+                        }
+                    }
+                }
+
+                // Snaps the vertices to the loaded symmetry group instead.
+                if let Some(group) = loaded_group.get() {
+                    if ui.button("Symmetrize to loaded group").clicked() {
+                        if let Some(mut p) = query.iter_mut().next() {
+                            let res = match group.group() {
+                                Ok(group) => {
+                                    p.symmetrize_with(group).map_err(|err| err.to_string())
+                                }
+                                Err(err) => Err(err.to_string()),
+                            };
+
+                            if let Err(err) = res {
+                                toasts.error(MiratopeError::operation("Symmetrizing", err));
+                            }
+                        }
+                    }
                 }
             });
 
@@ -512,21 +1576,39 @@ pub fn show_top_panel(
                 } else if let Some(mut p) = query.iter_mut().next() {
                     if ui.button("Dual").clicked() {
                         match p.try_dual_mut() {
-                            Ok(_) => println!("Dual succeeded."),
-                            Err(err) => eprintln!("Dual failed: {}", err),
+                            Ok(_) => {
+                                recorder.record("dual(p)");
+                                println!("Dual succeeded.");
+                            }
+                            Err(err) => toasts.error(MiratopeError::operation("Dual", err)),
                         }
                     }
                 }
 
                 ui.separator();
 
+                // Identifies the antipodal elements of the active polytope.
+                if ui.button("Antipodal quotient").clicked() {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        match p.antipodal_quotient() {
+                            Some(quotient) => {
+                                *p = quotient;
+                                recorder.record("antipodal(p)");
+                                println!("Antipodal quotient succeeded.");
+                            }
+                            None => toasts.error(MiratopeError::failed("Antipodal quotient")),
+                        }
+                    }
+                }
+
                 // Converts the active polytope into its Petrial.
                 if ui.button("Petrial").clicked() {
                     if let Some(mut p) = query.iter_mut().next() {
                         if p.petrial_mut() {
+                            recorder.record("petrial(p)");
                             println!("Petrial succeeded.");
                         } else {
-                            eprintln!("Petrial failed.");
+                            toasts.error(MiratopeError::failed("Petrial"));
                         }
                     }
                 }
@@ -541,7 +1623,7 @@ pub fn show_top_panel(
                                 *p = q;
                                 println!("Petrie polygon succeeded.")
                             }
-                            None => eprintln!("Petrie polygon failed."),
+                            None => toasts.error(MiratopeError::failed("Petrie polygon")),
                         }
                     }
                 }
@@ -556,6 +1638,7 @@ pub fn show_top_panel(
                 } else if let Some(mut p) = query.iter_mut().next() {
                     if ui.button("Pyramid").clicked() {
                         *p = p.pyramid();
+                        recorder.record("pyramid(p)");
                     }
                 }
 
@@ -567,6 +1650,7 @@ pub fn show_top_panel(
                 } else if let Some(mut p) = query.iter_mut().next() {
                     if ui.button("Prism").clicked() {
                         *p = p.prism();
+                        recorder.record("prism(p)");
                     }
                 }
 
@@ -578,6 +1662,7 @@ pub fn show_top_panel(
                 } else if let Some(mut p) = query.iter_mut().next() {
                     if ui.button("Tegum").clicked() {
                         *p = p.tegum();
+                        recorder.record("tegum(p)");
                     }
                 }
 
@@ -589,25 +1674,142 @@ pub fn show_top_panel(
                 } else if let Some(mut p) = query.iter_mut().next() {
                     if ui.button("Antiprism").clicked() {
                         match p.try_antiprism() {
-                            Ok(q) => *p = q,
-                            Err(err) => eprintln!("Antiprism failed: {}", err),
+                            Ok(q) => {
+                                *p = q;
+                                recorder.record("antiprism(p)");
+                            }
+                            Err(err) => toasts.error(MiratopeError::operation("Antiprism", err)),
                         }
                     }
                 }
 
-                // Converts the active polytope into its ditope.
-                if ui.button("Ditope").clicked() {
+                // Converts the active polytope into its antitegum.
+                if ui.button("Antitegum").clicked() {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        match p.try_antitegum() {
+                            Ok(q) => {
+                                *p = q;
+                                recorder.record("antitegum(p)");
+                            }
+                            Err(err) => toasts.error(MiratopeError::operation("Antitegum", err)),
+                        }
+                    }
+                }
+
+                // Attaches a prism onto the base of the active polytope.
+                if ui.button("Elongate").clicked() {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        match p.base_facet().and_then(|facet| p.elongate(facet)) {
+                            Some(q) => {
+                                *p = q;
+                                println!("Elongation succeeded.")
+                            }
+                            None => toasts.error(MiratopeError::failed("Elongation")),
+                        }
+                    }
+                }
+
+                // Attaches an antiprism onto the base of the active polytope.
+                if ui.button("Gyroelongate").clicked() {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        match p.base_facet().and_then(|facet| p.gyroelongate(facet)) {
+                            Some(q) => {
+                                *p = q;
+                                println!("Gyroelongation succeeded.")
+                            }
+                            None => toasts.error(MiratopeError::failed("Gyroelongation")),
+                        }
+                    }
+                }
+
+                // Replaces the active polytope by the convex hull of its
+                // vertices, as for point clouds loaded from a list of
+                // coordinates. This can take a while, so it runs in the
+                // background.
+                if ui
+                    .add(egui::Button::new("Convex hull").enabled(!background_task.is_running()))
+                    .clicked()
+                {
+                    if let Some(p) = query.iter_mut().next() {
+                        let vertices = p.vertices.clone();
+                        recorder.record("hull(p)");
+
+                        background_task.spawn("Convex hull", move |_| {
+                            match Concrete::convex_hull(&vertices) {
+                                Some(q) => TaskOutput::Polytope(q),
+                                None => TaskOutput::Error("there are no points".to_string()),
+                            }
+                        });
+                    }
+                }
+
+                // Replaces the active polytope by the Delaunay subdivision of
+                // its vertices. This also runs in the background.
+                if ui
+                    .add(egui::Button::new("Delaunay").enabled(!background_task.is_running()))
+                    .clicked()
+                {
+                    if let Some(p) = query.iter_mut().next() {
+                        let vertices = p.vertices.clone();
+                        recorder.record("delaunay(p)");
+
+                        background_task.spawn("Delaunay", move |_| {
+                            match Concrete::delaunay(&vertices) {
+                                Some(q) => TaskOutput::Polytope(q),
+                                None => TaskOutput::Error("there are no points".to_string()),
+                            }
+                        });
+                    }
+                }
+
+                // Converts the active polytope into its omnitruncate. This can
+                // take a while, so it runs in the background.
+                if ui
+                    .add(egui::Button::new("Omnitruncate").enabled(!background_task.is_running()))
+                    .clicked()
+                {
+                    if let Some(p) = query.iter_mut().next() {
+                        let mut p = p.clone();
+                        recorder.record("omnitruncate(p)");
+
+                        background_task.spawn("Omnitruncate", move |progress| {
+                            element_sort!(p);
+                            let omni = p.omnitruncate_with(|done, total| {
+                                progress.set(done, total);
+                                !progress.is_cancelled()
+                            });
+
+                            match omni {
+                                Some(q) => TaskOutput::Polytope(q),
+                                None => TaskOutput::Error("stopped".to_string()),
+                            }
+                        });
+                    }
+                }
+
+                // Converts the active polytope into its ditope, or into an
+                // iterated ditope of a given rank.
+                if advanced(&keyboard) {
+                    if ui.button("Ditope...").clicked() {
+                        ditope_window.open();
+                    }
+                } else if ui.button("Ditope").clicked() {
                     if let Some(mut p) = query.iter_mut().next() {
                         p.ditope_mut();
-                        println!("Ditope succeeded!");
+                        recorder.record("ditope(p)");
                     }
                 }
 
-                // Converts the active polytope into its hosotope.
-                if ui.button("Hosotope").clicked() {
+                // Converts the active polytope into its hosotope, or into an
+                // iterated hosotope of a given rank.
+                if advanced(&keyboard) {
+                    if ui.button("Hosotope...").clicked() {
+                        hosotope_window.open();
+                    }
+                } else if ui.button("Hosotope").clicked() {
                     if let Some(mut p) = query.iter_mut().next() {
                         p.hosotope_mut();
-                        println!("Hosotope succeeded!");
+                        recorder.record("hosotope(p)");
                     }
                 }
                 
@@ -638,11 +1840,43 @@ pub fn show_top_panel(
                     compound_window.open();
                 }
 
+                // Opens the window to copy a polytope around by the
+                // symmetries of another.
+                if ui.button("Orbit compound...").clicked() {
+                    orbit_compound_window.open();
+                }
+
+                // Splits a compound into its components, and stores each of
+                // them in a new memory slot.
+                if ui.button("Explode compound").clicked() {
+                    if let Some(p) = query.iter_mut().next() {
+                        let components = p.components();
+                        let count = components.len();
+
+                        for (idx, component) in components.into_iter().enumerate() {
+                            memory.push((component, Some(format!("component {}", idx + 1))));
+                        }
+
+                        show_memory.0 = true;
+                        println!("Exploded into {} components.", count);
+                    }
+                }
+
                 ui.separator();
 
                 if ui.button("Truncate...").clicked() {
                     truncate_window.open();
                 }
+
+                // Opens the window to cut the polytope with a hyperplane.
+                if ui.button("Cut...").clicked() {
+                    cut_window.open();
+                }
+
+                // Opens the window to augment, diminish, or gyrate.
+                if ui.button("Augment...").clicked() {
+                    augment_window.open();
+                }
             });
 
             // Toggles cross-section mode.
@@ -674,28 +1908,82 @@ pub fn show_top_panel(
                         section_state.open(original_polytope, vec![minmax]);
 						section_direction.clear();
                         section_direction.push(SectionDirection{0:direction});
+
+                        // If an element is selected, we slice through it.
+                        if let Some(&idx) = selection.elements().iter().next() {
+                            if let Some(direction) =
+                                section_state.slice_through(selection.rank(), idx)
+                            {
+                                section_direction[0].0 = direction;
+                            }
+                        }
                     }
                 };
             }
 
             menu::menu(ui, "Faceting", |ui| {
-                if ui.button("Enumerate facetings").clicked() {
-                    if let Some(mut p) = query.iter_mut().next() {
-                        let facetings = p.faceting(
-                            GroupEnum::Chiral(faceting_settings.chiral), 
-                            if faceting_settings.unit_edges {Some(1.0)} else {None}, 
-                            if faceting_settings.max_facet_types == 0 {None} else {Some(faceting_settings.max_facet_types)},
-                            if faceting_settings.max_per_hyperplane == 0 {None} else {Some(faceting_settings.max_per_hyperplane)},
-                            faceting_settings.compounds,
-                            faceting_settings.save,
-                            faceting_settings.save_facets
-                        );
-                        for faceting in facetings {
-                            memory.push(faceting);
-                        }
+                if ui
+                    .add(egui::Button::new("Enumerate facetings").enabled(!background_task.is_running()))
+                    .clicked()
+                {
+                    if let Some(p) = query.iter_mut().next() {
+                        let mut p = p.clone();
+                        let chiral = faceting_settings.chiral;
+                        let edge_length = if faceting_settings.unit_edges {Some(1.0)} else {None};
+                        let max_facet_types = if faceting_settings.max_facet_types == 0 {None} else {Some(faceting_settings.max_facet_types)};
+                        let max_per_hyperplane = if faceting_settings.max_per_hyperplane == 0 {None} else {Some(faceting_settings.max_per_hyperplane)};
+                        let compounds = faceting_settings.compounds;
+                        let save = faceting_settings.save;
+                        let save_facets = faceting_settings.save_facets;
+
+                        // Faceting can take minutes, so it runs in the background.
+                        background_task.spawn("Faceting", move |progress| {
+                            TaskOutput::Memory(p.faceting(
+                                GroupEnum::Chiral(chiral),
+                                edge_length,
+                                max_facet_types,
+                                max_per_hyperplane,
+                                compounds,
+                                save,
+                                save_facets,
+                                |done, total| {
+                                    progress.set(done, total);
+                                    !progress.is_cancelled()
+                                },
+                            ))
+                        });
                     }
                 }
-                
+
+                if ui
+                    .add(egui::Button::new("Search noble polytopes").enabled(!background_task.is_running()))
+                    .clicked()
+                {
+                    if let Some(p) = query.iter_mut().next() {
+                        let mut p = p.clone();
+                        let chiral = faceting_settings.chiral;
+                        let edge_length = if faceting_settings.unit_edges {Some(1.0)} else {None};
+                        let max_per_hyperplane = if faceting_settings.max_per_hyperplane == 0 {None} else {Some(faceting_settings.max_per_hyperplane)};
+
+                        background_task.spawn("Noble search", move |progress| {
+                            match p.noble_facetings(
+                                GroupEnum::Chiral(chiral),
+                                edge_length,
+                                max_per_hyperplane,
+                                |done, total| {
+                                    progress.set(done, total);
+                                    !progress.is_cancelled()
+                                },
+                            ) {
+                                Some(nobles) => TaskOutput::Memory(nobles),
+                                None => TaskOutput::Error(
+                                    "the vertices don't form a single orbit".to_string(),
+                                ),
+                            }
+                        });
+                    }
+                }
+
                 ui.separator();
 
                 if ui.button("Settings...").clicked() {
@@ -756,6 +2044,8 @@ fn show_views(
         hyperplane_pos,
         flatten,
         lock,
+        ghost,
+        thickness,
         ..
     } = (*section_state).clone()
     {
@@ -810,6 +2100,38 @@ fn show_views(
 			i = i + 1;
 		}
 
+        // Thickens the last slice into a slab. Slabs aren't flattened, since
+        // they have the same rank as the polytope.
+        let (min, max) = minmax.last().copied().unwrap_or_default();
+        let mut new_thickness = thickness;
+        ui.add(
+            egui::Slider::new(&mut new_thickness, 0.0..=(max - min).max(0.0))
+                .text("Slab thickness"),
+        );
+
+        #[allow(clippy::float_cmp)]
+        if thickness != new_thickness {
+            if let SectionState::Active { thickness, .. } = section_state.as_mut() {
+                *thickness = new_thickness;
+            } else {
+                unreachable!()
+            }
+        }
+
+        // Slices through the center of an element of the original polytope.
+        // For uniform polytopes, any element of a given rank will do.
+        ui.horizontal(|ui| {
+            ui.label("Slice through:");
+
+            for &(name, rank) in &SLICE_PRESETS {
+                if ui.button(name).clicked() {
+                    if let Some(direction) = section_state.slice_through(rank, 0) {
+                        section_direction[0].0 = direction;
+                    }
+                }
+            }
+        });
+
         ui.horizontal(|ui| {
             // Makes the current cross-section into the main polytope.
             if ui.button("Make main").clicked() {
@@ -840,10 +2162,12 @@ fn show_views(
             let mut new_flatten = flatten;
             ui.add(egui::Checkbox::new(&mut new_flatten, "Flatten"));
 
-            // Updates the flattening setting.
+            // Updates the flattening setting. A flattened cross-section can't
+            // be drawn along with the ghost.
             if flatten != new_flatten {
-                if let SectionState::Active { flatten, .. } = section_state.as_mut() {
+                if let SectionState::Active { flatten, ghost, .. } = section_state.as_mut() {
                     *flatten = new_flatten;
+                    *ghost &= !new_flatten;
                 } else {
                     unreachable!()
                 }
@@ -860,6 +2184,21 @@ fn show_views(
                     unreachable!()
                 }
             }
+
+            let mut new_ghost = ghost;
+            ui.add(egui::Checkbox::new(&mut new_ghost, "Ghost"))
+                .on_hover_text("Draws the original polytope around the cross-section.");
+
+            // Updates the ghost setting, unflattening the cross-section so
+            // that it lines up with the original polytope.
+            if ghost != new_ghost {
+                if let SectionState::Active { flatten, ghost, .. } = section_state.as_mut() {
+                    *ghost = new_ghost;
+                    *flatten &= !new_ghost;
+                } else {
+                    unreachable!()
+                }
+            }
         });
     }
 
@@ -883,6 +2222,8 @@ fn show_views(
             minmax,
             flatten,
             lock,
+            thickness,
+            ..
         } = section_state.as_mut()
         {
             // We don't update the view if it's locked.
@@ -902,17 +2243,23 @@ fn show_views(
 							.minmax(section_direction[i].0.clone())
 							.unwrap_or((-1.0, 1.0));
 
-						minmax[i].0 += f64::EPS;
-						let mut slice = r.cross_section(&hyperplane);
+						minmax[i].0 += f64::eps();
 
-						if *flatten {
-							slice.flatten_into(&hyperplane.subspace);
-							slice.recenter_with(
-								&hyperplane.flatten(&hyperplane.project(&Point::zeros(dim))),
-							);
-						}
+						// The last slice might be a slab instead.
+						if *thickness > 0.0 && i + 1 == hyperplane_pos.len() {
+							r = r.slab(&hyperplane, *thickness);
+						} else {
+							let mut slice = r.cross_section(&hyperplane);
+
+							if *flatten {
+								slice.project_onto(&hyperplane.subspace, Coincidence::Preserve);
+								slice.recenter_with(
+									&hyperplane.flatten(&hyperplane.project(&Point::zeros(dim))),
+								);
+							}
 
-						r = slice;
+							r = slice;
+						}
 					}
 					i = i + 1;
 				}
@@ -0,0 +1,221 @@
+//! Contains the vertex table, a window listing the coordinates of the vertices
+//! of the polytope, where they can also be edited.
+//!
+//! Any edit moves the vertex right away, and the mesh is rebuilt along with
+//! it. Lists of coordinates can also be pasted into the window, and either
+//! they, their convex hull or their Delaunay subdivision replace the polytope.
+
+use super::toast::Toasts;
+use crate::{Concrete, Point};
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+use miratope_core::{error::MiratopeError, file::csv::parse_points};
+
+/// The number of vertices shown at once.
+const PAGE_SIZE: usize = 100;
+
+/// The plugin in charge of the vertex table.
+pub struct VertexTablePlugin;
+
+impl Plugin for VertexTablePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<VertexTable>()
+            .add_system(show_vertex_table.system().after("show_top_panel"));
+    }
+}
+
+/// The state of the vertex table.
+#[derive(Default)]
+pub struct VertexTable {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The index of the page of vertices that's shown.
+    page: usize,
+
+    /// The coordinates pasted by the user, one point per line.
+    pasted: String,
+}
+
+impl VertexTable {
+    /// Opens the window.
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+}
+
+/// What's built from a pasted list of points.
+#[derive(Clone, Copy)]
+enum PasteAction {
+    /// The points themselves, with no other elements.
+    Points,
+
+    /// The convex hull of the points.
+    Hull,
+
+    /// The Delaunay subdivision of the points.
+    Delaunay,
+}
+
+impl PasteAction {
+    /// The name of the action.
+    fn name(self) -> &'static str {
+        match self {
+            Self::Points => "Points",
+            Self::Hull => "Convex hull",
+            Self::Delaunay => "Delaunay",
+        }
+    }
+
+    /// Builds a polytope from a list of points, or returns `None` if there's
+    /// no points.
+    fn build(self, points: Vec<Point>) -> Option<Concrete> {
+        match self {
+            Self::Points if points.is_empty() => None,
+            Self::Points => Some(Concrete::point_cloud(points)),
+            Self::Hull => Concrete::convex_hull(&points),
+            Self::Delaunay => Concrete::delaunay(&points),
+        }
+    }
+}
+
+/// Writes the vertices of a polytope, one per line, with their coordinates
+/// separated by commas.
+fn vertex_list(poly: &Concrete) -> String {
+    poly.vertices
+        .iter()
+        .map(|v| {
+            v.iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The system that shows the vertex table, and applies the edits to the
+/// polytope.
+fn show_vertex_table(
+    egui_ctx: Res<'_, EguiContext>,
+    mut query: Query<'_, '_, &mut Concrete>,
+    mut table: ResMut<'_, VertexTable>,
+    mut toasts: ResMut<'_, Toasts>,
+) {
+    if !table.open {
+        return;
+    }
+
+    let mut p = match query.iter_mut().next() {
+        Some(p) => p,
+        None => return,
+    };
+
+    let mut open = true;
+    let mut edits = Vec::new();
+    let mut action = None;
+
+    // The polytope is only borrowed mutably once there's an edit, so that it
+    // isn't rebuilt every frame.
+    let poly: &Concrete = &p;
+    let count = poly.vertices.len();
+    let pages = if count == 0 {
+        0
+    } else {
+        (count - 1) / PAGE_SIZE + 1
+    };
+
+    egui::Window::new("Vertices")
+        .open(&mut open)
+        .default_width(360.0)
+        .show(egui_ctx.ctx(), |ui| {
+            ui.horizontal(|ui| {
+                ui.label(format!("{} vertices", count));
+
+                if pages > 1 {
+                    ui.add(
+                        egui::DragValue::new(&mut table.page)
+                            .speed(0.05)
+                            .clamp_range(0..=pages - 1),
+                    );
+                    ui.label(format!("of {} pages", pages));
+                }
+
+                if ui.button("Copy").clicked() {
+                    ui.output().copied_text = vertex_list(poly);
+                }
+            });
+
+            table.page = table.page.min(pages.saturating_sub(1));
+            let start = table.page * PAGE_SIZE;
+            let end = (start + PAGE_SIZE).min(count);
+
+            egui::ScrollArea::auto_sized().show(ui, |ui| {
+                egui::Grid::new("vertex_table")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for idx in start..end {
+                            ui.label(idx.to_string());
+
+                            for (coord, x) in poly.vertices[idx].iter().enumerate() {
+                                let mut x = *x;
+                                if ui.add(egui::DragValue::new(&mut x).speed(0.01)).changed() {
+                                    edits.push((idx, coord, x));
+                                }
+                            }
+
+                            ui.end_row();
+                        }
+                    });
+            });
+
+            ui.separator();
+
+            ui.label("Paste a list of points, one per line:");
+            ui.add(
+                egui::TextEdit::multiline(&mut table.pasted)
+                    .code_editor()
+                    .desired_rows(4)
+                    .hint_text("1, 0, 0\n0, 1, 0\n0, 0, 1"),
+            );
+            ui.horizontal(|ui| {
+                for paste_action in [
+                    PasteAction::Points,
+                    PasteAction::Hull,
+                    PasteAction::Delaunay,
+                ] {
+                    if ui.button(paste_action.name()).clicked() {
+                        action = Some(paste_action);
+                    }
+                }
+            });
+        });
+
+    for (idx, coord, x) in edits {
+        p.vertices[idx][coord] = x;
+    }
+
+    if let Some(action) = action {
+        let name = action.name();
+        let res = parse_points(&table.pasted)
+            .map_err(|err| MiratopeError::operation(name, err))
+            .and_then(|points| {
+                action
+                    .build(points)
+                    .ok_or_else(|| MiratopeError::operation(name, "no points were given"))
+            });
+
+        match res {
+            Ok(q) => {
+                println!("{} has {} vertices.", name, q.vertices.len());
+                *p = q;
+            }
+            Err(err) => toasts.error(err),
+        }
+    }
+
+    if !open {
+        table.open = false;
+    }
+}
@@ -0,0 +1,223 @@
+//! Downloads OFF and GGB files uploaded to the [Polytope Wiki](crate::WIKI_LINK)
+//! into the local library.
+//!
+//! Each file is saved in the `Wiki` folder of the library, together with the
+//! source of its file page, which holds its description and categories. Files
+//! that were already downloaded are loaded from there, unless asked otherwise.
+//!
+//! Downloading needs the `wiki` feature, which adds an HTTP client. Without it,
+//! only the functions to read file names and file pages are available, which
+//! the [library index](super::library) uses to tag downloaded files.
+
+/// The folder of the library where downloaded files are saved.
+pub const WIKI_FOLDER: &str = "Wiki";
+
+/// The extension of the files where the source of each file page is saved.
+pub const METADATA_EXTENSION: &str = "wiki";
+
+/// Reads the name of a file on the wiki, given either as its name, as in
+/// `"Tesseract.off"`, as the title of its page, as in `"File:Tesseract.off"`,
+/// or as the link to its page. Spaces are replaced by underscores, as the wiki
+/// does. Returns `None` if the file isn't an OFF or a GGB file.
+pub fn file_name(input: &str) -> Option<String> {
+    let input = input.trim();
+    let name = input.rsplit('/').next().unwrap_or(input);
+    let name = name.strip_prefix("File:").unwrap_or(name);
+    let name = name.replace(' ', "_");
+
+    let ext = name.rsplit_once('.')?.1.to_lowercase();
+    if ext == "off" || ext == "ggb" {
+        Some(name)
+    } else {
+        None
+    }
+}
+
+/// Returns the categories a file page belongs to, from its source.
+pub fn categories(src: &str) -> Vec<String> {
+    const PREFIX: &str = "[[Category:";
+    let mut categories = Vec::new();
+
+    for (idx, _) in src.match_indices(PREFIX) {
+        let rest = &src[idx + PREFIX.len()..];
+        if let Some(end) = rest.find("]]") {
+            // Drops the sort key, as in [[Category:Polychora|Tesseract]].
+            let category = rest[..end].split('|').next().unwrap_or_default().trim();
+            if !category.is_empty() {
+                categories.push(category.replace('_', " "));
+            }
+        }
+    }
+
+    categories
+}
+
+#[cfg(all(feature = "wiki", not(target_arch = "wasm32")))]
+pub use download::*;
+
+/// Everything that needs an HTTP client.
+#[cfg(all(feature = "wiki", not(target_arch = "wasm32")))]
+mod download {
+    use std::{
+        fs,
+        io::Read,
+        path::{Path, PathBuf},
+    };
+
+    use super::{file_name, METADATA_EXTENSION, WIKI_FOLDER};
+    use crate::{
+        ui::task::{BackgroundTask, Progress, TaskOutput},
+        Concrete, WIKI_LINK,
+    };
+
+    use bevy_egui::egui::{self, Ui};
+    use miratope_core::{
+        error::{MiratopeError, MiratopeResult},
+        file::FromFile,
+    };
+
+    /// The script that returns the source of a page of the wiki.
+    const INDEX_LINK: &str = "https://polytope.miraheze.org/w/index.php";
+
+    /// The largest file that's downloaded, in bytes.
+    const MAX_SIZE: u64 = 64 << 20;
+
+    /// Percent-encodes the characters of a file name that can't go in a link.
+    fn encode(name: &str) -> String {
+        let mut encoded = String::with_capacity(name.len());
+
+        for byte in name.bytes() {
+            match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'_' | b'-' | b'.' | b'(' | b')' => {
+                    encoded.push(byte as char)
+                }
+                _ => encoded.push_str(&format!("%{:02X}", byte)),
+            }
+        }
+
+        encoded
+    }
+
+    /// Returns the link to the contents of a file on the wiki.
+    fn file_link(name: &str) -> String {
+        format!("{}Special:FilePath/{}", WIKI_LINK, encode(name))
+    }
+
+    /// Returns the link to the source of the page of a file on the wiki.
+    fn page_source_link(name: &str) -> String {
+        format!("{}?title=File:{}&action=raw", INDEX_LINK, encode(name))
+    }
+
+    /// Downloads the contents of a link.
+    fn get(link: &str) -> Result<Vec<u8>, String> {
+        let response = ureq::get(link).call().map_err(|err| err.to_string())?;
+
+        let mut bytes = Vec::new();
+        response
+            .into_reader()
+            .take(MAX_SIZE)
+            .read_to_end(&mut bytes)
+            .map_err(|err| err.to_string())?;
+        Ok(bytes)
+    }
+
+    /// Downloads a file from the wiki into the library, along with the source
+    /// of its page, and returns its path. If the file was already downloaded,
+    /// it's only downloaded again if `refresh` is set. Problems with the source
+    /// of the page are reported through `progress`.
+    pub fn download(
+        name: &str,
+        library: &Path,
+        refresh: bool,
+        progress: &Progress,
+    ) -> MiratopeResult<PathBuf> {
+        let folder = library.join(WIKI_FOLDER);
+        let path = folder.join(name);
+        if path.exists() && !refresh {
+            return Ok(path);
+        }
+
+        let link = file_link(name);
+        let mut bytes =
+            get(&link).map_err(|err| MiratopeError::operation("Downloading from the wiki", err))?;
+
+        // We make sure the file can be read before saving anything.
+        let ext = path.extension().unwrap_or_default().to_string_lossy();
+        if let Err(err) = Concrete::from_bytes(&bytes, &ext.to_lowercase()) {
+            return Err(MiratopeError::load(&link, err));
+        }
+
+        // OFF files keep track of where they came from.
+        if ext.eq_ignore_ascii_case("off") {
+            let mut off = format!("# Downloaded from {}\n", link).into_bytes();
+            off.append(&mut bytes);
+            bytes = off;
+        }
+
+        fs::create_dir_all(&folder).map_err(|err| MiratopeError::save(&folder, err))?;
+        fs::write(&path, bytes).map_err(|err| MiratopeError::save(&path, err))?;
+
+        // The file is still useful without its description.
+        match get(&page_source_link(name)) {
+            Ok(src) => {
+                let metadata = path.with_extension(METADATA_EXTENSION);
+                if let Err(err) = fs::write(&metadata, src) {
+                    progress.warn(MiratopeError::save(&metadata, err));
+                }
+            }
+            Err(err) => progress.warn(format!(
+                "The page of {} could not be downloaded: {}",
+                name, err
+            )),
+        }
+
+        Ok(path)
+    }
+
+    /// The state of the section of the library panel that downloads files
+    /// from the wiki.
+    #[derive(Default)]
+    pub struct WikiDownload {
+        /// The name of the file, or the link to its page.
+        input: String,
+
+        /// Whether to download the file again even if it's in the library.
+        refresh: bool,
+    }
+
+    impl WikiDownload {
+        /// Shows the section in a given `Ui`. Files are downloaded in the
+        /// background, and loaded once they're done.
+        pub fn show(&mut self, ui: &mut Ui, library: &Path, background_task: &mut BackgroundTask) {
+            ui.collapsing("Download from the wiki", |ui| {
+                ui.add(egui::TextEdit::singleline(&mut self.input).hint_text("Tesseract.off"));
+                ui.checkbox(&mut self.refresh, "Download again if already saved");
+
+                let name = file_name(&self.input);
+                let enabled = name.is_some() && !background_task.is_running();
+
+                if ui
+                    .add(egui::Button::new("Download").enabled(enabled))
+                    .clicked()
+                {
+                    if let Some(name) = name {
+                        let (library, refresh) = (library.to_path_buf(), self.refresh);
+
+                        background_task.spawn("Download from the wiki", move |progress| {
+                            let res = download(&name, &library, refresh, progress)
+                                .and_then(|path| {
+                                    Concrete::from_path(&path)
+                                        .map_err(|err| MiratopeError::load(&path, err))
+                                });
+
+                            match res {
+                                Ok(p) => TaskOutput::Polytope(p),
+                                Err(err) => TaskOutput::Error(err.to_string()),
+                            }
+                        });
+                    }
+                }
+            });
+        }
+    }
+}
@@ -6,11 +6,22 @@
 use std::marker::PhantomData;
 
 use super::{
+    camera::ProjectionType,
+    config::Tolerance,
     memory::{slot_label, Memory},
-    PointWidget,
+    toast::Toasts,
+    PointWidget, UnitPointWidget,
+};
+use crate::{
+    mesh::WireframeStyle, screenshot::RenderOptions, vector::VectorOptions, Concrete, Float,
+    Hyperplane, Hypersphere, Point,
+};
+use miratope_core::{
+    conc::ConcretePolytope,
+    error::{MiratopeError, MiratopeResult},
+    group::Group,
+    Polytope,
 };
-use crate::{Concrete, Float, Hypersphere, Point};
-use miratope_core::{conc::{ConcretePolytope}, Polytope};
 
 use bevy::prelude::*;
 use bevy_egui::{
@@ -46,14 +57,23 @@ impl Plugin for WindowPlugin {
             .add_plugin(PrismWindow::plugin())
             .add_plugin(TegumWindow::plugin())
             .add_plugin(AntiprismWindow::plugin())
+            .add_plugin(DitopeWindow::plugin())
+            .add_plugin(HosotopeWindow::plugin())
             .add_plugin(DuopyramidWindow::plugin())
             .add_plugin(DuoprismWindow::plugin())
             .add_plugin(DuotegumWindow::plugin())
             .add_plugin(DuocombWindow::plugin())
             .add_plugin(CompoundWindow::plugin())
+            .add_plugin(OrbitCompoundWindow::plugin())
             .add_plugin(TruncateWindow::plugin())
+            .add_plugin(CutWindow::plugin())
             .add_plugin(ScaleWindow::plugin())
-            .add_plugin(FacetingSettings::plugin());
+            .add_plugin(TransformWindow::plugin())
+            .add_plugin(AugmentWindow::plugin())
+            .add_plugin(FacetingSettings::plugin())
+            .add_plugin(RenderSettings::plugin())
+            .add_plugin(ToleranceSettings::plugin())
+            .add_plugin(ExportSettings::plugin());
     }
 }
 
@@ -145,13 +165,16 @@ macro_rules! impl_show {
             mut self_: ResMut<'_, Self>,
             egui_ctx: Res<'_, EguiContext>,
             mut query: Query<'_, '_, &mut Concrete>,
+            mut toasts: ResMut<'_, Toasts>,
         ) where
             Self: 'static,
         {
             match self_.show(egui_ctx.ctx()) {
                 ShowResult::Ok => {
                     for mut polytope in query.iter_mut() {
-                        self_.action(polytope.as_mut());
+                        if let Err(err) = self_.action(polytope.as_mut()) {
+                            toasts.error(err);
+                        }
                     }
                     self_.close()
                 }
@@ -167,7 +190,7 @@ macro_rules! impl_show {
 /// doesn't need to be updated when the polytope is changed.
 pub trait PlainWindow: Window {
     /// Applies the action of the window to the polytope.
-    fn action(&self, polytope: &mut Concrete);
+    fn action(&self, polytope: &mut Concrete) -> MiratopeResult<()>;
 
     /// Builds the window to be shown on screen.
     fn build(&mut self, ui: &mut Ui);
@@ -201,7 +224,7 @@ impl<T: PlainWindow + 'static> Plugin for PlainWindowPlugin<T> {
 /// to be updated when the dimension of the polytope is changed.
 pub trait UpdateWindow: Window {
     /// Applies the action of the window to the polytope.
-    fn action(&self, polytope: &mut Concrete);
+    fn action(&self, polytope: &mut Concrete) -> MiratopeResult<()>;
 
     /// Builds the window to be shown on screen.
     fn build(&mut self, ui: &mut Ui);
@@ -287,8 +310,9 @@ impl Slot {
 /// A window for any duo-something. All of these depend on the [`Memory`] but
 /// don't need to be updated when the polytope changes.
 pub trait DuoWindow: Window {
-    /// The duo-operation to apply.
-    fn operation(&self, p: &Concrete, q: &Concrete) -> Concrete;
+    /// The duo-operation to apply. Returns `None` if it fails, in which case
+    /// the polytope is left unchanged.
+    fn operation(&self, p: &Concrete, q: &Concrete) -> MiratopeResult<Concrete>;
 
     /// The slots in memory.
     fn slots(&self) -> [Slot; 2];
@@ -314,10 +338,12 @@ pub trait DuoWindow: Window {
     }
 
     /// Applies the action of the window to the polytope.
-    fn action(&self, polytope: &mut Concrete, memory: &Memory) {
+    fn action(&self, polytope: &mut Concrete, memory: &Memory) -> MiratopeResult<()> {
         if let [Some(p), Some(q)] = self.polytopes(polytope, memory) {
-            *polytope = self.operation(p, q);
+            *polytope = self.operation(p, q)?;
         }
+
+        Ok(())
     }
 
     /// Builds the window to be shown on screen.
@@ -419,13 +445,16 @@ pub trait DuoWindow: Window {
         egui_ctx: Res<'_, EguiContext>,
         mut query: Query<'_, '_, &mut Concrete>,
         memory: Res<'_, Memory>,
+        mut toasts: ResMut<'_, Toasts>,
     ) where
         Self: 'static,
     {
         for mut polytope in query.iter_mut() {
             match self_.show(egui_ctx.ctx(), &polytope, &memory) {
                 ShowResult::Ok => {
-                    self_.action(polytope.as_mut(), &memory);
+                    if let Err(err) = self_.action(polytope.as_mut(), &memory) {
+                        toasts.error(err);
+                    }
                     self_.close()
                 }
                 ShowResult::Close => self_.close(),
@@ -462,6 +491,13 @@ pub struct DualWindow {
 
     /// The radius of the sphere.
     radius: Float,
+
+    /// Whether the dual vertices of facets through the center are sent to
+    /// infinity, instead of making the dual fail.
+    infinity: bool,
+
+    /// The distance at which the vertices at infinity are placed.
+    distance: Float,
 }
 
 impl Default for DualWindow {
@@ -470,6 +506,8 @@ impl Default for DualWindow {
             open: false,
             center: Point::zeros(0),
             radius: 1.0,
+            infinity: false,
+            distance: 100.0,
         }
     }
 }
@@ -487,12 +525,24 @@ impl Window for DualWindow {
 }
 
 impl UpdateWindow for DualWindow {
-    fn action(&self, polytope: &mut Concrete) {
+    fn action(&self, polytope: &mut Concrete) -> MiratopeResult<()> {
         let sphere = Hypersphere::with_radius(self.center.clone(), self.radius);
 
-        if let Err(err) = polytope.try_dual_mut_with(&sphere) {
-            eprintln!("Dual failed: {}", err);
+        if self.infinity {
+            let at_infinity = polytope
+                .dual_mut_with_infinity(&sphere, self.distance)
+                .map_err(|err| MiratopeError::operation("Dual", err))?;
+
+            if !at_infinity.is_empty() {
+                println!("{} vertices sent to infinity.", at_infinity.len());
+            }
+        } else {
+            polytope
+                .try_dual_mut_with(&sphere)
+                .map_err(|err| MiratopeError::operation("Dual", err))?;
         }
+
+        Ok(())
     }
 
     fn build(&mut self, ui: &mut Ui) {
@@ -507,6 +557,20 @@ impl UpdateWindow for DualWindow {
 
             ui.label("Radius");
         });
+
+        ui.add(egui::Checkbox::new(&mut self.infinity, "Send degenerate facets to infinity"));
+
+        if self.infinity {
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::DragValue::new(&mut self.distance)
+                        .speed(1.0)
+                        .clamp_range(0.0..=Float::MAX),
+                );
+
+                ui.label("Distance to infinity");
+            });
+        }
     }
 
     fn dim(&self) -> usize {
@@ -561,8 +625,9 @@ impl Window for PyramidWindow {
 }
 
 impl UpdateWindow for PyramidWindow {
-    fn action(&self, polytope: &mut Concrete) {
+    fn action(&self, polytope: &mut Concrete) -> MiratopeResult<()> {
         *polytope = polytope.pyramid_with(self.offset.push(self.height));
+        Ok(())
     }
 
     fn build(&mut self, ui: &mut Ui) {
@@ -618,8 +683,9 @@ impl Window for PrismWindow {
 }
 
 impl PlainWindow for PrismWindow {
-    fn action(&self, polytope: &mut Concrete) {
+    fn action(&self, polytope: &mut Concrete) -> MiratopeResult<()> {
         *polytope = polytope.prism_with(self.height);
+        Ok(())
     }
 
     fn build(&mut self, ui: &mut Ui) {
@@ -682,13 +748,14 @@ impl Window for TegumWindow {
 }
 
 impl UpdateWindow for TegumWindow {
-    fn action(&self, polytope: &mut Concrete) {
+    fn action(&self, polytope: &mut Concrete) -> MiratopeResult<()> {
         let half_height = self.height / 2.0;
 
         *polytope = polytope.tegum_with(
             self.offset.push(self.height_offset + half_height),
             self.offset.push(self.height_offset - half_height),
         );
+        Ok(())
     }
 
     fn build(&mut self, ui: &mut Ui) {
@@ -762,7 +829,7 @@ impl Window for AntiprismWindow {
 }
 
 impl UpdateWindow for AntiprismWindow {
-    fn action(&self, polytope: &mut Concrete) {
+    fn action(&self, polytope: &mut Concrete) -> MiratopeResult<()> {
         let radius = self.dual.radius;
         let mut squared_radius = radius * radius;
         if self.retroprism {
@@ -771,10 +838,10 @@ impl UpdateWindow for AntiprismWindow {
 
         let sphere = Hypersphere::with_squared_radius(self.dual.center.clone(), squared_radius);
 
-        match polytope.try_antiprism_with(&sphere, self.height) {
-            Ok(antiprism) => *polytope = antiprism,
-            Err(err) => eprintln!("Antiprism failed: {}", err),
-        }
+        *polytope = polytope
+            .try_antiprism_with(&sphere, self.height)
+            .map_err(|err| MiratopeError::operation("Antiprism", err))?;
+        Ok(())
     }
 
     fn build(&mut self, ui: &mut Ui) {
@@ -818,6 +885,152 @@ impl UpdateWindow for AntiprismWindow {
     }
 }
 
+/// Applies an operation that raises the rank of a polytope by one, such as a
+/// ditope or a hosotope, until the polytope has a given rank. Ranks are given
+/// with the nullitope at &minus;1, as shown to the user.
+fn raise_rank(
+    polytope: &mut Concrete,
+    rank: isize,
+    operation: &'static str,
+    f: fn(&mut Concrete),
+) -> MiratopeResult<()> {
+    if polytope.is_nullitope() {
+        return Err(MiratopeError::operation(
+            operation,
+            "the nullitope can't be extended",
+        ));
+    }
+
+    let current = polytope.rank() as isize - 1;
+    if rank <= current {
+        return Err(MiratopeError::operation(
+            operation,
+            format!("the polytope already has rank {}", current),
+        ));
+    }
+
+    for _ in current..rank {
+        f(polytope);
+    }
+
+    Ok(())
+}
+
+/// Shows the rank selector for a window that raises the rank of a polytope.
+fn rank_selector(ui: &mut Ui, rank: &mut isize) {
+    ui.horizontal(|ui| {
+        ui.label("Rank:");
+        ui.add(egui::DragValue::new(rank).speed(0.03).clamp_range(1..=20));
+    });
+}
+
+/// Allows the user to take ditopes of a polytope repeatedly, until it reaches
+/// a given rank.
+pub struct DitopeWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The rank of the resulting polytope.
+    rank: isize,
+}
+
+impl Default for DitopeWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            rank: 3,
+        }
+    }
+}
+
+impl Window for DitopeWindow {
+    const NAME: &'static str = "Ditope";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl PlainWindow for DitopeWindow {
+    fn action(&self, polytope: &mut Concrete) -> MiratopeResult<()> {
+        raise_rank(polytope, self.rank, Self::NAME, Concrete::ditope_mut)
+    }
+
+    fn build(&mut self, ui: &mut Ui) {
+        rank_selector(ui, &mut self.rank);
+    }
+}
+
+/// Allows the user to take hosotopes of a polytope repeatedly, until it
+/// reaches a given rank.
+pub struct HosotopeWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The rank of the resulting polytope.
+    rank: isize,
+}
+
+impl Default for HosotopeWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            rank: 3,
+        }
+    }
+}
+
+impl Window for HosotopeWindow {
+    const NAME: &'static str = "Hosotope";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl PlainWindow for HosotopeWindow {
+    fn action(&self, polytope: &mut Concrete) -> MiratopeResult<()> {
+        raise_rank(polytope, self.rank, Self::NAME, Concrete::hosotope_mut)
+    }
+
+    fn build(&mut self, ui: &mut Ui) {
+        rank_selector(ui, &mut self.rank);
+    }
+}
+
+/// The label of the checkbox that rescales the factors of a product.
+const RESCALE_LABEL: &str = "Rescale factors";
+
+/// Returns the factors of a product, rescaled to unit edge length and centered
+/// at their circumcenters if `rescale` is set, so that the product of isogonal
+/// factors is isogonal.
+fn rescale_factors(
+    p: &Concrete,
+    q: &Concrete,
+    rescale: bool,
+    operation: &'static str,
+) -> MiratopeResult<(Concrete, Concrete)> {
+    if !rescale {
+        return Ok((p.clone(), q.clone()));
+    }
+
+    match (p.unit_centered(), q.unit_centered()) {
+        (Some(p), Some(q)) => Ok((p, q)),
+        _ => Err(MiratopeError::operation(
+            operation,
+            "the factors must be equilateral and circumscribable",
+        )),
+    }
+}
+
 /// A window that allows a user to build a duopyramid, either using the
 /// polytopes in memory or the currently loaded one.
 pub struct DuopyramidWindow {
@@ -827,11 +1040,20 @@ pub struct DuopyramidWindow {
     /// The slots corresponding to the selected polytopes.
     slots: [Slot; 2],
 
-    /// The height of the duopyramid.
-    height: Float,
+    /// The height at which each base is placed, measured from the origin in
+    /// opposite directions.
+    heights: [Float; 2],
 
     /// The offset of each base.
     offsets: [Point; 2],
+
+    /// Whether to ignore the heights and offsets, and instead place the bases
+    /// so that all lacing edges have unit length.
+    unit_edges: bool,
+
+    /// Whether to rescale the bases to unit edge length and center them at
+    /// their circumcenters before building the duopyramid.
+    rescale: bool,
 }
 
 impl Default for DuopyramidWindow {
@@ -839,8 +1061,10 @@ impl Default for DuopyramidWindow {
         Self {
             open: false,
             slots: Default::default(),
-            height: 1.0,
+            heights: [0.5, 0.5],
             offsets: [Point::zeros(0), Point::zeros(0)],
+            unit_edges: false,
+            rescale: false,
         }
     }
 }
@@ -858,9 +1082,18 @@ impl Window for DuopyramidWindow {
 }
 
 impl DuoWindow for DuopyramidWindow {
-    fn operation(&self, p: &Concrete, q: &Concrete) -> Concrete {
+    fn operation(&self, p: &Concrete, q: &Concrete) -> MiratopeResult<Concrete> {
+        let (p, q) = &rescale_factors(p, q, self.rescale, "Duopyramid")?;
+
+        if self.unit_edges {
+            return p.duopyramid_unit(q).ok_or_else(|| {
+                MiratopeError::operation("Duopyramid", "the bases can't be laced with unit edges")
+            });
+        }
+
         let [p_offset, q_offset] = &self.offsets;
-        Concrete::duopyramid_with(p, q, p_offset, q_offset, self.height)
+        let [p_height, q_height] = self.heights;
+        Ok(p.duopyramid_with_heights(q, p_offset, q_offset, p_height, q_height))
     }
 
     fn slots(&self) -> [Slot; 2] {
@@ -877,12 +1110,23 @@ impl DuoWindow for DuopyramidWindow {
         resize(&mut self.offsets[0], p_dim);
         resize(&mut self.offsets[1], q_dim);
 
+        ui.add(egui::Checkbox::new(&mut self.rescale, RESCALE_LABEL));
+        ui.add(egui::Checkbox::new(&mut self.unit_edges, "Unit edges"));
+        if self.unit_edges {
+            return;
+        }
+
         ui.add(PointWidget::new(&mut self.offsets[0], "Offset #1"));
         ui.add(PointWidget::new(&mut self.offsets[1], "Offset #2"));
 
         ui.horizontal(|ui| {
-            ui.add(egui::DragValue::new(&mut self.height).clamp_range(0.0..=Float::MAX));
-            ui.label("Height");
+            ui.add(egui::DragValue::new(&mut self.heights[0]).speed(0.01));
+            ui.label("Height #1");
+        });
+
+        ui.horizontal(|ui| {
+            ui.add(egui::DragValue::new(&mut self.heights[1]).speed(0.01));
+            ui.label("Height #2");
         });
     }
 }
@@ -896,6 +1140,10 @@ pub struct DuoprismWindow {
 
     /// The slots that are currently selected.
     slots: [Slot; 2],
+
+    /// Whether to rescale the factors to unit edge length and center them at
+    /// their circumcenters before building the duoprism.
+    rescale: bool,
 }
 
 impl Window for DuoprismWindow {
@@ -911,8 +1159,9 @@ impl Window for DuoprismWindow {
 }
 
 impl DuoWindow for DuoprismWindow {
-    fn operation(&self, p: &Concrete, q: &Concrete) -> Concrete {
-        p.duoprism(q)
+    fn operation(&self, p: &Concrete, q: &Concrete) -> MiratopeResult<Concrete> {
+        let (p, q) = &rescale_factors(p, q, self.rescale, "Duoprism")?;
+        Ok(p.duoprism(q))
     }
 
     fn slots(&self) -> [Slot; 2] {
@@ -922,6 +1171,10 @@ impl DuoWindow for DuoprismWindow {
     fn slots_mut(&mut self) -> &mut [Slot; 2] {
         &mut self.slots
     }
+
+    fn build(&mut self, ui: &mut Ui, _: &Concrete, _: &Memory) {
+        ui.add(egui::Checkbox::new(&mut self.rescale, RESCALE_LABEL));
+    }
 }
 
 /// A window that allows a user to build a duotegum, either using the polytopes
@@ -935,6 +1188,14 @@ pub struct DuotegumWindow {
 
     /// The offset of each base.
     offsets: [Point; 2],
+
+    /// Whether to ignore the offsets, and instead place the bases so that all
+    /// lacing edges have unit length.
+    unit_edges: bool,
+
+    /// Whether to rescale the bases to unit edge length and center them at
+    /// their circumcenters before building the duotegum.
+    rescale: bool,
 }
 
 impl Default for DuotegumWindow {
@@ -943,6 +1204,8 @@ impl Default for DuotegumWindow {
             open: false,
             slots: Default::default(),
             offsets: [Point::zeros(0), Point::zeros(0)],
+            unit_edges: false,
+            rescale: false,
         }
     }
 }
@@ -960,9 +1223,17 @@ impl Window for DuotegumWindow {
 }
 
 impl DuoWindow for DuotegumWindow {
-    fn operation(&self, p: &Concrete, q: &Concrete) -> Concrete {
+    fn operation(&self, p: &Concrete, q: &Concrete) -> MiratopeResult<Concrete> {
+        let (p, q) = &rescale_factors(p, q, self.rescale, "Duotegum")?;
+
+        if self.unit_edges {
+            return Concrete::duotegum_unit(p, q).ok_or_else(|| {
+                MiratopeError::operation("Duotegum", "the bases can't be laced with unit edges")
+            });
+        }
+
         let [p_offset, q_offset] = &self.offsets;
-        Concrete::duotegum_with(p, q, p_offset, q_offset)
+        Ok(Concrete::duotegum_with(p, q, p_offset, q_offset))
     }
 
     fn slots(&self) -> [Slot; 2] {
@@ -979,6 +1250,12 @@ impl DuoWindow for DuotegumWindow {
         resize(&mut self.offsets[0], p_dim);
         resize(&mut self.offsets[1], q_dim);
 
+        ui.add(egui::Checkbox::new(&mut self.rescale, RESCALE_LABEL));
+        ui.add(egui::Checkbox::new(&mut self.unit_edges, "Unit edges"));
+        if self.unit_edges {
+            return;
+        }
+
         ui.add(PointWidget::new(&mut self.offsets[0], "Offset #1"));
         ui.add(PointWidget::new(&mut self.offsets[1], "Offset #2"));
     }
@@ -1008,8 +1285,8 @@ impl Window for DuocombWindow {
 }
 
 impl DuoWindow for DuocombWindow {
-    fn operation(&self, p: &Concrete, q: &Concrete) -> Concrete {
-        p.duocomb(q)
+    fn operation(&self, p: &Concrete, q: &Concrete) -> MiratopeResult<Concrete> {
+        Ok(p.duocomb(q))
     }
 
     fn slots(&self) -> [Slot; 2] {
@@ -1045,10 +1322,107 @@ impl Window for CompoundWindow {
 }
 
 impl DuoWindow for CompoundWindow {
-    fn operation(&self, p: &Concrete, q: &Concrete) -> Concrete {
+    fn operation(&self, p: &Concrete, q: &Concrete) -> MiratopeResult<Concrete> {
         let mut p2 = p.clone();
         p2.comp_append(q.clone());
-        p2
+        Ok(p2)
+    }
+
+    fn slots(&self) -> [Slot; 2] {
+        self.slots
+    }
+
+    fn slots_mut(&mut self) -> &mut [Slot; 2] {
+        &mut self.slots
+    }
+}
+
+/// A window that builds the compound of the images of a polytope under a
+/// symmetry group, either that of a host polytope or that of a Coxeter
+/// diagram.
+pub struct OrbitCompoundWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The slots for the seed and the host polytopes.
+    slots: [Slot; 2],
+
+    /// Whether the group is read from a Coxeter diagram, rather than taken
+    /// from the host polytope.
+    use_diagram: bool,
+
+    /// The Coxeter diagram of the group.
+    diagram: String,
+
+    /// Whether only the rotations of the group are applied.
+    chiral: bool,
+}
+
+impl Default for OrbitCompoundWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            slots: [Slot::Loaded, Slot::None],
+            use_diagram: false,
+            diagram: "o5o3o".to_string(),
+            chiral: false,
+        }
+    }
+}
+
+impl Window for OrbitCompoundWindow {
+    const NAME: &'static str = "Orbit compound";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl OrbitCompoundWindow {
+    /// Builds the compound of the images of a polytope under the chosen group,
+    /// where the host polytope is only used if no diagram is.
+    fn orbit_compound(&self, seed: &Concrete, host: Option<&Concrete>) -> MiratopeResult<Concrete> {
+        const NAME: &str = "Orbit compound";
+
+        let group = if self.use_diagram {
+            Group::parse(&self.diagram)
+                .map_err(|err| MiratopeError::operation(NAME, err))?
+                .ok_or_else(|| MiratopeError::operation(NAME, "the group isn't finite"))?
+                .cache()
+        } else {
+            host.ok_or_else(|| MiratopeError::operation(NAME, "no host polytope was selected"))?
+                .clone()
+                .get_symmetry_group()
+                .0
+        };
+
+        let compound = if self.chiral {
+            seed.orbit_compound(group.rotations())
+        } else {
+            seed.orbit_compound(group)
+        };
+
+        let compound = compound.ok_or_else(|| {
+            MiratopeError::operation(
+                NAME,
+                "the group doesn't match the dimension of the polytope",
+            )
+        })?;
+        println!(
+            "Orbit compound has {} components.",
+            compound.components().len()
+        );
+        Ok(compound)
+    }
+}
+
+impl DuoWindow for OrbitCompoundWindow {
+    fn operation(&self, p: &Concrete, q: &Concrete) -> MiratopeResult<Concrete> {
+        self.orbit_compound(p, Some(q))
     }
 
     fn slots(&self) -> [Slot; 2] {
@@ -1058,6 +1432,29 @@ impl DuoWindow for CompoundWindow {
     fn slots_mut(&mut self) -> &mut [Slot; 2] {
         &mut self.slots
     }
+
+    fn action(&self, polytope: &mut Concrete, memory: &Memory) -> MiratopeResult<()> {
+        // The host polytope isn't needed when the group comes from a diagram.
+        if let [Some(seed), host] = self.polytopes(polytope, memory) {
+            *polytope = self.orbit_compound(seed, host)?;
+        }
+
+        Ok(())
+    }
+
+    fn build(&mut self, ui: &mut Ui, _: &Concrete, _: &Memory) {
+        ui.label("#1 is copied around by the symmetries of #2.");
+        ui.checkbox(&mut self.use_diagram, "Use a Coxeter diagram instead");
+
+        if self.use_diagram {
+            ui.horizontal(|ui| {
+                ui.label("Diagram:");
+                ui.text_edit_singleline(&mut self.diagram);
+            });
+        }
+
+        ui.checkbox(&mut self.chiral, "Rotations only");
+    }
 }
 
 /// A window to configure a truncation of the polytope.
@@ -1089,7 +1486,7 @@ impl Window for TruncateWindow {
 }
 
 impl UpdateWindow for TruncateWindow {
-    fn action(&self, polytope: &mut Concrete) {
+    fn action(&self, polytope: &mut Concrete) -> MiratopeResult<()> {
         let mut rings = Vec::new();
         for (rank, ringed) in self.truncate_type.iter().enumerate() {
             if *ringed {
@@ -1098,6 +1495,7 @@ impl UpdateWindow for TruncateWindow {
         }
         polytope.element_sort();
         *polytope = polytope.truncate_with(rings, self.depth.clone());
+        Ok(())
     }
 
     fn build(&mut self, ui: &mut Ui) {
@@ -1129,6 +1527,133 @@ impl UpdateWindow for TruncateWindow {
     }
 }
 
+/// A window that cuts off the part of a polytope on one side of a hyperplane,
+/// or truncates a single vertex.
+pub struct CutWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// Whether to truncate a single vertex, rather than cutting with an
+    /// arbitrary hyperplane.
+    vertex: bool,
+
+    /// Whether to keep the piece on the side of the normal, rather than the
+    /// opposite one.
+    other_side: bool,
+
+    /// The normal of the hyperplane.
+    normal: Point,
+
+    /// The distance from the hyperplane to the origin, along its normal.
+    pos: Float,
+
+    /// The index of the vertex to truncate.
+    idx: usize,
+
+    /// The distance from the vertex to the cut.
+    depth: Float,
+}
+
+impl Default for CutWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            vertex: false,
+            other_side: false,
+            normal: Point::zeros(0),
+            pos: 0.0,
+            idx: 0,
+            depth: 0.1,
+        }
+    }
+}
+
+impl Window for CutWindow {
+    const NAME: &'static str = "Cut";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl UpdateWindow for CutWindow {
+    fn action(&self, polytope: &mut Concrete) -> MiratopeResult<()> {
+        if self.vertex {
+            *polytope = polytope
+                .truncate_vertex(self.idx, self.depth)
+                .ok_or_else(|| {
+                    MiratopeError::operation(
+                        "Vertex truncation",
+                        format!("no vertex {} away from the center", self.idx),
+                    )
+                })?;
+        } else {
+            let sign = if self.other_side { -1.0 } else { 1.0 };
+            let hyperplane = Hyperplane::new(self.normal.clone() * sign, self.pos * sign);
+            *polytope = polytope.cut_with_hyperplane(&hyperplane);
+        }
+
+        Ok(())
+    }
+
+    fn build(&mut self, ui: &mut Ui) {
+        ui.checkbox(&mut self.vertex, "Truncate a single vertex");
+
+        if self.vertex {
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut self.idx));
+                ui.label("Vertex");
+            });
+
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::DragValue::new(&mut self.depth)
+                        .speed(0.01)
+                        .clamp_range(0.0..=Float::MAX),
+                );
+
+                ui.label("Depth");
+            });
+        } else {
+            ui.add(UnitPointWidget::new(&mut self.normal, "Normal"));
+
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut self.pos).speed(0.01));
+                ui.label("Position");
+            });
+
+            ui.checkbox(&mut self.other_side, "Keep the side of the normal");
+        }
+    }
+
+    fn dim(&self) -> usize {
+        self.normal.len()
+    }
+
+    fn default_with(dim: usize) -> Self {
+        let mut normal = Point::zeros(dim);
+        if dim != 0 {
+            normal[dim - 1] = 1.0;
+        }
+
+        Self {
+            normal,
+            ..Default::default()
+        }
+    }
+
+    fn update(&mut self, dim: usize) {
+        *self = Self {
+            open: self.open,
+            ..Self::default_with(dim)
+        };
+    }
+}
+
 /// A window that scales a polytope.
 #[derive(Default)]
 pub struct ScaleWindow {
@@ -1152,8 +1677,9 @@ impl Window for ScaleWindow {
 }
 
 impl PlainWindow for ScaleWindow {
-    fn action(&self, polytope: &mut Concrete) {
+    fn action(&self, polytope: &mut Concrete) -> MiratopeResult<()> {
         polytope.scale(self.scale);
+        Ok(())
     }
 
     fn build(&mut self, ui: &mut Ui) {
@@ -1166,31 +1692,409 @@ impl PlainWindow for ScaleWindow {
     }
 }
 
-/// A window that lets the user set settings for faceting.
-pub struct FacetingSettings {
-    /// Whether the window is open.
-    open: bool,
-
-    /// The maximum number of facet types considered. 1 for isotopic, 0 for no limit.
-    pub max_facet_types: usize,
+/// The transformations that can be applied from the [`TransformWindow`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TransformType {
+    /// Moves the polytope by a vector.
+    Translate,
 
-    /// The maximum number of facets generated in each hyperplane, to prevent combinatorial explosion. 0 for no limit.
-    pub max_per_hyperplane: usize,
+    /// Rotates the polytope within a coordinate plane.
+    Rotate,
 
-    /// Whether to take the rotation subgroup.
-    pub chiral: bool,
+    /// Reflects the polytope through a hyperplane.
+    Reflect,
 
-    /// Whether to use unit edges only (superregiment).
-    pub unit_edges: bool,
+    /// Scales the polytope about a center.
+    Scale,
+}
 
-    /// Whether to include trivial compounds (compounds of other full-symmetric facetings).
-    pub compounds: bool,
+impl Default for TransformType {
+    fn default() -> Self {
+        Self::Translate
+    }
+}
 
-    /// Whether to save the facetings in memory.
-    pub save: bool,
+impl TransformType {
+    /// All of the transformations, in the order they're shown on screen.
+    const ALL: [Self; 4] = [Self::Translate, Self::Rotate, Self::Reflect, Self::Scale];
 
-    /// Whether to save the facets in memory.
-    pub save_facets: bool,
+    /// The name of the transformation, as shown on screen.
+    fn name(self) -> &'static str {
+        match self {
+            Self::Translate => "Translate",
+            Self::Rotate => "Rotate",
+            Self::Reflect => "Reflect",
+            Self::Scale => "Scale",
+        }
+    }
+}
+
+/// The name of the `i`-th coordinate axis, as shown on screen.
+pub fn axis_name(i: usize) -> String {
+    const AXES: [&str; 4] = ["x", "y", "z", "w"];
+
+    match AXES.get(i) {
+        Some(axis) => axis.to_string(),
+        None => format!("x{}", i + 1),
+    }
+}
+
+/// A window that translates, rotates, reflects, or scales a polytope by exact
+/// amounts.
+pub struct TransformWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The transformation to apply.
+    transform_type: TransformType,
+
+    /// The vector by which the polytope is translated.
+    offset: Point,
+
+    /// The center of the rotation or of the scaling.
+    center: Point,
+
+    /// The coordinate axes spanning the plane of the rotation.
+    axes: [usize; 2],
+
+    /// The angle of the rotation, in degrees.
+    angle: Float,
+
+    /// The normal of the reflecting hyperplane.
+    normal: Point,
+
+    /// The position of the reflecting hyperplane along its normal.
+    pos: Float,
+
+    /// The scale factor.
+    scale: Float,
+}
+
+impl Default for TransformWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            transform_type: Default::default(),
+            offset: Point::zeros(0),
+            center: Point::zeros(0),
+            axes: [0, 1],
+            angle: 90.0,
+            normal: Point::zeros(0),
+            pos: 0.0,
+            scale: 1.0,
+        }
+    }
+}
+
+impl Window for TransformWindow {
+    const NAME: &'static str = "Transform";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl UpdateWindow for TransformWindow {
+    fn action(&self, polytope: &mut Concrete) -> MiratopeResult<()> {
+        match self.transform_type {
+            TransformType::Translate => polytope.translate(&self.offset),
+
+            TransformType::Rotate => {
+                let [i, j] = self.axes;
+                let dim = self.dim();
+
+                if i == j || i >= dim || j >= dim {
+                    return Err(MiratopeError::operation(
+                        "Rotation",
+                        "the axes must be two different coordinates of the polytope",
+                    ));
+                }
+
+                polytope.rotate_about(&self.center, i, j, self.angle.to_radians());
+            }
+
+            TransformType::Reflect => {
+                polytope.reflect_through(&Hyperplane::new(self.normal.clone(), self.pos))
+            }
+
+            TransformType::Scale => {
+                polytope.recenter_with(&self.center);
+                polytope.scale(self.scale);
+                polytope.translate(&self.center);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn build(&mut self, ui: &mut Ui) {
+        let transform_type = &mut self.transform_type;
+        egui::ComboBox::from_label("Transformation")
+            .selected_text(transform_type.name())
+            .show_ui(ui, |ui| {
+                for &ty in TransformType::ALL.iter() {
+                    ui.selectable_value(transform_type, ty, ty.name());
+                }
+            });
+
+        match self.transform_type {
+            TransformType::Translate => {
+                ui.add(PointWidget::new(&mut self.offset, "Offset"));
+            }
+
+            TransformType::Rotate => {
+                let max_axis = self.dim().saturating_sub(1);
+                let [i, j] = &mut self.axes;
+
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(i).clamp_range(0..=max_axis));
+                    ui.add(egui::DragValue::new(j).clamp_range(0..=max_axis));
+                    ui.label(format!("Plane ({}{})", axis_name(*i), axis_name(*j)));
+                });
+
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut self.angle).speed(0.5).suffix("°"));
+                    ui.label("Angle");
+                });
+
+                ui.add(PointWidget::new(&mut self.center, "Center"));
+            }
+
+            TransformType::Reflect => {
+                ui.add(UnitPointWidget::new(&mut self.normal, "Normal"));
+
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut self.pos).speed(0.01));
+                    ui.label("Position");
+                });
+            }
+
+            TransformType::Scale => {
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut self.scale).speed(0.01));
+                    ui.label("Factor");
+                });
+
+                ui.add(PointWidget::new(&mut self.center, "Center"));
+            }
+        }
+    }
+
+    fn dim(&self) -> usize {
+        self.offset.len()
+    }
+
+    fn default_with(dim: usize) -> Self {
+        let mut normal = Point::zeros(dim);
+        if dim != 0 {
+            normal[0] = 1.0;
+        }
+
+        Self {
+            offset: Point::zeros(dim),
+            center: Point::zeros(dim),
+            normal,
+            ..Default::default()
+        }
+    }
+
+    fn update(&mut self, dim: usize) {
+        resize(&mut self.offset, dim);
+        resize(&mut self.center, dim);
+
+        // The normal must stay a unit vector.
+        if self.normal.len() != dim {
+            self.normal = Self::default_with(dim).normal;
+        }
+    }
+}
+
+/// The operations that can be applied from the [`AugmentWindow`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum AugmentType {
+    /// Attaches a pyramid onto a facet.
+    Pyramid,
+
+    /// Attaches a cupola onto a facet.
+    Cupola,
+
+    /// Attaches a prism onto a facet.
+    Elongate,
+
+    /// Attaches an antiprism onto a facet.
+    Gyroelongate,
+
+    /// Removes a cap.
+    Diminish,
+
+    /// Rotates a cap.
+    Gyrate,
+}
+
+impl Default for AugmentType {
+    fn default() -> Self {
+        Self::Pyramid
+    }
+}
+
+impl AugmentType {
+    /// All of the operations, in the order they're shown.
+    const ALL: [Self; 6] = [
+        Self::Pyramid,
+        Self::Cupola,
+        Self::Elongate,
+        Self::Gyroelongate,
+        Self::Diminish,
+        Self::Gyrate,
+    ];
+
+    /// The name of the operation, as shown on screen.
+    fn name(self) -> &'static str {
+        match self {
+            Self::Pyramid => "Augment with pyramid",
+            Self::Cupola => "Augment with cupola",
+            Self::Elongate => "Elongate",
+            Self::Gyroelongate => "Gyroelongate",
+            Self::Diminish => "Diminish",
+            Self::Gyrate => "Gyrate",
+        }
+    }
+
+    /// Whether the operation acts on a cap rather than on a facet.
+    fn on_cap(self) -> bool {
+        matches!(self, Self::Diminish | Self::Gyrate)
+    }
+}
+
+/// A window that augments, diminishes, or gyrates a polytope on a chosen
+/// facet, as used to build the Johnson solids.
+#[derive(Default)]
+pub struct AugmentWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The operation to apply.
+    operation: AugmentType,
+
+    /// Whether to operate on the base facet of the polytope, rather than on a
+    /// facet with a given index.
+    base: bool,
+
+    /// The index of the facet to operate on. For operations on caps, the cap
+    /// is formed by the vertices of this facet.
+    idx: usize,
+
+    /// Whether the cap is the vertex with the given index, rather than the
+    /// vertices of a facet.
+    vertex_cap: bool,
+}
+
+impl Window for AugmentWindow {
+    const NAME: &'static str = "Augment";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl PlainWindow for AugmentWindow {
+    fn action(&self, polytope: &mut Concrete) -> MiratopeResult<()> {
+        let name = self.operation.name();
+        let idx = if self.base && !self.operation.on_cap() {
+            polytope
+                .base_facet()
+                .ok_or_else(|| MiratopeError::operation(name, "the polytope has no base facet"))?
+        } else {
+            self.idx
+        };
+
+        let cap = if self.vertex_cap {
+            Some(vec![idx])
+        } else {
+            polytope
+                .rank()
+                .checked_sub(1)
+                .and_then(|rank| polytope.abs().element_vertices(rank, idx))
+        };
+
+        let result = match self.operation {
+            AugmentType::Pyramid => polytope.augment(idx),
+            AugmentType::Cupola => polytope.augment_cupola(idx),
+            AugmentType::Elongate => polytope.elongate(idx),
+            AugmentType::Gyroelongate => polytope.gyroelongate(idx),
+            AugmentType::Diminish => cap.and_then(|cap| polytope.diminish(&cap)),
+            AugmentType::Gyrate => cap.and_then(|cap| polytope.gyrate(&cap)),
+        };
+
+        *polytope = result.ok_or_else(|| MiratopeError::failed(name))?;
+        println!("{} succeeded.", name);
+        Ok(())
+    }
+
+    fn build(&mut self, ui: &mut Ui) {
+        let operation = &mut self.operation;
+        egui::ComboBox::from_label("Operation")
+            .selected_text(operation.name())
+            .show_ui(ui, |ui| {
+                for &op in AugmentType::ALL.iter() {
+                    ui.selectable_value(operation, op, op.name());
+                }
+            });
+
+        if self.operation.on_cap() {
+            ui.checkbox(&mut self.vertex_cap, "Cap is a single vertex");
+        } else {
+            ui.checkbox(&mut self.base, "Base facet");
+        }
+
+        if self.operation.on_cap() || !self.base {
+            let label = if self.operation.on_cap() && self.vertex_cap {
+                "Vertex"
+            } else {
+                "Facet"
+            };
+
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut self.idx));
+                ui.label(label);
+            });
+        }
+    }
+}
+
+/// A window that lets the user set settings for faceting.
+pub struct FacetingSettings {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The maximum number of facet types considered. 1 for isotopic, 0 for no limit.
+    pub max_facet_types: usize,
+
+    /// The maximum number of facets generated in each hyperplane, to prevent combinatorial explosion. 0 for no limit.
+    pub max_per_hyperplane: usize,
+
+    /// Whether to take the rotation subgroup.
+    pub chiral: bool,
+
+    /// Whether to use unit edges only (superregiment).
+    pub unit_edges: bool,
+
+    /// Whether to include trivial compounds (compounds of other full-symmetric facetings).
+    pub compounds: bool,
+
+    /// Whether to save the facetings in memory.
+    pub save: bool,
+
+    /// Whether to save the facets in memory.
+    pub save_facets: bool,
 }
 
 impl Default for FacetingSettings {
@@ -1221,7 +2125,8 @@ impl Window for FacetingSettings {
 }
 
 impl PlainWindow for FacetingSettings {
-    fn action(&self, _polytope: &mut Concrete) {
+    fn action(&self, _polytope: &mut Concrete) -> MiratopeResult<()> {
+        Ok(())
     }
 
     fn build(&mut self, ui: &mut Ui) {
@@ -1267,4 +2172,353 @@ impl PlainWindow for FacetingSettings {
             );
         });
     }
-}
\ No newline at end of file
+}
+
+/// A window that lets the user set settings for screenshots, turntable
+/// animations and vector images.
+pub struct RenderSettings {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The width of the rendered image in pixels.
+    pub width: u32,
+
+    /// The height of the rendered image in pixels.
+    pub height: u32,
+
+    /// The number of frames in a turntable animation.
+    pub frames: u32,
+
+    /// Whether to draw the faces.
+    pub faces: bool,
+
+    /// Whether to draw the edges.
+    pub edges: bool,
+
+    /// Whether to draw the edges as tubes and the vertices as spheres, both
+    /// on screen and in rendered images.
+    pub tubes: bool,
+
+    /// The radius of the tubes drawn for the edges.
+    pub edge_radius: f32,
+
+    /// The radius of the spheres drawn for the vertices.
+    pub vertex_radius: f32,
+
+    /// The width of the edges in vector images, in pixels.
+    pub line_width: f32,
+
+    /// Whether to leave out the edges hidden behind faces in vector images.
+    pub hidden_lines: bool,
+
+    /// Whether every type of edge gets its own color in vector images.
+    pub color_by_type: bool,
+}
+
+impl Default for RenderSettings {
+    fn default() -> Self {
+        Self {
+            open: false,
+            width: 1920,
+            height: 1080,
+            frames: 60,
+            faces: true,
+            edges: true,
+            tubes: false,
+            edge_radius: 0.01,
+            vertex_radius: 0.02,
+            line_width: 2.0,
+            hidden_lines: true,
+            color_by_type: false,
+        }
+    }
+}
+
+impl RenderSettings {
+    /// Returns the options with which to render the polytope.
+    pub fn options(&self, projection_type: ProjectionType, background: Color) -> RenderOptions {
+        RenderOptions {
+            width: self.width,
+            height: self.height,
+            projection_type,
+            background,
+            faces: self.faces,
+            edges: self.edges,
+            wireframe: self.wireframe_style(),
+        }
+    }
+
+    /// Returns how the edges and vertices are drawn.
+    pub fn wireframe_style(&self) -> WireframeStyle {
+        if self.tubes {
+            WireframeStyle::Tubes {
+                edge_radius: self.edge_radius,
+                vertex_radius: self.vertex_radius,
+            }
+        } else {
+            WireframeStyle::Lines
+        }
+    }
+
+    /// Returns the options with which to export the polytope into a vector
+    /// image.
+    pub fn vector_options(
+        &self,
+        projection_type: ProjectionType,
+        background: Color,
+    ) -> VectorOptions {
+        VectorOptions {
+            render: self.options(projection_type, background),
+            line_width: self.line_width,
+            hidden_lines: self.hidden_lines,
+            color_by_type: self.color_by_type,
+        }
+    }
+}
+
+impl Window for RenderSettings {
+    const NAME: &'static str = "Render settings";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl PlainWindow for RenderSettings {
+    fn action(&self, _polytope: &mut Concrete) -> MiratopeResult<()> {
+        Ok(())
+    }
+
+    fn build(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Resolution");
+            ui.add(
+                egui::DragValue::new(&mut self.width)
+                    .speed(10)
+                    .clamp_range(1..=16384),
+            );
+            ui.label("×");
+            ui.add(
+                egui::DragValue::new(&mut self.height)
+                    .speed(10)
+                    .clamp_range(1..=16384),
+            );
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Turntable frames");
+            ui.add(
+                egui::DragValue::new(&mut self.frames)
+                    .speed(0.1)
+                    .clamp_range(1..=3600),
+            );
+        });
+
+        ui.horizontal(|ui| {
+            ui.add(egui::Checkbox::new(&mut self.faces, "Faces"));
+            ui.add(egui::Checkbox::new(&mut self.edges, "Edges"));
+        });
+
+        ui.add(egui::Checkbox::new(&mut self.tubes, "Tubes and spheres"));
+
+        if self.tubes {
+            ui.horizontal(|ui| {
+                ui.label("Edge radius");
+                ui.add(
+                    egui::DragValue::new(&mut self.edge_radius)
+                        .speed(0.001)
+                        .clamp_range(0.0..=1.0),
+                );
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Vertex radius");
+                ui.add(
+                    egui::DragValue::new(&mut self.vertex_radius)
+                        .speed(0.001)
+                        .clamp_range(0.0..=1.0),
+                );
+            });
+        }
+
+        ui.separator();
+        ui.label("Vector images");
+
+        ui.horizontal(|ui| {
+            ui.label("Line width");
+            ui.add(
+                egui::DragValue::new(&mut self.line_width)
+                    .speed(0.05)
+                    .clamp_range(0.1..=50.0),
+            );
+        });
+
+        ui.add(egui::Checkbox::new(&mut self.hidden_lines, "Remove hidden lines"));
+        ui.add(egui::Checkbox::new(&mut self.color_by_type, "Color edges by type"));
+    }
+}
+
+/// A window that lets the user set the tolerance used when comparing
+/// floating-point values. Cross-sections, duals and other operations on very
+/// large or very small polytopes might need a different one.
+pub struct ToleranceSettings {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The tolerance to set.
+    eps: f64,
+}
+
+impl Default for ToleranceSettings {
+    fn default() -> Self {
+        Self {
+            open: false,
+            eps: Tolerance::current().value(),
+        }
+    }
+}
+
+impl Window for ToleranceSettings {
+    const NAME: &'static str = "Tolerance";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl PlainWindow for ToleranceSettings {
+    fn action(&self, _polytope: &mut Concrete) -> MiratopeResult<()> {
+        Tolerance::new(self.eps).apply();
+        Ok(())
+    }
+
+    fn build(&mut self, ui: &mut Ui) {
+        ui.add(
+            egui::Slider::new(&mut self.eps, 1e-15..=1e-1)
+                .logarithmic(true)
+                .text("Tolerance"),
+        );
+
+        ui.label("Raise it for very large polytopes, lower it for very small ones.");
+    }
+
+    /// Resets the tolerance to its default value, rather than to the one in
+    /// use.
+    fn reset(&mut self) {
+        self.eps = Tolerance::default().value();
+        self.open();
+    }
+}
+
+/// A window that lets the user pick which elements of the polytope get exported
+/// by the "Export selection" button.
+pub struct ExportSettings {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The dimension of the polytope on screen.
+    dim: usize,
+
+    /// The rank of the elements to export. This is always between 1 and the
+    /// rank of the polytope on screen.
+    pub rank: usize,
+
+    /// A comma-separated list with the indices of the elements to export. If
+    /// empty, all elements of the given rank are exported.
+    pub indices: String,
+}
+
+impl Default for ExportSettings {
+    fn default() -> Self {
+        Self {
+            open: false,
+            dim: 0,
+            rank: 1,
+            indices: String::new(),
+        }
+    }
+}
+
+impl ExportSettings {
+    /// Returns the polytope made out of the selected elements, with its
+    /// vertices reindexed.
+    pub fn selection(&self, polytope: &Concrete) -> Result<Concrete, String> {
+        let indices = self.indices.trim();
+
+        let selection = if indices.is_empty() {
+            polytope.skeleton(self.rank)
+        } else {
+            let idxs = indices
+                .split(',')
+                .map(|idx| idx.trim().parse())
+                .collect::<Result<Vec<usize>, _>>()
+                .map_err(|err| format!("invalid index: {}", err))?;
+
+            polytope.elements(self.rank, &idxs)
+        };
+
+        selection.ok_or_else(|| format!("no such elements of rank {}", self.rank))
+    }
+}
+
+impl Window for ExportSettings {
+    const NAME: &'static str = "Export settings";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl UpdateWindow for ExportSettings {
+    fn action(&self, _polytope: &mut Concrete) -> MiratopeResult<()> {
+        Ok(())
+    }
+
+    fn build(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::DragValue::new(&mut self.rank)
+                    .speed(0.02)
+                    .clamp_range(1..=self.dim + 1),
+            );
+
+            ui.label("Rank");
+        });
+
+        ui.horizontal(|ui| {
+            ui.text_edit_singleline(&mut self.indices);
+            ui.label("Indices");
+        });
+
+        ui.label("Leave the indices empty to export every element of the rank.");
+    }
+
+    fn dim(&self) -> usize {
+        self.dim
+    }
+
+    fn default_with(dim: usize) -> Self {
+        Self {
+            dim,
+            rank: dim.max(1),
+            ..Default::default()
+        }
+    }
+
+    fn update(&mut self, dim: usize) {
+        self.dim = dim;
+        self.rank = self.rank.clamp(1, dim + 1);
+    }
+}
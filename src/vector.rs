@@ -0,0 +1,261 @@
+//! Exports the wireframe of the polytope, as seen on screen, into a vector
+//! image for publications: an SVG file, or a PostScript file if the path ends
+//! in `.eps` or `.ps`.
+//!
+//! Hidden lines are removed by drawing the faces into a depth buffer, just as
+//! for [screenshots](crate::screenshot), and only keeping the parts of each
+//! edge that lie in front of it.
+
+use std::{fmt::Write, fs, io, path::Path};
+
+use crate::{
+    mesh::Renderable,
+    screenshot::{Canvas, RenderOptions, EDGE_BIAS},
+    ui::palette_color,
+    Concrete,
+};
+
+use bevy::prelude::*;
+use miratope_core::abs::Ranked;
+
+/// The settings with which a polytope is exported into a vector image.
+#[derive(Clone, Copy)]
+pub struct VectorOptions {
+    /// The size, projection, and background of the image. Whether faces and
+    /// edges are drawn is ignored.
+    pub render: RenderOptions,
+
+    /// The width of the edges, in pixels.
+    pub line_width: f32,
+
+    /// Whether the edges hidden behind faces are left out.
+    pub hidden_lines: bool,
+
+    /// Whether every type of edge gets its own color.
+    pub color_by_type: bool,
+}
+
+/// A visible piece of an edge, in screen coordinates.
+struct Segment {
+    /// The starting point.
+    from: Vec2,
+
+    /// The ending point.
+    to: Vec2,
+
+    /// The index of the color of the segment.
+    color: usize,
+}
+
+/// Returns the index of the color of each edge, and the colors themselves.
+fn edge_colors(poly: &Concrete, color_by_type: bool) -> (Vec<usize>, Vec<Color>) {
+    let edge_count = poly.el_count(2);
+
+    // Edges are only given types when they're proper elements.
+    if !color_by_type || poly.rank() < 3 {
+        return (vec![0; edge_count], vec![Color::BLACK]);
+    }
+
+    let types = &poly.elements_by_type()[2];
+    let mut colors = vec![0; edge_count];
+    for (ty, edges) in types.iter().enumerate() {
+        for &edge in edges {
+            colors[edge] = ty;
+        }
+    }
+
+    (colors, (0..types.len()).map(palette_color).collect())
+}
+
+/// Projects the edges of a polytope onto the screen, and splits them into
+/// their visible pieces.
+fn segments(poly: &Concrete, camera: Mat4, options: &VectorOptions) -> (Vec<Segment>, Vec<Color>) {
+    let mut canvas = Canvas::new(camera, &options.render);
+    if options.hidden_lines {
+        canvas.faces(poly, options.render.projection_type);
+    }
+
+    let mut segments = Vec::new();
+    if poly.rank() < 2 {
+        return (segments, vec![Color::BLACK]);
+    }
+
+    let vertices = poly.projected_vertices(options.render.projection_type);
+    let (edge_colors, colors) = edge_colors(poly, options.color_by_type);
+
+    for (edge, &color) in poly.abs[2].iter().zip(&edge_colors) {
+        let [a, b] = [edge.subs[0], edge.subs[1]].map(|v| canvas.to_view(vertices[v]));
+
+        // We don't bother clipping edges partially behind the camera.
+        let (p, q) = match (canvas.to_screen(a), canvas.to_screen(b)) {
+            (Some(p), Some(q)) => (p, q),
+            _ => continue,
+        };
+
+        if !options.hidden_lines {
+            segments.push(Segment {
+                from: p.truncate(),
+                to: q.truncate(),
+                color,
+            });
+            continue;
+        }
+
+        // We sample the edge twice per pixel, and keep the visible runs.
+        let steps = 2 * (q.x - p.x).abs().max((q.y - p.y).abs()).ceil().max(1.0) as usize;
+        let mut start = None;
+        let mut end = 0.0;
+
+        for i in 0..=steps {
+            let t = i as f32 / steps as f32;
+            let s = p.lerp(q, t);
+
+            if canvas.is_visible(s.x as i64, s.y as i64, s.z * (1.0 + EDGE_BIAS)) {
+                start.get_or_insert(t);
+                end = t;
+            } else if let Some(t) = start.take() {
+                segments.push(Segment {
+                    from: p.lerp(q, t).truncate(),
+                    to: p.lerp(q, end).truncate(),
+                    color,
+                });
+            }
+        }
+
+        if let Some(t) = start {
+            segments.push(Segment {
+                from: p.lerp(q, t).truncate(),
+                to: p.lerp(q, end).truncate(),
+                color,
+            });
+        }
+    }
+
+    (segments, colors)
+}
+
+/// Returns the hexadecimal code of a color.
+fn hex(color: Color) -> String {
+    let [r, g, b, _] = color.as_rgba_f32();
+    let c = |x: f32| (x.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!("#{:02x}{:02x}{:02x}", c(r), c(g), c(b))
+}
+
+/// Writes the segments as an SVG image.
+fn svg(segments: &[Segment], colors: &[Color], options: &VectorOptions) -> String {
+    let RenderOptions { width, height, .. } = options.render;
+    let mut src = String::new();
+
+    // Writing to a string can't fail.
+    writeln!(src, r#"<?xml version="1.0" encoding="UTF-8"?>"#).unwrap();
+    writeln!(
+        src,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#,
+        width, height
+    )
+    .unwrap();
+    writeln!(
+        src,
+        r#"<rect width="100%" height="100%" fill="{}"/>"#,
+        hex(options.render.background)
+    )
+    .unwrap();
+    writeln!(
+        src,
+        r#"<g fill="none" stroke-width="{}" stroke-linecap="round">"#,
+        options.line_width
+    )
+    .unwrap();
+
+    // Every color gets a single path.
+    for (idx, &color) in colors.iter().enumerate() {
+        let mut path = String::new();
+        for seg in segments.iter().filter(|seg| seg.color == idx) {
+            write!(
+                path,
+                "M{:.2} {:.2}L{:.2} {:.2}",
+                seg.from.x, seg.from.y, seg.to.x, seg.to.y
+            )
+            .unwrap();
+        }
+
+        if !path.is_empty() {
+            writeln!(src, r#"<path stroke="{}" d="{}"/>"#, hex(color), path).unwrap();
+        }
+    }
+
+    writeln!(src, "</g>").unwrap();
+    writeln!(src, "</svg>").unwrap();
+    src
+}
+
+/// Writes the segments as an encapsulated PostScript file.
+fn postscript(segments: &[Segment], colors: &[Color], options: &VectorOptions) -> String {
+    let RenderOptions { width, height, .. } = options.render;
+    let h = height as f32;
+    let mut src = String::new();
+
+    let set_color = |src: &mut String, color: Color| {
+        let [r, g, b, _] = color.as_rgba_f32();
+        writeln!(src, "{:.3} {:.3} {:.3} setrgbcolor", r, g, b).unwrap();
+    };
+
+    writeln!(src, "%!PS-Adobe-3.0 EPSF-3.0").unwrap();
+    writeln!(src, "%%BoundingBox: 0 0 {} {}", width, height).unwrap();
+    writeln!(src, "%%Creator: Miratope").unwrap();
+    writeln!(src, "%%EndComments").unwrap();
+
+    set_color(&mut src, options.render.background);
+    writeln!(src, "0 0 {} {} rectfill", width, height).unwrap();
+    writeln!(src, "{} setlinewidth 1 setlinecap", options.line_width).unwrap();
+
+    // PostScript coordinates start from the bottom left corner.
+    for (idx, &color) in colors.iter().enumerate() {
+        let mut segments = segments.iter().filter(|seg| seg.color == idx).peekable();
+        if segments.peek().is_none() {
+            continue;
+        }
+
+        set_color(&mut src, color);
+        writeln!(src, "newpath").unwrap();
+        for seg in segments {
+            writeln!(
+                src,
+                "{:.2} {:.2} moveto {:.2} {:.2} lineto",
+                seg.from.x,
+                h - seg.from.y,
+                seg.to.x,
+                h - seg.to.y
+            )
+            .unwrap();
+        }
+        writeln!(src, "stroke").unwrap();
+    }
+
+    writeln!(src, "showpage").unwrap();
+    writeln!(src, "%%EOF").unwrap();
+    src
+}
+
+/// Exports the wireframe of a polytope as seen from a camera with a given
+/// transform into a vector image. The format is chosen from the extension of
+/// the path.
+pub fn save_vector<P: AsRef<Path>>(
+    poly: &Concrete,
+    camera: Mat4,
+    options: &VectorOptions,
+    path: P,
+) -> io::Result<()> {
+    let path = path.as_ref();
+    let (segments, colors) = segments(poly, camera, options);
+
+    let ext = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase());
+    let src = match ext.as_deref() {
+        Some("eps") | Some("ps") => postscript(&segments, &colors, options),
+        _ => svg(&segments, &colors, options),
+    };
+
+    fs::write(path, src)
+}
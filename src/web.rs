@@ -0,0 +1,68 @@
+//! The browser viewer, which lets pages like the
+//! [Polytope Wiki](crate::WIKI_LINK) embed an interactive polytope.
+//!
+//! There's no file system on the web, so the page sends the contents of the
+//! files to show through [`load_polytope`]. Miratope is drawn on the canvas
+//! with the id [`CANVAS_ID`].
+
+use std::cell::RefCell;
+
+use crate::{ui::toast::Toasts, Concrete};
+
+use bevy::prelude::*;
+use miratope_core::{conc::ConcretePolytope, error::MiratopeError, file::FromFile};
+use wasm_bindgen::prelude::*;
+
+/// The selector of the canvas Miratope is drawn on.
+pub const CANVAS_ID: &str = "#miratope";
+
+thread_local! {
+    /// The contents and the extension of the last file sent by the page, if
+    /// it hasn't been loaded yet. The web is single-threaded, so we don't need
+    /// anything fancier.
+    static PENDING: RefCell<Option<(Vec<u8>, String)>> = RefCell::new(None);
+}
+
+/// Loads a polytope from the contents of a file with a given extension, like
+/// `"off"` or `"ggb"`. This is meant to be called from JavaScript, as in
+/// `load_polytope(new TextEncoder().encode(src), "off")`.
+#[wasm_bindgen]
+pub fn load_polytope(bytes: &[u8], ext: &str) {
+    PENDING.with(|pending| *pending.borrow_mut() = Some((bytes.to_vec(), ext.to_string())));
+}
+
+/// The window Miratope is drawn on. This must be added before Bevy's default
+/// plugins.
+pub fn window_descriptor() -> WindowDescriptor {
+    WindowDescriptor {
+        canvas: Some(CANVAS_ID.to_string()),
+        ..Default::default()
+    }
+}
+
+/// The plugin that loads the polytopes sent by the page.
+pub struct WebPlugin;
+
+impl Plugin for WebPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(load_pending.system());
+    }
+}
+
+/// The system that loads the last polytope sent by the page.
+fn load_pending(mut query: Query<'_, '_, &mut Concrete>, mut toasts: ResMut<'_, Toasts>) {
+    let (bytes, ext) = match PENDING.with(|pending| pending.borrow_mut().take()) {
+        Some(file) => file,
+        None => return,
+    };
+
+    match Concrete::from_bytes(&bytes, &ext) {
+        Ok(mut q) => {
+            q.recenter();
+            if let Some(mut p) = query.iter_mut().next() {
+                *p = q;
+            }
+        }
+        Err(err) => toasts.error(MiratopeError::operation("Loading", err)),
+    }
+}